@@ -1047,11 +1047,11 @@ fn build_layout_mappings() -> BTreeMap<String, MappingEntry> {
                 FailureMode {
                     scenario: "flex-wrap causing multi-line flow".into(),
                     detection: "Flex layout with wrap enabled".into(),
-                    impact: "Terminal has no native wrapping flex; may need manual chunking".into(),
+                    impact: "Wrapped lines are sized by explicit cross sizes; content-driven line heights need FlexItem::cross".into(),
                 },
             ],
             remediation: RemediationStrategy {
-                approach: "Map flex-direction to Layout direction; flex-grow to Percentage/Min constraints".into(),
+                approach: "Map flex-direction to Layout direction; flex-grow/shrink/basis and align-self to FlexItem; flex-wrap to OverflowBehavior::Wrap with AlignContent".into(),
                 automatable: true,
                 effort: EffortLevel::Low,
             },
@@ -20,6 +20,8 @@
 //!
 //! `DegradationLevel` lives in the `ftui_render::budget` module.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 // ---------------------------------------------------------------------------
@@ -517,6 +519,46 @@ pub use spring::Spring;
 pub use stagger::{StaggerMode, stagger_offsets, stagger_offsets_with_jitter};
 pub use timeline::Timeline;
 
+// ---------------------------------------------------------------------------
+// Motion preference
+// ---------------------------------------------------------------------------
+
+/// Shared reduced-motion setting.
+///
+/// Clones share one flag, so an accessibility toggle flipped in one place
+/// reaches every animation holding a clone. Animations follow
+/// [`MotionPreference::global`] unless handed their own instance.
+#[derive(Debug, Clone, Default)]
+pub struct MotionPreference {
+    reduced: Arc<AtomicBool>,
+}
+
+impl MotionPreference {
+    /// Create an independent preference with full motion.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide preference.
+    #[must_use]
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<MotionPreference> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Whether animations should jump straight to their end state.
+    #[must_use]
+    pub fn is_reduced(&self) -> bool {
+        self.reduced.load(Ordering::Relaxed)
+    }
+
+    /// Turn reduced motion on or off for every clone of this preference.
+    pub fn set_reduced(&self, reduced: bool) {
+        self.reduced.store(reduced, Ordering::Relaxed);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Convenience constructors
 // ---------------------------------------------------------------------------
@@ -933,6 +975,18 @@ mod tests {
         assert!(par.is_complete());
     }
 
+    // ---- Motion preference tests ----
+
+    #[test]
+    fn motion_preference_clones_share_flag() {
+        let pref = MotionPreference::new();
+        let shared = pref.clone();
+        assert!(!shared.is_reduced());
+        pref.set_reduced(true);
+        assert!(shared.is_reduced());
+        assert!(!MotionPreference::new().is_reduced());
+    }
+
     // ---- Edge case tests ----
 
     #[test]
@@ -16,6 +16,12 @@
 //! 4. Non-blocking: [`KeySequenceAction::Pending`] signals that more input is
 //!    needed, but the caller can continue with other work.
 //!
+//! ## Custom Sequences
+//! [`feed_with`](KeySequenceInterpreter::feed_with) replaces the built-in
+//! patterns with a caller-supplied matcher, so grammars such as Vim's
+//! `"a2dw` reuse the same buffering. Completed runs are reported as
+//! [`KeySequenceKind::Custom`].
+//!
 //! ## Failure Modes
 //! - If the timeout expires mid-sequence, buffered keys are flushed as individual
 //!   [`Emit`](KeySequenceAction::Emit) actions (graceful degradation).
@@ -94,10 +100,17 @@ impl KeySequenceConfig {
 // ---------------------------------------------------------------------------
 
 /// Recognized key sequence patterns.
+///
+/// New patterns may be added, so matches outside this crate need a wildcard
+/// arm.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum KeySequenceKind {
     /// Double Escape (Esc Esc) - typically used for tree view toggle.
     DoubleEscape,
+    /// A sequence accepted by the matcher passed to
+    /// [`feed_with`](KeySequenceInterpreter::feed_with).
+    Custom,
 }
 
 impl KeySequenceKind {
@@ -106,10 +119,25 @@ impl KeySequenceKind {
     pub const fn name(&self) -> &'static str {
         match self {
             Self::DoubleEscape => "Esc Esc",
+            Self::Custom => "custom",
         }
     }
 }
 
+/// How buffered keys relate to a caller-defined sequence grammar.
+///
+/// Returned by the matcher passed to
+/// [`feed_with`](KeySequenceInterpreter::feed_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SequenceMatch {
+    /// The keys form a complete sequence.
+    Complete,
+    /// The keys start a sequence; keep buffering.
+    Prefix,
+    /// The keys are not part of any sequence.
+    NoMatch,
+}
+
 // ---------------------------------------------------------------------------
 // KeySequenceAction
 // ---------------------------------------------------------------------------
@@ -157,6 +185,7 @@ impl KeySequenceAction {
 ///
 /// Feed key events via [`feed`](Self::feed) and periodically call
 /// [`check_timeout`](Self::check_timeout) to handle expired sequences.
+#[derive(Clone)]
 pub struct KeySequenceInterpreter {
     config: KeySequenceConfig,
 
@@ -176,6 +205,12 @@ impl std::fmt::Debug for KeySequenceInterpreter {
     }
 }
 
+impl Default for KeySequenceInterpreter {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
 impl KeySequenceInterpreter {
     /// Create a new key sequence interpreter with the given configuration.
     #[must_use]
@@ -248,6 +283,58 @@ impl KeySequenceInterpreter {
         }
     }
 
+    /// Feed a key event, matching against `matcher` instead of the built-in
+    /// patterns.
+    ///
+    /// `matcher` is called once with every buffered key followed by `event`.
+    /// [`Prefix`](SequenceMatch::Prefix) buffers the key and returns
+    /// [`Pending`](KeySequenceAction::Pending);
+    /// [`Complete`](SequenceMatch::Complete) returns the whole run as an
+    /// [`EmitSequence`](KeySequenceAction::EmitSequence) of kind
+    /// [`KeySequenceKind::Custom`]; [`NoMatch`](SequenceMatch::NoMatch)
+    /// discards the buffer and emits `event` alone, as [`feed`](Self::feed)
+    /// does.
+    ///
+    /// Release and repeat events are passed through without calling
+    /// `matcher`. Timeouts work as for [`feed`](Self::feed); a caller that
+    /// never calls [`check_timeout`](Self::check_timeout) buffers without
+    /// a time limit.
+    pub fn feed_with(
+        &mut self,
+        event: &KeyEvent,
+        now: Instant,
+        matcher: impl FnOnce(&[KeyEvent]) -> SequenceMatch,
+    ) -> KeySequenceAction {
+        if event.kind != KeyEventKind::Press {
+            return KeySequenceAction::Emit(*event);
+        }
+        self.buffer.push(*event);
+        match matcher(&self.buffer) {
+            SequenceMatch::Complete => {
+                self.buffer_start = None;
+                KeySequenceAction::EmitSequence {
+                    kind: KeySequenceKind::Custom,
+                    keys: std::mem::take(&mut self.buffer),
+                }
+            }
+            SequenceMatch::Prefix => {
+                self.buffer_start.get_or_insert(now);
+                KeySequenceAction::Pending
+            }
+            SequenceMatch::NoMatch => {
+                self.buffer.clear();
+                self.buffer_start = None;
+                KeySequenceAction::Emit(*event)
+            }
+        }
+    }
+
+    /// Keys buffered towards a sequence that is not complete yet.
+    #[must_use]
+    pub fn pending_keys(&self) -> &[KeyEvent] {
+        &self.buffer
+    }
+
     /// Check if the sequence timeout has expired.
     ///
     /// Call this periodically (e.g., on tick) to flush expired sequences.
@@ -495,6 +582,62 @@ mod tests {
         assert!(!interp.has_pending());
     }
 
+    // --- Custom matcher tests ---
+
+    /// Matches `g g` and `d` followed by any key.
+    fn vim_like(keys: &[KeyEvent]) -> SequenceMatch {
+        match keys {
+            [k] if k.code == KeyCode::Char('g') || k.code == KeyCode::Char('d') => {
+                SequenceMatch::Prefix
+            }
+            [g, k] if g.code == KeyCode::Char('g') && k.code == KeyCode::Char('g') => {
+                SequenceMatch::Complete
+            }
+            [d, _] if d.code == KeyCode::Char('d') => SequenceMatch::Complete,
+            _ => SequenceMatch::NoMatch,
+        }
+    }
+
+    #[test]
+    fn custom_matcher_buffers_until_complete() {
+        let mut interp = KeySequenceInterpreter::with_defaults();
+        let t = now();
+
+        assert!(interp.feed_with(&key('d'), t, vim_like).is_pending());
+        assert_eq!(interp.pending_keys(), &[key('d')]);
+        let action = interp.feed_with(&key('w'), t + MS_300, vim_like);
+        assert_eq!(
+            action,
+            KeySequenceAction::EmitSequence {
+                kind: KeySequenceKind::Custom,
+                keys: vec![key('d'), key('w')],
+            }
+        );
+        assert!(!interp.has_pending());
+        assert!(interp.pending_keys().is_empty());
+    }
+
+    #[test]
+    fn custom_matcher_no_match_emits_key() {
+        let mut interp = KeySequenceInterpreter::with_defaults();
+        let t = now();
+
+        assert_eq!(
+            interp.feed_with(&key('x'), t, vim_like),
+            KeySequenceAction::Emit(key('x'))
+        );
+        assert!(interp.feed_with(&key('g'), t, vim_like).is_pending());
+        assert_eq!(
+            interp.feed_with(&key('x'), t, vim_like),
+            KeySequenceAction::Emit(key('x'))
+        );
+        assert!(!interp.has_pending());
+        assert!(matches!(
+            interp.feed_with(&key_release('g'), t, vim_like),
+            KeySequenceAction::Emit(_)
+        ));
+    }
+
     // --- Configuration tests ---
 
     #[test]
@@ -633,6 +776,7 @@ mod tests {
     #[test]
     fn sequence_kind_name() {
         assert_eq!(KeySequenceKind::DoubleEscape.name(), "Esc Esc");
+        assert_eq!(KeySequenceKind::Custom.name(), "custom");
     }
 
     // --- Default config tests ---
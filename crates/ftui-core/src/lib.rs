@@ -45,6 +45,7 @@ pub mod semantic_event;
 pub mod terminal_capabilities;
 #[cfg(all(not(target_arch = "wasm32"), feature = "crossterm"))]
 pub mod terminal_session;
+pub mod terminfo;
 #[cfg(all(not(target_arch = "wasm32"), feature = "crossterm"))]
 pub use terminal_session::with_panic_cleanup_suppressed;
 #[cfg(not(all(not(target_arch = "wasm32"), feature = "crossterm")))]
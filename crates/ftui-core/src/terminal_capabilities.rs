@@ -184,6 +184,9 @@ const KITTY_KEYBOARD_TERMINALS: &[&str] = &[
 /// mux/terminal instability around DEC ?2026 h/l in real-world setups.
 const SYNC_OUTPUT_TERMINALS: &[&str] = &["Alacritty", "Ghostty", "kitty", "Contour"];
 
+/// Terminal programs that render DEC double-width/double-height lines.
+const LINE_ATTRIBUTE_TERMINALS: &[&str] = &["Apple_Terminal", "Contour"];
+
 /// Known terminal profile identifiers.
 ///
 /// These names correspond to predefined capability configurations.
@@ -320,6 +323,11 @@ pub struct TerminalCapabilities {
     pub unicode_emoji: bool,
     /// Double-width glyph support (CJK/emoji).
     pub double_width: bool,
+    /// DEC line size attributes (DECDWL/DECDHL) for double-width and
+    /// double-height rows.
+    pub line_attributes: bool,
+    /// Kitty text sizing protocol (OSC 66) for multi-cell scaled text.
+    pub text_sizing: bool,
 
     // Advanced features
     /// Synchronized output (DEC mode 2026) to reduce flicker.
@@ -328,6 +336,12 @@ pub struct TerminalCapabilities {
     pub osc8_hyperlinks: bool,
     /// Scroll region support (DECSTBM).
     pub scroll_region: bool,
+    /// Styled and colored underlines (SGR 4:x and 58).
+    pub styled_underline: bool,
+    /// Cursor shape selection (DECSCUSR).
+    pub cursor_style: bool,
+    /// Alternate screen buffer (DEC mode 1049).
+    pub alt_screen: bool,
 
     // Multiplexer detection
     /// Running inside tmux.
@@ -423,9 +437,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: true,
             double_width: true,
+            line_attributes: false,
+            text_sizing: false,
             sync_output: true,
             osc8_hyperlinks: true,
             scroll_region: true,
+            styled_underline: true,
+            cursor_style: true,
+            alt_screen: true,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -451,9 +470,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: true,
             double_width: true,
+            line_attributes: true,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: true,
+            styled_underline: false,
+            cursor_style: true,
+            alt_screen: true,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -478,9 +502,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: false,
             double_width: true,
+            line_attributes: true,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: true,
+            styled_underline: false,
+            cursor_style: true,
+            alt_screen: true,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -505,9 +534,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: false,
             unicode_emoji: false,
             double_width: false,
+            line_attributes: true,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: true,
+            styled_underline: false,
+            cursor_style: false,
+            alt_screen: false,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -532,9 +566,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: false,
             unicode_emoji: false,
             double_width: false,
+            line_attributes: false,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: false,
+            styled_underline: false,
+            cursor_style: false,
+            alt_screen: false,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -560,9 +599,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: true,
             double_width: true,
+            line_attributes: false,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: true,
+            styled_underline: false,
+            cursor_style: false,
+            alt_screen: true,
             in_tmux: false,
             in_screen: true,
             in_zellij: false,
@@ -588,9 +632,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: true,
             double_width: true,
+            line_attributes: false,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: true,
+            styled_underline: false,
+            cursor_style: true,
+            alt_screen: true,
             in_tmux: true,
             in_screen: false,
             in_zellij: false,
@@ -615,9 +664,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: true,
             double_width: true,
+            line_attributes: false,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: true,
+            styled_underline: false,
+            cursor_style: true,
+            alt_screen: true,
             in_tmux: false,
             in_screen: false,
             in_zellij: true,
@@ -642,9 +696,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: true,
             double_width: true,
+            line_attributes: true,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: true,
             scroll_region: true,
+            styled_underline: false,
+            cursor_style: true,
+            alt_screen: true,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -669,9 +728,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: true,
             double_width: true,
+            line_attributes: false,
+            text_sizing: true,
             sync_output: true,
             osc8_hyperlinks: true,
             scroll_region: true,
+            styled_underline: true,
+            cursor_style: true,
+            alt_screen: true,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -696,9 +760,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: true,
             unicode_emoji: false,
             double_width: false,
+            line_attributes: false,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: true,
+            styled_underline: false,
+            cursor_style: false,
+            alt_screen: false,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -755,6 +824,13 @@ impl Default for CapabilityProfileBuilder {
     }
 }
 
+/// Start from an existing capability set, keeping its profile.
+impl From<TerminalCapabilities> for CapabilityProfileBuilder {
+    fn from(caps: TerminalCapabilities) -> Self {
+        Self { caps }
+    }
+}
+
 impl CapabilityProfileBuilder {
     /// Create a new builder with all capabilities disabled.
     pub fn new() -> Self {
@@ -766,9 +842,14 @@ impl CapabilityProfileBuilder {
                 unicode_box_drawing: false,
                 unicode_emoji: false,
                 double_width: false,
+                line_attributes: false,
+                text_sizing: false,
                 sync_output: false,
                 osc8_hyperlinks: false,
                 scroll_region: false,
+                styled_underline: false,
+                cursor_style: false,
+                alt_screen: false,
                 in_tmux: false,
                 in_screen: false,
                 in_zellij: false,
@@ -817,6 +898,18 @@ impl CapabilityProfileBuilder {
         self
     }
 
+    /// Set DEC line size attributes (DECDWL/DECDHL) support.
+    pub const fn line_attributes(mut self, enabled: bool) -> Self {
+        self.caps.line_attributes = enabled;
+        self
+    }
+
+    /// Set Kitty text sizing protocol (OSC 66) support.
+    pub const fn text_sizing(mut self, enabled: bool) -> Self {
+        self.caps.text_sizing = enabled;
+        self
+    }
+
     /// Set OSC 8 hyperlinks support.
     pub const fn osc8_hyperlinks(mut self, enabled: bool) -> Self {
         self.caps.osc8_hyperlinks = enabled;
@@ -829,6 +922,24 @@ impl CapabilityProfileBuilder {
         self
     }
 
+    /// Set styled underline (SGR 4:x and 58) support.
+    pub const fn styled_underline(mut self, enabled: bool) -> Self {
+        self.caps.styled_underline = enabled;
+        self
+    }
+
+    /// Set cursor shape (DECSCUSR) support.
+    pub const fn cursor_style(mut self, enabled: bool) -> Self {
+        self.caps.cursor_style = enabled;
+        self
+    }
+
+    /// Set alternate screen support.
+    pub const fn alt_screen(mut self, enabled: bool) -> Self {
+        self.caps.alt_screen = enabled;
+        self
+    }
+
     // ── Multiplexer Flags ──────────────────────────────────────────────
 
     /// Set whether running inside tmux.
@@ -888,6 +999,34 @@ impl CapabilityProfileBuilder {
         self.caps.osc52_clipboard = enabled;
         self
     }
+
+    // ── Terminfo ───────────────────────────────────────────────────────
+
+    /// Enable capabilities advertised by a terminfo entry.
+    ///
+    /// Maps `colors`, `Tc`/`RGB`, `Sync`, `Smulx`, `Ss` and `smcup`/`rmcup`
+    /// onto the color, synchronized output, styled underline, cursor style
+    /// and alternate screen flags. The entry only raises capabilities, never
+    /// lowers them: common entries such as `xterm-256color` omit `Tc`/`RGB`,
+    /// so true color detected from `COLORTERM` is kept. Mux policy still
+    /// applies through the `use_*` methods.
+    pub fn terminfo(mut self, info: &crate::terminfo::Terminfo) -> Self {
+        self.caps.true_color |= info.true_color();
+        self.caps.colors_256 |= self.caps.true_color || info.max_colors() >= 256;
+        if info.sync_output() {
+            self.caps.sync_output = true;
+        }
+        if info.styled_underline() {
+            self.caps.styled_underline = true;
+        }
+        if info.cursor_style() {
+            self.caps.cursor_style = true;
+        }
+        if info.alt_screen().is_some() {
+            self.caps.alt_screen = true;
+        }
+        self
+    }
 }
 
 impl TerminalCapabilities {
@@ -987,6 +1126,13 @@ impl TerminalCapabilities {
         // Scroll region support (broadly available except dumb)
         let scroll_region = !is_dumb;
 
+        // Styled underlines (kitty-style SGR 4:x, passed through by no mux)
+        let styled_underline = !is_dumb && !in_any_mux && (is_modern_terminal || is_kitty);
+
+        // Cursor shape and alternate screen (broadly available except dumb)
+        let cursor_style = !is_dumb;
+        let alt_screen = !is_dumb;
+
         // Kitty keyboard protocol (kitty + other compatible terminals)
         let kitty_keyboard = is_kitty
             || KITTY_KEYBOARD_TERMINALS.iter().any(|t| {
@@ -1011,6 +1157,19 @@ impl TerminalCapabilities {
         let unicode_emoji = !is_dumb && (is_modern_terminal || is_kitty);
         let double_width = !is_dumb;
 
+        // DEC line size attributes (multiplexers do not pass them through)
+        let line_attributes = !is_dumb
+            && !in_any_mux
+            && (is_windows_terminal
+                || LINE_ATTRIBUTE_TERMINALS.iter().any(|t| {
+                    let t_lower = t.to_ascii_lowercase();
+                    term_program_lower.contains(&t_lower)
+                }));
+
+        // Kitty text sizing (OSC 66); multiplexers cannot represent
+        // multi-cell glyphs in their own grid.
+        let text_sizing = !is_dumb && !in_any_mux && is_kitty;
+
         Self {
             profile: TerminalProfile::Detected,
             true_color,
@@ -1018,9 +1177,14 @@ impl TerminalCapabilities {
             unicode_box_drawing,
             unicode_emoji,
             double_width,
+            line_attributes,
+            text_sizing,
             sync_output,
             osc8_hyperlinks,
             scroll_region,
+            styled_underline,
+            cursor_style,
+            alt_screen,
             in_tmux,
             in_screen,
             in_zellij,
@@ -1046,9 +1210,14 @@ impl TerminalCapabilities {
             unicode_box_drawing: false,
             unicode_emoji: false,
             double_width: false,
+            line_attributes: false,
+            text_sizing: false,
             sync_output: false,
             osc8_hyperlinks: false,
             scroll_region: false,
+            styled_underline: false,
+            cursor_style: false,
+            alt_screen: false,
             in_tmux: false,
             in_screen: false,
             in_zellij: false,
@@ -1134,6 +1303,32 @@ impl TerminalCapabilities {
         self.osc8_hyperlinks
     }
 
+    /// Whether DEC line size attributes (DECDWL/DECDHL) should be emitted.
+    ///
+    /// Disabled in multiplexers, which track their own line state and drop
+    /// or mangle the `ESC #` sequences.
+    #[must_use]
+    #[inline]
+    pub const fn use_line_attributes(&self) -> bool {
+        if self.in_any_mux() {
+            return false;
+        }
+        self.line_attributes
+    }
+
+    /// Whether scaled text should be emitted with OSC 66.
+    ///
+    /// Disabled in multiplexers, which would store only the first cell of
+    /// each multi-cell glyph.
+    #[must_use]
+    #[inline]
+    pub const fn use_text_sizing(&self) -> bool {
+        if self.in_any_mux() {
+            return false;
+        }
+        self.text_sizing
+    }
+
     /// Whether OSC 52 clipboard access should be used.
     ///
     /// Gated by mux detection in `detect()`, and re-checked here to keep
@@ -1832,6 +2027,58 @@ mod tests {
         assert!(!caps.osc52_clipboard, "clipboard disabled in tmux");
     }
 
+    #[test]
+    fn detect_line_attributes() {
+        let caps = TerminalCapabilities::detect_from_inputs(&make_env(
+            "xterm-256color",
+            "Apple_Terminal",
+            "",
+        ));
+        assert!(caps.line_attributes);
+        assert!(caps.use_line_attributes());
+
+        let mut env = make_env("xterm-256color", "Apple_Terminal", "");
+        env.in_tmux = true;
+        let caps = TerminalCapabilities::detect_from_inputs(&env);
+        assert!(!caps.line_attributes, "tmux drops DECDWL/DECDHL");
+
+        let caps = TerminalCapabilities::detect_from_inputs(&make_env("xterm-kitty", "", ""));
+        assert!(!caps.line_attributes);
+    }
+
+    #[test]
+    fn line_attributes_policy_respects_mux() {
+        let caps = CapabilityProfileBuilder::new()
+            .line_attributes(true)
+            .in_screen(true)
+            .build();
+        assert!(caps.line_attributes);
+        assert!(!caps.use_line_attributes());
+        assert!(TerminalCapabilities::xterm().use_line_attributes());
+        assert!(!TerminalCapabilities::modern().use_line_attributes());
+    }
+
+    #[test]
+    fn detect_text_sizing() {
+        let caps = TerminalCapabilities::detect_from_inputs(&make_env("xterm-kitty", "", ""));
+        assert!(caps.text_sizing);
+        assert!(caps.use_text_sizing());
+
+        let mut env = make_env("xterm-kitty", "", "");
+        env.in_tmux = true;
+        let caps = TerminalCapabilities::detect_from_inputs(&env);
+        assert!(!caps.text_sizing);
+
+        let caps = TerminalCapabilities::detect_from_inputs(&make_env(
+            "xterm-256color",
+            "WezTerm",
+            "truecolor",
+        ));
+        assert!(!caps.text_sizing);
+        assert!(TerminalCapabilities::kitty().use_text_sizing());
+        assert!(!TerminalCapabilities::modern().use_text_sizing());
+    }
+
     #[test]
     fn detect_in_screen() {
         let mut env = make_env("screen", "", "");
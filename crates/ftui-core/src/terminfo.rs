@@ -0,0 +1,1121 @@
+#![forbid(unsafe_code)]
+
+//! Terminfo database loading and parameterized string expansion.
+//!
+//! Environment-variable detection in [`terminal_capabilities`] covers the
+//! terminals we know about. For everything else, the compiled terminfo entry
+//! for `$TERM` is the authoritative description of what the terminal can do.
+//! This module reads that entry and expands its parameterized strings so the
+//! presenter can drive terminals whose sequences differ from xterm.
+//!
+//! # Supported formats
+//!
+//! | Magic | Format | Numbers |
+//! |-------|--------|---------|
+//! | `0o432` | Legacy (SVr4) | 16-bit |
+//! | `0o1036` | Extended (ncurses 6.1+) | 32-bit |
+//!
+//! Both formats may carry the ncurses extended-capability section, which is
+//! where user-defined capabilities such as `Tc`, `RGB`, `Smulx`, `Ss` and
+//! `Sync` live.
+//!
+//! # Search order
+//!
+//! [`Terminfo::load`] looks for `<dir>/<first char>/<name>` (and the
+//! hex-directory layout used on macOS, `<dir>/<hex of first byte>/<name>`) in:
+//!
+//! 1. `$TERMINFO`
+//! 2. `$HOME/.terminfo`
+//! 3. each entry of `$TERMINFO_DIRS` (an empty entry means the system default)
+//! 4. `/etc/terminfo`, `/lib/terminfo`, `/usr/share/terminfo`,
+//!    `/usr/lib/terminfo`, `/usr/local/share/terminfo`
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ftui_core::terminal_capabilities::CapabilityProfileBuilder;
+//! use ftui_core::terminfo::Terminfo;
+//!
+//! let info = Terminfo::from_env().expect("terminfo entry for $TERM");
+//! let caps = CapabilityProfileBuilder::new().terminfo(&info).build();
+//! let cup = info.expand("cup", &[4, 10]).expect("cursor addressing");
+//! assert_eq!(cup, b"\x1b[5;11H");
+//! # let _ = caps;
+//! ```
+//!
+//! [`terminal_capabilities`]: crate::terminal_capabilities
+
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Magic number of the legacy compiled format (16-bit numbers).
+const MAGIC_LEGACY: u16 = 0o432;
+/// Magic number of the extended compiled format (32-bit numbers).
+const MAGIC_32BIT: u16 = 0o1036;
+/// Upper bound on entry size; real entries are a few KiB.
+const MAX_ENTRY_BYTES: usize = 64 * 1024;
+/// Directories searched after the environment-provided ones.
+const SYSTEM_DIRS: &[&str] = &[
+    "/etc/terminfo",
+    "/lib/terminfo",
+    "/usr/share/terminfo",
+    "/usr/lib/terminfo",
+    "/usr/local/share/terminfo",
+];
+
+// Standard capability names in compiled-entry order (from `term.h`).
+const BOOL_NAMES: &[&str] = &[
+    "bw", "am", "xsb", "xhp", "xenl", "eo", "gn", "hc", "km", "hs", "in", "da", "db", "mir",
+    "msgr", "os", "eslok", "xt", "hz", "ul", "xon", "nxon", "mc5i", "chts", "nrrmc", "npc",
+    "ndscr", "ccc", "bce", "hls", "xhpa", "crxm", "daisy", "xvpa", "sam", "cpix", "lpix", "OTbs",
+    "OTns", "OTnc", "OTMT", "OTNL", "OTpt", "OTxr",
+];
+
+const NUM_NAMES: &[&str] = &[
+    "cols", "it", "lines", "lm", "xmc", "pb", "vt", "wsl", "nlab", "lh", "lw", "ma", "wnum",
+    "colors", "pairs", "ncv", "bufsz", "spinv", "spinh", "maddr", "mjump", "mcs", "mls", "npins",
+    "orc", "orl", "orhi", "orvi", "cps", "widcs", "btns", "bitwin", "bitype", "OTug", "OTdC",
+    "OTdN", "OTdB", "OTdT", "OTkn",
+];
+
+const STRING_NAMES: &[&str] = &[
+    "cbt", "bel", "cr", "csr", "tbc", "clear", "el", "ed", "hpa", "cmdch", "cup", "cud1", "home",
+    "civis", "cub1", "mrcup", "cnorm", "cuf1", "ll", "cuu1", "cvvis", "dch1", "dl1", "dsl", "hd",
+    "smacs", "blink", "bold", "smcup", "smdc", "dim", "smir", "invis", "prot", "rev", "smso",
+    "smul", "ech", "rmacs", "sgr0", "rmcup", "rmdc", "rmir", "rmso", "rmul", "flash", "ff", "fsl",
+    "is1", "is2", "is3", "if", "ich1", "il1", "ip", "kbs", "ktbc", "kclr", "kctab", "kdch1",
+    "kdl1", "kcud1", "krmir", "kel", "ked", "kf0", "kf1", "kf10", "kf2", "kf3", "kf4", "kf5",
+    "kf6", "kf7", "kf8", "kf9", "khome", "kich1", "kil1", "kcub1", "kll", "knp", "kpp", "kcuf1",
+    "kind", "kri", "khts", "kcuu1", "rmkx", "smkx", "lf0", "lf1", "lf10", "lf2", "lf3", "lf4",
+    "lf5", "lf6", "lf7", "lf8", "lf9", "rmm", "smm", "nel", "pad", "dch", "dl", "cud", "ich",
+    "indn", "il", "cub", "cuf", "rin", "cuu", "pfkey", "pfloc", "pfx", "mc0", "mc4", "mc5", "rep",
+    "rs1", "rs2", "rs3", "rf", "rc", "vpa", "sc", "ind", "ri", "sgr", "hts", "wind", "ht", "tsl",
+    "uc", "hu", "iprog", "ka1", "ka3", "kb2", "kc1", "kc3", "mc5p", "rmp", "acsc", "pln", "kcbt",
+    "smxon", "rmxon", "smam", "rmam", "xonc", "xoffc", "enacs", "smln", "rmln", "kbeg", "kcan",
+    "kclo", "kcmd", "kcpy", "kcrt", "kend", "kent", "kext", "kfnd", "khlp", "kmrk", "kmsg", "kmov",
+    "knxt", "kopn", "kopt", "kprv", "kprt", "krdo", "kref", "krfr", "krpl", "krst", "kres", "ksav",
+    "kspd", "kund", "kBEG", "kCAN", "kCMD", "kCPY", "kCRT", "kDC", "kDL", "kslt", "kEND", "kEOL",
+    "kEXT", "kFND", "kHLP", "kHOM", "kIC", "kLFT", "kMSG", "kMOV", "kNXT", "kOPT", "kPRV", "kPRT",
+    "kRDO", "kRPL", "kRIT", "kRES", "kSAV", "kSPD", "kUND", "rfi", "kf11", "kf12", "kf13", "kf14",
+    "kf15", "kf16", "kf17", "kf18", "kf19", "kf20", "kf21", "kf22", "kf23", "kf24", "kf25", "kf26",
+    "kf27", "kf28", "kf29", "kf30", "kf31", "kf32", "kf33", "kf34", "kf35", "kf36", "kf37", "kf38",
+    "kf39", "kf40", "kf41", "kf42", "kf43", "kf44", "kf45", "kf46", "kf47", "kf48", "kf49", "kf50",
+    "kf51", "kf52", "kf53", "kf54", "kf55", "kf56", "kf57", "kf58", "kf59", "kf60", "kf61", "kf62",
+    "kf63", "el1", "mgc", "smgl", "smgr", "fln", "sclk", "dclk", "rmclk", "cwin", "wingo", "hup",
+    "dial", "qdial", "tone", "pulse", "hook", "pause", "wait", "u0", "u1", "u2", "u3", "u4", "u5",
+    "u6", "u7", "u8", "u9", "op", "oc", "initc", "initp", "scp", "setf", "setb", "cpi", "lpi",
+    "chr", "cvr", "defc", "swidm", "sdrfq", "sitm", "slm", "smicm", "snlq", "snrmq", "sshm",
+    "ssubm", "ssupm", "sum", "rwidm", "ritm", "rlm", "rmicm", "rshm", "rsubm", "rsupm", "rum",
+    "mhpa", "mcud1", "mcub1", "mcuf1", "mvpa", "mcuu1", "porder", "mcud", "mcub", "mcuf", "mcuu",
+    "scs", "smgb", "smgbp", "smglp", "smgrp", "smgt", "smgtp", "sbim", "scsd", "rbim", "rcsd",
+    "subcs", "supcs", "docr", "zerom", "csnm", "kmous", "minfo", "reqmp", "getm", "setaf", "setab",
+    "pfxl", "devt", "csin", "s0ds", "s1ds", "s2ds", "s3ds", "smglr", "smgtb", "birep", "binel",
+    "bicr", "colornm", "defbi", "endbi", "setcolor", "slines", "dispc", "smpch", "rmpch", "smsc",
+    "rmsc", "pctrm", "scesc", "scesa", "ehhlm", "elhlm", "elohlm", "erhlm", "ethlm", "evhlm",
+    "sgr1", "slength", "OTi2", "OTrs", "OTnl", "OTbc", "OTko", "OTma", "OTG2", "OTG3", "OTG1",
+    "OTG4", "OTGR", "OTGL", "OTGU", "OTGD", "OTGH", "OTGV", "OTGC", "meml", "memu", "box1",
+];
+
+/// Errors produced while locating, parsing, or expanding terminfo entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminfoError {
+    /// No entry for the terminal name was found in any search directory.
+    NotFound(String),
+    /// The entry could not be read.
+    Io(String),
+    /// The file does not start with a known magic number.
+    BadMagic(u16),
+    /// The entry ends before a section it declares.
+    Truncated,
+    /// A header count or size is out of range.
+    InvalidHeader(&'static str),
+    /// The capability is absent or cancelled in this entry.
+    MissingCapability(String),
+    /// A parameterized string could not be evaluated.
+    InvalidParameterString(String),
+}
+
+impl fmt::Display for TerminfoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(name) => write!(f, "no terminfo entry for {name:?}"),
+            Self::Io(msg) => write!(f, "terminfo read failed: {msg}"),
+            Self::BadMagic(magic) => write!(f, "bad terminfo magic {magic:#o}"),
+            Self::Truncated => write!(f, "terminfo entry is truncated"),
+            Self::InvalidHeader(what) => write!(f, "invalid terminfo header: {what}"),
+            Self::MissingCapability(name) => write!(f, "terminfo capability {name:?} is absent"),
+            Self::InvalidParameterString(msg) => {
+                write!(f, "invalid terminfo parameter string: {msg}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TerminfoError {}
+
+/// A parsed terminfo entry.
+///
+/// Standard and extended capabilities share one namespace keyed by their
+/// short capability name (`"cup"`, `"colors"`, `"Tc"`, ...). Absent and
+/// cancelled capabilities are simply not present.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Terminfo {
+    names: Vec<String>,
+    flags: HashSet<String>,
+    numbers: HashMap<String, i32>,
+    strings: HashMap<String, Vec<u8>>,
+}
+
+impl Terminfo {
+    /// Load the entry named by `$TERM`.
+    pub fn from_env() -> Result<Self, TerminfoError> {
+        let term = env::var("TERM").unwrap_or_default();
+        if term.is_empty() {
+            return Err(TerminfoError::NotFound(term));
+        }
+        Self::load(&term)
+    }
+
+    /// Load the entry for `term` from the standard search directories.
+    pub fn load(term: &str) -> Result<Self, TerminfoError> {
+        Self::load_from(term, &search_dirs())
+    }
+
+    /// Load the entry for `term`, searching only `dirs` (in order).
+    pub fn load_from(term: &str, dirs: &[PathBuf]) -> Result<Self, TerminfoError> {
+        // Names containing path separators could escape the search directory.
+        let Some(first) = term.bytes().next() else {
+            return Err(TerminfoError::NotFound(term.to_owned()));
+        };
+        if term.contains(['/', '\\']) || term.contains("..") {
+            return Err(TerminfoError::NotFound(term.to_owned()));
+        }
+        for dir in dirs {
+            let candidates = [
+                dir.join((first as char).to_string()).join(term),
+                dir.join(format!("{first:02x}")).join(term),
+            ];
+            for path in &candidates {
+                if path.is_file() {
+                    return Self::read(path);
+                }
+            }
+        }
+        Err(TerminfoError::NotFound(term.to_owned()))
+    }
+
+    /// Read and parse a compiled entry from `path`.
+    pub fn read(path: &Path) -> Result<Self, TerminfoError> {
+        let data = std::fs::read(path).map_err(|e| TerminfoError::Io(e.to_string()))?;
+        Self::parse(&data)
+    }
+
+    /// Parse a compiled terminfo entry.
+    pub fn parse(data: &[u8]) -> Result<Self, TerminfoError> {
+        if data.len() > MAX_ENTRY_BYTES {
+            return Err(TerminfoError::InvalidHeader("entry too large"));
+        }
+        let mut r = Reader { data, pos: 0 };
+        let magic = r.u16()?;
+        let number_width = match magic {
+            MAGIC_LEGACY => 2,
+            MAGIC_32BIT => 4,
+            other => return Err(TerminfoError::BadMagic(other)),
+        };
+        let names_size = r.count("names size")?;
+        let bool_count = r.count("boolean count")?;
+        let num_count = r.count("number count")?;
+        let str_count = r.count("string count")?;
+        let table_size = r.count("string table size")?;
+        if bool_count > BOOL_NAMES.len()
+            || num_count > NUM_NAMES.len()
+            || str_count > STRING_NAMES.len()
+        {
+            return Err(TerminfoError::InvalidHeader(
+                "too many standard capabilities",
+            ));
+        }
+
+        let mut info = Self::default();
+        let names = r.bytes(names_size)?;
+        let names = names.strip_suffix(&[0]).unwrap_or(names);
+        info.names = String::from_utf8_lossy(names)
+            .split('|')
+            .map(str::to_owned)
+            .collect();
+
+        for name in BOOL_NAMES.iter().take(bool_count) {
+            if r.u8()? == 1 {
+                info.flags.insert((*name).to_owned());
+            }
+        }
+        r.align();
+        for name in NUM_NAMES.iter().take(num_count) {
+            let value = r.number(number_width)?;
+            if value >= 0 {
+                info.numbers.insert((*name).to_owned(), value);
+            }
+        }
+        let offsets = r.offsets(str_count)?;
+        let table = r.bytes(table_size)?;
+        for (name, offset) in STRING_NAMES.iter().zip(offsets) {
+            if let Some(value) = offset.and_then(|o| c_str(table, o)) {
+                info.strings.insert((*name).to_owned(), value.to_vec());
+            }
+        }
+
+        r.align();
+        if r.remaining() >= 10 {
+            info.parse_extended(&mut r, number_width)?;
+        }
+        Ok(info)
+    }
+
+    /// Parse the ncurses extended-capability section.
+    fn parse_extended(
+        &mut self,
+        r: &mut Reader<'_>,
+        number_width: usize,
+    ) -> Result<(), TerminfoError> {
+        let bool_count = r.count("extended boolean count")?;
+        let num_count = r.count("extended number count")?;
+        let str_count = r.count("extended string count")?;
+        let _item_count = r.count("extended item count")?;
+        let table_size = r.count("extended table size")?;
+
+        let bools = r.bytes(bool_count)?.to_vec();
+        r.align();
+        let mut nums = Vec::with_capacity(num_count);
+        for _ in 0..num_count {
+            nums.push(r.number(number_width)?);
+        }
+        let value_offsets = r.offsets(str_count)?;
+        let name_offsets = r.offsets(bool_count + num_count + str_count)?;
+        let table = r.bytes(table_size)?;
+
+        // Capability names follow the last string value in the table.
+        let names_base = value_offsets
+            .iter()
+            .flatten()
+            .filter_map(|&o| c_str(table, o).map(|s| o + s.len() + 1))
+            .max()
+            .unwrap_or(0);
+        let names_table = table.get(names_base..).unwrap_or_default();
+        let mut names = name_offsets.into_iter().map(|o| {
+            o.and_then(|o| c_str(names_table, o))
+                .map(|s| String::from_utf8_lossy(s).into_owned())
+        });
+
+        for value in bools {
+            if let Some(name) = names.next().flatten()
+                && value == 1
+            {
+                self.flags.insert(name);
+            }
+        }
+        for value in nums {
+            if let Some(name) = names.next().flatten()
+                && value >= 0
+            {
+                self.numbers.insert(name, value);
+            }
+        }
+        for offset in value_offsets {
+            if let Some(name) = names.next().flatten()
+                && let Some(value) = offset.and_then(|o| c_str(table, o))
+            {
+                self.strings.insert(name, value.to_vec());
+            }
+        }
+        Ok(())
+    }
+
+    /// Add or replace boolean capability `name`.
+    ///
+    /// Useful for patching an entry that misreports a capability, or for
+    /// building entries in tests.
+    #[must_use]
+    pub fn with_flag(mut self, name: &str) -> Self {
+        self.flags.insert(name.to_owned());
+        self
+    }
+
+    /// Add or replace numeric capability `name`.
+    #[must_use]
+    pub fn with_number(mut self, name: &str, value: i32) -> Self {
+        self.numbers.insert(name.to_owned(), value);
+        self
+    }
+
+    /// Add or replace string capability `name`.
+    #[must_use]
+    pub fn with_string(mut self, name: &str, value: impl Into<Vec<u8>>) -> Self {
+        self.strings.insert(name.to_owned(), value.into());
+        self
+    }
+
+    /// Terminal names from the entry header (primary name first).
+    #[must_use]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Whether boolean capability `name` is set.
+    #[must_use]
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    /// Value of numeric capability `name`.
+    #[must_use]
+    pub fn number(&self, name: &str) -> Option<i32> {
+        self.numbers.get(name).copied()
+    }
+
+    /// Raw (unexpanded) value of string capability `name`.
+    #[must_use]
+    pub fn string(&self, name: &str) -> Option<&[u8]> {
+        self.strings.get(name).map(Vec::as_slice)
+    }
+
+    /// Expand string capability `name` with `params`.
+    pub fn expand(&self, name: &str, params: &[i32]) -> Result<Vec<u8>, TerminfoError> {
+        let cap = self
+            .string(name)
+            .ok_or_else(|| TerminfoError::MissingCapability(name.to_owned()))?;
+        tparm(cap, params)
+    }
+
+    /// Number of colors the terminal supports (`colors`).
+    #[must_use]
+    pub fn max_colors(&self) -> i32 {
+        self.number("colors").unwrap_or(0)
+    }
+
+    /// Whether 24-bit color is advertised (`Tc` flag or `RGB` capability).
+    #[must_use]
+    pub fn true_color(&self) -> bool {
+        self.flag("Tc")
+            || self.flag("RGB")
+            || self.max_colors() >= 1 << 24
+            || self.number("RGB").is_some()
+            || self.string("RGB").is_some()
+    }
+
+    /// Whether styled/colored underlines are advertised (`Smulx`).
+    #[must_use]
+    pub fn styled_underline(&self) -> bool {
+        self.string("Smulx").is_some()
+    }
+
+    /// Whether cursor shape selection (DECSCUSR) is advertised (`Ss`).
+    #[must_use]
+    pub fn cursor_style(&self) -> bool {
+        self.string("Ss").is_some()
+    }
+
+    /// Whether synchronized output is advertised (`Sync`).
+    #[must_use]
+    pub fn sync_output(&self) -> bool {
+        self.string("Sync").is_some() || self.flag("Sync")
+    }
+
+    /// Alternate-screen enter/exit sequences (`smcup`, `rmcup`).
+    #[must_use]
+    pub fn alt_screen(&self) -> Option<(&[u8], &[u8])> {
+        Some((self.string("smcup")?, self.string("rmcup")?))
+    }
+}
+
+/// Directories searched by [`Terminfo::load`], in order.
+#[must_use]
+pub fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    let system = || SYSTEM_DIRS.iter().map(PathBuf::from);
+    match env::var("TERMINFO_DIRS") {
+        Ok(list) => {
+            for entry in list.split(':') {
+                if entry.is_empty() {
+                    dirs.extend(system());
+                } else {
+                    dirs.push(PathBuf::from(entry));
+                }
+            }
+        }
+        Err(_) => dirs.extend(system()),
+    }
+    dirs
+}
+
+/// Byte reader over a little-endian compiled entry.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.pos)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], TerminfoError> {
+        let end = self.pos.checked_add(len).ok_or(TerminfoError::Truncated)?;
+        let slice = self
+            .data
+            .get(self.pos..end)
+            .ok_or(TerminfoError::Truncated)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, TerminfoError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, TerminfoError> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn i16(&mut self) -> Result<i16, TerminfoError> {
+        Ok(self.u16()? as i16)
+    }
+
+    /// A header count; negative values are malformed.
+    fn count(&mut self, what: &'static str) -> Result<usize, TerminfoError> {
+        usize::try_from(self.i16()?).map_err(|_| TerminfoError::InvalidHeader(what))
+    }
+
+    fn number(&mut self, width: usize) -> Result<i32, TerminfoError> {
+        if width == 4 {
+            let b = self.bytes(4)?;
+            Ok(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        } else {
+            Ok(i32::from(self.i16()?))
+        }
+    }
+
+    /// String-table offsets; negative entries (absent/cancelled) are `None`.
+    fn offsets(&mut self, count: usize) -> Result<Vec<Option<usize>>, TerminfoError> {
+        (0..count)
+            .map(|_| self.i16().map(|o| usize::try_from(o).ok()))
+            .collect()
+    }
+
+    /// Skip the pad byte that keeps sections on even offsets.
+    fn align(&mut self) {
+        if self.pos % 2 == 1 && self.pos < self.data.len() {
+            self.pos += 1;
+        }
+    }
+}
+
+/// NUL-terminated string at `offset` in `table`.
+fn c_str(table: &[u8], offset: usize) -> Option<&[u8]> {
+    let rest = table.get(offset..)?;
+    let end = rest.iter().position(|&b| b == 0)?;
+    Some(&rest[..end])
+}
+
+// ============================================================================
+// Parameterized string expansion
+// ============================================================================
+
+/// Expand a terminfo parameterized string (the `tparm` language).
+///
+/// Supports the full operator set described in `terminfo(5)`: parameter
+/// pushes, `%i`, constants, arithmetic/logical/bitwise operators, static and
+/// dynamic variables, `%?`/`%t`/`%e`/`%;` conditionals, and `printf`-style
+/// output with flags, width and precision. Padding directives (`$<..>`) are
+/// dropped because modern terminals do not need them.
+pub fn tparm(cap: &[u8], params: &[i32]) -> Result<Vec<u8>, TerminfoError> {
+    let mut p = [0i32; 9];
+    for (slot, value) in p.iter_mut().zip(params) {
+        *slot = *value;
+    }
+    let mut stack: Vec<i32> = Vec::new();
+    let mut dynamic = [0i32; 26];
+    let mut statics = [0i32; 26];
+    let mut out = Vec::with_capacity(cap.len());
+    let err = |msg: &str| TerminfoError::InvalidParameterString(msg.to_owned());
+
+    let mut i = 0;
+    while i < cap.len() {
+        let c = cap[i];
+        i += 1;
+        if c == b'$' && cap.get(i) == Some(&b'<') {
+            // Padding: `$<delay[*][/]>`.
+            match cap[i..].iter().position(|&b| b == b'>') {
+                Some(end) => i += end + 1,
+                None => out.push(c),
+            }
+            continue;
+        }
+        if c != b'%' {
+            out.push(c);
+            continue;
+        }
+        let Some(&op) = cap.get(i) else {
+            return Err(err("trailing '%'"));
+        };
+        i += 1;
+        match op {
+            b'%' => out.push(b'%'),
+            b'c' => {
+                let v = pop(&mut stack);
+                out.push(v as u8);
+            }
+            b'p' => {
+                let digit = cap
+                    .get(i)
+                    .copied()
+                    .ok_or_else(|| err("missing parameter index"))?;
+                i += 1;
+                if !(b'1'..=b'9').contains(&digit) {
+                    return Err(err("parameter index out of range"));
+                }
+                stack.push(p[usize::from(digit - b'1')]);
+            }
+            b'P' | b'g' => {
+                let name = cap
+                    .get(i)
+                    .copied()
+                    .ok_or_else(|| err("missing variable name"))?;
+                i += 1;
+                let slot = match name {
+                    b'a'..=b'z' => &mut dynamic[usize::from(name - b'a')],
+                    b'A'..=b'Z' => &mut statics[usize::from(name - b'A')],
+                    _ => return Err(err("invalid variable name")),
+                };
+                if op == b'P' {
+                    *slot = pop(&mut stack);
+                } else {
+                    stack.push(*slot);
+                }
+            }
+            b'\'' => {
+                let ch = cap
+                    .get(i)
+                    .copied()
+                    .ok_or_else(|| err("unterminated char constant"))?;
+                if cap.get(i + 1) != Some(&b'\'') {
+                    return Err(err("unterminated char constant"));
+                }
+                i += 2;
+                stack.push(i32::from(ch));
+            }
+            b'{' => {
+                let end = cap[i..]
+                    .iter()
+                    .position(|&b| b == b'}')
+                    .ok_or_else(|| err("unterminated integer constant"))?;
+                let text =
+                    std::str::from_utf8(&cap[i..i + end]).map_err(|_| err("bad constant"))?;
+                let value = text
+                    .parse::<i32>()
+                    .map_err(|_| err("bad integer constant"))?;
+                i += end + 1;
+                stack.push(value);
+            }
+            b'l' => {
+                // Parameters are integers, so string length is always zero.
+                pop(&mut stack);
+                stack.push(0);
+            }
+            b'+' | b'-' | b'*' | b'/' | b'm' | b'&' | b'|' | b'^' | b'=' | b'<' | b'>' | b'A'
+            | b'O' => {
+                let b = pop(&mut stack);
+                let a = pop(&mut stack);
+                let v = match op {
+                    b'+' => a.wrapping_add(b),
+                    b'-' => a.wrapping_sub(b),
+                    b'*' => a.wrapping_mul(b),
+                    b'/' => a.checked_div(b).unwrap_or(0),
+                    b'm' => a.checked_rem(b).unwrap_or(0),
+                    b'&' => a & b,
+                    b'|' => a | b,
+                    b'^' => a ^ b,
+                    b'=' => i32::from(a == b),
+                    b'<' => i32::from(a < b),
+                    b'>' => i32::from(a > b),
+                    b'A' => i32::from(a != 0 && b != 0),
+                    _ => i32::from(a != 0 || b != 0),
+                };
+                stack.push(v);
+            }
+            b'!' => {
+                let v = pop(&mut stack);
+                stack.push(i32::from(v == 0));
+            }
+            b'~' => {
+                let v = pop(&mut stack);
+                stack.push(!v);
+            }
+            b'i' => {
+                p[0] = p[0].wrapping_add(1);
+                p[1] = p[1].wrapping_add(1);
+            }
+            b'?' | b';' => {}
+            b't' => {
+                if pop(&mut stack) == 0 {
+                    // Jump to the matching `%e` (else branch) or `%;`.
+                    i = skip_branch(cap, i, true);
+                }
+            }
+            b'e' => {
+                // End of a taken then-branch: jump past the matching `%;`.
+                i = skip_branch(cap, i, false);
+            }
+            _ => {
+                let start = i - 1;
+                let spec_len = cap[start..]
+                    .iter()
+                    .position(|b| matches!(b, b'd' | b'o' | b'x' | b'X' | b's'))
+                    .ok_or_else(|| err("unknown '%' directive"))?;
+                let spec = &cap[start..start + spec_len];
+                let conv = cap[start + spec_len];
+                i = start + spec_len + 1;
+                format_value(&mut out, spec, conv, pop(&mut stack))?;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Pop a stack value; an empty stack yields zero, as in ncurses.
+fn pop(stack: &mut Vec<i32>) -> i32 {
+    stack.pop().unwrap_or(0)
+}
+
+/// Advance past a conditional branch starting at `i`.
+///
+/// With `stop_at_else`, stops after a same-level `%e`; always stops after the
+/// matching `%;`.
+fn skip_branch(cap: &[u8], mut i: usize, stop_at_else: bool) -> usize {
+    let mut depth = 0usize;
+    while i < cap.len() {
+        if cap[i] != b'%' || i + 1 >= cap.len() {
+            i += 1;
+            continue;
+        }
+        let op = cap[i + 1];
+        i += 2;
+        match op {
+            b'?' => depth += 1,
+            b';' if depth == 0 => return i,
+            b';' => depth -= 1,
+            b'e' if depth == 0 && stop_at_else => return i,
+            _ => {}
+        }
+    }
+    i
+}
+
+/// Render a `printf`-style directive (`%[:][flags][width][.prec]conv`).
+fn format_value(out: &mut Vec<u8>, spec: &[u8], conv: u8, value: i32) -> Result<(), TerminfoError> {
+    let spec = spec.strip_prefix(b":").unwrap_or(spec);
+    let mut left = false;
+    let mut plus = false;
+    let mut space = false;
+    let mut alt = false;
+    let mut zero = false;
+    let mut idx = 0;
+    while let Some(&f) = spec.get(idx) {
+        match f {
+            b'-' => left = true,
+            b'+' => plus = true,
+            b' ' => space = true,
+            b'#' => alt = true,
+            b'0' => zero = true,
+            _ => break,
+        }
+        idx += 1;
+    }
+    let rest = std::str::from_utf8(&spec[idx..])
+        .map_err(|_| TerminfoError::InvalidParameterString("bad format spec".into()))?;
+    let (width, precision) = match rest.split_once('.') {
+        Some((w, p)) => (w, Some(p)),
+        None => (rest, None),
+    };
+    let parse = |s: &str| -> Result<usize, TerminfoError> {
+        if s.is_empty() {
+            Ok(0)
+        } else {
+            s.parse()
+                .map_err(|_| TerminfoError::InvalidParameterString("bad format width".into()))
+        }
+    };
+    let width = parse(width)?;
+    let precision = precision.map(parse).transpose()?;
+
+    let (sign, mut body) = if conv == b's' {
+        (String::new(), value.to_string())
+    } else {
+        let magnitude = value.unsigned_abs();
+        let digits = match conv {
+            b'o' => format!("{magnitude:o}"),
+            b'x' => format!("{magnitude:x}"),
+            b'X' => format!("{magnitude:X}"),
+            _ => magnitude.to_string(),
+        };
+        let sign = if value < 0 {
+            "-"
+        } else if plus && conv == b'd' {
+            "+"
+        } else if space && conv == b'd' {
+            " "
+        } else {
+            ""
+        };
+        let prefix = match (alt, conv) {
+            (true, b'o') if !digits.starts_with('0') => "0",
+            (true, b'x') if value != 0 => "0x",
+            (true, b'X') if value != 0 => "0X",
+            _ => "",
+        };
+        (format!("{sign}{prefix}"), digits)
+    };
+
+    if let Some(precision) = precision {
+        if conv == b's' {
+            body = body.chars().take(precision).collect();
+        } else if body.len() < precision {
+            body = format!("{}{body}", "0".repeat(precision - body.len()));
+        }
+    }
+    let len = sign.len() + body.len();
+    let pad = width.saturating_sub(len);
+    if left {
+        out.extend_from_slice(sign.as_bytes());
+        out.extend_from_slice(body.as_bytes());
+        out.extend(std::iter::repeat_n(b' ', pad));
+    } else if zero && conv != b's' && precision.is_none() {
+        out.extend_from_slice(sign.as_bytes());
+        out.extend(std::iter::repeat_n(b'0', pad));
+        out.extend_from_slice(body.as_bytes());
+    } else {
+        out.extend(std::iter::repeat_n(b' ', pad));
+        out.extend_from_slice(sign.as_bytes());
+        out.extend_from_slice(body.as_bytes());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal compiled-entry writer for tests.
+    struct EntryBuilder {
+        names: &'static str,
+        bools: Vec<bool>,
+        numbers: Vec<i32>,
+        strings: Vec<Option<&'static [u8]>>,
+        ext_bools: Vec<&'static str>,
+        ext_strings: Vec<(&'static str, &'static [u8])>,
+        wide_numbers: bool,
+    }
+
+    impl EntryBuilder {
+        fn new(names: &'static str) -> Self {
+            Self {
+                names,
+                bools: Vec::new(),
+                numbers: Vec::new(),
+                strings: Vec::new(),
+                ext_bools: Vec::new(),
+                ext_strings: Vec::new(),
+                wide_numbers: false,
+            }
+        }
+
+        fn string(mut self, name: &str, value: &'static [u8]) -> Self {
+            let idx = STRING_NAMES.iter().position(|n| *n == name).unwrap();
+            if self.strings.len() <= idx {
+                self.strings.resize(idx + 1, None);
+            }
+            self.strings[idx] = Some(value);
+            self
+        }
+
+        fn number(mut self, name: &str, value: i32) -> Self {
+            let idx = NUM_NAMES.iter().position(|n| *n == name).unwrap();
+            if self.numbers.len() <= idx {
+                self.numbers.resize(idx + 1, -1);
+            }
+            self.numbers[idx] = value;
+            self
+        }
+
+        fn flag(mut self, name: &str) -> Self {
+            let idx = BOOL_NAMES.iter().position(|n| *n == name).unwrap();
+            if self.bools.len() <= idx {
+                self.bools.resize(idx + 1, false);
+            }
+            self.bools[idx] = true;
+            self
+        }
+
+        fn build(&self) -> Vec<u8> {
+            fn push16(out: &mut Vec<u8>, v: i32) {
+                out.extend_from_slice(&(v as i16).to_le_bytes());
+            }
+            fn pad(out: &mut Vec<u8>) {
+                if out.len() % 2 == 1 {
+                    out.push(0);
+                }
+            }
+            let push_num = |out: &mut Vec<u8>, v: i32| {
+                if self.wide_numbers {
+                    out.extend_from_slice(&v.to_le_bytes());
+                } else {
+                    push16(out, v);
+                }
+            };
+
+            let mut table = Vec::new();
+            let mut offsets = Vec::new();
+            for s in &self.strings {
+                match s {
+                    Some(s) => {
+                        offsets.push(table.len() as i32);
+                        table.extend_from_slice(s);
+                        table.push(0);
+                    }
+                    None => offsets.push(-1),
+                }
+            }
+
+            let mut out = Vec::new();
+            let magic = if self.wide_numbers {
+                MAGIC_32BIT
+            } else {
+                MAGIC_LEGACY
+            };
+            out.extend_from_slice(&magic.to_le_bytes());
+            push16(&mut out, self.names.len() as i32 + 1);
+            push16(&mut out, self.bools.len() as i32);
+            push16(&mut out, self.numbers.len() as i32);
+            push16(&mut out, self.strings.len() as i32);
+            push16(&mut out, table.len() as i32);
+            out.extend_from_slice(self.names.as_bytes());
+            out.push(0);
+            out.extend(self.bools.iter().map(|&b| u8::from(b)));
+            pad(&mut out);
+            for &n in &self.numbers {
+                push_num(&mut out, n);
+            }
+            for &o in &offsets {
+                push16(&mut out, o);
+            }
+            out.extend_from_slice(&table);
+
+            if self.ext_bools.is_empty() && self.ext_strings.is_empty() {
+                return out;
+            }
+            pad(&mut out);
+            let mut ext_table = Vec::new();
+            let mut value_offsets = Vec::new();
+            for (_, value) in &self.ext_strings {
+                value_offsets.push(ext_table.len() as i32);
+                ext_table.extend_from_slice(value);
+                ext_table.push(0);
+            }
+            let names_base = ext_table.len();
+            let mut name_offsets = Vec::new();
+            let all_names = self
+                .ext_bools
+                .iter()
+                .copied()
+                .chain(self.ext_strings.iter().map(|(n, _)| *n));
+            for name in all_names {
+                name_offsets.push((ext_table.len() - names_base) as i32);
+                ext_table.extend_from_slice(name.as_bytes());
+                ext_table.push(0);
+            }
+            push16(&mut out, self.ext_bools.len() as i32);
+            push16(&mut out, 0);
+            push16(&mut out, self.ext_strings.len() as i32);
+            push16(&mut out, (value_offsets.len() + name_offsets.len()) as i32);
+            push16(&mut out, ext_table.len() as i32);
+            out.extend(self.ext_bools.iter().map(|_| 1u8));
+            pad(&mut out);
+            for o in value_offsets.iter().chain(&name_offsets) {
+                push16(&mut out, *o);
+            }
+            out.extend_from_slice(&ext_table);
+            out
+        }
+    }
+
+    fn sample() -> EntryBuilder {
+        let mut entry = EntryBuilder::new("fancy-term|Fancy test terminal")
+            .flag("am")
+            .number("cols", 80)
+            .number("colors", 256)
+            .string("cup", b"\x1b[%i%p1%d;%p2%dH")
+            .string("sgr0", b"\x1b[m")
+            .string("smcup", b"\x1b[?1049h")
+            .string("rmcup", b"\x1b[?1049l");
+        entry.ext_bools = vec!["Tc"];
+        entry.ext_strings = vec![
+            ("Smulx", b"\x1b[4:%p1%dm"),
+            ("Ss", b"\x1b[%p1%d q"),
+            ("Sync", b"\x1b[?2026%?%p1%{1}%-%tl%eh%;"),
+        ];
+        entry
+    }
+
+    #[test]
+    fn parses_legacy_entry() {
+        let info = Terminfo::parse(&sample().build()).unwrap();
+        assert_eq!(info.names(), ["fancy-term", "Fancy test terminal"]);
+        assert!(info.flag("am"));
+        assert!(!info.flag("bw"));
+        assert_eq!(info.number("cols"), Some(80));
+        assert_eq!(info.max_colors(), 256);
+        assert_eq!(info.string("sgr0"), Some(&b"\x1b[m"[..]));
+        assert_eq!(info.string("el"), None);
+    }
+
+    #[test]
+    fn parses_32bit_numbers() {
+        let mut entry = sample().number("colors", 0x0100_0000);
+        entry.wide_numbers = true;
+        let info = Terminfo::parse(&entry.build()).unwrap();
+        assert_eq!(info.max_colors(), 0x0100_0000);
+        assert!(info.true_color());
+    }
+
+    #[test]
+    fn parses_extended_capabilities() {
+        let info = Terminfo::parse(&sample().build()).unwrap();
+        assert!(info.true_color());
+        assert!(info.styled_underline());
+        assert!(info.cursor_style());
+        assert!(info.sync_output());
+        assert_eq!(
+            info.alt_screen(),
+            Some((&b"\x1b[?1049h"[..], &b"\x1b[?1049l"[..]))
+        );
+    }
+
+    #[test]
+    fn builder_applies_terminfo() {
+        use crate::terminal_capabilities::CapabilityProfileBuilder;
+
+        let info = Terminfo::parse(&sample().build()).unwrap();
+        let caps = CapabilityProfileBuilder::new().terminfo(&info).build();
+        assert!(caps.colors_256);
+        assert!(caps.true_color);
+        assert!(caps.sync_output);
+        assert!(caps.styled_underline);
+        assert!(caps.cursor_style);
+        assert!(caps.alt_screen);
+        assert!(!caps.mouse_sgr);
+
+        let bare = CapabilityProfileBuilder::new()
+            .terminfo(&Terminfo::default())
+            .build();
+        assert!(!bare.styled_underline);
+        assert!(!bare.cursor_style);
+        assert!(!bare.alt_screen);
+
+        let kept = CapabilityProfileBuilder::new()
+            .true_color(true)
+            .colors_256(true)
+            .sync_output(true)
+            .terminfo(&Terminfo::default().with_number("colors", 256))
+            .build();
+        assert!(kept.true_color);
+        assert!(kept.colors_256);
+        assert!(kept.sync_output);
+
+        let raised = CapabilityProfileBuilder::new()
+            .terminfo(&Terminfo::default().with_number("colors", 256))
+            .build();
+        assert!(!raised.true_color);
+        assert!(raised.colors_256);
+    }
+
+    #[test]
+    fn rejects_bad_magic_and_truncation() {
+        assert_eq!(
+            Terminfo::parse(&[0x34, 0x12, 0, 0]),
+            Err(TerminfoError::BadMagic(0x1234))
+        );
+        let data = sample().build();
+        assert_eq!(Terminfo::parse(&data[..20]), Err(TerminfoError::Truncated));
+    }
+
+    #[test]
+    fn load_from_rejects_path_traversal() {
+        let err = Terminfo::load_from("../etc/passwd", &[PathBuf::from("/")]).unwrap_err();
+        assert!(matches!(err, TerminfoError::NotFound(_)));
+    }
+
+    #[test]
+    fn load_from_searches_letter_and_hex_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let hex = dir.path().join("66");
+        std::fs::create_dir_all(&hex).unwrap();
+        std::fs::write(hex.join("fancy-term"), sample().build()).unwrap();
+        let info = Terminfo::load_from("fancy-term", &[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(info.names()[0], "fancy-term");
+    }
+
+    #[test]
+    fn with_methods_patch_entry() {
+        let info = Terminfo::default()
+            .with_flag("Tc")
+            .with_number("colors", 8)
+            .with_string("cup", "\x1b[%i%p1%d;%p2%dH");
+        assert!(info.true_color());
+        assert_eq!(info.max_colors(), 8);
+        assert_eq!(info.expand("cup", &[0, 0]).unwrap(), b"\x1b[1;1H");
+        assert_eq!(
+            info.expand("el", &[]),
+            Err(TerminfoError::MissingCapability("el".into()))
+        );
+    }
+
+    #[test]
+    fn tparm_cursor_address() {
+        assert_eq!(
+            tparm(b"\x1b[%i%p1%d;%p2%dH", &[4, 10]).unwrap(),
+            b"\x1b[5;11H"
+        );
+    }
+
+    #[test]
+    fn tparm_conditionals_and_arithmetic() {
+        // xterm setaf.
+        let setaf = b"\x1b[%?%p1%{8}%<%t3%p1%d%e%p1%{16}%<%t9%p1%{8}%-%d%e38;5;%p1%d%;m";
+        assert_eq!(tparm(setaf, &[1]).unwrap(), b"\x1b[31m");
+        assert_eq!(tparm(setaf, &[9]).unwrap(), b"\x1b[91m");
+        assert_eq!(tparm(setaf, &[200]).unwrap(), b"\x1b[38;5;200m");
+    }
+
+    #[test]
+    fn tparm_variables_chars_and_padding() {
+        assert_eq!(tparm(b"%p1%Pa%ga%ga%+%d", &[3]).unwrap(), b"6");
+        assert_eq!(tparm(b"%p1%c$<5>", &[65]).unwrap(), b"A");
+        assert_eq!(tparm(b"%'a'%c%{2}%{3}%*%d", &[]).unwrap(), b"a6");
+    }
+
+    #[test]
+    fn tparm_format_flags() {
+        assert_eq!(tparm(b"%p1%03d", &[7]).unwrap(), b"007");
+        assert_eq!(tparm(b"%p1%:-3d|", &[7]).unwrap(), b"7  |");
+        assert_eq!(tparm(b"%p1%x%p1%#X", &[255]).unwrap(), b"ff0XFF");
+        assert!(tparm(b"%", &[]).is_err());
+    }
+}
@@ -0,0 +1,1257 @@
+#![forbid(unsafe_code)]
+
+//! Syntax-highlighted, foldable code editor widget.
+//!
+//! [`CodeEditor`] combines the editing model of
+//! [`TextArea`](ftui_widgets::textarea::TextArea) with the incremental
+//! tokenizer cache from [`crate::syntax`]:
+//!
+//! - Only edited lines are re-tokenized (plus any lines whose lexical state
+//!   the edit changed, e.g. after opening a block comment).
+//! - Tokens are rendered with a [`HighlightTheme`].
+//! - Regions fold by indentation or by brackets, with markers in the gutter.
+//! - Enter auto-indents, closing brackets dedent, and the bracket under the
+//!   cursor is highlighted with its match.
+//! - A gutter column shows [`Diagnostic`] markers.
+//!
+//! Lines are not soft-wrapped; long lines scroll horizontally.
+//!
+//! # Key bindings
+//!
+//! On top of the [`TextArea`] bindings:
+//!
+//! | Key | Action |
+//! |-----|--------|
+//! | Enter | New line, keeping (or increasing) indentation |
+//! | Tab | Insert one indent unit |
+//! | Alt-[ / Alt-] | Fold / unfold the region around the cursor |
+//!
+//! # Example
+//! ```ignore
+//! use ftui_extras::code_editor::{CodeEditor, Diagnostic, DiagnosticSeverity};
+//! use ftui_extras::syntax::rust_tokenizer;
+//!
+//! let mut editor = CodeEditor::new(rust_tokenizer()).with_text("fn main() {\n}\n");
+//! editor.set_diagnostics([Diagnostic::new(0, DiagnosticSeverity::Warning, "unused")]);
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::Arc;
+
+use ftui_core::event::{Event, KeyCode, KeyEvent, KeyEventKind, Modifiers};
+use ftui_core::geometry::Rect;
+use ftui_render::cell::{Cell, CellContent, PackedRgba};
+use ftui_render::frame::Frame;
+use ftui_style::Style;
+use ftui_text::editor::Editor;
+use ftui_text::{CursorNavigator, CursorPosition, grapheme_width, graphemes};
+use ftui_widgets::Widget;
+use ftui_widgets::textarea::TextArea;
+use ftui_widgets::vim::VimMode;
+
+use crate::syntax::{HighlightTheme, TokenKind, TokenizedText, Tokenizer};
+
+/// Visual cells per tab when measuring indentation.
+const TAB_WIDTH: usize = 4;
+
+/// How fold regions are detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FoldStrategy {
+    /// A line folds the following lines that are indented deeper than it
+    /// (blank lines included). Suits Python, YAML and similar.
+    Indentation,
+    /// A line folds up to the line before the bracket closing one it opens.
+    /// Brackets inside strings and comments are ignored.
+    #[default]
+    Brackets,
+}
+
+/// A foldable range of lines.
+///
+/// Folding hides lines `start + 1..=end`; the `start` line stays visible
+/// with a fold marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FoldRegion {
+    /// Line that opens the region.
+    pub start: usize,
+    /// Last line hidden when folded (inclusive).
+    pub end: usize,
+}
+
+impl FoldRegion {
+    /// Whether `line` is hidden when this region is folded.
+    #[must_use]
+    pub fn hides(&self, line: usize) -> bool {
+        line > self.start && line <= self.end
+    }
+}
+
+/// Severity of a [`Diagnostic`], most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl DiagnosticSeverity {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A message attached to a line, shown as a marker in the gutter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Line index (0-based).
+    pub line: usize,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic.
+    pub fn new(line: usize, severity: DiagnosticSeverity, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+/// Syntax-highlighted code editor with folding and a diagnostics gutter.
+pub struct CodeEditor {
+    area: TextArea,
+    tokenizer: Arc<dyn Tokenizer>,
+    tokens: TokenizedText,
+    /// Line texts the token cache was built from, without line endings.
+    lines: Vec<String>,
+    theme: HighlightTheme,
+    fold_strategy: FoldStrategy,
+    /// Fold regions sorted by start line, at most one per start line.
+    folds: Vec<FoldRegion>,
+    /// Start lines of the collapsed regions.
+    folded: BTreeSet<usize>,
+    diagnostics: Vec<Diagnostic>,
+    auto_indent: bool,
+    indent_unit: String,
+    show_line_numbers: bool,
+    gutter_style: Style,
+    cursor_line_style: Option<Style>,
+    selection_style: Style,
+    bracket_match_style: Style,
+    fold_marker_style: Style,
+    diagnostic_styles: [Style; 4],
+    /// First visible row, counted in unfolded lines.
+    scroll_top: std::cell::Cell<usize>,
+    /// Horizontal scroll in cells.
+    scroll_left: std::cell::Cell<usize>,
+}
+
+impl fmt::Debug for CodeEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodeEditor")
+            .field("tokenizer", &self.tokenizer.name())
+            .field("line_count", &self.lines.len())
+            .field("fold_strategy", &self.fold_strategy)
+            .field("folded", &self.folded)
+            .field("diagnostics", &self.diagnostics.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CodeEditor {
+    /// Create an empty editor highlighting with `tokenizer` and the dark
+    /// theme.
+    #[must_use]
+    pub fn new(tokenizer: impl Tokenizer + 'static) -> Self {
+        Self::with_shared_tokenizer(Arc::new(tokenizer))
+    }
+
+    /// Create an empty editor with a tokenizer shared with other editors.
+    #[must_use]
+    pub fn with_shared_tokenizer(tokenizer: Arc<dyn Tokenizer>) -> Self {
+        let mut editor = Self {
+            area: TextArea::new(),
+            tokenizer,
+            tokens: TokenizedText::default(),
+            lines: Vec::new(),
+            theme: HighlightTheme::dark(),
+            fold_strategy: FoldStrategy::default(),
+            folds: Vec::new(),
+            folded: BTreeSet::new(),
+            diagnostics: Vec::new(),
+            auto_indent: true,
+            indent_unit: "    ".to_owned(),
+            show_line_numbers: true,
+            gutter_style: Style::new().fg(PackedRgba::rgb(92, 99, 112)),
+            cursor_line_style: None,
+            selection_style: Style::new().reverse(),
+            bracket_match_style: Style::new().bold().underline(),
+            fold_marker_style: Style::new().fg(PackedRgba::rgb(97, 175, 239)),
+            diagnostic_styles: [
+                Style::new().fg(PackedRgba::rgb(224, 108, 117)),
+                Style::new().fg(PackedRgba::rgb(229, 192, 123)),
+                Style::new().fg(PackedRgba::rgb(97, 175, 239)),
+                Style::new().fg(PackedRgba::rgb(92, 99, 112)),
+            ],
+            scroll_top: std::cell::Cell::new(0),
+            scroll_left: std::cell::Cell::new(0),
+        };
+        editor.rebuild();
+        editor
+    }
+
+    // ── Builder methods ────────────────────────────────────────────
+
+    /// Set initial text content (builder).
+    #[must_use]
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set the highlight theme (builder).
+    #[must_use]
+    pub fn with_theme(mut self, theme: HighlightTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Set how fold regions are detected (builder).
+    #[must_use]
+    pub fn with_fold_strategy(mut self, strategy: FoldStrategy) -> Self {
+        self.fold_strategy = strategy;
+        self.folded.clear();
+        self.recompute_folds();
+        self
+    }
+
+    /// Enable or disable auto-indentation (builder). Enabled by default.
+    #[must_use]
+    pub fn with_auto_indent(mut self, enabled: bool) -> Self {
+        self.auto_indent = enabled;
+        self
+    }
+
+    /// Set the text inserted per indentation level (builder). Defaults to
+    /// four spaces.
+    #[must_use]
+    pub fn with_indent_unit(mut self, unit: impl Into<String>) -> Self {
+        self.indent_unit = unit.into();
+        self
+    }
+
+    /// Show or hide line numbers (builder). Shown by default.
+    #[must_use]
+    pub fn with_line_numbers(mut self, show: bool) -> Self {
+        self.show_line_numbers = show;
+        self
+    }
+
+    /// Set focus state (builder).
+    #[must_use]
+    pub fn with_focus(mut self, focused: bool) -> Self {
+        self.area.set_focused(focused);
+        self
+    }
+
+    /// Enable Vim-style modal editing (builder).
+    #[must_use]
+    pub fn with_vim(mut self, enabled: bool) -> Self {
+        self.area.set_vim_enabled(enabled);
+        self
+    }
+
+    /// Set the gutter style (builder).
+    #[must_use]
+    pub fn with_gutter_style(mut self, style: Style) -> Self {
+        self.gutter_style = style;
+        self
+    }
+
+    /// Set the style for the cursor line (builder).
+    #[must_use]
+    pub fn with_cursor_line_style(mut self, style: Style) -> Self {
+        self.cursor_line_style = Some(style);
+        self
+    }
+
+    /// Set the selection highlight style (builder).
+    #[must_use]
+    pub fn with_selection_style(mut self, style: Style) -> Self {
+        self.selection_style = style;
+        self
+    }
+
+    /// Set the style for the bracket under the cursor and its match (builder).
+    #[must_use]
+    pub fn with_bracket_match_style(mut self, style: Style) -> Self {
+        self.bracket_match_style = style;
+        self
+    }
+
+    /// Set the style for fold markers (builder).
+    #[must_use]
+    pub fn with_fold_marker_style(mut self, style: Style) -> Self {
+        self.fold_marker_style = style;
+        self
+    }
+
+    /// Set the gutter marker style for a diagnostic severity (builder).
+    #[must_use]
+    pub fn with_diagnostic_style(mut self, severity: DiagnosticSeverity, style: Style) -> Self {
+        self.diagnostic_styles[severity.index()] = style;
+        self
+    }
+
+    // ── State access ───────────────────────────────────────────────
+
+    /// Get the full text.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.area.text()
+    }
+
+    /// Replace the full text, re-tokenizing from scratch and clearing folds.
+    pub fn set_text(&mut self, text: &str) {
+        self.area.set_text(text);
+        self.folded.clear();
+        self.rebuild();
+    }
+
+    /// The underlying text area.
+    #[must_use]
+    pub fn text_area(&self) -> &TextArea {
+        &self.area
+    }
+
+    /// The underlying editor.
+    #[must_use]
+    pub fn editor(&self) -> &Editor {
+        self.area.editor()
+    }
+
+    /// Cursor position.
+    #[must_use]
+    pub fn cursor(&self) -> CursorPosition {
+        self.area.cursor()
+    }
+
+    /// Move the cursor, unfolding any region that hides it.
+    pub fn set_cursor_position(&mut self, pos: CursorPosition) {
+        self.area.set_cursor_position(pos);
+        self.reveal_line(self.area.cursor().line);
+    }
+
+    /// Cached tokens, one entry per line.
+    #[must_use]
+    pub fn tokens(&self) -> &TokenizedText {
+        &self.tokens
+    }
+
+    /// Whether the editor has focus.
+    #[must_use]
+    pub fn is_focused(&self) -> bool {
+        self.area.is_focused()
+    }
+
+    /// Set focus state.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.area.set_focused(focused);
+    }
+
+    /// Current Vim mode, when modal editing is on.
+    #[must_use]
+    pub fn vim_mode(&self) -> Option<VimMode> {
+        self.area.vim_mode()
+    }
+
+    // ── Diagnostics ────────────────────────────────────────────────
+
+    /// Replace all diagnostics.
+    pub fn set_diagnostics(&mut self, diagnostics: impl IntoIterator<Item = Diagnostic>) {
+        self.diagnostics = diagnostics.into_iter().collect();
+    }
+
+    /// Remove all diagnostics.
+    pub fn clear_diagnostics(&mut self) {
+        self.diagnostics.clear();
+    }
+
+    /// All diagnostics. Line numbers follow edits that insert or remove
+    /// lines above them.
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Diagnostics on a line.
+    pub fn diagnostics_at(&self, line: usize) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(move |d| d.line == line)
+    }
+
+    // ── Folding ────────────────────────────────────────────────────
+
+    /// All fold regions, sorted by start line.
+    #[must_use]
+    pub fn fold_regions(&self) -> &[FoldRegion] {
+        &self.folds
+    }
+
+    /// Whether the region starting at `line` is folded.
+    #[must_use]
+    pub fn is_folded(&self, line: usize) -> bool {
+        self.folded.contains(&line)
+    }
+
+    /// Whether `line` is hidden inside a folded region.
+    #[must_use]
+    pub fn is_hidden(&self, line: usize) -> bool {
+        self.folded
+            .range(..line)
+            .any(|&start| self.region_at(start).is_some_and(|r| r.hides(line)))
+    }
+
+    /// Fold the innermost region containing `line`. Returns whether
+    /// anything changed.
+    pub fn fold(&mut self, line: usize) -> bool {
+        let Some(region) = self.innermost_region(line, |_| true) else {
+            return false;
+        };
+        let changed = self.folded.insert(region.start);
+        if changed {
+            self.move_cursor_out_of_folds();
+        }
+        changed
+    }
+
+    /// Unfold the innermost folded region containing `line`. Returns
+    /// whether anything changed.
+    pub fn unfold(&mut self, line: usize) -> bool {
+        let folded = &self.folded;
+        match self.innermost_region(line, |r| folded.contains(&r.start)) {
+            Some(region) => self.folded.remove(&region.start),
+            None => false,
+        }
+    }
+
+    /// Fold the region containing `line` if it is open, unfold it otherwise.
+    pub fn toggle_fold(&mut self, line: usize) -> bool {
+        self.unfold(line) || self.fold(line)
+    }
+
+    /// Fold every region.
+    pub fn fold_all(&mut self) {
+        self.folded = self.folds.iter().map(|r| r.start).collect();
+        self.move_cursor_out_of_folds();
+    }
+
+    /// Unfold every region.
+    pub fn unfold_all(&mut self) {
+        self.folded.clear();
+    }
+
+    fn region_at(&self, start: usize) -> Option<FoldRegion> {
+        self.folds
+            .binary_search_by_key(&start, |r| r.start)
+            .ok()
+            .map(|i| self.folds[i])
+    }
+
+    /// Innermost region starting at or enclosing `line` that passes `filter`.
+    fn innermost_region(
+        &self,
+        line: usize,
+        filter: impl Fn(&FoldRegion) -> bool,
+    ) -> Option<FoldRegion> {
+        self.folds
+            .iter()
+            .filter(|r| r.start <= line && line <= r.end && filter(r))
+            .max_by_key(|r| r.start)
+            .copied()
+    }
+
+    /// Unfold every region hiding `line`.
+    fn reveal_line(&mut self, line: usize) {
+        let hiding: Vec<usize> = self
+            .folded
+            .range(..line)
+            .copied()
+            .filter(|&start| self.region_at(start).is_some_and(|r| r.hides(line)))
+            .collect();
+        for start in hiding {
+            self.folded.remove(&start);
+        }
+    }
+
+    /// Move the cursor to the start line of the outermost fold hiding it.
+    fn move_cursor_out_of_folds(&mut self) {
+        let cursor = self.area.cursor();
+        let outer = self
+            .folded
+            .range(..cursor.line)
+            .copied()
+            .find(|&start| self.region_at(start).is_some_and(|r| r.hides(cursor.line)));
+        if let Some(start) = outer {
+            let nav = CursorNavigator::new(self.area.editor().rope());
+            self.area
+                .set_cursor_position(nav.from_line_grapheme(start, cursor.grapheme));
+        }
+    }
+
+    /// Line indices that are not hidden by folds, in order.
+    fn visible_lines(&self) -> Vec<usize> {
+        let mut visible = Vec::with_capacity(self.lines.len());
+        let mut line = 0;
+        while line < self.lines.len() {
+            visible.push(line);
+            line = match self.folded.contains(&line).then(|| self.region_at(line)) {
+                Some(Some(region)) => region.end + 1,
+                _ => line + 1,
+            };
+        }
+        visible
+    }
+
+    fn recompute_folds(&mut self) {
+        self.folds = match self.fold_strategy {
+            FoldStrategy::Indentation => indentation_folds(&self.lines),
+            FoldStrategy::Brackets => self.bracket_folds(),
+        };
+        let folds = &self.folds;
+        self.folded
+            .retain(|start| folds.binary_search_by_key(start, |r| r.start).is_ok());
+    }
+
+    fn bracket_folds(&self) -> Vec<FoldRegion> {
+        let mut stack: Vec<(char, usize)> = Vec::new();
+        let mut folds: Vec<FoldRegion> = Vec::new();
+        for (line, ch, _) in self.brackets() {
+            if let Some(close) = closing_bracket(ch) {
+                stack.push((close, line));
+            } else if let Some(pos) = stack.iter().rposition(|&(close, _)| close == ch) {
+                let (_, open_line) = stack[pos];
+                stack.truncate(pos);
+                if line > open_line + 1 {
+                    folds.push(FoldRegion {
+                        start: open_line,
+                        end: line - 1,
+                    });
+                }
+            }
+        }
+        // Keep the widest region per start line.
+        folds.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+        folds.dedup_by_key(|r| r.start);
+        folds
+    }
+
+    /// Every bracket outside strings and comments as `(line, char, byte)`.
+    fn brackets(&self) -> impl Iterator<Item = (usize, char, usize)> + '_ {
+        self.tokens
+            .lines()
+            .iter()
+            .zip(&self.lines)
+            .enumerate()
+            .flat_map(|(line, (token_line, text))| {
+                token_line
+                    .tokens
+                    .iter()
+                    .filter(|t| matches!(t.kind, TokenKind::Delimiter | TokenKind::Punctuation))
+                    .flat_map(move |t| {
+                        text.get(t.range.clone())
+                            .unwrap_or_default()
+                            .char_indices()
+                            .map(move |(i, ch)| (line, ch, t.range.start + i))
+                    })
+                    .filter(|&(_, ch, _)| is_bracket(ch))
+            })
+    }
+
+    /// The bracket at (or just before) the cursor and its match, as
+    /// `(line, byte)` pairs.
+    #[must_use]
+    pub fn bracket_match(&self) -> Option<[(usize, usize); 2]> {
+        let cursor = self.area.cursor();
+        let nav = CursorNavigator::new(self.area.editor().rope());
+        let line_start = nav.to_byte_index(nav.from_line_grapheme(cursor.line, 0));
+        let at = nav.to_byte_index(cursor) - line_start;
+
+        let brackets: Vec<(usize, char, usize)> = self.brackets().collect();
+        let index = brackets
+            .iter()
+            .position(|&(l, _, b)| l == cursor.line && b == at)
+            .or_else(|| {
+                brackets
+                    .iter()
+                    .position(|&(l, ch, b)| l == cursor.line && b + ch.len_utf8() == at && at > 0)
+            })?;
+        let (line, ch, byte) = brackets[index];
+
+        let mut depth = 0usize;
+        let found = if let Some(close) = closing_bracket(ch) {
+            brackets[index + 1..].iter().find(|&&(_, c, _)| {
+                if c == ch {
+                    depth += 1;
+                } else if c == close {
+                    if depth == 0 {
+                        return true;
+                    }
+                    depth -= 1;
+                }
+                false
+            })
+        } else {
+            let open = opening_bracket(ch)?;
+            brackets[..index].iter().rev().find(|&&(_, c, _)| {
+                if c == ch {
+                    depth += 1;
+                } else if c == open {
+                    if depth == 0 {
+                        return true;
+                    }
+                    depth -= 1;
+                }
+                false
+            })
+        };
+        found.map(|&(l, _, b)| [(line, byte), (l, b)])
+    }
+
+    // ── Event handling ─────────────────────────────────────────────
+
+    /// Handle a terminal event.
+    ///
+    /// Returns `true` if the state changed.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        let before = self.area.cursor();
+        let changed = match event {
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press || key.kind == KeyEventKind::Repeat =>
+            {
+                self.handle_key(key)
+            }
+            _ => self.area.handle_event(event),
+        };
+        if !changed {
+            return false;
+        }
+
+        self.sync();
+        let cursor = self.area.cursor();
+        if self.is_hidden(cursor.line) {
+            let vertical = matches!(
+                event,
+                Event::Key(KeyEvent {
+                    code: KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown,
+                    ..
+                })
+            );
+            if vertical {
+                self.skip_folds(before.line, cursor);
+            } else {
+                self.reveal_line(cursor.line);
+            }
+        }
+        true
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let alt = key.modifiers.contains(Modifiers::ALT);
+        let plain = !key
+            .modifiers
+            .intersects(Modifiers::CTRL | Modifiers::ALT | Modifiers::SUPER);
+        let inserting = self
+            .area
+            .vim_mode()
+            .is_none_or(|mode| mode == VimMode::Insert);
+
+        match key.code {
+            KeyCode::Char('[') if alt => self.fold(self.area.cursor().line),
+            KeyCode::Char(']') if alt => self.unfold(self.area.cursor().line),
+            KeyCode::Enter if plain && inserting && self.auto_indent => {
+                self.insert_indented_newline();
+                true
+            }
+            KeyCode::Tab if plain && inserting => {
+                let unit = self.indent_unit.clone();
+                self.area.insert_text(&unit);
+                true
+            }
+            KeyCode::Char(ch)
+                if plain && inserting && self.auto_indent && opening_bracket(ch).is_some() =>
+            {
+                self.dedent_current_line();
+                self.area.insert_char(ch);
+                true
+            }
+            _ => self.area.handle_event(&Event::Key(*key)),
+        }
+    }
+
+    /// Insert a newline that keeps the current indentation, adding a level
+    /// after an opening bracket. Between a bracket pair, the closing bracket
+    /// moves to its own line.
+    fn insert_indented_newline(&mut self) {
+        let cursor = self.area.cursor();
+        let line = self
+            .area
+            .editor()
+            .line_text(cursor.line)
+            .unwrap_or_default();
+        let split = graphemes(&line)
+            .take(cursor.grapheme)
+            .map(str::len)
+            .sum::<usize>();
+        let (before, after) = line.split_at(split);
+        let indent: String = before
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+
+        let opens = before
+            .trim_end()
+            .chars()
+            .next_back()
+            .and_then(closing_bracket);
+        let mut text = format!("\n{indent}");
+        if let Some(close) = opens {
+            text.push_str(&self.indent_unit);
+            if after.trim_start().starts_with(close) {
+                let inner = text.clone();
+                text.push('\n');
+                text.push_str(&indent);
+                self.area.insert_text(&text);
+                let nav = CursorNavigator::new(self.area.editor().rope());
+                let target = nav.from_line_grapheme(cursor.line + 1, graphemes(&inner).count() - 1);
+                self.area.set_cursor_position(target);
+                return;
+            }
+        }
+        self.area.insert_text(&text);
+    }
+
+    /// Remove one indent unit before the cursor if only whitespace precedes
+    /// it on the line.
+    fn dedent_current_line(&mut self) {
+        if self.area.selection().is_some_and(|sel| !sel.is_empty()) {
+            return;
+        }
+        let cursor = self.area.cursor();
+        let line = self
+            .area
+            .editor()
+            .line_text(cursor.line)
+            .unwrap_or_default();
+        let before: String = graphemes(&line).take(cursor.grapheme).collect();
+        if before.is_empty() || !before.chars().all(|c| c == ' ' || c == '\t') {
+            return;
+        }
+        let remove = if before.ends_with('\t') {
+            1
+        } else {
+            before
+                .chars()
+                .rev()
+                .take_while(|c| *c == ' ')
+                .count()
+                .min(self.indent_unit.chars().count().max(1))
+        };
+        for _ in 0..remove {
+            self.area.delete_backward();
+        }
+    }
+
+    /// After a vertical move landed inside a fold, continue past it in the
+    /// direction of travel.
+    fn skip_folds(&mut self, from_line: usize, cursor: CursorPosition) {
+        let Some(start) = self
+            .folded
+            .range(..cursor.line)
+            .copied()
+            .find(|&start| self.region_at(start).is_some_and(|r| r.hides(cursor.line)))
+        else {
+            return;
+        };
+        let end = self.region_at(start).map_or(start, |r| r.end);
+        let target = if cursor.line > from_line && end + 1 < self.lines.len() {
+            end + 1
+        } else {
+            start
+        };
+        let nav = CursorNavigator::new(self.area.editor().rope());
+        self.area
+            .set_cursor_position(nav.from_visual_col(target, cursor.visual_col));
+    }
+
+    // ── Token cache ────────────────────────────────────────────────
+
+    fn rebuild(&mut self) {
+        let editor = self.area.editor_mut();
+        editor.take_line_changes();
+        self.lines = (0..editor.line_count())
+            .map(|line| editor.line_text(line).unwrap_or_default())
+            .collect();
+        self.tokens = TokenizedText::from_lines(self.tokenizer.as_ref(), &self.lines);
+        self.recompute_folds();
+    }
+
+    /// Bring the token cache, folds and diagnostics up to date with the
+    /// text, re-reading and re-tokenizing only the edited block of lines.
+    fn sync(&mut self) {
+        let Some(change) = self.area.editor_mut().take_line_changes() else {
+            return;
+        };
+        let old_len = self.lines.len();
+        if change.start + change.removed > old_len {
+            self.rebuild();
+            return;
+        }
+        let editor = self.area.editor();
+        let mut fresh: Vec<String> = (change.start..change.start + change.inserted)
+            .map(|line| editor.line_text(line).unwrap_or_default())
+            .collect();
+
+        // Narrow the block to the lines whose text actually changed.
+        let old = &self.lines[change.start..change.start + change.removed];
+        let same_front = old.iter().zip(&fresh).take_while(|(a, b)| a == b).count();
+        let same_back = old[same_front..]
+            .iter()
+            .rev()
+            .zip(fresh[same_front..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        if same_front == old.len() && old.len() == fresh.len() {
+            return;
+        }
+        let prefix = change.start + same_front;
+        let removed = old.len() - same_front - same_back;
+        fresh.truncate(fresh.len() - same_back);
+        self.lines
+            .splice(prefix..prefix + removed, fresh.drain(same_front..));
+        let new_len = self.lines.len();
+        self.tokens
+            .splice_lines(self.tokenizer.as_ref(), &self.lines, prefix, removed);
+
+        // Shift line-anchored state below the edited block.
+        let edit_end = prefix + removed;
+        let moved = |line: usize| -> Option<usize> {
+            if line < prefix {
+                Some(line)
+            } else if line >= edit_end {
+                (line + new_len).checked_sub(old_len)
+            } else {
+                None
+            }
+        };
+        self.folded = self.folded.iter().filter_map(|&l| moved(l)).collect();
+        for diagnostic in &mut self.diagnostics {
+            diagnostic.line = moved(diagnostic.line)
+                .unwrap_or(prefix)
+                .min(new_len.saturating_sub(1));
+        }
+        self.recompute_folds();
+    }
+}
+
+impl Widget for CodeEditor {
+    fn render(&self, area: Rect, frame: &mut Frame) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let number_width = if self.show_line_numbers {
+            self.lines.len().max(1).to_string().len() + 1
+        } else {
+            0
+        };
+        // Diagnostic marker, line number, fold marker, separator.
+        let gutter_width = 1 + number_width + 2;
+        if usize::from(area.width) <= gutter_width {
+            return;
+        }
+        let text_x = area.x + gutter_width as u16;
+        let text_width = usize::from(area.width) - gutter_width;
+        let height = usize::from(area.height);
+
+        let visible = self.visible_lines();
+        let cursor = self.area.cursor();
+        let cursor_row = visible
+            .iter()
+            .rposition(|&line| line <= cursor.line)
+            .unwrap_or(0);
+
+        let mut top = self.scroll_top.get().min(visible.len().saturating_sub(1));
+        if cursor_row < top {
+            top = cursor_row;
+        } else if cursor_row >= top + height {
+            top = cursor_row + 1 - height;
+        }
+        self.scroll_top.set(top);
+
+        let mut left = self.scroll_left.get();
+        if cursor.visual_col < left {
+            left = cursor.visual_col;
+        } else if cursor.visual_col >= left + text_width {
+            left = cursor.visual_col + 1 - text_width;
+        }
+        self.scroll_left.set(left);
+
+        let focused = self.area.is_focused();
+        let editor = self.area.editor();
+        let nav = CursorNavigator::new(editor.rope());
+        let selections: Vec<(usize, usize)> = editor
+            .selections()
+            .iter()
+            .filter(|sel| !sel.is_empty())
+            .map(|sel| sel.byte_range(&nav))
+            .collect();
+        let brackets = if focused { self.bracket_match() } else { None };
+
+        for (row, &line) in visible.iter().skip(top).take(height).enumerate() {
+            let y = area.y + row as u16;
+            let mut base = self.theme.text;
+            if focused
+                && line == cursor.line
+                && let Some(style) = self.cursor_line_style
+            {
+                base = style.merge(&base);
+            }
+            fill_row(frame, area.x, y, area.width, base);
+
+            // Gutter.
+            let severity = self.diagnostics_at(line).map(|d| d.severity).min();
+            if let Some(severity) = severity {
+                let style = self.diagnostic_styles[severity.index()].merge(&base);
+                draw_char(frame, area.x, y, '●', style);
+            }
+            if self.show_line_numbers {
+                let number = format!("{:>width$}", line + 1, width = number_width - 1);
+                for (x, ch) in (area.x + 1..).zip(number.chars()) {
+                    draw_char(frame, x, y, ch, self.gutter_style.merge(&base));
+                }
+            }
+            if self.region_at(line).is_some() {
+                let marker = if self.is_folded(line) { '▸' } else { '▾' };
+                let x = area.x + 1 + number_width as u16;
+                draw_char(frame, x, y, marker, self.fold_marker_style.merge(&base));
+            }
+
+            // Text.
+            let text = &self.lines[line];
+            let line_start = nav.to_byte_index(nav.from_line_grapheme(line, 0));
+            let styles = self.line_styles(line, text);
+            let mut col = 0usize;
+            let mut byte = 0usize;
+            for grapheme in graphemes(text) {
+                let at = byte;
+                byte += grapheme.len();
+                let (grapheme, width) = if grapheme == "\t" {
+                    (" ", 1)
+                } else {
+                    (grapheme, grapheme_width(grapheme))
+                };
+                if width == 0 || grapheme == "\r" || grapheme == "\r\n" {
+                    continue;
+                }
+                let start_col = col;
+                col += width;
+                if start_col < left {
+                    continue;
+                }
+                if start_col - left + width > text_width {
+                    break;
+                }
+
+                let mut style = styles
+                    .iter()
+                    .rev()
+                    .find(|(range, _)| range.contains(&at))
+                    .map_or(self.theme.text, |(_, style)| *style)
+                    .merge(&base);
+                let absolute = line_start + at;
+                if selections
+                    .iter()
+                    .any(|&(start, end)| start <= absolute && absolute < end)
+                {
+                    style = self.selection_style.merge(&style);
+                }
+                if brackets.is_some_and(|pair| pair.contains(&(line, at))) {
+                    style = self.bracket_match_style.merge(&style);
+                }
+                let x = text_x + (start_col - left) as u16;
+                draw_grapheme(frame, x, y, grapheme, width, style);
+            }
+            if self.is_folded(line) && col >= left && col - left + 2 <= text_width {
+                let x = text_x + (col - left) as u16 + 1;
+                draw_char(frame, x, y, '⋯', self.fold_marker_style.merge(&base));
+            }
+        }
+
+        if focused {
+            let cursor_y = cursor_row.checked_sub(top).filter(|row| *row < height);
+            if let Some(row) = cursor_y
+                && visible.get(cursor_row) == Some(&cursor.line)
+            {
+                let x = text_x + (cursor.visual_col - left) as u16;
+                frame.set_cursor(Some((x, area.y + row as u16)));
+            }
+        }
+    }
+
+    fn is_essential(&self) -> bool {
+        true
+    }
+}
+
+impl CodeEditor {
+    /// Token styles for a line as `(byte range, style)` pairs.
+    fn line_styles(&self, line: usize, text: &str) -> Vec<(std::ops::Range<usize>, Style)> {
+        let Some(token_line) = self.tokens.lines().get(line) else {
+            return Vec::new();
+        };
+        token_line
+            .tokens
+            .iter()
+            .filter(|t| t.range.end <= text.len())
+            .map(|t| (t.range.clone(), self.theme.style_for(t.kind)))
+            .collect()
+    }
+}
+
+/// Split text into lines the way the editor's rope counts them: a trailing
+/// newline yields a final empty line.
+fn is_bracket(ch: char) -> bool {
+    matches!(ch, '(' | ')' | '[' | ']' | '{' | '}')
+}
+
+/// The closing bracket for an opening one.
+fn closing_bracket(ch: char) -> Option<char> {
+    match ch {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+/// The opening bracket for a closing one.
+fn opening_bracket(ch: char) -> Option<char> {
+    match ch {
+        ')' => Some('('),
+        ']' => Some('['),
+        '}' => Some('{'),
+        _ => None,
+    }
+}
+
+/// Indentation width in cells, or `None` for a blank line.
+fn indent_width(line: &str) -> Option<usize> {
+    let mut width = 0;
+    for ch in line.chars() {
+        match ch {
+            ' ' => width += 1,
+            '\t' => width += TAB_WIDTH,
+            '\r' => return None,
+            _ => return Some(width),
+        }
+    }
+    None
+}
+
+fn indentation_folds(lines: &[String]) -> Vec<FoldRegion> {
+    let indents: Vec<Option<usize>> = lines.iter().map(|l| indent_width(l)).collect();
+    let mut folds = Vec::new();
+    for (start, indent) in indents.iter().enumerate() {
+        let Some(indent) = *indent else {
+            continue;
+        };
+        let mut end = start;
+        for (line, deeper) in indents.iter().enumerate().skip(start + 1) {
+            match deeper {
+                None => {}
+                Some(d) if *d > indent => end = line,
+                Some(_) => break,
+            }
+        }
+        if end > start {
+            folds.push(FoldRegion { start, end });
+        }
+    }
+    folds
+}
+
+fn fill_row(frame: &mut Frame, x: u16, y: u16, width: u16, style: Style) {
+    for dx in 0..width {
+        draw_char(frame, x + dx, y, ' ', style);
+    }
+}
+
+fn draw_char(frame: &mut Frame, x: u16, y: u16, ch: char, style: Style) {
+    let mut cell = Cell::from_char(ch);
+    apply_style(&mut cell, style);
+    frame.buffer.set_fast(x, y, cell);
+}
+
+fn draw_grapheme(frame: &mut Frame, x: u16, y: u16, grapheme: &str, width: usize, style: Style) {
+    let content = if width > 1 || grapheme.chars().count() > 1 {
+        let id = frame.intern_with_width(grapheme, width.min(u8::MAX as usize) as u8);
+        CellContent::from_grapheme(id)
+    } else if let Some(c) = grapheme.chars().next() {
+        CellContent::from_char(c)
+    } else {
+        return;
+    };
+    let mut cell = Cell::new(content);
+    apply_style(&mut cell, style);
+    frame.buffer.set_fast(x, y, cell);
+}
+
+/// Apply a style to a cell using merge semantics.
+fn apply_style(cell: &mut Cell, style: Style) {
+    if let Some(fg) = style.fg {
+        cell.fg = fg;
+    }
+    if let Some(bg) = style.bg {
+        match bg.a() {
+            0 => {}
+            255 => cell.bg = bg,
+            _ => cell.bg = bg.over(cell.bg),
+        }
+    }
+    if let Some(attrs) = style.attrs {
+        let cell_flags: ftui_render::cell::StyleFlags = attrs.into();
+        cell.attrs = cell.attrs.merged_flags(cell_flags);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::rust_tokenizer;
+    use ftui_render::grapheme_pool::GraphemePool;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code))
+    }
+
+    fn editor_at(text: &str, line: usize, grapheme: usize) -> CodeEditor {
+        let mut editor = CodeEditor::new(rust_tokenizer()).with_text(text);
+        editor.set_cursor_position(CursorPosition::new(line, grapheme, grapheme));
+        editor
+    }
+
+    fn lines(editor: &CodeEditor) -> Vec<String> {
+        editor.text().split('\n').map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn edits_retokenize_incrementally() {
+        let mut editor = editor_at("fn a() {\n    let x = 1;\n}\nfn b() {}", 0, 0);
+        editor.set_diagnostics([Diagnostic::new(3, DiagnosticSeverity::Error, "bad")]);
+
+        assert!(editor.handle_event(&key(KeyCode::Enter)));
+        assert_eq!(editor.diagnostics()[0].line, 4);
+        editor.set_cursor_position(CursorPosition::new(0, 0, 0));
+        editor.handle_event(&key(KeyCode::Char('/')));
+        editor.handle_event(&key(KeyCode::Char('*')));
+
+        let text = lines(&editor);
+        let expected: Vec<&str> = text.iter().map(String::as_str).collect();
+        let fresh = TokenizedText::from_lines(&rust_tokenizer(), &expected);
+        assert_eq!(editor.tokens().lines(), fresh.lines());
+        assert!(
+            editor.tokens().lines()[2]
+                .tokens
+                .iter()
+                .all(|t| t.kind.is_comment() || t.kind == TokenKind::Whitespace)
+        );
+    }
+
+    #[test]
+    fn joining_lines_keeps_tokens_and_folds_in_step() {
+        let mut editor = editor_at("fn a() {\n    one();\n}\nlet s = \"x\";\nfn b() {}", 4, 0);
+        assert!(editor.fold(1));
+
+        editor.handle_event(&key(KeyCode::Backspace));
+        assert_eq!(
+            editor.text(),
+            "fn a() {\n    one();\n}\nlet s = \"x\";fn b() {}"
+        );
+        assert!(editor.is_folded(0));
+
+        editor.set_cursor_position(CursorPosition::new(3, 8, 8));
+        editor.handle_event(&key(KeyCode::Enter));
+        let text = lines(&editor);
+        let fresh = TokenizedText::from_lines(&rust_tokenizer(), &text);
+        assert_eq!(editor.tokens().lines(), fresh.lines());
+        assert!(editor.is_folded(0));
+    }
+
+    #[test]
+    fn bracket_folds_hide_lines_and_skip_vertically() {
+        let mut editor = editor_at("fn main() {\n    one();\n    two();\n}\n", 0, 0);
+        assert_eq!(editor.fold_regions(), [FoldRegion { start: 0, end: 2 }]);
+
+        assert!(editor.fold(1));
+        assert!(editor.is_folded(0));
+        assert!(editor.is_hidden(1) && editor.is_hidden(2) && !editor.is_hidden(3));
+        assert_eq!(editor.visible_lines(), [0, 3, 4]);
+
+        editor.handle_event(&key(KeyCode::Down));
+        assert_eq!(editor.cursor().line, 3);
+        editor.handle_event(&key(KeyCode::Up));
+        assert_eq!(editor.cursor().line, 0);
+
+        let unfold = KeyEvent::new(KeyCode::Char(']')).with_modifiers(Modifiers::ALT);
+        assert!(editor.handle_event(&Event::Key(unfold)));
+        assert!(!editor.is_folded(0));
+    }
+
+    #[test]
+    fn indentation_folds() {
+        let editor = CodeEditor::new(rust_tokenizer())
+            .with_fold_strategy(FoldStrategy::Indentation)
+            .with_text("a:\n  b\n\n  c\nd");
+        assert_eq!(editor.fold_regions(), [FoldRegion { start: 0, end: 3 }]);
+    }
+
+    #[test]
+    fn enter_and_closing_bracket_adjust_indentation() {
+        let mut editor = editor_at("fn f() {}", 0, 8);
+        editor.handle_event(&key(KeyCode::Enter));
+        assert_eq!(editor.text(), "fn f() {\n    \n}");
+        assert_eq!((editor.cursor().line, editor.cursor().grapheme), (1, 4));
+
+        editor.handle_event(&key(KeyCode::Enter));
+        editor.handle_event(&key(KeyCode::Char('}')));
+        assert_eq!(editor.text(), "fn f() {\n    \n}\n}");
+    }
+
+    #[test]
+    fn bracket_match_and_render() {
+        let mut editor = editor_at("f(a[1])", 0, 1).with_focus(true);
+        assert_eq!(editor.bracket_match(), Some([(0, 1), (0, 6)]));
+        editor.set_cursor_position(CursorPosition::new(0, 7, 7));
+        assert_eq!(editor.bracket_match(), Some([(0, 6), (0, 1)]));
+        editor.set_diagnostics([Diagnostic::new(0, DiagnosticSeverity::Warning, "w")]);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(20, 3, &mut pool);
+        editor.render(Rect::from_size(20, 3), &mut frame);
+
+        let char_at = |x| frame.buffer.get(x, 0).and_then(|c| c.content.as_char());
+        assert_eq!(char_at(0), Some('●'));
+        assert_eq!(char_at(1), Some('1'));
+        assert_eq!(char_at(5), Some('f'));
+        assert_eq!(char_at(11), Some(')'));
+        assert_eq!(frame.cursor_position, Some((12, 0)));
+
+        // Too narrow for the gutter: draws nothing and does not panic.
+        editor.render(Rect::from_size(3, 3), &mut frame);
+    }
+}
@@ -0,0 +1,923 @@
+#![forbid(unsafe_code)]
+
+//! Read-only text source for files too large to load into memory.
+//!
+//! [`HugeFile`] opens a file without reading it up front. A background
+//! thread scans it in blocks and records where each line starts; lines are
+//! read on demand through a small block cache. It implements
+//! [`LineSource`], so `VirtualizedList::from_lines`,
+//! `LogViewer::from_source` and `TextArea::from_source` in `ftui-widgets`
+//! can display a multi-gigabyte log while it is still being indexed.
+//!
+//! Search streams through the file in blocks, independent of the index,
+//! and reports progress as it goes. Use [`HugeFile::search`] to search on
+//! the calling thread, or [`HugeFile::spawn_search`] to search in the
+//! background and poll a [`SearchHandle`] from the event loop.
+//!
+//! The file is treated as a snapshot: content appended after
+//! [`HugeFile::open`] is ignored.
+//!
+//! # Example
+//! ```ignore
+//! use ftui_extras::huge_file::{HugeFile, SearchOptions};
+//! use ftui_text::LineSource;
+//!
+//! let file = HugeFile::open("/var/log/huge.log")?;
+//! // Render what is indexed so far.
+//! for idx in 0..file.line_count().min(50) {
+//!     println!("{}", file.line_text(idx).unwrap_or_default());
+//! }
+//! let search = file.spawn_search("ERROR", SearchOptions::default());
+//! // ... later, on a tick:
+//! let progress = search.progress();
+//! ```
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::thread::{self, JoinHandle};
+
+use ftui_text::LineSource;
+
+/// Bytes read per block by the indexer, the search and the line cache.
+pub const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Number of blocks kept by the line cache.
+const CACHED_BLOCKS: usize = 32;
+
+/// Default cap on the bytes returned for a single line.
+pub const DEFAULT_MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// Line-start offsets shared with the indexing thread.
+#[derive(Debug, Default)]
+struct LineIndex {
+    /// Start offset of every line found so far; begins with 0.
+    starts: RwLock<Vec<u64>>,
+    /// Bytes scanned so far.
+    scanned: AtomicU64,
+    complete: AtomicBool,
+    cancelled: AtomicBool,
+    error: Mutex<Option<io::Error>>,
+}
+
+impl LineIndex {
+    fn new() -> Self {
+        Self {
+            starts: RwLock::new(vec![0]),
+            ..Self::default()
+        }
+    }
+
+    fn build(&self, path: &Path, len: u64) {
+        if let Err(err) = self.scan(path, len) {
+            *self.error.lock().unwrap_or_else(PoisonError::into_inner) = Some(err);
+        }
+        self.complete.store(true, Ordering::Release);
+    }
+
+    fn scan(&self, path: &Path, len: u64) -> io::Result<()> {
+        let mut file = File::open(path)?.take(len);
+        let mut buf = vec![0; BLOCK_SIZE];
+        let mut offset = 0u64;
+        let mut found = Vec::new();
+        while !self.cancelled.load(Ordering::Relaxed) {
+            let n = read_full(&mut file, &mut buf)?;
+            if n == 0 {
+                break;
+            }
+            found.extend(
+                buf[..n]
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &b)| b == b'\n')
+                    .map(|(i, _)| offset + i as u64 + 1),
+            );
+            offset += n as u64;
+            if !found.is_empty() {
+                self.starts
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .append(&mut found);
+            }
+            self.scanned.store(offset, Ordering::Release);
+        }
+        Ok(())
+    }
+}
+
+/// Most-recently-used cache of file blocks.
+#[derive(Debug)]
+struct BlockReader {
+    file: File,
+    /// `(block number, data)`, most recently used last.
+    blocks: VecDeque<(u64, Arc<[u8]>)>,
+}
+
+impl BlockReader {
+    fn block(&mut self, number: u64) -> io::Result<Arc<[u8]>> {
+        if let Some(pos) = self.blocks.iter().position(|(n, _)| *n == number) {
+            let entry = self.blocks.remove(pos).expect("position is in range");
+            let data = Arc::clone(&entry.1);
+            self.blocks.push_back(entry);
+            return Ok(data);
+        }
+        self.file
+            .seek(SeekFrom::Start(number * BLOCK_SIZE as u64))?;
+        let mut buf = vec![0; BLOCK_SIZE];
+        let n = read_full(&mut self.file, &mut buf)?;
+        buf.truncate(n);
+        let data: Arc<[u8]> = buf.into();
+        if self.blocks.len() >= CACHED_BLOCKS {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back((number, Arc::clone(&data)));
+        Ok(data)
+    }
+
+    /// Read bytes `start..end`, stopping early at end of file.
+    fn read_range(&mut self, start: u64, end: u64) -> io::Result<Vec<u8>> {
+        let mut out = Vec::with_capacity((end - start) as usize);
+        let mut pos = start;
+        while pos < end {
+            let number = pos / BLOCK_SIZE as u64;
+            let block = self.block(number)?;
+            let from = (pos - number * BLOCK_SIZE as u64) as usize;
+            if from >= block.len() {
+                break;
+            }
+            let to = block.len().min(from + (end - pos) as usize);
+            out.extend_from_slice(&block[from..to]);
+            pos += (to - from) as u64;
+        }
+        Ok(out)
+    }
+}
+
+/// A read-only, lazily indexed view of a large text file.
+pub struct HugeFile {
+    path: PathBuf,
+    len: u64,
+    max_line_bytes: usize,
+    index: Arc<LineIndex>,
+    indexer: Mutex<Option<JoinHandle<()>>>,
+    reader: Mutex<BlockReader>,
+}
+
+impl fmt::Debug for HugeFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HugeFile")
+            .field("path", &self.path)
+            .field("len", &self.len)
+            .field("indexed_lines", &self.line_count())
+            .field("complete", &self.is_complete())
+            .finish_non_exhaustive()
+    }
+}
+
+impl HugeFile {
+    /// Open a file and start indexing its lines in the background.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path)?;
+        let len = file.metadata()?.len();
+
+        let index = Arc::new(LineIndex::new());
+        let indexer = {
+            let index = Arc::clone(&index);
+            let path = path.clone();
+            thread::Builder::new()
+                .name("ftui-huge-file-index".into())
+                .spawn(move || index.build(&path, len))?
+        };
+
+        Ok(Self {
+            path,
+            len,
+            max_line_bytes: DEFAULT_MAX_LINE_BYTES,
+            index,
+            indexer: Mutex::new(Some(indexer)),
+            reader: Mutex::new(BlockReader {
+                file,
+                blocks: VecDeque::with_capacity(CACHED_BLOCKS),
+            }),
+        })
+    }
+
+    /// Cap the bytes returned for a single line (builder). Longer lines are
+    /// truncated. Defaults to [`DEFAULT_MAX_LINE_BYTES`].
+    #[must_use]
+    pub fn with_max_line_bytes(mut self, max: usize) -> Self {
+        self.max_line_bytes = max.max(1);
+        self
+    }
+
+    /// Path of the file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// File size in bytes when it was opened.
+    #[must_use]
+    pub fn len_bytes(&self) -> u64 {
+        self.len
+    }
+
+    /// Fraction of the file indexed so far, from 0.0 to 1.0.
+    #[must_use]
+    pub fn index_progress(&self) -> f64 {
+        if self.is_complete() || self.len == 0 {
+            return 1.0;
+        }
+        self.index.scanned.load(Ordering::Acquire) as f64 / self.len as f64
+    }
+
+    /// Block until indexing finishes, returning any I/O error it hit.
+    pub fn wait_for_index(&self) -> io::Result<()> {
+        let handle = self
+            .indexer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(handle) = handle
+            && handle.join().is_err()
+        {
+            return Err(io::Error::other("line indexer panicked"));
+        }
+        match self
+            .index
+            .error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Byte offset where line `idx` starts, if indexed.
+    #[must_use]
+    pub fn line_offset(&self, idx: usize) -> Option<u64> {
+        self.starts().get(idx).copied()
+    }
+
+    /// Read line `idx` without its terminator.
+    ///
+    /// Returns `Ok(None)` if the line is out of range or not indexed yet.
+    pub fn read_line(&self, idx: usize) -> io::Result<Option<String>> {
+        let complete = self.is_complete();
+        let (start, end) = {
+            let starts = self.starts();
+            let Some(&start) = starts.get(idx) else {
+                return Ok(None);
+            };
+            match starts.get(idx + 1) {
+                Some(&next) => (start, next - 1),
+                None if complete => (start, self.len),
+                None => return Ok(None),
+            }
+        };
+
+        let end = end.min(start + self.max_line_bytes as u64);
+        let mut bytes = self
+            .reader
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read_range(start, end)?;
+        if bytes.last() == Some(&b'\r') {
+            bytes.pop();
+        }
+        Ok(Some(match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        }))
+    }
+
+    /// Search the file on the calling thread.
+    ///
+    /// `on_progress` is called after every block; returning
+    /// [`ControlFlow::Break`] stops the search early. Returns the matches
+    /// found up to that point.
+    pub fn search(
+        &self,
+        query: &str,
+        options: SearchOptions,
+        mut on_progress: impl FnMut(&SearchProgress) -> ControlFlow<()>,
+    ) -> io::Result<Vec<FileMatch>> {
+        let mut matches = Vec::new();
+        stream_search(&self.path, self.len, query, options, |progress, found| {
+            matches.extend(found);
+            on_progress(&progress)
+        })?;
+        Ok(matches)
+    }
+
+    /// Search the file on a background thread.
+    #[must_use]
+    pub fn spawn_search(&self, query: &str, options: SearchOptions) -> SearchHandle {
+        let state = Arc::new(SearchState {
+            progress: Mutex::new(SearchProgress {
+                bytes_scanned: 0,
+                total_bytes: self.len,
+                matches: 0,
+            }),
+            ..SearchState::default()
+        });
+        let path = self.path.clone();
+        let len = self.len;
+        let query = query.to_owned();
+        let shared = Arc::clone(&state);
+        let thread = thread::spawn(move || {
+            let result = stream_search(&path, len, &query, options, |progress, found| {
+                shared
+                    .matches
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .extend(found);
+                *shared
+                    .progress
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner) = progress;
+                if shared.cancelled.load(Ordering::Relaxed) {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+            shared.done.store(true, Ordering::Release);
+            result
+        });
+        SearchHandle {
+            state,
+            thread: Some(thread),
+        }
+    }
+
+    fn starts(&self) -> std::sync::RwLockReadGuard<'_, Vec<u64>> {
+        self.index
+            .starts
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl LineSource for HugeFile {
+    /// Lines indexed so far. While indexing, the last (possibly partial)
+    /// line is not counted.
+    fn line_count(&self) -> usize {
+        let complete = self.is_complete();
+        let starts = self.starts().len();
+        if complete { starts } else { starts - 1 }
+    }
+
+    /// Line text, or `None` if not indexed yet or unreadable. Invalid UTF-8
+    /// is replaced with U+FFFD.
+    fn line_text(&self, idx: usize) -> Option<Cow<'_, str>> {
+        self.read_line(idx).ok().flatten().map(Cow::Owned)
+    }
+
+    fn is_complete(&self) -> bool {
+        self.index.complete.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for HugeFile {
+    fn drop(&mut self) {
+        self.index.cancelled.store(true, Ordering::Relaxed);
+        let handle = self
+            .indexer
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(handle) = handle {
+            let _ = handle.join();
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Search
+// ---------------------------------------------------------------------------
+
+/// Options for searching a [`HugeFile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Match case exactly. When `false`, ASCII letters match either case.
+    pub case_sensitive: bool,
+    /// Stop after this many matches.
+    pub max_matches: Option<usize>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            case_sensitive: true,
+            max_matches: None,
+        }
+    }
+}
+
+/// A match found by a file search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileMatch {
+    /// Line index (0-based).
+    pub line: usize,
+    /// Byte offset of the match within its line.
+    pub column: usize,
+    /// Byte offset of the match within the file.
+    pub offset: u64,
+}
+
+/// How far a search has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchProgress {
+    pub bytes_scanned: u64,
+    pub total_bytes: u64,
+    /// Matches found so far.
+    pub matches: usize,
+}
+
+impl SearchProgress {
+    /// Fraction of the file scanned, from 0.0 to 1.0.
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            self.bytes_scanned as f64 / self.total_bytes as f64
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SearchState {
+    progress: Mutex<SearchProgress>,
+    matches: Mutex<Vec<FileMatch>>,
+    cancelled: AtomicBool,
+    done: AtomicBool,
+}
+
+/// A search running on a background thread.
+///
+/// Dropping the handle cancels the search.
+#[derive(Debug)]
+pub struct SearchHandle {
+    state: Arc<SearchState>,
+    thread: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl SearchHandle {
+    /// Current progress.
+    #[must_use]
+    pub fn progress(&self) -> SearchProgress {
+        *self
+            .state
+            .progress
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Matches found so far.
+    #[must_use]
+    pub fn matches(&self) -> Vec<FileMatch> {
+        self.state
+            .matches
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Whether the search has finished, been cancelled or failed.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.state.done.load(Ordering::Acquire)
+    }
+
+    /// Ask the search to stop after the current block.
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Wait for the search to finish and return all matches.
+    pub fn wait(mut self) -> io::Result<Vec<FileMatch>> {
+        if let Some(thread) = self.thread.take() {
+            thread
+                .join()
+                .map_err(|_| io::Error::other("search thread panicked"))??;
+        }
+        Ok(std::mem::take(
+            &mut *self
+                .state
+                .matches
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        ))
+    }
+}
+
+impl Drop for SearchHandle {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Scan `path` for `query` line by line, calling `on_block` with the
+/// progress and new matches after every block.
+fn stream_search(
+    path: &Path,
+    len: u64,
+    query: &str,
+    options: SearchOptions,
+    mut on_block: impl FnMut(SearchProgress, Vec<FileMatch>) -> ControlFlow<()>,
+) -> io::Result<()> {
+    let mut progress = SearchProgress {
+        bytes_scanned: 0,
+        total_bytes: len,
+        matches: 0,
+    };
+    let limit = options.max_matches.unwrap_or(usize::MAX);
+    if query.is_empty() || limit == 0 {
+        progress.bytes_scanned = len;
+        let _ = on_block(progress, Vec::new());
+        return Ok(());
+    }
+    let needle = if options.case_sensitive {
+        query.as_bytes().to_vec()
+    } else {
+        query.as_bytes().to_ascii_lowercase()
+    };
+
+    let mut file = File::open(path)?.take(len);
+    let mut buf = vec![0; BLOCK_SIZE];
+    // Unterminated tail of the previous block, capped at one block: beyond
+    // that only the bytes a match could still start in are kept.
+    let mut pending: Vec<u8> = Vec::new();
+    // Bytes of the current line already dropped from the front of `pending`.
+    let mut dropped = 0usize;
+    let mut line = 0usize;
+    let mut line_start = 0u64;
+
+    loop {
+        let n = read_full(&mut file, &mut buf)?;
+        let at_end = n == 0;
+        pending.extend_from_slice(&buf[..n]);
+        if !options.case_sensitive {
+            let new = pending.len() - n;
+            pending[new..].make_ascii_lowercase();
+        }
+
+        let mut found = Vec::new();
+        let mut consumed = 0;
+        while progress.matches + found.len() < limit {
+            let rest = &pending[consumed..];
+            let remaining = limit - progress.matches;
+            let (text, next) = match rest.iter().position(|&b| b == b'\n') {
+                Some(eol) => (&rest[..eol], eol + 1),
+                None if at_end && !rest.is_empty() => (rest, rest.len()),
+                None => {
+                    if rest.len() > BLOCK_SIZE {
+                        let from = find_all(
+                            rest, &needle, line, line_start, dropped, remaining, &mut found,
+                        );
+                        let skip = from
+                            .max((rest.len() + 1).saturating_sub(needle.len()))
+                            .min(rest.len());
+                        dropped += skip;
+                        consumed += skip;
+                    }
+                    break;
+                }
+            };
+            find_all(
+                text, &needle, line, line_start, dropped, remaining, &mut found,
+            );
+            consumed += next;
+            line += 1;
+            line_start += (dropped + next) as u64;
+            dropped = 0;
+        }
+        pending.drain(..consumed);
+
+        progress.bytes_scanned += n as u64;
+        progress.matches += found.len();
+        let stop = progress.matches >= limit;
+        if at_end || stop {
+            let _ = on_block(progress, found);
+            return Ok(());
+        }
+        if on_block(progress, found).is_break() {
+            return Ok(());
+        }
+    }
+}
+
+/// Push the non-overlapping matches of `needle` in `text`, part of `line`
+/// starting `column` bytes into it, until `found` holds `limit` matches.
+/// Returns the offset in `text` where the next match could start.
+fn find_all(
+    text: &[u8],
+    needle: &[u8],
+    line: usize,
+    line_start: u64,
+    column: usize,
+    limit: usize,
+    found: &mut Vec<FileMatch>,
+) -> usize {
+    let mut from = 0;
+    while found.len() < limit
+        && let Some(pos) = find(&text[from..], needle)
+    {
+        let at = column + from + pos;
+        found.push(FileMatch {
+            line,
+            column: at,
+            offset: line_start + at as u64,
+        });
+        from += pos + needle.len();
+    }
+    from
+}
+
+/// Position of the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let first = *needle.first()?;
+    let last_start = haystack.len().checked_sub(needle.len())?;
+    let mut from = 0;
+    while from <= last_start {
+        let pos = from
+            + haystack[from..=last_start]
+                .iter()
+                .position(|&b| b == first)?;
+        if haystack[pos..].starts_with(needle) {
+            return Some(pos);
+        }
+        from = pos + 1;
+    }
+    None
+}
+
+/// Read until `buf` is full or the reader is exhausted.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::atomic::AtomicUsize;
+
+    /// A temporary file removed on drop.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(contents: &[u8]) -> Self {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let name = format!(
+                "ftui-huge-file-{}-{}.txt",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            );
+            let path = std::env::temp_dir().join(name);
+            File::create(&path)
+                .and_then(|mut f| f.write_all(contents))
+                .expect("write temp file");
+            Self(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn indexes_lines_like_a_rope() {
+        let tmp = TempFile::new(b"alpha\r\nbeta\n\ngamma");
+        let file = HugeFile::open(&tmp.0).unwrap();
+        file.wait_for_index().unwrap();
+
+        assert!(file.is_complete());
+        assert_eq!(file.index_progress(), 1.0);
+        assert_eq!(file.line_count(), 4);
+        assert_eq!(file.line_text(0).as_deref(), Some("alpha"));
+        assert_eq!(file.line_text(1).as_deref(), Some("beta"));
+        assert_eq!(file.line_text(2).as_deref(), Some(""));
+        assert_eq!(file.line_text(3).as_deref(), Some("gamma"));
+        assert_eq!(file.line_text(4), None);
+        assert_eq!(file.line_offset(3), Some(13));
+
+        let empty = TempFile::new(b"");
+        let file = HugeFile::open(&empty.0).unwrap();
+        file.wait_for_index().unwrap();
+        assert_eq!(file.line_count(), 1);
+        assert_eq!(file.line_text(0).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn lines_span_blocks_and_are_capped() {
+        let mut contents = vec![b'x'; BLOCK_SIZE + 10];
+        contents.extend_from_slice(b"\nshort\n");
+        let tmp = TempFile::new(&contents);
+        let file = HugeFile::open(&tmp.0).unwrap();
+        file.wait_for_index().unwrap();
+        assert_eq!(file.line_text(0).map(|l| l.len()), Some(BLOCK_SIZE + 10));
+        assert_eq!(file.line_text(1).as_deref(), Some("short"));
+
+        let file = file.with_max_line_bytes(4);
+        assert_eq!(file.line_text(0).as_deref(), Some("xxxx"));
+    }
+
+    #[test]
+    fn search_streams_with_progress() {
+        let mut contents = Vec::new();
+        for i in 0..20_000 {
+            contents.extend_from_slice(
+                format!("line {i} {}\n", if i % 5000 == 0 { "Error" } else { "ok" }).as_bytes(),
+            );
+        }
+        let tmp = TempFile::new(&contents);
+        let file = HugeFile::open(&tmp.0).unwrap();
+
+        let mut updates = Vec::new();
+        let matches = file
+            .search(
+                "error",
+                SearchOptions {
+                    case_sensitive: false,
+                    max_matches: None,
+                },
+                |p| {
+                    updates.push(*p);
+                    ControlFlow::Continue(())
+                },
+            )
+            .unwrap();
+        let lines: Vec<usize> = matches.iter().map(|m| m.line).collect();
+        assert_eq!(lines, [0, 5000, 10000, 15000]);
+        assert_eq!(matches[1].column, "line 5000 ".len());
+        assert!(updates.len() > 1);
+        assert!(
+            updates
+                .windows(2)
+                .all(|w| w[0].bytes_scanned <= w[1].bytes_scanned)
+        );
+        assert_eq!(updates.last().map(SearchProgress::fraction), Some(1.0));
+
+        let exact = file.search("error", SearchOptions::default(), |_| {
+            ControlFlow::Continue(())
+        });
+        assert!(exact.unwrap().is_empty());
+
+        let stopped = file
+            .search("ok", SearchOptions::default(), |_| ControlFlow::Break(()))
+            .unwrap();
+        assert!(!stopped.is_empty() && stopped.len() < 19_996);
+    }
+
+    #[test]
+    fn search_caps_unterminated_lines() {
+        let len = 5 * BLOCK_SIZE;
+        let mut contents = vec![b'x'; len];
+        let columns: Vec<usize> = (1..5).map(|k| k * BLOCK_SIZE - 3).collect();
+        for &column in &columns {
+            contents[column..column + 6].copy_from_slice(b"needle");
+        }
+        contents.extend_from_slice(b"\nxneedle");
+        let tmp = TempFile::new(&contents);
+        let file = HugeFile::open(&tmp.0).unwrap();
+
+        let matches = file
+            .search(
+                "NEEDLE",
+                SearchOptions {
+                    case_sensitive: false,
+                    max_matches: None,
+                },
+                |_| ControlFlow::Continue(()),
+            )
+            .unwrap();
+        let mut expected: Vec<FileMatch> = columns
+            .iter()
+            .map(|&column| FileMatch {
+                line: 0,
+                column,
+                offset: column as u64,
+            })
+            .collect();
+        expected.push(FileMatch {
+            line: 1,
+            column: 1,
+            offset: len as u64 + 2,
+        });
+        assert_eq!(matches, expected);
+
+        let overlapping = TempFile::new(&vec![b'a'; 3 * BLOCK_SIZE + 1]);
+        let file = HugeFile::open(&overlapping.0).unwrap();
+        let matches = file
+            .search(
+                "aa",
+                SearchOptions::default(),
+                |_| ControlFlow::Continue(()),
+            )
+            .unwrap();
+        assert_eq!(matches.len(), 3 * BLOCK_SIZE / 2);
+        assert!(matches.iter().enumerate().all(|(i, m)| m.column == 2 * i));
+    }
+
+    #[test]
+    fn background_search_finishes() {
+        let tmp = TempFile::new(b"a needle\nnothing\nneedle needle\n");
+        let file = HugeFile::open(&tmp.0).unwrap();
+        let search = file.spawn_search("needle", SearchOptions::default());
+        let matches = search.wait().unwrap();
+        let found: Vec<(usize, usize)> = matches.iter().map(|m| (m.line, m.column)).collect();
+        assert_eq!(found, [(0, 2), (2, 0), (2, 7)]);
+        assert_eq!(matches[2].offset, 24);
+
+        let limited = file.spawn_search(
+            "needle",
+            SearchOptions {
+                max_matches: Some(2),
+                ..SearchOptions::default()
+            },
+        );
+        assert_eq!(limited.wait().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn renders_through_log_viewer() {
+        use ftui_core::geometry::Rect;
+        use ftui_render::frame::Frame;
+        use ftui_render::grapheme_pool::GraphemePool;
+        use ftui_widgets::StatefulWidget;
+        use ftui_widgets::log_viewer::{LogViewer, LogViewerState};
+
+        let contents: String = (0..10_000).map(|i| format!("line {i}\n")).collect();
+        let tmp = TempFile::new(contents.as_bytes());
+        let file = HugeFile::open(&tmp.0).unwrap();
+        file.wait_for_index().unwrap();
+
+        let mut viewer = LogViewer::from_source(Arc::new(file));
+        assert_eq!(viewer.line_count(), 10_001);
+        assert_eq!(viewer.search("line 9998"), 1);
+        let mut state = LogViewerState::default();
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(40, 2, &mut pool);
+        viewer.render(Rect::new(0, 0, 40, 2), &mut frame, &mut state);
+        let row: String = (0..9)
+            .filter_map(|x| frame.buffer.get(x, 0).and_then(|c| c.content.as_char()))
+            .collect();
+        assert_eq!(row, "line 9998");
+    }
+
+    #[test]
+    fn renders_through_virtualized_list() {
+        use ftui_core::geometry::Rect;
+        use ftui_render::frame::Frame;
+        use ftui_render::grapheme_pool::GraphemePool;
+        use ftui_widgets::StatefulWidget;
+        use ftui_widgets::virtualized::{VirtualizedList, VirtualizedListState};
+
+        let contents: String = (0..10_000).map(|i| format!("line {i}\r\n")).collect();
+        let tmp = TempFile::new(contents.as_bytes());
+        let file = HugeFile::open(&tmp.0).unwrap();
+        file.wait_for_index().unwrap();
+
+        let mut state = VirtualizedListState::new();
+        state.select(Some(9_998));
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(12, 3, &mut pool);
+        VirtualizedList::from_lines(&file)
+            .show_scrollbar(false)
+            .render(Rect::new(0, 0, 12, 3), &mut frame, &mut state);
+
+        let rows: Vec<String> = (0..3)
+            .map(|y| {
+                (0..12)
+                    .filter_map(|x| frame.buffer.get(x, y)?.content.as_char())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_owned()
+            })
+            .collect();
+        assert_eq!(rows, ["line 9996", "line 9997", "line 9998"]);
+    }
+}
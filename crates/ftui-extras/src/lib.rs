@@ -32,8 +32,11 @@
 //! | `logging` | [`logging`] | Tracing subscriber for TUI logging |
 //! | `markdown` | [`markdown`] | Markdown to styled text rendering |
 //! | `pty-capture` | `pty_capture` | PTY session capture |
+//! | `screen-selection` | [`screen_selection`] | Mouse text selection and copy over the rendered screen |
 //! | `stopwatch` | [`stopwatch`] | Stopwatch timing utility |
 //! | `syntax` | [`syntax`] | Syntax highlighting spans |
+//! | `code-editor` | [`code_editor`] | Highlighted, foldable code editor widget (depends on syntax) |
+//! | `huge-file` | [`huge_file`] | Lazily indexed read-only source for multi-gigabyte files |
 //! | `timer` | [`timer`] | Countdown timer utility |
 //! | `traceback` | [`traceback`] | Error/stacktrace display |
 //! | `theme` | [`theme`] | Color themes + palette tokens |
@@ -116,9 +119,17 @@ pub mod markdown;
 #[cfg(feature = "pty-capture")]
 pub mod pty_capture;
 
+#[cfg(feature = "screen-selection")]
+pub mod screen_selection;
+
 #[cfg(feature = "syntax")]
 pub mod syntax;
 
+#[cfg(feature = "code-editor")]
+pub mod code_editor;
+#[cfg(feature = "huge-file")]
+pub mod huge_file;
+
 #[cfg(feature = "filepicker")]
 pub mod filepicker;
 
@@ -0,0 +1,561 @@
+#![forbid(unsafe_code)]
+
+//! Application-level text selection over the rendered screen.
+//!
+//! When mouse capture is enabled the terminal's native selection is no longer
+//! available. [`ScreenSelection`] restores it at the application level: it
+//! consumes mouse events, tracks a linear or rectangular selection over the
+//! rendered [`Buffer`], paints a highlight overlay, and copies the selected
+//! text through [`Clipboard`] (OSC 52 with external fallback).
+//!
+//! # Feature Gate
+//!
+//! Enabled via the `screen-selection` feature in `ftui-extras`.
+//!
+//! # Interaction
+//!
+//! | Gesture | Effect |
+//! |---------|--------|
+//! | Left drag | Linear (reading-order) selection |
+//! | Alt + left drag | Rectangular (block) selection |
+//! | Shift + left click | Extend the current selection to the click |
+//! | Left click | Clear the selection |
+//!
+//! # Widget cooperation
+//!
+//! Widgets re-register their regions every frame:
+//!
+//! - [`ScreenSelection::opt_out`] excludes a rectangle (e.g. a button). Drags
+//!   cannot start inside it and its cells are neither highlighted nor copied.
+//! - [`ScreenSelection::set_copy_text`] replaces the cells of a rectangle with
+//!   custom text when any part of it is selected.
+//! - [`ScreenSelection::set_soft_wrapped`] marks rows that continue on the
+//!   next row, so linear copies join them without a newline.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! // In update():
+//! if let Event::Mouse(mouse) = event {
+//!     if self.selection.handle_mouse(&mouse) {
+//!         return Cmd::none();
+//!     }
+//! }
+//!
+//! // At the end of view():
+//! self.selection.highlight(&mut frame.buffer);
+//!
+//! // On the copy key binding:
+//! self.selection.copy(&buffer, &pool, &clipboard, ClipboardSelection::Clipboard, &mut out)?;
+//! ```
+
+use std::io::Write;
+
+use ftui_core::event::{Modifiers, MouseButton, MouseEvent, MouseEventKind};
+use ftui_core::geometry::Rect;
+use ftui_render::buffer::Buffer;
+use ftui_render::cell::StyleFlags;
+use ftui_render::grapheme_pool::GraphemePool;
+
+use crate::clipboard::{Clipboard, ClipboardError, ClipboardSelection};
+
+/// Shape of a screen selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// Reading-order selection that wraps from the end of one row to the
+    /// start of the next.
+    #[default]
+    Linear,
+    /// Rectangular selection spanning the same columns on every row.
+    Block,
+}
+
+/// How a registered region participates in selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegionBehavior {
+    /// The region is never highlighted or copied.
+    OptOut,
+    /// The region copies as the given text instead of its cells.
+    CopyText(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SelectionRegion {
+    area: Rect,
+    behavior: RegionBehavior,
+}
+
+/// Mouse-driven selection over a rendered buffer.
+#[derive(Debug, Clone, Default)]
+pub struct ScreenSelection {
+    anchor: Option<(u16, u16)>,
+    head: (u16, u16),
+    mode: SelectionMode,
+    dragging: bool,
+    regions: Vec<SelectionRegion>,
+    soft_wrapped: Vec<bool>,
+}
+
+impl ScreenSelection {
+    /// Create an empty selection.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current selection mode.
+    #[must_use]
+    pub const fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// Whether nothing is selected.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.anchor.is_none()
+    }
+
+    /// Whether a drag is in progress.
+    #[must_use]
+    pub const fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Anchor and head of the selection, in the order they were set.
+    #[must_use]
+    pub fn endpoints(&self) -> Option<((u16, u16), (u16, u16))> {
+        self.anchor.map(|anchor| (anchor, self.head))
+    }
+
+    /// Select programmatically between two cells (inclusive).
+    pub fn select(&mut self, anchor: (u16, u16), head: (u16, u16), mode: SelectionMode) {
+        self.anchor = Some(anchor);
+        self.head = head;
+        self.mode = mode;
+        self.dragging = false;
+    }
+
+    /// Clear the selection.
+    pub fn clear(&mut self) {
+        self.anchor = None;
+        self.dragging = false;
+    }
+
+    /// Drop all registered regions and soft-wrap marks.
+    ///
+    /// Call at the start of each frame before widgets re-register.
+    pub fn clear_regions(&mut self) {
+        self.regions.clear();
+        self.soft_wrapped.clear();
+    }
+
+    /// Exclude `area` from selection.
+    pub fn opt_out(&mut self, area: Rect) {
+        self.regions.push(SelectionRegion {
+            area,
+            behavior: RegionBehavior::OptOut,
+        });
+    }
+
+    /// Copy `text` in place of the cells of `area` when it is selected.
+    pub fn set_copy_text(&mut self, area: Rect, text: impl Into<String>) {
+        self.regions.push(SelectionRegion {
+            area,
+            behavior: RegionBehavior::CopyText(text.into()),
+        });
+    }
+
+    /// Mark row `y` as soft-wrapped (its content continues on row `y + 1`).
+    pub fn set_soft_wrapped(&mut self, y: u16, wrapped: bool) {
+        let y = y as usize;
+        if self.soft_wrapped.len() <= y {
+            if !wrapped {
+                return;
+            }
+            self.soft_wrapped.resize(y + 1, false);
+        }
+        self.soft_wrapped[y] = wrapped;
+    }
+
+    /// Whether row `y` is marked as soft-wrapped.
+    #[must_use]
+    pub fn is_soft_wrapped(&self, y: u16) -> bool {
+        self.soft_wrapped.get(y as usize).copied().unwrap_or(false)
+    }
+
+    /// Feed a mouse event. Returns `true` if the event was consumed.
+    ///
+    /// Presses inside opt-out regions are not consumed so the owning widget
+    /// still receives them.
+    pub fn handle_mouse(&mut self, event: &MouseEvent) -> bool {
+        let pos = (event.x, event.y);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.region_at(pos.0, pos.1) == Some(&RegionBehavior::OptOut) {
+                    return false;
+                }
+                if event.modifiers.contains(Modifiers::SHIFT) && self.anchor.is_some() {
+                    self.head = pos;
+                } else {
+                    self.anchor = Some(pos);
+                    self.head = pos;
+                    self.mode = if event.modifiers.contains(Modifiers::ALT) {
+                        SelectionMode::Block
+                    } else {
+                        SelectionMode::Linear
+                    };
+                }
+                self.dragging = true;
+                true
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                self.head = pos;
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.dragging => {
+                self.head = pos;
+                self.dragging = false;
+                // A click without movement clears rather than selecting one cell.
+                if self.anchor == Some(pos) && !event.modifiers.contains(Modifiers::SHIFT) {
+                    self.anchor = None;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether cell `(x, y)` lies inside the selection geometry.
+    ///
+    /// Opt-out regions are not considered; see [`highlight`](Self::highlight).
+    #[must_use]
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        let Some((x0, y0, x1, y1)) = self.bounds() else {
+            return false;
+        };
+        if y < y0 || y > y1 {
+            return false;
+        }
+        match self.mode {
+            SelectionMode::Block => x >= x0 && x <= x1,
+            SelectionMode::Linear => (y > y0 || x >= x0) && (y < y1 || x <= x1),
+        }
+    }
+
+    /// Paint the selection highlight onto `buffer` by toggling reverse video.
+    ///
+    /// Wide characters are highlighted as a whole even if the selection only
+    /// covers one of their cells.
+    pub fn highlight(&self, buffer: &mut Buffer) {
+        let Some((_, y0, _, y1)) = self.bounds() else {
+            return;
+        };
+        let y1 = y1.min(buffer.height().saturating_sub(1));
+        for y in y0..=y1 {
+            let Some((x0, x1)) = self.row_span(buffer, y) else {
+                continue;
+            };
+            for x in x0..=x1 {
+                if self.region_at(x, y) == Some(&RegionBehavior::OptOut) {
+                    continue;
+                }
+                if let Some(cell) = buffer.get_mut(x, y) {
+                    let flags = cell.attrs.flags() ^ StyleFlags::REVERSE;
+                    cell.attrs = cell.attrs.with_flags(flags);
+                }
+            }
+        }
+    }
+
+    /// Extract the selected text from `buffer`.
+    ///
+    /// - Wide-character continuation cells are skipped so each glyph appears
+    ///   once.
+    /// - In linear mode, rows marked soft-wrapped are joined without a newline.
+    /// - Trailing whitespace is trimmed from each line, and trailing empty
+    ///   lines are dropped.
+    /// - Opt-out regions are skipped; copy-text regions emit their text once.
+    #[must_use]
+    pub fn extract_text(&self, buffer: &Buffer, pool: &GraphemePool) -> String {
+        let Some((_, y0, _, y1)) = self.bounds() else {
+            return String::new();
+        };
+        if buffer.width() == 0 || buffer.height() == 0 {
+            return String::new();
+        }
+        let y1 = y1.min(buffer.height() - 1);
+        let mut emitted = vec![false; self.regions.len()];
+        let mut out = String::new();
+        let mut line = String::new();
+
+        for y in y0..=y1 {
+            if let Some((x0, x1)) = self.row_span(buffer, y) {
+                for x in x0..=x1 {
+                    match self.region_index_at(x, y) {
+                        Some(i) => {
+                            if let RegionBehavior::CopyText(text) = &self.regions[i].behavior
+                                && !emitted[i]
+                            {
+                                emitted[i] = true;
+                                line.push_str(text);
+                            }
+                        }
+                        None => push_cell(&mut line, buffer, pool, x, y),
+                    }
+                }
+            }
+            let joined = self.mode == SelectionMode::Linear && self.is_soft_wrapped(y);
+            if y < y1 && !joined {
+                out.push_str(line.trim_end());
+                out.push('\n');
+                line.clear();
+            }
+        }
+        out.push_str(line.trim_end());
+
+        let trimmed = out.trim_end_matches('\n').len();
+        out.truncate(trimmed);
+        out
+    }
+
+    /// Copy the selected text to the clipboard.
+    ///
+    /// Returns [`ClipboardError::InvalidInput`] when nothing is selected.
+    pub fn copy(
+        &self,
+        buffer: &Buffer,
+        pool: &GraphemePool,
+        clipboard: &Clipboard,
+        selection: ClipboardSelection,
+        writer: &mut impl Write,
+    ) -> Result<(), ClipboardError> {
+        let text = self.extract_text(buffer, pool);
+        if text.is_empty() {
+            return Err(ClipboardError::InvalidInput("selection is empty".into()));
+        }
+        clipboard.set(&text, selection, writer)
+    }
+
+    /// Normalized bounds `(x0, y0, x1, y1)`.
+    ///
+    /// In linear mode `(x0, y0)` is the reading-order start and `(x1, y1)` the
+    /// end; in block mode they are the rectangle corners.
+    fn bounds(&self) -> Option<(u16, u16, u16, u16)> {
+        let (ax, ay) = self.anchor?;
+        let (hx, hy) = self.head;
+        Some(match self.mode {
+            SelectionMode::Linear => {
+                if (ay, ax) <= (hy, hx) {
+                    (ax, ay, hx, hy)
+                } else {
+                    (hx, hy, ax, ay)
+                }
+            }
+            SelectionMode::Block => (ax.min(hx), ay.min(hy), ax.max(hx), ay.max(hy)),
+        })
+    }
+
+    /// Selected column span on row `y`, widened to whole wide characters.
+    fn row_span(&self, buffer: &Buffer, y: u16) -> Option<(u16, u16)> {
+        let (bx0, y0, bx1, y1) = self.bounds()?;
+        let last = buffer.width().checked_sub(1)?;
+        if y < y0 || y > y1 || y >= buffer.height() {
+            return None;
+        }
+        let (mut x0, mut x1) = match self.mode {
+            SelectionMode::Block => (bx0, bx1),
+            SelectionMode::Linear => (
+                if y == y0 { bx0 } else { 0 },
+                if y == y1 { bx1 } else { last },
+            ),
+        };
+        if x0 > last {
+            return None;
+        }
+        x1 = x1.min(last);
+        while x0 > 0 && buffer.get(x0, y).is_some_and(|c| c.is_continuation()) {
+            x0 -= 1;
+        }
+        while x1 < last && buffer.get(x1 + 1, y).is_some_and(|c| c.is_continuation()) {
+            x1 += 1;
+        }
+        Some((x0, x1))
+    }
+
+    fn region_index_at(&self, x: u16, y: u16) -> Option<usize> {
+        // Later registrations win, matching paint order.
+        self.regions.iter().rposition(|r| r.area.contains(x, y))
+    }
+
+    fn region_at(&self, x: u16, y: u16) -> Option<&RegionBehavior> {
+        self.region_index_at(x, y)
+            .map(|i| &self.regions[i].behavior)
+    }
+}
+
+fn push_cell(line: &mut String, buffer: &Buffer, pool: &GraphemePool, x: u16, y: u16) {
+    let Some(cell) = buffer.get(x, y) else {
+        return;
+    };
+    if cell.is_continuation() {
+        return;
+    }
+    if cell.content.is_empty() {
+        line.push(' ');
+    } else if let Some(c) = cell.content.as_char() {
+        line.push(c);
+    } else if let Some(s) = cell.content.grapheme_id().and_then(|id| pool.get(id)) {
+        line.push_str(s);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ftui_core::terminal_capabilities::TerminalCapabilities;
+    use ftui_render::cell::Cell;
+
+    fn buffer_from(rows: &[&str], width: u16) -> Buffer {
+        let mut buf = Buffer::new(width, rows.len() as u16);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, ch) in row.chars().enumerate() {
+                buf.set_fast(x as u16, y as u16, Cell::from_char(ch));
+            }
+        }
+        buf
+    }
+
+    fn mouse(kind: MouseEventKind, x: u16, y: u16) -> MouseEvent {
+        MouseEvent::new(kind, x, y)
+    }
+
+    #[test]
+    fn drag_creates_linear_selection() {
+        let mut sel = ScreenSelection::new();
+        assert!(sel.handle_mouse(&mouse(MouseEventKind::Down(MouseButton::Left), 2, 0)));
+        assert!(sel.is_dragging());
+        assert!(sel.handle_mouse(&mouse(MouseEventKind::Drag(MouseButton::Left), 1, 1)));
+        assert!(sel.handle_mouse(&mouse(MouseEventKind::Up(MouseButton::Left), 1, 1)));
+        assert!(!sel.is_dragging());
+        assert_eq!(sel.mode(), SelectionMode::Linear);
+        assert_eq!(sel.endpoints(), Some(((2, 0), (1, 1))));
+    }
+
+    #[test]
+    fn click_without_drag_clears() {
+        let mut sel = ScreenSelection::new();
+        sel.select((0, 0), (3, 0), SelectionMode::Linear);
+        sel.handle_mouse(&mouse(MouseEventKind::Down(MouseButton::Left), 5, 1));
+        sel.handle_mouse(&mouse(MouseEventKind::Up(MouseButton::Left), 5, 1));
+        assert!(sel.is_empty());
+    }
+
+    #[test]
+    fn shift_click_extends_and_alt_drag_is_block() {
+        let mut sel = ScreenSelection::new();
+        sel.select((1, 0), (2, 0), SelectionMode::Linear);
+        let ev =
+            mouse(MouseEventKind::Down(MouseButton::Left), 4, 2).with_modifiers(Modifiers::SHIFT);
+        sel.handle_mouse(&ev);
+        assert_eq!(sel.endpoints(), Some(((1, 0), (4, 2))));
+
+        let ev =
+            mouse(MouseEventKind::Down(MouseButton::Left), 0, 0).with_modifiers(Modifiers::ALT);
+        sel.handle_mouse(&ev);
+        assert_eq!(sel.mode(), SelectionMode::Block);
+    }
+
+    #[test]
+    fn linear_extract_spans_rows() {
+        let buf = buffer_from(&["hello", "world"], 8);
+        let mut sel = ScreenSelection::new();
+        sel.select((3, 1), (2, 0), SelectionMode::Linear);
+        assert_eq!(sel.extract_text(&buf, &GraphemePool::new()), "llo\nworl");
+    }
+
+    #[test]
+    fn block_extract_uses_same_columns() {
+        let buf = buffer_from(&["abcdef", "ghijkl", "mnopqr"], 6);
+        let mut sel = ScreenSelection::new();
+        sel.select((1, 0), (3, 2), SelectionMode::Block);
+        assert_eq!(
+            sel.extract_text(&buf, &GraphemePool::new()),
+            "bcd\nhij\nnop"
+        );
+    }
+
+    #[test]
+    fn soft_wrapped_rows_join() {
+        let buf = buffer_from(&["abc", "def"], 3);
+        let mut sel = ScreenSelection::new();
+        sel.set_soft_wrapped(0, true);
+        sel.select((0, 0), (2, 1), SelectionMode::Linear);
+        assert_eq!(sel.extract_text(&buf, &GraphemePool::new()), "abcdef");
+    }
+
+    #[test]
+    fn wide_chars_copied_once_and_widened() {
+        let mut buf = Buffer::new(6, 1);
+        buf.set(0, 0, Cell::from_char('a'));
+        buf.set(1, 0, Cell::from_char('界'));
+        buf.set(3, 0, Cell::from_char('b'));
+        let mut sel = ScreenSelection::new();
+        // Start on the continuation cell of the wide glyph.
+        sel.select((2, 0), (3, 0), SelectionMode::Linear);
+        assert_eq!(sel.extract_text(&buf, &GraphemePool::new()), "界b");
+
+        sel.highlight(&mut buf);
+        let reversed = |x| {
+            buf.get(x, 0)
+                .unwrap()
+                .attrs
+                .flags()
+                .contains(StyleFlags::REVERSE)
+        };
+        assert!(!reversed(0));
+        assert!(reversed(1));
+        assert!(reversed(2));
+        assert!(reversed(3));
+    }
+
+    #[test]
+    fn opt_out_region_skipped_and_not_consumed() {
+        let buf = buffer_from(&["[OK] text"], 9);
+        let mut sel = ScreenSelection::new();
+        sel.opt_out(Rect::new(0, 0, 4, 1));
+        assert!(!sel.handle_mouse(&mouse(MouseEventKind::Down(MouseButton::Left), 1, 0)));
+        sel.select((0, 0), (8, 0), SelectionMode::Linear);
+        assert_eq!(sel.extract_text(&buf, &GraphemePool::new()), " text");
+    }
+
+    #[test]
+    fn copy_text_region_emitted_once() {
+        let buf = buffer_from(&["id: 3f..", "id: 3f.."], 8);
+        let mut sel = ScreenSelection::new();
+        sel.set_copy_text(Rect::new(4, 0, 4, 2), "3f9a2c");
+        sel.select((0, 0), (7, 1), SelectionMode::Block);
+        assert_eq!(
+            sel.extract_text(&buf, &GraphemePool::new()),
+            "id: 3f9a2c\nid:"
+        );
+    }
+
+    #[test]
+    fn copy_empty_selection_errors() {
+        let buf = Buffer::new(4, 1);
+        let sel = ScreenSelection::new();
+        let clipboard = Clipboard::new(TerminalCapabilities::basic());
+        let mut out = Vec::new();
+        let err = sel
+            .copy(
+                &buf,
+                &GraphemePool::new(),
+                &clipboard,
+                ClipboardSelection::Clipboard,
+                &mut out,
+            )
+            .unwrap_err();
+        assert!(matches!(err, ClipboardError::InvalidInput(_)));
+        assert!(out.is_empty());
+    }
+}
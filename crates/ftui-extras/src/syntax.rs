@@ -328,16 +328,17 @@ pub struct TokenizedText {
 
 impl TokenizedText {
     /// Tokenize an entire buffer from scratch (using `text.lines()`).
-    pub fn from_text<T: Tokenizer>(tokenizer: &T, text: &str) -> Self {
+    pub fn from_text<T: Tokenizer + ?Sized>(tokenizer: &T, text: &str) -> Self {
         let lines: Vec<&str> = text.lines().collect();
         Self::from_lines(tokenizer, &lines)
     }
 
     /// Tokenize an explicit slice of lines (preserves empty lines).
-    pub fn from_lines<T: Tokenizer>(tokenizer: &T, lines: &[&str]) -> Self {
+    pub fn from_lines<T: Tokenizer + ?Sized, S: AsRef<str>>(tokenizer: &T, lines: &[S]) -> Self {
         let mut state = LineState::Normal;
         let mut out = Vec::with_capacity(lines.len());
         for line in lines {
+            let line = line.as_ref();
             let (tokens, state_after) = tokenizer.tokenize_line(line, state);
             debug_assert!(validate_tokens(line, &tokens));
             out.push(TokenLine {
@@ -370,7 +371,12 @@ impl TokenizedText {
     /// This re-tokenizes the edited line and continues until the line's
     /// `state_after` matches the previous cached state (no further impact).
     /// If line counts change, it falls back to full re-tokenization.
-    pub fn update_line<T: Tokenizer>(&mut self, tokenizer: &T, lines: &[&str], line_index: usize) {
+    pub fn update_line<T: Tokenizer + ?Sized, S: AsRef<str>>(
+        &mut self,
+        tokenizer: &T,
+        lines: &[S],
+        line_index: usize,
+    ) {
         if line_index >= lines.len() {
             return;
         }
@@ -388,8 +394,72 @@ impl TokenizedText {
 
         #[allow(clippy::needless_range_loop)] // idx needed to index both `lines` and `self.lines`
         for idx in line_index..lines.len() {
-            let (tokens, state_after) = tokenizer.tokenize_line(lines[idx], state);
-            debug_assert!(validate_tokens(lines[idx], &tokens));
+            let line = lines[idx].as_ref();
+            let (tokens, state_after) = tokenizer.tokenize_line(line, state);
+            debug_assert!(validate_tokens(line, &tokens));
+
+            let unchanged =
+                self.lines[idx].state_after == state_after && self.lines[idx].tokens == tokens;
+
+            self.lines[idx] = TokenLine {
+                tokens,
+                state_after,
+            };
+
+            if unchanged {
+                break;
+            }
+
+            state = state_after;
+        }
+    }
+
+    /// Incrementally re-tokenize after a block of lines was replaced.
+    ///
+    /// The cached lines `start..start + removed` are replaced by the lines of
+    /// `lines` that now occupy their place; following lines are re-tokenized
+    /// only until their result matches the cached one (as in
+    /// [`update_line`](Self::update_line)). Falls back to full
+    /// re-tokenization if the arguments do not describe a valid splice.
+    pub fn splice_lines<T: Tokenizer + ?Sized, S: AsRef<str>>(
+        &mut self,
+        tokenizer: &T,
+        lines: &[S],
+        start: usize,
+        removed: usize,
+    ) {
+        let kept = self.lines.len().checked_sub(removed);
+        let Some(inserted) = kept
+            .filter(|_| start + removed <= self.lines.len())
+            .and_then(|kept| lines.len().checked_sub(kept))
+        else {
+            *self = Self::from_lines(tokenizer, lines);
+            return;
+        };
+
+        let mut state = if start == 0 {
+            LineState::Normal
+        } else {
+            self.lines[start - 1].state_after
+        };
+        let mut fresh = Vec::with_capacity(inserted);
+        for line in &lines[start..start + inserted] {
+            let line = line.as_ref();
+            let (tokens, state_after) = tokenizer.tokenize_line(line, state);
+            debug_assert!(validate_tokens(line, &tokens));
+            fresh.push(TokenLine {
+                tokens,
+                state_after,
+            });
+            state = state_after;
+        }
+        self.lines.splice(start..start + removed, fresh);
+
+        #[allow(clippy::needless_range_loop)] // idx needed to index both `lines` and `self.lines`
+        for idx in start + inserted..lines.len() {
+            let line = lines[idx].as_ref();
+            let (tokens, state_after) = tokenizer.tokenize_line(line, state);
+            debug_assert!(validate_tokens(line, &tokens));
 
             let unchanged =
                 self.lines[idx].state_after == state_after && self.lines[idx].tokens == tokens;
@@ -3823,6 +3893,35 @@ mod tests {
         assert_eq!(kinds, vec![TokenKind::Identifier]);
     }
 
+    #[test]
+    fn tokenized_text_splice_lines_matches_full_retokenize() {
+        let t = rust_tokenizer();
+        let lines = ["let a = 1;", "let b = 2;", "let c = 3;"];
+        let mut cache = TokenizedText::from_lines(&t, &lines);
+
+        // Insert an unterminated block comment: later lines become comments.
+        let inserted = [
+            "let a = 1;",
+            "/* start",
+            "let x = 0;",
+            "let b = 2;",
+            "let c = 3;",
+        ];
+        cache.splice_lines(&t, &inserted, 1, 0);
+        assert_eq!(
+            cache.lines(),
+            TokenizedText::from_lines(&t, &inserted).lines()
+        );
+
+        // Remove the comment again.
+        cache.splice_lines(&t, &lines, 1, 2);
+        assert_eq!(cache.lines(), TokenizedText::from_lines(&t, &lines).lines());
+
+        // An invalid splice falls back to a rebuild.
+        cache.splice_lines(&t, &lines[..1], 5, 1);
+        assert_eq!(cache.lines().len(), 1);
+    }
+
     // -- Trait bounds -------------------------------------------------------
 
     #[test]
@@ -0,0 +1,337 @@
+#![forbid(unsafe_code)]
+
+//! Stale-region detection for models that report partial damage.
+//!
+//! A model that overrides [`Model::take_damage`] promises that everything
+//! outside the reported regions looks exactly as it did last frame. When
+//! that promise is broken (an update changed something but forgot to
+//! invalidate it) the terminal keeps showing stale cells.
+//!
+//! [`DamageAuditor`] catches this in tests: for every frame it renders the
+//! model twice — once the way the runtime does (previous frame + damaged
+//! regions via [`Model::view_region`]) and once with a full [`Model::view`] —
+//! and reports every cell where the two disagree.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ftui_harness::damage_audit::DamageAuditor;
+//!
+//! let mut auditor = DamageAuditor::new(80, 24);
+//! auditor.assert_frame(&mut model); // first frame: full damage
+//! model.update(Msg::Increment);
+//! auditor.assert_frame(&mut model); // panics if a changed cell was not damaged
+//! ```
+//!
+//! [`Model::take_damage`]: ftui_runtime::program::Model::take_damage
+//! [`Model::view_region`]: ftui_runtime::program::Model::view_region
+//! [`Model::view`]: ftui_runtime::program::Model::view
+
+use std::fmt;
+
+use ftui_core::geometry::Rect;
+use ftui_render::buffer::Buffer;
+use ftui_render::cell::Cell;
+use ftui_render::frame::Frame;
+use ftui_render::grapheme_pool::GraphemePool;
+use ftui_runtime::damage::{self, Damage};
+use ftui_runtime::program::Model;
+
+/// Maximum number of stale cells listed in a [`DamageViolation`] message.
+const MAX_REPORTED_CELLS: usize = 16;
+
+/// A cell whose partial render differs from the full render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleCell {
+    /// Column.
+    pub x: u16,
+    /// Row.
+    pub y: u16,
+    /// Content produced by a full `view`.
+    pub expected: String,
+    /// Content produced by the partial render.
+    pub actual: String,
+}
+
+/// Partial render did not match the full render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DamageViolation {
+    /// Index of the audited frame (0 = first).
+    pub frame: u64,
+    /// Damaged regions the model reported for this frame.
+    pub regions: Vec<Rect>,
+    /// Cells that differ, in row-major order.
+    pub stale: Vec<StaleCell>,
+}
+
+impl fmt::Display for DamageViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "frame {}: {} stale cell(s) outside reported damage {:?}",
+            self.frame,
+            self.stale.len(),
+            self.regions
+        )?;
+        for cell in self.stale.iter().take(MAX_REPORTED_CELLS) {
+            writeln!(
+                f,
+                "  ({}, {}): expected {:?}, found {:?}",
+                cell.x, cell.y, cell.expected, cell.actual
+            )?;
+        }
+        if self.stale.len() > MAX_REPORTED_CELLS {
+            writeln!(
+                f,
+                "  ... and {} more",
+                self.stale.len() - MAX_REPORTED_CELLS
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DamageViolation {}
+
+/// Renders a model frame by frame and checks its damage reports.
+#[derive(Debug)]
+pub struct DamageAuditor {
+    width: u16,
+    height: u16,
+    pool: GraphemePool,
+    previous: Option<Buffer>,
+    frame: u64,
+}
+
+impl DamageAuditor {
+    /// Create an auditor for a `width` x `height` screen.
+    #[must_use]
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            pool: GraphemePool::new(),
+            previous: None,
+            frame: 0,
+        }
+    }
+
+    /// The last fully rendered frame.
+    #[must_use]
+    pub fn previous(&self) -> Option<&Buffer> {
+        self.previous.as_ref()
+    }
+
+    /// Render the next frame and verify that the model's damage covers every
+    /// changed cell.
+    ///
+    /// Takes the model's damage exactly once, like the runtime does. Full
+    /// damage (and the first frame) always passes.
+    pub fn check_frame<M: Model>(&mut self, model: &mut M) -> Result<(), DamageViolation> {
+        let index = self.frame;
+        self.frame += 1;
+
+        let damage = model.take_damage();
+        let full = self.render_full(model);
+        let previous = self.previous.replace(full);
+
+        let (Damage::Regions(regions), Some(previous)) = (damage, previous) else {
+            return Ok(());
+        };
+
+        let mut partial = Buffer::new(self.width, self.height);
+        let widened = damage::restore_undamaged(&mut partial, &previous, &regions);
+        let mut frame = Frame::from_buffer(partial, &mut self.pool);
+        damage::view_damaged(&*model, &mut frame, &widened);
+        let partial = frame.buffer;
+
+        let expected = self.previous.as_ref().expect("full render stored above");
+        let stale = self.diff(expected, &partial);
+        if stale.is_empty() {
+            Ok(())
+        } else {
+            Err(DamageViolation {
+                frame: index,
+                regions,
+                stale,
+            })
+        }
+    }
+
+    /// Like [`check_frame`](Self::check_frame), but panics on a violation.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the list of stale cells when damage is incomplete.
+    pub fn assert_frame<M: Model>(&mut self, model: &mut M) {
+        if let Err(violation) = self.check_frame(model) {
+            panic!("{violation}");
+        }
+    }
+
+    fn render_full<M: Model>(&mut self, model: &M) -> Buffer {
+        let mut frame = Frame::new(self.width, self.height, &mut self.pool);
+        model.view(&mut frame);
+        frame.buffer
+    }
+
+    fn diff(&self, expected: &Buffer, actual: &Buffer) -> Vec<StaleCell> {
+        let mut stale = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (Some(want), Some(got)) = (expected.get(x, y), actual.get(x, y)) else {
+                    continue;
+                };
+                if want != got {
+                    stale.push(StaleCell {
+                        x,
+                        y,
+                        expected: self.describe(want),
+                        actual: self.describe(got),
+                    });
+                }
+            }
+        }
+        stale
+    }
+
+    fn describe(&self, cell: &Cell) -> String {
+        if cell.is_continuation() {
+            return String::from("<continuation>");
+        }
+        if let Some(ch) = cell.content.as_char() {
+            return ch.to_string();
+        }
+        cell.content
+            .grapheme_id()
+            .and_then(|id| self.pool.get(id))
+            .map_or_else(String::new, str::to_owned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ftui_core::event::Event;
+    use ftui_runtime::damage::DamageTracker;
+    use ftui_runtime::program::Cmd;
+
+    enum Msg {
+        Top(char),
+        Bottom(char),
+        BottomQuietly(char),
+    }
+
+    impl From<Event> for Msg {
+        fn from(_: Event) -> Self {
+            Msg::Top('?')
+        }
+    }
+
+    struct TwoRows {
+        top: char,
+        bottom: char,
+        damage: DamageTracker,
+    }
+
+    impl TwoRows {
+        fn new() -> Self {
+            Self {
+                top: 'a',
+                bottom: 'b',
+                damage: DamageTracker::new(),
+            }
+        }
+    }
+
+    impl Model for TwoRows {
+        type Message = Msg;
+
+        fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+            match msg {
+                Msg::Top(c) => {
+                    self.top = c;
+                    self.damage.invalidate(Rect::new(0, 0, 4, 1));
+                }
+                Msg::Bottom(c) => {
+                    self.bottom = c;
+                    self.damage.invalidate(Rect::new(0, 1, 4, 1));
+                }
+                Msg::BottomQuietly(c) => self.bottom = c,
+            }
+            Cmd::none()
+        }
+
+        fn view(&self, frame: &mut Frame) {
+            for x in 0..4 {
+                frame.buffer.set(x, 0, Cell::from_char(self.top));
+                frame.buffer.set(x, 1, Cell::from_char(self.bottom));
+            }
+        }
+
+        fn take_damage(&mut self) -> Damage {
+            self.damage.take()
+        }
+    }
+
+    #[test]
+    fn first_frame_passes() {
+        let mut model = TwoRows::new();
+        let mut auditor = DamageAuditor::new(4, 2);
+        assert!(auditor.check_frame(&mut model).is_ok());
+        assert!(auditor.previous().is_some());
+    }
+
+    #[test]
+    fn complete_damage_passes() {
+        let mut model = TwoRows::new();
+        let mut auditor = DamageAuditor::new(4, 2);
+        auditor.assert_frame(&mut model);
+        let _ = model.update(Msg::Top('x'));
+        auditor.assert_frame(&mut model);
+        let _ = model.update(Msg::Bottom('y'));
+        auditor.assert_frame(&mut model);
+    }
+
+    #[test]
+    fn missing_damage_is_reported() {
+        let mut model = TwoRows::new();
+        let mut auditor = DamageAuditor::new(4, 2);
+        auditor.assert_frame(&mut model);
+        let _ = model.update(Msg::Top('x'));
+        let _ = model.update(Msg::BottomQuietly('z'));
+
+        let violation = auditor.check_frame(&mut model).unwrap_err();
+        assert_eq!(violation.frame, 1);
+        assert_eq!(violation.regions, vec![Rect::new(0, 0, 4, 1)]);
+        assert_eq!(violation.stale.len(), 4);
+        assert_eq!(
+            violation.stale[0],
+            StaleCell {
+                x: 0,
+                y: 1,
+                expected: "z".into(),
+                actual: "b".into(),
+            }
+        );
+        assert!(violation.to_string().contains("(0, 1)"));
+    }
+
+    #[test]
+    #[should_panic(expected = "stale cell")]
+    fn assert_frame_panics_on_violation() {
+        let mut model = TwoRows::new();
+        let mut auditor = DamageAuditor::new(4, 2);
+        auditor.assert_frame(&mut model);
+        let _ = model.update(Msg::BottomQuietly('z'));
+        auditor.assert_frame(&mut model);
+    }
+
+    #[test]
+    fn no_damage_keeps_previous_frame() {
+        let mut model = TwoRows::new();
+        let mut auditor = DamageAuditor::new(4, 2);
+        auditor.assert_frame(&mut model);
+        auditor.assert_frame(&mut model);
+    }
+}
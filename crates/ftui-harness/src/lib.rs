@@ -47,6 +47,7 @@ pub mod asciicast;
 pub mod baseline_capture;
 pub mod benchmark_gate;
 pub mod cost_surface;
+pub mod damage_audit;
 pub mod determinism;
 pub mod doctor_cost_profile;
 pub mod doctor_topology;
@@ -120,6 +120,24 @@ impl LayoutCacheKey {
         }
     }
 
+    /// Distinguish layouts that share constraints and direction but differ
+    /// in other parameters, such as the variants of a responsive layout.
+    ///
+    /// The variant is folded into both constraint fingerprints, so keys
+    /// with different variants do not collide.
+    #[must_use]
+    pub fn with_variant(mut self, variant: u64) -> Self {
+        let mut h1 = std::collections::hash_map::DefaultHasher::new();
+        let mut h2 = rustc_hash::FxHasher::default();
+        self.constraints_hash.hash(&mut h1);
+        variant.hash(&mut h1);
+        self.constraints_hash_fx.hash(&mut h2);
+        variant.hash(&mut h2);
+        self.constraints_hash = h1.finish();
+        self.constraints_hash_fx = h2.finish();
+        self
+    }
+
     /// Reconstruct the area Rect from cached components.
     #[inline]
     pub fn area(&self) -> Rect {
@@ -785,6 +803,19 @@ mod tests {
         assert_ne!(k1, k2);
     }
 
+    #[test]
+    fn variant_distinguishes_keys() {
+        let base = LayoutCacheKey::new(
+            Rect::new(0, 0, 80, 24),
+            &[Constraint::Fill],
+            Direction::Horizontal,
+            None,
+        );
+        assert_eq!(base.with_variant(1), base.with_variant(1));
+        assert_ne!(base.with_variant(1), base.with_variant(2));
+        assert_ne!(base.with_variant(0), base);
+    }
+
     #[test]
     fn different_direction_different_key() {
         let k1 = LayoutCacheKey::new(
@@ -0,0 +1,1447 @@
+#![forbid(unsafe_code)]
+
+//! Cassowary linear constraint solver and constraint-based layout.
+//!
+//! [`Flex`](crate::Flex) and [`Grid`](crate::Grid) size each track on its own.
+//! Some layouts need relations *between* regions instead: "the sidebar is as
+//! wide as the header label", "these two columns in different containers
+//! share a left edge". This module provides:
+//!
+//! - [`Solver`] - an incremental implementation of the Cassowary simplex
+//!   algorithm over linear equalities and inequalities with [`Strength`]s.
+//! - [`ConstraintLayout`] - named rectangles whose edges are solver variables,
+//!   solved against an area that can change every frame.
+//!
+//! # Incremental solving
+//!
+//! The container area is held in *edit variables*. When the terminal resizes,
+//! [`ConstraintLayout::solve`] only suggests the new area and re-optimizes the
+//! existing tableau (dual simplex); constraints are not re-added.
+//!
+//! # Strengths
+//!
+//! Required constraints must hold; adding one that conflicts with other
+//! required constraints fails with [`SolverError::Unsatisfiable`]. Weaker
+//! constraints are satisfied as well as possible, stronger ones first.
+//!
+//! # Example
+//!
+//! ```
+//! use ftui_core::geometry::Rect;
+//! use ftui_layout::cassowary::{ConstraintLayout, Strength};
+//!
+//! let mut layout = ConstraintLayout::new();
+//! let area = layout.area();
+//! let label = layout.rect("label");
+//! let sidebar = layout.rect("sidebar");
+//!
+//! layout.add(label.width.equal_to(12.0)).unwrap();
+//! layout.add(sidebar.width.equal_to(label.width)).unwrap();
+//! layout.add(sidebar.left.equal_to(area.left)).unwrap();
+//! layout.add(sidebar.top.equal_to(area.top)).unwrap();
+//! layout.add(sidebar.height.equal_to(area.height).strength(Strength::STRONG)).unwrap();
+//!
+//! let rects = layout.solve(Rect::new(0, 0, 80, 24));
+//! let sidebar = rects[layout.index_of("sidebar").unwrap()];
+//! assert_eq!(sidebar, Rect::new(0, 0, 12, 24));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use ftui_core::geometry::Rect;
+use smallvec::SmallVec;
+
+use crate::cache::{LayoutCache, LayoutCacheKey};
+use crate::{Direction, LayoutSizeHint, Rects};
+
+/// Values closer to zero than this are treated as zero.
+const EPSILON: f64 = 1.0e-8;
+
+#[inline]
+fn near_zero(value: f64) -> bool {
+    value.abs() < EPSILON
+}
+
+// ============================================================================
+// Variables, expressions and constraints
+// ============================================================================
+
+/// A solver variable, created by [`Solver::new_variable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Variable(u32);
+
+impl Variable {
+    /// Numeric ID of the variable, unique within its solver.
+    #[inline]
+    #[must_use]
+    pub const fn id(self) -> u32 {
+        self.0
+    }
+
+    /// Constraint `self == rhs` (required).
+    #[must_use]
+    pub fn equal_to(self, rhs: impl Into<Expression>) -> LinearConstraint {
+        Expression::from(self).equal_to(rhs)
+    }
+
+    /// Constraint `self <= rhs` (required).
+    #[must_use]
+    pub fn at_most(self, rhs: impl Into<Expression>) -> LinearConstraint {
+        Expression::from(self).at_most(rhs)
+    }
+
+    /// Constraint `self >= rhs` (required).
+    #[must_use]
+    pub fn at_least(self, rhs: impl Into<Expression>) -> LinearConstraint {
+        Expression::from(self).at_least(rhs)
+    }
+}
+
+/// A variable multiplied by a coefficient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Term {
+    /// The variable.
+    pub variable: Variable,
+    /// Its coefficient.
+    pub coefficient: f64,
+}
+
+/// A linear expression: a sum of terms plus a constant.
+///
+/// Built from [`Variable`]s and `f64`s with `+`, `-`, unary `-` and `* f64`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Expression {
+    /// Variable terms. The same variable may appear more than once.
+    pub terms: Vec<Term>,
+    /// Constant part.
+    pub constant: f64,
+}
+
+impl Expression {
+    /// A constant expression.
+    #[must_use]
+    pub const fn constant(value: f64) -> Self {
+        Self {
+            terms: Vec::new(),
+            constant: value,
+        }
+    }
+
+    /// Constraint `self == rhs` (required).
+    #[must_use]
+    pub fn equal_to(self, rhs: impl Into<Expression>) -> LinearConstraint {
+        LinearConstraint::new(self, Relation::Equal, rhs)
+    }
+
+    /// Constraint `self <= rhs` (required).
+    #[must_use]
+    pub fn at_most(self, rhs: impl Into<Expression>) -> LinearConstraint {
+        LinearConstraint::new(self, Relation::LessOrEqual, rhs)
+    }
+
+    /// Constraint `self >= rhs` (required).
+    #[must_use]
+    pub fn at_least(self, rhs: impl Into<Expression>) -> LinearConstraint {
+        LinearConstraint::new(self, Relation::GreaterOrEqual, rhs)
+    }
+}
+
+impl From<Variable> for Expression {
+    fn from(variable: Variable) -> Self {
+        Self {
+            terms: vec![Term {
+                variable,
+                coefficient: 1.0,
+            }],
+            constant: 0.0,
+        }
+    }
+}
+
+impl From<f64> for Expression {
+    fn from(value: f64) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl<T: Into<Expression>> Add<T> for Expression {
+    type Output = Expression;
+
+    fn add(mut self, rhs: T) -> Expression {
+        let rhs = rhs.into();
+        self.terms.extend(rhs.terms);
+        self.constant += rhs.constant;
+        self
+    }
+}
+
+impl<T: Into<Expression>> Sub<T> for Expression {
+    type Output = Expression;
+
+    fn sub(self, rhs: T) -> Expression {
+        self + -rhs.into()
+    }
+}
+
+impl Mul<f64> for Expression {
+    type Output = Expression;
+
+    fn mul(mut self, rhs: f64) -> Expression {
+        for term in &mut self.terms {
+            term.coefficient *= rhs;
+        }
+        self.constant *= rhs;
+        self
+    }
+}
+
+impl Neg for Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        self * -1.0
+    }
+}
+
+impl<T: Into<Expression>> Add<T> for Variable {
+    type Output = Expression;
+
+    fn add(self, rhs: T) -> Expression {
+        Expression::from(self) + rhs
+    }
+}
+
+impl<T: Into<Expression>> Sub<T> for Variable {
+    type Output = Expression;
+
+    fn sub(self, rhs: T) -> Expression {
+        Expression::from(self) - rhs
+    }
+}
+
+impl Mul<f64> for Variable {
+    type Output = Expression;
+
+    fn mul(self, rhs: f64) -> Expression {
+        Expression::from(self) * rhs
+    }
+}
+
+impl Neg for Variable {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        -Expression::from(self)
+    }
+}
+
+/// Relation between the two sides of a constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// `lhs <= rhs`
+    LessOrEqual,
+    /// `lhs == rhs`
+    Equal,
+    /// `lhs >= rhs`
+    GreaterOrEqual,
+}
+
+/// Priority of a constraint.
+///
+/// Strengths combine three tiers (strong, medium, weak), each in `0..=1000`,
+/// so that any amount of violation of a stronger tier outweighs the weaker
+/// ones. [`Strength::REQUIRED`] constraints must always hold.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Strength(f64);
+
+impl Strength {
+    /// Must hold; conflicts are reported as errors.
+    pub const REQUIRED: Self = Self(1_001_001_000.0);
+    /// Strong preference.
+    pub const STRONG: Self = Self(1_000_000.0);
+    /// Medium preference.
+    pub const MEDIUM: Self = Self(1_000.0);
+    /// Weak preference.
+    pub const WEAK: Self = Self(1.0);
+
+    /// Combine strong, medium and weak tiers, each clamped to `0..=1000`.
+    #[must_use]
+    pub fn new(strong: f64, medium: f64, weak: f64) -> Self {
+        let tier = |v: f64| v.clamp(0.0, 1000.0);
+        Self(tier(strong) * 1_000_000.0 + tier(medium) * 1_000.0 + tier(weak))
+    }
+
+    /// Numeric weight of the strength.
+    #[inline]
+    #[must_use]
+    pub const fn value(self) -> f64 {
+        self.0
+    }
+
+    /// Whether this is [`Strength::REQUIRED`] (or stronger).
+    #[inline]
+    #[must_use]
+    pub fn is_required(self) -> bool {
+        self.0 >= Self::REQUIRED.0
+    }
+
+    fn clip(self) -> Self {
+        Self(self.0.clamp(0.0, Self::REQUIRED.0))
+    }
+}
+
+/// A linear constraint with a strength.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearConstraint {
+    /// `lhs - rhs`, compared against zero.
+    expression: Expression,
+    relation: Relation,
+    strength: Strength,
+}
+
+impl LinearConstraint {
+    /// Create a required constraint `lhs relation rhs`.
+    #[must_use]
+    pub fn new(lhs: impl Into<Expression>, relation: Relation, rhs: impl Into<Expression>) -> Self {
+        Self {
+            expression: lhs.into() - rhs,
+            relation,
+            strength: Strength::REQUIRED,
+        }
+    }
+
+    /// Set the strength of the constraint.
+    #[must_use]
+    pub fn strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    fn hash_into(&self, h1: &mut impl Hasher, h2: &mut impl Hasher) {
+        for term in &self.expression.terms {
+            term.variable.hash(h1);
+            term.variable.hash(h2);
+            term.coefficient.to_bits().hash(h1);
+            term.coefficient.to_bits().hash(h2);
+        }
+        self.expression.constant.to_bits().hash(h1);
+        self.expression.constant.to_bits().hash(h2);
+        self.relation.hash(h1);
+        self.relation.hash(h2);
+        self.strength.0.to_bits().hash(h1);
+        self.strength.0.to_bits().hash(h2);
+    }
+}
+
+/// Handle for a constraint added to a [`Solver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstraintId(u32);
+
+/// Errors reported by the [`Solver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverError {
+    /// A required constraint conflicts with the required constraints already
+    /// in the solver. The solver is unchanged.
+    Unsatisfiable,
+    /// The constraint is not in the solver.
+    UnknownConstraint(ConstraintId),
+    /// The variable is already an edit variable.
+    DuplicateEditVariable(Variable),
+    /// The variable is not an edit variable.
+    UnknownEditVariable(Variable),
+    /// Edit variables cannot be required.
+    RequiredEditStrength,
+    /// The tableau is inconsistent (unbounded objective or missing pivot).
+    Internal,
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsatisfiable => write!(f, "required constraint cannot be satisfied"),
+            Self::UnknownConstraint(id) => write!(f, "unknown constraint {}", id.0),
+            Self::DuplicateEditVariable(v) => {
+                write!(f, "variable {} is already an edit variable", v.0)
+            }
+            Self::UnknownEditVariable(v) => write!(f, "variable {} is not an edit variable", v.0),
+            Self::RequiredEditStrength => write!(f, "edit variables cannot be required"),
+            Self::Internal => write!(f, "constraint solver reached an inconsistent state"),
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+// ============================================================================
+// Tableau
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum SymbolKind {
+    External,
+    Slack,
+    Error,
+    Dummy,
+}
+
+/// A tableau column. Ordered by ID so that pivoting is deterministic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Symbol {
+    id: u32,
+    kind: SymbolKind,
+}
+
+impl Symbol {
+    fn is_pivotable(self) -> bool {
+        matches!(self.kind, SymbolKind::Slack | SymbolKind::Error)
+    }
+}
+
+/// A tableau row: `basic = constant + sum(coefficient * symbol)`.
+#[derive(Debug, Clone, Default)]
+struct Row {
+    cells: BTreeMap<Symbol, f64>,
+    constant: f64,
+}
+
+impl Row {
+    fn new(constant: f64) -> Self {
+        Self {
+            cells: BTreeMap::new(),
+            constant,
+        }
+    }
+
+    fn add(&mut self, value: f64) -> f64 {
+        self.constant += value;
+        self.constant
+    }
+
+    fn insert_symbol(&mut self, symbol: Symbol, coefficient: f64) {
+        let cell = self.cells.entry(symbol).or_insert(0.0);
+        *cell += coefficient;
+        if near_zero(*cell) {
+            self.cells.remove(&symbol);
+        }
+    }
+
+    fn insert_row(&mut self, other: &Row, coefficient: f64) {
+        self.constant += other.constant * coefficient;
+        for (&symbol, &c) in &other.cells {
+            self.insert_symbol(symbol, c * coefficient);
+        }
+    }
+
+    fn remove(&mut self, symbol: Symbol) {
+        self.cells.remove(&symbol);
+    }
+
+    fn reverse_sign(&mut self) {
+        self.constant = -self.constant;
+        for c in self.cells.values_mut() {
+            *c = -*c;
+        }
+    }
+
+    /// Rewrite `0 = row` as `symbol = ...`. `symbol` must be in the row.
+    fn solve_for(&mut self, symbol: Symbol) {
+        let Some(c) = self.cells.remove(&symbol) else {
+            return;
+        };
+        let coefficient = -1.0 / c;
+        self.constant *= coefficient;
+        for c in self.cells.values_mut() {
+            *c *= coefficient;
+        }
+    }
+
+    /// Rewrite `lhs = row` as `rhs = ...`.
+    fn solve_for_symbols(&mut self, lhs: Symbol, rhs: Symbol) {
+        self.insert_symbol(lhs, -1.0);
+        self.solve_for(rhs);
+    }
+
+    fn coefficient_for(&self, symbol: Symbol) -> f64 {
+        self.cells.get(&symbol).copied().unwrap_or(0.0)
+    }
+
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        if let Some(c) = self.cells.remove(&symbol) {
+            self.insert_row(row, c);
+        }
+    }
+}
+
+/// Marker symbols that identify a constraint's row.
+#[derive(Debug, Clone, Copy)]
+struct Tag {
+    marker: Symbol,
+    other: Option<Symbol>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EditInfo {
+    tag: Tag,
+    constraint: ConstraintId,
+    constant: f64,
+}
+
+/// Incremental Cassowary constraint solver.
+///
+/// Variables are created with [`new_variable`](Self::new_variable); their
+/// values reflect the optimal solution after every successful call.
+#[derive(Debug, Clone, Default)]
+pub struct Solver {
+    next_variable: u32,
+    next_symbol: u32,
+    next_constraint: u32,
+    constraints: BTreeMap<ConstraintId, (LinearConstraint, Tag)>,
+    vars: BTreeMap<Variable, Symbol>,
+    rows: BTreeMap<Symbol, Row>,
+    edits: BTreeMap<Variable, EditInfo>,
+    infeasible: Vec<Symbol>,
+    objective: Row,
+    artificial: Option<Row>,
+}
+
+impl Solver {
+    /// Create an empty solver.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new variable.
+    pub fn new_variable(&mut self) -> Variable {
+        let variable = Variable(self.next_variable);
+        self.next_variable += 1;
+        variable
+    }
+
+    /// Number of constraints in the solver, including edit constraints.
+    #[must_use]
+    pub fn constraint_count(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Whether the constraint is in the solver.
+    #[must_use]
+    pub fn has_constraint(&self, id: ConstraintId) -> bool {
+        self.constraints.contains_key(&id)
+    }
+
+    /// Add a constraint and re-optimize.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError::Unsatisfiable`] when a required constraint
+    /// conflicts with the required constraints already present.
+    pub fn add_constraint(
+        &mut self,
+        constraint: LinearConstraint,
+    ) -> Result<ConstraintId, SolverError> {
+        let (mut row, tag) = self.create_row(&constraint);
+        let mut subject = choose_subject(&row, tag);
+        if subject.is_none() && row.cells.keys().all(|s| s.kind == SymbolKind::Dummy) {
+            if !near_zero(row.constant) {
+                return Err(SolverError::Unsatisfiable);
+            }
+            subject = Some(tag.marker);
+        }
+        match subject {
+            Some(subject) => {
+                row.solve_for(subject);
+                self.substitute(subject, &row);
+                self.rows.insert(subject, row);
+            }
+            None => {
+                // Pivoting on the artificial objective rewrites the tableau;
+                // keep a copy so a rejected constraint leaves no trace.
+                let saved = (self.rows.clone(), self.objective.clone());
+                let satisfied = self.add_with_artificial_variable(&row);
+                if satisfied != Ok(true) {
+                    (self.rows, self.objective) = saved;
+                    self.infeasible.clear();
+                    return Err(satisfied.err().unwrap_or(SolverError::Unsatisfiable));
+                }
+            }
+        }
+
+        let id = ConstraintId(self.next_constraint);
+        self.next_constraint += 1;
+        self.constraints.insert(id, (constraint, tag));
+        self.optimize(false)?;
+        Ok(id)
+    }
+
+    /// Remove a constraint and re-optimize.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError::UnknownConstraint`] when the constraint is not
+    /// in the solver.
+    pub fn remove_constraint(&mut self, id: ConstraintId) -> Result<(), SolverError> {
+        let (constraint, tag) = self
+            .constraints
+            .remove(&id)
+            .ok_or(SolverError::UnknownConstraint(id))?;
+
+        // Drop the error terms from the objective.
+        let strength = constraint.strength.clip().value();
+        for symbol in [Some(tag.marker), tag.other].into_iter().flatten() {
+            if symbol.kind == SymbolKind::Error {
+                match self.rows.get(&symbol) {
+                    Some(row) => self.objective.insert_row(row, -strength),
+                    None => self.objective.insert_symbol(symbol, -strength),
+                }
+            }
+        }
+
+        if self.rows.remove(&tag.marker).is_none() {
+            let (leaving, mut row) = self
+                .marker_leaving_row(tag.marker)
+                .ok_or(SolverError::Internal)?;
+            row.solve_for_symbols(leaving, tag.marker);
+            self.substitute(tag.marker, &row);
+        }
+        self.optimize(false)
+    }
+
+    /// Make `variable` an edit variable whose value is set with
+    /// [`suggest_value`](Self::suggest_value).
+    ///
+    /// # Errors
+    ///
+    /// Fails when the variable is already an edit variable or `strength` is
+    /// required.
+    pub fn add_edit_variable(
+        &mut self,
+        variable: Variable,
+        strength: Strength,
+    ) -> Result<(), SolverError> {
+        if self.edits.contains_key(&variable) {
+            return Err(SolverError::DuplicateEditVariable(variable));
+        }
+        let strength = strength.clip();
+        if strength.is_required() {
+            return Err(SolverError::RequiredEditStrength);
+        }
+        let constraint = LinearConstraint::new(variable, Relation::Equal, 0.0).strength(strength);
+        let id = self.add_constraint(constraint)?;
+        let tag = self.constraints[&id].1;
+        self.edits.insert(
+            variable,
+            EditInfo {
+                tag,
+                constraint: id,
+                constant: 0.0,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop treating `variable` as an edit variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError::UnknownEditVariable`] when it is not one.
+    pub fn remove_edit_variable(&mut self, variable: Variable) -> Result<(), SolverError> {
+        let info = self
+            .edits
+            .remove(&variable)
+            .ok_or(SolverError::UnknownEditVariable(variable))?;
+        self.remove_constraint(info.constraint)
+    }
+
+    /// Whether `variable` is an edit variable.
+    #[must_use]
+    pub fn has_edit_variable(&self, variable: Variable) -> bool {
+        self.edits.contains_key(&variable)
+    }
+
+    /// Suggest a value for an edit variable and re-optimize incrementally.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError::UnknownEditVariable`] when `variable` is not an
+    /// edit variable.
+    pub fn suggest_value(&mut self, variable: Variable, value: f64) -> Result<(), SolverError> {
+        let info = self
+            .edits
+            .get_mut(&variable)
+            .ok_or(SolverError::UnknownEditVariable(variable))?;
+        let delta = value - info.constant;
+        info.constant = value;
+        let tag = info.tag;
+
+        if let Some(row) = self.rows.get_mut(&tag.marker) {
+            if row.add(-delta) < 0.0 {
+                self.infeasible.push(tag.marker);
+            }
+        } else if let Some(other) = tag.other
+            && let Some(row) = self.rows.get_mut(&other)
+        {
+            if row.add(delta) < 0.0 {
+                self.infeasible.push(other);
+            }
+        } else {
+            for (&symbol, row) in &mut self.rows {
+                let c = row.coefficient_for(tag.marker);
+                if c != 0.0 && row.add(delta * c) < 0.0 && symbol.kind != SymbolKind::External {
+                    self.infeasible.push(symbol);
+                }
+            }
+        }
+        self.dual_optimize()
+    }
+
+    /// Current value of `variable` (0 when it is unconstrained).
+    #[must_use]
+    pub fn value(&self, variable: Variable) -> f64 {
+        self.vars
+            .get(&variable)
+            .and_then(|symbol| self.rows.get(symbol))
+            .map_or(0.0, |row| row.constant)
+    }
+
+    fn new_symbol(&mut self, kind: SymbolKind) -> Symbol {
+        let symbol = Symbol {
+            id: self.next_symbol,
+            kind,
+        };
+        self.next_symbol += 1;
+        symbol
+    }
+
+    fn var_symbol(&mut self, variable: Variable) -> Symbol {
+        if let Some(&symbol) = self.vars.get(&variable) {
+            return symbol;
+        }
+        let symbol = self.new_symbol(SymbolKind::External);
+        self.vars.insert(variable, symbol);
+        symbol
+    }
+
+    /// Build the tableau row for a constraint, with basic variables
+    /// substituted out.
+    fn create_row(&mut self, constraint: &LinearConstraint) -> (Row, Tag) {
+        let expression = &constraint.expression;
+        let mut row = Row::new(expression.constant);
+        for term in &expression.terms {
+            if near_zero(term.coefficient) {
+                continue;
+            }
+            let symbol = self.var_symbol(term.variable);
+            match self.rows.get(&symbol) {
+                Some(basic) => row.insert_row(basic, term.coefficient),
+                None => row.insert_symbol(symbol, term.coefficient),
+            }
+        }
+
+        let strength = constraint.strength.clip();
+        let weight = strength.value();
+        let tag = match constraint.relation {
+            Relation::LessOrEqual | Relation::GreaterOrEqual => {
+                let coefficient = if constraint.relation == Relation::LessOrEqual {
+                    1.0
+                } else {
+                    -1.0
+                };
+                let slack = self.new_symbol(SymbolKind::Slack);
+                row.insert_symbol(slack, coefficient);
+                let other = (!strength.is_required()).then(|| {
+                    let error = self.new_symbol(SymbolKind::Error);
+                    row.insert_symbol(error, -coefficient);
+                    self.objective.insert_symbol(error, weight);
+                    error
+                });
+                Tag {
+                    marker: slack,
+                    other,
+                }
+            }
+            Relation::Equal if strength.is_required() => {
+                let dummy = self.new_symbol(SymbolKind::Dummy);
+                row.insert_symbol(dummy, 1.0);
+                Tag {
+                    marker: dummy,
+                    other: None,
+                }
+            }
+            Relation::Equal => {
+                let plus = self.new_symbol(SymbolKind::Error);
+                let minus = self.new_symbol(SymbolKind::Error);
+                row.insert_symbol(plus, -1.0);
+                row.insert_symbol(minus, 1.0);
+                self.objective.insert_symbol(plus, weight);
+                self.objective.insert_symbol(minus, weight);
+                Tag {
+                    marker: plus,
+                    other: Some(minus),
+                }
+            }
+        };
+
+        if row.constant < 0.0 {
+            row.reverse_sign();
+        }
+        (row, tag)
+    }
+
+    /// Add `row` through an artificial variable. Returns whether the row
+    /// could be satisfied.
+    fn add_with_artificial_variable(&mut self, row: &Row) -> Result<bool, SolverError> {
+        let art = self.new_symbol(SymbolKind::Slack);
+        self.rows.insert(art, row.clone());
+        self.artificial = Some(row.clone());
+        let optimized = self.optimize(true);
+        let artificial = self.artificial.take();
+        optimized?;
+        let success = artificial.is_some_and(|a| near_zero(a.constant));
+
+        if let Some(mut row) = self.rows.remove(&art) {
+            if row.cells.is_empty() {
+                return Ok(success);
+            }
+            let Some(entering) = row.cells.keys().copied().find(|s| s.is_pivotable()) else {
+                return Ok(false);
+            };
+            row.solve_for_symbols(art, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+
+        for row in self.rows.values_mut() {
+            row.remove(art);
+        }
+        self.objective.remove(art);
+        Ok(success)
+    }
+
+    /// Replace `symbol` with `row` everywhere in the tableau.
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        for (&basic, r) in &mut self.rows {
+            r.substitute(symbol, row);
+            if basic.kind != SymbolKind::External && r.constant < 0.0 {
+                self.infeasible.push(basic);
+            }
+        }
+        self.objective.substitute(symbol, row);
+        if let Some(artificial) = &mut self.artificial {
+            artificial.substitute(symbol, row);
+        }
+    }
+
+    /// Primal simplex on the objective (or the artificial objective).
+    fn optimize(&mut self, artificial: bool) -> Result<(), SolverError> {
+        loop {
+            let objective = if artificial {
+                self.artificial.as_ref().ok_or(SolverError::Internal)?
+            } else {
+                &self.objective
+            };
+            let Some(entering) = objective
+                .cells
+                .iter()
+                .find(|&(s, &c)| s.kind != SymbolKind::Dummy && c < 0.0)
+                .map(|(&s, _)| s)
+            else {
+                return Ok(());
+            };
+
+            let mut ratio = f64::MAX;
+            let mut leaving = None;
+            for (&symbol, row) in &self.rows {
+                if symbol.kind == SymbolKind::External {
+                    continue;
+                }
+                let c = row.coefficient_for(entering);
+                if c < 0.0 {
+                    let r = -row.constant / c;
+                    if r < ratio {
+                        ratio = r;
+                        leaving = Some(symbol);
+                    }
+                }
+            }
+            let leaving = leaving.ok_or(SolverError::Internal)?;
+            let mut row = self.rows.remove(&leaving).ok_or(SolverError::Internal)?;
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+    }
+
+    /// Dual simplex: restore feasibility of rows whose constant went negative.
+    fn dual_optimize(&mut self) -> Result<(), SolverError> {
+        while let Some(leaving) = self.infeasible.pop() {
+            let Some(mut row) = self.rows.remove(&leaving) else {
+                continue;
+            };
+            if row.constant >= 0.0 {
+                self.rows.insert(leaving, row);
+                continue;
+            }
+
+            let mut ratio = f64::MAX;
+            let mut entering = None;
+            for (&symbol, &c) in &row.cells {
+                if c > 0.0 && symbol.kind != SymbolKind::Dummy {
+                    let r = self.objective.coefficient_for(symbol) / c;
+                    if r < ratio {
+                        ratio = r;
+                        entering = Some(symbol);
+                    }
+                }
+            }
+            let Some(entering) = entering else {
+                self.rows.insert(leaving, row);
+                return Err(SolverError::Internal);
+            };
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+        Ok(())
+    }
+
+    /// Pick (and remove) the row to pivot a non-basic marker into.
+    fn marker_leaving_row(&mut self, marker: Symbol) -> Option<(Symbol, Row)> {
+        let mut r1 = f64::MAX;
+        let mut r2 = f64::MAX;
+        let (mut first, mut second, mut third) = (None, None, None);
+        for (&symbol, row) in &self.rows {
+            let c = row.coefficient_for(marker);
+            if c == 0.0 {
+                continue;
+            }
+            if symbol.kind == SymbolKind::External {
+                third = Some(symbol);
+            } else if c < 0.0 {
+                let r = -row.constant / c;
+                if r < r1 {
+                    r1 = r;
+                    first = Some(symbol);
+                }
+            } else {
+                let r = row.constant / c;
+                if r < r2 {
+                    r2 = r;
+                    second = Some(symbol);
+                }
+            }
+        }
+        let leaving = first.or(second).or(third)?;
+        self.rows.remove(&leaving).map(|row| (leaving, row))
+    }
+}
+
+/// Pick the symbol to solve a new row for, if one exists without an
+/// artificial variable.
+fn choose_subject(row: &Row, tag: Tag) -> Option<Symbol> {
+    if let Some(&symbol) = row.cells.keys().find(|s| s.kind == SymbolKind::External) {
+        return Some(symbol);
+    }
+    [Some(tag.marker), tag.other]
+        .into_iter()
+        .flatten()
+        .find(|&s| s.is_pivotable() && row.coefficient_for(s) < 0.0)
+}
+
+// ============================================================================
+// Constraint layout
+// ============================================================================
+
+/// Strength of the container area edits: just below required, so a resize
+/// always wins over preferences but never breaks required constraints.
+const AREA_STRENGTH: Strength = Strength(1_000_000_000.0);
+
+/// The four variables of a rectangle in a [`ConstraintLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RectVars {
+    /// Left edge (x).
+    pub left: Variable,
+    /// Top edge (y).
+    pub top: Variable,
+    /// Width.
+    pub width: Variable,
+    /// Height.
+    pub height: Variable,
+}
+
+impl RectVars {
+    /// Right edge: `left + width`.
+    #[must_use]
+    pub fn right(&self) -> Expression {
+        self.left + self.width
+    }
+
+    /// Bottom edge: `top + height`.
+    #[must_use]
+    pub fn bottom(&self) -> Expression {
+        self.top + self.height
+    }
+
+    /// Horizontal center: `left + width / 2`.
+    #[must_use]
+    pub fn center_x(&self) -> Expression {
+        self.left + self.width * 0.5
+    }
+
+    /// Vertical center: `top + height / 2`.
+    #[must_use]
+    pub fn center_y(&self) -> Expression {
+        self.top + self.height * 0.5
+    }
+}
+
+/// Constraints added for one size hint.
+type HintConstraints = SmallVec<[ConstraintId; 3]>;
+
+/// Layout of named rectangles related by linear constraints.
+///
+/// Each rectangle is kept inside the container area with strong (not
+/// required) constraints and has non-negative size. Results are returned in
+/// the order the rectangles were created; [`index_of`](Self::index_of) maps
+/// names to positions.
+#[derive(Debug, Clone)]
+pub struct ConstraintLayout {
+    solver: Solver,
+    area: RectVars,
+    names: Vec<String>,
+    rects: Vec<RectVars>,
+    hints: BTreeMap<(usize, bool), (LayoutSizeHint, HintConstraints)>,
+    last_area: Option<Rect>,
+}
+
+impl Default for ConstraintLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConstraintLayout {
+    /// Create an empty layout.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut solver = Solver::new();
+        let area = new_rect_vars(&mut solver);
+        for variable in [area.left, area.top, area.width, area.height] {
+            solver
+                .add_edit_variable(variable, AREA_STRENGTH)
+                .expect("fresh variables are not edit variables");
+        }
+        Self {
+            solver,
+            area,
+            names: Vec::new(),
+            rects: Vec::new(),
+            hints: BTreeMap::new(),
+            last_area: None,
+        }
+    }
+
+    /// Variables of the container area.
+    #[must_use]
+    pub const fn area(&self) -> RectVars {
+        self.area
+    }
+
+    /// Variables of the rectangle `name`, creating it on first use.
+    pub fn rect(&mut self, name: &str) -> RectVars {
+        if let Some(index) = self.index_of(name) {
+            return self.rects[index];
+        }
+        let vars = new_rect_vars(&mut self.solver);
+        let area = self.area;
+        let constraints = [
+            vars.width.at_least(0.0),
+            vars.height.at_least(0.0),
+            vars.left.at_least(area.left).strength(Strength::STRONG),
+            vars.top.at_least(area.top).strength(Strength::STRONG),
+            vars.right()
+                .at_most(area.right())
+                .strength(Strength::STRONG),
+            vars.bottom()
+                .at_most(area.bottom())
+                .strength(Strength::STRONG),
+        ];
+        for constraint in constraints {
+            self.solver
+                .add_constraint(constraint)
+                .expect("constraints on fresh variables are satisfiable");
+        }
+        self.names.push(name.to_owned());
+        self.rects.push(vars);
+        vars
+    }
+
+    /// Variables of an existing rectangle.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<RectVars> {
+        self.index_of(name).map(|index| self.rects[index])
+    }
+
+    /// Position of `name` in solved results.
+    #[must_use]
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Rectangle names in result order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Add a constraint.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError::Unsatisfiable`] when a required constraint
+    /// conflicts with existing required constraints.
+    pub fn add(&mut self, constraint: LinearConstraint) -> Result<ConstraintId, SolverError> {
+        self.solver.add_constraint(constraint)
+    }
+
+    /// Remove a constraint previously returned by [`add`](Self::add).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError::UnknownConstraint`] for unknown IDs.
+    pub fn remove(&mut self, id: ConstraintId) -> Result<(), SolverError> {
+        self.solver.remove_constraint(id)
+    }
+
+    /// Constrain the size of `name` along `direction` by a measured hint.
+    ///
+    /// The minimum and maximum are strong, the preferred size medium.
+    /// Replaces the previous hint for the same rectangle and axis; an
+    /// identical hint is a no-op.
+    pub fn size_hint(&mut self, name: &str, direction: Direction, hint: LayoutSizeHint) {
+        let vars = self.rect(name);
+        let Some(index) = self.index_of(name) else {
+            return;
+        };
+        let horizontal = direction == Direction::Horizontal;
+        let key = (index, horizontal);
+        if let Some((old, ids)) = self.hints.get(&key) {
+            if *old == hint {
+                return;
+            }
+            for &id in ids {
+                // IDs in `hints` are live: they are only removed here.
+                let _ = self.solver.remove_constraint(id);
+            }
+        }
+
+        let size = if horizontal { vars.width } else { vars.height };
+        let mut constraints = vec![
+            size.at_least(f64::from(hint.min))
+                .strength(Strength::STRONG),
+            size.equal_to(f64::from(hint.preferred))
+                .strength(Strength::MEDIUM),
+        ];
+        if let Some(max) = hint.max {
+            constraints.push(size.at_most(f64::from(max)).strength(Strength::STRONG));
+        }
+        let ids = constraints
+            .into_iter()
+            .filter_map(|c| self.solver.add_constraint(c).ok())
+            .collect();
+        self.hints.insert(key, (hint, ids));
+    }
+
+    /// Solve for `area`.
+    ///
+    /// Only the area edit variables change between calls, so resizing
+    /// re-optimizes the existing solution instead of rebuilding it. Results
+    /// are rounded to cells (edges are rounded, so adjacent rectangles stay
+    /// adjacent) and clipped to `area`.
+    pub fn solve(&mut self, area: Rect) -> Rects {
+        if self.last_area != Some(area) {
+            let suggestions = [
+                (self.area.left, area.x),
+                (self.area.top, area.y),
+                (self.area.width, area.width),
+                (self.area.height, area.height),
+            ];
+            for (variable, value) in suggestions {
+                self.solver
+                    .suggest_value(variable, f64::from(value))
+                    .expect("area edits are non-required and always feasible");
+            }
+            self.last_area = Some(area);
+        }
+        self.rects
+            .iter()
+            .map(|vars| self.rounded(vars, area))
+            .collect()
+    }
+
+    /// Solve for `area`, measuring every rectangle along `direction` first.
+    ///
+    /// `measurer(index, available)` is called with each rectangle's result
+    /// index and the area extent along `direction`, like
+    /// [`Flex::split_with_measurer`](crate::Flex::split_with_measurer).
+    pub fn solve_with_measurer<F>(
+        &mut self,
+        area: Rect,
+        direction: Direction,
+        mut measurer: F,
+    ) -> Rects
+    where
+        F: FnMut(usize, u16) -> LayoutSizeHint,
+    {
+        let available = match direction {
+            Direction::Horizontal => area.width,
+            Direction::Vertical => area.height,
+        };
+        for index in 0..self.names.len() {
+            let hint = measurer(index, available);
+            let name = self.names[index].clone();
+            self.size_hint(&name, direction, hint);
+        }
+        self.solve(area)
+    }
+
+    /// Solve through a [`LayoutCache`].
+    ///
+    /// The key covers the area and every constraint in the solver, so
+    /// structurally identical layouts share entries and any constraint or
+    /// hint change misses.
+    pub fn solve_cached(&mut self, area: Rect, cache: &mut LayoutCache) -> Rects {
+        let key = self.cache_key(area);
+        cache.get_or_compute(key, || self.solve(area))
+    }
+
+    /// Cache key for solving at `area`.
+    #[must_use]
+    pub fn cache_key(&self, area: Rect) -> LayoutCacheKey {
+        let mut h1 = std::collections::hash_map::DefaultHasher::new();
+        let mut h2 = rustc_hash::FxHasher::default();
+        "cassowary".hash(&mut h1);
+        "cassowary".hash(&mut h2);
+        for (constraint, _) in self.solver.constraints.values() {
+            constraint.hash_into(&mut h1, &mut h2);
+        }
+        LayoutCacheKey {
+            area_x: area.x,
+            area_y: area.y,
+            area_width: area.width,
+            area_height: area.height,
+            constraints_hash: h1.finish(),
+            constraints_hash_fx: h2.finish(),
+            constraints_len: self.rects.len().min(usize::from(u16::MAX)) as u16,
+            direction: Direction::Horizontal,
+            intrinsics_hash: None,
+            intrinsics_hash_fx: None,
+        }
+    }
+
+    /// Round the edges of `vars` to cells and clip them to `area`.
+    fn rounded(&self, vars: &RectVars, area: Rect) -> Rect {
+        let column = |v: f64| v.round().clamp(f64::from(area.x), f64::from(area.right())) as u16;
+        let row = |v: f64| v.round().clamp(f64::from(area.y), f64::from(area.bottom())) as u16;
+        let left = self.solver.value(vars.left);
+        let top = self.solver.value(vars.top);
+        let x = column(left);
+        let y = row(top);
+        let right = column(left + self.solver.value(vars.width)).max(x);
+        let bottom = row(top + self.solver.value(vars.height)).max(y);
+        Rect::new(x, y, right - x, bottom - y)
+    }
+}
+
+fn new_rect_vars(solver: &mut Solver) -> RectVars {
+    RectVars {
+        left: solver.new_variable(),
+        top: solver.new_variable(),
+        width: solver.new_variable(),
+        height: solver.new_variable(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    #[test]
+    fn solves_required_equalities() {
+        let mut solver = Solver::new();
+        let x = solver.new_variable();
+        let y = solver.new_variable();
+        solver.add_constraint((x + y).equal_to(10.0)).unwrap();
+        solver.add_constraint(x.equal_to(3.0)).unwrap();
+        assert!(close(solver.value(x), 3.0));
+        assert!(close(solver.value(y), 7.0));
+    }
+
+    #[test]
+    fn stronger_preference_wins() {
+        let mut solver = Solver::new();
+        let x = solver.new_variable();
+        solver
+            .add_constraint(x.equal_to(10.0).strength(Strength::WEAK))
+            .unwrap();
+        solver
+            .add_constraint(x.equal_to(20.0).strength(Strength::STRONG))
+            .unwrap();
+        assert!(close(solver.value(x), 20.0));
+        solver.add_constraint(x.at_most(15.0)).unwrap();
+        assert!(close(solver.value(x), 15.0));
+    }
+
+    #[test]
+    fn conflicting_required_constraint_is_rejected() {
+        let mut solver = Solver::new();
+        let x = solver.new_variable();
+        solver.add_constraint(x.at_least(10.0)).unwrap();
+        assert_eq!(
+            solver.add_constraint(x.at_most(5.0)),
+            Err(SolverError::Unsatisfiable)
+        );
+        assert_eq!(
+            solver.add_constraint(x.equal_to(3.0)),
+            Err(SolverError::Unsatisfiable)
+        );
+        // The solver keeps working after a rejected constraint.
+        solver.add_constraint(x.equal_to(12.0)).unwrap();
+        assert!(close(solver.value(x), 12.0));
+    }
+
+    #[test]
+    fn removing_constraint_restores_weaker_solution() {
+        let mut solver = Solver::new();
+        let x = solver.new_variable();
+        solver
+            .add_constraint(x.equal_to(4.0).strength(Strength::WEAK))
+            .unwrap();
+        let strong = solver
+            .add_constraint(x.equal_to(9.0).strength(Strength::STRONG))
+            .unwrap();
+        assert!(close(solver.value(x), 9.0));
+        solver.remove_constraint(strong).unwrap();
+        assert!(close(solver.value(x), 4.0));
+        assert_eq!(
+            solver.remove_constraint(strong),
+            Err(SolverError::UnknownConstraint(strong))
+        );
+    }
+
+    #[test]
+    fn edit_variables_resolve_incrementally() {
+        let mut solver = Solver::new();
+        let width = solver.new_variable();
+        let left = solver.new_variable();
+        let right = solver.new_variable();
+        solver.add_edit_variable(width, Strength::STRONG).unwrap();
+        solver.add_constraint(left.equal_to(right)).unwrap();
+        solver
+            .add_constraint((left + right).equal_to(width))
+            .unwrap();
+
+        for w in [80.0, 31.0, 120.0] {
+            solver.suggest_value(width, w).unwrap();
+            assert!(close(solver.value(left), w / 2.0));
+            assert!(close(solver.value(right), w / 2.0));
+        }
+        assert_eq!(
+            solver.add_edit_variable(width, Strength::WEAK),
+            Err(SolverError::DuplicateEditVariable(width))
+        );
+        assert_eq!(
+            solver.add_edit_variable(left, Strength::REQUIRED),
+            Err(SolverError::RequiredEditStrength)
+        );
+    }
+
+    #[test]
+    fn aligns_regions_across_containers() {
+        let mut layout = ConstraintLayout::new();
+        let area = layout.area();
+        let header_label = layout.rect("header_label");
+        let sidebar = layout.rect("sidebar");
+        let main = layout.rect("main");
+
+        layout.add(header_label.width.equal_to(14.0)).unwrap();
+        layout.add(header_label.left.equal_to(area.left)).unwrap();
+        layout.add(header_label.top.equal_to(area.top)).unwrap();
+        layout.add(header_label.height.equal_to(1.0)).unwrap();
+        layout
+            .add(sidebar.width.equal_to(header_label.width))
+            .unwrap();
+        layout.add(sidebar.left.equal_to(area.left)).unwrap();
+        layout
+            .add(sidebar.top.equal_to(header_label.bottom()))
+            .unwrap();
+        layout
+            .add(sidebar.bottom().equal_to(area.bottom()))
+            .unwrap();
+        layout.add(main.left.equal_to(sidebar.right())).unwrap();
+        layout.add(main.right().equal_to(area.right())).unwrap();
+        layout.add(main.top.equal_to(sidebar.top)).unwrap();
+        layout.add(main.height.equal_to(sidebar.height)).unwrap();
+
+        for (w, h) in [(80, 24), (40, 10), (120, 50)] {
+            let rects = layout.solve(Rect::new(0, 0, w, h));
+            assert_eq!(rects[1], Rect::new(0, 1, 14, h - 1));
+            assert_eq!(rects[2], Rect::new(14, 1, w - 14, h - 1));
+        }
+    }
+
+    #[test]
+    fn size_hints_bound_rectangles() {
+        let mut layout = ConstraintLayout::new();
+        let area = layout.area();
+        let panel = layout.rect("panel");
+        layout
+            .add(panel.width.equal_to(area.width).strength(Strength::WEAK))
+            .unwrap();
+        layout.add(panel.top.equal_to(area.top)).unwrap();
+        layout.add(panel.left.equal_to(area.left)).unwrap();
+        layout.size_hint(
+            "panel",
+            Direction::Horizontal,
+            LayoutSizeHint {
+                min: 10,
+                preferred: 20,
+                max: Some(30),
+            },
+        );
+        assert_eq!(layout.solve(Rect::new(0, 0, 80, 5))[0].width, 20);
+
+        let rects =
+            layout.solve_with_measurer(Rect::new(0, 0, 80, 5), Direction::Horizontal, |_, _| {
+                LayoutSizeHint::at_least(25, 40)
+            });
+        assert_eq!(rects[0].width, 40);
+        // Results are clipped to the area even when hints overflow it.
+        assert_eq!(layout.solve(Rect::new(0, 0, 12, 5))[0].width, 12);
+    }
+
+    #[test]
+    fn solve_cached_keys_on_constraints_and_area() {
+        let mut cache = LayoutCache::new(8);
+        let mut layout = ConstraintLayout::new();
+        let a = layout.rect("a");
+        layout.add(a.width.equal_to(5.0)).unwrap();
+        let area = Rect::new(0, 0, 20, 4);
+
+        let first = layout.solve_cached(area, &mut cache);
+        let second = layout.solve_cached(area, &mut cache);
+        assert_eq!(first, second);
+        assert_eq!(cache.stats().hits, 1);
+
+        layout.add(a.left.equal_to(3.0)).unwrap();
+        let moved = layout.solve_cached(area, &mut cache);
+        assert_eq!(moved[0].x, 3);
+        assert_eq!(cache.stats().misses, 2);
+    }
+}
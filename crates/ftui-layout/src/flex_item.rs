@@ -0,0 +1,663 @@
+#![forbid(unsafe_code)]
+
+//! Flex item model: grow, shrink, basis and cross-axis alignment.
+//!
+//! [`Constraint`]s describe each track on its own. A [`FlexItem`] instead
+//! follows the CSS flexbox model: every item starts at its *basis*, then free
+//! space is handed out in proportion to `grow` factors, or taken back in
+//! proportion to `shrink × basis`, while per-item `min`/`max` bounds hold.
+//!
+//! Items are set with [`Flex::items`]. With
+//! [`OverflowBehavior::Wrap`], items (or constraints, converted with
+//! `FlexItem::from`) are broken into lines that are placed along the cross
+//! axis according to [`Flex::align_content`] and [`Flex::line_gap`].
+//!
+//! # Example
+//!
+//! ```
+//! use ftui_core::geometry::Rect;
+//! use ftui_layout::{Flex, FlexBasis, FlexItem};
+//!
+//! let flex = Flex::horizontal().items([
+//!     FlexItem::new().basis(FlexBasis::Cells(20)).shrink(0.0),
+//!     FlexItem::flex(2.0),
+//!     FlexItem::flex(1.0).max(10),
+//! ]);
+//!
+//! let rects = flex.split(Rect::new(0, 0, 50, 3));
+//! assert_eq!(rects[0].width, 20);
+//! assert_eq!(rects[1].width, 20);
+//! assert_eq!(rects[2].width, 10);
+//! ```
+
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+use ftui_core::geometry::Rect;
+
+use crate::{
+    Constraint, Direction, Flex, LayoutSizeHint, OverflowBehavior, Rects, Sizes, direction,
+    round_layout_stable,
+};
+
+/// Values closer to zero than this are treated as zero.
+const EPSILON: f64 = 1.0e-6;
+
+/// Initial main size of a flex item, before free space is distributed.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FlexBasis {
+    /// The preferred size reported by the measurer (zero without one).
+    #[default]
+    Content,
+    /// The minimum size reported by the measurer (zero without one).
+    MinContent,
+    /// An exact number of cells.
+    Cells(u16),
+    /// A percentage of the container's main size (0.0 to 100.0).
+    Percentage(f32),
+}
+
+/// Placement of an item on the cross axis of its line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CrossAlignment {
+    /// Fill the line (items with an explicit cross size start at the top/left).
+    #[default]
+    Stretch,
+    /// Align to the start of the line.
+    Start,
+    /// Center within the line.
+    Center,
+    /// Align to the end of the line.
+    End,
+}
+
+/// Placement of wrapped lines on the cross axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AlignContent {
+    /// Share leftover space equally between lines.
+    #[default]
+    Stretch,
+    /// Pack lines at the start.
+    Start,
+    /// Pack lines in the center.
+    Center,
+    /// Pack lines at the end.
+    End,
+    /// Distribute leftover space between lines (no outer space).
+    SpaceBetween,
+    /// Distribute leftover space evenly around each line.
+    SpaceAround,
+}
+
+/// A flex item: how one child takes part in free-space distribution.
+///
+/// The default item has basis [`FlexBasis::Content`], does not grow and
+/// shrinks with factor 1, like CSS `flex: 0 1 auto`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexItem {
+    grow: f32,
+    shrink: f32,
+    basis: FlexBasis,
+    min: u16,
+    max: Option<u16>,
+    cross: Option<u16>,
+    align_self: Option<CrossAlignment>,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        Self {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: FlexBasis::Content,
+            min: 0,
+            max: None,
+            cross: None,
+            align_self: None,
+        }
+    }
+}
+
+impl FlexItem {
+    /// Create an item with the default factors (`flex: 0 1 auto`).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An item that grows by `grow` from a zero basis (CSS `flex: <grow>`).
+    #[must_use]
+    pub fn flex(grow: f32) -> Self {
+        Self::new().grow(grow).basis(FlexBasis::Cells(0))
+    }
+
+    /// An item of exactly `size` cells that neither grows nor shrinks.
+    #[must_use]
+    pub fn fixed(size: u16) -> Self {
+        Self::new().basis(FlexBasis::Cells(size)).shrink(0.0)
+    }
+
+    /// Set the grow factor (negative values are treated as zero).
+    #[must_use]
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow.max(0.0);
+        self
+    }
+
+    /// Set the shrink factor (negative values are treated as zero).
+    #[must_use]
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink.max(0.0);
+        self
+    }
+
+    /// Set the basis.
+    #[must_use]
+    pub fn basis(mut self, basis: FlexBasis) -> Self {
+        self.basis = basis;
+        self
+    }
+
+    /// Set the minimum main size.
+    #[must_use]
+    pub fn min(mut self, min: u16) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum main size. A minimum larger than the maximum wins.
+    #[must_use]
+    pub fn max(mut self, max: u16) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the cross-axis size. Items without one stretch to their line.
+    #[must_use]
+    pub fn cross(mut self, size: u16) -> Self {
+        self.cross = Some(size);
+        self
+    }
+
+    /// Override the container's [`Flex::align_items`] for this item.
+    #[must_use]
+    pub fn align_self(mut self, alignment: CrossAlignment) -> Self {
+        self.align_self = Some(alignment);
+        self
+    }
+
+    /// Minimum main size of this item when its content needs `content`.
+    pub(crate) fn min_main(&self, content: u16) -> u16 {
+        let floor = match self.basis {
+            FlexBasis::Cells(size) if self.shrink <= 0.0 => size,
+            _ => content,
+        };
+        floor.min(self.max.unwrap_or(u16::MAX)).max(self.min)
+    }
+
+    /// Feed every field into `state`, hashing floats by their bits.
+    pub(crate) fn hash_into<H: Hasher>(&self, state: &mut H) {
+        self.grow.to_bits().hash(state);
+        self.shrink.to_bits().hash(state);
+        match self.basis {
+            FlexBasis::Content => 0u8.hash(state),
+            FlexBasis::MinContent => 1u8.hash(state),
+            FlexBasis::Cells(size) => (2u8, size).hash(state),
+            FlexBasis::Percentage(pct) => (3u8, pct.to_bits()).hash(state),
+        }
+        (self.min, self.max, self.cross, self.align_self).hash(state);
+    }
+
+    /// Resolve the basis and bounds against the container's main size.
+    fn resolve<F>(&self, index: usize, available: u16, measurer: &F) -> ResolvedItem
+    where
+        F: Fn(usize, u16) -> LayoutSizeHint,
+    {
+        let hint = match self.basis {
+            FlexBasis::Content | FlexBasis::MinContent => measurer(index, available),
+            FlexBasis::Cells(_) | FlexBasis::Percentage(_) => LayoutSizeHint::ZERO,
+        };
+        let base = match self.basis {
+            FlexBasis::Content => hint.preferred.min(hint.max.unwrap_or(u16::MAX)),
+            FlexBasis::MinContent => hint.min,
+            FlexBasis::Cells(cells) => cells,
+            FlexBasis::Percentage(p) => (f32::from(available) * p / 100.0)
+                .round()
+                .clamp(0.0, f32::from(u16::MAX)) as u16,
+        };
+        // Content never shrinks below what the measurer says it needs.
+        let min = self.min.max(hint.min);
+        let max = self.max.unwrap_or(u16::MAX).max(min);
+        ResolvedItem {
+            grow: f64::from(self.grow),
+            shrink: f64::from(self.shrink),
+            min: f64::from(min),
+            max: f64::from(max),
+            hypothetical: base.clamp(min, max),
+        }
+    }
+}
+
+impl From<Constraint> for FlexItem {
+    /// Express a track constraint as a flex item, for wrapping constraint
+    /// lists.
+    fn from(constraint: Constraint) -> Self {
+        match constraint {
+            Constraint::Fixed(size) => Self::fixed(size),
+            Constraint::Percentage(p) => Self::new().basis(FlexBasis::Percentage(p)),
+            Constraint::Min(size) => Self::fixed(size).grow(1.0).min(size),
+            Constraint::Max(size) => Self::flex(1.0).max(size),
+            Constraint::Ratio(n, d) => {
+                let p = if d == 0 {
+                    0.0
+                } else {
+                    n as f32 * 100.0 / d as f32
+                };
+                Self::new().basis(FlexBasis::Percentage(p))
+            }
+            Constraint::Fill => Self::flex(1.0),
+            Constraint::FitContent => Self::new(),
+            Constraint::FitContentBounded { min, max } => Self::new().min(min).max(max),
+            Constraint::FitMin => Self::new().basis(FlexBasis::MinContent),
+        }
+    }
+}
+
+/// A flex item with its basis and bounds resolved to cells.
+#[derive(Debug, Clone, Copy)]
+struct ResolvedItem {
+    grow: f64,
+    shrink: f64,
+    min: f64,
+    max: f64,
+    /// Basis clamped to the bounds.
+    hypothetical: u16,
+}
+
+impl Flex {
+    /// Items to lay out with the flex item model, if it applies.
+    pub(crate) fn flex_items(&self) -> Option<Cow<'_, [FlexItem]>> {
+        if !self.items.is_empty() {
+            Some(Cow::Borrowed(&self.items))
+        } else if self.overflow == OverflowBehavior::Wrap {
+            Some(
+                self.constraints
+                    .iter()
+                    .map(|&c| FlexItem::from(c))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Lay out flex items in `inner` (the area after margins).
+    pub(crate) fn split_items<F>(&self, inner: Rect, items: &[FlexItem], measurer: &F) -> Rects
+    where
+        F: Fn(usize, u16) -> LayoutSizeHint,
+    {
+        let (main, cross) = match self.direction {
+            Direction::Horizontal => (inner.width, inner.height),
+            Direction::Vertical => (inner.height, inner.width),
+        };
+        let resolved: Vec<ResolvedItem> = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| item.resolve(i, main, measurer))
+            .collect();
+
+        let wrap = self.overflow == OverflowBehavior::Wrap;
+        let lines = if wrap {
+            break_lines(&resolved, main, self.gap)
+        } else {
+            std::iter::once(0..items.len()).collect()
+        };
+        let placed = if wrap {
+            // A line is as thick as its thickest item; lines of stretched
+            // items start one cell thick.
+            let thickness: Sizes = lines
+                .iter()
+                .map(|line| {
+                    items[line.clone()]
+                        .iter()
+                        .filter_map(|item| item.cross)
+                        .max()
+                        .unwrap_or(1)
+                })
+                .collect();
+            self.place_lines(&thickness, cross)
+        } else {
+            vec![(0, cross)]
+        };
+
+        let mut rects = Rects::with_capacity(items.len());
+        for (line, (offset, thickness)) in lines.into_iter().zip(placed) {
+            let count = line.len() as u64;
+            let gaps = (count.saturating_sub(1) * u64::from(self.gap)).min(u64::from(u16::MAX));
+            let available = main.saturating_sub(gaps as u16);
+            let sizes = resolve_line(&resolved[line.clone()], available);
+            let line_area = match self.direction {
+                Direction::Horizontal => {
+                    Rect::new(inner.x, inner.y + offset, inner.width, thickness)
+                }
+                Direction::Vertical => {
+                    Rect::new(inner.x + offset, inner.y, thickness, inner.height)
+                }
+            };
+            let line_rects = self.sizes_to_rects(line_area, &sizes);
+            for (rect, item) in line_rects.into_iter().zip(&items[line]) {
+                rects.push(self.align_cross(rect, item));
+            }
+        }
+
+        if self.flow_direction.is_rtl() && self.direction == Direction::Horizontal {
+            direction::mirror_rects_horizontal(&mut rects, inner);
+        }
+        rects
+    }
+
+    /// Offset and thickness of each line within `cross` cells.
+    fn place_lines(&self, thickness: &[u16], cross: u16) -> Vec<(u16, u16)> {
+        let n = thickness.len();
+        if n == 0 {
+            return Vec::new();
+        }
+        let gaps = (n as u64 - 1) * u64::from(self.line_gap);
+        let used = thickness.iter().map(|&t| u64::from(t)).sum::<u64>() + gaps;
+        let leftover = u64::from(cross).saturating_sub(used);
+
+        let mut sizes: Vec<u64> = thickness.iter().map(|&t| u64::from(t)).collect();
+        if self.align_content == AlignContent::Stretch {
+            let share = leftover as f64 / n as f64;
+            let extra = round_layout_stable(&vec![share; n], leftover as u16, None);
+            for (size, extra) in sizes.iter_mut().zip(extra) {
+                *size += u64::from(extra);
+            }
+        }
+        let start = match self.align_content {
+            AlignContent::End => leftover,
+            AlignContent::Center => leftover / 2,
+            _ => 0,
+        };
+
+        let mut placed = Vec::with_capacity(n);
+        let mut pos = start;
+        for (k, &size) in sizes.iter().enumerate() {
+            let spread = match self.align_content {
+                AlignContent::SpaceBetween if n > 1 => leftover * k as u64 / (n as u64 - 1),
+                AlignContent::SpaceAround => {
+                    let denominator = 2 * n as u64;
+                    (leftover * (2 * k as u64 + 1) + denominator / 2) / denominator
+                }
+                _ => 0,
+            };
+            let offset = (pos + spread).min(u64::from(cross));
+            let size = size.min(u64::from(cross) - offset);
+            placed.push((offset as u16, size as u16));
+            pos += sizes[k] + u64::from(self.line_gap);
+        }
+        placed
+    }
+
+    /// Apply the item's cross size and alignment within its line.
+    fn align_cross(&self, rect: Rect, item: &FlexItem) -> Rect {
+        let Some(size) = item.cross else {
+            return rect;
+        };
+        let alignment = item.align_self.unwrap_or(self.align_items);
+        let extent = match self.direction {
+            Direction::Horizontal => rect.height,
+            Direction::Vertical => rect.width,
+        };
+        let size = size.min(extent);
+        let offset = match alignment {
+            CrossAlignment::Stretch | CrossAlignment::Start => 0,
+            CrossAlignment::Center => (extent - size) / 2,
+            CrossAlignment::End => extent - size,
+        };
+        match self.direction {
+            Direction::Horizontal => Rect::new(rect.x, rect.y + offset, rect.width, size),
+            Direction::Vertical => Rect::new(rect.x + offset, rect.y, size, rect.height),
+        }
+    }
+}
+
+/// Break items into lines greedily by their hypothetical main sizes.
+fn break_lines(items: &[ResolvedItem], main: u16, gap: u16) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut used = 0u32;
+    for (i, item) in items.iter().enumerate() {
+        let size = u32::from(item.hypothetical);
+        let needed = if i == start {
+            size
+        } else {
+            used + u32::from(gap) + size
+        };
+        if i > start && needed > u32::from(main) {
+            lines.push(start..i);
+            start = i;
+            used = size;
+        } else {
+            used = needed;
+        }
+    }
+    if start < items.len() {
+        lines.push(start..items.len());
+    }
+    lines
+}
+
+/// Resolve the main sizes of one line of items sharing `available` cells.
+///
+/// Follows the CSS "resolve flexible lengths" loop: distribute free space by
+/// grow (or scaled shrink) factors, clamp to the bounds, freeze the items
+/// that hit a bound in the direction of the total violation, and repeat.
+fn resolve_line(items: &[ResolvedItem], available: u16) -> Sizes {
+    let available = f64::from(available);
+    let hypothetical: Vec<f64> = items.iter().map(|r| f64::from(r.hypothetical)).collect();
+    let growing = hypothetical.iter().sum::<f64>() <= available;
+    let weight = |r: &ResolvedItem, h: f64| if growing { r.grow } else { r.shrink * h };
+
+    let mut sizes = hypothetical.clone();
+    let mut frozen: Vec<bool> = items
+        .iter()
+        .zip(&hypothetical)
+        .map(|(r, &h)| weight(r, h) <= 0.0)
+        .collect();
+
+    loop {
+        let unfrozen: Vec<usize> = (0..items.len()).filter(|&i| !frozen[i]).collect();
+        if unfrozen.is_empty() {
+            break;
+        }
+        let fixed: f64 = (0..items.len())
+            .map(|i| if frozen[i] { sizes[i] } else { hypothetical[i] })
+            .sum();
+        let free = available - fixed;
+        let total_weight: f64 = unfrozen
+            .iter()
+            .map(|&i| weight(&items[i], hypothetical[i]))
+            .sum();
+
+        let mut violation = 0.0;
+        let mut clamped = sizes.clone();
+        for &i in &unfrozen {
+            let share = free * weight(&items[i], hypothetical[i]) / total_weight;
+            sizes[i] = hypothetical[i] + share;
+            clamped[i] = sizes[i].clamp(items[i].min, items[i].max);
+            violation += clamped[i] - sizes[i];
+        }
+
+        if violation.abs() < EPSILON {
+            // Either nothing was clamped, or min and max clamps cancel out:
+            // accept the clamped sizes for everyone.
+            for &i in &unfrozen {
+                sizes[i] = clamped[i];
+            }
+            break;
+        }
+        for &i in &unfrozen {
+            let delta = clamped[i] - sizes[i];
+            if delta * violation > 0.0 {
+                frozen[i] = true;
+                sizes[i] = clamped[i];
+            } else {
+                sizes[i] = hypothetical[i];
+            }
+        }
+    }
+
+    let total = sizes
+        .iter()
+        .sum::<f64>()
+        .round()
+        .clamp(0.0, f64::from(u16::MAX)) as u16;
+    round_layout_stable(&sizes, total, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Alignment;
+
+    fn widths(rects: &[Rect]) -> Vec<u16> {
+        rects.iter().map(|r| r.width).collect()
+    }
+
+    #[test]
+    fn grow_distributes_free_space_by_factor() {
+        let flex = Flex::horizontal().items([
+            FlexItem::fixed(10),
+            FlexItem::flex(1.0),
+            FlexItem::flex(3.0),
+        ]);
+        let rects = flex.split(Rect::new(0, 0, 50, 2));
+        assert_eq!(widths(&rects), [10, 10, 30]);
+        assert_eq!(rects[2].x, 20);
+    }
+
+    #[test]
+    fn shrink_is_scaled_by_basis_and_respects_min() {
+        let flex = Flex::horizontal().items([
+            FlexItem::new().basis(FlexBasis::Cells(40)),
+            FlexItem::new().basis(FlexBasis::Cells(20)),
+        ]);
+        // 60 cells of basis into 30: each loses half of its basis.
+        assert_eq!(widths(&flex.split(Rect::new(0, 0, 30, 1))), [20, 10]);
+
+        let flex = Flex::horizontal().items([
+            FlexItem::new().basis(FlexBasis::Cells(40)).min(35),
+            FlexItem::new().basis(FlexBasis::Cells(20)),
+        ]);
+        assert_eq!(widths(&flex.split(Rect::new(0, 0, 45, 1))), [35, 10]);
+    }
+
+    #[test]
+    fn max_clamp_frees_space_for_other_items() {
+        let flex = Flex::horizontal()
+            .gap(1)
+            .items([FlexItem::flex(1.0).max(5), FlexItem::flex(1.0)]);
+        assert_eq!(widths(&flex.split(Rect::new(0, 0, 31, 1))), [5, 25]);
+    }
+
+    #[test]
+    fn content_basis_uses_measurer() {
+        let flex = Flex::horizontal().items([FlexItem::new(), FlexItem::flex(1.0)]);
+        let rects = flex.split_with_measurer(Rect::new(0, 0, 40, 1), |i, _| match i {
+            0 => LayoutSizeHint::at_least(4, 12),
+            _ => LayoutSizeHint::ZERO,
+        });
+        assert_eq!(widths(&rects), [12, 28]);
+
+        // Shrinking stops at the measured minimum.
+        let rects = flex.split_with_measurer(Rect::new(0, 0, 3, 1), |i, _| match i {
+            0 => LayoutSizeHint::at_least(4, 12),
+            _ => LayoutSizeHint::ZERO,
+        });
+        assert_eq!(rects[0].width, 3, "clipped to the area");
+    }
+
+    #[test]
+    fn align_self_positions_items_on_cross_axis() {
+        let flex = Flex::horizontal()
+            .align_items(CrossAlignment::Center)
+            .items([
+                FlexItem::flex(1.0).cross(2),
+                FlexItem::flex(1.0).cross(2).align_self(CrossAlignment::End),
+                FlexItem::flex(1.0),
+            ]);
+        let rects = flex.split(Rect::new(0, 0, 9, 6));
+        assert_eq!(rects[0], Rect::new(0, 2, 3, 2));
+        assert_eq!(rects[1], Rect::new(3, 4, 3, 2));
+        assert_eq!(rects[2], Rect::new(6, 0, 3, 6));
+    }
+
+    #[test]
+    fn wrap_breaks_lines_and_grows_within_each() {
+        let flex = Flex::horizontal()
+            .overflow(OverflowBehavior::Wrap)
+            .gap(1)
+            .line_gap(1)
+            .align_content(AlignContent::Start)
+            .items([
+                FlexItem::fixed(6),
+                FlexItem::fixed(6).grow(1.0),
+                FlexItem::fixed(6),
+            ]);
+        let rects = flex.split(Rect::new(0, 0, 14, 10));
+        assert_eq!(rects[0], Rect::new(0, 0, 6, 1));
+        assert_eq!(rects[1], Rect::new(7, 0, 7, 1));
+        assert_eq!(rects[2], Rect::new(0, 2, 6, 1));
+    }
+
+    #[test]
+    fn align_content_distributes_lines() {
+        let base = Flex::horizontal()
+            .overflow(OverflowBehavior::Wrap)
+            .items([FlexItem::fixed(5).cross(2), FlexItem::fixed(5).cross(2)]);
+        let area = Rect::new(0, 0, 8, 10);
+
+        let stretched = base.split(area);
+        assert_eq!((stretched[0].y, stretched[1].y), (0, 5));
+        assert_eq!(stretched[0].height, 2, "explicit cross size is kept");
+
+        let ys = |content| {
+            let rects = base.clone().align_content(content).split(area);
+            (rects[0].y, rects[1].y)
+        };
+        assert_eq!(ys(AlignContent::Start), (0, 2));
+        assert_eq!(ys(AlignContent::End), (6, 8));
+        assert_eq!(ys(AlignContent::Center), (3, 5));
+        assert_eq!(ys(AlignContent::SpaceBetween), (0, 8));
+        assert_eq!(ys(AlignContent::SpaceAround), (2, 7));
+    }
+
+    #[test]
+    fn wrapped_constraints_and_justify() {
+        let flex = Flex::horizontal()
+            .overflow(OverflowBehavior::Wrap)
+            .alignment(Alignment::End)
+            .constraints([
+                Constraint::Fixed(4),
+                Constraint::Fixed(4),
+                Constraint::Fixed(4),
+            ]);
+        let rects = flex.split(Rect::new(0, 0, 10, 2));
+        assert_eq!(rects[0], Rect::new(2, 0, 4, 1));
+        assert_eq!(rects[1], Rect::new(6, 0, 4, 1));
+        assert_eq!(rects[2], Rect::new(6, 1, 4, 1));
+    }
+
+    #[test]
+    fn constraint_conversion() {
+        assert_eq!(FlexItem::from(Constraint::Fill), FlexItem::flex(1.0));
+        assert_eq!(
+            FlexItem::from(Constraint::Ratio(1, 4)).basis,
+            FlexBasis::Percentage(25.0)
+        );
+        let min = FlexItem::from(Constraint::Min(3));
+        assert_eq!((min.min, min.grow, min.shrink), (3, 1.0, 0.0));
+    }
+}
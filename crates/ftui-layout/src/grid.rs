@@ -7,6 +7,10 @@
 //! - Cell spanning (colspan, rowspan)
 //! - Named areas for semantic layout references
 //! - Gap configuration
+//! - Content-sized and `minmax()`-style tracks ([`TrackSize`])
+//! - Auto-placement of items ([`GridItem`], [`GridAutoFlow`]) with implicit tracks
+//! - Auto-fill columns that adapt their count to the width
+//! - Template-area strings like `"head head" "nav main"`
 //!
 //! # Example
 //!
@@ -36,24 +40,57 @@
 //! let header_left = layout.cell(0, 0);
 //! let content_main = layout.cell(1, 1);
 //! ```
+//!
+//! # Auto-placement
+//!
+//! ```
+//! use ftui_layout::grid::{Grid, GridItem};
+//! use ftui_core::geometry::Rect;
+//!
+//! // Cards at least 30 columns wide: 4 per row at 120 columns, 1 at 40.
+//! let grid = Grid::new()
+//!     .auto_fill_columns(30)
+//!     .items((0..6).map(|_| GridItem::auto()));
+//!
+//! let wide = grid.split(Rect::new(0, 0, 120, 20));
+//! assert_eq!(wide.num_cols(), 4);
+//! assert_eq!(wide.item(5).y, wide.cell(1, 1).y);
+//!
+//! let narrow = grid.split(Rect::new(0, 0, 40, 30));
+//! assert_eq!(narrow.num_cols(), 1);
+//! assert_eq!(narrow.num_rows(), 6);
+//! ```
 
-use crate::{Constraint, Sizes};
-use ftui_core::geometry::Rect;
+use crate::{Constraint, Direction, LayoutSizeHint, Sizes};
+use ftui_core::geometry::{Rect, Size};
 use std::collections::HashMap;
+use std::fmt;
 
 /// A 2D grid layout container.
 #[derive(Debug, Clone, Default)]
 pub struct Grid {
-    /// Row constraints (height of each row).
-    row_constraints: Vec<Constraint>,
-    /// Column constraints (width of each column).
-    col_constraints: Vec<Constraint>,
+    /// Row tracks (height of each row).
+    row_tracks: Vec<TrackSize>,
+    /// Column tracks (width of each column).
+    col_tracks: Vec<TrackSize>,
     /// Gap between rows.
     row_gap: u16,
     /// Gap between columns.
     col_gap: u16,
     /// Named areas mapping to (row, col, rowspan, colspan).
     named_areas: HashMap<String, GridArea>,
+    /// Rows and columns spanned by template areas.
+    template_size: (usize, usize),
+    /// Items placed on the grid, in order.
+    items: Vec<GridItem>,
+    /// Auto-placement direction.
+    auto_flow: GridAutoFlow,
+    /// Size of implicit rows.
+    auto_rows: TrackSize,
+    /// Size of implicit columns.
+    auto_columns: TrackSize,
+    /// Minimum column width for auto-fill columns.
+    auto_fill: Option<u16>,
     /// Overflow behavior for content exceeding the grid bounds.
     overflow: crate::OverflowBehavior,
 }
@@ -95,6 +132,309 @@ impl GridArea {
             colspan: colspan.max(1),
         }
     }
+
+    /// One past the last row.
+    #[inline]
+    fn row_end(&self) -> usize {
+        self.row + self.rowspan
+    }
+
+    /// One past the last column.
+    #[inline]
+    fn col_end(&self) -> usize {
+        self.col + self.colspan
+    }
+}
+
+/// Size of a grid track (row or column).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrackSize {
+    /// A plain layout constraint.
+    Constraint(Constraint),
+    /// CSS `minmax(min, max)`: at least `min` cells, growing up to `max`
+    /// (`None` grows like `1fr`).
+    MinMax {
+        /// Minimum size in cells.
+        min: u16,
+        /// Maximum size in cells, or `None` to share leftover space.
+        max: Option<u16>,
+    },
+    /// Sized to the largest preferred size among single-span items in the
+    /// track, as reported by the measurer (CSS `auto`).
+    Content,
+}
+
+impl Default for TrackSize {
+    fn default() -> Self {
+        Self::Constraint(Constraint::Fill)
+    }
+}
+
+impl From<Constraint> for TrackSize {
+    fn from(constraint: Constraint) -> Self {
+        Self::Constraint(constraint)
+    }
+}
+
+impl TrackSize {
+    /// The constraint used to solve this track.
+    fn constraint(self) -> Constraint {
+        match self {
+            Self::Constraint(constraint) => constraint,
+            Self::MinMax { min, max: None } => Constraint::Min(min),
+            Self::MinMax {
+                min,
+                max: Some(max),
+            } => Constraint::FitContentBounded {
+                min,
+                max: max.max(min),
+            },
+            Self::Content => Constraint::FitContent,
+        }
+    }
+}
+
+/// `count` copies of `track`, like CSS `repeat(count, track)`.
+#[must_use]
+pub fn repeat(count: usize, track: impl Into<TrackSize>) -> Vec<TrackSize> {
+    vec![track.into(); count]
+}
+
+/// Direction in which auto-placed items fill the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GridAutoFlow {
+    /// Fill each row, adding rows as needed.
+    #[default]
+    Row,
+    /// Fill each column, adding columns as needed.
+    Column,
+    /// Like [`Row`](Self::Row), but back-fill holes left by larger items.
+    RowDense,
+    /// Like [`Column`](Self::Column), but back-fill holes left by larger items.
+    ColumnDense,
+}
+
+impl GridAutoFlow {
+    fn is_row(self) -> bool {
+        matches!(self, Self::Row | Self::RowDense)
+    }
+
+    fn is_dense(self) -> bool {
+        matches!(self, Self::RowDense | Self::ColumnDense)
+    }
+}
+
+/// An item placed on the grid, explicitly or by auto-placement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridItem {
+    placement: ItemPlacement,
+    rowspan: usize,
+    colspan: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ItemPlacement {
+    Auto,
+    Area(GridArea),
+    Named(String),
+}
+
+impl GridItem {
+    /// A single-cell item placed by the grid's auto flow.
+    #[must_use]
+    pub fn auto() -> Self {
+        Self {
+            placement: ItemPlacement::Auto,
+            rowspan: 1,
+            colspan: 1,
+        }
+    }
+
+    /// An item at an explicit area.
+    #[must_use]
+    pub fn at(area: GridArea) -> Self {
+        Self {
+            placement: ItemPlacement::Area(area),
+            rowspan: area.rowspan,
+            colspan: area.colspan,
+        }
+    }
+
+    /// An item covering a named area. Unknown names are auto-placed.
+    #[must_use]
+    pub fn named(name: impl Into<String>) -> Self {
+        Self {
+            placement: ItemPlacement::Named(name.into()),
+            rowspan: 1,
+            colspan: 1,
+        }
+    }
+
+    /// Set the span of an auto-placed item.
+    #[must_use]
+    pub fn span(mut self, rowspan: usize, colspan: usize) -> Self {
+        self.rowspan = rowspan.max(1);
+        self.colspan = colspan.max(1);
+        self
+    }
+
+    /// The fixed area of this item, if it has one.
+    fn fixed_area(&self, named_areas: &HashMap<String, GridArea>) -> Option<GridArea> {
+        match &self.placement {
+            ItemPlacement::Auto => None,
+            ItemPlacement::Area(area) => Some(*area),
+            ItemPlacement::Named(name) => named_areas.get(name).copied(),
+        }
+    }
+}
+
+/// Error parsing a grid template-area string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridTemplateError {
+    /// The template has no rows.
+    Empty,
+    /// A quoted row is missing its closing quote.
+    UnterminatedRow,
+    /// A row has a different number of cells than the first row.
+    RaggedRow {
+        /// Index of the offending row.
+        row: usize,
+        /// Cells in the first row.
+        expected: usize,
+        /// Cells in this row.
+        found: usize,
+    },
+    /// The cells of a named area do not form a rectangle.
+    NotRectangular(String),
+}
+
+impl fmt::Display for GridTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "grid template has no rows"),
+            Self::UnterminatedRow => write!(f, "grid template row is missing a closing quote"),
+            Self::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "grid template row {row} has {found} cells, expected {expected}"
+            ),
+            Self::NotRectangular(name) => {
+                write!(f, "grid template area {name:?} is not a rectangle")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridTemplateError {}
+
+/// Named areas parsed from a template-area string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridTemplate {
+    areas: HashMap<String, GridArea>,
+    rows: usize,
+    cols: usize,
+}
+
+impl GridTemplate {
+    /// Parse a CSS `grid-template-areas` string.
+    ///
+    /// Rows are either double-quoted strings (`"head head" "nav main"`) or,
+    /// when the template has no quotes, non-empty lines. Cells are separated
+    /// by whitespace; a cell of dots (`.`) is left unnamed. Each name must
+    /// cover a rectangle.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GridTemplateError`] for empty or ragged templates,
+    /// unterminated quotes and non-rectangular areas.
+    pub fn parse(template: &str) -> Result<Self, GridTemplateError> {
+        let rows: Vec<&str> = if template.contains('"') {
+            let mut rows = Vec::new();
+            let mut rest = template;
+            while let Some(start) = rest.find('"') {
+                let after = &rest[start + 1..];
+                let end = after.find('"').ok_or(GridTemplateError::UnterminatedRow)?;
+                rows.push(&after[..end]);
+                rest = &after[end + 1..];
+            }
+            rows
+        } else {
+            template
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect()
+        };
+        if rows.is_empty() {
+            return Err(GridTemplateError::Empty);
+        }
+
+        let cells: Vec<Vec<&str>> = rows
+            .iter()
+            .map(|r| r.split_whitespace().collect())
+            .collect();
+        let cols = cells[0].len();
+        if cols == 0 {
+            return Err(GridTemplateError::Empty);
+        }
+        // (min row, min col, max row, max col, cell count) per name.
+        let mut bounds: HashMap<&str, (usize, usize, usize, usize, usize)> = HashMap::new();
+        for (row, line) in cells.iter().enumerate() {
+            if line.len() != cols {
+                return Err(GridTemplateError::RaggedRow {
+                    row,
+                    expected: cols,
+                    found: line.len(),
+                });
+            }
+            for (col, &name) in line.iter().enumerate() {
+                if name.chars().all(|c| c == '.') {
+                    continue;
+                }
+                let entry = bounds.entry(name).or_insert((row, col, row, col, 0));
+                entry.0 = entry.0.min(row);
+                entry.1 = entry.1.min(col);
+                entry.2 = entry.2.max(row);
+                entry.3 = entry.3.max(col);
+                entry.4 += 1;
+            }
+        }
+
+        let mut areas = HashMap::with_capacity(bounds.len());
+        for (name, (r0, c0, r1, c1, count)) in bounds {
+            let area = GridArea::span(r0, c0, r1 - r0 + 1, c1 - c0 + 1);
+            if area.rowspan * area.colspan != count {
+                return Err(GridTemplateError::NotRectangular(name.to_owned()));
+            }
+            areas.insert(name.to_owned(), area);
+        }
+        Ok(Self {
+            areas,
+            rows: rows.len(),
+            cols,
+        })
+    }
+
+    /// Named areas of the template.
+    #[must_use]
+    pub fn areas(&self) -> &HashMap<String, GridArea> {
+        &self.areas
+    }
+
+    /// Number of rows in the template.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns in the template.
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
 }
 
 /// Result of solving a grid layout.
@@ -110,6 +450,8 @@ pub struct GridLayout {
     col_positions: Sizes,
     /// Named areas from the grid definition.
     named_areas: HashMap<String, GridArea>,
+    /// Resolved area of each item, in item order.
+    items: Vec<GridArea>,
     /// Gap between rows.
     row_gap: u16,
     /// Gap between columns.
@@ -126,17 +468,32 @@ impl Grid {
         Self::default()
     }
 
-    /// Set the row constraints.
+    /// Set the row tracks.
+    ///
+    /// Accepts [`Constraint`]s or [`TrackSize`]s.
+    #[must_use]
+    pub fn rows<T: Into<TrackSize>>(mut self, tracks: impl IntoIterator<Item = T>) -> Self {
+        self.row_tracks = tracks.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the column tracks.
+    ///
+    /// Accepts [`Constraint`]s or [`TrackSize`]s.
     #[must_use]
-    pub fn rows(mut self, constraints: impl IntoIterator<Item = Constraint>) -> Self {
-        self.row_constraints = constraints.into_iter().collect();
+    pub fn columns<T: Into<TrackSize>>(mut self, tracks: impl IntoIterator<Item = T>) -> Self {
+        self.col_tracks = tracks.into_iter().map(Into::into).collect();
+        self.auto_fill = None;
         self
     }
 
-    /// Set the column constraints.
+    /// Use as many columns of at least `min_width` cells as fit, sharing
+    /// leftover width (CSS `repeat(auto-fill, minmax(min_width, 1fr))`).
+    ///
+    /// Replaces the column tracks; there is always at least one column.
     #[must_use]
-    pub fn columns(mut self, constraints: impl IntoIterator<Item = Constraint>) -> Self {
-        self.col_constraints = constraints.into_iter().collect();
+    pub fn auto_fill_columns(mut self, min_width: u16) -> Self {
+        self.auto_fill = Some(min_width);
         self
     }
 
@@ -177,6 +534,73 @@ impl Grid {
         self
     }
 
+    /// Define named areas from a template-area string.
+    ///
+    /// ```
+    /// use ftui_layout::grid::Grid;
+    /// use ftui_layout::Constraint;
+    /// use ftui_core::geometry::Rect;
+    ///
+    /// let grid = Grid::new()
+    ///     .rows([Constraint::Fixed(1), Constraint::Fill])
+    ///     .columns([Constraint::Fixed(10), Constraint::Fill])
+    ///     .template_areas(r#""head head" "nav main""#)
+    ///     .unwrap();
+    /// let layout = grid.split(Rect::new(0, 0, 40, 10));
+    /// assert_eq!(layout.area("head"), Some(Rect::new(0, 0, 40, 1)));
+    /// assert_eq!(layout.area("nav"), Some(Rect::new(0, 1, 10, 9)));
+    /// ```
+    ///
+    /// Rows and columns the template covers beyond the explicit tracks are
+    /// added as implicit tracks.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`GridTemplateError`] from [`GridTemplate::parse`].
+    pub fn template_areas(self, template: &str) -> Result<Self, GridTemplateError> {
+        GridTemplate::parse(template).map(|template| self.template(template))
+    }
+
+    /// Define named areas from a parsed [`GridTemplate`].
+    #[must_use]
+    pub fn template(mut self, template: GridTemplate) -> Self {
+        self.template_size = (
+            self.template_size.0.max(template.rows),
+            self.template_size.1.max(template.cols),
+        );
+        self.named_areas.extend(template.areas);
+        self
+    }
+
+    /// Set the items to place. Results are available from
+    /// [`GridLayout::item`] in the same order.
+    #[must_use]
+    pub fn items(mut self, items: impl IntoIterator<Item = GridItem>) -> Self {
+        self.items = items.into_iter().collect();
+        self
+    }
+
+    /// Set the auto-placement direction.
+    #[must_use]
+    pub fn auto_flow(mut self, flow: GridAutoFlow) -> Self {
+        self.auto_flow = flow;
+        self
+    }
+
+    /// Set the size of implicit rows (default [`Constraint::Fill`]).
+    #[must_use]
+    pub fn auto_rows(mut self, track: impl Into<TrackSize>) -> Self {
+        self.auto_rows = track.into();
+        self
+    }
+
+    /// Set the size of implicit columns (default [`Constraint::Fill`]).
+    #[must_use]
+    pub fn auto_columns(mut self, track: impl Into<TrackSize>) -> Self {
+        self.auto_columns = track.into();
+        self
+    }
+
     /// Set the overflow behavior for this grid.
     #[must_use]
     pub fn overflow(mut self, overflow: crate::OverflowBehavior) -> Self {
@@ -190,22 +614,83 @@ impl Grid {
         self.overflow
     }
 
-    /// Get the number of rows.
+    /// Get the number of explicit rows.
     #[inline]
     pub fn num_rows(&self) -> usize {
-        self.row_constraints.len()
+        self.row_tracks.len()
     }
 
-    /// Get the number of columns.
+    /// Get the number of explicit columns.
     #[inline]
     pub fn num_cols(&self) -> usize {
-        self.col_constraints.len()
+        self.col_tracks.len()
+    }
+
+    /// Smallest area in which every item still gets its minimum size.
+    ///
+    /// `items[i]` is the minimum size of item `i`; missing entries count as
+    /// zero. Tracks contribute their lower bound; single-span items raise
+    /// the minimum of their track, and spanning items push any remaining
+    /// shortfall into the last non-fixed track they cover. Auto-fill grids
+    /// count a single column.
+    #[must_use]
+    pub fn min_size(&self, items: &[Size]) -> Size {
+        let col_tracks = match self.auto_fill {
+            Some(min_width) => vec![TrackSize::MinMax {
+                min: min_width,
+                max: None,
+            }],
+            None => self.col_tracks.clone(),
+        };
+        let (placed, num_rows, num_cols) =
+            self.place_items(self.row_tracks.len(), col_tracks.len());
+        let row_tracks = extend_tracks(&self.row_tracks, num_rows, self.auto_rows);
+        let col_tracks = extend_tracks(&col_tracks, num_cols, self.auto_columns);
+        let item = |index: usize| items.get(index).copied().unwrap_or(Size::ZERO);
+
+        let widths = min_tracks(&col_tracks, &placed, self.col_gap, |index, area| {
+            (area.col, area.colspan, item(index).width)
+        });
+        let heights = min_tracks(&row_tracks, &placed, self.row_gap, |index, area| {
+            (area.row, area.rowspan, item(index).height)
+        });
+        Size::new(
+            total_with_gaps(&widths, self.col_gap),
+            total_with_gaps(&heights, self.row_gap),
+        )
     }
 
     /// Split the given area according to the grid configuration.
     pub fn split(&self, area: Rect) -> GridLayout {
-        let num_rows = self.row_constraints.len();
-        let num_cols = self.col_constraints.len();
+        self.split_with_measurer(area, |_, _, _| LayoutSizeHint::ZERO)
+    }
+
+    /// Split the given area, sizing [`TrackSize::Content`] tracks by the
+    /// items placed in them.
+    ///
+    /// `measurer(item, axis, available)` returns the size hint of an item
+    /// along `axis`. Columns are solved first; when measuring heights,
+    /// `available` is the width of the item's columns.
+    pub fn split_with_measurer<F>(&self, area: Rect, measurer: F) -> GridLayout
+    where
+        F: Fn(usize, Direction, u16) -> LayoutSizeHint,
+    {
+        let col_tracks = match self.auto_fill {
+            Some(min_width) => {
+                let count = auto_fill_count(area.width, min_width, self.col_gap);
+                repeat(
+                    count,
+                    TrackSize::MinMax {
+                        min: min_width,
+                        max: None,
+                    },
+                )
+            }
+            None => self.col_tracks.clone(),
+        };
+        let (items, num_rows, num_cols) = self.place_items(self.row_tracks.len(), col_tracks.len());
+        let row_tracks = extend_tracks(&self.row_tracks, num_rows, self.auto_rows);
+        let col_tracks = extend_tracks(&col_tracks, num_cols, self.auto_columns);
 
         if num_rows == 0 || num_cols == 0 || area.is_empty() {
             return GridLayout {
@@ -214,6 +699,7 @@ impl Grid {
                 row_positions: smallvec::smallvec![area.y; num_rows],
                 col_positions: smallvec::smallvec![area.x; num_cols],
                 named_areas: self.named_areas.clone(),
+                items,
                 row_gap: 0,
                 col_gap: 0,
                 bounds: area,
@@ -238,9 +724,24 @@ impl Grid {
         let available_height = area.height.saturating_sub(total_row_gap);
         let available_width = area.width.saturating_sub(total_col_gap);
 
-        // Solve constraints
-        let row_heights = crate::solve_constraints(&self.row_constraints, available_height);
-        let col_widths = crate::solve_constraints(&self.col_constraints, available_width);
+        // Solve columns first: row heights may depend on column widths.
+        let col_hints = track_hints(
+            &col_tracks,
+            &items,
+            |a| (a.col, a.colspan),
+            |i, _| measurer(i, Direction::Horizontal, available_width),
+        );
+        let col_widths = solve_tracks(&col_tracks, available_width, &col_hints);
+        let row_hints = track_hints(
+            &row_tracks,
+            &items,
+            |a| (a.row, a.rowspan),
+            |i, a| {
+                let width = span_extent(&col_widths, a.col, a.colspan, self.col_gap);
+                measurer(i, Direction::Vertical, width)
+            },
+        );
+        let row_heights = solve_tracks(&row_tracks, available_height, &row_hints);
 
         // Calculate positions
         let row_positions = self.calculate_positions(&row_heights, area.y, self.row_gap);
@@ -252,12 +753,83 @@ impl Grid {
             row_positions,
             col_positions,
             named_areas: self.named_areas.clone(),
+            items,
             row_gap: self.row_gap,
             col_gap: self.col_gap,
             bounds: area,
         }
     }
 
+    /// Resolve every item to an area and count the rows and columns of the
+    /// grid, including implicit tracks.
+    fn place_items(
+        &self,
+        explicit_rows: usize,
+        explicit_cols: usize,
+    ) -> (Vec<GridArea>, usize, usize) {
+        let mut rows = explicit_rows.max(self.template_size.0);
+        let mut cols = explicit_cols.max(self.template_size.1);
+        if self.items.is_empty() {
+            return (Vec::new(), rows, cols);
+        }
+
+        let fixed: Vec<Option<GridArea>> = self
+            .items
+            .iter()
+            .map(|item| item.fixed_area(&self.named_areas))
+            .collect();
+        for area in fixed.iter().flatten() {
+            rows = rows.max(area.row_end());
+            cols = cols.max(area.col_end());
+        }
+
+        // Work in (major, minor) coordinates: major is the flow axis, which
+        // grows; minor is the cross axis, whose size is fixed.
+        let row_flow = self.auto_flow.is_row();
+        let dense = self.auto_flow.is_dense();
+        let to_flow = |a: &GridArea| {
+            if row_flow {
+                (a.row, a.col, a.rowspan, a.colspan)
+            } else {
+                (a.col, a.row, a.colspan, a.rowspan)
+            }
+        };
+        let cross = if row_flow { cols } else { rows }.max(1);
+        let mut occupancy = Occupancy::new(cross);
+        for area in fixed.iter().flatten() {
+            let (major, minor, major_span, minor_span) = to_flow(area);
+            occupancy.mark(major, minor, major_span, minor_span);
+        }
+
+        let mut cursor = (0, 0);
+        let mut placed = Vec::with_capacity(self.items.len());
+        for (item, fixed) in self.items.iter().zip(fixed) {
+            if let Some(area) = fixed {
+                placed.push(area);
+                continue;
+            }
+            let (major_span, minor_span) = if row_flow {
+                (item.rowspan, item.colspan.min(cross))
+            } else {
+                (item.colspan, item.rowspan.min(cross))
+            };
+            let start = if dense { (0, 0) } else { cursor };
+            let (major, minor) = occupancy.find(start, major_span, minor_span);
+            occupancy.mark(major, minor, major_span, minor_span);
+            cursor = (major, minor + minor_span);
+
+            let area = if row_flow {
+                GridArea::span(major, minor, major_span, minor_span)
+            } else {
+                GridArea::span(minor, major, minor_span, major_span)
+            };
+            rows = rows.max(area.row_end());
+            cols = cols.max(area.col_end());
+            placed.push(area);
+        }
+        (placed, rows, cols)
+    }
+
     /// Calculate cumulative positions from sizes.
     fn calculate_positions(&self, sizes: &[u16], start: u16, gap: u16) -> Sizes {
         let mut positions = Sizes::with_capacity(sizes.len());
@@ -275,6 +847,178 @@ impl Grid {
     }
 }
 
+/// Cells taken by placed items, indexed `[major][minor]`.
+struct Occupancy {
+    cross: usize,
+    cells: Vec<Vec<bool>>,
+}
+
+impl Occupancy {
+    fn new(cross: usize) -> Self {
+        Self {
+            cross,
+            cells: Vec::new(),
+        }
+    }
+
+    fn is_free(&self, major: usize, minor: usize) -> bool {
+        self.cells
+            .get(major)
+            .and_then(|line| line.get(minor))
+            .is_none_or(|&taken| !taken)
+    }
+
+    fn fits(&self, major: usize, minor: usize, major_span: usize, minor_span: usize) -> bool {
+        minor + minor_span <= self.cross
+            && (major..major + major_span)
+                .all(|m| (minor..minor + minor_span).all(|n| self.is_free(m, n)))
+    }
+
+    fn mark(&mut self, major: usize, minor: usize, major_span: usize, minor_span: usize) {
+        let width = self.cross.max(minor + minor_span);
+        if self.cells.len() < major + major_span {
+            self.cells.resize(major + major_span, Vec::new());
+        }
+        for line in &mut self.cells[major..major + major_span] {
+            if line.len() < width {
+                line.resize(width, false);
+            }
+            line[minor..minor + minor_span].fill(true);
+        }
+    }
+
+    /// First free slot at or after `start` in flow order.
+    fn find(&self, start: (usize, usize), major_span: usize, minor_span: usize) -> (usize, usize) {
+        let (mut major, mut minor) = start;
+        loop {
+            while minor + minor_span <= self.cross {
+                if self.fits(major, minor, major_span, minor_span) {
+                    return (major, minor);
+                }
+                minor += 1;
+            }
+            major += 1;
+            minor = 0;
+        }
+    }
+}
+
+/// Number of auto-fill columns of at least `min_width` that fit in `width`.
+fn auto_fill_count(width: u16, min_width: u16, gap: u16) -> usize {
+    let track = u32::from(min_width.max(1)) + u32::from(gap);
+    ((u32::from(width) + u32::from(gap)) / track).max(1) as usize
+}
+
+/// Minimum size of each track given the items placed on it.
+///
+/// `span(index, area)` returns the start track, span and minimum size of an
+/// item along this axis.
+fn min_tracks<S>(tracks: &[TrackSize], items: &[GridArea], gap: u16, span: S) -> Vec<u16>
+where
+    S: Fn(usize, &GridArea) -> (usize, usize, u16),
+{
+    let mut mins: Vec<u16> = tracks
+        .iter()
+        .map(|track| crate::constraint_min(track.constraint(), 0))
+        .collect();
+    for (index, area) in items.iter().enumerate() {
+        let (start, len, min) = span(index, area);
+        if len == 1
+            && let Some(slot) = mins.get_mut(start)
+        {
+            *slot = (*slot).max(crate::constraint_min(tracks[start].constraint(), min));
+        }
+    }
+    for (index, area) in items.iter().enumerate() {
+        let (start, len, min) = span(index, area);
+        let end = start.saturating_add(len).min(mins.len());
+        if len <= 1 || start >= end {
+            continue;
+        }
+        let covered = total_with_gaps(&mins[start..end], gap);
+        let Some(last) = (start..end)
+            .rev()
+            .find(|&track| !matches!(tracks[track].constraint(), Constraint::Fixed(_)))
+        else {
+            continue;
+        };
+        mins[last] = mins[last].saturating_add(min.saturating_sub(covered));
+    }
+    mins
+}
+
+/// Sum of track sizes plus the gaps between them, saturating at `u16::MAX`.
+fn total_with_gaps(sizes: &[u16], gap: u16) -> u16 {
+    let tracks: u32 = sizes.iter().map(|&size| u32::from(size)).sum();
+    let gaps = u32::from(gap) * sizes.len().saturating_sub(1) as u32;
+    (tracks + gaps).min(u32::from(u16::MAX)) as u16
+}
+
+/// Pad `tracks` to `count` with `auto` tracks.
+fn extend_tracks(tracks: &[TrackSize], count: usize, auto: TrackSize) -> Vec<TrackSize> {
+    let mut tracks = tracks.to_vec();
+    if tracks.len() < count {
+        tracks.resize(count, auto);
+    }
+    tracks
+}
+
+/// Size hint of each track: the largest hint among single-span items in a
+/// content track, or the bounds of a `minmax` track.
+fn track_hints<S, M>(
+    tracks: &[TrackSize],
+    items: &[GridArea],
+    span: S,
+    measure: M,
+) -> Vec<LayoutSizeHint>
+where
+    S: Fn(&GridArea) -> (usize, usize),
+    M: Fn(usize, &GridArea) -> LayoutSizeHint,
+{
+    let mut hints: Vec<LayoutSizeHint> = tracks
+        .iter()
+        .map(|track| match *track {
+            TrackSize::MinMax {
+                min,
+                max: Some(max),
+            } => LayoutSizeHint::exact(max.max(min)),
+            _ => LayoutSizeHint::ZERO,
+        })
+        .collect();
+    if !tracks.contains(&TrackSize::Content) {
+        return hints;
+    }
+    for (index, area) in items.iter().enumerate() {
+        let (track, count) = span(area);
+        if count != 1 || tracks.get(track) != Some(&TrackSize::Content) {
+            continue;
+        }
+        let item = measure(index, area);
+        let hint = &mut hints[track];
+        hint.min = hint.min.max(item.min);
+        hint.preferred = hint.preferred.max(item.preferred);
+    }
+    hints
+}
+
+/// Solve track sizes with per-track hints.
+fn solve_tracks(tracks: &[TrackSize], available: u16, hints: &[LayoutSizeHint]) -> Sizes {
+    let constraints: Vec<Constraint> = tracks.iter().map(|t| t.constraint()).collect();
+    crate::solve_constraints_with_hints(&constraints, available, &|i, _| hints[i], None)
+}
+
+/// Total extent of `span` tracks starting at `start`, including gaps.
+fn span_extent(sizes: &[u16], start: usize, span: usize, gap: u16) -> u16 {
+    let end = (start + span).min(sizes.len());
+    let tracks = sizes
+        .get(start..end)
+        .unwrap_or_default()
+        .iter()
+        .fold(0u16, |acc, &s| acc.saturating_add(s));
+    let gaps = end.saturating_sub(start).saturating_sub(1) as u16;
+    tracks.saturating_add(gap.saturating_mul(gaps))
+}
+
 impl GridLayout {
     /// Get the rectangle for a specific cell.
     ///
@@ -336,6 +1080,27 @@ impl GridLayout {
             .map(|a| self.span(a.row, a.col, a.rowspan, a.colspan))
     }
 
+    /// Get the rectangle of the item at `index` (in [`Grid::items`] order).
+    ///
+    /// Returns an empty Rect for unknown indices.
+    pub fn item(&self, index: usize) -> Rect {
+        self.items.get(index).map_or_else(Rect::default, |a| {
+            self.span(a.row, a.col, a.rowspan, a.colspan)
+        })
+    }
+
+    /// Get the resolved grid area of the item at `index`.
+    #[inline]
+    pub fn item_area(&self, index: usize) -> Option<GridArea> {
+        self.items.get(index).copied()
+    }
+
+    /// Get the number of placed items.
+    #[inline]
+    pub fn num_items(&self) -> usize {
+        self.items.len()
+    }
+
     /// Get the number of rows in this layout.
     #[inline]
     pub fn num_rows(&self) -> usize {
@@ -896,4 +1661,218 @@ mod tests {
         assert_eq!(r.width, 20);
         assert_eq!(r.height, 10);
     }
+
+    #[test]
+    fn template_parses_quoted_rows() {
+        let template = GridTemplate::parse(r#""head head" "nav main" ". main""#).unwrap();
+        assert_eq!(template.rows(), 3);
+        assert_eq!(template.cols(), 2);
+        assert_eq!(template.areas()["head"], GridArea::span(0, 0, 1, 2));
+        assert_eq!(template.areas()["nav"], GridArea::cell(1, 0));
+        assert_eq!(template.areas()["main"], GridArea::span(1, 1, 2, 1));
+        assert_eq!(template.areas().len(), 3);
+    }
+
+    #[test]
+    fn template_parses_bare_lines() {
+        let template = GridTemplate::parse("a b\n a c\n").unwrap();
+        assert_eq!(template.areas()["a"], GridArea::span(0, 0, 2, 1));
+        assert_eq!(template.areas()["c"], GridArea::cell(1, 1));
+    }
+
+    #[test]
+    fn template_errors() {
+        assert_eq!(GridTemplate::parse("  "), Err(GridTemplateError::Empty));
+        assert_eq!(
+            GridTemplate::parse(r#""a b" "c"#),
+            Err(GridTemplateError::UnterminatedRow)
+        );
+        assert_eq!(
+            GridTemplate::parse(r#""a b" "c""#),
+            Err(GridTemplateError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            GridTemplate::parse(r#""a a" "a b""#),
+            Err(GridTemplateError::NotRectangular("a".into()))
+        );
+    }
+
+    #[test]
+    fn template_adds_implicit_tracks() {
+        let grid = Grid::new()
+            .template_areas(r#""head head" "nav main""#)
+            .unwrap();
+        let layout = grid.split(Rect::new(0, 0, 20, 10));
+        assert_eq!(layout.num_rows(), 2);
+        assert_eq!(layout.num_cols(), 2);
+        assert_eq!(layout.area("head"), Some(Rect::new(0, 0, 20, 5)));
+        assert_eq!(layout.area("main"), Some(Rect::new(10, 5, 10, 5)));
+    }
+
+    #[test]
+    fn auto_placement_row_flow_with_spans() {
+        let grid = Grid::new()
+            .columns(repeat(3, Constraint::Fixed(10)))
+            .auto_rows(Constraint::Fixed(2))
+            .items([
+                GridItem::auto(),
+                GridItem::auto().span(1, 2),
+                GridItem::auto().span(1, 2),
+                GridItem::auto(),
+            ]);
+        let layout = grid.split(Rect::new(0, 0, 30, 10));
+        assert_eq!(layout.item_area(0), Some(GridArea::cell(0, 0)));
+        assert_eq!(layout.item_area(1), Some(GridArea::span(0, 1, 1, 2)));
+        assert_eq!(layout.item_area(2), Some(GridArea::span(1, 0, 1, 2)));
+        // Sparse flow never goes back.
+        assert_eq!(layout.item_area(3), Some(GridArea::cell(1, 2)));
+        assert_eq!(layout.num_rows(), 2);
+        assert_eq!(layout.item(1), Rect::new(10, 0, 20, 2));
+    }
+
+    #[test]
+    fn min_size_combines_tracks_items_and_spans() {
+        let grid = Grid::new()
+            .rows([Constraint::Fixed(1), Constraint::Fill])
+            .columns([Constraint::Fixed(10), Constraint::Min(5), Constraint::Fill])
+            .gap(1)
+            .items([
+                GridItem::at(GridArea::cell(1, 1)),
+                GridItem::at(GridArea::cell(1, 2)),
+                GridItem::at(GridArea::span(0, 0, 1, 3)),
+            ]);
+        let items = [Size::new(8, 4), Size::new(2, 2), Size::new(40, 1)];
+        // Columns 10 + 8 + 2 widened to 40 by the spanning item (2 gaps).
+        assert_eq!(grid.min_size(&items), Size::new(40, 6));
+        assert_eq!(Grid::new().min_size(&[]), Size::ZERO);
+    }
+
+    #[test]
+    fn auto_placement_skips_fixed_items() {
+        let grid = Grid::new()
+            .columns(repeat(2, Constraint::Fill))
+            .area("hero", GridArea::cell(0, 0))
+            .items([GridItem::auto(), GridItem::named("hero"), GridItem::auto()]);
+        let layout = grid.split(Rect::new(0, 0, 20, 10));
+        assert_eq!(layout.item_area(0), Some(GridArea::cell(0, 1)));
+        assert_eq!(layout.item_area(1), Some(GridArea::cell(0, 0)));
+        assert_eq!(layout.item_area(2), Some(GridArea::cell(1, 0)));
+    }
+
+    #[test]
+    fn dense_flow_backfills_holes() {
+        let items = [
+            GridItem::auto().span(1, 2),
+            GridItem::auto().span(1, 2),
+            GridItem::auto(),
+        ];
+        let sparse = Grid::new()
+            .columns(repeat(3, Constraint::Fill))
+            .items(items.clone())
+            .split(Rect::new(0, 0, 30, 10));
+        assert_eq!(sparse.item_area(2), Some(GridArea::cell(1, 2)));
+
+        let dense = Grid::new()
+            .columns(repeat(3, Constraint::Fill))
+            .auto_flow(GridAutoFlow::RowDense)
+            .items(items)
+            .split(Rect::new(0, 0, 30, 10));
+        assert_eq!(dense.item_area(2), Some(GridArea::cell(0, 2)));
+    }
+
+    #[test]
+    fn column_flow_fills_columns() {
+        let grid = Grid::new()
+            .rows(repeat(2, Constraint::Fill))
+            .auto_flow(GridAutoFlow::Column)
+            .items((0..5).map(|_| GridItem::auto()));
+        let layout = grid.split(Rect::new(0, 0, 30, 10));
+        assert_eq!(layout.num_cols(), 3);
+        assert_eq!(layout.item_area(1), Some(GridArea::cell(1, 0)));
+        assert_eq!(layout.item_area(2), Some(GridArea::cell(0, 1)));
+        assert_eq!(layout.item_area(4), Some(GridArea::cell(0, 2)));
+    }
+
+    #[test]
+    fn oversized_span_is_clamped_to_cross_axis() {
+        let grid = Grid::new()
+            .columns(repeat(2, Constraint::Fill))
+            .items([GridItem::auto().span(1, 5)]);
+        let layout = grid.split(Rect::new(0, 0, 20, 4));
+        assert_eq!(layout.item_area(0), Some(GridArea::span(0, 0, 1, 2)));
+    }
+
+    #[test]
+    fn auto_fill_reflows_columns() {
+        let grid = Grid::new()
+            .auto_fill_columns(20)
+            .col_gap(1)
+            .items((0..4).map(|_| GridItem::auto()));
+        for (width, cols, rows) in [(83, 4, 1), (62, 3, 2), (41, 2, 2), (30, 1, 4), (5, 1, 4)] {
+            let layout = grid.split(Rect::new(0, 0, width, 40));
+            assert_eq!(layout.num_cols(), cols, "width {width}");
+            assert_eq!(layout.num_rows(), rows, "width {width}");
+        }
+        let layout = grid.split(Rect::new(0, 0, 83, 40));
+        assert_eq!(layout.item(3), Rect::new(63, 0, 20, 40));
+    }
+
+    #[test]
+    fn content_tracks_use_measurer() {
+        let grid = Grid::new()
+            .rows([
+                TrackSize::Content,
+                TrackSize::Content,
+                Constraint::Fill.into(),
+            ])
+            .columns([TrackSize::Content, Constraint::Fill.into()])
+            .items((0..4).map(|_| GridItem::auto()));
+        let layout = grid.split_with_measurer(Rect::new(0, 0, 40, 20), |item, axis, available| {
+            match axis {
+                Direction::Horizontal => LayoutSizeHint::exact(if item == 2 { 12 } else { 6 }),
+                Direction::Vertical => {
+                    // Row height depends on the width the item was given.
+                    LayoutSizeHint::exact(60 / available.max(1))
+                }
+            }
+        });
+        assert_eq!(layout.col_width(0), 12);
+        assert_eq!(layout.col_width(1), 28);
+        // Row 0: items 0 (12 wide -> 5) and 1 (28 wide -> 2).
+        assert_eq!(layout.row_height(0), 5);
+        assert_eq!(layout.row_height(1), 5);
+        assert_eq!(layout.row_height(2), 10);
+    }
+
+    #[test]
+    fn minmax_tracks() {
+        let grid = Grid::new().rows([Constraint::Fixed(1)]).columns([
+            TrackSize::MinMax {
+                min: 5,
+                max: Some(10),
+            },
+            TrackSize::MinMax { min: 8, max: None },
+        ]);
+        let wide = grid.split(Rect::new(0, 0, 40, 1));
+        assert_eq!(wide.col_width(0), 10);
+        assert_eq!(wide.col_width(1), 30);
+        let narrow = grid.split(Rect::new(0, 0, 14, 1));
+        assert_eq!(narrow.col_width(0), 6);
+        assert_eq!(narrow.col_width(1), 8);
+    }
+
+    #[test]
+    fn split_without_items_matches_explicit_tracks() {
+        let grid = Grid::new()
+            .rows([Constraint::Fixed(3), Constraint::Fill])
+            .columns([Constraint::Percentage(25.0), Constraint::Fill]);
+        let layout = grid.split(Rect::new(0, 0, 40, 10));
+        assert_eq!(layout.num_items(), 0);
+        assert_eq!(layout.item(0), Rect::default());
+        assert_eq!(layout.cell(1, 1), Rect::new(10, 3, 30, 7));
+    }
 }
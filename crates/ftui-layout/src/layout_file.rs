@@ -0,0 +1,1161 @@
+#![forbid(unsafe_code)]
+
+//! Declarative layout files with hot reload.
+//!
+//! A [`LayoutFile`] describes a tree of flex, grid, slot, and pane nodes as
+//! data. It loads from JSON, or from TOML with the `layout-toml` feature, and
+//! compiles into a validated [`LayoutTemplate`]. Errors point at the source
+//! line for malformed documents and at the node path (for example
+//! `root.children[1].size`) for invalid values.
+//!
+//! At render time [`LayoutTemplate::resolve`] turns an area into named
+//! rectangles. [`LayoutFileWatcher`] re-reads the file when it changes on
+//! disk and keeps the last good template if an edit fails to load, so
+//! layouts can be tweaked while the app runs.
+//!
+//! # Example
+//!
+//! ```toml
+//! version = 1
+//!
+//! [root]
+//! type = "flex"
+//! direction = "vertical"
+//!
+//! [[root.children]]
+//! type = "slot"
+//! name = "header"
+//! size = 3
+//!
+//! [[root.children]]
+//! type = "flex"
+//! direction = "horizontal"
+//! direction_at = { xs = "vertical", md = "horizontal" }
+//! size = "fill"
+//! gap = 1
+//!
+//! [[root.children.children]]
+//! type = "pane"
+//! name = "main"
+//! size = "2/3"
+//!
+//! [[root.children.children]]
+//! type = "slot"
+//! name = "sidebar"
+//! size = "fill"
+//! when = { min = "md" }
+//! ```
+//!
+//! ```ignore
+//! let mut watcher = LayoutFileWatcher::open("dashboard.toml")?;
+//! // On each tick:
+//! if let LayoutReload::Failed(err) = watcher.poll() {
+//!     tracing::warn!("layout reload failed: {err}");
+//! }
+//! let resolved = watcher.template().resolve(frame.area());
+//! if let Some(header) = resolved.rect("header") {
+//!     // draw the header
+//! }
+//! ```
+//!
+//! # Sizes
+//!
+//! Node `size` values and grid tracks use a compact syntax:
+//!
+//! | Syntax        | Constraint                               |
+//! |---------------|------------------------------------------|
+//! | `12`, `"12"`  | `Fixed(12)`                              |
+//! | `"25%"`       | `Percentage(25.0)`                       |
+//! | `"min:5"`     | `Min(5)`                                 |
+//! | `"max:40"`    | `Max(40)`                                |
+//! | `"1/3"`       | `Ratio(1, 3)`                            |
+//! | `"fill"`      | `Fill`                                   |
+//! | `"fit"`       | `FitContent`                             |
+//! | `"fit:2..10"` | `FitContentBounded { min: 2, max: 10 }`  |
+//! | `"fit-min"`   | `FitMin`                                 |
+//!
+//! Grid tracks also accept `"content"`. A node without a `size` fills.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::grid::{Grid, GridItem, GridTemplate, TrackSize};
+use crate::{
+    Alignment, Breakpoint, Breakpoints, Constraint, Direction, Flex, Rect, Responsive, Sides,
+};
+
+/// Current layout file schema version.
+pub const LAYOUT_FILE_VERSION: u16 = 1;
+
+fn default_layout_file_version() -> u16 {
+    LAYOUT_FILE_VERSION
+}
+
+/// Serializable layout document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutFile {
+    #[serde(default = "default_layout_file_version")]
+    pub version: u16,
+    /// Breakpoint thresholds; defaults to [`Breakpoints::DEFAULT`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakpoints: Option<LayoutFileBreakpoints>,
+    pub root: LayoutFileNode,
+}
+
+/// Minimum widths for each breakpoint tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutFileBreakpoints {
+    pub sm: u16,
+    pub md: u16,
+    pub lg: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xl: Option<u16>,
+}
+
+/// One node of a layout document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutFileNode {
+    /// Name under which the resolved rectangle is published.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Size within a parent flex; defaults to `"fill"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<LayoutFileSize>,
+    /// Template area within a parent grid; auto-placed when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    /// Breakpoint range in which the node is present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<LayoutFileWhen>,
+    #[serde(flatten)]
+    pub kind: LayoutFileNodeKind,
+}
+
+/// Node variants.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LayoutFileNodeKind {
+    /// One-dimensional split of the node's area among its children.
+    Flex {
+        #[serde(default)]
+        direction: LayoutFileDirection,
+        /// Per-breakpoint direction overrides, keyed by breakpoint name.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        direction_at: BTreeMap<String, LayoutFileDirection>,
+        #[serde(default)]
+        gap: u16,
+        #[serde(default)]
+        margin: u16,
+        #[serde(default)]
+        align: LayoutFileAlign,
+        #[serde(default)]
+        children: Vec<LayoutFileNode>,
+    },
+    /// Two-dimensional grid with optional template areas.
+    Grid {
+        #[serde(default)]
+        rows: Vec<LayoutFileSize>,
+        #[serde(default)]
+        columns: Vec<LayoutFileSize>,
+        #[serde(default)]
+        gap: u16,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        row_gap: Option<u16>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        col_gap: Option<u16>,
+        /// Template rows, e.g. `["head head", "nav main"]`.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        areas: Vec<String>,
+        #[serde(default)]
+        children: Vec<LayoutFileNode>,
+    },
+    /// Named region the application draws into.
+    Slot,
+    /// Named region that hosts a pane workspace.
+    Pane,
+}
+
+/// Flex direction in layout files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutFileDirection {
+    #[default]
+    Vertical,
+    Horizontal,
+}
+
+impl From<LayoutFileDirection> for Direction {
+    fn from(direction: LayoutFileDirection) -> Self {
+        match direction {
+            LayoutFileDirection::Vertical => Self::Vertical,
+            LayoutFileDirection::Horizontal => Self::Horizontal,
+        }
+    }
+}
+
+/// Flex main-axis alignment in layout files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutFileAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceAround,
+    SpaceBetween,
+}
+
+impl From<LayoutFileAlign> for Alignment {
+    fn from(align: LayoutFileAlign) -> Self {
+        match align {
+            LayoutFileAlign::Start => Self::Start,
+            LayoutFileAlign::Center => Self::Center,
+            LayoutFileAlign::End => Self::End,
+            LayoutFileAlign::SpaceAround => Self::SpaceAround,
+            LayoutFileAlign::SpaceBetween => Self::SpaceBetween,
+        }
+    }
+}
+
+/// Size value: a bare cell count or a string in the size syntax.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LayoutFileSize {
+    Cells(u16),
+    Text(String),
+}
+
+/// Inclusive breakpoint range, by breakpoint name.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LayoutFileWhen {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+}
+
+/// Source format of a layout document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutFileFormat {
+    Json,
+    Toml,
+}
+
+impl LayoutFileFormat {
+    /// Format implied by a file extension; anything but `.toml` is JSON.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+}
+
+impl fmt::Display for LayoutFileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "JSON"),
+            Self::Toml => write!(f, "TOML"),
+        }
+    }
+}
+
+/// Errors while loading a layout document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutFileError {
+    /// The source is malformed or does not match the schema.
+    Parse {
+        format: LayoutFileFormat,
+        /// 1-based line.
+        line: usize,
+        /// 1-based column.
+        column: usize,
+        message: String,
+    },
+    /// A node holds an invalid value.
+    Invalid { path: String, message: String },
+    /// The document declares a schema version this build cannot read.
+    UnsupportedVersion { found: u16, supported: u16 },
+    /// Support for the format was not compiled in.
+    UnsupportedFormat { format: LayoutFileFormat },
+    /// The file could not be read.
+    Io { path: PathBuf, message: String },
+}
+
+impl fmt::Display for LayoutFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse {
+                format,
+                line,
+                column,
+                message,
+            } => write!(f, "{format} layout error at {line}:{column}: {message}"),
+            Self::Invalid { path, message } => write!(f, "invalid layout at {path}: {message}"),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported layout file version {found} (supported: {supported})"
+            ),
+            Self::UnsupportedFormat { format } => {
+                write!(f, "{format} layout files are not enabled in this build")
+            }
+            Self::Io { path, message } => {
+                write!(f, "cannot read layout file {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutFileError {}
+
+impl LayoutFile {
+    /// Parse a JSON document without validating it.
+    pub fn from_json_str(source: &str) -> Result<Self, LayoutFileError> {
+        serde_json::from_str(source).map_err(|err| LayoutFileError::Parse {
+            format: LayoutFileFormat::Json,
+            line: err.line(),
+            column: err.column(),
+            message: err.to_string(),
+        })
+    }
+
+    /// Parse a TOML document without validating it.
+    #[cfg(feature = "layout-toml")]
+    pub fn from_toml_str(source: &str) -> Result<Self, LayoutFileError> {
+        toml::from_str(source).map_err(|err| {
+            let (line, column) = err
+                .span()
+                .map_or((1, 1), |span| line_column(source, span.start));
+            LayoutFileError::Parse {
+                format: LayoutFileFormat::Toml,
+                line,
+                column,
+                message: err.message().to_string(),
+            }
+        })
+    }
+
+    /// Parse a document in the given format without validating it.
+    pub fn parse(source: &str, format: LayoutFileFormat) -> Result<Self, LayoutFileError> {
+        match format {
+            LayoutFileFormat::Json => Self::from_json_str(source),
+            #[cfg(feature = "layout-toml")]
+            LayoutFileFormat::Toml => Self::from_toml_str(source),
+            #[cfg(not(feature = "layout-toml"))]
+            LayoutFileFormat::Toml => Err(LayoutFileError::UnsupportedFormat { format }),
+        }
+    }
+
+    /// Validate the document and compile it for resolution.
+    pub fn compile(&self) -> Result<LayoutTemplate, LayoutFileError> {
+        if self.version != LAYOUT_FILE_VERSION {
+            return Err(LayoutFileError::UnsupportedVersion {
+                found: self.version,
+                supported: LAYOUT_FILE_VERSION,
+            });
+        }
+        let breakpoints = self
+            .breakpoints
+            .map_or(Breakpoints::DEFAULT, |bp| match bp.xl {
+                Some(xl) => Breakpoints::new_with_xl(bp.sm, bp.md, bp.lg, xl),
+                None => Breakpoints::new(bp.sm, bp.md, bp.lg),
+            });
+        let mut names = HashSet::new();
+        let root = compile_node(&self.root, "root".to_string(), None, &mut names)?;
+        Ok(LayoutTemplate { breakpoints, root })
+    }
+}
+
+/// 1-based line and column of a byte offset.
+#[cfg(feature = "layout-toml")]
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |tail| tail.chars().count())
+        + 1;
+    (line, column)
+}
+
+/// Validated layout ready to resolve against an area.
+#[derive(Debug, Clone)]
+pub struct LayoutTemplate {
+    breakpoints: Breakpoints,
+    root: TemplateNode,
+}
+
+#[derive(Debug, Clone)]
+struct TemplateNode {
+    name: Option<String>,
+    size: Constraint,
+    area: Option<String>,
+    min: Breakpoint,
+    max: Breakpoint,
+    kind: TemplateKind,
+}
+
+#[derive(Debug, Clone)]
+enum TemplateKind {
+    Flex {
+        direction: Responsive<Direction>,
+        gap: u16,
+        margin: u16,
+        alignment: Alignment,
+        children: Vec<TemplateNode>,
+    },
+    Grid {
+        grid: Grid,
+        children: Vec<TemplateNode>,
+    },
+    Slot,
+    Pane,
+}
+
+/// Which container a node sits in, for validating `size` and `area`.
+enum ParentKind<'a> {
+    Flex,
+    Grid { areas: &'a HashSet<String> },
+}
+
+fn invalid(path: &str, message: impl Into<String>) -> LayoutFileError {
+    LayoutFileError::Invalid {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+fn compile_node(
+    node: &LayoutFileNode,
+    path: String,
+    parent: Option<ParentKind<'_>>,
+    names: &mut HashSet<String>,
+) -> Result<TemplateNode, LayoutFileError> {
+    if let Some(name) = &node.name {
+        if name.is_empty() {
+            return Err(invalid(&format!("{path}.name"), "name must not be empty"));
+        }
+        if !names.insert(name.clone()) {
+            return Err(invalid(
+                &format!("{path}.name"),
+                format!("duplicate node name {name:?}"),
+            ));
+        }
+    }
+
+    let size = match &node.size {
+        Some(size) => {
+            parse_constraint(size).map_err(|msg| invalid(&format!("{path}.size"), msg))?
+        }
+        None => Constraint::Fill,
+    };
+    if node.size.is_some() && !matches!(parent, Some(ParentKind::Flex)) {
+        return Err(invalid(
+            &format!("{path}.size"),
+            "size only applies to children of a flex node",
+        ));
+    }
+    match (&node.area, &parent) {
+        (None, _) => {}
+        (Some(area), Some(ParentKind::Grid { areas })) => {
+            if !areas.contains(area) {
+                return Err(invalid(
+                    &format!("{path}.area"),
+                    format!("grid has no template area {area:?}"),
+                ));
+            }
+        }
+        (Some(_), _) => {
+            return Err(invalid(
+                &format!("{path}.area"),
+                "area only applies to children of a grid node",
+            ));
+        }
+    }
+
+    let (min, max) = match &node.when {
+        Some(when) => {
+            let min = when
+                .min
+                .as_deref()
+                .map(|name| parse_breakpoint(name, &format!("{path}.when.min")))
+                .transpose()?
+                .unwrap_or(Breakpoint::Xs);
+            let max = when
+                .max
+                .as_deref()
+                .map(|name| parse_breakpoint(name, &format!("{path}.when.max")))
+                .transpose()?
+                .unwrap_or(Breakpoint::Xl);
+            if min > max {
+                return Err(invalid(
+                    &format!("{path}.when"),
+                    format!("min {min:?} is above max {max:?}"),
+                ));
+            }
+            (min, max)
+        }
+        None => (Breakpoint::Xs, Breakpoint::Xl),
+    };
+
+    let kind = match &node.kind {
+        LayoutFileNodeKind::Flex {
+            direction,
+            direction_at,
+            gap,
+            margin,
+            align,
+            children,
+        } => {
+            let mut responsive = Responsive::new(Direction::from(*direction));
+            for (name, direction) in direction_at {
+                let breakpoint = parse_breakpoint(name, &format!("{path}.direction_at.{name}"))?;
+                responsive.set(breakpoint, Direction::from(*direction));
+            }
+            let children = children
+                .iter()
+                .enumerate()
+                .map(|(index, child)| {
+                    compile_node(
+                        child,
+                        format!("{path}.children[{index}]"),
+                        Some(ParentKind::Flex),
+                        names,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            TemplateKind::Flex {
+                direction: responsive,
+                gap: *gap,
+                margin: *margin,
+                alignment: Alignment::from(*align),
+                children,
+            }
+        }
+        LayoutFileNodeKind::Grid {
+            rows,
+            columns,
+            gap,
+            row_gap,
+            col_gap,
+            areas,
+            children,
+        } => {
+            let rows = compile_tracks(rows, &format!("{path}.rows"))?;
+            let columns = compile_tracks(columns, &format!("{path}.columns"))?;
+            let mut grid = Grid::new()
+                .rows(rows)
+                .columns(columns)
+                .row_gap(row_gap.unwrap_or(*gap))
+                .col_gap(col_gap.unwrap_or(*gap));
+            let mut area_names = HashSet::new();
+            if !areas.is_empty() {
+                let template = GridTemplate::parse(&areas.join("\n"))
+                    .map_err(|err| invalid(&format!("{path}.areas"), err.to_string()))?;
+                area_names.extend(template.areas().keys().cloned());
+                grid = grid.template(template);
+            }
+            let children = children
+                .iter()
+                .enumerate()
+                .map(|(index, child)| {
+                    compile_node(
+                        child,
+                        format!("{path}.children[{index}]"),
+                        Some(ParentKind::Grid { areas: &area_names }),
+                        names,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            TemplateKind::Grid { grid, children }
+        }
+        LayoutFileNodeKind::Slot | LayoutFileNodeKind::Pane => {
+            if node.name.is_none() {
+                return Err(invalid(&path, "slot and pane nodes need a name"));
+            }
+            if matches!(node.kind, LayoutFileNodeKind::Pane) {
+                TemplateKind::Pane
+            } else {
+                TemplateKind::Slot
+            }
+        }
+    };
+
+    Ok(TemplateNode {
+        name: node.name.clone(),
+        size,
+        area: node.area.clone(),
+        min,
+        max,
+        kind,
+    })
+}
+
+fn compile_tracks(
+    tracks: &[LayoutFileSize],
+    path: &str,
+) -> Result<Vec<TrackSize>, LayoutFileError> {
+    tracks
+        .iter()
+        .enumerate()
+        .map(|(index, track)| match track {
+            LayoutFileSize::Text(text) if text.trim() == "content" => Ok(TrackSize::Content),
+            _ => parse_constraint(track)
+                .map(TrackSize::from)
+                .map_err(|msg| invalid(&format!("{path}[{index}]"), msg)),
+        })
+        .collect()
+}
+
+fn parse_breakpoint(name: &str, path: &str) -> Result<Breakpoint, LayoutFileError> {
+    match name {
+        "xs" => Ok(Breakpoint::Xs),
+        "sm" => Ok(Breakpoint::Sm),
+        "md" => Ok(Breakpoint::Md),
+        "lg" => Ok(Breakpoint::Lg),
+        "xl" => Ok(Breakpoint::Xl),
+        other => Err(invalid(
+            path,
+            format!("unknown breakpoint {other:?} (expected xs, sm, md, lg or xl)"),
+        )),
+    }
+}
+
+/// Parse the size syntax described in the module docs.
+fn parse_constraint(size: &LayoutFileSize) -> Result<Constraint, String> {
+    let text = match size {
+        LayoutFileSize::Cells(cells) => return Ok(Constraint::Fixed(*cells)),
+        LayoutFileSize::Text(text) => text.trim(),
+    };
+    let cells = |value: &str| {
+        value
+            .trim()
+            .parse::<u16>()
+            .map_err(|_| format!("expected a cell count in {text:?}"))
+    };
+    match text {
+        "fill" => return Ok(Constraint::Fill),
+        "fit" => return Ok(Constraint::FitContent),
+        "fit-min" => return Ok(Constraint::FitMin),
+        _ => {}
+    }
+    if let Some(percent) = text.strip_suffix('%') {
+        let value = percent
+            .trim()
+            .parse::<f32>()
+            .map_err(|_| format!("expected a percentage in {text:?}"))?;
+        if !(0.0..=100.0).contains(&value) {
+            return Err(format!("percentage {value} is outside 0..=100"));
+        }
+        return Ok(Constraint::Percentage(value));
+    }
+    if let Some(value) = text.strip_prefix("min:") {
+        return cells(value).map(Constraint::Min);
+    }
+    if let Some(value) = text.strip_prefix("max:") {
+        return cells(value).map(Constraint::Max);
+    }
+    if let Some(range) = text.strip_prefix("fit:") {
+        let (min, max) = range
+            .split_once("..")
+            .ok_or_else(|| format!("expected fit:MIN..MAX, got {text:?}"))?;
+        let (min, max) = (cells(min)?, cells(max)?);
+        if min > max {
+            return Err(format!("fit bounds {min}..{max} are reversed"));
+        }
+        return Ok(Constraint::FitContentBounded { min, max });
+    }
+    if let Some((numerator, denominator)) = text.split_once('/') {
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("expected a ratio like 1/3, got {text:?}"))
+        };
+        let (numerator, denominator) = (parse(numerator)?, parse(denominator)?);
+        if denominator == 0 {
+            return Err(format!("ratio {text:?} has a zero denominator"));
+        }
+        return Ok(Constraint::Ratio(numerator, denominator));
+    }
+    text.parse::<u16>()
+        .map(Constraint::Fixed)
+        .map_err(|_| format!("unrecognized size {text:?}"))
+}
+
+impl TemplateNode {
+    fn present_at(&self, breakpoint: Breakpoint) -> bool {
+        (self.min..=self.max).contains(&breakpoint)
+    }
+}
+
+impl LayoutTemplate {
+    /// Parse, validate, and compile a JSON document.
+    pub fn from_json_str(source: &str) -> Result<Self, LayoutFileError> {
+        LayoutFile::from_json_str(source)?.compile()
+    }
+
+    /// Parse, validate, and compile a TOML document.
+    #[cfg(feature = "layout-toml")]
+    pub fn from_toml_str(source: &str) -> Result<Self, LayoutFileError> {
+        LayoutFile::from_toml_str(source)?.compile()
+    }
+
+    /// Load a layout file, choosing the format from its extension.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, LayoutFileError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|err| LayoutFileError::Io {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })?;
+        LayoutFile::parse(&source, LayoutFileFormat::from_path(path))?.compile()
+    }
+
+    /// Breakpoint thresholds used to classify the resolved area.
+    #[must_use]
+    pub const fn breakpoints(&self) -> Breakpoints {
+        self.breakpoints
+    }
+
+    /// Resolve every named node against `area`.
+    ///
+    /// The breakpoint comes from the area width. Nodes outside their `when`
+    /// range are skipped along with their subtree, and flex siblings share
+    /// the space without them.
+    #[must_use]
+    pub fn resolve(&self, area: Rect) -> ResolvedLayout {
+        let breakpoint = self.breakpoints.classify_width(area.width);
+        let mut resolved = ResolvedLayout {
+            area,
+            breakpoint,
+            rects: BTreeMap::new(),
+            panes: Vec::new(),
+        };
+        if self.root.present_at(breakpoint) {
+            resolve_node(&self.root, area, breakpoint, &mut resolved);
+        }
+        resolved
+    }
+}
+
+fn resolve_node(
+    node: &TemplateNode,
+    area: Rect,
+    breakpoint: Breakpoint,
+    resolved: &mut ResolvedLayout,
+) {
+    if let Some(name) = &node.name {
+        resolved.rects.insert(name.clone(), area);
+    }
+    match &node.kind {
+        TemplateKind::Flex {
+            direction,
+            gap,
+            margin,
+            alignment,
+            children,
+        } => {
+            let present = children
+                .iter()
+                .filter(|child| child.present_at(breakpoint))
+                .collect::<Vec<_>>();
+            let rects = Flex::default()
+                .direction(*direction.resolve(breakpoint))
+                .constraints(present.iter().map(|child| child.size))
+                .gap(*gap)
+                .margin(Sides::all(*margin))
+                .alignment(*alignment)
+                .split(area);
+            for (child, rect) in present.into_iter().zip(rects) {
+                resolve_node(child, rect, breakpoint, resolved);
+            }
+        }
+        TemplateKind::Grid { grid, children } => {
+            let present = children
+                .iter()
+                .filter(|child| child.present_at(breakpoint))
+                .collect::<Vec<_>>();
+            let layout = grid
+                .clone()
+                .items(present.iter().map(|child| {
+                    child
+                        .area
+                        .as_ref()
+                        .map_or_else(GridItem::auto, |area| GridItem::named(area.clone()))
+                }))
+                .split(area);
+            for (index, child) in present.into_iter().enumerate() {
+                resolve_node(child, layout.item(index), breakpoint, resolved);
+            }
+        }
+        TemplateKind::Slot => {}
+        TemplateKind::Pane => {
+            if let Some(name) = &node.name {
+                resolved.panes.push(name.clone());
+            }
+        }
+    }
+}
+
+/// Named rectangles produced by [`LayoutTemplate::resolve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedLayout {
+    pub area: Rect,
+    pub breakpoint: Breakpoint,
+    rects: BTreeMap<String, Rect>,
+    panes: Vec<String>,
+}
+
+impl ResolvedLayout {
+    /// Rectangle of a named node, if it is present at this size.
+    #[must_use]
+    pub fn rect(&self, name: &str) -> Option<Rect> {
+        self.rects.get(name).copied()
+    }
+
+    /// Iterate named rectangles in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Rect)> + '_ {
+        self.rects.iter().map(|(name, rect)| (name.as_str(), *rect))
+    }
+
+    /// Names of present pane nodes, in document order.
+    ///
+    /// Each pane's rectangle is the area to hand to
+    /// [`PaneTree::solve_layout`](crate::PaneTree::solve_layout).
+    #[must_use]
+    pub fn panes(&self) -> &[String] {
+        &self.panes
+    }
+}
+
+/// Outcome of [`LayoutFileWatcher::poll`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutReload {
+    /// The file has not changed since the last poll.
+    Unchanged,
+    /// The file changed and the new template is active.
+    Reloaded,
+    /// The file changed but failed to load; the previous template stays
+    /// active.
+    Failed(LayoutFileError),
+}
+
+/// Polls a layout file and recompiles it when it changes.
+///
+/// Changes are detected from the file's modification time and length, so
+/// polling is cheap enough to run on every tick.
+#[derive(Debug, Clone)]
+pub struct LayoutFileWatcher {
+    path: PathBuf,
+    stamp: Option<(Option<SystemTime>, u64)>,
+    template: LayoutTemplate,
+    last_error: Option<LayoutFileError>,
+}
+
+impl LayoutFileWatcher {
+    /// Load the file for the first time.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, LayoutFileError> {
+        let path = path.into();
+        let stamp = file_stamp(&path);
+        let template = LayoutTemplate::from_path(&path)?;
+        Ok(Self {
+            path,
+            stamp,
+            template,
+            last_error: None,
+        })
+    }
+
+    /// Watched path.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Most recent template that loaded successfully.
+    #[must_use]
+    pub const fn template(&self) -> &LayoutTemplate {
+        &self.template
+    }
+
+    /// Error from the latest reload attempt, cleared by a successful reload.
+    #[must_use]
+    pub const fn last_error(&self) -> Option<&LayoutFileError> {
+        self.last_error.as_ref()
+    }
+
+    /// Reload the file if it changed since the last poll.
+    pub fn poll(&mut self) -> LayoutReload {
+        let stamp = file_stamp(&self.path);
+        if stamp == self.stamp {
+            return LayoutReload::Unchanged;
+        }
+        self.stamp = stamp;
+        match LayoutTemplate::from_path(&self.path) {
+            Ok(template) => {
+                self.template = template;
+                self.last_error = None;
+                LayoutReload::Reloaded
+            }
+            Err(err) => {
+                self.last_error = Some(err.clone());
+                LayoutReload::Failed(err)
+            }
+        }
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<(Option<SystemTime>, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DASHBOARD: &str = r#"{
+        "version": 1,
+        "root": {
+            "type": "flex",
+            "direction": "vertical",
+            "children": [
+                { "type": "slot", "name": "header", "size": 3 },
+                {
+                    "type": "flex",
+                    "name": "body",
+                    "direction": "horizontal",
+                    "direction_at": { "xs": "vertical", "md": "horizontal" },
+                    "size": "fill",
+                    "children": [
+                        { "type": "pane", "name": "main", "size": "2/3" },
+                        { "type": "slot", "name": "sidebar", "size": "fill", "when": { "min": "md" } }
+                    ]
+                },
+                { "type": "slot", "name": "status", "size": "1" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn json_layout_resolves_named_rects() {
+        let template = LayoutTemplate::from_json_str(DASHBOARD).expect("valid layout");
+        let resolved = template.resolve(Rect::new(0, 0, 120, 40));
+
+        assert_eq!(resolved.breakpoint, Breakpoint::Lg);
+        assert_eq!(resolved.rect("header"), Some(Rect::new(0, 0, 120, 3)));
+        assert_eq!(resolved.rect("status"), Some(Rect::new(0, 39, 120, 1)));
+        assert_eq!(resolved.rect("main"), Some(Rect::new(0, 3, 80, 36)));
+        assert_eq!(resolved.rect("sidebar"), Some(Rect::new(80, 3, 40, 36)));
+        assert_eq!(resolved.panes(), ["main".to_string()]);
+        assert_eq!(resolved.iter().count(), 5);
+    }
+
+    #[test]
+    fn breakpoints_hide_nodes_and_switch_direction() {
+        let template = LayoutTemplate::from_json_str(DASHBOARD).expect("valid layout");
+        let resolved = template.resolve(Rect::new(0, 0, 50, 40));
+
+        assert_eq!(resolved.breakpoint, Breakpoint::Xs);
+        assert_eq!(resolved.rect("sidebar"), None);
+        let body = resolved.rect("body").expect("body present");
+        let main = resolved.rect("main").expect("main present");
+        assert_eq!(main.width, body.width);
+        assert_eq!(main.height, 24);
+    }
+
+    #[test]
+    fn grid_children_land_in_template_areas() {
+        let source = r#"{
+            "root": {
+                "type": "grid",
+                "rows": [1, "fill"],
+                "columns": [10, "fill"],
+                "areas": ["head head", "nav main"],
+                "children": [
+                    { "type": "slot", "name": "content", "area": "main" },
+                    { "type": "slot", "name": "title", "area": "head" },
+                    { "type": "slot", "name": "menu" }
+                ]
+            }
+        }"#;
+        let template = LayoutTemplate::from_json_str(source).expect("valid layout");
+        let resolved = template.resolve(Rect::new(0, 0, 40, 10));
+
+        assert_eq!(resolved.rect("title"), Some(Rect::new(0, 0, 40, 1)));
+        assert_eq!(resolved.rect("content"), Some(Rect::new(10, 1, 30, 9)));
+        assert_eq!(resolved.rect("menu"), Some(Rect::new(0, 1, 10, 9)));
+    }
+
+    #[test]
+    fn size_syntax_parses_every_constraint() {
+        let cases = [
+            (LayoutFileSize::Cells(4), Constraint::Fixed(4)),
+            (LayoutFileSize::Text("7".into()), Constraint::Fixed(7)),
+            (
+                LayoutFileSize::Text("25%".into()),
+                Constraint::Percentage(25.0),
+            ),
+            (LayoutFileSize::Text("min:5".into()), Constraint::Min(5)),
+            (LayoutFileSize::Text("max:40".into()), Constraint::Max(40)),
+            (LayoutFileSize::Text("1/3".into()), Constraint::Ratio(1, 3)),
+            (LayoutFileSize::Text("fill".into()), Constraint::Fill),
+            (LayoutFileSize::Text("fit".into()), Constraint::FitContent),
+            (
+                LayoutFileSize::Text("fit:2..10".into()),
+                Constraint::FitContentBounded { min: 2, max: 10 },
+            ),
+            (LayoutFileSize::Text("fit-min".into()), Constraint::FitMin),
+        ];
+        for (size, expected) in cases {
+            assert_eq!(parse_constraint(&size), Ok(expected), "{size:?}");
+        }
+        assert!(parse_constraint(&LayoutFileSize::Text("150%".into())).is_err());
+        assert!(parse_constraint(&LayoutFileSize::Text("1/0".into())).is_err());
+        assert!(parse_constraint(&LayoutFileSize::Text("wide".into())).is_err());
+    }
+
+    #[test]
+    fn validation_errors_name_the_node_path() {
+        let bad_size = DASHBOARD.replace(r#""size": "2/3""#, r#""size": "two thirds""#);
+        let err = LayoutTemplate::from_json_str(&bad_size).expect_err("bad size");
+        assert!(matches!(
+            &err,
+            LayoutFileError::Invalid { path, .. } if path == "root.children[1].children[0].size"
+        ));
+        assert!(err.to_string().contains("two thirds"));
+
+        let duplicate = DASHBOARD.replace(r#""name": "status""#, r#""name": "header""#);
+        let err = LayoutTemplate::from_json_str(&duplicate).expect_err("duplicate name");
+        assert!(matches!(
+            &err,
+            LayoutFileError::Invalid { path, .. } if path == "root.children[2].name"
+        ));
+
+        let unnamed = r#"{ "root": { "type": "flex", "children": [ { "type": "slot" } ] } }"#;
+        let err = LayoutTemplate::from_json_str(unnamed).expect_err("unnamed slot");
+        assert!(matches!(
+            &err,
+            LayoutFileError::Invalid { path, .. } if path == "root.children[0]"
+        ));
+
+        let bad_breakpoint = DASHBOARD.replace(r#""min": "md""#, r#""min": "huge""#);
+        let err = LayoutTemplate::from_json_str(&bad_breakpoint).expect_err("bad breakpoint");
+        assert!(matches!(
+            &err,
+            LayoutFileError::Invalid { path, .. } if path == "root.children[1].children[1].when.min"
+        ));
+
+        let unknown_area = r#"{ "root": { "type": "grid", "areas": ["a b"],
+            "children": [ { "type": "slot", "name": "x", "area": "c" } ] } }"#;
+        let err = LayoutTemplate::from_json_str(unknown_area).expect_err("unknown area");
+        assert!(matches!(
+            &err,
+            LayoutFileError::Invalid { path, .. } if path == "root.children[0].area"
+        ));
+    }
+
+    #[test]
+    fn parse_errors_report_line_and_column() {
+        let source = "{\n  \"root\": {\n    \"type\": \"flux\"\n  }\n}";
+        let err = LayoutTemplate::from_json_str(source).expect_err("unknown node type");
+        let LayoutFileError::Parse { format, line, .. } = err else {
+            unreachable!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(format, LayoutFileFormat::Json);
+        assert!(line >= 3, "line {line} should point into the root object");
+
+        let err = LayoutTemplate::from_json_str(
+            r#"{ "version": 9, "root": { "type": "slot", "name": "a" } }"#,
+        )
+        .expect_err("future version");
+        assert_eq!(
+            err,
+            LayoutFileError::UnsupportedVersion {
+                found: 9,
+                supported: LAYOUT_FILE_VERSION,
+            }
+        );
+    }
+
+    #[test]
+    fn layout_file_round_trips_through_json() {
+        let file = LayoutFile::from_json_str(DASHBOARD).expect("parse");
+        let json = serde_json::to_string(&file).expect("serialize");
+        let reparsed = LayoutFile::from_json_str(&json).expect("reparse");
+        assert_eq!(reparsed, file);
+    }
+
+    #[cfg(feature = "layout-toml")]
+    #[test]
+    fn toml_layout_matches_json_layout() {
+        let source = r#"
+version = 1
+
+[root]
+type = "flex"
+direction = "vertical"
+
+[[root.children]]
+type = "slot"
+name = "header"
+size = 3
+
+[[root.children]]
+type = "pane"
+name = "main"
+"#;
+        let template = LayoutTemplate::from_toml_str(source).expect("valid toml layout");
+        let resolved = template.resolve(Rect::new(0, 0, 80, 24));
+        assert_eq!(resolved.rect("header"), Some(Rect::new(0, 0, 80, 3)));
+        assert_eq!(resolved.rect("main"), Some(Rect::new(0, 3, 80, 21)));
+
+        let err = LayoutTemplate::from_toml_str("[root]\ntype = \"slot\"\nname = 3\n")
+            .expect_err("bad name type");
+        assert!(matches!(
+            err,
+            LayoutFileError::Parse {
+                format: LayoutFileFormat::Toml,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn watcher_reloads_changes_and_keeps_last_good_template() {
+        let path = std::env::temp_dir().join(format!(
+            "ftui-layout-watch-{}-{:?}.json",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, DASHBOARD).expect("write layout");
+
+        let mut watcher = LayoutFileWatcher::open(&path).expect("open layout");
+        assert_eq!(watcher.poll(), LayoutReload::Unchanged);
+
+        let taller_header = DASHBOARD.replace(r#""size": 3"#, r#""size": 5 "#);
+        std::fs::write(&path, &taller_header).expect("rewrite layout");
+        assert_eq!(watcher.poll(), LayoutReload::Reloaded);
+        let area = Rect::new(0, 0, 120, 40);
+        assert_eq!(
+            watcher.template().resolve(area).rect("header"),
+            Some(Rect::new(0, 0, 120, 5))
+        );
+
+        std::fs::write(&path, "{ not json").expect("break layout");
+        assert!(matches!(watcher.poll(), LayoutReload::Failed(_)));
+        assert!(watcher.last_error().is_some());
+        assert_eq!(
+            watcher.template().resolve(area).rect("header"),
+            Some(Rect::new(0, 0, 120, 5))
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
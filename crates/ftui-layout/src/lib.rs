@@ -7,8 +7,12 @@
 //! - [`Flex`] - 1D constraint-based layout (rows or columns)
 //! - [`Grid`] - 2D constraint-based layout with cell spanning
 //! - [`Constraint`] - Size constraints (Fixed, Percentage, Min, Max, Ratio, FitContent)
+//! - [`FlexItem`] - CSS-style grow/shrink/basis items with wrapping
 //! - [`debug`] - Layout constraint debugging and introspection
 //! - [`cache`] - Layout result caching for memoization
+//! - [`cassowary`] - Linear constraint solver for layouts that relate regions
+//! - [`layout_file`] - Declarative JSON/TOML layouts with hot reload
+//! - [`transition`] - Animated interpolation between successive layouts
 //!
 //! # Role in FrankenTUI
 //! `ftui-layout` is the geometry solver for widgets and screens. It converts
@@ -40,12 +44,15 @@
 //! ```
 
 pub mod cache;
+pub mod cassowary;
 pub mod debug;
 pub mod dep_graph;
 pub mod direction;
 pub mod egraph;
+pub mod flex_item;
 pub mod grid;
 pub mod incremental;
+pub mod layout_file;
 pub mod pane;
 #[cfg(test)]
 mod repro_max_constraint;
@@ -53,6 +60,7 @@ mod repro_max_constraint;
 mod repro_space_around;
 pub mod responsive;
 pub mod responsive_layout;
+pub mod transition;
 pub mod veb_tree;
 pub mod visibility;
 pub mod workspace;
@@ -60,20 +68,29 @@ pub mod workspace;
 pub use cache::{
     CoherenceCache, CoherenceId, LayoutCache, LayoutCacheKey, LayoutCacheStats, S3FifoLayoutCache,
 };
+pub use cassowary::{ConstraintLayout, RectVars};
 pub use direction::{FlowDirection, LogicalAlignment, LogicalSides, mirror_rects_horizontal};
+pub use flex_item::{AlignContent, CrossAlignment, FlexBasis, FlexItem};
 pub use ftui_core::geometry::{Rect, Sides, Size};
-pub use grid::{Grid, GridArea, GridLayout};
+pub use grid::{
+    Grid, GridArea, GridAutoFlow, GridItem, GridLayout, GridTemplate, GridTemplateError, TrackSize,
+};
+pub use layout_file::{
+    LAYOUT_FILE_VERSION, LayoutFile, LayoutFileError, LayoutFileFormat, LayoutFileNode,
+    LayoutFileNodeKind, LayoutFileWatcher, LayoutReload, LayoutTemplate, ResolvedLayout,
+};
 pub use pane::{
     PANE_DEFAULT_MARGIN_CELLS, PANE_DEFAULT_PADDING_CELLS, PANE_DRAG_RESIZE_DEFAULT_HYSTERESIS,
     PANE_DRAG_RESIZE_DEFAULT_THRESHOLD, PANE_EDGE_GRIP_INSET_CELLS, PANE_MAGNETIC_FIELD_CELLS,
     PANE_SEMANTIC_INPUT_EVENT_SCHEMA_VERSION, PANE_SEMANTIC_INPUT_TRACE_SCHEMA_VERSION,
     PANE_SNAP_DEFAULT_HYSTERESIS_BPS, PANE_SNAP_DEFAULT_STEP_BPS, PANE_TREE_SCHEMA_VERSION,
-    PaneCancelReason, PaneConstraints, PaneCoordinateNormalizationError, PaneCoordinateNormalizer,
-    PaneCoordinateRoundingPolicy, PaneDockPreview, PaneDockZone, PaneDragBehaviorTuning,
-    PaneDragResizeEffect, PaneDragResizeMachine, PaneDragResizeMachineError,
-    PaneDragResizeNoopReason, PaneDragResizeState, PaneDragResizeTransition, PaneEdgeResizePlan,
-    PaneEdgeResizePlanError, PaneGroupTransformPlan, PaneId, PaneIdAllocator, PaneInertialThrow,
-    PaneInputCoordinate, PaneInteractionPolicyError, PaneInteractionTimeline,
+    PaneCancelReason, PaneCommand, PaneCommandPlan, PaneCommandPlanError, PaneConstraints,
+    PaneCoordinateNormalizationError, PaneCoordinateNormalizer, PaneCoordinateRoundingPolicy,
+    PaneDirection, PaneDockPreview, PaneDockZone, PaneDragBehaviorTuning, PaneDragResizeEffect,
+    PaneDragResizeMachine, PaneDragResizeMachineError, PaneDragResizeNoopReason,
+    PaneDragResizeState, PaneDragResizeTransition, PaneEdgeResizePlan, PaneEdgeResizePlanError,
+    PaneFloating, PaneFloatingRect, PaneGroupTransformPlan, PaneId, PaneIdAllocator,
+    PaneInertialThrow, PaneInputCoordinate, PaneInteractionPolicyError, PaneInteractionTimeline,
     PaneInteractionTimelineCheckpointDecision, PaneInteractionTimelineEntry,
     PaneInteractionTimelineError, PaneInteractionTimelineReplayDiagnostics,
     PaneInteractionTimelineRetentionDiagnostics, PaneInvariantCode, PaneInvariantIssue,
@@ -93,10 +110,11 @@ pub use pane::{
     PaneSplitRatio, PaneTransaction, PaneTransactionOutcome, PaneTree, PaneTreeSnapshot, SplitAxis,
 };
 pub use responsive::Responsive;
-pub use responsive_layout::{ResponsiveLayout, ResponsiveSplit};
+pub use responsive_layout::{ContainerQuery, ResponsiveLayout, ResponsiveSplit};
 pub use smallvec;
 use smallvec::SmallVec;
 use std::cmp::min;
+pub use transition::{LayoutDiff, LayoutTransition, RectChange, TransitionMotion};
 pub use visibility::Visibility;
 pub use workspace::{
     MigrationResult, WORKSPACE_SCHEMA_VERSION, WorkspaceMetadata, WorkspaceMigrationError,
@@ -278,7 +296,8 @@ pub enum OverflowBehavior {
         max_content: Option<u16>,
     },
     /// Items that don't fit are wrapped to the next row/column.
-    /// Only meaningful for [`Flex`] containers.
+    /// Only meaningful for [`Flex`] containers; lines are placed with
+    /// [`Flex::align_content`] and [`Flex::line_gap`].
     Wrap,
 }
 
@@ -505,6 +524,10 @@ pub struct Flex {
     alignment: Alignment,
     flow_direction: direction::FlowDirection,
     overflow: OverflowBehavior,
+    items: Vec<FlexItem>,
+    align_items: CrossAlignment,
+    align_content: AlignContent,
+    line_gap: u16,
 }
 
 impl Flex {
@@ -579,16 +602,128 @@ impl Flex {
         self
     }
 
+    /// Set flex items (grow, shrink, basis, bounds, cross alignment).
+    ///
+    /// When items are set they replace the constraints; see [`flex_item`].
+    #[must_use]
+    pub fn items(mut self, items: impl IntoIterator<Item = FlexItem>) -> Self {
+        self.items = items.into_iter().collect();
+        self
+    }
+
+    /// Set the default cross-axis alignment of flex items.
+    #[must_use]
+    pub fn align_items(mut self, alignment: CrossAlignment) -> Self {
+        self.align_items = alignment;
+        self
+    }
+
+    /// Set how wrapped lines share the cross axis.
+    #[must_use]
+    pub fn align_content(mut self, align_content: AlignContent) -> Self {
+        self.align_content = align_content;
+        self
+    }
+
+    /// Set the gap between wrapped lines.
+    #[must_use]
+    pub fn line_gap(mut self, gap: u16) -> Self {
+        self.line_gap = gap;
+        self
+    }
+
     /// Get the current overflow behavior.
     #[must_use]
     pub fn overflow_behavior(&self) -> OverflowBehavior {
         self.overflow
     }
 
-    /// Number of constraints (and thus output rects from [`split`](Self::split)).
+    /// Cache key for splitting `area` with this layout.
+    ///
+    /// Covers every parameter that affects [`split`](Self::split): besides
+    /// the constraints and direction it folds in margin, gaps, alignment,
+    /// flow direction, overflow and the flex items, so two layouts share a
+    /// key only when they produce the same rects.
+    #[must_use]
+    pub fn cache_key(&self, area: Rect) -> LayoutCacheKey {
+        use std::hash::{Hash, Hasher};
+
+        let mut params = std::collections::hash_map::DefaultHasher::new();
+        let Sides {
+            top,
+            right,
+            bottom,
+            left,
+        } = self.margin;
+        (top, right, bottom, left, self.gap, self.line_gap).hash(&mut params);
+        (self.alignment as u8, self.flow_direction, self.overflow).hash(&mut params);
+        (self.align_items, self.align_content).hash(&mut params);
+        self.items.len().hash(&mut params);
+        for item in &self.items {
+            item.hash_into(&mut params);
+        }
+        LayoutCacheKey::new(area, &self.constraints, self.direction, None)
+            .with_variant(params.finish())
+    }
+
+    /// Number of constraints, or of flex items when set (and thus output
+    /// rects from [`split`](Self::split)).
     #[must_use]
     pub fn constraint_count(&self) -> usize {
-        self.constraints.len()
+        if self.items.is_empty() {
+            self.constraints.len()
+        } else {
+            self.items.len()
+        }
+    }
+
+    /// Smallest area in which every child still gets its minimum size.
+    ///
+    /// `children[i]` is the minimum size of the child in slot `i`; missing
+    /// entries count as zero. `Fixed` slots need exactly their size, `Min`
+    /// and bounded fit slots at least their lower bound, and `Max` slots at
+    /// most their cap. Proportional slots only need their child's minimum,
+    /// so for percentage and ratio layouts the result is a lower bound.
+    /// Margins and gaps are included; with [`OverflowBehavior::Wrap`] each
+    /// child may take a line of its own.
+    #[must_use]
+    pub fn min_size(&self, children: &[Size]) -> Size {
+        type Axis = fn(Size) -> u16;
+        let (main_of, cross_of): (Axis, Axis) = match self.direction {
+            Direction::Horizontal => (|size| size.width, |size| size.height),
+            Direction::Vertical => (|size| size.height, |size| size.width),
+        };
+        let count = self.constraint_count();
+        let child = |index: usize| children.get(index).copied().unwrap_or(Size::ZERO);
+        let slot_main = |index: usize| {
+            let content = main_of(child(index));
+            match self.items.get(index) {
+                Some(item) => item.min_main(content),
+                None => constraint_min(self.constraints[index], content),
+            }
+        };
+        let gaps = |gap: u16| u32::from(gap) * count.saturating_sub(1) as u32;
+
+        let (main, cross) = if matches!(self.overflow, OverflowBehavior::Wrap) {
+            let main = (0..count).map(|i| u32::from(slot_main(i))).max();
+            let cross = (0..count)
+                .map(|i| u32::from(cross_of(child(i))))
+                .sum::<u32>();
+            (main.unwrap_or(0), cross + gaps(self.line_gap))
+        } else {
+            let main = (0..count).map(|i| u32::from(slot_main(i))).sum::<u32>();
+            let cross = (0..count).map(|i| u32::from(cross_of(child(i)))).max();
+            (main + gaps(self.gap), cross.unwrap_or(0))
+        };
+        let clamp = |value: u32| value.min(u32::from(u16::MAX)) as u16;
+        let (width, height) = match self.direction {
+            Direction::Horizontal => (main, cross),
+            Direction::Vertical => (cross, main),
+        };
+        Size::new(
+            clamp(width + u32::from(self.margin.horizontal_sum())),
+            clamp(height + u32::from(self.margin.vertical_sum())),
+        )
     }
 
     /// Split the given area into smaller rectangles according to the configuration.
@@ -596,7 +731,12 @@ impl Flex {
         // Apply margin
         let inner = area.inner(self.margin);
         if inner.is_empty() {
-            return self.constraints.iter().map(|_| Rect::default()).collect();
+            return (0..self.constraint_count())
+                .map(|_| Rect::default())
+                .collect();
+        }
+        if let Some(items) = self.flex_items() {
+            return self.split_items(inner, &items, &|_, _| LayoutSizeHint::ZERO);
         }
 
         let total_size = match self.direction {
@@ -775,7 +915,12 @@ impl Flex {
         // Apply margin
         let inner = area.inner(self.margin);
         if inner.is_empty() {
-            return self.constraints.iter().map(|_| Rect::default()).collect();
+            return (0..self.constraint_count())
+                .map(|_| Rect::default())
+                .collect();
+        }
+        if let Some(items) = self.flex_items() {
+            return self.split_items(inner, &items, &measurer);
         }
 
         let total_size = match self.direction {
@@ -823,7 +968,12 @@ impl Flex {
         // Apply margin
         let inner = area.inner(self.margin);
         if inner.is_empty() {
-            return self.constraints.iter().map(|_| Rect::default()).collect();
+            return (0..self.constraint_count())
+                .map(|_| Rect::default())
+                .collect();
+        }
+        if let Some(items) = self.flex_items() {
+            return self.split_items(inner, &items, &measurer);
         }
 
         let total_size = match self.direction {
@@ -862,6 +1012,23 @@ impl Flex {
     }
 }
 
+/// Minimum size of a slot under `constraint` whose content needs `content`.
+///
+/// Shared by [`Flex::min_size`] and [`Grid::min_size`](grid::Grid::min_size).
+pub(crate) fn constraint_min(constraint: Constraint, content: u16) -> u16 {
+    match constraint {
+        Constraint::Fixed(size) => size,
+        Constraint::Min(min) => content.max(min),
+        Constraint::Max(max) => content.min(max),
+        Constraint::FitContentBounded { min, max } => content.clamp(min, max.max(min)),
+        Constraint::Percentage(_)
+        | Constraint::Ratio(_, _)
+        | Constraint::Fill
+        | Constraint::FitContent
+        | Constraint::FitMin => content,
+    }
+}
+
 /// Solve 1D constraints to determine sizes.
 ///
 /// This shared logic is used by both Flex and Grid layouts.
@@ -1323,6 +1490,40 @@ fn redistribute_overflow(floors: &[u16], total: u16) -> Sizes {
 mod tests {
     use super::*;
 
+    #[test]
+    fn min_size_sums_main_axis_and_maxes_cross_axis() {
+        let flex = Flex::horizontal()
+            .gap(1)
+            .margin(Sides::all(1))
+            .constraints([
+                Constraint::Fixed(10),
+                Constraint::Min(8),
+                Constraint::Max(4),
+                Constraint::Fill,
+            ]);
+        let children = [
+            Size::new(30, 2),
+            Size::new(5, 6),
+            Size::new(9, 1),
+            Size::new(7, 3),
+        ];
+        // 10 + 8 + 4 + 7 content, 3 gaps, 2 margin columns; tallest child + margins.
+        assert_eq!(flex.min_size(&children), Size::new(34, 8));
+
+        let column = Flex::vertical().constraints([Constraint::Fixed(3), Constraint::Fill]);
+        assert_eq!(column.min_size(&[Size::new(20, 1)]), Size::new(20, 3));
+    }
+
+    #[test]
+    fn min_size_wrapping_takes_widest_item() {
+        let flex = Flex::horizontal()
+            .overflow(OverflowBehavior::Wrap)
+            .line_gap(1)
+            .items([FlexItem::fixed(12), FlexItem::new().min(5)]);
+        let children = [Size::new(0, 2), Size::new(3, 4)];
+        assert_eq!(flex.min_size(&children), Size::new(12, 7));
+    }
+
     #[test]
     fn fixed_split() {
         let flex = Flex::horizontal().constraints([Constraint::Fixed(10), Constraint::Fixed(20)]);
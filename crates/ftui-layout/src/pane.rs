@@ -7,11 +7,15 @@
 //! - Explicit parent/child relationships for split trees.
 //! - Canonical serialization snapshots with forward-compatible extension bags.
 //! - Strict validation that rejects malformed trees.
+//! - Tabbed leaves, a non-destructive zoom state, and floating subtrees with
+//!   their own placement and z-order.
+//! - Keyboard commands ([`PaneCommand`]) that plan into the same operations
+//!   as pointer interactions.
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
-use ftui_core::geometry::{Rect, Sides};
+use ftui_core::geometry::{Rect, Sides, Size};
 use serde::{Deserialize, Serialize};
 
 /// Current pane tree schema version.
@@ -190,7 +194,16 @@ impl Default for PaneConstraints {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PaneLeaf {
     /// Host-provided stable surface key (for replay/diff mapping).
+    ///
+    /// For tabbed leaves this is the surface key of the active tab.
     pub surface_key: String,
+    /// Ordered tab stack of surface keys. Empty for a single-surface leaf;
+    /// otherwise holds at least two tabs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tabs: Vec<String>,
+    /// Index of the active tab in `tabs`.
+    #[serde(default, skip_serializing_if = "is_zero_usize")]
+    pub active_tab: usize,
     /// Forward-compatible extension bag.
     #[serde(
         default,
@@ -206,9 +219,76 @@ impl PaneLeaf {
     pub fn new(surface_key: impl Into<String>) -> Self {
         Self {
             surface_key: surface_key.into(),
+            tabs: Vec::new(),
+            active_tab: 0,
             extensions: BTreeMap::new(),
         }
     }
+
+    /// Build a leaf holding an ordered stack of tabs.
+    ///
+    /// `active` is clamped into range. An empty stack yields a leaf with an
+    /// empty surface key.
+    #[must_use]
+    pub fn tabbed<I, S>(surface_keys: I, active: usize) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut leaf = Self::new(String::new());
+        leaf.set_tab_stack(surface_keys.into_iter().map(Into::into).collect(), active);
+        leaf
+    }
+
+    /// Number of tabs (1 for a leaf without a tab stack).
+    #[must_use]
+    pub fn tab_count(&self) -> usize {
+        self.tabs.len().max(1)
+    }
+
+    /// Surface key of the tab at `index`.
+    #[must_use]
+    pub fn tab(&self, index: usize) -> Option<&str> {
+        if self.tabs.is_empty() {
+            return (index == 0).then_some(self.surface_key.as_str());
+        }
+        self.tabs.get(index).map(String::as_str)
+    }
+
+    /// Index of the active tab.
+    #[must_use]
+    pub const fn active_tab(&self) -> usize {
+        self.active_tab
+    }
+
+    fn tab_stack(&self) -> Vec<String> {
+        if self.tabs.is_empty() {
+            vec![self.surface_key.clone()]
+        } else {
+            self.tabs.clone()
+        }
+    }
+
+    /// Replace the tab stack, keeping `surface_key` in sync with the active
+    /// tab. A single tab is stored without a stack.
+    fn set_tab_stack(&mut self, mut tabs: Vec<String>, active: usize) {
+        let active = active.min(tabs.len().saturating_sub(1));
+        self.surface_key = tabs.get(active).cloned().unwrap_or_default();
+        if tabs.len() <= 1 {
+            tabs.clear();
+            self.active_tab = 0;
+        } else {
+            self.active_tab = active;
+        }
+        self.tabs = tabs;
+    }
+
+    fn tab_stack_is_consistent(&self) -> bool {
+        if self.tabs.is_empty() {
+            return self.active_tab == 0;
+        }
+        self.tabs.len() > 1 && self.tabs.get(self.active_tab) == Some(&self.surface_key)
+    }
 }
 
 /// Split payload with child references.
@@ -269,6 +349,73 @@ impl PaneNodeRecord {
     }
 }
 
+fn is_zero_usize(value: &usize) -> bool {
+    *value == 0
+}
+
+fn check_tab_index(
+    node_id: PaneId,
+    leaf: &PaneLeaf,
+    index: usize,
+) -> Result<(), PaneOperationFailure> {
+    let tab_count = leaf.tab_count();
+    if index >= tab_count {
+        return Err(PaneOperationFailure::TabOutOfRange {
+            node_id,
+            index,
+            tab_count,
+        });
+    }
+    Ok(())
+}
+
+/// Placement of a floating pane, relative to the layout area origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaneFloatingRect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl PaneFloatingRect {
+    /// Create a floating placement.
+    #[must_use]
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Resolve against a layout area, clipping to its bounds.
+    #[must_use]
+    pub fn resolve(self, area: Rect) -> Rect {
+        Rect::new(
+            area.x.saturating_add(self.x),
+            area.y.saturating_add(self.y),
+            self.width,
+            self.height,
+        )
+        .intersection(&area)
+    }
+}
+
+/// A pane subtree floating above the split tree.
+///
+/// The subtree root has no parent; it is reachable through this record
+/// instead of the tree root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaneFloating {
+    /// Root of the floating subtree.
+    pub id: PaneId,
+    pub rect: PaneFloatingRect,
+    /// Stacking order; higher values are drawn on top.
+    pub z: u32,
+}
+
 /// Canonical serialized pane tree shape.
 ///
 /// The extension maps are reserved for forward-compatible fields.
@@ -279,6 +426,12 @@ pub struct PaneTreeSnapshot {
     pub root: PaneId,
     pub next_id: PaneId,
     pub nodes: Vec<PaneNodeRecord>,
+    /// Docked node maximized to the full layout area, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zoomed: Option<PaneId>,
+    /// Floating subtrees, bottom to top.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub floating: Vec<PaneFloating>,
     #[serde(default)]
     pub extensions: BTreeMap<String, String>,
 }
@@ -288,9 +441,12 @@ fn default_schema_version() -> u16 {
 }
 
 impl PaneTreeSnapshot {
-    /// Canonicalize node ordering by ID for deterministic serialization.
+    /// Canonicalize node ordering by ID and floating panes by stacking order
+    /// for deterministic serialization.
     pub fn canonicalize(&mut self) {
         self.nodes.sort_by_key(|node| node.id);
+        self.floating
+            .sort_by_key(|floating| (floating.z, floating.id));
     }
 
     /// Deterministic hash for diagnostics over serialized tree state.
@@ -341,6 +497,9 @@ pub enum PaneInvariantCode {
     CycleDetected,
     UnreachableNode,
     NextIdNotGreaterThanExisting,
+    InvalidTabStack,
+    InvalidFloatingRoot,
+    InvalidZoomTarget,
 }
 
 /// One actionable invariant finding.
@@ -402,6 +561,9 @@ pub enum PaneRepairAction {
         before: PaneId,
         after: PaneId,
     },
+    ClearZoom {
+        node_id: PaneId,
+    },
 }
 
 /// Outcome from successful safe repair pass.
@@ -475,6 +637,8 @@ impl std::error::Error for PaneRepairError {
 pub struct PaneLayout {
     pub area: Rect,
     rects: BTreeMap<PaneId, Rect>,
+    /// Solved floating subtree roots, bottom to top.
+    floating: Vec<PaneId>,
 }
 
 impl PaneLayout {
@@ -484,6 +648,15 @@ impl PaneLayout {
         self.rects.get(&node_id).copied()
     }
 
+    /// Floating subtree roots in this layout, bottom to top.
+    ///
+    /// Hosts draw the docked panes first, then each floating subtree in
+    /// this order.
+    #[must_use]
+    pub fn floating(&self) -> &[PaneId] {
+        &self.floating
+    }
+
     /// Iterate all solved rectangles in deterministic ID order.
     pub fn iter(&self) -> impl Iterator<Item = (PaneId, Rect)> + '_ {
         self.rects.iter().map(|(node_id, rect)| (*node_id, *rect))
@@ -903,16 +1076,175 @@ pub enum PaneLayoutIntelligenceMode {
     Compact,
 }
 
+/// Cardinal direction for keyboard pane commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaneDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl PaneDirection {
+    /// Split axis this direction travels along.
+    #[must_use]
+    pub const fn axis(self) -> SplitAxis {
+        match self {
+            Self::Left | Self::Right => SplitAxis::Horizontal,
+            Self::Up | Self::Down => SplitAxis::Vertical,
+        }
+    }
+
+    /// Whether this direction points from a split's first child toward its
+    /// second child.
+    #[must_use]
+    pub const fn toward_second(self) -> bool {
+        matches!(self, Self::Right | Self::Down)
+    }
+
+    const fn dock_zone(self) -> PaneDockZone {
+        match self {
+            Self::Left => PaneDockZone::Left,
+            Self::Right => PaneDockZone::Right,
+            Self::Up => PaneDockZone::Top,
+            Self::Down => PaneDockZone::Bottom,
+        }
+    }
+}
+
+/// Keyboard-driven pane management command acting on the focused leaf.
+///
+/// Commands plan into ordinary [`PaneOperation`]s, so they undo, redo, and
+/// replay through [`PaneInteractionTimeline`] exactly like pointer gestures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum PaneCommand {
+    /// Move focus to the nearest leaf in `direction`.
+    FocusNeighbor { direction: PaneDirection },
+    /// Exchange the focused leaf with its neighbor in `direction`.
+    SwapWithNeighbor { direction: PaneDirection },
+    /// Re-dock the focused leaf on the `direction` side of its neighbor.
+    MoveToward { direction: PaneDirection },
+    /// Flip the orientation of the split directly containing the focused leaf.
+    RotateSplit,
+    /// Give every pane in the focused tree an equal share of its axis.
+    Equalize,
+    /// Move the nearest split border on `direction`'s axis by `cells`
+    /// toward `direction`.
+    ResizeBy {
+        direction: PaneDirection,
+        cells: u16,
+    },
+    /// Close the focused leaf and let its sibling take the space.
+    Close,
+    /// Move the focused leaf's tabs into its neighbor in `direction`, then
+    /// close it.
+    MergeInto { direction: PaneDirection },
+}
+
+/// Planned keyboard command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneCommandPlan {
+    pub command: PaneCommand,
+    /// Leaf that should hold focus once the operations are applied.
+    pub focus: PaneId,
+    pub operations: Vec<PaneOperation>,
+}
+
+/// Errors while planning keyboard pane commands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaneCommandPlanError {
+    MissingLeaf {
+        leaf: PaneId,
+    },
+    NodeNotLeaf {
+        node: PaneId,
+    },
+    MissingLayoutRect {
+        node: PaneId,
+    },
+    NoNeighbor {
+        leaf: PaneId,
+        direction: PaneDirection,
+    },
+    NoParentSplit {
+        leaf: PaneId,
+    },
+    NoAxisSplit {
+        leaf: PaneId,
+        axis: SplitAxis,
+    },
+    CannotCloseRoot {
+        leaf: PaneId,
+    },
+    InvalidRatio {
+        numerator: u32,
+        denominator: u32,
+    },
+}
+
+impl fmt::Display for PaneCommandPlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLeaf { leaf } => write!(f, "pane leaf {} not found", leaf.get()),
+            Self::NodeNotLeaf { node } => write!(f, "node {} is not a leaf", node.get()),
+            Self::MissingLayoutRect { node } => {
+                write!(f, "layout missing rectangle for node {}", node.get())
+            }
+            Self::NoNeighbor { leaf, direction } => {
+                write!(f, "no pane {direction:?} of leaf {}", leaf.get())
+            }
+            Self::NoParentSplit { leaf } => {
+                write!(f, "leaf {} is not inside a split", leaf.get())
+            }
+            Self::NoAxisSplit { leaf, axis } => {
+                write!(
+                    f,
+                    "no ancestor split on {axis:?} axis for leaf {}",
+                    leaf.get()
+                )
+            }
+            Self::CannotCloseRoot { leaf } => {
+                write!(f, "leaf {} is the root and cannot be closed", leaf.get())
+            }
+            Self::InvalidRatio {
+                numerator,
+                denominator,
+            } => write!(
+                f,
+                "invalid planned ratio {numerator}/{denominator} for pane command"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PaneCommandPlanError {}
+
 /// One persistent timeline event for deterministic undo/redo/replay.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PaneInteractionTimelineEntry {
     pub sequence: u64,
     pub operation_id: u64,
     pub operation: PaneOperation,
+    /// Further operations applied after `operation` in the same undo step,
+    /// with operation ids following `operation_id`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grouped_operations: Vec<PaneOperation>,
     pub before_hash: u64,
     pub after_hash: u64,
 }
 
+impl PaneInteractionTimelineEntry {
+    /// Every operation of the entry paired with its operation id, in apply order.
+    pub fn operations(&self) -> impl Iterator<Item = (u64, &PaneOperation)> {
+        std::iter::once(&self.operation)
+            .chain(&self.grouped_operations)
+            .enumerate()
+            .map(|(index, operation)| (self.operation_id.saturating_add(index as u64), operation))
+    }
+}
+
 /// One replay checkpoint in the interaction timeline.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PaneInteractionTimelineCheckpoint {
@@ -3342,6 +3674,40 @@ fn dock_preview_for_rect_with_motion(
     best
 }
 
+/// Gap and perpendicular overlap of `candidate` relative to `origin`, when
+/// `candidate` lies entirely on the `direction` side.
+fn directional_adjacency(
+    origin: Rect,
+    candidate: Rect,
+    direction: PaneDirection,
+) -> Option<(u16, u16)> {
+    let gap = match direction {
+        PaneDirection::Left => origin.x.checked_sub(candidate.right())?,
+        PaneDirection::Right => candidate.x.checked_sub(origin.right())?,
+        PaneDirection::Up => origin.y.checked_sub(candidate.bottom())?,
+        PaneDirection::Down => candidate.y.checked_sub(origin.bottom())?,
+    };
+    let (start, end, other_start, other_end) = match direction.axis() {
+        SplitAxis::Horizontal => (origin.y, origin.bottom(), candidate.y, candidate.bottom()),
+        SplitAxis::Vertical => (origin.x, origin.right(), candidate.x, candidate.right()),
+    };
+    let overlap = end.min(other_end).saturating_sub(start.max(other_start));
+    (overlap > 0).then_some((gap, overlap))
+}
+
+/// Manhattan distance between rectangle centers, in half cells.
+fn rect_center_distance(a: Rect, b: Rect) -> u32 {
+    let center = |rect: Rect| {
+        (
+            2 * u32::from(rect.x) + u32::from(rect.width),
+            2 * u32::from(rect.y) + u32::from(rect.height),
+        )
+    };
+    let (ax, ay) = center(a);
+    let (bx, by) = center(b);
+    ax.abs_diff(bx) + ay.abs_diff(by)
+}
+
 fn zone_to_axis_placement_and_target_share(
     zone: PaneDockZone,
     incoming_share_bps: u16,
@@ -3402,6 +3768,40 @@ pub enum PaneOperation {
     },
     /// Canonicalize all split ratios to reduced form and validate positivity.
     NormalizeRatios,
+    /// Set the orientation of an existing split node, keeping its children
+    /// and ratio.
+    SetSplitAxis { split: PaneId, axis: SplitAxis },
+    /// Insert a tab after the active tab of a leaf and activate it.
+    AddTab { target: PaneId, surface_key: String },
+    /// Activate the tab at `index`.
+    SelectTab { target: PaneId, index: usize },
+    /// Close the tab at `index`; the last remaining tab cannot be closed.
+    CloseTab { target: PaneId, index: usize },
+    /// Reorder a tab within its leaf.
+    MoveTab {
+        target: PaneId,
+        from: usize,
+        to: usize,
+    },
+    /// Maximize a docked node to the full layout area, or clear the zoom
+    /// with `None`. The split tree is left untouched.
+    SetZoom { target: Option<PaneId> },
+    /// Detach a docked subtree and float it above all other floating panes.
+    ///
+    /// Floating subtrees are docked back with
+    /// [`MoveSubtree`](Self::MoveSubtree) or closed with
+    /// [`CloseNode`](Self::CloseNode).
+    FloatNode {
+        target: PaneId,
+        rect: PaneFloatingRect,
+    },
+    /// Reposition or resize a floating subtree.
+    MoveFloating {
+        target: PaneId,
+        rect: PaneFloatingRect,
+    },
+    /// Bring a floating subtree to the top of the stacking order.
+    RaiseFloating { target: PaneId },
 }
 
 impl PaneOperation {
@@ -3415,6 +3815,15 @@ impl PaneOperation {
             Self::SwapNodes { .. } => PaneOperationKind::SwapNodes,
             Self::SetSplitRatio { .. } => PaneOperationKind::SetSplitRatio,
             Self::NormalizeRatios => PaneOperationKind::NormalizeRatios,
+            Self::SetSplitAxis { .. } => PaneOperationKind::SetSplitAxis,
+            Self::AddTab { .. } => PaneOperationKind::AddTab,
+            Self::SelectTab { .. } => PaneOperationKind::SelectTab,
+            Self::CloseTab { .. } => PaneOperationKind::CloseTab,
+            Self::MoveTab { .. } => PaneOperationKind::MoveTab,
+            Self::SetZoom { .. } => PaneOperationKind::SetZoom,
+            Self::FloatNode { .. } => PaneOperationKind::FloatNode,
+            Self::MoveFloating { .. } => PaneOperationKind::MoveFloating,
+            Self::RaiseFloating { .. } => PaneOperationKind::RaiseFloating,
         }
     }
 
@@ -3429,8 +3838,18 @@ impl PaneOperation {
             } => {
                 vec![*source, *target]
             }
-            Self::SetSplitRatio { split, .. } => vec![*split],
-            Self::NormalizeRatios => Vec::new(),
+            Self::SetSplitRatio { split, .. } | Self::SetSplitAxis { split, .. } => vec![*split],
+            Self::NormalizeRatios | Self::SetZoom { target: None } => Vec::new(),
+            Self::AddTab { target, .. }
+            | Self::SelectTab { target, .. }
+            | Self::CloseTab { target, .. }
+            | Self::MoveTab { target, .. }
+            | Self::SetZoom {
+                target: Some(target),
+            }
+            | Self::FloatNode { target, .. }
+            | Self::MoveFloating { target, .. }
+            | Self::RaiseFloating { target } => vec![*target],
         }
     }
 }
@@ -3445,6 +3864,15 @@ pub enum PaneOperationKind {
     SwapNodes,
     SetSplitRatio,
     NormalizeRatios,
+    SetSplitAxis,
+    AddTab,
+    SelectTab,
+    CloseTab,
+    MoveTab,
+    SetZoom,
+    FloatNode,
+    MoveFloating,
+    RaiseFloating,
 }
 
 /// Successful transactional operation result.
@@ -3510,6 +3938,20 @@ pub enum PaneOperationFailure {
         numerator: u32,
         denominator: u32,
     },
+    TabOutOfRange {
+        node_id: PaneId,
+        index: usize,
+        tab_count: usize,
+    },
+    CannotCloseLastTab {
+        node_id: PaneId,
+    },
+    NotFloating {
+        node_id: PaneId,
+    },
+    NotDocked {
+        node_id: PaneId,
+    },
     Validation(PaneModelError),
 }
 
@@ -3565,6 +4007,24 @@ impl fmt::Display for PaneOperationFailure {
                 "split node {} has invalid ratio {numerator}/{denominator}",
                 node_id.0
             ),
+            Self::TabOutOfRange {
+                node_id,
+                index,
+                tab_count,
+            } => write!(
+                f,
+                "tab {index} out of range for leaf {} with {tab_count} tab(s)",
+                node_id.0
+            ),
+            Self::CannotCloseLastTab { node_id } => {
+                write!(f, "cannot close the last tab of leaf {}", node_id.0)
+            }
+            Self::NotFloating { node_id } => {
+                write!(f, "node {} is not a floating pane", node_id.0)
+            }
+            Self::NotDocked { node_id } => {
+                write!(f, "node {} is not docked in the split tree", node_id.0)
+            }
             Self::Validation(err) => write!(f, "{err}"),
         }
     }
@@ -3755,6 +4215,9 @@ pub struct PaneTree {
     root: PaneId,
     next_id: PaneId,
     nodes: BTreeMap<PaneId, PaneNodeRecord>,
+    zoomed: Option<PaneId>,
+    /// Floating subtrees, bottom to top.
+    floating: Vec<PaneFloating>,
     extensions: BTreeMap<String, String>,
 }
 
@@ -3773,6 +4236,8 @@ impl PaneTree {
             root,
             next_id: root.checked_next().unwrap_or(root),
             nodes,
+            zoomed: None,
+            floating: Vec::new(),
             extensions: BTreeMap::new(),
         }
     }
@@ -3792,12 +4257,20 @@ impl PaneTree {
                 return Err(PaneModelError::DuplicateNodeId { node_id });
             }
         }
-        validate_tree(snapshot.root, snapshot.next_id, &nodes)?;
+        validate_tree(
+            snapshot.root,
+            snapshot.next_id,
+            &nodes,
+            &snapshot.floating,
+            snapshot.zoomed,
+        )?;
         Ok(Self {
             schema_version: snapshot.schema_version,
             root: snapshot.root,
             next_id: snapshot.next_id,
             nodes,
+            zoomed: snapshot.zoomed,
+            floating: snapshot.floating,
             extensions: snapshot.extensions,
         })
     }
@@ -3810,6 +4283,8 @@ impl PaneTree {
             root: self.root,
             next_id: self.next_id,
             nodes: self.nodes.values().cloned().collect(),
+            zoomed: self.zoomed,
+            floating: self.floating.clone(),
             extensions: self.extensions.clone(),
         };
         snapshot.canonicalize();
@@ -3845,9 +4320,45 @@ impl PaneTree {
         self.nodes.values()
     }
 
+    /// Docked node currently maximized to the full layout area.
+    #[must_use]
+    pub const fn zoomed(&self) -> Option<PaneId> {
+        self.zoomed
+    }
+
+    /// Floating subtrees, bottom to top.
+    #[must_use]
+    pub fn floating(&self) -> &[PaneFloating] {
+        &self.floating
+    }
+
+    /// Root of the floating subtree containing `node_id`, if it floats.
+    #[must_use]
+    pub fn floating_root_of(&self, node_id: PaneId) -> Option<PaneId> {
+        let top = self.ancestor_chain(node_id)?.pop()?;
+        self.floating
+            .iter()
+            .any(|floating| floating.id == top)
+            .then_some(top)
+    }
+
+    /// Whether `node_id` belongs to the split tree under the root.
+    #[must_use]
+    pub fn is_docked(&self, node_id: PaneId) -> bool {
+        self.ancestor_chain(node_id)
+            .and_then(|chain| chain.last().copied())
+            == Some(self.root)
+    }
+
     /// Validate internal invariants.
     pub fn validate(&self) -> Result<(), PaneModelError> {
-        validate_tree(self.root, self.next_id, &self.nodes)
+        validate_tree(
+            self.root,
+            self.next_id,
+            &self.nodes,
+            &self.floating,
+            self.zoomed,
+        )
     }
 
     /// Structured invariant diagnostics for the current tree snapshot.
@@ -3950,6 +4461,14 @@ impl PaneTree {
                     mix(&mut hash, 1);
                     mix_str(&mut hash, &leaf.surface_key);
                     mix_extensions(&mut hash, &leaf.extensions);
+                    if !leaf.tabs.is_empty() {
+                        mix(&mut hash, 3);
+                        mix_u64(&mut hash, leaf.tabs.len() as u64);
+                        for tab in &leaf.tabs {
+                            mix_str(&mut hash, tab);
+                        }
+                        mix_u64(&mut hash, leaf.active_tab as u64);
+                    }
                 }
                 PaneNodeKind::Split(split) => {
                     mix(&mut hash, 2);
@@ -3966,6 +4485,23 @@ impl PaneTree {
             }
         }
 
+        if let Some(zoomed) = self.zoomed {
+            mix(&mut hash, 4);
+            mix_u64(&mut hash, zoomed.get());
+        }
+        if !self.floating.is_empty() {
+            mix(&mut hash, 5);
+            mix_u64(&mut hash, self.floating.len() as u64);
+            for floating in &self.floating {
+                mix_u64(&mut hash, floating.id.get());
+                mix_u16(&mut hash, floating.rect.x);
+                mix_u16(&mut hash, floating.rect.y);
+                mix_u16(&mut hash, floating.rect.width);
+                mix_u16(&mut hash, floating.rect.height);
+                mix_u32(&mut hash, floating.z);
+            }
+        }
+
         hash
     }
 
@@ -4172,12 +4708,22 @@ impl PaneTree {
         kind: PaneOperationKind,
     ) -> PaneValidationStrategy {
         match kind {
-            PaneOperationKind::SetSplitRatio => PaneValidationStrategy::LocalClosure,
+            PaneOperationKind::SetSplitRatio | PaneOperationKind::SetSplitAxis => {
+                PaneValidationStrategy::LocalClosure
+            }
             PaneOperationKind::SplitLeaf
             | PaneOperationKind::CloseNode
             | PaneOperationKind::MoveSubtree
             | PaneOperationKind::SwapNodes
-            | PaneOperationKind::NormalizeRatios => PaneValidationStrategy::FullTree,
+            | PaneOperationKind::NormalizeRatios
+            | PaneOperationKind::AddTab
+            | PaneOperationKind::SelectTab
+            | PaneOperationKind::CloseTab
+            | PaneOperationKind::MoveTab
+            | PaneOperationKind::SetZoom
+            | PaneOperationKind::FloatNode
+            | PaneOperationKind::MoveFloating
+            | PaneOperationKind::RaiseFloating => PaneValidationStrategy::FullTree,
         }
     }
 
@@ -4286,7 +4832,25 @@ impl PaneTree {
                 self.apply_set_split_ratio(split, ratio, touched)
             }
             PaneOperation::NormalizeRatios => self.apply_normalize_ratios(touched),
-        }
+            PaneOperation::SetSplitAxis { split, axis } => {
+                self.apply_set_split_axis(split, axis, touched)
+            }
+            PaneOperation::AddTab {
+                target,
+                surface_key,
+            } => self.apply_add_tab(target, surface_key),
+            PaneOperation::SelectTab { target, index } => self.apply_select_tab(target, index),
+            PaneOperation::CloseTab { target, index } => self.apply_close_tab(target, index),
+            PaneOperation::MoveTab { target, from, to } => self.apply_move_tab(target, from, to),
+            PaneOperation::SetZoom { target } => self.apply_set_zoom(target),
+            PaneOperation::FloatNode { target, rect } => {
+                self.apply_float_node(target, rect, touched)
+            }
+            PaneOperation::MoveFloating { target, rect } => self.apply_move_floating(target, rect),
+            PaneOperation::RaiseFloating { target } => self.apply_raise_floating(target),
+        }?;
+        self.clear_stale_zoom();
+        Ok(())
     }
 
     fn apply_operation_inner_ref(
@@ -4324,7 +4888,27 @@ impl PaneTree {
                 self.apply_set_split_ratio(*split, *ratio, touched)
             }
             PaneOperation::NormalizeRatios => self.apply_normalize_ratios(touched),
-        }
+            PaneOperation::SetSplitAxis { split, axis } => {
+                self.apply_set_split_axis(*split, *axis, touched)
+            }
+            PaneOperation::AddTab {
+                target,
+                surface_key,
+            } => self.apply_add_tab(*target, surface_key.clone()),
+            PaneOperation::SelectTab { target, index } => self.apply_select_tab(*target, *index),
+            PaneOperation::CloseTab { target, index } => self.apply_close_tab(*target, *index),
+            PaneOperation::MoveTab { target, from, to } => self.apply_move_tab(*target, *from, *to),
+            PaneOperation::SetZoom { target } => self.apply_set_zoom(*target),
+            PaneOperation::FloatNode { target, rect } => {
+                self.apply_float_node(*target, *rect, touched)
+            }
+            PaneOperation::MoveFloating { target, rect } => {
+                self.apply_move_floating(*target, *rect)
+            }
+            PaneOperation::RaiseFloating { target } => self.apply_raise_floating(*target),
+        }?;
+        self.clear_stale_zoom();
+        Ok(())
     }
 
     fn apply_split_leaf(
@@ -4381,7 +4965,7 @@ impl PaneTree {
         if let Some(parent_id) = target_parent {
             self.replace_child(parent_id, target, split_id)?;
         } else {
-            self.root = split_id;
+            self.replace_top_level(target, split_id);
         }
 
         Ok(())
@@ -4404,6 +4988,14 @@ impl PaneTree {
             let _ = touched.insert(*node_id);
         }
 
+        if self.floating_index(target).is_some() {
+            self.floating.retain(|floating| floating.id != target);
+            for node_id in subtree_ids {
+                let _ = self.nodes.remove(&node_id);
+            }
+            return Ok(());
+        }
+
         let (parent_id, sibling_id, grandparent_id) =
             self.promote_sibling_after_detach(target, touched)?;
         let _ = touched.insert(parent_id);
@@ -4452,32 +5044,37 @@ impl PaneTree {
             });
         }
 
-        let source_parent = self
-            .nodes
-            .get(&source)
-            .and_then(|node| node.parent)
-            .ok_or(PaneOperationFailure::CannotMoveRoot { node_id: source })?;
-        if source_parent == target {
-            return Err(PaneOperationFailure::TargetRemovedByDetach {
-                target,
-                detached_parent: source_parent,
-            });
-        }
-
         let _ = touched.insert(source);
         let _ = touched.insert(target);
-        let _ = touched.insert(source_parent);
 
-        let (removed_parent, sibling_id, grandparent_id) =
-            self.promote_sibling_after_detach(source, touched)?;
-        let _ = touched.insert(removed_parent);
-        let _ = touched.insert(sibling_id);
-        if let Some(grandparent_id) = grandparent_id {
-            let _ = touched.insert(grandparent_id);
-        }
+        if self.floating_index(source).is_some() {
+            // Docking a floating subtree: nothing to detach.
+            self.floating.retain(|floating| floating.id != source);
+        } else {
+            let source_parent = self
+                .nodes
+                .get(&source)
+                .and_then(|node| node.parent)
+                .ok_or(PaneOperationFailure::CannotMoveRoot { node_id: source })?;
+            if source_parent == target {
+                return Err(PaneOperationFailure::TargetRemovedByDetach {
+                    target,
+                    detached_parent: source_parent,
+                });
+            }
+            let _ = touched.insert(source_parent);
 
-        if let Some(source_node) = self.nodes.get_mut(&source) {
-            source_node.parent = None;
+            let (removed_parent, sibling_id, grandparent_id) =
+                self.promote_sibling_after_detach(source, touched)?;
+            let _ = touched.insert(removed_parent);
+            let _ = touched.insert(sibling_id);
+            if let Some(grandparent_id) = grandparent_id {
+                let _ = touched.insert(grandparent_id);
+            }
+
+            if let Some(source_node) = self.nodes.get_mut(&source) {
+                source_node.parent = None;
+            }
         }
 
         if !self.nodes.contains_key(&target) {
@@ -4516,7 +5113,7 @@ impl PaneTree {
         if let Some(parent_id) = target_parent {
             self.replace_child(parent_id, target, split_id)?;
         } else {
-            self.root = split_id;
+            self.replace_top_level(target, split_id);
         }
 
         Ok(())
@@ -4587,7 +5184,7 @@ impl PaneTree {
                 if let Some(second_node) = self.nodes.get_mut(&second) {
                     second_node.parent = None;
                 }
-                self.root = second;
+                self.replace_top_level(first, second);
             }
             (Some(parent_id), None) => {
                 let _ = touched.insert(parent_id);
@@ -4598,9 +5195,9 @@ impl PaneTree {
                 if let Some(second_node) = self.nodes.get_mut(&second) {
                     second_node.parent = Some(parent_id);
                 }
-                self.root = first;
+                self.replace_top_level(second, first);
             }
-            (None, None) => {}
+            (None, None) => self.swap_top_level(first, second),
         }
 
         Ok(())
@@ -4651,24 +5248,249 @@ impl PaneTree {
         Ok(())
     }
 
-    fn replace_child(
+    fn apply_set_split_axis(
         &mut self,
-        parent_id: PaneId,
-        old_child: PaneId,
-        new_child: PaneId,
+        split_id: PaneId,
+        axis: SplitAxis,
+        touched: &mut BTreeSet<PaneId>,
     ) -> Result<(), PaneOperationFailure> {
-        let parent = self
+        let node = self
             .nodes
-            .get_mut(&parent_id)
-            .ok_or(PaneOperationFailure::MissingNode { node_id: parent_id })?;
-        let PaneNodeKind::Split(split) = &mut parent.kind else {
-            return Err(PaneOperationFailure::ParentNotSplit { node_id: parent_id });
+            .get_mut(&split_id)
+            .ok_or(PaneOperationFailure::MissingNode { node_id: split_id })?;
+        let PaneNodeKind::Split(split) = &mut node.kind else {
+            return Err(PaneOperationFailure::ParentNotSplit { node_id: split_id });
         };
+        split.axis = axis;
+        let _ = touched.insert(split_id);
+        Ok(())
+    }
 
-        if split.first == old_child {
-            split.first = new_child;
-            return Ok(());
-        }
+    fn leaf_mut(&mut self, node_id: PaneId) -> Result<&mut PaneLeaf, PaneOperationFailure> {
+        match self.nodes.get_mut(&node_id) {
+            Some(PaneNodeRecord {
+                kind: PaneNodeKind::Leaf(leaf),
+                ..
+            }) => Ok(leaf),
+            Some(_) => Err(PaneOperationFailure::NodeNotLeaf { node_id }),
+            None => Err(PaneOperationFailure::MissingNode { node_id }),
+        }
+    }
+
+    fn apply_add_tab(
+        &mut self,
+        target: PaneId,
+        surface_key: String,
+    ) -> Result<(), PaneOperationFailure> {
+        let leaf = self.leaf_mut(target)?;
+        let mut tabs = leaf.tab_stack();
+        let index = leaf.active_tab + 1;
+        tabs.insert(index, surface_key);
+        leaf.set_tab_stack(tabs, index);
+        Ok(())
+    }
+
+    fn apply_select_tab(
+        &mut self,
+        target: PaneId,
+        index: usize,
+    ) -> Result<(), PaneOperationFailure> {
+        let leaf = self.leaf_mut(target)?;
+        check_tab_index(target, leaf, index)?;
+        let tabs = leaf.tab_stack();
+        leaf.set_tab_stack(tabs, index);
+        Ok(())
+    }
+
+    fn apply_close_tab(
+        &mut self,
+        target: PaneId,
+        index: usize,
+    ) -> Result<(), PaneOperationFailure> {
+        let leaf = self.leaf_mut(target)?;
+        check_tab_index(target, leaf, index)?;
+        if leaf.tab_count() == 1 {
+            return Err(PaneOperationFailure::CannotCloseLastTab { node_id: target });
+        }
+        let mut tabs = leaf.tab_stack();
+        let _ = tabs.remove(index);
+        // Closing a tab before the active one keeps the same tab active;
+        // closing the active tab activates its right neighbour.
+        let active = if index < leaf.active_tab {
+            leaf.active_tab - 1
+        } else {
+            leaf.active_tab
+        };
+        leaf.set_tab_stack(tabs, active);
+        Ok(())
+    }
+
+    fn apply_move_tab(
+        &mut self,
+        target: PaneId,
+        from: usize,
+        to: usize,
+    ) -> Result<(), PaneOperationFailure> {
+        let leaf = self.leaf_mut(target)?;
+        check_tab_index(target, leaf, from)?;
+        check_tab_index(target, leaf, to)?;
+        let mut tabs = leaf.tab_stack();
+        let tab = tabs.remove(from);
+        tabs.insert(to, tab);
+        // The active tab keeps focus wherever it ends up.
+        let active = leaf.active_tab;
+        let active = if active == from {
+            to
+        } else if from < active && to >= active {
+            active - 1
+        } else if from > active && to <= active {
+            active + 1
+        } else {
+            active
+        };
+        leaf.set_tab_stack(tabs, active);
+        Ok(())
+    }
+
+    fn apply_set_zoom(&mut self, target: Option<PaneId>) -> Result<(), PaneOperationFailure> {
+        if let Some(node_id) = target {
+            if !self.nodes.contains_key(&node_id) {
+                return Err(PaneOperationFailure::MissingNode { node_id });
+            }
+            if !self.is_docked(node_id) {
+                return Err(PaneOperationFailure::NotDocked { node_id });
+            }
+        }
+        self.zoomed = target;
+        Ok(())
+    }
+
+    fn apply_float_node(
+        &mut self,
+        target: PaneId,
+        rect: PaneFloatingRect,
+        touched: &mut BTreeSet<PaneId>,
+    ) -> Result<(), PaneOperationFailure> {
+        if !self.nodes.contains_key(&target) {
+            return Err(PaneOperationFailure::MissingNode { node_id: target });
+        }
+        if target == self.root {
+            return Err(PaneOperationFailure::CannotMoveRoot { node_id: target });
+        }
+        if !self.is_docked(target) {
+            return Err(PaneOperationFailure::NotDocked { node_id: target });
+        }
+
+        let (parent_id, sibling_id, grandparent_id) =
+            self.promote_sibling_after_detach(target, touched)?;
+        let _ = touched.insert(parent_id);
+        let _ = touched.insert(sibling_id);
+        if let Some(grandparent_id) = grandparent_id {
+            let _ = touched.insert(grandparent_id);
+        }
+        if let Some(node) = self.nodes.get_mut(&target) {
+            node.parent = None;
+        }
+
+        let z = self.next_floating_z();
+        self.floating.push(PaneFloating {
+            id: target,
+            rect,
+            z,
+        });
+        Ok(())
+    }
+
+    fn apply_move_floating(
+        &mut self,
+        target: PaneId,
+        rect: PaneFloatingRect,
+    ) -> Result<(), PaneOperationFailure> {
+        let index = self
+            .floating_index(target)
+            .ok_or(PaneOperationFailure::NotFloating { node_id: target })?;
+        self.floating[index].rect = rect;
+        Ok(())
+    }
+
+    fn apply_raise_floating(&mut self, target: PaneId) -> Result<(), PaneOperationFailure> {
+        let index = self
+            .floating_index(target)
+            .ok_or(PaneOperationFailure::NotFloating { node_id: target })?;
+        if index + 1 == self.floating.len() {
+            return Ok(());
+        }
+        let mut floating = self.floating.remove(index);
+        floating.z = self.next_floating_z();
+        self.floating.push(floating);
+        Ok(())
+    }
+
+    fn floating_index(&self, node_id: PaneId) -> Option<usize> {
+        self.floating
+            .iter()
+            .position(|floating| floating.id == node_id)
+    }
+
+    fn next_floating_z(&self) -> u32 {
+        self.floating
+            .last()
+            .map_or(0, |floating| floating.z.saturating_add(1))
+    }
+
+    /// Point whichever top-level slot (root or floating record) holds `old`
+    /// at `new`.
+    fn replace_top_level(&mut self, old: PaneId, new: PaneId) {
+        if self.root == old {
+            self.root = new;
+        } else if let Some(index) = self.floating_index(old) {
+            self.floating[index].id = new;
+        }
+    }
+
+    fn swap_top_level(&mut self, first: PaneId, second: PaneId) {
+        let first_index = self.floating_index(first);
+        let second_index = self.floating_index(second);
+        if let Some(index) = first_index {
+            self.floating[index].id = second;
+        }
+        if let Some(index) = second_index {
+            self.floating[index].id = first;
+        }
+        if self.root == first {
+            self.root = second;
+        } else if self.root == second {
+            self.root = first;
+        }
+    }
+
+    /// Drop the zoom once its node is closed or leaves the split tree.
+    fn clear_stale_zoom(&mut self) {
+        if let Some(zoomed) = self.zoomed
+            && !self.is_docked(zoomed)
+        {
+            self.zoomed = None;
+        }
+    }
+
+    fn replace_child(
+        &mut self,
+        parent_id: PaneId,
+        old_child: PaneId,
+        new_child: PaneId,
+    ) -> Result<(), PaneOperationFailure> {
+        let parent = self
+            .nodes
+            .get_mut(&parent_id)
+            .ok_or(PaneOperationFailure::MissingNode { node_id: parent_id })?;
+        let PaneNodeKind::Split(split) = &mut parent.kind else {
+            return Err(PaneOperationFailure::ParentNotSplit { node_id: parent_id });
+        };
+
+        if split.first == old_child {
+            split.first = new_child;
+            return Ok(());
+        }
         if split.second == old_child {
             split.second = new_child;
             return Ok(());
@@ -4744,7 +5566,7 @@ impl PaneTree {
             let _ = touched.insert(grandparent_id);
             self.replace_child(grandparent_id, parent_id, sibling_id)?;
         } else {
-            self.root = sibling_id;
+            self.replace_top_level(parent_id, sibling_id);
         }
 
         let sibling_node =
@@ -4816,13 +5638,74 @@ impl PaneTree {
     /// - If clamping is required by constraints, we clamp into the feasible
     ///   interval for the first child; remainder goes to the second child.
     ///
+    /// While a node is zoomed, only its subtree is solved (over the full
+    /// area). Floating subtrees are solved into their own rectangles on top;
+    /// those entirely outside `area` are omitted.
+    ///
     /// Complexity:
     /// - Time: `O(node_count)` (single DFS over split tree)
     /// - Space: `O(node_count)` (output rectangle map)
     pub fn solve_layout(&self, area: Rect) -> Result<PaneLayout, PaneModelError> {
         let mut rects = BTreeMap::new();
-        self.solve_node(self.root, area, &mut rects)?;
-        Ok(PaneLayout { area, rects })
+        self.solve_node(self.zoomed.unwrap_or(self.root), area, &mut rects)?;
+        let mut floating = Vec::with_capacity(self.floating.len());
+        for record in &self.floating {
+            let rect = record.rect.resolve(area);
+            if rect.is_empty() {
+                continue;
+            }
+            self.solve_node(record.id, rect, &mut rects)?;
+            floating.push(record.id);
+        }
+        Ok(PaneLayout {
+            area,
+            rects,
+            floating,
+        })
+    }
+
+    /// Smallest area that satisfies every docked pane's minimum size.
+    ///
+    /// Minimums propagate up from the leaves: a horizontal split needs the
+    /// sum of its children's widths and the larger of their heights, a
+    /// vertical split the reverse, and every node at least its own
+    /// constraints. A zoomed node is measured alone. Floating panes are
+    /// sized relative to the area and are not included. [`solve_layout`]
+    /// fails for any area smaller than this.
+    ///
+    /// [`solve_layout`]: Self::solve_layout
+    #[must_use]
+    pub fn minimum_size(&self) -> Size {
+        self.subtree_minimum_size(self.zoomed.unwrap_or(self.root))
+    }
+
+    /// Smallest area that satisfies the minimums of the subtree rooted at
+    /// `node_id`, or zero for an unknown node.
+    #[must_use]
+    pub fn subtree_minimum_size(&self, node_id: PaneId) -> Size {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return Size::ZERO;
+        };
+        let own = Size::new(node.constraints.min_width, node.constraints.min_height);
+        let PaneNodeKind::Split(split) = &node.kind else {
+            return own;
+        };
+        let first = self.subtree_minimum_size(split.first);
+        let second = self.subtree_minimum_size(split.second);
+        let children = match split.axis {
+            SplitAxis::Horizontal => Size::new(
+                first.width.saturating_add(second.width),
+                first.height.max(second.height),
+            ),
+            SplitAxis::Vertical => Size::new(
+                first.width.max(second.width),
+                first.height.saturating_add(second.height),
+            ),
+        };
+        Size::new(
+            own.width.max(children.width),
+            own.height.max(children.height),
+        )
     }
 
     fn solve_node(
@@ -5406,6 +6289,284 @@ impl PaneTree {
         Ok(operations)
     }
 
+    /// Plan a keyboard pane command for the `focus` leaf.
+    ///
+    /// Neighbor lookups and cell-based resizes read rectangles from `layout`,
+    /// which should be solved from the current tree. `FocusNeighbor` plans no
+    /// operations; every other command plans the same operations a pointer
+    /// gesture with the same effect would.
+    pub fn plan_command(
+        &self,
+        focus: PaneId,
+        layout: &PaneLayout,
+        command: PaneCommand,
+    ) -> Result<PaneCommandPlan, PaneCommandPlanError> {
+        let node = self
+            .nodes
+            .get(&focus)
+            .ok_or(PaneCommandPlanError::MissingLeaf { leaf: focus })?;
+        let PaneNodeKind::Leaf(leaf) = &node.kind else {
+            return Err(PaneCommandPlanError::NodeNotLeaf { node: focus });
+        };
+
+        let (next_focus, operations) = match command {
+            PaneCommand::FocusNeighbor { direction } => {
+                (self.neighbor_leaf(focus, layout, direction)?, Vec::new())
+            }
+            PaneCommand::SwapWithNeighbor { direction } => {
+                let neighbor = self.neighbor_leaf(focus, layout, direction)?;
+                (
+                    focus,
+                    vec![PaneOperation::SwapNodes {
+                        first: focus,
+                        second: neighbor,
+                    }],
+                )
+            }
+            PaneCommand::MoveToward { direction } => {
+                let neighbor = self.neighbor_leaf(focus, layout, direction)?;
+                let (axis, placement, _) =
+                    zone_to_axis_placement_and_target_share(direction.dock_zone(), 5_000);
+                (
+                    focus,
+                    vec![PaneOperation::MoveSubtree {
+                        source: focus,
+                        target: neighbor,
+                        axis,
+                        ratio: PaneSplitRatio::default(),
+                        placement,
+                    }],
+                )
+            }
+            PaneCommand::RotateSplit => {
+                let parent = node
+                    .parent
+                    .ok_or(PaneCommandPlanError::NoParentSplit { leaf: focus })?;
+                let Some(PaneNodeKind::Split(split)) = self.nodes.get(&parent).map(|p| &p.kind)
+                else {
+                    return Err(PaneCommandPlanError::NoParentSplit { leaf: focus });
+                };
+                let axis = match split.axis {
+                    SplitAxis::Horizontal => SplitAxis::Vertical,
+                    SplitAxis::Vertical => SplitAxis::Horizontal,
+                };
+                (
+                    focus,
+                    vec![PaneOperation::SetSplitAxis {
+                        split: parent,
+                        axis,
+                    }],
+                )
+            }
+            PaneCommand::Equalize => {
+                let top = self.top_level_of(focus).unwrap_or(self.root);
+                (focus, self.equalize_operations(top)?)
+            }
+            PaneCommand::ResizeBy { direction, cells } => (
+                focus,
+                self.resize_by_operations(focus, layout, direction, cells)?,
+            ),
+            PaneCommand::Close => {
+                if focus == self.root {
+                    return Err(PaneCommandPlanError::CannotCloseRoot { leaf: focus });
+                }
+                (
+                    self.focus_after_close(focus, layout),
+                    vec![PaneOperation::CloseNode { target: focus }],
+                )
+            }
+            PaneCommand::MergeInto { direction } => {
+                let neighbor = self.neighbor_leaf(focus, layout, direction)?;
+                let mut operations = (0..leaf.tab_count())
+                    .filter_map(|index| leaf.tab(index))
+                    .map(|surface_key| PaneOperation::AddTab {
+                        target: neighbor,
+                        surface_key: surface_key.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                operations.push(PaneOperation::CloseNode { target: focus });
+                (neighbor, operations)
+            }
+        };
+
+        Ok(PaneCommandPlan {
+            command,
+            focus: next_focus,
+            operations,
+        })
+    }
+
+    /// Apply all operations generated by a keyboard command plan.
+    pub fn apply_command_plan(
+        &mut self,
+        operation_seed: u64,
+        plan: &PaneCommandPlan,
+    ) -> Result<Vec<PaneOperationOutcome>, PaneOperationError> {
+        let mut outcomes = Vec::with_capacity(plan.operations.len());
+        for (index, operation) in plan.operations.iter().cloned().enumerate() {
+            outcomes.push(
+                self.apply_operation(operation_seed.saturating_add(index as u64), operation)?,
+            );
+        }
+        Ok(outcomes)
+    }
+
+    /// Nearest leaf in `direction` that shares an edge span with `leaf`.
+    ///
+    /// Candidates must sit entirely on the `direction` side and overlap on the
+    /// perpendicular axis; the smallest gap wins, then the largest overlap,
+    /// then the lowest id. Only leaves in the same top-level tree (docked or
+    /// the same floating subtree) are considered.
+    fn neighbor_leaf(
+        &self,
+        leaf: PaneId,
+        layout: &PaneLayout,
+        direction: PaneDirection,
+    ) -> Result<PaneId, PaneCommandPlanError> {
+        let origin = layout
+            .rect(leaf)
+            .ok_or(PaneCommandPlanError::MissingLayoutRect { node: leaf })?;
+        let top = self.top_level_of(leaf);
+        self.nodes
+            .values()
+            .filter(|node| node.id != leaf && matches!(node.kind, PaneNodeKind::Leaf(_)))
+            .filter(|node| self.top_level_of(node.id) == top)
+            .filter_map(|node| {
+                let rect = layout.rect(node.id)?;
+                let (gap, overlap) = directional_adjacency(origin, rect, direction)?;
+                Some((gap, std::cmp::Reverse(overlap), node.id))
+            })
+            .min()
+            .map(|(_, _, node_id)| node_id)
+            .ok_or(PaneCommandPlanError::NoNeighbor { leaf, direction })
+    }
+
+    fn top_level_of(&self, node_id: PaneId) -> Option<PaneId> {
+        self.ancestor_chain(node_id)
+            .and_then(|chain| chain.last().copied())
+    }
+
+    fn equalize_operations(&self, top: PaneId) -> Result<Vec<PaneOperation>, PaneCommandPlanError> {
+        let mut operations = Vec::new();
+        for node in self.nodes.values() {
+            let PaneNodeKind::Split(split) = &node.kind else {
+                continue;
+            };
+            if !self.subtree_contains(top, node.id) {
+                continue;
+            }
+            let first = self.axis_pane_count(split.first, split.axis);
+            let second = self.axis_pane_count(split.second, split.axis);
+            let ratio = PaneSplitRatio::new(first, second).map_err(|_| {
+                PaneCommandPlanError::InvalidRatio {
+                    numerator: first,
+                    denominator: second,
+                }
+            })?;
+            if ratio != split.ratio {
+                operations.push(PaneOperation::SetSplitRatio {
+                    split: node.id,
+                    ratio,
+                });
+            }
+        }
+        Ok(operations)
+    }
+
+    /// Number of panes a subtree lines up along `axis`.
+    fn axis_pane_count(&self, node_id: PaneId, axis: SplitAxis) -> u32 {
+        match self.nodes.get(&node_id).map(|node| &node.kind) {
+            Some(PaneNodeKind::Split(split)) => {
+                let first = self.axis_pane_count(split.first, axis);
+                let second = self.axis_pane_count(split.second, axis);
+                if split.axis == axis {
+                    first.saturating_add(second)
+                } else {
+                    first.max(second)
+                }
+            }
+            _ => 1,
+        }
+    }
+
+    fn resize_by_operations(
+        &self,
+        leaf: PaneId,
+        layout: &PaneLayout,
+        direction: PaneDirection,
+        cells: u16,
+    ) -> Result<Vec<PaneOperation>, PaneCommandPlanError> {
+        let axis = direction.axis();
+        let split_id = self
+            .nearest_axis_split_for_node(leaf, axis)
+            .ok_or(PaneCommandPlanError::NoAxisSplit { leaf, axis })?;
+        let Some(PaneNodeKind::Split(split)) = self.nodes.get(&split_id).map(|node| &node.kind)
+        else {
+            return Err(PaneCommandPlanError::NoAxisSplit { leaf, axis });
+        };
+        let split_rect = layout
+            .rect(split_id)
+            .ok_or(PaneCommandPlanError::MissingLayoutRect { node: split_id })?;
+        let first_rect = layout
+            .rect(split.first)
+            .ok_or(PaneCommandPlanError::MissingLayoutRect { node: split.first })?;
+        let (total, first) = match axis {
+            SplitAxis::Horizontal => (split_rect.width, first_rect.width),
+            SplitAxis::Vertical => (split_rect.height, first_rect.height),
+        };
+        if total < 2 {
+            return Ok(Vec::new());
+        }
+        let first = if direction.toward_second() {
+            first.saturating_add(cells)
+        } else {
+            first.saturating_sub(cells)
+        }
+        .clamp(1, total - 1);
+        let ratio =
+            PaneSplitRatio::new(u32::from(first), u32::from(total - first)).map_err(|_| {
+                PaneCommandPlanError::InvalidRatio {
+                    numerator: u32::from(first),
+                    denominator: u32::from(total - first),
+                }
+            })?;
+        if ratio == split.ratio {
+            return Ok(Vec::new());
+        }
+        Ok(vec![PaneOperation::SetSplitRatio {
+            split: split_id,
+            ratio,
+        }])
+    }
+
+    /// Leaf that inherits focus when `leaf` closes: the closest leaf of the
+    /// sibling subtree that takes over its space, or of the docked tree when
+    /// a floating leaf closes.
+    fn focus_after_close(&self, leaf: PaneId, layout: &PaneLayout) -> PaneId {
+        let scope = self
+            .nodes
+            .get(&leaf)
+            .and_then(|node| node.parent)
+            .and_then(|parent| match &self.nodes.get(&parent)?.kind {
+                PaneNodeKind::Split(split) if split.first == leaf => Some(split.second),
+                PaneNodeKind::Split(split) => Some(split.first),
+                PaneNodeKind::Leaf(_) => None,
+            })
+            .unwrap_or(self.root);
+        let origin = layout.rect(leaf);
+        self.nodes
+            .values()
+            .filter(|node| matches!(node.kind, PaneNodeKind::Leaf(_)))
+            .filter(|node| self.subtree_contains(scope, node.id))
+            .min_by_key(|node| {
+                let distance = origin
+                    .zip(layout.rect(node.id))
+                    .map_or(u32::MAX, |(from, to)| rect_center_distance(from, to));
+                (distance, node.id)
+            })
+            .map_or(scope, |node| node.id)
+    }
+
     fn choose_dock_preview_excluding(
         &self,
         layout: &PaneLayout,
@@ -5418,7 +6579,7 @@ impl PaneTree {
             if !matches!(node.kind, PaneNodeKind::Leaf(_)) {
                 continue;
             }
-            if excluded == Some(node.id) {
+            if excluded.is_some_and(|excluded| self.subtree_contains(excluded, node.id)) {
                 continue;
             }
             let Some(rect) = layout.rect(node.id) else {
@@ -5475,7 +6636,7 @@ impl PaneTree {
             if !matches!(node.kind, PaneNodeKind::Leaf(_)) {
                 continue;
             }
-            if excluded == Some(node.id) {
+            if excluded.is_some_and(|excluded| self.subtree_contains(excluded, node.id)) {
                 continue;
             }
             let Some(rect) = layout.rect(node.id) else {
@@ -5534,6 +6695,11 @@ impl PaneTree {
             .find(|candidate| ancestor_paths.iter().all(|path| path.contains(candidate)))
     }
 
+    fn subtree_contains(&self, subtree_root: PaneId, node: PaneId) -> bool {
+        self.ancestor_chain(node)
+            .is_some_and(|chain| chain.contains(&subtree_root))
+    }
+
     fn ancestor_chain(&self, node: PaneId) -> Option<Vec<PaneId>> {
         let mut out = Vec::new();
         let mut cursor = Some(node);
@@ -5580,7 +6746,7 @@ impl PaneInteractionTimeline {
     pub fn next_operation_id(&self) -> u64 {
         self.entries
             .iter()
-            .map(|entry| entry.operation_id)
+            .filter_map(|entry| entry.operations().last().map(|(id, _)| id))
             .max()
             .unwrap_or(0)
             .saturating_add(1)
@@ -5623,7 +6789,8 @@ impl PaneInteractionTimeline {
         let retained_operation_payload_bytes = self
             .entries
             .iter()
-            .map(|entry| pane_operation_retained_payload_bytes(&entry.operation))
+            .flat_map(PaneInteractionTimelineEntry::operations)
+            .map(|(_, operation)| pane_operation_retained_payload_bytes(operation))
             .sum::<usize>();
         let estimated_entry_struct_bytes = self
             .entries
@@ -5720,6 +6887,7 @@ impl PaneInteractionTimeline {
             sequence,
             operation_id,
             operation,
+            grouped_operations: Vec::new(),
             before_hash: outcome.before_hash,
             after_hash: outcome.after_hash,
         });
@@ -5729,38 +6897,88 @@ impl PaneInteractionTimeline {
         Ok(outcome)
     }
 
-    /// Apply one operation and merge adjacent resize deltas for the same split.
+    /// Apply and record every operation of a keyboard command plan.
     ///
-    /// The first delta keeps its pre-gesture hash so undo still restores the
-    /// state before the drag began, while replay uses the most recent ratio.
-    /// Coalescing is limited to entries whose operation id is above the
-    /// gesture-start boundary, so separate drags on the same split remain
-    /// separate undo steps.
-    pub fn apply_and_record_coalesced_resize_delta(
+    /// The plan becomes a single entry, so one undo reverts the whole command.
+    /// Operations take consecutive ids from `operation_seed`. If any operation
+    /// fails, the ones already applied are rolled back and nothing is recorded.
+    pub fn apply_and_record_command_plan(
         &mut self,
         tree: &mut PaneTree,
         sequence: u64,
-        operation_id: u64,
-        operation: PaneOperation,
-        coalesce_after_operation_id: u64,
-    ) -> Result<PaneOperationOutcome, PaneOperationError> {
+        operation_seed: u64,
+        plan: &PaneCommandPlan,
+    ) -> Result<Vec<PaneOperationOutcome>, PaneOperationError> {
+        let Some((first, rest)) = plan.operations.split_first() else {
+            return Ok(Vec::new());
+        };
+        let original = tree.clone();
+        let mut outcomes = Vec::with_capacity(plan.operations.len());
+        for (index, operation) in plan.operations.iter().cloned().enumerate() {
+            match tree.apply_operation(operation_seed.saturating_add(index as u64), operation) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(err) => {
+                    *tree = original;
+                    return Err(err);
+                }
+            }
+        }
+
         if self.baseline.is_none() {
-            self.baseline = Some(tree.to_snapshot());
+            self.baseline = Some(original.to_snapshot());
         }
         if self.cursor < self.entries.len() {
             self.entries.truncate(self.cursor);
             self.checkpoints
                 .retain(|checkpoint| checkpoint.applied_len <= self.cursor);
         }
-        let coalesced_before_hash = match &operation {
-            PaneOperation::SetSplitRatio { split, .. } if self.cursor == self.entries.len() => self
-                .entries
-                .last()
+        self.entries.push(PaneInteractionTimelineEntry {
+            sequence,
+            operation_id: operation_seed,
+            operation: first.clone(),
+            grouped_operations: rest.to_vec(),
+            before_hash: outcomes[0].before_hash,
+            after_hash: outcomes[outcomes.len() - 1].after_hash,
+        });
+        self.cursor = self.entries.len();
+        self.enforce_entry_limit();
+        self.maybe_record_checkpoint(tree);
+        Ok(outcomes)
+    }
+
+    /// Apply one operation and merge adjacent resize deltas for the same split.
+    ///
+    /// The first delta keeps its pre-gesture hash so undo still restores the
+    /// state before the drag began, while replay uses the most recent ratio.
+    /// Coalescing is limited to entries whose operation id is above the
+    /// gesture-start boundary, so separate drags on the same split remain
+    /// separate undo steps.
+    pub fn apply_and_record_coalesced_resize_delta(
+        &mut self,
+        tree: &mut PaneTree,
+        sequence: u64,
+        operation_id: u64,
+        operation: PaneOperation,
+        coalesce_after_operation_id: u64,
+    ) -> Result<PaneOperationOutcome, PaneOperationError> {
+        if self.baseline.is_none() {
+            self.baseline = Some(tree.to_snapshot());
+        }
+        if self.cursor < self.entries.len() {
+            self.entries.truncate(self.cursor);
+            self.checkpoints
+                .retain(|checkpoint| checkpoint.applied_len <= self.cursor);
+        }
+        let coalesced_before_hash = match &operation {
+            PaneOperation::SetSplitRatio { split, .. } if self.cursor == self.entries.len() => self
+                .entries
+                .last()
                 .and_then(|entry| match &entry.operation {
                     PaneOperation::SetSplitRatio {
                         split: previous_split,
                         ..
                     } if previous_split == split
+                        && entry.grouped_operations.is_empty()
                         && entry.operation_id > coalesce_after_operation_id =>
                     {
                         Some(entry.before_hash)
@@ -5778,6 +6996,7 @@ impl PaneInteractionTimeline {
                 sequence,
                 operation_id,
                 operation,
+                grouped_operations: Vec::new(),
                 before_hash,
                 after_hash: outcome.after_hash,
             };
@@ -5793,6 +7012,7 @@ impl PaneInteractionTimeline {
             sequence,
             operation_id,
             operation,
+            grouped_operations: Vec::new(),
             before_hash: outcome.before_hash,
             after_hash: outcome.after_hash,
         });
@@ -5826,8 +7046,10 @@ impl PaneInteractionTimeline {
     pub fn replay(&self) -> Result<PaneTree, PaneInteractionTimelineError> {
         let (mut tree, start_idx) = self.restore_replay_start()?;
         for entry in self.entries.iter().take(self.cursor).skip(start_idx) {
-            tree.apply_operation_in_place_for_replay(entry.operation_id, &entry.operation)
-                .map_err(|source| PaneInteractionTimelineError::ApplyFailed { source })?;
+            for (operation_id, operation) in entry.operations() {
+                tree.apply_operation_in_place_for_replay(operation_id, operation)
+                    .map_err(|source| PaneInteractionTimelineError::ApplyFailed { source })?;
+            }
         }
         Ok(tree)
     }
@@ -5893,9 +7115,14 @@ impl PaneInteractionTimeline {
             return;
         };
 
-        for entry in self.entries.iter().take(prune_count) {
+        for (operation_id, operation) in self
+            .entries
+            .iter()
+            .take(prune_count)
+            .flat_map(PaneInteractionTimelineEntry::operations)
+        {
             if baseline_tree
-                .apply_operation_in_place_for_replay(entry.operation_id, &entry.operation)
+                .apply_operation_in_place_for_replay(operation_id, operation)
                 .is_err()
             {
                 return;
@@ -5965,11 +7192,20 @@ impl PaneTimelineSnapshotRetentionStats {
 fn pane_operation_retained_payload_bytes(operation: &PaneOperation) -> usize {
     match operation {
         PaneOperation::SplitLeaf { new_leaf, .. } => pane_leaf_retained_payload_bytes(new_leaf),
+        PaneOperation::AddTab { surface_key, .. } => surface_key.len(),
         PaneOperation::CloseNode { .. }
         | PaneOperation::MoveSubtree { .. }
         | PaneOperation::SwapNodes { .. }
         | PaneOperation::SetSplitRatio { .. }
-        | PaneOperation::NormalizeRatios => 0,
+        | PaneOperation::NormalizeRatios
+        | PaneOperation::SetSplitAxis { .. }
+        | PaneOperation::SelectTab { .. }
+        | PaneOperation::CloseTab { .. }
+        | PaneOperation::MoveTab { .. }
+        | PaneOperation::SetZoom { .. }
+        | PaneOperation::FloatNode { .. }
+        | PaneOperation::MoveFloating { .. }
+        | PaneOperation::RaiseFloating { .. } => 0,
     }
 }
 
@@ -5978,7 +7214,9 @@ fn pane_leaf_retained_payload_bytes(leaf: &PaneLeaf) -> usize {
 }
 
 fn pane_leaf_surface_payload_bytes(leaf: &PaneLeaf) -> usize {
-    leaf.surface_key.len()
+    leaf.surface_key
+        .len()
+        .saturating_add(leaf.tabs.iter().map(String::len).sum::<usize>())
 }
 
 fn string_map_payload_bytes(map: &BTreeMap<String, String>) -> usize {
@@ -6140,6 +7378,17 @@ pub enum PaneModelError {
     PaneIdOverflow {
         current: PaneId,
     },
+    InvalidTabStack {
+        node_id: PaneId,
+        active_tab: usize,
+        tab_count: usize,
+    },
+    InvalidFloatingRoot {
+        node_id: PaneId,
+    },
+    InvalidZoomTarget {
+        node_id: PaneId,
+    },
 }
 
 impl fmt::Display for PaneModelError {
@@ -6255,6 +7504,27 @@ impl fmt::Display for PaneModelError {
             Self::PaneIdOverflow { current } => {
                 write!(f, "pane id overflow after {}", current.0)
             }
+            Self::InvalidTabStack {
+                node_id,
+                active_tab,
+                tab_count,
+            } => write!(
+                f,
+                "leaf {} has inconsistent tab stack (active {active_tab} of {tab_count})",
+                node_id.0
+            ),
+            Self::InvalidFloatingRoot { node_id } => write!(
+                f,
+                "floating pane {} must be an existing, unparented, non-root node listed once",
+                node_id.0
+            ),
+            Self::InvalidZoomTarget { node_id } => {
+                write!(
+                    f,
+                    "zoomed node {} is not docked in the split tree",
+                    node_id.0
+                )
+            }
         }
     }
 }
@@ -6350,6 +7620,14 @@ fn snapshot_state_hash(snapshot: &PaneTreeSnapshot) -> u64 {
                 mix(&mut hash, 1);
                 mix_str(&mut hash, &leaf.surface_key);
                 mix_extensions(&mut hash, &leaf.extensions);
+                if !leaf.tabs.is_empty() {
+                    mix(&mut hash, 3);
+                    mix_u64(&mut hash, leaf.tabs.len() as u64);
+                    for tab in &leaf.tabs {
+                        mix_str(&mut hash, tab);
+                    }
+                    mix_u64(&mut hash, leaf.active_tab as u64);
+                }
             }
             PaneNodeKind::Split(split) => {
                 mix(&mut hash, 2);
@@ -6366,6 +7644,23 @@ fn snapshot_state_hash(snapshot: &PaneTreeSnapshot) -> u64 {
         }
     }
 
+    if let Some(zoomed) = canonical.zoomed {
+        mix(&mut hash, 4);
+        mix_u64(&mut hash, zoomed.get());
+    }
+    if !canonical.floating.is_empty() {
+        mix(&mut hash, 5);
+        mix_u64(&mut hash, canonical.floating.len() as u64);
+        for floating in &canonical.floating {
+            mix_u64(&mut hash, floating.id.get());
+            mix_u16(&mut hash, floating.rect.x);
+            mix_u16(&mut hash, floating.rect.y);
+            mix_u16(&mut hash, floating.rect.width);
+            mix_u16(&mut hash, floating.rect.height);
+            mix_u32(&mut hash, floating.z);
+        }
+    }
+
     hash
 }
 
@@ -6474,6 +7769,26 @@ fn build_invariant_report(snapshot: &PaneTreeSnapshot) -> PaneInvariantReport {
         );
     }
 
+    let mut floating_roots = BTreeSet::new();
+    for record in &snapshot.floating {
+        if record.id == snapshot.root
+            || !nodes.contains_key(&record.id)
+            || !floating_roots.insert(record.id)
+        {
+            push_invariant_issue(
+                &mut issues,
+                PaneInvariantCode::InvalidFloatingRoot,
+                false,
+                Some(record.id),
+                None,
+                format!(
+                    "floating pane {} must be an existing non-root node listed once",
+                    record.id.get()
+                ),
+            );
+        }
+    }
+
     let mut expected_parents = BTreeMap::new();
     for node in nodes.values() {
         if let Err(err) = node.constraints.validate(node.id) {
@@ -6487,6 +7802,24 @@ fn build_invariant_report(snapshot: &PaneTreeSnapshot) -> PaneInvariantReport {
             );
         }
 
+        if let PaneNodeKind::Leaf(leaf) = &node.kind
+            && !leaf.tab_stack_is_consistent()
+        {
+            push_invariant_issue(
+                &mut issues,
+                PaneInvariantCode::InvalidTabStack,
+                false,
+                Some(node.id),
+                None,
+                format!(
+                    "leaf {} has inconsistent tab stack (active {} of {})",
+                    node.id.get(),
+                    leaf.active_tab,
+                    leaf.tabs.len()
+                ),
+            );
+        }
+
         if let Some(parent) = node.parent
             && !nodes.contains_key(&parent)
         {
@@ -6602,8 +7935,25 @@ fn build_invariant_report(snapshot: &PaneTreeSnapshot) -> PaneInvariantReport {
         );
     }
 
+    for node_id in &floating_roots {
+        if let Some(parent) = expected_parents.get(node_id) {
+            push_invariant_issue(
+                &mut issues,
+                PaneInvariantCode::InvalidFloatingRoot,
+                false,
+                Some(*node_id),
+                Some(*parent),
+                format!(
+                    "floating pane {} is also a child of split {}",
+                    node_id.get(),
+                    parent.get()
+                ),
+            );
+        }
+    }
+
     for node in nodes.values() {
-        let expected_parent = if node.id == snapshot.root {
+        let expected_parent = if node.id == snapshot.root || floating_roots.contains(&node.id) {
             None
         } else {
             expected_parents.get(&node.id).copied()
@@ -6637,6 +7987,30 @@ fn build_invariant_report(snapshot: &PaneTreeSnapshot) -> PaneInvariantReport {
             &mut visited,
             &mut cycle_nodes,
         );
+        if let Some(zoomed) = snapshot.zoomed
+            && !visited.contains(&zoomed)
+        {
+            push_invariant_issue(
+                &mut issues,
+                PaneInvariantCode::InvalidZoomTarget,
+                true,
+                Some(zoomed),
+                None,
+                format!(
+                    "zoomed node {} is not docked in the split tree",
+                    zoomed.get()
+                ),
+            );
+        }
+        for floating_root in &floating_roots {
+            dfs_collect_cycles_and_reachable(
+                *floating_root,
+                &nodes,
+                &mut visiting,
+                &mut visited,
+                &mut cycle_nodes,
+            );
+        }
 
         for node_id in cycle_nodes {
             push_invariant_issue(
@@ -6729,8 +8103,13 @@ fn repair_snapshot_safe(
         }
     }
 
+    let floating_roots = snapshot
+        .floating
+        .iter()
+        .map(|floating| floating.id)
+        .collect::<BTreeSet<_>>();
     for node in nodes.values_mut() {
-        let expected_parent = if node.id == snapshot.root {
+        let expected_parent = if node.id == snapshot.root || floating_roots.contains(&node.id) {
             None
         } else {
             expected_parents.get(&node.id).copied()
@@ -6778,6 +8157,21 @@ fn repair_snapshot_safe(
             &mut cycle_nodes,
         );
     }
+    if let Some(zoomed) = snapshot.zoomed
+        && !visited.contains(&zoomed)
+    {
+        actions.push(PaneRepairAction::ClearZoom { node_id: zoomed });
+        snapshot.zoomed = None;
+    }
+    for floating_root in &floating_roots {
+        dfs_collect_cycles_and_reachable(
+            *floating_root,
+            &nodes,
+            &mut visiting,
+            &mut visited,
+            &mut cycle_nodes,
+        );
+    }
     if !cycle_nodes.is_empty() {
         let mut codes = vec![PaneInvariantCode::CycleDetected];
         codes.sort();
@@ -6839,11 +8233,21 @@ fn validate_tree(
     root: PaneId,
     next_id: PaneId,
     nodes: &BTreeMap<PaneId, PaneNodeRecord>,
+    floating: &[PaneFloating],
+    zoomed: Option<PaneId>,
 ) -> Result<(), PaneModelError> {
     if !nodes.contains_key(&root) {
         return Err(PaneModelError::MissingRoot { root });
     }
 
+    let mut floating_roots = BTreeSet::new();
+    for record in floating {
+        if record.id == root || !nodes.contains_key(&record.id) || !floating_roots.insert(record.id)
+        {
+            return Err(PaneModelError::InvalidFloatingRoot { node_id: record.id });
+        }
+    }
+
     let max_existing = nodes.keys().next_back().copied().unwrap_or(root);
     if next_id <= max_existing {
         return Err(PaneModelError::NextIdNotGreaterThanExisting {
@@ -6866,6 +8270,16 @@ fn validate_tree(
             });
         }
 
+        if let PaneNodeKind::Leaf(leaf) = &node.kind
+            && !leaf.tab_stack_is_consistent()
+        {
+            return Err(PaneModelError::InvalidTabStack {
+                node_id: node.id,
+                active_tab: leaf.active_tab,
+                tab_count: leaf.tabs.len(),
+            });
+        }
+
         if let PaneNodeKind::Split(split) = &node.kind {
             if split.ratio.numerator() == 0 || split.ratio.denominator() == 0 {
                 return Err(PaneModelError::InvalidSplitRatio {
@@ -6907,9 +8321,16 @@ fn validate_tree(
     if let Some(parent) = nodes.get(&root).and_then(|node| node.parent) {
         return Err(PaneModelError::RootHasParent { root, parent });
     }
+    if let Some(node_id) = floating_roots
+        .iter()
+        .copied()
+        .find(|node_id| expected_parents.contains_key(node_id))
+    {
+        return Err(PaneModelError::InvalidFloatingRoot { node_id });
+    }
 
     for node in nodes.values() {
-        let expected = if node.id == root {
+        let expected = if node.id == root || floating_roots.contains(&node.id) {
             None
         } else {
             expected_parents.get(&node.id).copied()
@@ -6926,6 +8347,14 @@ fn validate_tree(
     let mut visiting = BTreeSet::new();
     let mut visited = BTreeSet::new();
     dfs_validate(root, nodes, &mut visiting, &mut visited)?;
+    if let Some(node_id) = zoomed
+        && !visited.contains(&node_id)
+    {
+        return Err(PaneModelError::InvalidZoomTarget { node_id });
+    }
+    for floating_root in &floating_roots {
+        dfs_validate(*floating_root, nodes, &mut visiting, &mut visited)?;
+    }
 
     if visited.len() != nodes.len()
         && let Some(node_id) = nodes.keys().find(|node_id| !visited.contains(node_id))
@@ -7095,6 +8524,8 @@ mod tests {
                     Some(root),
                     PaneLeaf {
                         surface_key: "right".to_string(),
+                        tabs: Vec::new(),
+                        active_tab: 0,
                         extensions: BTreeMap::new(),
                     },
                 ),
@@ -7113,10 +8544,14 @@ mod tests {
                     Some(root),
                     PaneLeaf {
                         surface_key: "left".to_string(),
+                        tabs: Vec::new(),
+                        active_tab: 0,
                         extensions: BTreeMap::new(),
                     },
                 ),
             ],
+            zoomed: None,
+            floating: Vec::new(),
             extensions: BTreeMap::new(),
         }
     }
@@ -7171,10 +8606,34 @@ mod tests {
                     PaneLeaf::new("right_bottom"),
                 ),
             ],
+            zoomed: None,
+            floating: Vec::new(),
             extensions: BTreeMap::new(),
         }
     }
 
+    #[test]
+    fn minimum_size_propagates_through_splits() {
+        let mut snapshot = make_nested_snapshot();
+        for node in &mut snapshot.nodes {
+            let (min_width, min_height) = match node.id.get() {
+                2 => (20, 5),
+                4 => (10, 4),
+                5 => (12, 3),
+                _ => continue,
+            };
+            node.constraints.min_width = min_width;
+            node.constraints.min_height = min_height;
+        }
+        let tree = PaneTree::from_snapshot(snapshot).expect("snapshot should validate");
+
+        assert_eq!(tree.subtree_minimum_size(id(3)), Size::new(12, 7));
+        assert_eq!(tree.minimum_size(), Size::new(32, 7));
+        assert_eq!(tree.subtree_minimum_size(id(99)), Size::ZERO);
+        assert!(tree.solve_layout(Rect::new(0, 0, 31, 7)).is_err());
+        assert!(tree.solve_layout(Rect::new(0, 0, 40, 10)).is_ok());
+    }
+
     #[test]
     fn ratio_is_normalized() {
         let ratio = PaneSplitRatio::new(12, 8).expect("ratio should normalize");
@@ -9206,6 +10665,669 @@ mod tests {
         assert!(!plan.operations.is_empty());
     }
 
+    #[test]
+    fn tab_operations_keep_surface_key_on_active_tab() {
+        let mut tree = PaneTree::from_snapshot(make_valid_snapshot()).expect("valid tree");
+        let left = id(2);
+        for (op_id, key) in [(1, "b"), (2, "c")] {
+            tree.apply_operation(
+                op_id,
+                PaneOperation::AddTab {
+                    target: left,
+                    surface_key: key.to_string(),
+                },
+            )
+            .expect("add tab");
+        }
+        let leaf_of = |tree: &PaneTree| {
+            let PaneNodeKind::Leaf(leaf) = &tree.node(left).expect("left exists").kind else {
+                unreachable!("left must be a leaf");
+            };
+            leaf.clone()
+        };
+        let leaf = leaf_of(&tree);
+        assert_eq!(leaf.tab_count(), 3);
+        assert_eq!(leaf.active_tab(), 2);
+        assert_eq!(leaf.surface_key, "c");
+
+        tree.apply_operation(
+            3,
+            PaneOperation::SelectTab {
+                target: left,
+                index: 0,
+            },
+        )
+        .expect("select tab");
+        assert_eq!(leaf_of(&tree).surface_key, "left");
+
+        tree.apply_operation(
+            4,
+            PaneOperation::MoveTab {
+                target: left,
+                from: 0,
+                to: 2,
+            },
+        )
+        .expect("move tab");
+        let leaf = leaf_of(&tree);
+        assert_eq!(leaf.tab(0), Some("b"));
+        assert_eq!(leaf.tab(2), Some("left"));
+        assert_eq!(leaf.active_tab(), 2);
+        assert_eq!(leaf.surface_key, "left");
+
+        tree.apply_operation(
+            5,
+            PaneOperation::CloseTab {
+                target: left,
+                index: 0,
+            },
+        )
+        .expect("close tab");
+        let leaf = leaf_of(&tree);
+        assert_eq!(leaf.tab_count(), 2);
+        assert_eq!(leaf.active_tab(), 1);
+        assert_eq!(leaf.surface_key, "left");
+        assert!(tree.validate().is_ok());
+    }
+
+    #[test]
+    fn closing_last_tab_is_rejected() {
+        let mut tree = PaneTree::singleton("only");
+        let err = tree
+            .apply_operation(
+                1,
+                PaneOperation::CloseTab {
+                    target: id(1),
+                    index: 0,
+                },
+            )
+            .expect_err("last tab cannot close");
+        assert_eq!(
+            err.reason,
+            PaneOperationFailure::CannotCloseLastTab { node_id: id(1) }
+        );
+
+        let err = tree
+            .apply_operation(
+                2,
+                PaneOperation::SelectTab {
+                    target: id(1),
+                    index: 3,
+                },
+            )
+            .expect_err("index out of range");
+        assert_eq!(
+            err.reason,
+            PaneOperationFailure::TabOutOfRange {
+                node_id: id(1),
+                index: 3,
+                tab_count: 1,
+            }
+        );
+        assert_eq!(err.before_hash, err.after_hash);
+    }
+
+    #[test]
+    fn tab_stack_round_trips_and_changes_hash() {
+        let plain = PaneTree::from_snapshot(make_valid_snapshot()).expect("valid tree");
+        let mut snapshot = make_valid_snapshot();
+        for node in &mut snapshot.nodes {
+            if node.id == id(2) {
+                node.kind = PaneNodeKind::Leaf(PaneLeaf::tabbed(["left", "logs"], 0));
+            }
+        }
+        let tabbed = PaneTree::from_snapshot(snapshot).expect("tabbed tree validates");
+        assert_ne!(plain.state_hash(), tabbed.state_hash());
+
+        let json = serde_json::to_string(&tabbed.to_snapshot()).expect("serialize");
+        let parsed: PaneTreeSnapshot = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed.state_hash(), tabbed.state_hash());
+    }
+
+    #[test]
+    fn inconsistent_tab_stack_is_rejected() {
+        let mut snapshot = make_valid_snapshot();
+        for node in &mut snapshot.nodes {
+            if let PaneNodeKind::Leaf(leaf) = &mut node.kind
+                && node.id == id(2)
+            {
+                leaf.tabs = vec!["left".to_string(), "logs".to_string()];
+                leaf.active_tab = 5;
+            }
+        }
+        let err = PaneTree::from_snapshot(snapshot).expect_err("bad tab stack");
+        assert_eq!(
+            err,
+            PaneModelError::InvalidTabStack {
+                node_id: id(2),
+                active_tab: 5,
+                tab_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn zoom_solves_target_over_full_area_without_touching_tree() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        let area = Rect::new(0, 0, 80, 24);
+        let before = tree.to_snapshot().nodes;
+        tree.apply_operation(
+            1,
+            PaneOperation::SetZoom {
+                target: Some(id(4)),
+            },
+        )
+        .expect("zoom");
+        assert_eq!(tree.zoomed(), Some(id(4)));
+        assert_eq!(tree.to_snapshot().nodes, before);
+
+        let layout = tree.solve_layout(area).expect("layout");
+        assert_eq!(layout.rect(id(4)), Some(area));
+        assert_eq!(layout.rect(id(2)), None);
+
+        tree.apply_operation(2, PaneOperation::SetZoom { target: None })
+            .expect("unzoom");
+        let layout = tree.solve_layout(area).expect("layout");
+        assert!(layout.rect(id(2)).is_some());
+    }
+
+    #[test]
+    fn zoom_clears_when_zoomed_node_is_closed() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        tree.apply_operation(
+            1,
+            PaneOperation::SetZoom {
+                target: Some(id(4)),
+            },
+        )
+        .expect("zoom");
+        tree.apply_operation(2, PaneOperation::CloseNode { target: id(3) })
+            .expect("close subtree");
+        assert_eq!(tree.zoomed(), None);
+        assert!(tree.validate().is_ok());
+    }
+
+    #[test]
+    fn float_node_detaches_subtree_and_stacks_by_z() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        tree.apply_operation(
+            1,
+            PaneOperation::FloatNode {
+                target: id(4),
+                rect: PaneFloatingRect::new(10, 5, 20, 8),
+            },
+        )
+        .expect("float right_top");
+        assert_eq!(tree.node(id(4)).and_then(|node| node.parent), None);
+        assert!(tree.node(id(3)).is_none(), "old split parent is removed");
+        assert_eq!(tree.floating_root_of(id(4)), Some(id(4)));
+        assert!(!tree.is_docked(id(4)));
+
+        tree.apply_operation(
+            2,
+            PaneOperation::FloatNode {
+                target: id(2),
+                rect: PaneFloatingRect::new(0, 0, 10, 4),
+            },
+        )
+        .expect("float left");
+        assert_eq!(tree.root(), id(5));
+
+        let area = Rect::new(0, 0, 80, 24);
+        let layout = tree.solve_layout(area).expect("layout");
+        assert_eq!(layout.rect(id(5)), Some(area));
+        assert_eq!(layout.rect(id(4)), Some(Rect::new(10, 5, 20, 8)));
+        assert_eq!(layout.floating(), &[id(4), id(2)]);
+
+        tree.apply_operation(3, PaneOperation::RaiseFloating { target: id(4) })
+            .expect("raise");
+        let layout = tree.solve_layout(area).expect("layout");
+        assert_eq!(layout.floating(), &[id(2), id(4)]);
+        assert!(tree.validate().is_ok());
+
+        let restored = PaneTree::from_snapshot(tree.to_snapshot()).expect("round trip");
+        assert_eq!(restored.state_hash(), tree.state_hash());
+    }
+
+    #[test]
+    fn float_node_rejects_root_and_zoom_rejects_floating() {
+        let mut tree = PaneTree::from_snapshot(make_valid_snapshot()).expect("valid tree");
+        let err = tree
+            .apply_operation(
+                1,
+                PaneOperation::FloatNode {
+                    target: id(1),
+                    rect: PaneFloatingRect::new(0, 0, 4, 4),
+                },
+            )
+            .expect_err("root cannot float");
+        assert_eq!(
+            err.reason,
+            PaneOperationFailure::CannotMoveRoot { node_id: id(1) }
+        );
+
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        tree.apply_operation(
+            2,
+            PaneOperation::FloatNode {
+                target: id(3),
+                rect: PaneFloatingRect::new(0, 0, 4, 4),
+            },
+        )
+        .expect("float");
+        let err = tree
+            .apply_operation(
+                3,
+                PaneOperation::SetZoom {
+                    target: Some(id(4)),
+                },
+            )
+            .expect_err("floating panes cannot zoom");
+        assert_eq!(
+            err.reason,
+            PaneOperationFailure::NotDocked { node_id: id(4) }
+        );
+        let err = tree
+            .apply_operation(4, PaneOperation::RaiseFloating { target: id(2) })
+            .expect_err("docked pane is not floating");
+        assert_eq!(
+            err.reason,
+            PaneOperationFailure::NotFloating { node_id: id(2) }
+        );
+    }
+
+    #[test]
+    fn closing_floating_root_drops_record() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        tree.apply_operation(
+            1,
+            PaneOperation::FloatNode {
+                target: id(3),
+                rect: PaneFloatingRect::new(0, 0, 20, 10),
+            },
+        )
+        .expect("float");
+        tree.apply_operation(2, PaneOperation::CloseNode { target: id(3) })
+            .expect("close floating");
+        assert!(tree.floating().is_empty());
+        assert!(tree.node(id(3)).is_none());
+        assert!(tree.node(id(4)).is_none());
+        assert!(tree.node(id(5)).is_none());
+        assert!(tree.validate().is_ok());
+    }
+
+    #[test]
+    fn floating_pane_docks_back_through_dock_preview() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        tree.apply_operation(
+            1,
+            PaneOperation::FloatNode {
+                target: id(4),
+                rect: PaneFloatingRect::new(10, 10, 20, 10),
+            },
+        )
+        .expect("float");
+        let layout = tree
+            .solve_layout(Rect::new(0, 0, 100, 40))
+            .expect("layout should solve");
+        let left_rect = layout.rect(id(2)).expect("left rect");
+        let pointer = PanePointerPosition::new(
+            i32::from(left_rect.x),
+            i32::from(left_rect.y.saturating_add(left_rect.height / 2)),
+        );
+
+        let plan = tree
+            .plan_reflow_move_with_preview(
+                id(4),
+                &layout,
+                pointer,
+                PaneMotionVector::from_delta(-24, 0, 48, 0),
+                None,
+                PANE_MAGNETIC_FIELD_CELLS,
+            )
+            .expect("dock plan should build");
+        assert_eq!(plan.preview.target, id(2));
+        assert_eq!(plan.preview.zone, PaneDockZone::Left);
+        tree.apply_reflow_move_plan(10, &plan).expect("dock");
+
+        assert!(tree.floating().is_empty());
+        assert!(tree.is_docked(id(4)));
+        let parent = tree
+            .node(id(4))
+            .and_then(|node| node.parent)
+            .expect("docked pane has a parent");
+        let PaneNodeKind::Split(split) = &tree.node(parent).expect("parent exists").kind else {
+            unreachable!("parent must be a split");
+        };
+        assert_eq!(split.first, id(4));
+        assert_eq!(split.second, id(2));
+        assert!(tree.validate().is_ok());
+    }
+
+    #[test]
+    fn floating_root_with_parent_is_rejected() {
+        let mut snapshot = make_valid_snapshot();
+        snapshot.floating.push(PaneFloating {
+            id: id(2),
+            rect: PaneFloatingRect::new(0, 0, 10, 10),
+            z: 0,
+        });
+        let err = PaneTree::from_snapshot(snapshot).expect_err("split child cannot float");
+        assert_eq!(err, PaneModelError::InvalidFloatingRoot { node_id: id(2) });
+    }
+
+    #[test]
+    fn focus_neighbor_follows_layout_geometry() {
+        let tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        let layout = tree
+            .solve_layout(Rect::new(0, 0, 100, 40))
+            .expect("layout should solve");
+        let focus = |from: u64, direction| {
+            tree.plan_command(id(from), &layout, PaneCommand::FocusNeighbor { direction })
+                .map(|plan| {
+                    assert!(plan.operations.is_empty());
+                    plan.focus
+                })
+        };
+
+        assert_eq!(focus(2, PaneDirection::Right), Ok(id(4)));
+        assert_eq!(focus(4, PaneDirection::Down), Ok(id(5)));
+        assert_eq!(focus(5, PaneDirection::Left), Ok(id(2)));
+        assert_eq!(
+            focus(2, PaneDirection::Left),
+            Err(PaneCommandPlanError::NoNeighbor {
+                leaf: id(2),
+                direction: PaneDirection::Left,
+            })
+        );
+        assert_eq!(
+            tree.plan_command(id(3), &layout, PaneCommand::Equalize),
+            Err(PaneCommandPlanError::NodeNotLeaf { node: id(3) })
+        );
+    }
+
+    #[test]
+    fn swap_and_move_commands_plan_pointer_operations() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        let layout = tree
+            .solve_layout(Rect::new(0, 0, 100, 40))
+            .expect("layout should solve");
+
+        let swap = tree
+            .plan_command(
+                id(5),
+                &layout,
+                PaneCommand::SwapWithNeighbor {
+                    direction: PaneDirection::Left,
+                },
+            )
+            .expect("swap plan");
+        assert_eq!(
+            swap.operations,
+            vec![PaneOperation::SwapNodes {
+                first: id(5),
+                second: id(2),
+            }]
+        );
+
+        let plan = tree
+            .plan_command(
+                id(4),
+                &layout,
+                PaneCommand::MoveToward {
+                    direction: PaneDirection::Left,
+                },
+            )
+            .expect("move plan");
+        assert_eq!(plan.focus, id(4));
+        assert!(matches!(
+            plan.operations.as_slice(),
+            [PaneOperation::MoveSubtree {
+                source,
+                target,
+                axis: SplitAxis::Horizontal,
+                placement: PanePlacement::IncomingFirst,
+                ..
+            }] if *source == id(4) && *target == id(2)
+        ));
+        tree.apply_command_plan(1, &plan).expect("move applies");
+        let layout = tree
+            .solve_layout(Rect::new(0, 0, 100, 40))
+            .expect("layout should solve");
+        let moved = layout.rect(id(4)).expect("moved rect");
+        let anchor = layout.rect(id(2)).expect("anchor rect");
+        assert!(moved.right() <= anchor.x);
+        assert!(tree.validate().is_ok());
+    }
+
+    #[test]
+    fn rotate_split_flips_parent_axis() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        let area = Rect::new(0, 0, 100, 40);
+        let layout = tree.solve_layout(area).expect("layout should solve");
+        let plan = tree
+            .plan_command(id(4), &layout, PaneCommand::RotateSplit)
+            .expect("rotate plan");
+        assert_eq!(
+            plan.operations,
+            vec![PaneOperation::SetSplitAxis {
+                split: id(3),
+                axis: SplitAxis::Horizontal,
+            }]
+        );
+        tree.apply_command_plan(1, &plan).expect("rotate applies");
+        let layout = tree.solve_layout(area).expect("layout should solve");
+        let top = layout.rect(id(4)).expect("rect");
+        let bottom = layout.rect(id(5)).expect("rect");
+        assert_eq!(top.y, bottom.y);
+        assert!(top.right() <= bottom.x);
+
+        let single = PaneTree::singleton("solo");
+        let layout = single.solve_layout(area).expect("layout should solve");
+        assert_eq!(
+            single.plan_command(id(1), &layout, PaneCommand::RotateSplit),
+            Err(PaneCommandPlanError::NoParentSplit { leaf: id(1) })
+        );
+    }
+
+    #[test]
+    fn equalize_weights_splits_by_pane_count() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        tree.apply_operation(
+            1,
+            PaneOperation::SetSplitAxis {
+                split: id(3),
+                axis: SplitAxis::Horizontal,
+            },
+        )
+        .expect("axis change");
+        let area = Rect::new(0, 0, 90, 30);
+        let layout = tree.solve_layout(area).expect("layout should solve");
+        let plan = tree
+            .plan_command(id(2), &layout, PaneCommand::Equalize)
+            .expect("equalize plan");
+        assert_eq!(
+            plan.operations,
+            vec![PaneOperation::SetSplitRatio {
+                split: id(1),
+                ratio: PaneSplitRatio::new(1, 2).expect("valid ratio"),
+            }]
+        );
+        tree.apply_command_plan(2, &plan).expect("equalize applies");
+        let layout = tree.solve_layout(area).expect("layout should solve");
+        for leaf in [2, 4, 5] {
+            assert_eq!(layout.rect(id(leaf)).map(|rect| rect.width), Some(30));
+        }
+    }
+
+    #[test]
+    fn resize_by_moves_nearest_border_in_cells() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        let area = Rect::new(0, 0, 100, 40);
+        let layout = tree.solve_layout(area).expect("layout should solve");
+        let plan = tree
+            .plan_command(
+                id(2),
+                &layout,
+                PaneCommand::ResizeBy {
+                    direction: PaneDirection::Right,
+                    cells: 5,
+                },
+            )
+            .expect("resize plan");
+        tree.apply_command_plan(1, &plan).expect("resize applies");
+        let layout = tree.solve_layout(area).expect("layout should solve");
+        assert_eq!(layout.rect(id(2)).map(|rect| rect.width), Some(55));
+
+        let plan = tree
+            .plan_command(
+                id(5),
+                &layout,
+                PaneCommand::ResizeBy {
+                    direction: PaneDirection::Up,
+                    cells: 500,
+                },
+            )
+            .expect("clamped resize plan");
+        tree.apply_command_plan(2, &plan).expect("resize applies");
+        let layout = tree.solve_layout(area).expect("layout should solve");
+        assert_eq!(layout.rect(id(4)).map(|rect| rect.height), Some(1));
+
+        assert_eq!(
+            tree.plan_command(
+                id(2),
+                &layout,
+                PaneCommand::ResizeBy {
+                    direction: PaneDirection::Up,
+                    cells: 1,
+                },
+            ),
+            Err(PaneCommandPlanError::NoAxisSplit {
+                leaf: id(2),
+                axis: SplitAxis::Vertical,
+            })
+        );
+    }
+
+    #[test]
+    fn close_and_merge_commands_pick_next_focus() {
+        let tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        let layout = tree
+            .solve_layout(Rect::new(0, 0, 100, 40))
+            .expect("layout should solve");
+
+        let close = tree
+            .plan_command(id(4), &layout, PaneCommand::Close)
+            .expect("close plan");
+        assert_eq!(close.focus, id(5));
+        assert_eq!(
+            close.operations,
+            vec![PaneOperation::CloseNode { target: id(4) }]
+        );
+
+        let close = tree
+            .plan_command(id(2), &layout, PaneCommand::Close)
+            .expect("close plan");
+        assert_eq!(close.focus, id(4));
+
+        let mut merged = tree.clone();
+        let plan = tree
+            .plan_command(
+                id(4),
+                &layout,
+                PaneCommand::MergeInto {
+                    direction: PaneDirection::Down,
+                },
+            )
+            .expect("merge plan");
+        assert_eq!(plan.focus, id(5));
+        merged.apply_command_plan(1, &plan).expect("merge applies");
+        assert!(merged.node(id(4)).is_none());
+        let PaneNodeKind::Leaf(leaf) = &merged.node(id(5)).expect("target survives").kind else {
+            unreachable!("merge target is a leaf");
+        };
+        assert_eq!(leaf.tab(0), Some("right_bottom"));
+        assert_eq!(leaf.tab(1), Some("right_top"));
+        assert_eq!(leaf.surface_key, "right_top");
+
+        let single = PaneTree::singleton("solo");
+        let layout = single
+            .solve_layout(Rect::new(0, 0, 10, 10))
+            .expect("layout should solve");
+        assert_eq!(
+            single.plan_command(id(1), &layout, PaneCommand::Close),
+            Err(PaneCommandPlanError::CannotCloseRoot { leaf: id(1) })
+        );
+    }
+
+    #[test]
+    fn command_plans_record_undoable_timeline_entries() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        let original_hash = tree.state_hash();
+        let mut timeline = PaneInteractionTimeline::with_baseline(&tree);
+        let layout = tree
+            .solve_layout(Rect::new(0, 0, 100, 40))
+            .expect("layout should solve");
+        let plan = tree
+            .plan_command(
+                id(4),
+                &layout,
+                PaneCommand::MergeInto {
+                    direction: PaneDirection::Down,
+                },
+            )
+            .expect("merge plan");
+
+        let outcomes = timeline
+            .apply_and_record_command_plan(&mut tree, 7, 100, &plan)
+            .expect("plan records");
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(timeline.applied_len(), 1);
+        let entry = &timeline.entries[0];
+        assert_eq!(entry.sequence, 7);
+        assert_eq!(entry.before_hash, original_hash);
+        assert_eq!(entry.after_hash, tree.state_hash());
+        let ids: Vec<u64> = entry.operations().map(|(id, _)| id).collect();
+        assert_eq!(ids, [100, 101]);
+        assert_eq!(timeline.next_operation_id(), 102);
+
+        assert!(timeline.undo(&mut tree).expect("undo merge"));
+        assert_eq!(tree.state_hash(), original_hash);
+        assert!(!timeline.undo(&mut tree).expect("nothing left"));
+        assert!(timeline.redo(&mut tree).expect("redo merge"));
+        assert!(tree.node(id(4)).is_none());
+    }
+
+    #[test]
+    fn failed_command_plan_rolls_back_and_records_nothing() {
+        let mut tree = PaneTree::from_snapshot(make_nested_snapshot()).expect("valid tree");
+        let original = tree.clone();
+        let mut timeline = PaneInteractionTimeline::with_baseline(&tree);
+        let layout = tree
+            .solve_layout(Rect::new(0, 0, 100, 40))
+            .expect("layout should solve");
+        let mut plan = tree
+            .plan_command(
+                id(4),
+                &layout,
+                PaneCommand::MergeInto {
+                    direction: PaneDirection::Down,
+                },
+            )
+            .expect("merge plan");
+        plan.operations
+            .push(PaneOperation::CloseNode { target: id(4) });
+
+        assert!(
+            timeline
+                .apply_and_record_command_plan(&mut tree, 7, 100, &plan)
+                .is_err()
+        );
+        assert_eq!(tree, original);
+        assert!(timeline.entries.is_empty());
+        assert_eq!(timeline.applied_len(), 0);
+    }
+
     #[test]
     fn group_move_and_group_resize_plan_generation() {
         let tree = PaneTree::from_snapshot(make_valid_snapshot()).expect("valid tree");
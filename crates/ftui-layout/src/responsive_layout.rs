@@ -34,6 +34,8 @@
 //! 3. `split()` auto-detects breakpoint from area width.
 //! 4. `split_for()` uses an explicit breakpoint (no auto-detection).
 //! 5. Result count may differ between breakpoints (caller must handle this).
+//! 6. Container queries are checked in insertion order against the split
+//!    area and take precedence over breakpoints, including in `split_for()`.
 //!
 //! # Failure Modes
 //!
@@ -41,8 +43,29 @@
 //! - Breakpoint changes mid-session: caller must handle state transitions
 //!   (e.g., re-mapping children). Use [`ResponsiveSplit::breakpoint`] to
 //!   detect changes.
+//!
+//! # Container Queries
+//!
+//! Breakpoints usually track the terminal width, shared app-wide. A
+//! [`ContainerQuery`] instead tests the size of the area being split, so a
+//! panel placed in a narrow pane switches to its compact layout even on a
+//! wide terminal:
+//!
+//! ```ignore
+//! let layout = ResponsiveLayout::new(two_column())
+//!     .when(ContainerQuery::new().max_width(40), single_column());
+//!
+//! // Each pane leaf is evaluated against its own rectangle.
+//! for (pane, split) in layout.split_pane_leaves(&tree, &pane_layout, &mut cache) {
+//!     // draw into split.rects
+//! }
+//! ```
+
+use std::hash::{Hash, Hasher};
 
 use super::{Breakpoint, Breakpoints, Flex, Rect, Responsive};
+use crate::cache::LayoutCache;
+use crate::pane::{PaneId, PaneLayout, PaneNodeKind, PaneTree};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -55,6 +78,74 @@ pub struct ResponsiveSplit {
     pub breakpoint: Breakpoint,
     /// The resulting layout rectangles.
     pub rects: crate::Rects,
+    /// Index of the container query that selected the layout, if any.
+    pub query: Option<usize>,
+}
+
+/// Size condition evaluated against the container being split.
+///
+/// All bounds are inclusive; unset bounds always match. An empty query
+/// matches every area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ContainerQuery {
+    /// Smallest matching width in columns.
+    pub min_width: Option<u16>,
+    /// Largest matching width in columns.
+    pub max_width: Option<u16>,
+    /// Smallest matching height in rows.
+    pub min_height: Option<u16>,
+    /// Largest matching height in rows.
+    pub max_height: Option<u16>,
+}
+
+impl ContainerQuery {
+    /// Create a query that matches every area.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            min_width: None,
+            max_width: None,
+            min_height: None,
+            max_height: None,
+        }
+    }
+
+    /// Require at least `width` columns.
+    #[must_use]
+    pub const fn min_width(mut self, width: u16) -> Self {
+        self.min_width = Some(width);
+        self
+    }
+
+    /// Require at most `width` columns.
+    #[must_use]
+    pub const fn max_width(mut self, width: u16) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Require at least `height` rows.
+    #[must_use]
+    pub const fn min_height(mut self, height: u16) -> Self {
+        self.min_height = Some(height);
+        self
+    }
+
+    /// Require at most `height` rows.
+    #[must_use]
+    pub const fn max_height(mut self, height: u16) -> Self {
+        self.max_height = Some(height);
+        self
+    }
+
+    /// Whether the container area satisfies every bound.
+    #[must_use]
+    pub fn matches(&self, area: Rect) -> bool {
+        self.min_width.is_none_or(|min| area.width >= min)
+            && self.max_width.is_none_or(|max| area.width <= max)
+            && self.min_height.is_none_or(|min| area.height >= min)
+            && self.max_height.is_none_or(|max| area.height <= max)
+    }
 }
 
 /// A breakpoint-aware layout that switches [`Flex`] configuration at different
@@ -69,6 +160,8 @@ pub struct ResponsiveLayout {
     layouts: Responsive<Flex>,
     /// Breakpoint thresholds for width classification.
     breakpoints: Breakpoints,
+    /// Container queries, checked in order before breakpoints.
+    queries: Vec<(ContainerQuery, Flex)>,
 }
 
 // ---------------------------------------------------------------------------
@@ -84,6 +177,7 @@ impl ResponsiveLayout {
         Self {
             layouts: Responsive::new(base),
             breakpoints: Breakpoints::DEFAULT,
+            queries: Vec::new(),
         }
     }
 
@@ -103,6 +197,16 @@ impl ResponsiveLayout {
         self
     }
 
+    /// Use `layout` whenever the split area matches `query` (builder pattern).
+    ///
+    /// Queries are checked in the order they were added and override the
+    /// breakpoint layouts.
+    #[must_use]
+    pub fn when(mut self, query: ContainerQuery, layout: Flex) -> Self {
+        self.queries.push((query, layout));
+        self
+    }
+
     /// Set the layout for a specific breakpoint (mutating).
     pub fn set(&mut self, bp: Breakpoint, layout: Flex) {
         self.layouts.set(bp, layout);
@@ -137,10 +241,78 @@ impl ResponsiveLayout {
     /// a shared app-level breakpoint state).
     #[must_use]
     pub fn split_for(&self, bp: Breakpoint, area: Rect) -> ResponsiveSplit {
-        let flex = self.layouts.resolve(bp);
+        let (query, flex) = self.select(bp, area);
         ResponsiveSplit {
             breakpoint: bp,
             rects: flex.split(area),
+            query,
+        }
+    }
+
+    /// Like [`split`](Self::split), memoized in a [`LayoutCache`].
+    ///
+    /// The cache key covers the selected layout's full parameters plus which
+    /// query or breakpoint selected it, so layouts that share constraints do
+    /// not collide even across `ResponsiveLayout`s sharing one cache.
+    pub fn split_cached(&self, area: Rect, cache: &mut LayoutCache) -> ResponsiveSplit {
+        let bp = self.breakpoints.classify_width(area.width);
+        self.split_for_cached(bp, area, cache)
+    }
+
+    /// Like [`split_for`](Self::split_for), memoized in a [`LayoutCache`].
+    pub fn split_for_cached(
+        &self,
+        bp: Breakpoint,
+        area: Rect,
+        cache: &mut LayoutCache,
+    ) -> ResponsiveSplit {
+        let (query, flex) = self.select(bp, area);
+        let mut variant = std::collections::hash_map::DefaultHasher::new();
+        match query {
+            Some(index) => (0u8, index).hash(&mut variant),
+            None => (1u8, bp).hash(&mut variant),
+        }
+        let key = flex.cache_key(area).with_variant(variant.finish());
+        ResponsiveSplit {
+            breakpoint: bp,
+            rects: cache.get_or_compute(key, || flex.split(area)),
+            query,
+        }
+    }
+
+    /// Split every leaf of a pane layout against its own rectangle.
+    ///
+    /// Container queries see each leaf's size, so panes of different
+    /// dimensions pick different layouts. Leaves are returned in ID order.
+    pub fn split_pane_leaves(
+        &self,
+        tree: &PaneTree,
+        layout: &PaneLayout,
+        cache: &mut LayoutCache,
+    ) -> Vec<(PaneId, ResponsiveSplit)> {
+        layout
+            .iter()
+            .filter(|(id, _)| {
+                tree.node(*id)
+                    .is_some_and(|node| matches!(node.kind, PaneNodeKind::Leaf(_)))
+            })
+            .map(|(id, rect)| (id, self.split_cached(rect, cache)))
+            .collect()
+    }
+
+    /// Container query that matches `area`, if any.
+    #[must_use]
+    pub fn matching_query(&self, area: Rect) -> Option<usize> {
+        self.queries
+            .iter()
+            .position(|(query, _)| query.matches(area))
+    }
+
+    /// Layout chosen for `area` at breakpoint `bp`.
+    fn select(&self, bp: Breakpoint, area: Rect) -> (Option<usize>, &Flex) {
+        match self.matching_query(area) {
+            Some(index) => (Some(index), &self.queries[index].1),
+            None => (None, self.layouts.resolve(bp)),
         }
     }
 
@@ -400,11 +572,146 @@ mod tests {
         assert_eq!(layout.breakpoints(), bps);
     }
 
+    #[test]
+    fn container_query_matches_inclusive_bounds() {
+        let query = ContainerQuery::new()
+            .min_width(20)
+            .max_width(40)
+            .max_height(10);
+        assert!(query.matches(area(20, 10)));
+        assert!(query.matches(area(40, 1)));
+        assert!(!query.matches(area(19, 10)));
+        assert!(!query.matches(area(41, 10)));
+        assert!(!query.matches(area(30, 11)));
+        assert!(ContainerQuery::new().matches(area(0, 0)));
+    }
+
+    #[test]
+    fn container_query_overrides_breakpoint() {
+        let layout = ResponsiveLayout::new(single_column())
+            .at(Breakpoint::Sm, three_column())
+            .when(ContainerQuery::new().max_height(5), two_column());
+
+        // A short panel uses the query layout even at a wide breakpoint.
+        let result = layout.split_for(Breakpoint::Xl, area(200, 4));
+        assert_eq!(result.query, Some(0));
+        assert_eq!(result.rects.len(), 2);
+
+        let result = layout.split_for(Breakpoint::Xl, area(200, 24));
+        assert_eq!(result.query, None);
+        assert_eq!(result.rects.len(), 3);
+    }
+
+    #[test]
+    fn first_matching_query_wins() {
+        let layout = ResponsiveLayout::new(three_column())
+            .when(ContainerQuery::new().max_width(40), single_column())
+            .when(ContainerQuery::new().max_width(80), two_column());
+
+        assert_eq!(layout.matching_query(area(30, 24)), Some(0));
+        assert_eq!(layout.matching_query(area(60, 24)), Some(1));
+        assert_eq!(layout.matching_query(area(100, 24)), None);
+        assert_eq!(layout.split(area(30, 24)).rects.len(), 1);
+        assert_eq!(layout.split(area(60, 24)).rects.len(), 2);
+    }
+
+    #[test]
+    fn cached_split_keys_on_selected_variant() {
+        // Same constraints and direction, different gap: only the variant
+        // tells the cache entries apart.
+        let wide = Flex::horizontal().constraints([Constraint::Fill, Constraint::Fill]);
+        let narrow = wide.clone().gap(2);
+        let layout = ResponsiveLayout::new(wide).when(ContainerQuery::new().max_height(5), narrow);
+        let mut cache = LayoutCache::new(16);
+
+        let short = Rect::new(0, 0, 40, 5);
+        let tall = Rect::new(0, 0, 40, 6);
+        assert_eq!(layout.split_cached(short, &mut cache), layout.split(short));
+        assert_eq!(layout.split_cached(tall, &mut cache), layout.split(tall));
+        assert_eq!(layout.split_cached(short, &mut cache), layout.split(short));
+        assert_eq!(cache.stats().hits, 1);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn layouts_sharing_a_cache_do_not_collide() {
+        use crate::FlexItem;
+
+        let base = Flex::horizontal().constraints([Constraint::Fill, Constraint::Fill]);
+        let gapped = ResponsiveLayout::new(base.clone().gap(3));
+        let plain = ResponsiveLayout::new(base);
+        let grow = ResponsiveLayout::new(
+            Flex::horizontal().items([FlexItem::flex(1.0), FlexItem::flex(3.0)]),
+        );
+        let even = ResponsiveLayout::new(
+            Flex::horizontal().items([FlexItem::flex(1.0), FlexItem::flex(1.0)]),
+        );
+        let mut cache = LayoutCache::new(16);
+
+        let area = Rect::new(0, 0, 40, 5);
+        for layout in [&plain, &gapped, &grow, &even] {
+            assert_eq!(layout.split_cached(area, &mut cache), layout.split(area));
+        }
+        assert_eq!(cache.stats().hits, 0);
+        assert_eq!(cache.len(), 4);
+    }
+
+    #[test]
+    fn pane_leaves_use_their_own_size() {
+        use crate::pane::{
+            PaneId, PaneLeaf, PaneNodeRecord, PaneSplit, PaneSplitRatio, PaneTree,
+            PaneTreeSnapshot, SplitAxis,
+        };
+        use std::collections::BTreeMap;
+
+        let root = PaneId::new(1).unwrap();
+        let left = PaneId::new(2).unwrap();
+        let right = PaneId::new(3).unwrap();
+        let snapshot = PaneTreeSnapshot {
+            schema_version: crate::pane::PANE_TREE_SCHEMA_VERSION,
+            root,
+            next_id: PaneId::new(4).unwrap(),
+            nodes: vec![
+                PaneNodeRecord::split(
+                    root,
+                    None,
+                    PaneSplit {
+                        axis: SplitAxis::Horizontal,
+                        ratio: PaneSplitRatio::new(1, 4).unwrap(),
+                        first: left,
+                        second: right,
+                    },
+                ),
+                PaneNodeRecord::leaf(left, Some(root), PaneLeaf::new("left")),
+                PaneNodeRecord::leaf(right, Some(root), PaneLeaf::new("right")),
+            ],
+            zoomed: None,
+            floating: Vec::new(),
+            extensions: BTreeMap::new(),
+        };
+        let tree = PaneTree::from_snapshot(snapshot).unwrap();
+        let pane_layout = tree.solve_layout(area(160, 40)).unwrap();
+
+        let layout = ResponsiveLayout::new(three_column())
+            .when(ContainerQuery::new().max_width(60), single_column());
+        let mut cache = LayoutCache::new(16);
+        let splits = layout.split_pane_leaves(&tree, &pane_layout, &mut cache);
+
+        assert_eq!(splits.len(), 2);
+        assert_eq!(splits[0].0, left);
+        assert_eq!(splits[0].1.query, Some(0));
+        assert_eq!(splits[0].1.rects.len(), 1);
+        assert_eq!(splits[1].0, right);
+        assert_eq!(splits[1].1.query, None);
+        assert_eq!(splits[1].1.rects.len(), 3);
+    }
+
     #[test]
     fn responsive_split_debug() {
         let split = ResponsiveSplit {
             breakpoint: Breakpoint::Md,
             rects: smallvec::smallvec![Rect::new(0, 0, 50, 24)],
+            query: None,
         };
         let dbg = format!("{:?}", split);
         assert!(dbg.contains("Md"));
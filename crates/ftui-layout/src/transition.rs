@@ -0,0 +1,589 @@
+#![forbid(unsafe_code)]
+
+//! Animated transitions between successive layouts.
+//!
+//! When a pane splits, a sidebar toggles, or a breakpoint changes, the
+//! solved rectangles jump from one frame to the next. [`LayoutTransition`]
+//! diffs two sets of keyed rectangles and interpolates them over time using
+//! the [`ftui_core::animation`] primitives, so every widget animates layout
+//! changes the same way.
+//!
+//! Keys identify a region across both layouts: indices for [`Flex`] splits,
+//! item indices for [`GridLayout`](crate::grid::GridLayout), and [`PaneId`]s
+//! for [`PaneLayout`](crate::PaneLayout). Regions present on only one side
+//! grow from, or shrink toward, their own center.
+//!
+//! # Driving ticks
+//!
+//! The transition is advanced explicitly with [`LayoutTransition::tick`].
+//! `ftui_runtime::animation_ticks` yields a frame-rate subscription only
+//! while [`is_active`](LayoutTransition::is_active) is true, so the runtime
+//! stops ticking once the layout settles:
+//!
+//! ```ignore
+//! fn subscriptions(&self) -> Vec<Box<dyn Subscription<Msg>>> {
+//!     animation_ticks(SIDEBAR_ANIM, self.sidebar.is_active(), FRAME, || Msg::AnimTick)
+//!         .into_iter()
+//!         .collect()
+//! }
+//!
+//! // In update():
+//! Msg::ToggleSidebar => {
+//!     let next = self.solve(area);
+//!     self.sidebar.retarget(next.iter().copied().enumerate());
+//! }
+//! Msg::AnimTick => self.sidebar.tick(FRAME),
+//! ```
+//!
+//! Transitions follow the shared [`MotionPreference::global`] setting. While
+//! it asks for reduced motion they complete immediately and never request
+//! ticks; turning it on mid-transition finishes on the next tick.
+//!
+//! [`Flex`]: crate::Flex
+//! [`PaneId`]: crate::PaneId
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use ftui_core::animation::{Animation, EasingFn, Fade, MotionPreference, Spring, ease_in_out};
+use ftui_core::geometry::Rect;
+
+/// How a keyed rectangle differs between two layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RectChange {
+    /// Present in both layouts at the same position and size.
+    Unchanged(Rect),
+    /// Present in both layouts with a different position or size.
+    Moved { from: Rect, to: Rect },
+    /// Present only in the new layout.
+    Entered(Rect),
+    /// Present only in the old layout.
+    Exited(Rect),
+}
+
+impl RectChange {
+    /// Rectangle at the start of the transition.
+    ///
+    /// Entering regions start collapsed at the center of their target.
+    #[must_use]
+    pub fn start(&self) -> Rect {
+        match *self {
+            Self::Unchanged(rect) => rect,
+            Self::Moved { from, .. } | Self::Exited(from) => from,
+            Self::Entered(to) => collapsed(to),
+        }
+    }
+
+    /// Rectangle at the end of the transition.
+    ///
+    /// Exiting regions end collapsed at the center of their origin.
+    #[must_use]
+    pub fn end(&self) -> Rect {
+        match *self {
+            Self::Unchanged(rect) => rect,
+            Self::Moved { to, .. } | Self::Entered(to) => to,
+            Self::Exited(from) => collapsed(from),
+        }
+    }
+}
+
+/// Keyed difference between two layouts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutDiff<K: Ord> {
+    changes: BTreeMap<K, RectChange>,
+}
+
+impl<K: Ord> LayoutDiff<K> {
+    /// Diff two sets of keyed rectangles.
+    ///
+    /// Later duplicates of a key replace earlier ones.
+    pub fn new(
+        from: impl IntoIterator<Item = (K, Rect)>,
+        to: impl IntoIterator<Item = (K, Rect)>,
+    ) -> Self {
+        let mut changes: BTreeMap<K, RectChange> = from
+            .into_iter()
+            .map(|(key, rect)| (key, RectChange::Exited(rect)))
+            .collect();
+        for (key, to) in to {
+            let change = match changes.remove(&key) {
+                Some(RectChange::Exited(from)) if from == to => RectChange::Unchanged(to),
+                Some(RectChange::Exited(from)) => RectChange::Moved { from, to },
+                _ => RectChange::Entered(to),
+            };
+            changes.insert(key, change);
+        }
+        Self { changes }
+    }
+
+    /// Change for a single key.
+    #[must_use]
+    pub fn get(&self, key: &K) -> Option<&RectChange> {
+        self.changes.get(key)
+    }
+
+    /// Iterate changes in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &RectChange)> + '_ {
+        self.changes.iter()
+    }
+
+    /// Whether every region is unchanged.
+    #[must_use]
+    pub fn is_static(&self) -> bool {
+        self.changes
+            .values()
+            .all(|change| matches!(change, RectChange::Unchanged(_)))
+    }
+}
+
+/// Timing used to interpolate a transition.
+#[derive(Debug, Clone)]
+pub enum TransitionMotion {
+    /// Fixed duration with an easing curve.
+    Eased {
+        duration: Duration,
+        easing: EasingFn,
+    },
+    /// Spring physics on normalized progress; settles when the spring rests.
+    Spring(Spring),
+}
+
+impl Default for TransitionMotion {
+    fn default() -> Self {
+        Self::Eased {
+            duration: Duration::from_millis(180),
+            easing: ease_in_out,
+        }
+    }
+}
+
+impl TransitionMotion {
+    fn start(&self) -> Progress {
+        match self {
+            Self::Eased { duration, easing } => {
+                Progress::Eased(Fade::new(*duration).easing(*easing))
+            }
+            Self::Spring(spring) => {
+                let mut spring = spring.clone();
+                spring.reset();
+                Progress::Spring(spring)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Progress {
+    Eased(Fade),
+    Spring(Spring),
+    Done,
+}
+
+impl Progress {
+    fn tick(&mut self, dt: Duration) {
+        match self {
+            Self::Eased(fade) => fade.tick(dt),
+            Self::Spring(spring) => spring.tick(dt),
+            Self::Done => {}
+        }
+        let complete = match self {
+            Self::Eased(fade) => fade.is_complete(),
+            Self::Spring(spring) => spring.is_complete(),
+            Self::Done => true,
+        };
+        if complete {
+            *self = Self::Done;
+        }
+    }
+
+    fn value(&self) -> f32 {
+        match self {
+            Self::Eased(fade) => fade.value(),
+            Self::Spring(spring) => spring.value(),
+            Self::Done => 1.0,
+        }
+    }
+}
+
+/// Interpolates keyed rectangles from one layout to the next.
+#[derive(Debug, Clone)]
+pub struct LayoutTransition<K: Ord> {
+    diff: LayoutDiff<K>,
+    motion: TransitionMotion,
+    progress: Progress,
+    preference: MotionPreference,
+}
+
+impl<K: Ord + Clone> LayoutTransition<K> {
+    /// Settled transition showing `rects`.
+    pub fn settled(rects: impl IntoIterator<Item = (K, Rect)>) -> Self {
+        let changes = rects
+            .into_iter()
+            .map(|(key, rect)| (key, RectChange::Unchanged(rect)))
+            .collect();
+        Self {
+            diff: LayoutDiff { changes },
+            motion: TransitionMotion::default(),
+            progress: Progress::Done,
+            preference: MotionPreference::global().clone(),
+        }
+    }
+
+    /// Start animating from `from` to `to`.
+    pub fn new(
+        from: impl IntoIterator<Item = (K, Rect)>,
+        to: impl IntoIterator<Item = (K, Rect)>,
+    ) -> Self {
+        let mut transition = Self::settled(std::iter::empty());
+        transition.start(LayoutDiff::new(from, to));
+        transition
+    }
+
+    /// Set the timing for this and later transitions (builder pattern).
+    #[must_use]
+    pub fn motion(mut self, motion: TransitionMotion) -> Self {
+        self.motion = motion;
+        if !matches!(self.progress, Progress::Done) {
+            self.progress = self.motion.start();
+        }
+        self
+    }
+
+    /// Follow `preference` instead of [`MotionPreference::global`] (builder
+    /// pattern).
+    ///
+    /// While it asks for reduced motion, transitions jump straight to their
+    /// target layout and [`is_active`](Self::is_active) stays false.
+    #[must_use]
+    pub fn motion_preference(mut self, preference: MotionPreference) -> Self {
+        self.preference = preference;
+        if self.is_reduced_motion() {
+            self.finish();
+        }
+        self
+    }
+
+    /// Whether the motion preference currently asks for reduced motion.
+    #[must_use]
+    pub fn is_reduced_motion(&self) -> bool {
+        self.preference.is_reduced()
+    }
+
+    /// Animate toward a new layout, starting from the current rectangles.
+    ///
+    /// Regions already exiting keep shrinking from where they are; a key
+    /// that reappears grows back from its current rectangle.
+    pub fn retarget(&mut self, to: impl IntoIterator<Item = (K, Rect)>) {
+        let current = self.rects();
+        self.start(LayoutDiff::new(current, to));
+    }
+
+    fn start(&mut self, diff: LayoutDiff<K>) {
+        let animate = !self.is_reduced_motion() && !diff.is_static();
+        self.diff = diff;
+        self.progress = self.motion.start();
+        if !animate {
+            self.finish();
+        }
+    }
+
+    /// Jump to the end of the transition.
+    pub fn finish(&mut self) {
+        self.progress = Progress::Done;
+        self.diff.changes.retain(|_, change| {
+            if matches!(change, RectChange::Exited(_)) {
+                return false;
+            }
+            *change = RectChange::Unchanged(change.end());
+            true
+        });
+    }
+
+    /// Advance the transition by `dt`.
+    ///
+    /// Finishes at once if reduced motion was turned on since it started.
+    pub fn tick(&mut self, dt: Duration) {
+        if self.is_active() {
+            if self.is_reduced_motion() {
+                self.finish();
+                return;
+            }
+            self.progress.tick(dt);
+            if matches!(self.progress, Progress::Done) {
+                self.finish();
+            }
+        }
+    }
+
+    /// Whether the transition still needs ticks.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        !matches!(self.progress, Progress::Done)
+    }
+
+    /// Eased progress in `[0.0, 1.0]`.
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        self.progress.value()
+    }
+
+    /// Differences the current transition animates.
+    #[must_use]
+    pub const fn diff(&self) -> &LayoutDiff<K> {
+        &self.diff
+    }
+
+    /// Interpolated rectangle for `key`, if it is visible.
+    #[must_use]
+    pub fn rect(&self, key: &K) -> Option<Rect> {
+        let t = self.progress();
+        self.diff
+            .get(key)
+            .map(|change| lerp_rect(change.start(), change.end(), t))
+    }
+
+    /// Whether `key` is shrinking out of the layout.
+    #[must_use]
+    pub fn is_exiting(&self, key: &K) -> bool {
+        matches!(self.diff.get(key), Some(RectChange::Exited(_)))
+    }
+
+    /// Interpolated rectangles in key order, including exiting regions.
+    #[must_use]
+    pub fn rects(&self) -> Vec<(K, Rect)> {
+        let t = self.progress();
+        self.diff
+            .iter()
+            .map(|(key, change)| (key.clone(), lerp_rect(change.start(), change.end(), t)))
+            .collect()
+    }
+}
+
+/// Zero-size rectangle at the center of `rect`.
+fn collapsed(rect: Rect) -> Rect {
+    Rect::new(rect.x + rect.width / 2, rect.y + rect.height / 2, 0, 0)
+}
+
+/// Interpolate the edges of two rectangles.
+fn lerp_rect(from: Rect, to: Rect, t: f32) -> Rect {
+    if t <= 0.0 {
+        return from;
+    }
+    if t >= 1.0 {
+        return to;
+    }
+    let t = f64::from(t);
+    let lerp = |a: u16, b: u16| {
+        let value = f64::from(a) + (f64::from(b) - f64::from(a)) * t;
+        value.round().clamp(0.0, f64::from(u16::MAX)) as u16
+    };
+    let left = lerp(from.x, to.x);
+    let top = lerp(from.y, to.y);
+    let right = lerp(from.right(), to.right()).max(left);
+    let bottom = lerp(from.bottom(), to.bottom()).max(top);
+    Rect::new(left, top, right - left, bottom - top)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: Duration = Duration::from_millis(50);
+
+    fn linear_motion() -> TransitionMotion {
+        TransitionMotion::Eased {
+            duration: Duration::from_millis(100),
+            easing: ftui_core::animation::linear,
+        }
+    }
+
+    #[test]
+    fn diff_classifies_changes() {
+        let diff = LayoutDiff::new(
+            [
+                (1, Rect::new(0, 0, 10, 10)),
+                (2, Rect::new(10, 0, 10, 10)),
+                (3, Rect::new(20, 0, 10, 10)),
+            ],
+            [
+                (1, Rect::new(0, 0, 10, 10)),
+                (2, Rect::new(10, 0, 20, 10)),
+                (4, Rect::new(30, 0, 10, 10)),
+            ],
+        );
+        assert_eq!(
+            diff.get(&1),
+            Some(&RectChange::Unchanged(Rect::new(0, 0, 10, 10)))
+        );
+        assert_eq!(
+            diff.get(&2),
+            Some(&RectChange::Moved {
+                from: Rect::new(10, 0, 10, 10),
+                to: Rect::new(10, 0, 20, 10),
+            })
+        );
+        assert_eq!(
+            diff.get(&3),
+            Some(&RectChange::Exited(Rect::new(20, 0, 10, 10)))
+        );
+        assert_eq!(
+            diff.get(&4),
+            Some(&RectChange::Entered(Rect::new(30, 0, 10, 10)))
+        );
+        assert!(!diff.is_static());
+    }
+
+    #[test]
+    fn eased_transition_interpolates_edges() {
+        let mut transition = LayoutTransition::new(
+            [(0, Rect::new(0, 0, 20, 10))],
+            [(0, Rect::new(0, 0, 40, 10))],
+        )
+        .motion(linear_motion());
+        assert!(transition.is_active());
+        assert_eq!(transition.rect(&0), Some(Rect::new(0, 0, 20, 10)));
+
+        transition.tick(STEP);
+        assert_eq!(transition.rect(&0), Some(Rect::new(0, 0, 30, 10)));
+
+        transition.tick(STEP);
+        assert!(!transition.is_active());
+        assert_eq!(transition.rect(&0), Some(Rect::new(0, 0, 40, 10)));
+    }
+
+    #[test]
+    fn entering_grows_and_exiting_is_dropped() {
+        let mut transition = LayoutTransition::new(
+            [
+                ("main", Rect::new(0, 0, 40, 10)),
+                ("old", Rect::new(40, 0, 20, 10)),
+            ],
+            [
+                ("main", Rect::new(0, 0, 60, 10)),
+                ("new", Rect::new(0, 10, 60, 4)),
+            ],
+        )
+        .motion(linear_motion());
+
+        assert_eq!(transition.rect(&"new"), Some(Rect::new(30, 12, 0, 0)));
+        assert!(transition.is_exiting(&"old"));
+        transition.tick(STEP);
+        assert_eq!(transition.rect(&"new"), Some(Rect::new(15, 11, 30, 2)));
+        assert_eq!(transition.rect(&"old"), Some(Rect::new(45, 3, 10, 5)));
+
+        transition.tick(STEP);
+        assert_eq!(transition.rect(&"old"), None);
+        assert_eq!(
+            transition.rects(),
+            vec![
+                ("main", Rect::new(0, 0, 60, 10)),
+                ("new", Rect::new(0, 10, 60, 4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reduced_motion_jumps_to_target() {
+        let reduced = MotionPreference::new();
+        reduced.set_reduced(true);
+        let transition = LayoutTransition::new(
+            [(0, Rect::new(0, 0, 20, 10))],
+            [(0, Rect::new(0, 0, 40, 10))],
+        )
+        .motion_preference(reduced.clone());
+        assert!(!transition.is_active());
+        assert_eq!(transition.rect(&0), Some(Rect::new(0, 0, 40, 10)));
+
+        let mut transition =
+            LayoutTransition::settled([(0, Rect::new(0, 0, 40, 10))]).motion_preference(reduced);
+        transition.retarget([(1, Rect::new(0, 0, 40, 10))]);
+        assert!(!transition.is_active());
+        assert_eq!(transition.rects(), vec![(1, Rect::new(0, 0, 40, 10))]);
+    }
+
+    #[test]
+    fn shared_preference_finishes_running_transition() {
+        let preference = MotionPreference::new();
+        let mut transition = LayoutTransition::settled([(0, Rect::new(0, 0, 20, 10))])
+            .motion_preference(preference.clone());
+        transition.retarget([(0, Rect::new(0, 0, 40, 10))]);
+        assert!(transition.is_active());
+
+        preference.set_reduced(true);
+        transition.tick(Duration::from_millis(16));
+        assert!(!transition.is_active());
+        assert_eq!(transition.rect(&0), Some(Rect::new(0, 0, 40, 10)));
+    }
+
+    #[test]
+    fn static_layouts_do_not_request_ticks() {
+        let mut transition = LayoutTransition::settled([(0, Rect::new(0, 0, 20, 10))]);
+        transition.retarget([(0, Rect::new(0, 0, 20, 10))]);
+        assert!(!transition.is_active());
+    }
+
+    #[test]
+    fn retarget_starts_from_current_rects() {
+        let mut transition = LayoutTransition::new(
+            [(0, Rect::new(0, 0, 20, 10))],
+            [(0, Rect::new(0, 0, 40, 10))],
+        )
+        .motion(linear_motion());
+        transition.tick(STEP);
+        transition.retarget([(0, Rect::new(0, 0, 20, 10))]);
+
+        assert_eq!(
+            transition.diff().get(&0),
+            Some(&RectChange::Moved {
+                from: Rect::new(0, 0, 30, 10),
+                to: Rect::new(0, 0, 20, 10),
+            })
+        );
+        assert!(transition.is_active());
+    }
+
+    #[test]
+    fn spring_transition_settles() {
+        let mut transition = LayoutTransition::new(
+            [(0, Rect::new(0, 0, 20, 10))],
+            [(0, Rect::new(0, 0, 40, 10))],
+        )
+        .motion(TransitionMotion::Spring(Spring::normalized()));
+        let mut ticks = 0;
+        while transition.is_active() {
+            transition.tick(Duration::from_millis(16));
+            ticks += 1;
+            assert!(ticks < 1_000, "spring never settled");
+        }
+        assert_eq!(transition.rect(&0), Some(Rect::new(0, 0, 40, 10)));
+    }
+
+    #[test]
+    fn pane_layouts_transition_by_pane_id() {
+        use crate::pane::{PaneId, PaneLeaf, PaneSplitRatio, PaneTree};
+
+        let mut tree = PaneTree::singleton("editor");
+        let area = Rect::new(0, 0, 80, 24);
+        let before = tree.solve_layout(area).unwrap();
+        let root = tree.root();
+        let outcome = tree
+            .apply_operation(
+                1,
+                crate::pane::PaneOperation::SplitLeaf {
+                    target: root,
+                    axis: crate::pane::SplitAxis::Horizontal,
+                    ratio: PaneSplitRatio::new(1, 1).unwrap(),
+                    placement: crate::pane::PanePlacement::ExistingFirst,
+                    new_leaf: PaneLeaf::new("terminal"),
+                },
+            )
+            .unwrap();
+        assert!(!outcome.touched_nodes.is_empty());
+        let after = tree.solve_layout(area).unwrap();
+
+        let transition: LayoutTransition<PaneId> =
+            LayoutTransition::new(before.iter(), after.iter()).motion(linear_motion());
+        assert!(transition.is_active());
+        assert_eq!(transition.rect(&root), Some(area));
+    }
+}
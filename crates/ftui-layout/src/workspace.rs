@@ -1,8 +1,16 @@
-//! Persisted workspace schema v1 with versioning and migration scaffolding.
+//! Persisted workspace schema with versioning and migration.
 //!
 //! A [`WorkspaceSnapshot`] wraps the pane tree snapshot with workspace-level
 //! metadata, active pane tracking, and forward-compatible extension bags.
 //!
+//! # Schema History
+//!
+//! - **v1**: split tree of single-surface leaves.
+//! - **v2**: leaves may hold tab stacks, one docked node may be zoomed, and
+//!   subtrees may float above the split tree. v1 readers would silently drop
+//!   floating panes, so the version was bumped; v1 snapshots migrate
+//!   unchanged.
+//!
 //! # Schema Versioning Policy
 //!
 //! - **Additive fields** may be carried in `extensions` maps without a version bump.
@@ -23,6 +31,8 @@
 //!     root: PaneId::default(),
 //!     next_id: PaneId::new(2).unwrap(),
 //!     nodes: vec![PaneNodeRecord::leaf(PaneId::default(), None, PaneLeaf::new("main"))],
+//!     zoomed: None,
+//!     floating: Vec::new(),
 //!     extensions: BTreeMap::new(),
 //! };
 //!
@@ -45,7 +55,10 @@ use crate::pane::{
 };
 
 /// Current workspace schema version.
-pub const WORKSPACE_SCHEMA_VERSION: u16 = 1;
+pub const WORKSPACE_SCHEMA_VERSION: u16 = 2;
+
+/// First workspace schema version, before tabs, zoom and floating panes.
+const WORKSPACE_SCHEMA_V1: u16 = 1;
 
 // =========================================================================
 // Core schema types
@@ -80,7 +93,7 @@ fn default_workspace_version() -> u16 {
 }
 
 impl WorkspaceSnapshot {
-    /// Create a new v1 workspace snapshot.
+    /// Create a new workspace snapshot at the current schema version.
     #[must_use]
     pub fn new(pane_tree: PaneTreeSnapshot, metadata: WorkspaceMetadata) -> Self {
         Self {
@@ -457,8 +470,7 @@ impl fmt::Display for WorkspaceSnapshotJsonError {
 
 /// Attempt to migrate a workspace snapshot to the current schema version.
 ///
-/// For v1 (current), this is a no-op identity migration. Future versions
-/// will chain migrations through each intermediate version.
+/// Migrations chain through each intermediate version.
 pub fn migrate_workspace(
     snapshot: WorkspaceSnapshot,
 ) -> Result<MigrationResult, WorkspaceMigrationError> {
@@ -472,6 +484,15 @@ pub fn migrate_workspace(
                 snapshot,
             })
         }
+        WORKSPACE_SCHEMA_V1 => {
+            let snapshot = migrate_v1_to_v2(snapshot);
+            Ok(MigrationResult {
+                from_version: WORKSPACE_SCHEMA_V1,
+                to_version: WORKSPACE_SCHEMA_VERSION,
+                warnings: Vec::new(),
+                snapshot,
+            })
+        }
         v if v > WORKSPACE_SCHEMA_VERSION => {
             Err(WorkspaceMigrationError::UnsupportedVersion { version: v })
         }
@@ -482,6 +503,13 @@ pub fn migrate_workspace(
     }
 }
 
+/// v1 → v2: tab stacks, zoom and floating panes are additive with serde
+/// defaults, so a decoded v1 snapshot is already a valid v2 snapshot.
+fn migrate_v1_to_v2(mut snapshot: WorkspaceSnapshot) -> WorkspaceSnapshot {
+    snapshot.schema_version = WORKSPACE_SCHEMA_VERSION;
+    snapshot
+}
+
 /// Check whether a snapshot requires migration.
 #[must_use]
 pub fn needs_migration(snapshot: &WorkspaceSnapshot) -> bool {
@@ -545,8 +573,8 @@ pub fn to_canonical_workspace_snapshot_json(
 mod tests {
     use super::*;
     use crate::pane::{
-        PaneInteractionTimelineEntry, PaneLeaf, PaneNodeKind, PaneNodeRecord, PaneOperation,
-        PaneSplit, PaneSplitRatio, PaneTree, SplitAxis,
+        PaneFloatingRect, PaneInteractionTimelineEntry, PaneLeaf, PaneNodeKind, PaneNodeRecord,
+        PaneOperation, PaneSplit, PaneSplitRatio, PaneTree, SplitAxis,
     };
 
     fn minimal_tree() -> PaneTreeSnapshot {
@@ -559,6 +587,8 @@ mod tests {
                 None,
                 PaneLeaf::new("main"),
             )],
+            zoomed: None,
+            floating: Vec::new(),
             extensions: BTreeMap::new(),
         }
     }
@@ -585,6 +615,8 @@ mod tests {
                 PaneNodeRecord::leaf(left_id, Some(root_id), PaneLeaf::new("left")),
                 PaneNodeRecord::leaf(right_id, Some(root_id), PaneLeaf::new("right")),
             ],
+            zoomed: None,
+            floating: Vec::new(),
             extensions: BTreeMap::new(),
         }
     }
@@ -596,10 +628,10 @@ mod tests {
     // ---- Construction ----
 
     #[test]
-    fn new_snapshot_has_v1() {
+    fn new_snapshot_has_current_version() {
         let snap = minimal_snapshot();
         assert_eq!(snap.schema_version, WORKSPACE_SCHEMA_VERSION);
-        assert_eq!(snap.schema_version, 1);
+        assert_eq!(snap.schema_version, 2);
     }
 
     #[test]
@@ -657,7 +689,7 @@ mod tests {
             err,
             WorkspaceValidationError::UnsupportedVersion {
                 found: 99,
-                expected: 1
+                expected: 2
             }
         ));
     }
@@ -720,6 +752,7 @@ mod tests {
                 sequence: 1,
                 operation_id: 10,
                 operation: PaneOperation::NormalizeRatios,
+                grouped_operations: Vec::new(),
                 before_hash: 1,
                 after_hash: 2,
             });
@@ -740,6 +773,7 @@ mod tests {
                 sequence: 1,
                 operation_id: 10,
                 operation: PaneOperation::NormalizeRatios,
+                grouped_operations: Vec::new(),
                 before_hash: 1,
                 after_hash: 2,
             });
@@ -976,15 +1010,49 @@ mod tests {
     // ---- Migration ----
 
     #[test]
-    fn migrate_v1_is_noop() {
+    fn migrate_current_is_noop() {
         let snap = minimal_snapshot();
         let result = migrate_workspace(snap.clone()).unwrap();
-        assert_eq!(result.from_version, 1);
-        assert_eq!(result.to_version, 1);
+        assert_eq!(result.from_version, 2);
+        assert_eq!(result.to_version, 2);
         assert_eq!(result.snapshot, snap);
         assert!(result.warnings.is_empty());
     }
 
+    #[test]
+    fn migrate_v1_to_v2_stamps_version() {
+        let mut snap = WorkspaceSnapshot::new(split_tree(), WorkspaceMetadata::new("legacy"));
+        snap.schema_version = 1;
+        let result = migrate_workspace(snap.clone()).unwrap();
+        assert_eq!(result.from_version, 1);
+        assert_eq!(result.to_version, 2);
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.snapshot.schema_version, WORKSPACE_SCHEMA_VERSION);
+        assert_eq!(result.snapshot.pane_tree, snap.pane_tree);
+        assert!(result.snapshot.validate().is_ok());
+    }
+
+    #[test]
+    fn canonical_json_v1_document_migrates() {
+        let json = r#"{
+            "schema_version": 1,
+            "pane_tree": {
+                "schema_version": 1,
+                "root": 1,
+                "next_id": 2,
+                "nodes": [{"id": 1, "kind": "leaf", "surface_key": "main"}]
+            },
+            "metadata": {"name": "v1"}
+        }"#;
+
+        let result = decode_workspace_snapshot_json(json).unwrap();
+
+        assert_eq!(result.from_version, 1);
+        assert_eq!(result.to_version, WORKSPACE_SCHEMA_VERSION);
+        assert!(result.snapshot.pane_tree.zoomed.is_none());
+        assert!(result.snapshot.pane_tree.floating.is_empty());
+    }
+
     #[test]
     fn migrate_future_version_fails() {
         let mut snap = minimal_snapshot();
@@ -1003,7 +1071,7 @@ mod tests {
         let err = migrate_workspace(snap).unwrap_err();
         assert!(matches!(
             err,
-            WorkspaceMigrationError::NoMigrationPath { from: 0, to: 1 }
+            WorkspaceMigrationError::NoMigrationPath { from: 0, to: 2 }
         ));
     }
 
@@ -1104,11 +1172,56 @@ mod tests {
         assert!(matches!(
             err,
             WorkspaceSnapshotJsonError::MigrationFailed {
-                source: WorkspaceMigrationError::NoMigrationPath { from: 0, to: 1 }
+                source: WorkspaceMigrationError::NoMigrationPath { from: 0, to: 2 }
             }
         ));
     }
 
+    #[test]
+    fn canonical_json_roundtrips_tabs_zoom_and_floating() {
+        let left = PaneId::new(2).unwrap();
+        let right = PaneId::new(3).unwrap();
+        let mut tree = PaneTree::from_snapshot(split_tree()).unwrap();
+        tree.apply_operation(
+            1,
+            PaneOperation::AddTab {
+                target: left,
+                surface_key: "left-2".to_string(),
+            },
+        )
+        .unwrap();
+        tree.apply_operation(
+            2,
+            PaneOperation::FloatNode {
+                target: right,
+                rect: PaneFloatingRect::new(4, 2, 30, 10),
+            },
+        )
+        .unwrap();
+        tree.apply_operation(3, PaneOperation::SetZoom { target: Some(left) })
+            .unwrap();
+        let snap = WorkspaceSnapshot::new(tree.to_snapshot(), WorkspaceMetadata::new("rich"))
+            .with_active_pane(right);
+
+        let json = to_canonical_workspace_snapshot_json(&snap).unwrap();
+        let decoded = decode_workspace_snapshot_json(&json).unwrap();
+
+        assert_eq!(decoded.snapshot.pane_tree.zoomed, Some(left));
+        assert_eq!(decoded.snapshot.pane_tree.floating.len(), 1);
+        assert_eq!(decoded.snapshot.pane_tree.floating[0].id, right);
+        let restored = PaneTree::from_snapshot(decoded.snapshot.pane_tree.clone()).unwrap();
+        assert_eq!(restored.state_hash(), tree.state_hash());
+        let PaneNodeKind::Leaf(leaf) = &restored.node(left).unwrap().kind else {
+            unreachable!("left pane is a leaf");
+        };
+        assert_eq!(leaf.tab_count(), 2);
+        assert_eq!(leaf.surface_key, "left-2");
+        assert_eq!(
+            to_canonical_workspace_snapshot_json(&decoded.snapshot).unwrap(),
+            json
+        );
+    }
+
     #[test]
     fn canonical_json_parse_error_uses_import_context() {
         let err = decode_workspace_snapshot_json("{not json").unwrap_err();
@@ -1141,11 +1254,11 @@ mod tests {
     fn validation_error_display() {
         let err = WorkspaceValidationError::UnsupportedVersion {
             found: 99,
-            expected: 1,
+            expected: 2,
         };
         let msg = format!("{err}");
         assert!(msg.contains("99"));
-        assert!(msg.contains("1"));
+        assert!(msg.contains("2"));
     }
 
     #[test]
@@ -28,6 +28,8 @@ fn split_tree_snapshot() -> PaneTreeSnapshot {
             PaneNodeRecord::leaf(left_id, Some(root_id), PaneLeaf::new("left")),
             PaneNodeRecord::leaf(right_id, Some(root_id), PaneLeaf::new("right")),
         ],
+        zoomed: None,
+        floating: Vec::new(),
         extensions: BTreeMap::new(),
     }
 }
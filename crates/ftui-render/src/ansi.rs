@@ -22,11 +22,14 @@
 //! | CSI | `ESC [ top ; bottom r` | DECSTBM (Set Scroll Region) |
 //! | CSI | `ESC [ ? 2026 h/l` | Synchronized Output (DEC) |
 //! | OSC | `ESC ] 8 ; ; url ST` | Hyperlink (OSC 8) |
+//! | OSC | `ESC ] 66 ; s=n ; text ST` | Scaled text (Kitty text sizing) |
 //! | DEC | `ESC 7` / `ESC 8` | Cursor save/restore (DECSC/DECRC) |
+//! | DEC | `ESC # 3/4/5/6` | Line size (DECDHL/DECSWL/DECDWL) |
 
 use std::io::{self, Write};
 
 use crate::cell::{PackedRgba, StyleFlags};
+use crate::line_size::LineSize;
 
 const MAX_OSC8_FIELD_BYTES: usize = 4096;
 
@@ -502,6 +505,12 @@ pub fn cursor_restore<W: Write>(w: &mut W) -> io::Result<()> {
     w.write_all(CURSOR_RESTORE)
 }
 
+/// Set the line size of the cursor row: `ESC # 3/4/5/6` (DECDHL/DECSWL/DECDWL)
+#[inline]
+pub fn line_size<W: Write>(w: &mut W, size: LineSize) -> io::Result<()> {
+    w.write_all(size.escape_sequence())
+}
+
 /// Hide cursor: `CSI ? 25 l`
 pub const CURSOR_HIDE: &[u8] = b"\x1b[?25l";
 
@@ -642,6 +651,19 @@ pub fn hyperlink_start_with_id<W: Write>(w: &mut W, id: &str, url: &str) -> io::
     write!(w, "\x1b]8;id={id};{url}\x07")
 }
 
+// =============================================================================
+// OSC 66 Text Sizing
+// =============================================================================
+
+/// Draw `text` at an integer scale (Kitty text sizing protocol).
+///
+/// Format: `OSC 66 ; s=scale ; text ST`
+/// The text must be free of control characters (see
+/// [`ScaledText::new`](crate::text_sizing::ScaledText::new)).
+pub fn text_size<W: Write>(w: &mut W, scale: u8, text: &str) -> io::Result<()> {
+    write!(w, "\x1b]66;s={scale};{text}\x1b\\")
+}
+
 // =============================================================================
 // Mode Control
 // =============================================================================
@@ -947,6 +969,21 @@ mod tests {
         assert_eq!(to_bytes(cursor_restore), b"\x1b8");
     }
 
+    #[test]
+    fn line_size_sequences() {
+        let mut buf = Vec::new();
+        line_size(&mut buf, LineSize::DoubleWidth).unwrap();
+        line_size(&mut buf, LineSize::Normal).unwrap();
+        assert_eq!(buf, b"\x1b#6\x1b#5");
+    }
+
+    #[test]
+    fn text_size_sequence() {
+        let mut buf = Vec::new();
+        text_size(&mut buf, 2, "Hi").unwrap();
+        assert_eq!(buf, b"\x1b]66;s=2;Hi\x1b\\");
+    }
+
     #[test]
     fn cursor_visibility() {
         assert_eq!(to_bytes(cursor_hide), b"\x1b[?25l");
@@ -55,7 +55,9 @@
 use smallvec::SmallVec;
 
 use crate::budget::DegradationLevel;
-use crate::cell::{Cell, GraphemeId};
+use crate::cell::{Cell, CellContent, GraphemeId};
+use crate::line_size::LineSize;
+use crate::text_sizing::ScaledText;
 use ftui_core::geometry::Rect;
 
 /// Maximum number of dirty spans per row before falling back to full-row scan.
@@ -210,6 +212,10 @@ pub struct DirtySpanStats {
 pub struct Buffer {
     width: u16,
     height: u16,
+    /// Coordinates of the top-left cell; `(0, 0)` unless built with
+    /// [`Buffer::new_at`].
+    origin_x: u16,
+    origin_y: u16,
     cells: Vec<Cell>,
     scissor_stack: Vec<Rect>,
     opacity_stack: Vec<f32>,
@@ -237,6 +243,12 @@ pub struct Buffer {
     dirty_cells: usize,
     /// Whether the whole buffer is marked dirty (bitmap may be stale).
     dirty_all: bool,
+    /// Per-row DEC line size attributes.
+    ///
+    /// Invariant: `line_sizes.len() == height`
+    line_sizes: Vec<LineSize>,
+    /// Scaled text runs (OSC 66), sorted by anchor in row-major order.
+    scaled_texts: Vec<ScaledText>,
 }
 
 impl Buffer {
@@ -264,6 +276,8 @@ impl Buffer {
         Self {
             width,
             height,
+            origin_x: 0,
+            origin_y: 0,
             cells,
             scissor_stack: vec![Rect::from_size(width, height)],
             opacity_stack: vec![1.0],
@@ -278,9 +292,25 @@ impl Buffer {
             dirty_bits,
             dirty_cells,
             dirty_all,
+            line_sizes: vec![LineSize::Normal; height as usize],
+            scaled_texts: Vec::new(),
         }
     }
 
+    /// Create a buffer covering `area` instead of starting at the origin.
+    ///
+    /// Cells are addressed in the same coordinates as `area`, so widgets can
+    /// render into it at their usual positions while only the cells inside
+    /// `area` are stored; writes outside it are dropped. Used to render an
+    /// off-screen slice of a larger virtual surface.
+    pub fn new_at(area: Rect) -> Self {
+        let mut buffer = Self::new(area.width, area.height);
+        buffer.origin_x = area.x;
+        buffer.origin_y = area.y;
+        buffer.scissor_stack = vec![buffer.bounds()];
+        buffer
+    }
+
     /// Buffer width in cells.
     #[inline]
     pub const fn width(&self) -> u16 {
@@ -308,7 +338,7 @@ impl Buffer {
     /// Bounding rect of the entire buffer.
     #[inline]
     pub const fn bounds(&self) -> Rect {
-        Rect::from_size(self.width, self.height)
+        Rect::new(self.origin_x, self.origin_y, self.width, self.height)
     }
 
     /// Return the height of content (last non-empty row + 1).
@@ -340,18 +370,22 @@ impl Buffer {
     /// the dirty-soundness invariant.
     #[inline]
     fn mark_dirty_row(&mut self, y: u16) {
-        if let Some(slot) = self.dirty_rows.get_mut(y as usize) {
+        let Some(row) = self.row(y) else {
+            return;
+        };
+        if let Some(slot) = self.dirty_rows.get_mut(row) {
             *slot = true;
         }
     }
 
-    /// Mark a range of cells in a row as dirty in the bitmap (end exclusive).
+    /// Mark a range of cells in a storage row as dirty in the bitmap (end
+    /// exclusive, columns relative to the buffer's left edge).
     #[inline]
-    fn mark_dirty_bits_range(&mut self, y: u16, start: u16, end: u16) {
+    fn mark_dirty_bits_range(&mut self, row: usize, start: u16, end: u16) {
         if self.dirty_all {
             return;
         }
-        if y >= self.height {
+        if row >= self.height as usize {
             return;
         }
 
@@ -364,7 +398,7 @@ impl Buffer {
             return;
         }
 
-        let row_start = y as usize * width as usize;
+        let row_start = row * width as usize;
         let slice = &mut self.dirty_bits[row_start + start as usize..row_start + end as usize];
         let newly_dirty = slice.iter().filter(|&&b| b == 0).count();
         slice.fill(1);
@@ -374,7 +408,9 @@ impl Buffer {
     /// Mark an entire row as dirty in the bitmap.
     #[inline]
     fn mark_dirty_bits_row(&mut self, y: u16) {
-        self.mark_dirty_bits_range(y, 0, self.width);
+        if let Some(row) = self.row(y) {
+            self.mark_dirty_bits_range(row, 0, self.width);
+        }
     }
 
     /// Mark a row as fully dirty (full scan).
@@ -382,7 +418,7 @@ impl Buffer {
     fn mark_dirty_row_full(&mut self, y: u16) {
         self.mark_dirty_row(y);
         if self.dirty_span_config.enabled
-            && let Some(row) = self.dirty_spans.get_mut(y as usize)
+            && let Some(row) = self.row(y).and_then(|row| self.dirty_spans.get_mut(row))
         {
             row.set_full();
         }
@@ -394,7 +430,9 @@ impl Buffer {
     pub(crate) fn mark_dirty_span(&mut self, y: u16, x0: u16, x1: u16) {
         self.mark_dirty_row(y);
         let width = self.width;
-        let (start, mut end) = if x0 <= x1 { (x0, x1) } else { (x1, x0) };
+        let (start, end) = if x0 <= x1 { (x0, x1) } else { (x1, x0) };
+        let start = start.saturating_sub(self.origin_x);
+        let mut end = end.saturating_sub(self.origin_x);
         if start >= width {
             return;
         }
@@ -405,7 +443,10 @@ impl Buffer {
             return;
         }
 
-        self.mark_dirty_bits_range(y, start, end);
+        let Some(row) = self.row(y) else {
+            return;
+        };
+        self.mark_dirty_bits_range(row, start, end);
 
         if !self.dirty_span_config.enabled {
             return;
@@ -421,7 +462,7 @@ impl Buffer {
             return;
         }
 
-        let Some(row) = self.dirty_spans.get_mut(y as usize) else {
+        let Some(row) = self.dirty_spans.get_mut(row) else {
             return;
         };
 
@@ -490,7 +531,10 @@ impl Buffer {
     /// Check if a specific row is dirty.
     #[inline]
     pub fn is_row_dirty(&self, y: u16) -> bool {
-        self.dirty_rows.get(y as usize).copied().unwrap_or(false)
+        self.row(y)
+            .and_then(|row| self.dirty_rows.get(row))
+            .copied()
+            .unwrap_or(false)
     }
 
     /// Get the dirty row flags as a slice.
@@ -612,6 +656,8 @@ impl Buffer {
     /// Returns `None` if coordinates are out of bounds.
     #[inline]
     fn index(&self, x: u16, y: u16) -> Option<usize> {
+        let x = x.checked_sub(self.origin_x)?;
+        let y = y.checked_sub(self.origin_y)?;
         if x < self.width && y < self.height {
             Some(y as usize * self.width as usize + x as usize)
         } else {
@@ -619,6 +665,13 @@ impl Buffer {
         }
     }
 
+    /// Storage row of `y`, or `None` if it is out of bounds.
+    #[inline]
+    fn row(&self, y: u16) -> Option<usize> {
+        let y = y.checked_sub(self.origin_y)?;
+        (y < self.height).then_some(y as usize)
+    }
+
     /// Convert (x, y) coordinates to a linear index without bounds checking.
     ///
     /// # Safety
@@ -626,6 +679,7 @@ impl Buffer {
     /// Caller must ensure x < width and y < height.
     #[inline]
     pub(crate) fn index_unchecked(&self, x: u16, y: u16) -> usize {
+        let (x, y) = (x - self.origin_x, y - self.origin_y);
         debug_assert!(x < self.width && y < self.height);
         y as usize * self.width as usize + x as usize
     }
@@ -657,6 +711,7 @@ impl Buffer {
     #[must_use]
     pub fn get_mut(&mut self, x: u16, y: u16) -> Option<&mut Cell> {
         let idx = self.index(x, y)?;
+        self.drop_scaled_text(Rect::new(x, y, 1, 1));
         self.mark_dirty_span(y, x, x.saturating_add(1));
         Some(&mut self.cells[idx])
     }
@@ -766,10 +821,6 @@ impl Buffer {
     /// scans forward and clears them to prevent visual artifacts.
     #[inline]
     fn cleanup_orphaned_tails(&mut self, start_x: u16, y: u16) {
-        if start_x >= self.width {
-            return;
-        }
-
         // Optimization: check first cell without loop overhead
         let Some(idx) = self.index(start_x, y) else {
             return;
@@ -781,7 +832,7 @@ impl Buffer {
         // Found an orphan, start scanning
         let mut x = start_x;
         let mut max_x = x;
-        let row_end_idx = (y as usize * self.width as usize) + self.width as usize;
+        let row_end_idx = idx - idx % self.width as usize + self.width as usize;
         let mut curr_idx = idx;
 
         while curr_idx < row_end_idx && self.cells[curr_idx].is_continuation() {
@@ -839,6 +890,7 @@ impl Buffer {
         if existing.content.width() > 1 || existing.is_continuation() {
             return self.set(x, y, cell);
         }
+        self.drop_scaled_text(Rect::new(x, y, 1, 1));
 
         // All fast-path conditions met: direct write.
         //
@@ -881,6 +933,7 @@ impl Buffer {
             if !self.current_scissor().contains(x, y) {
                 return;
             }
+            self.drop_scaled_text(Rect::new(x, y, 1, 1));
 
             // Cleanup overlaps and track any cleared span.
             let mut span_start = x;
@@ -920,7 +973,7 @@ impl Buffer {
                 return;
             };
             // Check bounds
-            if cx >= self.width || y >= self.height {
+            if self.index(cx, y).is_none() {
                 return;
             }
             // Check scissor
@@ -930,6 +983,7 @@ impl Buffer {
         }
 
         // If we get here, it's safe to write everything.
+        self.drop_scaled_text(Rect::new(x, y, width as u16, 1));
 
         // Cleanup overlaps for all cells and track any cleared span.
         let mut span_start = x;
@@ -990,6 +1044,7 @@ impl Buffer {
     #[inline]
     pub fn set_raw(&mut self, x: u16, y: u16, cell: Cell) {
         if let Some(idx) = self.index(x, y) {
+            self.drop_scaled_text(Rect::new(x, y, 1, 1));
             let mut span = DirtySpan::new(x, x.saturating_add(1));
             let raw_wide_head = cell.content.width() > 1 && !cell.is_continuation();
 
@@ -1013,6 +1068,7 @@ impl Buffer {
         if clipped.is_empty() {
             return;
         }
+        self.drop_scaled_text(clipped);
 
         // Fast path: full-row fill with an opaque, single-width cell and no opacity.
         // Safe because every cell in the row is overwritten, and no blending is required.
@@ -1021,12 +1077,12 @@ impl Buffer {
             && !cell.is_continuation()
             && self.current_opacity() >= 1.0
             && cell.bg.a() == 255
-            && clipped.x == 0
+            && clipped.x == self.origin_x
             && clipped.width == self.width
         {
             let row_width = self.width as usize;
             for y in clipped.y..clipped.bottom() {
-                let row_start = y as usize * row_width;
+                let row_start = (y - self.origin_y) as usize * row_width;
                 let row_end = row_start + row_width;
                 self.cells[row_start..row_end].fill(cell);
                 self.mark_dirty_row_full(y);
@@ -1044,10 +1100,10 @@ impl Buffer {
             && self.scissor_stack.len() == 1
         {
             let row_width = self.width as usize;
-            let x_start = clipped.x as usize;
-            let x_end = clipped.right() as usize;
+            let x_start = (clipped.x - self.origin_x) as usize;
+            let x_end = (clipped.right() - self.origin_x) as usize;
             for y in clipped.y..clipped.bottom() {
-                let row_start = y as usize * row_width;
+                let row_start = (y - self.origin_y) as usize * row_width;
                 let mut dirty_left = clipped.x;
                 let mut dirty_right = clipped.right();
 
@@ -1072,7 +1128,7 @@ impl Buffer {
                             for cx in hx..x_start {
                                 self.cells[row_start + cx] = Cell::default();
                             }
-                            dirty_left = hx as u16;
+                            dirty_left = hx as u16 + self.origin_x;
                         }
                     }
                 }
@@ -1083,7 +1139,7 @@ impl Buffer {
                     let mut cx = x_end;
                     while cx < row_width && self.cells[row_start + cx].is_continuation() {
                         self.cells[row_start + cx] = Cell::default();
-                        dirty_right = (cx as u16).saturating_add(1);
+                        dirty_right = (cx as u16 + self.origin_x).saturating_add(1);
                         cx += 1;
                     }
                 }
@@ -1110,9 +1166,13 @@ impl Buffer {
     }
 
     /// Clear all cells to the default.
+    ///
+    /// Also resets every row to [`LineSize::Normal`].
     #[inline]
     pub fn clear(&mut self) {
         self.cells.fill(Cell::default());
+        self.line_sizes.fill(LineSize::Normal);
+        self.scaled_texts.clear();
         self.mark_all_dirty();
     }
 
@@ -1121,12 +1181,12 @@ impl Buffer {
     /// This restores scissor/opacity stacks to their base values to ensure
     /// each frame starts from a clean rendering state.
     pub fn reset_for_frame(&mut self) {
+        let bounds = self.bounds();
         self.scissor_stack.truncate(1);
         if let Some(base) = self.scissor_stack.first_mut() {
-            *base = Rect::from_size(self.width, self.height);
+            *base = bounds;
         } else {
-            self.scissor_stack
-                .push(Rect::from_size(self.width, self.height));
+            self.scissor_stack.push(bounds);
         }
 
         self.opacity_stack.truncate(1);
@@ -1146,6 +1206,8 @@ impl Buffer {
             self.clear();
             return;
         }
+        self.line_sizes.fill(LineSize::Normal);
+        self.scaled_texts.clear();
 
         let width = cell.content.width();
         if width <= 1 {
@@ -1181,10 +1243,12 @@ impl Buffer {
 
     /// Get mutable raw access to the cell slice.
     ///
-    /// Marks all rows dirty since caller may modify arbitrary cells.
+    /// Marks all rows dirty and forgets scaled text runs since caller may
+    /// modify arbitrary cells.
     #[inline]
     pub fn cells_mut(&mut self) -> &mut [Cell] {
         self.mark_all_dirty();
+        self.scaled_texts.clear();
         &mut self.cells
     }
 
@@ -1195,39 +1259,39 @@ impl Buffer {
     /// Panics if `y >= height`.
     #[inline]
     pub fn row_cells(&self, y: u16) -> &[Cell] {
-        let start = y as usize * self.width as usize;
+        let start = (y - self.origin_y) as usize * self.width as usize;
         &self.cells[start..start + self.width as usize]
     }
 
     /// Get mutable cells for a contiguous span on a row.
     ///
     /// The requested range is treated as half-open `[x0, x1)` and clamped to
-    /// the buffer width. The span is marked dirty once before returning the
-    /// mutable slice.
+    /// the buffer width. The span is marked dirty once, and any scaled text
+    /// run it overlaps is forgotten, before returning the mutable slice.
     ///
     /// This is a raw bulk-mutation helper: callers must already have applied
     /// any required scissor/opacity clipping and must not use it for writes
     /// that can change cell-content width invariants.
     #[inline]
     pub fn row_cells_mut_span(&mut self, y: u16, x0: u16, x1: u16) -> Option<&mut [Cell]> {
-        if y >= self.height {
-            return None;
-        }
+        let row = self.row(y)?;
         if x0 >= x1 {
             return None;
         }
 
-        let start = x0.min(self.width);
-        let end = x1.min(self.width);
+        let bounds = self.bounds();
+        let start = x0.clamp(bounds.left(), bounds.right());
+        let end = x1.min(bounds.right());
         if start >= end {
             return None;
         }
 
         self.mark_dirty_span(y, start, end);
+        self.drop_scaled_text(Rect::new(start, y, end - start, 1));
 
-        let row_start = y as usize * self.width as usize;
-        let slice_start = row_start + start as usize;
-        let slice_end = row_start + end as usize;
+        let row_start = row * self.width as usize;
+        let slice_start = row_start + (start - self.origin_x) as usize;
+        let slice_end = row_start + (end - self.origin_x) as usize;
         Some(&mut self.cells[slice_start..slice_end])
     }
 
@@ -1387,9 +1451,120 @@ impl Buffer {
         self.pop_scissor();
     }
 
-    /// Check if two buffers have identical content.
+    /// Check if two buffers have identical content and line sizes.
     pub fn content_eq(&self, other: &Buffer) -> bool {
-        self.width == other.width && self.height == other.height && self.cells == other.cells
+        self.bounds() == other.bounds()
+            && self.cells == other.cells
+            && self.line_sizes == other.line_sizes
+            && self.scaled_texts == other.scaled_texts
+    }
+
+    // ----- Line size attributes -----
+
+    /// DEC line size of row `y` ([`LineSize::Normal`] when out of bounds).
+    #[inline]
+    #[must_use]
+    pub fn line_size(&self, y: u16) -> LineSize {
+        self.row(y)
+            .and_then(|row| self.line_sizes.get(row))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Set the DEC line size of row `y`.
+    ///
+    /// A change marks the whole row dirty: the terminal redraws every glyph
+    /// of a row whose size changes. Cells are not moved; on a scaled row only
+    /// the first [`LineSize::visible_columns`] cells are shown.
+    pub fn set_line_size(&mut self, y: u16, size: LineSize) {
+        let Some(slot) = self.row(y).and_then(|row| self.line_sizes.get_mut(row)) else {
+            return;
+        };
+        if *slot != size {
+            *slot = size;
+            self.mark_dirty_row_full(y);
+        }
+    }
+
+    /// Line sizes of all rows.
+    #[inline]
+    pub fn line_sizes(&self) -> &[LineSize] {
+        &self.line_sizes
+    }
+
+    /// Whether any row is double-width or double-height.
+    #[inline]
+    pub fn has_scaled_lines(&self) -> bool {
+        self.line_sizes.iter().any(|size| size.is_scaled())
+    }
+
+    // ----- Scaled text (OSC 66) -----
+
+    /// Reserve the block of cells for a scaled text run.
+    ///
+    /// Removes any run the block overlaps, fills the block with blanks in
+    /// `base_cell`'s style, and records `run`. The caller then prints the
+    /// fallback text on the top row, as
+    /// [`Frame::print_scaled_text`](crate::frame::Frame::print_scaled_text)
+    /// does. Returns `false` without recording anything when the block does
+    /// not fit inside the current scissor region.
+    pub fn reserve_scaled_text(&mut self, run: ScaledText, base_cell: Cell) -> bool {
+        let area = run.area();
+        if area.is_empty() || self.current_scissor().intersection(&area) != area {
+            return false;
+        }
+        let blank = Cell {
+            content: CellContent::from_char(' '),
+            ..base_cell
+        };
+        self.fill(area, blank);
+        self.insert_scaled_text(run);
+        true
+    }
+
+    /// Record `run` without touching its cells, keeping anchor order.
+    pub(crate) fn insert_scaled_text(&mut self, run: ScaledText) {
+        let at = self
+            .scaled_texts
+            .partition_point(|other| (other.y(), other.x()) < (run.y(), run.x()));
+        self.scaled_texts.insert(at, run);
+    }
+
+    /// Forget runs under a cell write to `area`.
+    ///
+    /// Anything drawn over a reserved block replaces the run there, so the
+    /// presenter must emit the new cells instead of the stale OSC 66 text.
+    #[inline]
+    fn drop_scaled_text(&mut self, area: Rect) {
+        if !self.scaled_texts.is_empty() {
+            self.remove_scaled_text(area);
+        }
+    }
+
+    /// Forget every scaled text run whose block overlaps `area`.
+    ///
+    /// The fallback cells stay in place.
+    pub fn remove_scaled_text(&mut self, area: Rect) {
+        self.scaled_texts
+            .retain(|run| run.area().intersection_opt(&area).is_none());
+    }
+
+    /// The scaled text run covering `(x, y)`, if any.
+    #[must_use]
+    pub fn scaled_text_at(&self, x: u16, y: u16) -> Option<&ScaledText> {
+        self.scaled_texts.iter().find(|run| run.contains(x, y))
+    }
+
+    /// All scaled text runs, sorted by anchor in row-major order.
+    #[inline]
+    pub fn scaled_texts(&self) -> &[ScaledText] {
+        &self.scaled_texts
+    }
+
+    /// Whether any scaled text run is recorded.
+    #[inline]
+    pub fn has_scaled_text(&self) -> bool {
+        !self.scaled_texts.is_empty()
     }
 }
 
@@ -1811,6 +1986,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn new_at_addresses_cells_in_area_coordinates() {
+        let mut buf = Buffer::new_at(Rect::new(10, 100, 4, 2));
+        assert_eq!(buf.bounds(), Rect::new(10, 100, 4, 2));
+        assert_eq!(buf.current_scissor(), buf.bounds());
+
+        buf.fill(Rect::new(0, 0, 12, 101), Cell::from_char('a'));
+        buf.set(13, 101, Cell::from_char('b'));
+        buf.set(9, 100, Cell::from_char('x'));
+        buf.set(14, 101, Cell::from_char('x'));
+
+        assert_eq!(buf.get(10, 100).unwrap().content.as_char(), Some('a'));
+        assert_eq!(buf.get(11, 100).unwrap().content.as_char(), Some('a'));
+        assert!(buf.get(12, 100).unwrap().is_empty());
+        assert_eq!(buf.get(13, 101).unwrap().content.as_char(), Some('b'));
+        assert!(buf.get(9, 100).is_none());
+        assert!(buf.get(0, 0).is_none());
+        assert!(buf.is_row_dirty(101));
+        assert_eq!(buf.row_cells(101)[3].content.as_char(), Some('b'));
+
+        // A wide glyph straddling the left edge is dropped whole.
+        buf.set(9, 101, Cell::from_char('中'));
+        assert!(!buf.get(10, 101).unwrap().is_continuation());
+    }
+
     #[test]
     fn set_fast_matches_set_for_transparent_bg() {
         let red = PackedRgba::rgb(255, 0, 0);
@@ -2698,6 +2898,77 @@ mod tests {
         assert!(!buf1.content_eq(&buf2));
     }
 
+    #[test]
+    fn reserve_scaled_text_blanks_block_and_records_run() {
+        let mut buf = Buffer::new(10, 4);
+        buf.fill(Rect::new(0, 0, 10, 4), Cell::from_char('.'));
+        let base = Cell::default().with_bg(PackedRgba::rgb(0, 0, 255));
+        assert!(buf.reserve_scaled_text(ScaledText::new(1, 1, "ab", 2), base));
+
+        assert!(buf.has_scaled_text());
+        assert_eq!(buf.get(1, 1).unwrap().content.as_char(), Some(' '));
+        assert_eq!(buf.get(4, 2).unwrap().bg, PackedRgba::rgb(0, 0, 255));
+        assert_eq!(buf.get(5, 1).unwrap().content.as_char(), Some('.'));
+        assert_eq!(buf.scaled_text_at(4, 2).map(ScaledText::text), Some("ab"));
+        assert!(buf.scaled_text_at(0, 1).is_none());
+    }
+
+    #[test]
+    fn reserve_scaled_text_rejects_blocks_outside_scissor() {
+        let mut buf = Buffer::new(6, 3);
+        assert!(!buf.reserve_scaled_text(ScaledText::new(4, 0, "ab", 2), Cell::default()));
+        buf.push_scissor(Rect::new(0, 0, 3, 3));
+        assert!(!buf.reserve_scaled_text(ScaledText::new(0, 0, "ab", 2), Cell::default()));
+        assert!(!buf.has_scaled_text());
+    }
+
+    #[test]
+    fn writing_into_scaled_block_drops_run() {
+        let run = || ScaledText::new(1, 1, "ab", 2);
+        let writes: [fn(&mut Buffer); 6] = [
+            |buf| buf.set(4, 2, Cell::from_char('x')),
+            |buf| buf.set_fast(4, 2, Cell::from_char('x')),
+            |buf| buf.set_raw(4, 2, Cell::from_char('x')),
+            |buf| buf.fill(Rect::new(3, 0, 2, 2), Cell::from_char('x')),
+            |buf| buf.get_mut(4, 2).unwrap().content = CellContent::from_char('x'),
+            |buf| {
+                buf.row_cells_mut_span(2, 0, 2)
+                    .unwrap()
+                    .fill(Cell::from_char('x'))
+            },
+        ];
+        for write in writes {
+            let mut buf = Buffer::new(10, 4);
+            buf.reserve_scaled_text(run(), Cell::default());
+            buf.reserve_scaled_text(ScaledText::new(6, 0, "c", 2), Cell::default());
+            write(&mut buf);
+            assert!(buf.scaled_text_at(1, 1).is_none());
+            assert!(buf.scaled_text_at(6, 0).is_some());
+        }
+
+        let mut buf = Buffer::new(10, 4);
+        buf.reserve_scaled_text(run(), Cell::default());
+        buf.set(5, 1, Cell::from_char('x'));
+        assert!(buf.scaled_text_at(1, 1).is_some());
+    }
+
+    #[test]
+    fn overlapping_scaled_text_replaces_previous_run() {
+        let mut buf = Buffer::new(10, 4);
+        buf.reserve_scaled_text(ScaledText::new(0, 0, "ab", 2), Cell::default());
+        buf.reserve_scaled_text(ScaledText::new(6, 0, "c", 2), Cell::default());
+        buf.reserve_scaled_text(ScaledText::new(2, 1, "x", 2), Cell::default());
+        let anchors: Vec<_> = buf.scaled_texts().iter().map(|r| (r.x(), r.y())).collect();
+        assert_eq!(anchors, vec![(6, 0), (2, 1)]);
+
+        let other = buf.clone();
+        buf.remove_scaled_text(Rect::new(7, 1, 1, 1));
+        assert_eq!(buf.scaled_texts().len(), 1);
+        assert!(!buf.content_eq(&other));
+        buf.clear();
+        assert!(!buf.has_scaled_text());
+    }
+
     // ====== Property tests (proptest) ======
 
     mod property {
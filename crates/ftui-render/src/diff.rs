@@ -1056,6 +1056,50 @@ fn reserve_changes_capacity(width: u16, height: u16, changes: &mut Vec<(u16, u16
     }
 }
 
+/// Mark every cell of rows whose DEC line size changed.
+///
+/// Changing a row's size makes the terminal re-lay-out all of its glyphs, so
+/// the whole row must be re-emitted even where cells are unchanged. Line size
+/// changes are rare; the common path is a single slice comparison.
+fn apply_line_size_changes(old: &Buffer, new: &Buffer, changes: &mut Vec<(u16, u16)>) {
+    let (old_sizes, new_sizes) = (old.line_sizes(), new.line_sizes());
+    if old_sizes == new_sizes {
+        return;
+    }
+    let changed = |y: u16| old_sizes.get(y as usize) != new_sizes.get(y as usize);
+    changes.retain(|&(_, y)| !changed(y));
+    for y in (0..new.height()).filter(|&y| changed(y)) {
+        changes.extend((0..new.width()).map(|x| (x, y)));
+    }
+    changes.sort_unstable_by_key(|&(x, y)| (y, x));
+}
+
+/// Re-emit every cell of a scaled text run that was added or removed.
+///
+/// A run's fallback cells can be identical on both sides (same text, new
+/// scale), and the presenter writes a run only from its anchor cell, so the
+/// whole block is marked changed.
+fn apply_scaled_text_changes(old: &Buffer, new: &Buffer, changes: &mut Vec<(u16, u16)>) {
+    let (old_runs, new_runs) = (old.scaled_texts(), new.scaled_texts());
+    if old_runs == new_runs {
+        return;
+    }
+    let bounds = new.bounds();
+    let before = changes.len();
+    let added = new_runs.iter().filter(|run| !old_runs.contains(run));
+    let removed = old_runs.iter().filter(|run| !new_runs.contains(run));
+    for run in added.chain(removed) {
+        let area = run.area().intersection(&bounds);
+        for y in area.top()..area.bottom() {
+            changes.extend((area.left()..area.right()).map(|x| (x, y)));
+        }
+    }
+    if changes.len() > before {
+        changes.sort_unstable_by_key(|&(x, y)| (y, x));
+        changes.dedup();
+    }
+}
+
 fn compute_changes(old: &Buffer, new: &Buffer, changes: &mut Vec<(u16, u16)>) {
     #[cfg(feature = "tracing")]
     let _span = tracing::debug_span!("diff_compute", width = old.width(), height = old.height());
@@ -1359,6 +1403,8 @@ impl BufferDiff {
     pub fn compute_into(&mut self, old: &Buffer, new: &Buffer) {
         self.last_tile_stats = None;
         compute_changes(old, new, &mut self.changes);
+        apply_line_size_changes(old, new, &mut self.changes);
+        apply_scaled_text_changes(old, new, &mut self.changes);
     }
 
     /// Compute the diff between two buffers using dirty-row hints.
@@ -1388,6 +1434,8 @@ impl BufferDiff {
             &self.tile_config,
             &mut self.last_tile_stats,
         );
+        apply_line_size_changes(old, new, &mut self.changes);
+        apply_scaled_text_changes(old, new, &mut self.changes);
     }
 
     /// Compute the diff with a certificate-based skip hint.
@@ -1465,6 +1513,10 @@ impl BufferDiff {
                     }
                 }
 
+                apply_line_size_changes(old, new, &mut self.changes);
+
+                apply_scaled_text_changes(old, new, &mut self.changes);
+
                 #[cfg(feature = "tracing")]
                 tracing::debug!(
                     event = "diff_narrow_certified",
@@ -1667,6 +1719,53 @@ mod tests {
         assert_eq!(diff.changes()[5], (2, 1));
     }
 
+    #[test]
+    fn line_size_change_invalidates_whole_row() {
+        use crate::line_size::LineSize;
+
+        let mut old = Buffer::new(4, 3);
+        old.set_raw(1, 0, Cell::from_char('a'));
+        old.set_raw(2, 2, Cell::from_char('b'));
+        let mut new = old.clone();
+        new.set_raw(0, 0, Cell::from_char('c'));
+        new.set_raw(3, 2, Cell::from_char('d'));
+        new.set_line_size(1, LineSize::DoubleWidth);
+
+        let expected = vec![(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (3, 2)];
+        assert_eq!(BufferDiff::compute(&old, &new).changes(), expected);
+        assert_eq!(BufferDiff::compute_dirty(&old, &new).changes(), expected);
+
+        let mut diff = BufferDiff::new();
+        diff.compute_certified_into(&old, &new, DiffSkipHint::NarrowToRows(vec![0]));
+        assert_eq!(diff.changes(), &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn scaled_text_change_invalidates_whole_block() {
+        use crate::text_sizing::ScaledText;
+
+        let mut old = Buffer::new(6, 3);
+        old.reserve_scaled_text(ScaledText::new(1, 0, "a", 2), Cell::default());
+        let mut new = old.clone();
+        new.reserve_scaled_text(ScaledText::new(1, 0, "a", 3), Cell::default());
+        new.set_raw(5, 2, Cell::from_char('z'));
+
+        let expected = vec![
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (1, 2),
+            (2, 2),
+            (3, 2),
+            (5, 2),
+        ];
+        assert_eq!(BufferDiff::compute(&old, &new).changes(), expected);
+        assert_eq!(BufferDiff::compute_dirty(&old, &new).changes(), expected);
+    }
+
     #[test]
     fn single_cell_change_detected() {
         let old = Buffer::new(10, 10);
@@ -191,7 +191,7 @@ impl Draw for Buffer {
     }
 
     fn print_text(&mut self, x: u16, y: u16, text: &str, base_cell: Cell) -> u16 {
-        self.print_text_clipped(x, y, text, base_cell, self.width())
+        self.print_text_clipped(x, y, text, base_cell, self.bounds().right())
     }
 
     fn print_text_clipped(
@@ -32,9 +32,10 @@
 use crate::arena::FrameArena;
 use crate::budget::DegradationLevel;
 use crate::buffer::Buffer;
-use crate::cell::{Cell, CellContent, GraphemeId};
+use crate::cell::{Cell, CellContent, GraphemeId, StyleFlags};
 use crate::drawing::{BorderChars, Draw};
 use crate::grapheme_pool::GraphemePool;
+use crate::text_sizing::ScaledText;
 use crate::{display_width, grapheme_width};
 use ftui_core::geometry::Rect;
 use unicode_segmentation::UnicodeSegmentation;
@@ -172,6 +173,10 @@ impl HitCell {
 pub struct HitGrid {
     width: u16,
     height: u16,
+    /// Coordinates of the top-left cell; `(0, 0)` unless built with
+    /// [`HitGrid::new_at`].
+    origin_x: u16,
+    origin_y: u16,
     cells: Vec<HitCell>,
 }
 
@@ -182,10 +187,28 @@ impl HitGrid {
         Self {
             width,
             height,
+            origin_x: 0,
+            origin_y: 0,
             cells: vec![HitCell::default(); size],
         }
     }
 
+    /// Create a hit grid covering `area`, addressed in the same coordinates.
+    ///
+    /// The counterpart of [`Buffer::new_at`] for off-screen frames.
+    pub fn new_at(area: Rect) -> Self {
+        let mut grid = Self::new(area.width, area.height);
+        grid.origin_x = area.x;
+        grid.origin_y = area.y;
+        grid
+    }
+
+    /// Area covered by the grid.
+    #[inline]
+    pub const fn bounds(&self) -> Rect {
+        Rect::new(self.origin_x, self.origin_y, self.width, self.height)
+    }
+
     /// Grid width.
     #[inline]
     pub const fn width(&self) -> u16 {
@@ -201,6 +224,8 @@ impl HitGrid {
     /// Convert (x, y) to linear index.
     #[inline]
     fn index(&self, x: u16, y: u16) -> Option<usize> {
+        let x = x.checked_sub(self.origin_x)?;
+        let y = y.checked_sub(self.origin_y)?;
         if x < self.width && y < self.height {
             Some(y as usize * self.width as usize + x as usize)
         } else {
@@ -238,20 +263,19 @@ impl HitGrid {
         data: HitData,
         owner: Option<HitOwner>,
     ) {
-        // Use usize to avoid overflow for large coordinates
-        let x_end = (rect.x as usize + rect.width as usize).min(self.width as usize);
-        let y_end = (rect.y as usize + rect.height as usize).min(self.height as usize);
-
-        // Check if there's anything to do
-        if rect.x as usize >= x_end || rect.y as usize >= y_end {
+        let Some(rect) = rect.intersection_opt(&self.bounds()) else {
             return;
-        }
+        };
+        let x_start = (rect.x - self.origin_x) as usize;
+        let x_end = x_start + rect.width as usize;
+        let y_start = (rect.y - self.origin_y) as usize;
+        let y_end = y_start + rect.height as usize;
 
         let hit_cell = HitCell::new_with_owner(widget_id, region, data, owner);
 
-        for y in rect.y as usize..y_end {
+        for y in y_start..y_end {
             let row_start = y * self.width as usize;
-            let start = row_start + rect.x as usize;
+            let start = row_start + x_start;
             let end = row_start + x_end;
 
             // Optimize: use slice fill for contiguous memory access
@@ -278,12 +302,13 @@ impl HitGrid {
 
     /// Return all hits within the given rectangle.
     pub fn hits_in(&self, rect: Rect) -> Vec<(HitId, HitRegion, HitData)> {
-        let x_end = (rect.x as usize + rect.width as usize).min(self.width as usize) as u16;
-        let y_end = (rect.y as usize + rect.height as usize).min(self.height as usize) as u16;
         let mut hits = Vec::new();
+        let Some(rect) = rect.intersection_opt(&self.bounds()) else {
+            return hits;
+        };
 
-        for y in rect.y..y_end {
-            for x in rect.x..x_end {
+        for y in rect.y..rect.bottom() {
+            for x in rect.x..rect.right() {
                 if let Some((id, region, data)) = self.hit_test(x, y) {
                     hits.push((id, region, data));
                 }
@@ -585,6 +610,43 @@ impl<'a> Frame<'a> {
         }
     }
 
+    /// Draw `text` at `scale` times its normal size (Kitty text sizing).
+    ///
+    /// Reserves a block of `scale` rows by `width * scale` columns at
+    /// `(x, y)` and prints the fallback, the text in bold at normal size, on
+    /// its top row. Terminals with OSC 66 support show the scaled text
+    /// instead. Returns `false` when the block does not fit inside the
+    /// scissor region; only the bold fallback is drawn then.
+    pub fn print_scaled_text(
+        &mut self,
+        x: u16,
+        y: u16,
+        text: &str,
+        scale: u8,
+        base_cell: Cell,
+    ) -> bool {
+        let run = ScaledText::new(x, y, text, scale);
+        if run.scale() == 1 {
+            self.print_text(x, y, run.text(), base_cell);
+            return true;
+        }
+        let max_x = run.area().right();
+        let reserved = self.buffer.reserve_scaled_text(run.clone(), base_cell);
+        let bold = Cell {
+            attrs: base_cell
+                .attrs
+                .with_flags(base_cell.attrs.flags() | StyleFlags::BOLD),
+            ..base_cell
+        };
+        let max_x = if reserved { max_x } else { self.width() };
+        self.print_text_clipped(x, y, run.text(), bold, max_x);
+        if reserved {
+            // Printing the fallback into the block dropped the run.
+            self.buffer.insert_scaled_text(run);
+        }
+        reserved
+    }
+
     /// Set the widget render budget for this frame.
     pub fn set_widget_budget(&mut self, budget: WidgetBudget) {
         self.widget_budget = budget;
@@ -634,7 +696,7 @@ impl<'a> Frame<'a> {
     /// Enable hit testing on an existing frame.
     pub fn enable_hit_testing(&mut self) {
         if self.hit_grid.is_none() {
-            self.hit_grid = Some(HitGrid::new(self.width(), self.height()));
+            self.hit_grid = Some(HitGrid::new_at(self.bounds()));
         }
     }
 
@@ -848,6 +910,30 @@ mod tests {
     use super::*;
     use crate::cell::Cell;
 
+    #[test]
+    fn print_scaled_text_reserves_block_with_bold_fallback() {
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(10, 3, &mut pool);
+        assert!(frame.print_scaled_text(1, 0, "Hi", 2, Cell::default()));
+
+        let run = frame.buffer.scaled_text_at(4, 1).expect("run recorded");
+        assert_eq!(run.area(), Rect::new(1, 0, 4, 2));
+        let h = frame.buffer.get(1, 0).unwrap();
+        assert_eq!(h.content.as_char(), Some('H'));
+        assert!(h.attrs.flags().contains(StyleFlags::BOLD));
+        assert_eq!(frame.buffer.get(2, 0).unwrap().content.as_char(), Some('i'));
+        assert_eq!(frame.buffer.get(3, 0).unwrap().content.as_char(), Some(' '));
+    }
+
+    #[test]
+    fn print_scaled_text_that_does_not_fit_draws_fallback_only() {
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(10, 3, &mut pool);
+        assert!(!frame.print_scaled_text(0, 2, "Hi", 2, Cell::default()));
+        assert!(!frame.buffer.has_scaled_text());
+        assert_eq!(frame.buffer.get(1, 2).unwrap().content.as_char(), Some('i'));
+    }
+
     #[test]
     fn frame_creation() {
         let mut pool = GraphemePool::new();
@@ -1052,6 +1138,25 @@ mod tests {
         assert!(grid.hit_test(10, 10).is_none());
     }
 
+    #[test]
+    fn hit_grid_new_at_uses_area_coordinates() {
+        let mut grid = HitGrid::new_at(Rect::new(20, 50, 4, 4));
+        grid.register(
+            Rect::new(18, 48, 4, 4),
+            HitId::new(1),
+            HitRegion::Content,
+            0,
+        );
+
+        assert_eq!(
+            grid.hit_test(21, 51),
+            Some((HitId::new(1), HitRegion::Content, 0))
+        );
+        assert!(grid.hit_test(22, 51).is_none());
+        assert!(grid.hit_test(1, 1).is_none());
+        assert_eq!(grid.hits_in(Rect::new(0, 0, 30, 60)).len(), 4);
+    }
+
     #[test]
     fn hit_grid_edge_and_corner_cells() {
         let mut grid = HitGrid::new(4, 4);
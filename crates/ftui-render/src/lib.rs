@@ -34,6 +34,7 @@ pub mod frame;
 pub mod frame_guardrails;
 pub mod grapheme_pool;
 pub mod headless;
+pub mod line_size;
 pub mod link_registry;
 pub mod presenter;
 
@@ -42,6 +43,7 @@ pub mod roaring_bitmap;
 pub mod sanitize;
 pub mod spatial_hit_index;
 pub mod terminal_model;
+pub mod text_sizing;
 
 // Re-export text width helpers from ftui-core (single source of truth).
 pub(crate) use ftui_core::text_width::{char_width, display_width, grapheme_width};
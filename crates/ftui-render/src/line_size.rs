@@ -0,0 +1,356 @@
+#![forbid(unsafe_code)]
+
+//! DEC line size attributes (DECDWL / DECDHL).
+//!
+//! A row of a [`Buffer`] can be marked double-width or as the top or bottom
+//! half of a double-height line. Terminals that implement the DEC line
+//! attributes draw such a row with every glyph scaled up, which is a cheap way
+//! to get large clocks and KPI banners without a custom font.
+//!
+//! | Size | Sequence | Visible columns |
+//! |------|----------|-----------------|
+//! | [`LineSize::Normal`] | `ESC # 5` (DECSWL) | `width` |
+//! | [`LineSize::DoubleWidth`] | `ESC # 6` (DECDWL) | `width / 2` |
+//! | [`LineSize::DoubleHeightTop`] | `ESC # 3` (DECDHL) | `width / 2` |
+//! | [`LineSize::DoubleHeightBottom`] | `ESC # 4` (DECDHL) | `width / 2` |
+//!
+//! Cells keep their logical column: on a scaled row, cell `x` is drawn at
+//! screen columns `2x` and `2x + 1`, and cells at `x >= width / 2` are not
+//! visible. A double-height line is two rows carrying the same text, the first
+//! marked [`DoubleHeightTop`](LineSize::DoubleHeightTop) and the second
+//! [`DoubleHeightBottom`](LineSize::DoubleHeightBottom).
+//!
+//! # Fallback
+//!
+//! When the terminal lacks line attributes, [`apply_fallback`] rewrites scaled
+//! rows into ordinary cells before diffing: double-height rows are drawn with
+//! a small quadrant-block font (each glyph spans 2×2 cells), and characters
+//! without a block glyph, as well as double-width rows, are letter-spaced.
+
+use crate::buffer::Buffer;
+use crate::cell::{Cell, CellContent};
+
+/// Per-row line size attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LineSize {
+    /// Single-width, single-height (DECSWL).
+    #[default]
+    Normal,
+    /// Double-width, single-height (DECDWL).
+    DoubleWidth,
+    /// Top half of a double-height, double-width line (DECDHL).
+    DoubleHeightTop,
+    /// Bottom half of a double-height, double-width line (DECDHL).
+    DoubleHeightBottom,
+}
+
+impl LineSize {
+    /// Whether glyphs on this row are drawn at twice their width.
+    #[inline]
+    pub const fn is_scaled(self) -> bool {
+        !matches!(self, Self::Normal)
+    }
+
+    /// Number of cells that are visible on a row of `width` columns.
+    #[inline]
+    pub const fn visible_columns(self, width: u16) -> u16 {
+        if self.is_scaled() {
+            let half = width / 2;
+            if half == 0 { 1 } else { half }
+        } else {
+            width
+        }
+    }
+
+    /// The escape sequence that selects this line size for the cursor row.
+    #[inline]
+    pub const fn escape_sequence(self) -> &'static [u8] {
+        match self {
+            Self::Normal => b"\x1b#5",
+            Self::DoubleWidth => b"\x1b#6",
+            Self::DoubleHeightTop => b"\x1b#3",
+            Self::DoubleHeightBottom => b"\x1b#4",
+        }
+    }
+
+    /// Parse the final byte of an `ESC #` sequence.
+    #[inline]
+    pub const fn from_escape_final(byte: u8) -> Option<Self> {
+        match byte {
+            b'5' => Some(Self::Normal),
+            b'6' => Some(Self::DoubleWidth),
+            b'3' => Some(Self::DoubleHeightTop),
+            b'4' => Some(Self::DoubleHeightBottom),
+            _ => None,
+        }
+    }
+}
+
+/// Rewrite every scaled row of `buffer` into normal cells.
+///
+/// Used when the terminal cannot display DEC line attributes. Afterwards all
+/// rows are [`LineSize::Normal`] and the rewritten rows are marked dirty.
+pub fn apply_fallback(buffer: &mut Buffer) {
+    if !buffer.has_scaled_lines() {
+        return;
+    }
+    let width = buffer.width();
+    let mut source: Vec<Cell> = Vec::with_capacity(width as usize);
+    for y in 0..buffer.height() {
+        let size = buffer.line_size(y);
+        if !size.is_scaled() {
+            continue;
+        }
+        source.clear();
+        source.extend_from_slice(buffer.row_cells(y));
+        buffer.set_line_size(y, LineSize::Normal);
+        let Some(row) = buffer.row_cells_mut_span(y, 0, width) else {
+            continue;
+        };
+        row.fill(Cell::default());
+        expand_row(&source, row, size);
+    }
+}
+
+/// Expand one scaled row: each visible source cell becomes two destination
+/// cells.
+///
+/// Double-height rows are drawn with the top or bottom glyph pixel rows;
+/// double-width rows letter-space the text. Only the
+/// [`LineSize::visible_columns`] a terminal would show are expanded.
+fn expand_row(source: &[Cell], row: &mut [Cell], size: LineSize) {
+    let half = match size {
+        LineSize::DoubleHeightTop => Some(0),
+        LineSize::DoubleHeightBottom => Some(2),
+        _ => None,
+    };
+    let visible = usize::from(size.visible_columns(source.len() as u16));
+    for (x, cell) in source.iter().take(visible).enumerate() {
+        let dst = x * 2;
+        if dst >= row.len() || cell.is_continuation() {
+            continue;
+        }
+        let glyph = half.and_then(|h| {
+            cell.content
+                .as_char()
+                .and_then(|c| block_glyph(c).map(|g| (g, h)))
+        });
+        match glyph {
+            Some((bitmap, h)) => {
+                let upper = bitmap[h];
+                let lower = bitmap[h + 1];
+                for col in 0..2 {
+                    let Some(slot) = row.get_mut(dst + col) else {
+                        break;
+                    };
+                    let quadrant = quadrant_char(upper, lower, col);
+                    *slot = Cell {
+                        content: CellContent::from_char(quadrant),
+                        ..*cell
+                    };
+                }
+            }
+            None => {
+                // Bottom halves of glyph-less characters stay blank so the
+                // character is not shown twice.
+                if half == Some(2) {
+                    if let Some(slot) = row.get_mut(dst) {
+                        *slot = Cell {
+                            content: CellContent::from_char(' '),
+                            ..*cell
+                        };
+                    }
+                    continue;
+                }
+                let width = cell.content.width().max(1);
+                row[dst] = *cell;
+                for off in 1..width {
+                    if let Some(slot) = row.get_mut(dst + off) {
+                        *slot = Cell::CONTINUATION;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Quadrant characters indexed by `TL | TR << 1 | BL << 2 | BR << 3`.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// Quadrant character for destination column `col` (0 or 1) of a glyph,
+/// given its two 4-pixel-wide bitmap rows.
+fn quadrant_char(upper: u8, lower: u8, col: usize) -> char {
+    // Bitmap rows are 3 pixels wide (bit 2 = left); pixel 3 is spacing.
+    let px = |row: u8, i: usize| -> usize { usize::from(i < 3 && row & (0b100 >> i) != 0) };
+    let (l, r) = (col * 2, col * 2 + 1);
+    let index = px(upper, l) | px(upper, r) << 1 | px(lower, l) << 2 | px(lower, r) << 3;
+    QUADRANTS[index]
+}
+
+/// 3×4 pixel bitmap for characters of the block font.
+///
+/// Each row uses bits 2..=0 (left to right). Lowercase letters use the
+/// uppercase glyphs.
+fn block_glyph(c: char) -> Option<[u8; 4]> {
+    let bitmap = match c.to_ascii_uppercase() {
+        ' ' => [0b000, 0b000, 0b000, 0b000],
+        '0' | 'O' => [0b111, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b111],
+        '2' => [0b110, 0b001, 0b010, 0b111],
+        '3' => [0b111, 0b011, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001],
+        '5' => [0b111, 0b110, 0b001, 0b110],
+        '6' => [0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010],
+        '8' => [0b111, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001],
+        'A' => [0b010, 0b101, 0b111, 0b101],
+        'B' => [0b110, 0b111, 0b101, 0b110],
+        'C' => [0b111, 0b100, 0b100, 0b111],
+        'D' => [0b110, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100],
+        'G' => [0b111, 0b100, 0b101, 0b111],
+        'H' => [0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b101, 0b111],
+        'K' => [0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101],
+        'P' => [0b111, 0b101, 0b111, 0b100],
+        'Q' => [0b111, 0b101, 0b101, 0b011],
+        'R' => [0b111, 0b101, 0b110, 0b101],
+        'S' => [0b011, 0b100, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b101],
+        'X' => [0b101, 0b010, 0b010, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010],
+        'Z' => [0b111, 0b010, 0b100, 0b111],
+        ':' => [0b000, 0b010, 0b000, 0b010],
+        '.' => [0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b010, 0b100],
+        '-' => [0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010],
+        '=' => [0b000, 0b111, 0b000, 0b111],
+        '%' => [0b101, 0b001, 0b100, 0b101],
+        '/' => [0b001, 0b010, 0b010, 0b100],
+        '!' => [0b010, 0b010, 0b000, 0b010],
+        '?' => [0b111, 0b001, 0b000, 0b010],
+        '(' => [0b010, 0b100, 0b100, 0b010],
+        ')' => [0b010, 0b001, 0b001, 0b010],
+        _ => return None,
+    };
+    Some(bitmap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_string(buffer: &Buffer, y: u16) -> String {
+        buffer
+            .row_cells(y)
+            .iter()
+            .map(|c| c.content.as_char().unwrap_or(' '))
+            .collect()
+    }
+
+    #[test]
+    fn escape_sequences_round_trip() {
+        for size in [
+            LineSize::Normal,
+            LineSize::DoubleWidth,
+            LineSize::DoubleHeightTop,
+            LineSize::DoubleHeightBottom,
+        ] {
+            let seq = size.escape_sequence();
+            assert_eq!(&seq[..2], b"\x1b#");
+            assert_eq!(LineSize::from_escape_final(seq[2]), Some(size));
+        }
+        assert_eq!(LineSize::from_escape_final(b'8'), None);
+    }
+
+    #[test]
+    fn visible_columns_halve_on_scaled_rows() {
+        assert_eq!(LineSize::Normal.visible_columns(80), 80);
+        assert_eq!(LineSize::DoubleWidth.visible_columns(80), 40);
+        assert_eq!(LineSize::DoubleHeightTop.visible_columns(1), 1);
+    }
+
+    #[test]
+    fn fallback_letter_spaces_double_width_rows() {
+        let mut buffer = Buffer::new(6, 1);
+        for (x, c) in "abc".chars().enumerate() {
+            buffer.set_raw(x as u16, 0, Cell::from_char(c));
+        }
+        buffer.set_line_size(0, LineSize::DoubleWidth);
+        apply_fallback(&mut buffer);
+        assert_eq!(buffer.line_size(0), LineSize::Normal);
+        assert_eq!(row_string(&buffer, 0), "a b c ");
+    }
+
+    #[test]
+    fn fallback_expands_only_visible_columns_on_odd_widths() {
+        let mut buffer = Buffer::new(5, 1);
+        for (x, c) in "abcde".chars().enumerate() {
+            buffer.set_raw(x as u16, 0, Cell::from_char(c));
+        }
+        buffer.set_line_size(0, LineSize::DoubleWidth);
+        apply_fallback(&mut buffer);
+        // Two of five columns are visible; the third is not half-drawn.
+        assert_eq!(row_string(&buffer, 0), "a b  ");
+    }
+
+    #[test]
+    fn fallback_draws_block_glyphs_for_double_height() {
+        let mut buffer = Buffer::new(4, 2);
+        for y in 0..2 {
+            buffer.set_raw(0, y, Cell::from_char('1'));
+            buffer.set_raw(1, y, Cell::from_char('H'));
+        }
+        buffer.set_line_size(0, LineSize::DoubleHeightTop);
+        buffer.set_line_size(1, LineSize::DoubleHeightBottom);
+        apply_fallback(&mut buffer);
+
+        // '1' = .#. / ##. / .#. / ###   'H' = #.# / ### / #.# / #.#
+        assert_eq!(row_string(&buffer, 0), "▟ ▙▌");
+        assert_eq!(row_string(&buffer, 1), "▟▖▌▌");
+        assert!(!buffer.has_scaled_lines());
+    }
+
+    #[test]
+    fn fallback_keeps_style_of_source_cell() {
+        let mut buffer = Buffer::new(2, 1);
+        let styled = Cell::from_char('8').with_fg(crate::cell::PackedRgba::RED);
+        buffer.set_raw(0, 0, styled);
+        buffer.set_line_size(0, LineSize::DoubleHeightTop);
+        apply_fallback(&mut buffer);
+        assert_eq!(buffer.get(0, 0).unwrap().fg, crate::cell::PackedRgba::RED);
+        assert_eq!(buffer.get(1, 0).unwrap().fg, crate::cell::PackedRgba::RED);
+    }
+
+    #[test]
+    fn fallback_shows_unknown_chars_once() {
+        let mut buffer = Buffer::new(2, 2);
+        buffer.set_raw(0, 0, Cell::from_char('#'));
+        buffer.set_raw(0, 1, Cell::from_char('#'));
+        buffer.set_line_size(0, LineSize::DoubleHeightTop);
+        buffer.set_line_size(1, LineSize::DoubleHeightBottom);
+        apply_fallback(&mut buffer);
+        assert_eq!(row_string(&buffer, 0), "# ");
+        assert_eq!(row_string(&buffer, 1), "  ");
+    }
+
+    #[test]
+    fn block_font_covers_clock_and_kpi_characters() {
+        for c in "0123456789:.,-+%/ ".chars() {
+            assert!(block_glyph(c).is_some(), "missing glyph for {c:?}");
+        }
+        assert_eq!(block_glyph('k'), block_glyph('K'));
+    }
+}
@@ -11,6 +11,8 @@
 //! - **Run grouping**: Use ChangeRuns to minimize cursor positioning
 //! - **Single write**: Buffer all output and flush once per frame
 //! - **Synchronized output**: Use DEC 2026 to prevent flicker on supported terminals
+//! - **Terminfo mode**: Optionally take cursor motion, color, and attribute
+//!   sequences from a [`Terminfo`] entry instead of assuming xterm
 //!
 //! # Usage
 //!
@@ -42,15 +44,110 @@ use crate::counting_writer::{CountingWriter, PresentStats, StatsCollector};
 use crate::diff::{BufferDiff, ChangeRun};
 use crate::display_width;
 use crate::grapheme_pool::GraphemePool;
+use crate::line_size::LineSize;
 use crate::link_registry::LinkRegistry;
 use crate::sanitize::sanitize;
+use crate::text_sizing::ScaledText;
 
 pub use ftui_core::terminal_capabilities::TerminalCapabilities;
+pub use ftui_core::terminfo::Terminfo;
 
 /// Size of the internal write buffer (64KB).
 const BUFFER_CAPACITY: usize = 64 * 1024;
 /// Maximum hyperlink URL length allowed in OSC 8 payloads.
 const MAX_SAFE_HYPERLINK_URL_BYTES: usize = 4096;
+/// Terminfo attribute capabilities, in SGR flag order.
+const TERMINFO_ATTRS: [(StyleFlags, &str); 8] = [
+    (StyleFlags::BOLD, "bold"),
+    (StyleFlags::DIM, "dim"),
+    (StyleFlags::ITALIC, "sitm"),
+    (StyleFlags::UNDERLINE, "smul"),
+    (StyleFlags::BLINK, "blink"),
+    (StyleFlags::REVERSE, "rev"),
+    (StyleFlags::HIDDEN, "invis"),
+    (StyleFlags::STRIKETHROUGH, "smxx"),
+];
+
+/// Canonical xterm RGB values for the 16 standard palette entries.
+const TERMINFO_PALETTE16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Map a color to the `setaf`/`setab` parameter for a terminfo entry.
+///
+/// Entries with the `RGB` capability (or 2^24 colors) take the packed
+/// `0xRRGGBB` value; otherwise the nearest palette index for `colors` is used.
+/// Returns `None` when the entry needs SGR truecolor (`Tc`) or has no colors.
+fn terminfo_color_param(info: &Terminfo, color: PackedRgba) -> Option<i32> {
+    let (r, g, b) = (color.r(), color.g(), color.b());
+    let colors = info.max_colors();
+    if info.flag("RGB") || colors >= 1 << 24 {
+        return Some((i32::from(r) << 16) | (i32::from(g) << 8) | i32::from(b));
+    }
+    if info.flag("Tc") {
+        return None;
+    }
+    if colors >= 256 {
+        return Some(i32::from(rgb_to_256_index(r, g, b)));
+    }
+    if colors < 8 {
+        return None;
+    }
+    let limit = colors.min(16) as usize;
+    TERMINFO_PALETTE16[..limit]
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &rgb)| rgb_distance((r, g, b), rgb))
+        .map(|(idx, _)| idx as i32)
+}
+
+/// Nearest xterm 256-color index (6x6x6 cube or grayscale ramp).
+fn rgb_to_256_index(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |v: u8| -> u8 {
+        match v {
+            0..48 => 0,
+            48..115 => 1,
+            _ => (v - 35) / 40,
+        }
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (
+        LEVELS[ri as usize],
+        LEVELS[gi as usize],
+        LEVELS[bi as usize],
+    );
+    let cube_idx = 16 + 36 * ri + 6 * gi + bi;
+    let avg = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+    let gray_step = (avg.saturating_sub(3) / 10).min(23);
+    let gray = 8 + 10 * gray_step;
+    if rgb_distance((r, g, b), (gray, gray, gray)) < rgb_distance((r, g, b), cube) {
+        232 + gray_step
+    } else {
+        cube_idx
+    }
+}
+
+#[inline]
+fn rgb_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| u32::from(x.abs_diff(y)).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
 
 #[inline]
 fn is_safe_hyperlink_url(url: &str) -> bool {
@@ -419,6 +516,15 @@ pub struct Presenter<W: Write> {
     plan_scratch: cost_model::RowPlanScratch,
     /// Reusable buffer for change runs, avoiding per-frame allocation.
     runs_buf: Vec<ChangeRun>,
+    /// Terminfo entry used for cursor and attribute sequences (None = ANSI).
+    terminfo: Option<Box<Terminfo>>,
+    /// Cached DEC line attribute policy for the lifetime of this presenter.
+    line_attributes_enabled: bool,
+    /// Line size of each terminal row (viewport offset applied) as last
+    /// emitted (missing = normal).
+    line_sizes: Vec<LineSize>,
+    /// Cached OSC 66 text sizing policy for the lifetime of this presenter.
+    text_sizing_enabled: bool,
 }
 
 impl<W: Write> Presenter<W> {
@@ -432,12 +538,35 @@ impl<W: Write> Presenter<W> {
             cursor_y: None,
             viewport_offset_y: 0,
             hyperlinks_enabled: capabilities.use_hyperlinks(),
+            line_attributes_enabled: capabilities.use_line_attributes(),
+            text_sizing_enabled: capabilities.use_text_sizing(),
             capabilities,
             plan_scratch: cost_model::RowPlanScratch::default(),
             runs_buf: Vec::new(),
+            terminfo: None,
+            line_sizes: Vec::new(),
         }
     }
 
+    /// Take cursor motion and attribute sequences from a terminfo entry.
+    ///
+    /// In terminfo mode the presenter positions the cursor with `cup`, resets
+    /// with `sgr0`, and sets attributes with `bold`, `dim`, `sitm`, `smul`,
+    /// `blink`, `rev`, `invis` and `smxx`. Attributes the entry lacks are not
+    /// emitted, and style changes always re-establish the full style because
+    /// terminfo has no per-attribute off sequences. Capabilities missing from
+    /// the entry fall back to ANSI. Pass `None` to return to ANSI output.
+    pub fn set_terminfo(&mut self, terminfo: Option<Terminfo>) {
+        self.terminfo = terminfo.map(Box::new);
+        self.invalidate_cursor();
+    }
+
+    /// The terminfo entry in use, if terminfo mode is enabled.
+    #[inline]
+    pub fn terminfo(&self) -> Option<&Terminfo> {
+        self.terminfo.as_deref()
+    }
+
     /// Get mutable access to the innermost writer (`W`).
     ///
     /// This allows the caller to write raw data (e.g. logs) bypassing the
@@ -536,7 +665,9 @@ impl<W: Write> Presenter<W> {
         } else {
             #[cfg(feature = "tracing")]
             tracing::warn!("sync brackets unsupported; falling back to cursor-hide strategy");
-            ansi::cursor_hide(&mut self.writer)?;
+            if !self.write_terminfo("civis", &[])? {
+                ansi::cursor_hide(&mut self.writer)?;
+            }
         }
 
         // Emit diff using run grouping for efficiency.
@@ -548,7 +679,11 @@ impl<W: Write> Presenter<W> {
         let bracket_end_result = if bracket_supported {
             ansi::sync_end(&mut self.writer)
         } else {
-            ansi::cursor_show(&mut self.writer)
+            match self.write_terminfo("cnorm", &[]) {
+                Ok(true) => Ok(()),
+                Ok(false) => ansi::cursor_show(&mut self.writer),
+                Err(err) => Err(err),
+            }
         };
 
         let flush_result = self.writer.flush();
@@ -596,6 +731,9 @@ impl<W: Write> Presenter<W> {
         #[cfg(feature = "tracing")]
         tracing::trace!(run_count = self.runs_buf.len(), "emitting runs (reuse)");
 
+        // Scaled text runs replace the cells of their block when supported.
+        let text_sizing = self.text_sizing_enabled && buffer.has_scaled_text();
+
         // Group runs by row and apply cost model per row
         let mut i = 0;
         while i < self.runs_buf.len() {
@@ -606,6 +744,18 @@ impl<W: Write> Presenter<W> {
             while i < self.runs_buf.len() && self.runs_buf[i].y == row_y {
                 i += 1;
             }
+            // Select the row's line size before planning: the escape needs
+            // the cursor on the row and leaves it at column 0.
+            let line_size = buffer.line_size(row_y);
+            if self.line_attributes_enabled {
+                self.sync_line_size(row_y, line_size)?;
+            }
+            let visible_end = if self.line_attributes_enabled {
+                line_size.visible_columns(buffer.width())
+            } else {
+                buffer.width()
+            };
+
             let row_runs = &self.runs_buf[row_start..i];
 
             let plan = cost_model::plan_row_reuse(
@@ -625,15 +775,27 @@ impl<W: Write> Presenter<W> {
 
             let row = buffer.row_cells(row_y);
             for span in plan.spans() {
+                // Cells past the middle of a scaled row are off-screen; writing
+                // them would wrap onto the next row.
+                if span.x0 >= visible_end {
+                    continue;
+                }
                 self.move_cursor_optimal(span.x0, span.y)?;
                 // Hot path: avoid recomputing `y * width + x` for every cell.
                 let start = span.x0 as usize;
-                let end = span.x1 as usize;
+                let end = span.x1.min(visible_end - 1) as usize;
                 debug_assert!(start <= end);
                 debug_assert!(end < row.len());
                 let mut idx = start;
                 while idx <= end {
                     let cell = &row[idx];
+                    if text_sizing && let Some(run) = buffer.scaled_text_at(idx as u16, row_y) {
+                        if run.is_anchor(idx as u16, row_y) {
+                            self.emit_scaled_text(idx as u16, cell, run, links)?;
+                        }
+                        idx = run.area().right() as usize;
+                        continue;
+                    }
                     self.emit_cell(idx as u16, cell, pool, links)?;
 
                     // Repair invalid wide-char tails.
@@ -651,7 +813,7 @@ impl<W: Write> Presenter<W> {
                     if width > 1 && should_repair_invalid_tail {
                         for off in 1..width {
                             let tx = idx + off;
-                            if tx >= row.len() {
+                            if tx >= row.len() || tx >= visible_end as usize {
                                 break;
                             }
                             if row[tx].is_continuation() {
@@ -676,6 +838,44 @@ impl<W: Write> Presenter<W> {
         Ok(())
     }
 
+    /// Emit a scaled text run from its anchor cell with OSC 66.
+    ///
+    /// The terminal draws the whole block and leaves the cursor on the top
+    /// row just past it; the anchor cell supplies the style and link.
+    fn emit_scaled_text(
+        &mut self,
+        x: u16,
+        cell: &Cell,
+        run: &ScaledText,
+        links: Option<&LinkRegistry>,
+    ) -> io::Result<()> {
+        if self.cursor_x != Some(x)
+            && let Some(y) = self.cursor_y
+        {
+            self.move_cursor_optimal(x, y)?;
+        }
+        self.emit_style_changes(cell)?;
+        self.emit_link_changes(cell, links)?;
+        ansi::text_size(&mut self.writer, run.scale(), run.text())?;
+        self.cursor_x = Some(x.saturating_add(run.area().width));
+        Ok(())
+    }
+
+    /// Emit a DEC line size sequence for row `y` if the terminal row differs.
+    fn sync_line_size(&mut self, y: u16, size: LineSize) -> io::Result<()> {
+        let index = y.saturating_add(self.viewport_offset_y) as usize;
+        if self.line_sizes.get(index).copied().unwrap_or_default() == size {
+            return Ok(());
+        }
+        self.move_cursor_optimal(0, y)?;
+        ansi::line_size(&mut self.writer, size)?;
+        if self.line_sizes.len() <= index {
+            self.line_sizes.resize(index + 1, LineSize::Normal);
+        }
+        self.line_sizes[index] = size;
+        Ok(())
+    }
+
     /// Prepare the runs buffer from a diff.
     ///
     /// Helper for external callers to populate the runs buffer before calling `emit_diff_runs`.
@@ -688,7 +888,7 @@ impl<W: Write> Presenter<W> {
     /// Callers that drive emission manually through `emit_diff_runs` must
     /// invoke this before returning control to non-UI terminal output.
     pub fn finish_frame(&mut self) -> io::Result<()> {
-        let reset_result = ansi::sgr_reset(&mut self.writer);
+        let reset_result = self.sgr_reset();
         self.current_style = None;
 
         let hyperlink_close_result = if self.current_link.is_some() {
@@ -713,7 +913,7 @@ impl<W: Write> Presenter<W> {
 
     /// Best-effort frame cleanup used on error and drop paths.
     pub fn finish_frame_best_effort(&mut self) {
-        let _ = ansi::sgr_reset(&mut self.writer);
+        let _ = self.sgr_reset();
         self.current_style = None;
 
         if self.current_link.is_some() {
@@ -848,6 +1048,10 @@ impl<W: Write> Presenter<W> {
         }
 
         match self.current_style {
+            // Terminfo has no per-attribute off sequences; always rebuild.
+            _ if self.terminfo.is_some() => {
+                self.emit_style_full(new_style)?;
+            }
             None => {
                 // No known style state: re-establish a full terminal style baseline.
                 self.emit_style_full(new_style)?;
@@ -863,19 +1067,68 @@ impl<W: Write> Presenter<W> {
 
     /// Full style apply (reset + set all properties). Used when previous state is unknown.
     fn emit_style_full(&mut self, style: CellStyle) -> io::Result<()> {
-        ansi::sgr_reset(&mut self.writer)?;
-        if style.fg.a() > 0 {
+        self.sgr_reset()?;
+        if style.fg.a() > 0 && !self.write_terminfo_color("setaf", style.fg)? {
             ansi::sgr_fg_packed(&mut self.writer, style.fg)?;
         }
-        if style.bg.a() > 0 {
+        if style.bg.a() > 0 && !self.write_terminfo_color("setab", style.bg)? {
             ansi::sgr_bg_packed(&mut self.writer, style.bg)?;
         }
-        if !style.attrs.is_empty() {
+        if style.attrs.is_empty() {
+            return Ok(());
+        }
+        if self.terminfo.is_some() {
+            for (flag, cap) in TERMINFO_ATTRS {
+                if style.attrs.contains(flag) {
+                    self.write_terminfo(cap, &[])?;
+                }
+            }
+        } else {
             ansi::sgr_flags(&mut self.writer, style.attrs)?;
         }
         Ok(())
     }
 
+    /// Reset all attributes, using `sgr0` in terminfo mode.
+    fn sgr_reset(&mut self) -> io::Result<()> {
+        if self.write_terminfo("sgr0", &[])? {
+            return Ok(());
+        }
+        ansi::sgr_reset(&mut self.writer)
+    }
+
+    /// Write terminfo capability `name` expanded with `params`.
+    ///
+    /// Returns `Ok(false)` when terminfo mode is off or the entry lacks a
+    /// usable capability, so the caller can fall back to ANSI.
+    fn write_terminfo(&mut self, name: &str, params: &[i32]) -> io::Result<bool> {
+        let Some(info) = self.terminfo.as_deref() else {
+            return Ok(false);
+        };
+        match info.expand(name, params) {
+            Ok(seq) => {
+                self.writer.write_all(&seq)?;
+                Ok(true)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Write a color through `setaf`/`setab`, mapped to the entry's palette.
+    ///
+    /// Returns `false` (writing nothing) when terminfo is off, the capability
+    /// is missing, or the entry only advertises 24-bit color through `Tc`,
+    /// whose `setaf` cannot carry RGB; callers then fall back to SGR.
+    fn write_terminfo_color(&mut self, name: &str, color: PackedRgba) -> io::Result<bool> {
+        let Some(info) = self.terminfo.as_deref() else {
+            return Ok(false);
+        };
+        let Some(param) = terminfo_color_param(info, color) else {
+            return Ok(false);
+        };
+        self.write_terminfo(name, &[param])
+    }
+
     #[inline]
     fn dec_len_u8(value: u8) -> u32 {
         if value >= 100 {
@@ -1129,11 +1382,10 @@ impl<W: Write> Presenter<W> {
         }
 
         // Use CUP (cursor position) for absolute positioning
-        ansi::cup(
-            &mut self.writer,
-            y.saturating_add(self.viewport_offset_y),
-            x,
-        )?;
+        let actual_y = y.saturating_add(self.viewport_offset_y);
+        if !self.write_terminfo("cup", &[i32::from(actual_y), i32::from(x)])? {
+            ansi::cup(&mut self.writer, actual_y, x)?;
+        }
         self.cursor_x = Some(x);
         self.cursor_y = Some(y);
         Ok(())
@@ -1153,6 +1405,13 @@ impl<W: Write> Presenter<W> {
         let same_row = self.cursor_y == Some(y);
         let actual_y = y.saturating_add(self.viewport_offset_y);
 
+        // The cost model is calibrated for ANSI; terminfo mode always uses `cup`.
+        if self.write_terminfo("cup", &[i32::from(actual_y), i32::from(x)])? {
+            self.cursor_x = Some(x);
+            self.cursor_y = Some(y);
+            return Ok(());
+        }
+
         if same_row {
             if let Some(cx) = self.cursor_x {
                 if x > cx {
@@ -1203,8 +1462,13 @@ impl<W: Write> Presenter<W> {
 
     /// Clear the entire screen.
     pub fn clear_screen(&mut self) -> io::Result<()> {
-        ansi::erase_display(&mut self.writer, ansi::EraseDisplayMode::All)?;
-        ansi::cup(&mut self.writer, 0, 0)?;
+        // Terminfo `clear` also homes the cursor.
+        if !self.write_terminfo("clear", &[])? {
+            ansi::erase_display(&mut self.writer, ansi::EraseDisplayMode::All)?;
+            ansi::cup(&mut self.writer, 0, 0)?;
+        }
+        // Erasing the display resets every line to single width.
+        self.line_sizes.clear();
         self.cursor_x = Some(0);
         self.cursor_y = Some(0);
         self.writer.flush()
@@ -1219,13 +1483,17 @@ impl<W: Write> Presenter<W> {
 
     /// Hide the cursor.
     pub fn hide_cursor(&mut self) -> io::Result<()> {
-        ansi::cursor_hide(&mut self.writer)?;
+        if !self.write_terminfo("civis", &[])? {
+            ansi::cursor_hide(&mut self.writer)?;
+        }
         self.writer.flush()
     }
 
     /// Show the cursor.
     pub fn show_cursor(&mut self) -> io::Result<()> {
-        ansi::cursor_show(&mut self.writer)?;
+        if !self.write_terminfo("cnorm", &[])? {
+            ansi::cursor_show(&mut self.writer)?;
+        }
         self.writer.flush()
     }
 
@@ -1235,16 +1503,26 @@ impl<W: Write> Presenter<W> {
         self.writer.flush()
     }
 
-    /// Reset the presenter state.
+    /// Forget the cursor position, style and hyperlink state.
     ///
-    /// Useful after resize or when terminal state is unknown.
-    pub fn reset(&mut self) {
+    /// Use between frames when something else moved the cursor or reset
+    /// attributes but the screen contents and line sizes are intact.
+    pub fn invalidate_cursor(&mut self) {
         self.current_style = None;
         self.current_link = None;
         self.cursor_x = None;
         self.cursor_y = None;
     }
 
+    /// Reset the presenter state.
+    ///
+    /// Useful after resize or when terminal state is unknown. Also assumes
+    /// every terminal row is back to single width, as after ED2 or a resize.
+    pub fn reset(&mut self) {
+        self.invalidate_cursor();
+        self.line_sizes.clear();
+    }
+
     /// Flush any buffered output.
     pub fn flush(&mut self) -> io::Result<()> {
         self.writer.flush()
@@ -1266,6 +1544,7 @@ mod tests {
     use super::*;
     use crate::cell::{CellAttrs, CellContent};
     use crate::link_registry::LinkRegistry;
+    use crate::terminal_model::TerminalModel;
 
     fn test_presenter() -> Presenter<Vec<u8>> {
         let caps = TerminalCapabilities::basic();
@@ -1446,6 +1725,154 @@ mod tests {
         );
     }
 
+    fn test_presenter_with_line_attributes() -> Presenter<Vec<u8>> {
+        let mut caps = TerminalCapabilities::basic();
+        caps.line_attributes = true;
+        Presenter::new(Vec::new(), caps)
+    }
+
+    #[test]
+    fn double_width_row_emits_line_attribute() {
+        let mut presenter = test_presenter_with_line_attributes();
+        let mut buffer = Buffer::new(6, 2);
+        for (x, ch) in "ABCDEF".chars().enumerate() {
+            buffer.set_raw(x as u16, 0, Cell::from_char(ch));
+        }
+        buffer.set_line_size(0, LineSize::DoubleWidth);
+
+        let old = Buffer::new(6, 2);
+        let diff = BufferDiff::compute(&old, &buffer);
+        presenter.present(&buffer, &diff).unwrap();
+        let output = get_output(presenter);
+
+        assert!(output.windows(3).any(|w| w == b"\x1b#6"));
+        let mut model = TerminalModel::new(6, 2);
+        model.process(&output);
+        assert_eq!(model.line_size(0), LineSize::DoubleWidth);
+        assert_eq!(model.row_text(0).as_deref(), Some("ABC"));
+        assert_eq!(model.row_text(1).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn line_attribute_reset_when_row_returns_to_normal() {
+        let mut presenter = test_presenter_with_line_attributes();
+        let mut scaled = Buffer::new(4, 1);
+        scaled.set_raw(0, 0, Cell::from_char('X'));
+        scaled.set_line_size(0, LineSize::DoubleHeightTop);
+        let blank = Buffer::new(4, 1);
+        presenter
+            .present(&scaled, &BufferDiff::compute(&blank, &scaled))
+            .unwrap();
+
+        let mut normal = Buffer::new(4, 1);
+        normal.set_raw(0, 0, Cell::from_char('X'));
+        presenter
+            .present(&normal, &BufferDiff::compute(&scaled, &normal))
+            .unwrap();
+        let output = get_output(presenter);
+
+        let mut model = TerminalModel::new(4, 1);
+        model.process(&output);
+        assert_eq!(model.line_size(0), LineSize::Normal);
+        assert!(output.windows(3).any(|w| w == b"\x1b#5"));
+    }
+
+    #[test]
+    fn line_attributes_not_emitted_without_support() {
+        let mut presenter = test_presenter();
+        let mut buffer = Buffer::new(4, 1);
+        buffer.set_raw(0, 0, Cell::from_char('X'));
+        buffer.set_line_size(0, LineSize::DoubleWidth);
+
+        let old = Buffer::new(4, 1);
+        let diff = BufferDiff::compute(&old, &buffer);
+        presenter.present(&buffer, &diff).unwrap();
+        let output = get_output(presenter);
+
+        assert!(!output.windows(2).any(|w| w == b"\x1b#"));
+    }
+
+    #[test]
+    fn scaled_text_emitted_once_from_anchor() {
+        let mut caps = TerminalCapabilities::basic();
+        caps.text_sizing = true;
+        let mut presenter = Presenter::new(Vec::new(), caps);
+        let mut pool = GraphemePool::new();
+        let mut frame = crate::frame::Frame::new(8, 3, &mut pool);
+        frame.print_scaled_text(1, 0, "Hi", 2, Cell::default());
+        frame.buffer.set_raw(6, 1, Cell::from_char('z'));
+        let buffer = frame.buffer;
+
+        let old = Buffer::new(8, 3);
+        let diff = BufferDiff::compute(&old, &buffer);
+        presenter.present(&buffer, &diff).unwrap();
+        let output = get_output(presenter);
+
+        let osc = b"\x1b]66;s=2;Hi\x1b\\";
+        assert_eq!(output.windows(osc.len()).filter(|w| w == osc).count(), 1);
+        let mut model = TerminalModel::new(8, 3);
+        model.process(&output);
+        assert_eq!(model.scaled_texts().len(), 1);
+        assert_eq!(
+            model.scaled_texts()[0].area(),
+            ftui_core::geometry::Rect::new(1, 0, 4, 2)
+        );
+        assert_eq!(model.cell(6, 1).unwrap().text, "z");
+    }
+
+    #[test]
+    fn overlay_over_scaled_text_replaces_run() {
+        let mut caps = TerminalCapabilities::basic();
+        caps.text_sizing = true;
+        let mut pool = GraphemePool::new();
+        let mut frame = crate::frame::Frame::new(8, 3, &mut pool);
+        frame.print_scaled_text(1, 0, "Hi", 2, Cell::default());
+        let old = frame.buffer.clone();
+        frame.buffer.fill(
+            ftui_core::geometry::Rect::new(2, 0, 3, 2),
+            Cell::from_char('#'),
+        );
+        let buffer = frame.buffer;
+
+        let mut presenter = Presenter::new(Vec::new(), caps);
+        let diff = BufferDiff::compute(&old, &buffer);
+        presenter.present(&buffer, &diff).unwrap();
+        let output = get_output(presenter);
+
+        assert!(!output.windows(4).any(|w| w == b"]66;"));
+        let mut model = TerminalModel::new(8, 3);
+        model.process(&output);
+        assert_eq!(model.cell(2, 0).unwrap().text, "#");
+        assert_eq!(model.cell(4, 1).unwrap().text, "#");
+    }
+
+    #[test]
+    fn scaled_text_falls_back_to_bold_without_support() {
+        let mut presenter = test_presenter();
+        let mut pool = GraphemePool::new();
+        let mut frame = crate::frame::Frame::new(8, 3, &mut pool);
+        frame.print_scaled_text(0, 0, "Hi", 2, Cell::default());
+        let buffer = frame.buffer;
+
+        let old = Buffer::new(8, 3);
+        let diff = BufferDiff::compute(&old, &buffer);
+        presenter.present(&buffer, &diff).unwrap();
+        let output = get_output(presenter);
+
+        assert!(!output.windows(4).any(|w| w == b"]66;"));
+        let mut model = TerminalModel::new(8, 3);
+        model.process(&output);
+        assert_eq!(model.row_text(0).as_deref(), Some("Hi"));
+        assert!(
+            model
+                .cell(0, 0)
+                .unwrap()
+                .attrs
+                .flags()
+                .contains(StyleFlags::BOLD)
+        );
+    }
+
     #[test]
     fn hyperlink_sequences_emitted_and_closed() {
         let mut presenter = test_presenter_with_hyperlinks();
@@ -1625,6 +2052,110 @@ mod tests {
         assert!(output_str.contains("\x1b[?25h")); // Show
     }
 
+    fn odd_terminfo() -> Terminfo {
+        // A non-xterm terminal: VT52-style addressing and private attributes.
+        Terminfo::default()
+            .with_string("cup", "\x1bY%p1%' '%+%c%p2%' '%+%c")
+            .with_string("sgr0", "<N>")
+            .with_string("bold", "<B>")
+            .with_string("civis", "<H>")
+            .with_string("cnorm", "<S>")
+    }
+
+    #[test]
+    fn terminfo_mode_uses_entry_sequences() {
+        let mut presenter = test_presenter();
+        presenter.set_terminfo(Some(odd_terminfo()));
+        let mut buffer = Buffer::new(10, 3);
+        let bold_italic = CellAttrs::new(StyleFlags::BOLD | StyleFlags::ITALIC, 0);
+        let cell = Cell::from_char('X')
+            .with_fg(PackedRgba::TRANSPARENT)
+            .with_attrs(bold_italic);
+        buffer.set_raw(2, 1, cell);
+
+        let diff = BufferDiff::compute(&Buffer::new(10, 3), &buffer);
+        presenter.present(&buffer, &diff).unwrap();
+        let output = String::from_utf8_lossy(&get_output(presenter)).into_owned();
+
+        assert!(
+            output.starts_with("<H>"),
+            "cursor hidden via civis: {output:?}"
+        );
+        assert!(
+            output.ends_with("<S>"),
+            "cursor shown via cnorm: {output:?}"
+        );
+        assert!(output.contains("\x1bY!\"<N><B>X"), "got {output:?}");
+        // No xterm CUP or SGR, and italic is skipped because `sitm` is absent.
+        assert!(!output.contains("\x1b["), "got {output:?}");
+    }
+
+    #[test]
+    fn terminfo_mode_falls_back_to_ansi_for_missing_caps() {
+        let mut presenter = test_presenter();
+        presenter.set_terminfo(Some(Terminfo::default().with_string("sgr0", "<N>")));
+        presenter.position_cursor(3, 0).unwrap();
+        presenter.set_terminfo(None);
+        assert!(presenter.terminfo().is_none());
+        presenter.position_cursor(4, 0).unwrap();
+        let output = String::from_utf8_lossy(&get_output(presenter)).into_owned();
+        assert_eq!(output, "\x1b[1;4H\x1b[1;5H");
+    }
+
+    #[test]
+    fn terminfo_mode_emits_colors_via_setaf_setab() {
+        let mut presenter = test_presenter();
+        presenter.set_terminfo(Some(
+            odd_terminfo()
+                .with_number("colors", 256)
+                .with_string("setaf", "<F%p1%d>")
+                .with_string("setab", "<K%p1%d>"),
+        ));
+        let mut buffer = Buffer::new(4, 1);
+        let cell = Cell::from_char('X')
+            .with_fg(PackedRgba::rgb(255, 0, 0))
+            .with_bg(PackedRgba::rgb(128, 128, 128));
+        buffer.set_raw(0, 0, cell);
+
+        let diff = BufferDiff::compute(&Buffer::new(4, 1), &buffer);
+        presenter.present(&buffer, &diff).unwrap();
+        let output = String::from_utf8_lossy(&get_output(presenter)).into_owned();
+
+        assert!(output.contains("<N><F196><K244>X"), "got {output:?}");
+        assert!(!output.contains("\x1b["), "got {output:?}");
+    }
+
+    #[test]
+    fn terminfo_color_param_follows_entry_palette() {
+        let red = PackedRgba::rgb(250, 10, 10);
+        let direct = Terminfo::default().with_flag("RGB");
+        assert_eq!(terminfo_color_param(&direct, red), Some(0xFA0A0A));
+        let eight = Terminfo::default().with_number("colors", 8);
+        assert_eq!(terminfo_color_param(&eight, red), Some(1));
+        let sixteen = Terminfo::default().with_number("colors", 16);
+        assert_eq!(terminfo_color_param(&sixteen, red), Some(9));
+        // `Tc` entries keep SGR truecolor; monochrome entries have no palette.
+        let tc = Terminfo::default()
+            .with_flag("Tc")
+            .with_number("colors", 256);
+        assert_eq!(terminfo_color_param(&tc, red), None);
+        assert_eq!(terminfo_color_param(&Terminfo::default(), red), None);
+    }
+
+    #[test]
+    fn terminfo_mode_falls_back_to_sgr_without_setaf() {
+        let mut presenter = test_presenter();
+        presenter.set_terminfo(Some(odd_terminfo().with_number("colors", 256)));
+        let mut buffer = Buffer::new(4, 1);
+        buffer.set_raw(0, 0, Cell::from_char('X').with_fg(PackedRgba::rgb(1, 2, 3)));
+
+        let diff = BufferDiff::compute(&Buffer::new(4, 1), &buffer);
+        presenter.present(&buffer, &diff).unwrap();
+        let output = String::from_utf8_lossy(&get_output(presenter)).into_owned();
+
+        assert!(output.contains("<N>\x1b[38;2;1;2;3mX"), "got {output:?}");
+    }
+
     #[test]
     fn reset_clears_state() {
         let mut presenter = test_presenter();
@@ -14,6 +14,10 @@
 //! - Erase operations (EL, ED)
 //! - OSC 8 hyperlinks
 //! - DEC 2026 synchronized output (tracked but visual effects ignored)
+//! - DEC line size attributes (`ESC # 3/4/5/6`): scaled rows hold half as
+//!   many columns
+//! - OSC 66 scaled text: the block is reserved, its text is stored in the
+//!   top-left cell, and the run is listed by [`TerminalModel::scaled_texts`]
 //!
 //! # Usage
 //!
@@ -28,6 +32,8 @@
 use crate::{
     cell::{CellAttrs, PackedRgba, StyleFlags},
     char_width,
+    line_size::LineSize,
+    text_sizing::ScaledText,
 };
 
 /// A single cell in the terminal model grid.
@@ -123,6 +129,7 @@ impl ModeFlags {
 enum ParseState {
     Ground,
     Escape,
+    EscapeHash,
     CsiEntry,
     CsiParam,
     OscEntry,
@@ -138,6 +145,10 @@ pub struct TerminalModel {
     width: usize,
     height: usize,
     cells: Vec<ModelCell>,
+    /// DEC line size per row.
+    line_sizes: Vec<LineSize>,
+    /// OSC 66 scaled text runs currently on screen.
+    scaled_texts: Vec<ScaledText>,
     cursor_x: usize,
     cursor_y: usize,
     sgr: SgrState,
@@ -174,6 +185,8 @@ impl TerminalModel {
             width,
             height,
             cells,
+            line_sizes: vec![LineSize::Normal; height],
+            scaled_texts: Vec::new(),
             cursor_x: 0,
             cursor_y: 0,
             sgr: SgrState::default(),
@@ -239,6 +252,23 @@ impl TerminalModel {
         }
     }
 
+    /// Get the DEC line size of row `y` (normal when out of bounds).
+    #[must_use]
+    pub fn line_size(&self, y: usize) -> LineSize {
+        self.line_sizes.get(y).copied().unwrap_or_default()
+    }
+
+    /// OSC 66 scaled text runs currently on screen, in the order drawn.
+    #[must_use]
+    pub fn scaled_texts(&self) -> &[ScaledText] {
+        &self.scaled_texts
+    }
+
+    /// Number of addressable columns on row `y`.
+    fn line_columns(&self, y: usize) -> usize {
+        self.line_size(y).visible_columns(self.width as u16) as usize
+    }
+
     /// Get the current cell under the cursor.
     #[must_use]
     pub fn current_cell(&self) -> Option<&ModelCell> {
@@ -289,6 +319,8 @@ impl TerminalModel {
     /// Reset the terminal model to initial state.
     pub fn reset(&mut self) {
         self.cells.fill(ModelCell::default());
+        self.line_sizes.fill(LineSize::Normal);
+        self.scaled_texts.clear();
         self.cursor_x = 0;
         self.cursor_y = 0;
         self.sgr = SgrState::default();
@@ -318,6 +350,7 @@ impl TerminalModel {
         match self.parse_state {
             ParseState::Ground => self.ground_state(b),
             ParseState::Escape => self.escape_state(b),
+            ParseState::EscapeHash => self.escape_hash_state(b),
             ParseState::CsiEntry => self.csi_entry_state(b),
             ParseState::CsiParam => self.csi_param_state(b),
             ParseState::OscEntry => self.osc_entry_state(b),
@@ -369,6 +402,10 @@ impl TerminalModel {
                 // Application/Normal keypad mode (ignored)
                 self.parse_state = ParseState::Ground;
             }
+            b'#' => {
+                // DEC line attributes / screen alignment
+                self.parse_state = ParseState::EscapeHash;
+            }
             0x1B => {
                 // ESC ESC - stay in escape (malformed, but handle gracefully)
             }
@@ -379,6 +416,33 @@ impl TerminalModel {
         }
     }
 
+    fn escape_hash_state(&mut self, b: u8) {
+        self.parse_state = ParseState::Ground;
+        if let Some(size) = LineSize::from_escape_final(b) {
+            self.set_line_size(self.cursor_y, size);
+        }
+    }
+
+    /// Apply a DEC line size to row `y`.
+    ///
+    /// Like a VT terminal, switching to a scaled size discards the cells that
+    /// no longer fit and clamps the cursor to the new right margin.
+    fn set_line_size(&mut self, y: usize, size: LineSize) {
+        let Some(slot) = self.line_sizes.get_mut(y) else {
+            return;
+        };
+        *slot = size;
+        let columns = self.line_columns(y);
+        for x in columns..self.width {
+            if let Some(cell) = self.cell_mut(x, y) {
+                *cell = ModelCell::default();
+            }
+        }
+        if self.cursor_y == y {
+            self.cursor_x = self.cursor_x.min(columns - 1);
+        }
+    }
+
     fn csi_entry_state(&mut self, b: u8) {
         match b {
             b'0'..=b'9' => {
@@ -596,7 +660,12 @@ impl TerminalModel {
             return;
         }
 
-        if self.cursor_x < self.width && self.cursor_y < self.height {
+        if self.cursor_x < self.line_columns(self.cursor_y) && self.cursor_y < self.height {
+            // Overwriting any cell of a scaled run erases the whole run.
+            if !self.scaled_texts.is_empty() {
+                let (x, y) = (self.cursor_x as u16, self.cursor_y as u16);
+                self.scaled_texts.retain(|run| !run.contains(x, y));
+            }
             let cell = &mut self.cells[self.cursor_y * self.width + self.cursor_x];
             cell.text = ch.to_string();
             cell.fg = self.sgr.fg;
@@ -605,7 +674,7 @@ impl TerminalModel {
             cell.link_id = self.current_link_id;
 
             // Handle wide characters (clear the next cell if it exists)
-            if width == 2 && self.cursor_x + 1 < self.width {
+            if width == 2 && self.cursor_x + 1 < self.line_columns(self.cursor_y) {
                 let next_cell = &mut self.cells[self.cursor_y * self.width + self.cursor_x + 1];
                 next_cell.text = String::new(); // Clear content (placeholder)
                 next_cell.fg = self.sgr.fg; // Extend background color
@@ -618,7 +687,7 @@ impl TerminalModel {
         self.cursor_x += width;
 
         // Handle line wrap if at edge
-        if self.cursor_x >= self.width {
+        if self.cursor_x >= self.line_columns(self.cursor_y) {
             self.cursor_x = 0;
             if self.cursor_y + 1 < self.height {
                 self.cursor_y += 1;
@@ -661,7 +730,7 @@ impl TerminalModel {
         let row = self.csi_params.first().copied().unwrap_or(1).max(1) as usize;
         let col = self.csi_params.get(1).copied().unwrap_or(1).max(1) as usize;
         self.cursor_y = (row - 1).min(self.height - 1);
-        self.cursor_x = (col - 1).min(self.width - 1);
+        self.cursor_x = (col - 1).min(self.line_columns(self.cursor_y) - 1);
     }
 
     fn csi_cuu(&mut self) {
@@ -676,7 +745,7 @@ impl TerminalModel {
 
     fn csi_cuf(&mut self) {
         let n = self.csi_params.first().copied().unwrap_or(1).max(1) as usize;
-        self.cursor_x = (self.cursor_x + n).min(self.width - 1);
+        self.cursor_x = (self.cursor_x + n).min(self.line_columns(self.cursor_y) - 1);
     }
 
     fn csi_cub(&mut self) {
@@ -686,12 +755,13 @@ impl TerminalModel {
 
     fn csi_cha(&mut self) {
         let col = self.csi_params.first().copied().unwrap_or(1).max(1) as usize;
-        self.cursor_x = (col - 1).min(self.width - 1);
+        self.cursor_x = (col - 1).min(self.line_columns(self.cursor_y) - 1);
     }
 
     fn csi_vpa(&mut self) {
         let row = self.csi_params.first().copied().unwrap_or(1).max(1) as usize;
         self.cursor_y = (row - 1).min(self.height - 1);
+        self.cursor_x = self.cursor_x.min(self.line_columns(self.cursor_y) - 1);
     }
 
     fn csi_ed(&mut self) {
@@ -706,6 +776,8 @@ impl TerminalModel {
                     for x in 0..self.width {
                         self.erase_cell(x, y);
                     }
+                    // Fully erased lines become single width.
+                    self.line_sizes[y] = LineSize::Normal;
                 }
             }
             1 => {
@@ -714,6 +786,7 @@ impl TerminalModel {
                     for x in 0..self.width {
                         self.erase_cell(x, y);
                     }
+                    self.line_sizes[y] = LineSize::Normal;
                 }
                 for x in 0..=self.cursor_x {
                     self.erase_cell(x, self.cursor_y);
@@ -724,6 +797,8 @@ impl TerminalModel {
                 for cell in &mut self.cells {
                     *cell = ModelCell::default();
                 }
+                self.line_sizes.fill(LineSize::Normal);
+                self.scaled_texts.clear();
             }
             _ => {}
         }
@@ -936,12 +1011,62 @@ impl TerminalModel {
         let mut parts = data.splitn(2, ';');
         let code: u32 = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
 
-        // OSC 8 - hyperlink (other OSC codes ignored)
-        if code == 8
-            && let Some(rest) = parts.next()
-        {
-            let rest = rest.to_string();
-            self.handle_osc8(&rest);
+        // OSC 8 - hyperlink, OSC 66 - scaled text (other OSC codes ignored)
+        match (code, parts.next()) {
+            (8, Some(rest)) => {
+                let rest = rest.to_string();
+                self.handle_osc8(&rest);
+            }
+            (66, Some(rest)) => {
+                let rest = rest.to_string();
+                self.handle_osc66(&rest);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_osc66(&mut self, payload: &str) {
+        // Format: OSC 66 ; metadata ; text ST
+        let (x0, y0) = (self.cursor_x, self.cursor_y);
+        let Some(run) = ScaledText::parse_osc(x0 as u16, y0 as u16, payload) else {
+            return;
+        };
+        let area = run.area();
+        let right = (area.right() as usize).min(self.line_columns(y0));
+        let bottom = (area.bottom() as usize).min(self.height);
+        self.scaled_texts
+            .retain(|other| other.area().intersection_opt(&area).is_none());
+
+        for y in y0..bottom {
+            for x in x0..right {
+                let (fg, bg, flags, link) = (
+                    self.sgr.fg,
+                    self.sgr.bg,
+                    self.sgr.flags,
+                    self.current_link_id,
+                );
+                if let Some(cell) = self.cell_mut(x, y) {
+                    cell.text = if (x, y) == (x0, y0) {
+                        run.text().to_string()
+                    } else {
+                        String::new()
+                    };
+                    cell.fg = fg;
+                    cell.bg = bg;
+                    cell.attrs = CellAttrs::new(flags, link);
+                    cell.link_id = link;
+                }
+            }
+        }
+        self.scaled_texts.push(run);
+
+        // The cursor ends on the top row just past the block.
+        self.cursor_x = right;
+        if self.cursor_x >= self.line_columns(y0) {
+            self.cursor_x = 0;
+            if self.cursor_y + 1 < self.height {
+                self.cursor_y += 1;
+            }
         }
     }
 
@@ -1171,6 +1296,32 @@ mod tests {
         assert_eq!(cell_after.link_id, 0);
     }
 
+    #[test]
+    fn osc66_reserves_scaled_block() {
+        let mut model = TerminalModel::new(10, 4);
+        model.process(b"\x1b[2;2H\x1b]66;s=2;Hi\x1b\\!");
+
+        let runs = model.scaled_texts();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].area(), ftui_core::geometry::Rect::new(1, 1, 4, 2));
+        assert_eq!(model.cell(1, 1).unwrap().text, "Hi");
+        assert_eq!(model.cell(2, 2).unwrap().text, "");
+        assert_eq!(model.cell(5, 1).unwrap().text, "!");
+        assert_eq!(model.cursor(), (6, 1));
+    }
+
+    #[test]
+    fn overwriting_scaled_block_removes_run() {
+        let mut model = TerminalModel::new(10, 4);
+        model.process(b"\x1b]66;s=2;ab\x07");
+        assert_eq!(model.scaled_texts().len(), 1);
+        model.process(b"\x1b[2;3Hx");
+        assert!(model.scaled_texts().is_empty());
+
+        model.process(b"\x1b[1;1H\x1b]66;s=3;a\x07\x1b[2J");
+        assert!(model.scaled_texts().is_empty());
+    }
+
     #[test]
     fn dangling_link_detection() {
         let mut model = TerminalModel::new(20, 5);
@@ -1226,6 +1377,53 @@ mod tests {
         assert_eq!(model.cursor(), (3, 1));
     }
 
+    #[test]
+    fn double_width_line_wraps_at_half_width() {
+        let mut model = TerminalModel::new(6, 3);
+        model.process(b"\x1b#6ABCDE");
+        assert_eq!(model.line_size(0), LineSize::DoubleWidth);
+        assert_eq!(model.row_text(0), Some("ABC".to_string()));
+        assert_eq!(model.row_text(1), Some("DE".to_string()));
+        assert_eq!(model.line_size(1), LineSize::Normal);
+    }
+
+    #[test]
+    fn scaling_a_line_discards_cells_past_half_width() {
+        let mut model = TerminalModel::new(6, 2);
+        model.process(b"ABCDEF\x1b[1;6H\x1b#3");
+        assert_eq!(model.line_size(0), LineSize::DoubleHeightTop);
+        assert_eq!(model.row_text(0), Some("ABC".to_string()));
+        assert_eq!(model.cursor(), (2, 0));
+
+        model.process(b"\x1b[1;6H");
+        assert_eq!(model.cursor(), (2, 0));
+        model.process(b"\x1b#5\x1b[1;6H");
+        assert_eq!(model.line_size(0), LineSize::Normal);
+        assert_eq!(model.cursor(), (5, 0));
+    }
+
+    #[test]
+    fn erase_display_resets_line_sizes() {
+        let mut model = TerminalModel::new(6, 3);
+        model.process(b"\x1b#6\n\x1b#3\n\x1b#4");
+        model.process(b"\x1b[2;1H\x1b[0J");
+        assert_eq!(model.line_size(0), LineSize::DoubleWidth);
+        assert_eq!(model.line_size(1), LineSize::DoubleHeightTop);
+        assert_eq!(model.line_size(2), LineSize::Normal);
+
+        model.process(b"\x1b[2J");
+        assert_eq!(model.line_size(0), LineSize::Normal);
+        assert_eq!(model.line_size(1), LineSize::Normal);
+    }
+
+    #[test]
+    fn unknown_hash_sequence_is_ignored() {
+        let mut model = TerminalModel::new(6, 2);
+        model.process(b"\x1b#8AB");
+        assert_eq!(model.line_size(0), LineSize::Normal);
+        assert_eq!(model.row_text(0), Some("AB".to_string()));
+    }
+
     #[test]
     fn cr_lf_handling() {
         let mut model = TerminalModel::new(20, 5);
@@ -0,0 +1,177 @@
+#![forbid(unsafe_code)]
+
+//! Kitty text sizing protocol (OSC 66).
+//!
+//! Terminals that implement the protocol draw a run of text at an integer
+//! scale: at scale `s` every cell of the run becomes an `s`×`s` block, so a
+//! string `w` columns wide covers `w * s` columns and `s` rows. This is a good
+//! fit for headings and large dashboard numbers.
+//!
+//! A [`ScaledText`] is recorded on the [`Buffer`](crate::buffer::Buffer)
+//! alongside the cells it covers. The cells hold the fallback rendering (the
+//! text in bold at normal size on the top row of the block, the rest of the
+//! block blank), so a buffer is always presentable. When the terminal supports
+//! OSC 66, the presenter emits the run once from its anchor cell and skips the
+//! rest of the block.
+//!
+//! ```text
+//! OSC 66 ; s=<scale> ; <text> ST
+//! ```
+
+use ftui_core::geometry::Rect;
+
+use crate::display_width;
+
+/// A run of text drawn at an integer scale.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScaledText {
+    x: u16,
+    y: u16,
+    scale: u8,
+    columns: u16,
+    text: String,
+}
+
+impl ScaledText {
+    /// Largest scale the protocol allows.
+    pub const MAX_SCALE: u8 = 7;
+
+    /// Create a run anchored at `(x, y)`.
+    ///
+    /// `scale` is clamped to `1..=MAX_SCALE`. Control characters are dropped
+    /// from `text`, since they cannot appear inside an OSC payload.
+    #[must_use]
+    pub fn new(x: u16, y: u16, text: &str, scale: u8) -> Self {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        let columns = display_width(&text).min(u16::MAX as usize) as u16;
+        Self {
+            x,
+            y,
+            scale: scale.clamp(1, Self::MAX_SCALE),
+            columns,
+            text,
+        }
+    }
+
+    /// Column of the top-left cell.
+    #[inline]
+    #[must_use]
+    pub const fn x(&self) -> u16 {
+        self.x
+    }
+
+    /// Row of the top-left cell.
+    #[inline]
+    #[must_use]
+    pub const fn y(&self) -> u16 {
+        self.y
+    }
+
+    /// Scale factor (1 = normal size).
+    #[inline]
+    #[must_use]
+    pub const fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Display width of the text at normal size.
+    #[inline]
+    #[must_use]
+    pub const fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    /// The text of the run.
+    #[inline]
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The block of cells the scaled text covers.
+    #[must_use]
+    pub fn area(&self) -> Rect {
+        let scale = u16::from(self.scale);
+        Rect::new(self.x, self.y, self.columns.saturating_mul(scale), scale)
+    }
+
+    /// Whether `(x, y)` is the anchor cell the run is emitted from.
+    #[inline]
+    #[must_use]
+    pub const fn is_anchor(&self, x: u16, y: u16) -> bool {
+        self.x == x && self.y == y
+    }
+
+    /// Whether the block covers `(x, y)`.
+    #[must_use]
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        self.area().contains(x, y)
+    }
+
+    /// Parse an OSC 66 payload (`metadata ; text`, without the leading `66;`).
+    ///
+    /// Only the `s` (scale) and `w` (width) keys are interpreted; a non-zero
+    /// width overrides the measured width of the text. Returns `None` when
+    /// the payload has no text separator.
+    #[must_use]
+    pub fn parse_osc(x: u16, y: u16, payload: &str) -> Option<Self> {
+        let (metadata, text) = payload.split_once(';')?;
+        let mut scale = 1u8;
+        let mut width = 0u16;
+        for pair in metadata.split(':') {
+            match pair.split_once('=') {
+                Some(("s", value)) => scale = value.parse().unwrap_or(1),
+                Some(("w", value)) => width = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+        let mut run = Self::new(x, y, text, scale);
+        if width > 0 {
+            run.columns = width;
+        }
+        Some(run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area_covers_scaled_block() {
+        let run = ScaledText::new(2, 1, "Hi", 3);
+        assert_eq!(run.area(), Rect::new(2, 1, 6, 3));
+        assert!(run.contains(7, 3));
+        assert!(!run.contains(8, 1));
+        assert!(run.is_anchor(2, 1));
+        assert!(!run.is_anchor(3, 1));
+    }
+
+    #[test]
+    fn scale_is_clamped_and_controls_dropped() {
+        let run = ScaledText::new(0, 0, "a\x1bb\x07", 9);
+        assert_eq!(run.scale(), ScaledText::MAX_SCALE);
+        assert_eq!(run.text(), "ab");
+        assert_eq!(ScaledText::new(0, 0, "x", 0).scale(), 1);
+    }
+
+    #[test]
+    fn wide_text_is_measured_in_cells() {
+        let run = ScaledText::new(0, 0, "中a", 2);
+        assert_eq!(run.columns(), 3);
+        assert_eq!(run.area(), Rect::new(0, 0, 6, 2));
+    }
+
+    #[test]
+    fn parse_osc_reads_scale_and_width() {
+        let run = ScaledText::parse_osc(1, 2, "s=2:w=3;ab").unwrap();
+        assert_eq!(run.scale(), 2);
+        assert_eq!(run.columns(), 3);
+        assert_eq!(run.text(), "ab");
+        assert_eq!(run.area(), Rect::new(1, 2, 6, 2));
+
+        let plain = ScaledText::parse_osc(0, 0, ";ab").unwrap();
+        assert_eq!(plain.scale(), 1);
+        assert!(ScaledText::parse_osc(0, 0, "s=2").is_none());
+    }
+}
@@ -0,0 +1,394 @@
+#![forbid(unsafe_code)]
+
+//! Region-level damage tracking for partial view rendering.
+//!
+//! By default the runtime calls [`Model::view`] for every frame and the diff
+//! engine discovers what changed afterwards. Models with large, mostly static
+//! screens can do better: they record which areas their last update touched
+//! and the runtime re-renders only those areas on top of the previously
+//! presented buffer.
+//!
+//! # Protocol
+//!
+//! 1. The model owns a [`DamageTracker`] and calls [`DamageTracker::invalidate`]
+//!    (or [`DamageTracker::invalidate_named`]) from `update`.
+//! 2. The runtime calls [`Model::take_damage`] before rendering.
+//! 3. For [`Damage::Regions`], each region is restored from the previous frame
+//!    minus the damaged cells, which are cleared and handed to
+//!    [`Model::view_region`] with a scissor clipped to the region.
+//! 4. For [`Damage::Full`] (the default), `view` runs as usual.
+//!
+//! The runtime falls back to a full view whenever it has no trustworthy
+//! previous frame (first frame, resize, screen-mode change, failed or skipped
+//! present, a too-small fallback frame, or a frame that recorded hit
+//! regions).
+//!
+//! # Invariants
+//!
+//! - A fresh tracker reports [`Damage::Full`], so the first frame is complete.
+//! - Invalidating an unknown named region degrades to full damage instead of
+//!   leaving stale cells on screen.
+//! - Regions never split a wide glyph: damaged spans are widened to cover any
+//!   wide character that straddles their edges.
+//! - Only damaged areas are rendered, so any layout state a widget records
+//!   while rendering is refreshed only there. A widget that moved must be
+//!   covered by the damage even if its cells look the same.
+//! - Hit regions are not copied from the previous frame. A frame that
+//!   enables hit testing is always followed by a full view, so the hit grid
+//!   never covers only the damaged areas.
+//!
+//! Stale-region bugs (forgetting to invalidate something that changed) can be
+//! caught in tests with `ftui_harness::damage_audit`.
+//!
+//! [`Model::view`]: crate::program::Model::view
+//! [`Model::take_damage`]: crate::program::Model::take_damage
+//! [`Model::view_region`]: crate::program::Model::view_region
+
+use std::collections::HashMap;
+
+use ftui_core::geometry::Rect;
+use ftui_render::buffer::Buffer;
+use ftui_render::cell::Cell;
+use ftui_render::frame::Frame;
+use ftui_render::text_sizing::ScaledText;
+
+use crate::program::Model;
+
+/// Areas of the screen that must be re-rendered for the next frame.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Damage {
+    /// Everything is stale; call [`Model::view`](crate::program::Model::view).
+    #[default]
+    Full,
+    /// Only these areas are stale. An empty list means nothing changed.
+    Regions(Vec<Rect>),
+}
+
+impl Damage {
+    /// Whether this damage requires a full re-render.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        matches!(self, Self::Full)
+    }
+
+    /// Whether nothing needs to be re-rendered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Regions(regions) if regions.is_empty())
+    }
+}
+
+/// Accumulates damaged areas between frames.
+///
+/// Starts fully damaged so the first frame is rendered with `view`.
+#[derive(Debug, Clone)]
+pub struct DamageTracker {
+    full: bool,
+    regions: Vec<Rect>,
+    named: HashMap<String, Rect>,
+}
+
+impl Default for DamageTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DamageTracker {
+    /// Create a tracker that reports full damage until first taken.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            full: true,
+            regions: Vec::new(),
+            named: HashMap::new(),
+        }
+    }
+
+    /// Mark an area as stale. Empty rectangles are ignored.
+    pub fn invalidate(&mut self, rect: Rect) {
+        if self.full || rect.is_empty() {
+            return;
+        }
+        self.regions.push(rect);
+    }
+
+    /// Mark the whole screen as stale.
+    pub fn invalidate_all(&mut self) {
+        self.full = true;
+        self.regions.clear();
+    }
+
+    /// Register (or move) a named region, typically from `view` or after a
+    /// layout pass. The region itself is not invalidated.
+    pub fn define_region(&mut self, name: impl Into<String>, rect: Rect) {
+        self.named.insert(name.into(), rect);
+    }
+
+    /// Forget a named region.
+    pub fn remove_region(&mut self, name: &str) {
+        self.named.remove(name);
+    }
+
+    /// Look up a named region.
+    #[must_use]
+    pub fn region(&self, name: &str) -> Option<Rect> {
+        self.named.get(name).copied()
+    }
+
+    /// Mark a named region as stale.
+    ///
+    /// Unknown names fall back to full damage: redrawing too much is a
+    /// performance issue, redrawing too little is a correctness issue.
+    pub fn invalidate_named(&mut self, name: &str) {
+        match self.named.get(name) {
+            Some(&rect) => self.invalidate(rect),
+            None => self.invalidate_all(),
+        }
+    }
+
+    /// Whether any damage is pending.
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.full || !self.regions.is_empty()
+    }
+
+    /// Take the accumulated damage and reset the tracker.
+    ///
+    /// Overlapping and touching regions are merged into their bounding box.
+    pub fn take(&mut self) -> Damage {
+        if std::mem::take(&mut self.full) {
+            self.regions.clear();
+            return Damage::Full;
+        }
+        Damage::Regions(merge_regions(std::mem::take(&mut self.regions)))
+    }
+}
+
+/// Whether two rectangles overlap or share an edge.
+fn touches(a: &Rect, b: &Rect) -> bool {
+    a.left() <= b.right() && b.left() <= a.right() && a.top() <= b.bottom() && b.top() <= a.bottom()
+}
+
+/// Merge overlapping or adjacent rectangles until no two touch.
+fn merge_regions(mut regions: Vec<Rect>) -> Vec<Rect> {
+    let mut merged: Vec<Rect> = Vec::with_capacity(regions.len());
+    while let Some(mut rect) = regions.pop() {
+        let mut i = 0;
+        while i < merged.len() {
+            if touches(&rect, &merged[i]) {
+                rect = rect.union(&merged.swap_remove(i));
+                // The grown rect may now touch entries already checked.
+                i = 0;
+            } else {
+                i += 1;
+            }
+        }
+        merged.push(rect);
+    }
+    merged
+}
+
+/// Prepare `buffer` for a partial render.
+///
+/// Copies `previous` into `buffer`, clears the damaged cells, and returns the
+/// regions clipped to the buffer and widened so they never split a wide glyph
+/// or a scaled text block. Only the cleared rows are left marked dirty, so the
+/// diff engine can skip everything that was carried over. Row line sizes and
+/// scaled text runs outside the damage are carried over as well.
+///
+/// # Panics
+///
+/// Panics if `buffer` and `previous` differ in size.
+pub fn restore_undamaged(buffer: &mut Buffer, previous: &Buffer, regions: &[Rect]) -> Vec<Rect> {
+    assert_eq!(
+        (buffer.width(), buffer.height()),
+        (previous.width(), previous.height()),
+        "partial render requires a previous buffer of the same size"
+    );
+    buffer.clone_from(previous);
+    buffer.clear_dirty();
+
+    let bounds = buffer.bounds();
+    let mut out = Vec::with_capacity(regions.len());
+    for region in regions {
+        let Some(clipped) = region.intersection_opt(&bounds) else {
+            continue;
+        };
+        let mut left = clipped.left();
+        let mut right = clipped.right();
+        for y in clipped.top()..clipped.bottom() {
+            left = left.min(wide_start(previous, left, y));
+            right = right.max(wide_end(previous, right, y));
+        }
+        let widened = previous
+            .scaled_texts()
+            .iter()
+            .map(ScaledText::area)
+            .filter(|area| area.intersection_opt(&clipped).is_some())
+            .fold(
+                Rect::new(left, clipped.y, right - left, clipped.height),
+                |acc, area| acc.union(&area),
+            )
+            .intersection(&bounds);
+        buffer.remove_scaled_text(widened);
+        for y in widened.top()..widened.bottom() {
+            if let Some(row) = buffer.row_cells_mut_span(y, widened.left(), widened.right()) {
+                row.fill(Cell::default());
+            }
+        }
+        out.push(widened);
+    }
+    out
+}
+
+/// Leftmost column of the glyph covering `(x, y)`.
+fn wide_start(buffer: &Buffer, mut x: u16, y: u16) -> u16 {
+    while x > 0 && buffer.get(x, y).is_some_and(Cell::is_continuation) {
+        x -= 1;
+    }
+    x
+}
+
+/// First column past the glyph that starts before `x` on row `y`.
+fn wide_end(buffer: &Buffer, mut x: u16, y: u16) -> u16 {
+    while x < buffer.width() && buffer.get(x, y).is_some_and(Cell::is_continuation) {
+        x += 1;
+    }
+    x
+}
+
+/// Render damaged regions through [`Model::view_region`].
+///
+/// Each call runs with the buffer scissor narrowed to its region, so drawing
+/// outside the region is discarded. `regions` should come from
+/// [`restore_undamaged`].
+///
+/// [`Model::view_region`]: crate::program::Model::view_region
+pub fn view_damaged<M: Model>(model: &M, frame: &mut Frame, regions: &[Rect]) {
+    for &region in regions {
+        frame.buffer.push_scissor(region);
+        model.view_region(frame, region);
+        frame.buffer.pop_scissor();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ftui_render::line_size::LineSize;
+
+    #[test]
+    fn new_tracker_is_fully_damaged() {
+        let mut tracker = DamageTracker::new();
+        assert!(tracker.is_dirty());
+        assert_eq!(tracker.take(), Damage::Full);
+        assert!(!tracker.is_dirty());
+        assert!(tracker.take().is_empty());
+    }
+
+    #[test]
+    fn invalidate_collects_regions() {
+        let mut tracker = DamageTracker::new();
+        tracker.take();
+        tracker.invalidate(Rect::new(0, 0, 4, 1));
+        tracker.invalidate(Rect::new(10, 5, 2, 2));
+        tracker.invalidate(Rect::new(3, 3, 0, 5));
+        let Damage::Regions(mut regions) = tracker.take() else {
+            panic!("expected regions");
+        };
+        regions.sort_by_key(|r| (r.y, r.x));
+        assert_eq!(regions, vec![Rect::new(0, 0, 4, 1), Rect::new(10, 5, 2, 2)]);
+    }
+
+    #[test]
+    fn overlapping_regions_are_merged() {
+        let mut tracker = DamageTracker::new();
+        tracker.take();
+        tracker.invalidate(Rect::new(0, 0, 4, 2));
+        tracker.invalidate(Rect::new(2, 1, 4, 2));
+        tracker.invalidate(Rect::new(6, 0, 1, 1));
+        assert_eq!(tracker.take(), Damage::Regions(vec![Rect::new(0, 0, 7, 3)]));
+    }
+
+    #[test]
+    fn named_regions() {
+        let mut tracker = DamageTracker::new();
+        tracker.take();
+        tracker.define_region("status", Rect::new(0, 23, 80, 1));
+        assert_eq!(tracker.region("status"), Some(Rect::new(0, 23, 80, 1)));
+        tracker.invalidate_named("status");
+        assert_eq!(
+            tracker.take(),
+            Damage::Regions(vec![Rect::new(0, 23, 80, 1)])
+        );
+    }
+
+    #[test]
+    fn unknown_named_region_falls_back_to_full() {
+        let mut tracker = DamageTracker::new();
+        tracker.take();
+        tracker.invalidate(Rect::new(0, 0, 1, 1));
+        tracker.invalidate_named("missing");
+        assert_eq!(tracker.take(), Damage::Full);
+    }
+
+    #[test]
+    fn restore_keeps_undamaged_cells_and_clears_damaged_ones() {
+        let mut previous = Buffer::new(4, 2);
+        for x in 0..4 {
+            previous.set_raw(x, 0, Cell::from_char('a'));
+            previous.set_raw(x, 1, Cell::from_char('b'));
+        }
+        previous.set_line_size(0, LineSize::DoubleWidth);
+        let mut buffer = Buffer::new(4, 2);
+        let regions = restore_undamaged(&mut buffer, &previous, &[Rect::new(1, 1, 2, 1)]);
+
+        assert_eq!(regions, vec![Rect::new(1, 1, 2, 1)]);
+        assert_eq!(buffer.get(0, 0).unwrap().content.as_char(), Some('a'));
+        assert_eq!(buffer.get(0, 1).unwrap().content.as_char(), Some('b'));
+        assert!(buffer.get(1, 1).unwrap().is_empty());
+        assert!(buffer.get(2, 1).unwrap().is_empty());
+        assert_eq!(buffer.get(3, 1).unwrap().content.as_char(), Some('b'));
+        assert_eq!(buffer.line_size(0), LineSize::DoubleWidth);
+        assert!(!buffer.is_row_dirty(0));
+        assert!(buffer.is_row_dirty(1));
+    }
+
+    #[test]
+    fn restore_widens_regions_over_wide_glyphs() {
+        let mut previous = Buffer::new(6, 1);
+        previous.set_raw(0, 0, Cell::from_char('中'));
+        previous.set_raw(1, 0, Cell::CONTINUATION);
+        previous.set_raw(4, 0, Cell::from_char('中'));
+        previous.set_raw(5, 0, Cell::CONTINUATION);
+        let mut buffer = Buffer::new(6, 1);
+        let regions = restore_undamaged(&mut buffer, &previous, &[Rect::new(1, 0, 4, 1)]);
+        assert_eq!(regions, vec![Rect::new(0, 0, 6, 1)]);
+    }
+
+    #[test]
+    fn restore_widens_regions_over_scaled_text() {
+        let mut previous = Buffer::new(8, 3);
+        previous.reserve_scaled_text(ScaledText::new(2, 0, "ab", 2), Cell::default());
+        previous.reserve_scaled_text(ScaledText::new(0, 2, "c", 1), Cell::default());
+        let mut buffer = Buffer::new(8, 3);
+        let regions = restore_undamaged(&mut buffer, &previous, &[Rect::new(0, 1, 3, 1)]);
+
+        assert_eq!(regions, vec![Rect::new(0, 0, 6, 2)]);
+        assert_eq!(buffer.scaled_texts().len(), 1);
+        assert_eq!(buffer.scaled_texts()[0].y(), 2);
+    }
+
+    #[test]
+    fn restore_drops_regions_outside_buffer() {
+        let previous = Buffer::new(4, 2);
+        let mut buffer = Buffer::new(4, 2);
+        let regions = restore_undamaged(
+            &mut buffer,
+            &previous,
+            &[Rect::new(10, 10, 2, 2), Rect::new(2, 1, 10, 10)],
+        );
+        assert_eq!(regions, vec![Rect::new(2, 1, 2, 1)]);
+    }
+}
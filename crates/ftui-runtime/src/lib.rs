@@ -14,6 +14,7 @@
 //! - [`Cmd`] - Commands for side effects
 //! - [`Subscription`] - Trait for continuous event sources
 //! - [`Every`] - Built-in tick subscription
+//! - [`animation_ticks`] - Frame ticks gated on an animation being active
 //!
 //! # Role in FrankenTUI
 //! `ftui-runtime` is the orchestrator. It consumes input events from
@@ -36,6 +37,7 @@ pub mod conformal_frame_guard;
 pub mod conformal_predictor;
 pub mod conformal_stages;
 pub mod cost_model;
+pub mod damage;
 pub mod debug_trace;
 pub mod decision_core;
 pub mod degradation_cascade;
@@ -57,6 +59,7 @@ pub mod lens;
 pub mod locale;
 pub mod log_sink;
 pub mod metrics_registry;
+pub mod min_size;
 pub mod policy_config;
 pub mod policy_registry;
 pub mod process_subscription;
@@ -136,7 +139,7 @@ pub use render_trace::{
 pub use retry::{BackoffStrategy, RetryPolicy, task_with_retry, task_with_timeout};
 pub use simulator::ProgramSimulator;
 pub use string_model::{StringModel, StringModelAdapter};
-pub use subscription::{Every, StopSignal, SubId, Subscription};
+pub use subscription::{Every, StopSignal, SubId, Subscription, animation_ticks};
 pub use terminal_writer::{ScreenMode, TerminalWriter, UiAnchor, inline_active_widgets};
 pub use tick_strategy::{
     ActiveOnly, ActivePlusAdjacent, AllocationCurve, Custom, DecayConfig, MarkovPredictor,
@@ -174,6 +177,7 @@ pub use cost_model::{
     BatchCostParams, BatchCostResult, CacheCostParams, CacheCostResult, PipelineCostParams,
     PipelineCostResult, StageStats,
 };
+pub use damage::{Damage, DamageTracker};
 pub use decision_core::{
     Action as DecisionAction, Decision, DecisionCore, Outcome as DecisionOutcome, Posterior,
     State as DecisionState, argmin_expected_loss, second_best_loss,
@@ -8,9 +8,11 @@
 
 use crate::reactive::{Observable, Subscription};
 pub use ftui_i18n::catalog::Locale;
+use ftui_text::hyphenation::{HyphenationDict, HyphenationRegistry};
 use std::cell::RefCell;
 use std::env;
 use std::rc::Rc;
+use std::sync::Arc;
 
 thread_local! {
     static GLOBAL_CONTEXT: LocaleContext = LocaleContext::system();
@@ -89,6 +91,14 @@ impl LocaleContext {
     pub fn version(&self) -> u64 {
         self.current.version()
     }
+
+    /// Hyphenation dictionary for the active locale, honoring overrides.
+    ///
+    /// Pass the result to `WrapOptions::hyphenation`.
+    #[must_use]
+    pub fn hyphenation(&self, registry: &HyphenationRegistry) -> Option<Arc<HyphenationDict>> {
+        registry.for_locale(&self.current_locale())
+    }
 }
 
 /// RAII guard for scoped locale overrides.
@@ -282,6 +292,23 @@ mod tests {
         assert!(fired.get());
     }
 
+    #[test]
+    fn hyphenation_follows_active_locale() {
+        let mut registry = HyphenationRegistry::with_builtin();
+        registry.insert(HyphenationDict::new("de", &["1schiff"], &[]));
+
+        let ctx = LocaleContext::new("de_AT.UTF-8");
+        let dict = ctx.hyphenation(&registry).expect("de dictionary");
+        assert_eq!(dict.language, "de");
+        {
+            let _guard = ctx.push_override("en-GB");
+            let dict = ctx.hyphenation(&registry).expect("en dictionary");
+            assert_eq!(dict.language, "en");
+        }
+        ctx.set_locale("ja");
+        assert!(ctx.hyphenation(&registry).is_none());
+    }
+
     #[test]
     fn detect_system_locale_empty_lc_all_uses_lang() {
         let locale = detect_system_locale_from(Some(""), Some("ja_JP.UTF-8"));
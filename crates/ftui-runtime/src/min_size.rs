@@ -0,0 +1,182 @@
+#![forbid(unsafe_code)]
+
+//! Minimum-size negotiation and the "terminal too small" fallback.
+//!
+//! A model reports the smallest frame its view can lay out through
+//! [`Model::min_size`], usually aggregated from its layout with
+//! `Flex::min_size`, `Grid::min_size`, or `PaneTree::minimum_size`. When
+//! the frame is smaller in either dimension, the runtime calls
+//! [`Model::view_too_small`] instead of `view`, so widgets never overdraw
+//! a squeezed layout.
+//!
+//! The default [`Model::view_too_small`] draws [`render_too_small_notice`].
+//! Models override it to render a degraded layout instead.
+//!
+//! ```ignore
+//! fn min_size(&self) -> Option<Size> {
+//!     Some(self.panes.minimum_size())
+//! }
+//! ```
+//!
+//! [`Model::min_size`]: crate::program::Model::min_size
+//! [`Model::view_too_small`]: crate::program::Model::view_too_small
+
+use ftui_core::geometry::Size;
+use ftui_render::cell::Cell;
+use ftui_render::frame::Frame;
+
+use crate::program::Model;
+
+/// Render the model, or its too-small fallback when the frame is smaller
+/// than `min_size`, the model's current
+/// [`Model::min_size`](crate::program::Model::min_size).
+///
+/// Returns `true` when the regular view ran.
+pub fn view_with_min_size<M: Model>(model: &M, frame: &mut Frame, min_size: Option<Size>) -> bool {
+    match too_small(min_size, frame) {
+        Some(needed) => {
+            tracing::debug!(
+                needed_width = needed.width,
+                needed_height = needed.height,
+                width = frame.buffer.width(),
+                height = frame.buffer.height(),
+                "frame below model minimum size"
+            );
+            model.view_too_small(frame, needed);
+            false
+        }
+        None => {
+            model.view(frame);
+            true
+        }
+    }
+}
+
+/// Minimum size the frame falls short of, if any.
+fn too_small(min_size: Option<Size>, frame: &Frame) -> Option<Size> {
+    min_size.filter(|needed| {
+        frame.buffer.width() < needed.width || frame.buffer.height() < needed.height
+    })
+}
+
+/// Draw a centered "please enlarge the terminal" notice.
+///
+/// Lines are dropped when the frame is too short and truncated when it is
+/// too narrow; the required size is never dropped.
+pub fn render_too_small_notice(frame: &mut Frame, needed: Size) {
+    let width = frame.buffer.width();
+    let height = frame.buffer.height();
+    frame.buffer.clear();
+
+    let size_line = format!(
+        "need {}\u{d7}{}, have {}\u{d7}{}",
+        needed.width, needed.height, width, height
+    );
+    let lines: Vec<&str> = match height {
+        0 => return,
+        1 => vec![size_line.as_str()],
+        2 => vec!["Terminal too small", size_line.as_str()],
+        _ => vec![
+            "Terminal too small",
+            size_line.as_str(),
+            "Please enlarge the terminal",
+        ],
+    };
+
+    let top = (height - lines.len() as u16) / 2;
+    for (row, line) in lines.iter().enumerate() {
+        let len = line.chars().count().min(usize::from(width)) as u16;
+        let left = (width - len) / 2;
+        for (col, ch) in line.chars().take(usize::from(len)).enumerate() {
+            frame
+                .buffer
+                .set(left + col as u16, top + row as u16, Cell::from_char(ch));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program::Cmd;
+    use ftui_core::event::Event;
+    use ftui_render::buffer::Buffer;
+    use ftui_render::grapheme_pool::GraphemePool;
+
+    struct Dashboard;
+
+    #[derive(Debug)]
+    struct Msg;
+
+    impl From<Event> for Msg {
+        fn from(_: Event) -> Self {
+            Msg
+        }
+    }
+
+    impl Model for Dashboard {
+        type Message = Msg;
+
+        fn update(&mut self, _msg: Msg) -> Cmd<Msg> {
+            Cmd::none()
+        }
+
+        fn view(&self, frame: &mut Frame) {
+            frame.buffer.set(0, 0, Cell::from_char('D'));
+        }
+
+        fn min_size(&self) -> Option<Size> {
+            Some(Size::new(40, 10))
+        }
+    }
+
+    fn row_text(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.width())
+            .map(|x| {
+                buffer
+                    .get(x, y)
+                    .and_then(|cell| cell.content.as_char())
+                    .unwrap_or(' ')
+            })
+            .collect::<String>()
+            .trim()
+            .to_string()
+    }
+
+    #[test]
+    fn view_runs_when_frame_is_large_enough() {
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(40, 10, &mut pool);
+        assert!(view_with_min_size(
+            &Dashboard,
+            &mut frame,
+            Dashboard.min_size()
+        ));
+        assert_eq!(row_text(&frame.buffer, 0), "D");
+    }
+
+    #[test]
+    fn notice_replaces_view_when_too_small() {
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(30, 5, &mut pool);
+        assert!(!view_with_min_size(
+            &Dashboard,
+            &mut frame,
+            Dashboard.min_size()
+        ));
+        assert_eq!(row_text(&frame.buffer, 1), "Terminal too small");
+        assert_eq!(
+            row_text(&frame.buffer, 2),
+            "need 40\u{d7}10, have 30\u{d7}5"
+        );
+        assert_eq!(row_text(&frame.buffer, 3), "Please enlarge the terminal");
+    }
+
+    #[test]
+    fn single_row_notice_keeps_the_size_line() {
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(12, 1, &mut pool);
+        render_too_small_notice(&mut frame, Size::new(40, 10));
+        assert_eq!(row_text(&frame.buffer, 0), "need 40\u{d7}10,");
+    }
+}
@@ -52,6 +52,7 @@
 //! ```
 
 use crate::StorageResult;
+use crate::damage::{self, Damage};
 use crate::evidence_sink::{EvidenceSink, EvidenceSinkConfig};
 use crate::evidence_telemetry::{
     BudgetDecisionSnapshot, ConformalSnapshot, ResizeDecisionSnapshot, set_budget_snapshot,
@@ -60,6 +61,7 @@ use crate::evidence_telemetry::{
 use crate::input_fairness::{FairnessDecision, FairnessEventType, InputFairnessGuard};
 use crate::input_macro::{EventRecorder, InputMacro};
 use crate::locale::LocaleContext;
+use crate::min_size;
 use crate::queueing_scheduler::{EstimateSource, QueueingScheduler, SchedulerConfig, WeightSource};
 use crate::render_trace::RenderTraceConfig;
 use crate::resize_coalescer::{CoalesceAction, CoalescerConfig, ResizeCoalescer};
@@ -74,7 +76,7 @@ use ftui_backend::{BackendEventSource, BackendFeatures};
 use ftui_core::event::{
     Event, KeyCode, KeyEvent, KeyEventKind, Modifiers, MouseButton, MouseEvent, MouseEventKind,
 };
-#[cfg(feature = "crossterm-compat")]
+#[cfg(any(feature = "crossterm-compat", feature = "native-backend"))]
 use ftui_core::terminal_capabilities::TerminalCapabilities;
 #[cfg(feature = "crossterm-compat")]
 use ftui_core::terminal_session::{SessionOptions, TerminalSession};
@@ -84,7 +86,7 @@ use ftui_layout::{
     PaneDragResizeTransition, PaneInertialThrow, PaneLayout, PaneModifierSnapshot,
     PaneMotionVector, PaneNodeKind, PanePointerButton, PanePointerPosition,
     PanePressureSnapProfile, PaneResizeDirection, PaneResizeTarget, PaneSemanticInputEvent,
-    PaneSemanticInputEventKind, PaneTree, Rect, SplitAxis,
+    PaneSemanticInputEventKind, PaneTree, Rect, Size, SplitAxis,
 };
 use ftui_render::arena::FrameArena;
 use ftui_render::budget::{
@@ -148,6 +150,64 @@ pub trait Model: Sized {
     /// Called after updates when the UI needs to be redrawn.
     fn view(&self, frame: &mut Frame);
 
+    /// Report which areas changed since the last frame.
+    ///
+    /// Called once per frame before rendering. Returning
+    /// [`Damage::Regions`](crate::damage::Damage::Regions) lets the runtime
+    /// keep the previous frame and re-render only those areas through
+    /// [`view_region`](Self::view_region). See [`crate::damage`].
+    ///
+    /// Areas outside the regions are not rendered, so layout state a widget
+    /// records while rendering is refreshed only inside them. Include every
+    /// widget whose position changed. Damage is ignored, and `view` runs in
+    /// full, when the previous frame was not drawn by `view` (for example
+    /// the [`view_too_small`](Self::view_too_small) fallback) or recorded
+    /// hit regions, which are not carried over between frames.
+    ///
+    /// # Default
+    ///
+    /// Returns [`Damage::Full`](crate::damage::Damage::Full).
+    fn take_damage(&mut self) -> crate::damage::Damage {
+        crate::damage::Damage::Full
+    }
+
+    /// Render one damaged area of the frame.
+    ///
+    /// The area has already been cleared and the buffer scissor is narrowed
+    /// to it, so drawing elsewhere is discarded. The cursor is not carried
+    /// over from the previous frame; set it here if the model shows one.
+    ///
+    /// # Default
+    ///
+    /// Calls [`view`](Self::view), clipped to `region`.
+    fn view_region(&self, frame: &mut Frame, _region: Rect) {
+        self.view(frame);
+    }
+
+    /// Smallest frame the view can lay out.
+    ///
+    /// When the frame is narrower or shorter than this, the runtime calls
+    /// [`view_too_small`](Self::view_too_small) instead of
+    /// [`view`](Self::view). See [`crate::min_size`].
+    ///
+    /// # Default
+    ///
+    /// Returns `None` (no minimum).
+    fn min_size(&self) -> Option<Size> {
+        None
+    }
+
+    /// Render a frame smaller than [`min_size`](Self::min_size).
+    ///
+    /// `needed` is the reported minimum. Override to draw a degraded layout.
+    ///
+    /// # Default
+    ///
+    /// Draws [`render_too_small_notice`](crate::min_size::render_too_small_notice).
+    fn view_too_small(&self, frame: &mut Frame, needed: Size) {
+        crate::min_size::render_too_small_notice(frame, needed);
+    }
+
     /// Declare active subscriptions.
     ///
     /// Called after each `update()`. The runtime compares the returned set
@@ -2470,6 +2530,14 @@ pub struct ProgramConfig {
     /// Defaults to `true` for application safety. Set to `false` in tests or
     /// when the embedding application manages signals.
     pub intercept_signals: bool,
+    /// Take capabilities, cursor and attribute sequences from the `$TERM`
+    /// terminfo entry.
+    ///
+    /// The entry can raise the detected color depth, synchronized output and
+    /// alternate screen support but never lowers them. Only the native backend honors this. When
+    /// no entry can be loaded the detected capabilities and ANSI sequences
+    /// are used. Defaults to `false`.
+    pub terminfo: bool,
     /// Optional tick strategy for selective background screen ticking.
     ///
     /// When `None` (default), all screens tick every frame (current behavior).
@@ -2517,6 +2585,7 @@ impl Default for ProgramConfig {
             effect_queue: EffectQueueConfig::default(),
             guardrails: GuardrailsConfig::default(),
             intercept_signals: true,
+            terminfo: false,
             tick_strategy: None,
             runtime_lane: RuntimeLane::default(),
             rollout_policy: RolloutPolicy::default(),
@@ -2749,6 +2818,13 @@ impl ProgramConfig {
         self
     }
 
+    /// Enable or disable terminfo-driven output on the native backend.
+    #[must_use]
+    pub fn with_terminfo(mut self, enabled: bool) -> Self {
+        self.terminfo = enabled;
+        self
+    }
+
     /// Set frame guardrails configuration.
     #[must_use]
     pub fn with_guardrails(mut self, config: GuardrailsConfig) -> Self {
@@ -4185,6 +4261,8 @@ pub struct Program<M: Model, E: BackendEventSource<Error = io::Error>, W: Write
     tick_strategy: Option<Box<dyn crate::tick_strategy::TickStrategy>>,
     /// Last active screen observed by the tick strategy dispatch path.
     last_active_screen_for_strategy: Option<String>,
+    /// Whether the previous frame can seed a partial render.
+    prev_frame_reusable: bool,
 }
 
 #[cfg(feature = "crossterm-compat")]
@@ -4345,6 +4423,7 @@ impl<M: Model> Program<M, CrosstermEventSource, Stdout> {
                 .tick_strategy
                 .map(|strategy| Box::new(strategy) as Box<dyn crate::tick_strategy::TickStrategy>),
             last_active_screen_for_strategy: None,
+            prev_frame_reusable: false,
         })
     }
 }
@@ -4471,6 +4550,7 @@ impl<M: Model, E: BackendEventSource<Error = io::Error>, W: Write + Send> Progra
                 .tick_strategy
                 .map(|strategy| Box::new(strategy) as Box<dyn crate::tick_strategy::TickStrategy>),
             last_active_screen_for_strategy: None,
+            prev_frame_reusable: false,
         })
     }
 }
@@ -4496,6 +4576,25 @@ const fn sanitize_backend_features_for_capabilities(
     }
 }
 
+/// Refine detected capabilities with the `$TERM` terminfo entry, if loaded.
+///
+/// The entry decides the color depth and can enable synchronized output,
+/// styled underlines, cursor styles and the alternate screen.
+#[cfg(feature = "native-backend")]
+fn capabilities_with_terminfo(
+    capabilities: TerminalCapabilities,
+    terminfo: Option<&ftui_core::terminfo::Terminfo>,
+) -> TerminalCapabilities {
+    match terminfo {
+        Some(info) => {
+            ftui_core::terminal_capabilities::CapabilityProfileBuilder::from(capabilities)
+                .terminfo(info)
+                .build()
+        }
+        None => capabilities,
+    }
+}
+
 #[cfg(feature = "native-backend")]
 impl<M: Model> Program<M, ftui_tty::TtyBackend, Stdout> {
     /// Create a program backed by the native TTY backend (no Crossterm).
@@ -4514,7 +4613,13 @@ impl<M: Model> Program<M, ftui_tty::TtyBackend, Stdout> {
     where
         M::Message: Send + 'static,
     {
-        let capabilities = ftui_core::terminal_capabilities::TerminalCapabilities::with_overrides();
+        let terminfo = if config.terminfo {
+            ftui_core::terminfo::Terminfo::from_env().ok()
+        } else {
+            None
+        };
+        let capabilities =
+            capabilities_with_terminfo(TerminalCapabilities::with_overrides(), terminfo.as_ref());
         let mouse_capture = config.resolved_mouse_capture();
         let requested_features = BackendFeatures {
             mouse_capture,
@@ -4525,19 +4630,23 @@ impl<M: Model> Program<M, ftui_tty::TtyBackend, Stdout> {
         let features =
             sanitize_backend_features_for_capabilities(requested_features, &capabilities);
         let options = ftui_tty::TtySessionOptions {
-            alternate_screen: matches!(config.screen_mode, ScreenMode::AltScreen),
+            alternate_screen: matches!(config.screen_mode, ScreenMode::AltScreen)
+                && capabilities.alt_screen,
             features,
             intercept_signals: config.intercept_signals,
         };
         let backend = ftui_tty::TtyBackend::open(0, 0, options)?;
 
-        let writer = TerminalWriter::with_diff_config(
+        let mut writer = TerminalWriter::with_diff_config(
             io::stdout(),
             config.screen_mode,
             config.ui_anchor,
             capabilities,
             config.diff_config.clone(),
         );
+        if terminfo.is_some() {
+            writer.set_terminfo(terminfo);
+        }
 
         Self::with_event_source(model, backend, features, writer, config)
     }
@@ -5526,6 +5635,7 @@ impl<M: Model, E: BackendEventSource<Error = io::Error>, W: Write + Send> Progra
                 elapsed_ms = self.budget.elapsed().as_millis() as u32,
                 "frame present skipped: budget exhausted after render"
             );
+            self.prev_frame_reusable = false;
         }
 
         if let Some(ref frame_timing) = self.frame_timing {
@@ -5690,7 +5800,22 @@ impl<M: Model, E: BackendEventSource<Error = io::Error>, W: Write + Send> Progra
 
         // Note: Frame borrows the pool and links from writer.
         // We scope it so it drops before we call present_ui (which needs exclusive writer access).
-        let buffer = self.writer.take_render_buffer(self.width, frame_height);
+        let mut buffer = self.writer.take_render_buffer(self.width, frame_height);
+
+        let min_size = self.model.min_size();
+        let too_small =
+            min_size.is_some_and(|min| self.width < min.width || frame_height < min.height);
+        // Partial render: only when the writer still holds the frame that is on
+        // screen and `view` drew it, otherwise fall back to a full view.
+        let damaged = match self.model.take_damage() {
+            Damage::Regions(regions) if self.prev_frame_reusable && !too_small => self
+                .writer
+                .previous_buffer()
+                .filter(|prev| prev.width() == buffer.width() && prev.height() == buffer.height())
+                .map(|prev| damage::restore_undamaged(&mut buffer, prev, &regions)),
+            Damage::Regions(_) | Damage::Full => None,
+        };
+
         let (pool, links) = self.writer.pool_and_links_mut();
         let mut frame = Frame::from_buffer(buffer, pool);
         frame.set_degradation(self.budget.degradation());
@@ -5705,7 +5830,16 @@ impl<M: Model, E: BackendEventSource<Error = io::Error>, W: Write + Send> Progra
             widget_count = tracing::field::Empty
         )
         .entered();
-        self.model.view(&mut frame);
+        let viewed = match damaged {
+            Some(regions) => {
+                damage::view_damaged(&self.model, &mut frame, &regions);
+                true
+            }
+            None => min_size::view_with_min_size(&self.model, &mut frame, min_size),
+        };
+        // Hit regions are not carried over between frames, so a model that
+        // records them gets a full view every frame.
+        self.prev_frame_reusable = viewed && frame.hit_grid.is_none();
         self.widget_signals = frame.take_widget_signals();
         tracing::Span::current().record("duration_us", view_start.elapsed().as_micros() as u64);
         // widget_count would require tracking in Frame
@@ -5767,7 +5901,7 @@ impl<M: Model, E: BackendEventSource<Error = io::Error>, W: Write + Send> Progra
             widget_count = tracing::field::Empty
         )
         .entered();
-        self.model.view(&mut frame);
+        min_size::view_with_min_size(&self.model, &mut frame, self.model.min_size());
         tracing::Span::current().record("duration_us", view_start.elapsed().as_micros() as u64);
 
         (frame.buffer, frame.cursor_position)
@@ -6683,6 +6817,28 @@ mod tests {
         assert!(config.resolved_mouse_capture());
     }
 
+    #[cfg(feature = "native-backend")]
+    #[test]
+    fn terminfo_without_true_color_keeps_detected_true_color() {
+        let detected = TerminalCapabilities::builder()
+            .true_color(true)
+            .colors_256(true)
+            .build();
+        let info = ftui_core::terminfo::Terminfo::default().with_number("colors", 256);
+        let writer = TerminalWriter::new(
+            Vec::new(),
+            ScreenMode::AltScreen,
+            UiAnchor::Bottom,
+            capabilities_with_terminfo(detected, Some(&info)),
+        );
+        assert!(writer.capabilities().true_color);
+        assert!(writer.capabilities().colors_256);
+
+        let basic = capabilities_with_terminfo(TerminalCapabilities::basic(), Some(&info));
+        assert!(!basic.true_color);
+        assert!(basic.colors_256);
+    }
+
     #[cfg(feature = "native-backend")]
     #[test]
     fn sanitize_backend_features_disables_unsupported_features() {
@@ -6824,6 +6980,8 @@ mod tests {
                     ftui_layout::PaneLeaf::new("right_bottom"),
                 ),
             ],
+            zoomed: None,
+            floating: Vec::new(),
             extensions: std::collections::BTreeMap::new(),
         };
         ftui_layout::PaneTree::from_snapshot(snapshot).expect("valid nested pane tree")
@@ -9389,6 +9547,7 @@ mod tests {
                 .tick_strategy
                 .map(|strategy| Box::new(strategy) as Box<dyn crate::tick_strategy::TickStrategy>),
             last_active_screen_for_strategy: None,
+            prev_frame_reusable: false,
         }
     }
 
@@ -10184,6 +10343,117 @@ mod tests {
         assert_eq!(program.frame_idx, 1);
     }
 
+    #[test]
+    fn headless_partial_render_after_too_small_notice_redraws_fully() {
+        struct ShrinkModel {
+            min: Option<Size>,
+            damage: Damage,
+        }
+
+        #[derive(Debug)]
+        enum ShrinkMsg {
+            Noop,
+        }
+
+        impl From<Event> for ShrinkMsg {
+            fn from(_: Event) -> Self {
+                ShrinkMsg::Noop
+            }
+        }
+
+        impl Model for ShrinkModel {
+            type Message = ShrinkMsg;
+
+            fn update(&mut self, _msg: Self::Message) -> Cmd<Self::Message> {
+                Cmd::none()
+            }
+
+            fn view(&self, frame: &mut Frame) {
+                frame.buffer.set_raw(0, 0, Cell::from_char('V'));
+            }
+
+            fn take_damage(&mut self) -> Damage {
+                std::mem::take(&mut self.damage)
+            }
+
+            fn min_size(&self) -> Option<Size> {
+                self.min
+            }
+        }
+
+        fn screen_text(buffer: &Buffer) -> String {
+            (0..buffer.height())
+                .flat_map(|y| (0..buffer.width()).map(move |x| (x, y)))
+                .filter_map(|(x, y)| buffer.get(x, y)?.content.as_char())
+                .collect()
+        }
+
+        let model = ShrinkModel {
+            min: Some(Size::new(200, 100)),
+            damage: Damage::Full,
+        };
+        let mut program = headless_program_with_config(model, ProgramConfig::default());
+        program.render_frame().expect("render frame");
+        let notice = screen_text(program.writer.previous_buffer().expect("presented"));
+        assert!(notice.contains("need"));
+
+        program.model_mut().min = None;
+        program.model_mut().damage = Damage::Regions(vec![Rect::new(0, 0, 1, 1)]);
+        program.dirty = true;
+        program.render_frame().expect("render frame");
+        let text = screen_text(program.writer.previous_buffer().expect("presented"));
+        assert!(text.starts_with('V'));
+        assert!(!text.contains("need"));
+    }
+
+    #[test]
+    fn headless_partial_render_skipped_when_hit_regions_recorded() {
+        struct HitModel {
+            full_views: std::cell::Cell<usize>,
+        }
+
+        #[derive(Debug)]
+        enum HitMsg {
+            Noop,
+        }
+
+        impl From<Event> for HitMsg {
+            fn from(_: Event) -> Self {
+                HitMsg::Noop
+            }
+        }
+
+        impl Model for HitModel {
+            type Message = HitMsg;
+
+            fn update(&mut self, _msg: Self::Message) -> Cmd<Self::Message> {
+                Cmd::none()
+            }
+
+            fn view(&self, frame: &mut Frame) {
+                self.full_views.set(self.full_views.get() + 1);
+                frame.enable_hit_testing();
+                frame.register_hit_region(Rect::new(10, 0, 5, 1), HitId::new(1));
+            }
+
+            fn take_damage(&mut self) -> Damage {
+                Damage::Regions(vec![Rect::new(0, 0, 1, 1)])
+            }
+
+            fn view_region(&self, _frame: &mut Frame, _region: Rect) {}
+        }
+
+        let model = HitModel {
+            full_views: std::cell::Cell::new(0),
+        };
+        let mut program = headless_program_with_config(model, ProgramConfig::default());
+        for _ in 0..3 {
+            program.dirty = true;
+            program.render_frame().expect("render frame");
+        }
+        assert_eq!(program.model().full_views.get(), 3);
+    }
+
     #[test]
     fn headless_render_frame_emits_budget_evidence_with_controller() {
         use ftui_render::budget::BudgetControllerConfig;
@@ -150,7 +150,7 @@ impl<M: Model> ProgramSimulator<M> {
     /// result. Returns a reference to the captured buffer.
     pub fn capture_frame(&mut self, width: u16, height: u16) -> &Buffer {
         let mut frame = Frame::new(width, height, &mut self.pool);
-        self.model.view(&mut frame);
+        crate::min_size::view_with_min_size(&self.model, &mut frame, self.model.min_size());
         self.frames.push(frame.buffer);
         self.frames.last().expect("frame just pushed")
     }
@@ -507,6 +507,32 @@ impl<M: Send + 'static> Subscription<M> for Every<M> {
     }
 }
 
+/// Frame ticks for an animation, requested only while it runs.
+///
+/// Returns an [`Every`] subscription with `id` while `active` is true and
+/// `None` once the animation settles, so the runtime stops the timer between
+/// animations. Pass the animation's `is_active()`; animations that follow
+/// [`MotionPreference`](ftui_core::animation::MotionPreference) never become
+/// active under reduced motion and so never request ticks.
+///
+/// # Example
+///
+/// ```ignore
+/// fn subscriptions(&self) -> Vec<Box<dyn Subscription<Msg>>> {
+///     animation_ticks(SIDEBAR_ANIM, self.sidebar.is_active(), FRAME, || Msg::AnimTick)
+///         .into_iter()
+///         .collect()
+/// }
+/// ```
+pub fn animation_ticks<M: Send + 'static>(
+    id: SubId,
+    active: bool,
+    interval: Duration,
+    make_msg: impl Fn() -> M + Send + Sync + 'static,
+) -> Option<Box<dyn Subscription<M>>> {
+    active.then(|| Box::new(Every::with_id(id, interval, make_msg)) as Box<dyn Subscription<M>>)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -844,6 +870,14 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn animation_ticks_only_while_active() {
+        let interval = Duration::from_millis(16);
+        assert!(animation_ticks(7, false, interval, || TestMsg::Tick).is_none());
+        let sub = animation_ticks(7, true, interval, || TestMsg::Tick).unwrap();
+        assert_eq!(sub.id(), 7);
+    }
+
     #[test]
     fn every_with_id_preserves_custom_id() {
         let sub = Every::<TestMsg>::with_id(12345, Duration::from_secs(1), || TestMsg::Tick);
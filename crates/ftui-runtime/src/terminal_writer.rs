@@ -74,6 +74,7 @@ use ftui_render::counting_writer::CountingWriter;
 use ftui_render::diff::{BufferDiff, TileDiffConfig, TileDiffFallback, TileDiffStats};
 use ftui_render::diff_strategy::{DiffStrategy, DiffStrategyConfig, DiffStrategySelector};
 use ftui_render::grapheme_pool::GraphemePool;
+use ftui_render::line_size;
 use ftui_render::link_registry::LinkRegistry;
 use ftui_render::presenter::Presenter;
 use ftui_render::sanitize::sanitize;
@@ -449,6 +450,9 @@ pub struct TerminalWriter<W: Write> {
     ui_anchor: UiAnchor,
     /// Previous buffer for diffing.
     prev_buffer: Option<Buffer>,
+    /// Whether `prev_buffer` had scaled rows rewritten by the line size
+    /// fallback, so it no longer matches what the model drew.
+    prev_rewritten: bool,
     /// Spare buffer for reuse as the next render target.
     spare_buffer: Option<Buffer>,
     /// Pre-allocated buffer for zero-alloc clone in present_ui.
@@ -614,6 +618,7 @@ impl<W: Write> TerminalWriter<W> {
             auto_ui_height,
             ui_anchor,
             prev_buffer: None,
+            prev_rewritten: false,
             spare_buffer: None,
             clone_buf: None,
             pool: GraphemePool::new(),
@@ -663,6 +668,16 @@ impl<W: Write> TerminalWriter<W> {
             .expect("presenter has been consumed")
     }
 
+    /// Reset the presenter after the terminal rows moved or were erased.
+    ///
+    /// ED2, resizes and inline re-anchoring all return rows to single width,
+    /// so the presenter's DEC line size cache must be dropped with them.
+    fn reset_presenter(&mut self) {
+        if let Some(presenter) = self.presenter.as_mut() {
+            presenter.reset();
+        }
+    }
+
     /// Reset diff strategy state when the previous buffer is invalidated.
     fn reset_diff_strategy(&mut self) {
         if self.diff_config.reset_on_invalidation {
@@ -724,6 +739,13 @@ impl<W: Write> TerminalWriter<W> {
         self.render_trace = recorder;
     }
 
+    /// Take cursor and attribute sequences from a terminfo entry.
+    ///
+    /// `None` restores ANSI output. See [`Presenter::set_terminfo`].
+    pub fn set_terminfo(&mut self, terminfo: Option<ftui_core::terminfo::Terminfo>) {
+        self.presenter_mut().set_terminfo(terminfo);
+    }
+
     /// Get mutable access to the diff strategy selector.
     ///
     /// Useful for advanced scenarios like manual posterior updates.
@@ -755,6 +777,7 @@ impl<W: Write> TerminalWriter<W> {
         self.spare_buffer = None;
         self.clone_buf = None;
         self.reset_diff_on_resize();
+        self.reset_presenter();
         // Reset scroll region on resize; it will be re-established on next present
         if self.scroll_region_active {
             let _ = self.deactivate_scroll_region();
@@ -779,6 +802,24 @@ impl<W: Write> TerminalWriter<W> {
         buffer
     }
 
+    /// The buffer most recently presented, used as the diff baseline.
+    ///
+    /// `None` when the next frame will be a full redraw (first frame, after a
+    /// resize, screen clear, or failed present), or when scaled rows were
+    /// rewritten because the terminal lacks DEC line attributes.
+    #[inline]
+    pub fn previous_buffer(&self) -> Option<&Buffer> {
+        if self.prev_rewritten {
+            return None;
+        }
+        self.prev_buffer.as_ref()
+    }
+
+    /// Whether scaled rows must be rewritten before presenting `buffer`.
+    fn needs_line_size_fallback(&self, buffer: &Buffer) -> bool {
+        buffer.has_scaled_lines() && !self.capabilities.use_line_attributes()
+    }
+
     /// Get the current terminal width.
     #[inline]
     pub fn width(&self) -> u16 {
@@ -860,6 +901,7 @@ impl<W: Write> TerminalWriter<W> {
                 if clamped != previous_effective {
                     self.prev_buffer = None;
                     self.reset_diff_strategy();
+                    self.reset_presenter();
                     if self.scroll_region_active {
                         let _ = self.deactivate_scroll_region();
                     }
@@ -876,6 +918,7 @@ impl<W: Write> TerminalWriter<W> {
             self.auto_ui_height = None;
             self.prev_buffer = None;
             self.reset_diff_strategy();
+            self.reset_presenter();
             if self.scroll_region_active {
                 let _ = self.deactivate_scroll_region();
             }
@@ -988,9 +1031,16 @@ impl<W: Write> TerminalWriter<W> {
         // Ensure erase operations clear to the terminal default background.
         // Without this, stale background fills can persist when inline regions shrink.
         self.writer().write_all(SGR_BG_DEFAULT)?;
+        // Erasing keeps DEC line attributes, so return the rows to single
+        // width explicitly.
+        let line_attributes = self.capabilities.use_line_attributes();
         for row in start_row..end_row {
             write!(self.writer(), "\x1b[{};1H", row.saturating_add(1))?;
             self.writer().write_all(ERASE_LINE)?;
+            if line_attributes {
+                self.writer()
+                    .write_all(line_size::LineSize::Normal.escape_sequence())?;
+            }
         }
         Ok(())
     }
@@ -1045,12 +1095,23 @@ impl<W: Write> TerminalWriter<W> {
     /// 8. Ends synchronized output
     ///
     /// In AltScreen mode, this just renders the buffer and positions cursor.
+    ///
+    /// Rows with a DEC line size are rewritten into normal cells first when
+    /// the terminal cannot display them (see [`line_size::apply_fallback`]).
     pub fn present_ui(
         &mut self,
         buffer: &Buffer,
         cursor: Option<(u16, u16)>,
         cursor_visible: bool,
     ) -> io::Result<()> {
+        if self.needs_line_size_fallback(buffer) {
+            let mut rewritten = buffer.clone();
+            line_size::apply_fallback(&mut rewritten);
+            let result = self.present_ui_owned(rewritten, cursor, cursor_visible);
+            self.prev_rewritten = true;
+            return result;
+        }
+        self.prev_rewritten = false;
         let mode_str = match self.screen_mode {
             ScreenMode::Inline { .. } => "inline",
             ScreenMode::InlineAuto { .. } => "inline_auto",
@@ -1166,10 +1227,14 @@ impl<W: Write> TerminalWriter<W> {
     /// that won't be reused, as it avoids an O(width × height) clone.
     pub fn present_ui_owned(
         &mut self,
-        buffer: Buffer,
+        mut buffer: Buffer,
         cursor: Option<(u16, u16)>,
         cursor_visible: bool,
     ) -> io::Result<()> {
+        self.prev_rewritten = self.needs_line_size_fallback(&buffer);
+        if self.prev_rewritten {
+            line_size::apply_fallback(&mut buffer);
+        }
         let mode_str = match self.screen_mode {
             ScreenMode::Inline { .. } => "inline",
             ScreenMode::InlineAuto { .. } => "inline_auto",
@@ -1634,6 +1699,7 @@ impl<W: Write> TerminalWriter<W> {
 
                 if self.prev_buffer.is_none() || dims_changed {
                     self.clear_rows(ui_y_start, visible_height)?;
+                    self.reset_presenter();
                 } else {
                     // If dimensions match but the buffer is shorter than the visible height,
                     // clear the remaining rows to prevent garbage from logs or previous frames.
@@ -1667,7 +1733,7 @@ impl<W: Write> TerminalWriter<W> {
                     // Reset presenter state (cursor unknown) because we manually moved cursor/saved
                     // and apply viewport offset for inline positioning.
                     let presenter = self.presenter.as_mut().expect("presenter consumed");
-                    presenter.reset();
+                    presenter.invalidate_cursor();
                     presenter.set_viewport_offset_y(ui_y_start);
 
                     if decision.has_diff {
@@ -1808,7 +1874,7 @@ impl<W: Write> TerminalWriter<W> {
 
                 // Reset presenter state (cursor and style) because we manually moved
                 // the cursor and reset the style at the end of the previous frame.
-                presenter.reset();
+                presenter.invalidate_cursor();
                 // AltScreen always starts at (0,0) relative to terminal.
                 presenter.set_viewport_offset_y(0);
 
@@ -1935,6 +2001,7 @@ impl<W: Write> TerminalWriter<W> {
                     self.prev_buffer = None;
                     self.last_inline_region = None;
                     self.reset_diff_strategy();
+                    self.reset_presenter();
                 }
 
                 self.writer().write_all(text.as_bytes())?;
@@ -1951,6 +2018,7 @@ impl<W: Write> TerminalWriter<W> {
                     self.prev_buffer = None;
                     self.last_inline_region = None;
                     self.reset_diff_strategy();
+                    self.reset_presenter();
                 }
 
                 self.writer().write_all(text.as_bytes())?;
@@ -2027,6 +2095,7 @@ impl<W: Write> TerminalWriter<W> {
         self.prev_buffer = None;
         self.last_inline_region = None;
         self.reset_diff_strategy();
+        self.reset_presenter();
         if let Some(err) = first_error {
             Err(err)
         } else {
@@ -2437,6 +2506,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn present_ui_uses_terminfo_sequences() {
+        let terminfo = ftui_core::terminfo::Terminfo::default()
+            .with_string("cup", "<%i%p1%d,%p2%d>")
+            .with_string("sgr0", "<N>");
+        let mut output = Vec::new();
+        {
+            let mut writer = TerminalWriter::new(
+                &mut output,
+                ScreenMode::AltScreen,
+                UiAnchor::Bottom,
+                basic_caps(),
+            );
+            writer.set_size(10, 5);
+            writer.set_terminfo(Some(terminfo));
+
+            let mut buffer = Buffer::new(10, 5);
+            buffer.set_raw(3, 2, Cell::from_char('x'));
+            writer.present_ui(&buffer, None, true).unwrap();
+        }
+
+        let output = String::from_utf8_lossy(&output);
+        assert!(output.contains("<3,1>   x"), "{output:?}");
+        assert!(!output.contains("\x1b[3;1H"), "{output:?}");
+    }
+
     #[test]
     fn present_ui_with_sync_output() {
         let mut output = Vec::new();
@@ -2841,6 +2936,67 @@ mod tests {
         assert!(!output_str.contains("test log"));
     }
 
+    #[test]
+    fn scaled_rows_fall_back_without_line_attributes() {
+        use ftui_render::line_size::LineSize;
+
+        let mut output = Vec::new();
+        {
+            let mut writer = TerminalWriter::new(
+                &mut output,
+                ScreenMode::AltScreen,
+                UiAnchor::Bottom,
+                basic_caps(),
+            );
+            writer.set_size(4, 1);
+            let mut buffer = Buffer::new(4, 1);
+            buffer.set(0, 0, Cell::from_char('A'));
+            buffer.set(1, 0, Cell::from_char('B'));
+            buffer.set_line_size(0, LineSize::DoubleWidth);
+            writer.present_ui(&buffer, None, true).unwrap();
+
+            let prev = writer.prev_buffer.as_ref().unwrap();
+            assert_eq!(prev.line_size(0), LineSize::Normal);
+            assert_eq!(prev.get(2, 0).unwrap().content.as_char(), Some('B'));
+            assert!(writer.previous_buffer().is_none());
+
+            writer.present_ui(&Buffer::new(4, 1), None, true).unwrap();
+            assert!(writer.previous_buffer().is_some());
+        }
+        assert!(!output.windows(2).any(|w| w == b"\x1b#"));
+    }
+
+    #[test]
+    fn clear_screen_reemits_line_sizes() {
+        use ftui_render::line_size::LineSize;
+
+        let mut caps = basic_caps();
+        caps.line_attributes = true;
+        let mut output = Vec::new();
+        {
+            let mut writer =
+                TerminalWriter::new(&mut output, ScreenMode::AltScreen, UiAnchor::Bottom, caps);
+            writer.set_size(4, 2);
+            let mut buffer = Buffer::new(4, 2);
+            buffer.set(0, 0, Cell::from_char('A'));
+            buffer.set(0, 1, Cell::from_char('A'));
+            buffer.set_line_size(0, LineSize::DoubleHeightTop);
+            buffer.set_line_size(1, LineSize::DoubleHeightBottom);
+            writer.present_ui(&buffer, None, true).unwrap();
+            writer.clear_screen().unwrap();
+            writer.present_ui(&buffer, None, true).unwrap();
+        }
+
+        let clear = output
+            .windows(4)
+            .position(|w| w == b"\x1b[2J")
+            .expect("clear_screen writes ED2");
+        let (before, after) = output.split_at(clear);
+        assert!(before.windows(3).any(|w| w == b"\x1b#3"));
+        assert!(after.windows(3).any(|w| w == b"\x1b#3"));
+        assert!(after.windows(3).any(|w| w == b"\x1b#4"));
+    }
+
     #[test]
     fn clear_screen_resets_prev_buffer() {
         let mut output = Vec::new();
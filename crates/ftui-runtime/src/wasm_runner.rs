@@ -221,7 +221,7 @@ impl<M: Model> WasmRunner<M> {
     /// Render the current frame unconditionally.
     pub fn force_render(&mut self) -> RenderedFrame<'_> {
         let mut frame = Frame::new(self.width, self.height, &mut self.pool);
-        self.model.view(&mut frame);
+        crate::min_size::view_with_min_size(&self.model, &mut frame, self.model.min_size());
 
         // Compute diff against previous buffer.
         let diff = self
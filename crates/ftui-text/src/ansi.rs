@@ -0,0 +1,648 @@
+//! ANSI escape-sequence parsing into styled text.
+//!
+//! This module converts terminal output (as produced by `cargo`, `git`, test
+//! runners and other subprocesses) into [`Text`] made of styled [`Span`]s.
+//!
+//! # Supported sequences
+//! - SGR (`ESC [ ... m`): bold, dim, italic, underline (single, double and
+//!   curly via `4:n`), blink, reverse, hidden, strikethrough, and 16-color,
+//!   256-color and truecolor foreground, background and underline colors in
+//!   both the `;` and `:` parameter forms
+//! - OSC 8 hyperlinks (`ESC ] 8 ; params ; URI ST`), stored as span links
+//! - `\n` and `\r\n` line endings; a bare `\r` discards the current line, the
+//!   way progress bars redraw themselves
+//!
+//! Every other escape sequence (cursor movement, erase, mode changes, window
+//! titles, clipboard access, DCS/APC strings) and every other control
+//! character is dropped, so untrusted output cannot reach the terminal.
+//!
+//! # Streaming
+//! [`AnsiParser`] keeps its state between calls, so a sequence or a UTF-8
+//! character split across two reads is decoded as if it had arrived whole.
+//!
+//! # Example
+//! ```
+//! use ftui_text::ansi::{AnsiParser, parse_ansi};
+//!
+//! let text = parse_ansi("\x1b[1;31merror\x1b[0m: mismatched types");
+//! assert_eq!(text.to_plain_text(), "error: mismatched types");
+//!
+//! let mut parser = AnsiParser::new();
+//! assert!(parser.feed("\x1b[3").is_empty());
+//! let lines = parser.feed("2mok\x1b[0m\n");
+//! assert_eq!(lines[0].to_plain_text(), "ok");
+//! ```
+
+use crate::text::{Line, Span, Text};
+use ftui_render::cell::PackedRgba;
+use ftui_style::color::ansi256_to_rgb;
+use ftui_style::{Style, StyleFlags};
+
+/// Longest CSI parameter string accepted before the sequence is discarded.
+const MAX_CSI_LEN: usize = 256;
+
+/// Longest OSC payload accepted before the sequence is discarded.
+const MAX_OSC_LEN: usize = 4096;
+
+const ANY_UNDERLINE: StyleFlags = StyleFlags(
+    StyleFlags::UNDERLINE.0 | StyleFlags::DOUBLE_UNDERLINE.0 | StyleFlags::CURLY_UNDERLINE.0,
+);
+
+/// Escape-sequence state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Plain text.
+    Ground,
+    /// After `ESC`.
+    Escape,
+    /// After `ESC` and one or more intermediate bytes.
+    EscapeIntermediate,
+    /// Inside a control sequence (`ESC [`).
+    Csi,
+    /// Inside an operating system command (`ESC ]`).
+    Osc,
+    /// After `ESC` inside an OSC, expecting `\` to terminate it.
+    OscEscape,
+    /// Inside a DCS, SOS, PM or APC string, ignored until ST.
+    Ignored,
+    /// After `ESC` inside an ignored string.
+    IgnoredEscape,
+}
+
+/// Streaming parser turning ANSI-colored output into styled lines.
+///
+/// Feed output as it arrives with [`feed`](Self::feed) or
+/// [`feed_bytes`](Self::feed_bytes); each call returns the lines completed by
+/// that chunk. Style and hyperlink state carry over between lines, as they
+/// do in a terminal.
+#[derive(Debug, Clone)]
+pub struct AnsiParser {
+    state: State,
+    params: String,
+    /// The current CSI has a private marker, intermediates or is too long.
+    csi_ignored: bool,
+    osc: String,
+    osc_overflow: bool,
+    flags: StyleFlags,
+    fg: Option<PackedRgba>,
+    bg: Option<PackedRgba>,
+    underline_color: Option<PackedRgba>,
+    link: Option<String>,
+    /// Spans of the current, unterminated line.
+    spans: Vec<Span<'static>>,
+    /// Text written since the last style or link change.
+    run: String,
+    /// A `\r` was seen and not yet followed by `\n`.
+    pending_cr: bool,
+    /// Trailing bytes of an incomplete UTF-8 character.
+    utf8_tail: Vec<u8>,
+}
+
+impl Default for AnsiParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnsiParser {
+    /// Create a parser with no active style.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: State::Ground,
+            params: String::new(),
+            csi_ignored: false,
+            osc: String::new(),
+            osc_overflow: false,
+            flags: StyleFlags::NONE,
+            fg: None,
+            bg: None,
+            underline_color: None,
+            link: None,
+            spans: Vec::new(),
+            run: String::new(),
+            pending_cr: false,
+            utf8_tail: Vec::new(),
+        }
+    }
+
+    /// Parse a chunk of output, returning the lines it completed.
+    pub fn feed(&mut self, input: &str) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        self.feed_into(input, &mut lines);
+        lines
+    }
+
+    /// Parse a chunk of raw bytes, returning the lines it completed.
+    ///
+    /// A UTF-8 character split across chunks is held back until the rest
+    /// arrives; invalid bytes decode as U+FFFD.
+    pub fn feed_bytes(&mut self, bytes: &[u8]) -> Vec<Line<'static>> {
+        let mut buf = std::mem::take(&mut self.utf8_tail);
+        buf.extend_from_slice(bytes);
+
+        let mut lines = Vec::new();
+        let mut rest = buf.as_slice();
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    self.feed_into(valid, &mut lines);
+                    break;
+                }
+                Err(err) => {
+                    let (valid, after) = rest.split_at(err.valid_up_to());
+                    self.feed_into(std::str::from_utf8(valid).unwrap_or_default(), &mut lines);
+                    match err.error_len() {
+                        Some(len) => {
+                            self.feed_into("\u{fffd}", &mut lines);
+                            rest = &after[len..];
+                        }
+                        None => {
+                            self.utf8_tail = after.to_vec();
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        lines
+    }
+
+    /// End the stream, returning the unterminated last line (possibly empty).
+    ///
+    /// An incomplete escape sequence is discarded and an incomplete UTF-8
+    /// character decodes as U+FFFD. The current style is kept.
+    pub fn finish(&mut self) -> Line<'static> {
+        if !self.utf8_tail.is_empty() {
+            self.utf8_tail.clear();
+            let mut lines = Vec::new();
+            self.feed_into("\u{fffd}", &mut lines);
+        }
+        self.state = State::Ground;
+        self.pending_cr = false;
+        self.end_line()
+    }
+
+    /// The current, unterminated line, e.g. a prompt without a newline.
+    #[must_use]
+    pub fn partial_line(&self) -> Line<'static> {
+        let mut line = Line::from_spans(self.spans.iter().cloned());
+        if !self.run.is_empty() {
+            line.push_span(self.run_span(self.run.clone()));
+        }
+        line
+    }
+
+    /// Style that text written now would receive.
+    #[must_use]
+    pub fn style(&self) -> Style {
+        Style {
+            fg: self.fg,
+            bg: self.bg,
+            attrs: (!self.flags.is_empty()).then_some(self.flags),
+            underline_color: self.underline_color,
+        }
+    }
+
+    /// Active OSC 8 hyperlink target, if any.
+    #[must_use]
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    /// Discard all state, including the current line and style.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn feed_into(&mut self, input: &str, lines: &mut Vec<Line<'static>>) {
+        for ch in input.chars() {
+            self.advance(ch, lines);
+        }
+    }
+
+    fn advance(&mut self, ch: char, lines: &mut Vec<Line<'static>>) {
+        match self.state {
+            State::Ground => self.ground(ch, lines),
+            State::Escape => self.escape(ch),
+            State::EscapeIntermediate => match ch {
+                '\x1b' => self.state = State::Escape,
+                '\x20'..='\x2f' => {}
+                _ => self.state = State::Ground,
+            },
+            State::Csi => self.csi(ch),
+            State::Osc => match ch {
+                '\x07' | '\u{9c}' => self.dispatch_osc(),
+                '\x1b' => self.state = State::OscEscape,
+                '\x18' | '\x1a' => self.state = State::Ground,
+                _ if self.osc.len() < MAX_OSC_LEN => self.osc.push(ch),
+                _ => self.osc_overflow = true,
+            },
+            State::OscEscape => {
+                if ch == '\\' {
+                    self.dispatch_osc();
+                } else {
+                    self.state = State::Escape;
+                    self.escape(ch);
+                }
+            }
+            State::Ignored => match ch {
+                '\x1b' => self.state = State::IgnoredEscape,
+                '\u{9c}' | '\x18' | '\x1a' => self.state = State::Ground,
+                _ => {}
+            },
+            State::IgnoredEscape => match ch {
+                '\\' => self.state = State::Ground,
+                '\x1b' => {}
+                _ => self.state = State::Ignored,
+            },
+        }
+    }
+
+    fn ground(&mut self, ch: char, lines: &mut Vec<Line<'static>>) {
+        match ch {
+            '\n' => {
+                self.pending_cr = false;
+                lines.push(self.end_line());
+            }
+            '\r' => self.pending_cr = true,
+            '\x1b' => self.state = State::Escape,
+            '\u{9b}' => self.begin_csi(),
+            '\u{9d}' => self.begin_osc(),
+            '\u{90}' | '\u{98}' | '\u{9e}' | '\u{9f}' => self.state = State::Ignored,
+            '\t' => self.push_char(ch),
+            _ if ch.is_control() => {}
+            _ => self.push_char(ch),
+        }
+    }
+
+    fn escape(&mut self, ch: char) {
+        match ch {
+            '[' => self.begin_csi(),
+            ']' => self.begin_osc(),
+            'P' | 'X' | '^' | '_' => self.state = State::Ignored,
+            '\x1b' => {}
+            '\x20'..='\x2f' => self.state = State::EscapeIntermediate,
+            _ => self.state = State::Ground,
+        }
+    }
+
+    fn begin_csi(&mut self) {
+        self.params.clear();
+        self.csi_ignored = false;
+        self.state = State::Csi;
+    }
+
+    fn csi(&mut self, ch: char) {
+        match ch {
+            '0'..='9' | ';' | ':' => {
+                if self.params.len() < MAX_CSI_LEN {
+                    self.params.push(ch);
+                } else {
+                    self.csi_ignored = true;
+                }
+            }
+            '<'..='?' | '\x20'..='\x2f' => self.csi_ignored = true,
+            '\x40'..='\x7e' => {
+                if ch == 'm' && !self.csi_ignored {
+                    let params = std::mem::take(&mut self.params);
+                    self.apply_sgr(&params);
+                }
+                self.state = State::Ground;
+            }
+            '\x1b' => self.state = State::Escape,
+            '\x18' | '\x1a' => self.state = State::Ground,
+            _ if ch.is_ascii_control() => {}
+            _ => self.state = State::Ground,
+        }
+    }
+
+    fn begin_osc(&mut self) {
+        self.osc.clear();
+        self.osc_overflow = false;
+        self.state = State::Osc;
+    }
+
+    fn dispatch_osc(&mut self) {
+        self.state = State::Ground;
+        if self.osc_overflow {
+            return;
+        }
+        let Some(("8", rest)) = self.osc.split_once(';') else {
+            return;
+        };
+        let Some((_params, uri)) = rest.split_once(';') else {
+            return;
+        };
+        if uri.chars().any(char::is_control) {
+            return;
+        }
+        let link = (!uri.is_empty()).then(|| uri.to_string());
+        if link != self.link {
+            self.flush_run();
+            self.link = link;
+        }
+    }
+
+    fn apply_sgr(&mut self, params: &str) {
+        self.flush_run();
+        let groups: Vec<&str> = params.split(';').collect();
+        let mut i = 0;
+        while i < groups.len() {
+            let group = groups[i];
+            i += 1;
+            let mut sub = group.split(':').map(parse_param);
+            let code = sub.next().flatten().unwrap_or(0);
+            match code {
+                0 => {
+                    self.flags = StyleFlags::NONE;
+                    self.fg = None;
+                    self.bg = None;
+                    self.underline_color = None;
+                }
+                1 => self.flags.insert(StyleFlags::BOLD),
+                2 => self.flags.insert(StyleFlags::DIM),
+                3 => self.flags.insert(StyleFlags::ITALIC),
+                4 => {
+                    self.flags.remove(ANY_UNDERLINE);
+                    match sub.next().flatten() {
+                        Some(0) => {}
+                        Some(2) => self.flags.insert(StyleFlags::DOUBLE_UNDERLINE),
+                        Some(3) => self.flags.insert(StyleFlags::CURLY_UNDERLINE),
+                        _ => self.flags.insert(StyleFlags::UNDERLINE),
+                    }
+                }
+                5 | 6 => self.flags.insert(StyleFlags::BLINK),
+                7 => self.flags.insert(StyleFlags::REVERSE),
+                8 => self.flags.insert(StyleFlags::HIDDEN),
+                9 => self.flags.insert(StyleFlags::STRIKETHROUGH),
+                21 => {
+                    self.flags.remove(ANY_UNDERLINE);
+                    self.flags.insert(StyleFlags::DOUBLE_UNDERLINE);
+                }
+                22 => {
+                    self.flags.remove(StyleFlags::BOLD);
+                    self.flags.remove(StyleFlags::DIM);
+                }
+                23 => self.flags.remove(StyleFlags::ITALIC),
+                24 => self.flags.remove(ANY_UNDERLINE),
+                25 => self.flags.remove(StyleFlags::BLINK),
+                27 => self.flags.remove(StyleFlags::REVERSE),
+                28 => self.flags.remove(StyleFlags::HIDDEN),
+                29 => self.flags.remove(StyleFlags::STRIKETHROUGH),
+                30..=37 => self.fg = indexed_color(code - 30),
+                39 => self.fg = None,
+                40..=47 => self.bg = indexed_color(code - 40),
+                49 => self.bg = None,
+                59 => self.underline_color = None,
+                90..=97 => self.fg = indexed_color(code - 90 + 8),
+                100..=107 => self.bg = indexed_color(code - 100 + 8),
+                38 | 48 | 58 => {
+                    let color = if group.contains(':') {
+                        let args: Vec<Option<u16>> = sub.collect();
+                        extended_color(&args, true)
+                    } else {
+                        let args: Vec<Option<u16>> =
+                            groups[i..].iter().map(|g| parse_param(g)).collect();
+                        i += match args.first().copied().flatten() {
+                            Some(5) => 2,
+                            Some(2) => 4,
+                            // Unknown color model: the rest is uninterpretable.
+                            _ => groups.len(),
+                        };
+                        extended_color(&args, false)
+                    };
+                    if let Some(color) = color {
+                        match code {
+                            38 => self.fg = Some(color),
+                            48 => self.bg = Some(color),
+                            _ => self.underline_color = Some(color),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn push_char(&mut self, ch: char) {
+        if self.pending_cr {
+            self.pending_cr = false;
+            self.spans.clear();
+            self.run.clear();
+        }
+        self.run.push(ch);
+    }
+
+    fn run_span(&self, content: String) -> Span<'static> {
+        let style = self.style();
+        let span = if style.is_empty() {
+            Span::raw(content)
+        } else {
+            Span::styled(content, style)
+        };
+        match &self.link {
+            Some(link) => span.link(link.clone()),
+            None => span,
+        }
+    }
+
+    fn flush_run(&mut self) {
+        if self.run.is_empty() {
+            return;
+        }
+        let content = std::mem::take(&mut self.run);
+        let span = self.run_span(content);
+        self.spans.push(span);
+    }
+
+    fn end_line(&mut self) -> Line<'static> {
+        self.flush_run();
+        Line::from_spans(std::mem::take(&mut self.spans))
+    }
+}
+
+fn parse_param(param: &str) -> Option<u16> {
+    param.parse().ok()
+}
+
+fn indexed_color(index: u16) -> Option<PackedRgba> {
+    let rgb = ansi256_to_rgb(u8::try_from(index).ok()?);
+    Some(PackedRgba::rgb(rgb.r, rgb.g, rgb.b))
+}
+
+/// Decode the arguments following 38, 48 or 58: `5;n` or `2;r;g;b`.
+///
+/// The colon form (`2:cs:r:g:b`) may carry a colorspace id before the
+/// channels; the semicolon form never does, and trailing arguments belong
+/// to the next attribute.
+fn extended_color(args: &[Option<u16>], colon: bool) -> Option<PackedRgba> {
+    match args.first().copied().flatten()? {
+        5 => indexed_color(args.get(1).copied().flatten()?),
+        2 => {
+            let channels = if colon && args.len() >= 5 {
+                &args[2..5]
+            } else {
+                args.get(1..4)?
+            };
+            let channel = |c: Option<u16>| u8::try_from(c.unwrap_or(0)).ok();
+            Some(PackedRgba::rgb(
+                channel(channels[0])?,
+                channel(channels[1])?,
+                channel(channels[2])?,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Parse ANSI-colored output into styled [`Text`].
+///
+/// Lines split on `\n` exactly like [`Text::raw`]; see the
+/// [module documentation](self) for the supported sequences.
+#[must_use]
+pub fn parse_ansi(input: &str) -> Text<'static> {
+    if input.is_empty() {
+        return Text::new();
+    }
+    let mut parser = AnsiParser::new();
+    let mut lines = parser.feed(input);
+    lines.push(parser.finish());
+    Text::from_lines(lines)
+}
+
+impl Text<'static> {
+    /// Create styled text from ANSI-colored output.
+    ///
+    /// Shorthand for [`parse_ansi`].
+    #[must_use]
+    pub fn from_ansi(input: &str) -> Self {
+        parse_ansi(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(line: &Line<'_>) -> Vec<(String, Style)> {
+        line.spans()
+            .iter()
+            .map(|span| (span.as_str().to_string(), span.style.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn plain_text_passes_through() {
+        let text = parse_ansi("hello\nworld");
+        assert_eq!(text.height(), 2);
+        assert_eq!(text.to_plain_text(), "hello\nworld");
+        assert!(text.lines()[0].spans()[0].style.is_none());
+    }
+
+    #[test]
+    fn basic_sgr_attributes_and_colors() {
+        let text = parse_ansi("\x1b[1;31merror\x1b[0m: \x1b[4mdetail\x1b[24m");
+        let line = spans(&text.lines()[0]);
+        assert_eq!(line[0].0, "error");
+        assert!(line[0].1.has_attr(StyleFlags::BOLD));
+        assert_eq!(line[0].1.fg, indexed_color(1));
+        assert_eq!(line[1], (": ".to_string(), Style::new()));
+        assert!(line[2].1.has_attr(StyleFlags::UNDERLINE));
+    }
+
+    #[test]
+    fn bright_256_and_truecolor() {
+        let text = parse_ansi(
+            "\x1b[92ma\x1b[38;5;208mb\x1b[48;2;10;20;30mc\x1b[38:2::1:2:3md\x1b[58:5:196me",
+        );
+        let line = spans(&text.lines()[0]);
+        assert_eq!(line[0].1.fg, indexed_color(10));
+        assert_eq!(line[1].1.fg, indexed_color(208));
+        assert_eq!(line[2].1.bg, Some(PackedRgba::rgb(10, 20, 30)));
+        assert_eq!(line[3].1.fg, Some(PackedRgba::rgb(1, 2, 3)));
+        assert_eq!(line[4].1.underline_color, indexed_color(196));
+    }
+
+    #[test]
+    fn underline_variants() {
+        let text = parse_ansi("\x1b[4:3ma\x1b[21mb\x1b[4:0mc");
+        let line = spans(&text.lines()[0]);
+        assert!(line[0].1.has_attr(StyleFlags::CURLY_UNDERLINE));
+        assert!(line[1].1.has_attr(StyleFlags::DOUBLE_UNDERLINE));
+        assert!(!line[1].1.has_attr(StyleFlags::CURLY_UNDERLINE));
+        assert_eq!(line[2].1, Style::new());
+    }
+
+    #[test]
+    fn sequences_split_across_chunks() {
+        let mut parser = AnsiParser::new();
+        assert!(parser.feed("\x1b").is_empty());
+        assert!(parser.feed("[1;3").is_empty());
+        assert!(parser.feed("2mgr").is_empty());
+        let lines = parser.feed("een\x1b[0m\n");
+        assert_eq!(lines.len(), 1);
+        let line = spans(&lines[0]);
+        assert_eq!(line[0].0, "green");
+        assert_eq!(line[0].1.fg, indexed_color(2));
+    }
+
+    #[test]
+    fn utf8_split_across_byte_chunks() {
+        let mut parser = AnsiParser::new();
+        let bytes = "caf\u{e9}\n".as_bytes();
+        assert!(parser.feed_bytes(&bytes[..4]).is_empty());
+        let lines = parser.feed_bytes(&bytes[4..]);
+        assert_eq!(lines[0].to_plain_text(), "caf\u{e9}");
+    }
+
+    #[test]
+    fn osc8_hyperlinks() {
+        let text = parse_ansi("see \x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x07 now");
+        let line = text.lines()[0].spans();
+        assert_eq!(line.len(), 3);
+        assert!(line[0].link.is_none());
+        assert_eq!(line[1].as_str(), "docs");
+        assert_eq!(line[1].link.as_deref(), Some("https://example.com"));
+        assert!(line[2].link.is_none());
+    }
+
+    #[test]
+    fn cursor_movement_and_unsafe_sequences_are_dropped() {
+        let text = parse_ansi(
+            "a\x1b[2J\x1b[10;20Hb\x1b[?25lc\x1b]0;title\x07d\x1bPq#payload\x1b\\e\x1b7f\x07\x08g",
+        );
+        assert_eq!(text.to_plain_text(), "abcdefg");
+        assert!(text.lines()[0].spans()[0].style.is_none());
+    }
+
+    #[test]
+    fn carriage_returns() {
+        let text = parse_ansi("one\r\ntwo\n 10%\r 50%\r100%\n");
+        assert_eq!(text.to_plain_text(), "one\ntwo\n100%\n");
+    }
+
+    #[test]
+    fn style_carries_across_lines() {
+        let mut parser = AnsiParser::new();
+        let lines = parser.feed("\x1b[33mwarn\nstill\n");
+        assert_eq!(lines[1].spans()[0].style, Some(parser.style()));
+        assert_eq!(parser.style().fg, indexed_color(3));
+    }
+
+    #[test]
+    fn partial_line_and_finish() {
+        let mut parser = AnsiParser::new();
+        assert!(parser.feed("$ \x1b[1mprompt").is_empty());
+        assert_eq!(parser.partial_line().to_plain_text(), "$ prompt");
+        assert_eq!(parser.finish().to_plain_text(), "$ prompt");
+        assert!(parser.finish().is_empty());
+    }
+
+    #[test]
+    fn text_from_ansi_matches_parse_ansi() {
+        let input = "\x1b[35mmagenta\x1b[m\nplain";
+        assert_eq!(Text::from_ansi(input), parse_ansi(input));
+        assert!(Text::from_ansi("").is_empty());
+    }
+}
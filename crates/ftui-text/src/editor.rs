@@ -25,6 +25,27 @@
 //! ed.delete_backward(); // deletes the space
 //! assert_eq!(ed.text(), "helloworld");
 //! ```
+//!
+//! # Multiple cursors
+//!
+//! Secondary cursors ([`Caret`]s) are added with
+//! [`Editor::add_cursor_below`], [`Editor::add_next_occurrence`] or
+//! [`Editor::select_block`]. Every edit and movement then applies to all
+//! cursors, overlapping selections merge, and each edit undoes as one step.
+//!
+//! ```
+//! use ftui_text::editor::Editor;
+//!
+//! let mut ed = Editor::with_text("a\nb");
+//! ed.move_to_document_start();
+//! ed.add_cursor_below();
+//! ed.insert_text("- ");
+//! assert_eq!(ed.text(), "- a\n- b");
+//! ed.undo();
+//! assert_eq!(ed.text(), "a\nb");
+//! ```
+
+use std::ops::Range;
 
 use crate::cursor::{CursorNavigator, CursorPosition};
 use crate::rope::Rope;
@@ -45,6 +66,8 @@ enum EditOp {
         deleted: String,
         inserted: String,
     },
+    /// Edits applied in order as one undo step (multi-cursor edits).
+    Batch(Vec<EditOp>),
 }
 
 impl EditOp {
@@ -67,6 +90,7 @@ impl EditOp {
                 deleted: inserted.clone(),
                 inserted: deleted.clone(),
             },
+            Self::Batch(ops) => Self::Batch(ops.iter().rev().map(Self::inverse).collect()),
         }
     }
 
@@ -77,6 +101,7 @@ impl EditOp {
             Self::Replace {
                 deleted, inserted, ..
             } => deleted.len() + inserted.len(),
+            Self::Batch(ops) => ops.iter().map(Self::byte_len).sum(),
         }
     }
 }
@@ -106,6 +131,71 @@ impl Selection {
     }
 }
 
+/// A cursor together with its own selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Caret {
+    /// Cursor position (the selection head when a selection is active).
+    pub cursor: CursorPosition,
+    /// Selection anchored at this cursor, if any.
+    pub selection: Option<Selection>,
+}
+
+impl Caret {
+    /// Byte range covered by the caret: its selection, or the empty range at
+    /// the cursor.
+    #[must_use]
+    pub fn byte_range(&self, nav: &CursorNavigator<'_>) -> (usize, usize) {
+        match self.selection {
+            Some(sel) if !sel.is_empty() => sel.byte_range(nav),
+            _ => {
+                let byte = nav.to_byte_index(self.cursor);
+                (byte, byte)
+            }
+        }
+    }
+}
+
+/// Block of lines touched by edits, as reported by
+/// [`Editor::take_line_changes`].
+///
+/// Lines `start..start + removed` of the text before the edits became lines
+/// `start..start + inserted` of the current text; lines outside the block
+/// are unchanged apart from the shift by `inserted - removed`. The block may
+/// include untouched lines between two distant edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineChanges {
+    /// First line of the block.
+    pub start: usize,
+    /// Lines the block spanned before the edits.
+    pub removed: usize,
+    /// Lines the block spans now.
+    pub inserted: usize,
+}
+
+impl LineChanges {
+    /// Extend this block with a later edit, given in the coordinates of the
+    /// text between the two.
+    fn merge(self, later: Self) -> Self {
+        let end = self.start + self.inserted;
+        let end = if end <= later.start {
+            end
+        } else if end >= later.start + later.removed {
+            end + later.inserted - later.removed
+        } else {
+            later.start + later.inserted
+        };
+        let start = self.start.min(later.start);
+        let inserted = end.max(later.start + later.inserted) - start;
+        let growth =
+            (self.inserted + later.inserted) as isize - (self.removed + later.removed) as isize;
+        Self {
+            start,
+            removed: inserted.saturating_add_signed(-growth),
+            inserted,
+        }
+    }
+}
+
 /// Core text editor combining Rope storage with cursor management.
 ///
 /// Provides insert/delete/move operations with grapheme-aware cursor
@@ -119,6 +209,8 @@ pub struct Editor {
     cursor: CursorPosition,
     /// Active selection (None when no selection).
     selection: Option<Selection>,
+    /// Secondary cursors for multi-cursor editing, in no particular order.
+    extra_cursors: Vec<Caret>,
     /// Undo stack: (operation, cursor-before).
     undo_stack: Vec<(EditOp, CursorPosition)>,
     /// Redo stack: (operation, cursor-before).
@@ -129,6 +221,11 @@ pub struct Editor {
     current_undo_size: usize,
     /// Maximum size of undo history in bytes (default 10MB).
     max_undo_size: usize,
+    /// Undo entries dropped from the bottom of the stack so far, so that
+    /// checkpoints stay valid when history is pruned.
+    undo_pruned: usize,
+    /// Lines edited since the last [`Editor::take_line_changes`].
+    line_changes: Option<LineChanges>,
 }
 
 impl Default for Editor {
@@ -145,11 +242,14 @@ impl Editor {
             rope: Rope::new(),
             cursor: CursorPosition::default(),
             selection: None,
+            extra_cursors: Vec::new(),
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             max_history: 1000,
             current_undo_size: 0,
             max_undo_size: 10 * 1024 * 1024, // 10MB default
+            undo_pruned: 0,
+            line_changes: None,
         }
     }
 
@@ -163,11 +263,14 @@ impl Editor {
             rope,
             cursor,
             selection: None,
+            extra_cursors: Vec::new(),
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             max_history: 1000,
             current_undo_size: 0,
             max_undo_size: 10 * 1024 * 1024,
+            undo_pruned: 0,
+            line_changes: None,
         }
     }
 
@@ -181,8 +284,7 @@ impl Editor {
         self.max_undo_size = bytes;
         // Prune if now over limit
         while self.current_undo_size > self.max_undo_size && !self.undo_stack.is_empty() {
-            let (op, _) = self.undo_stack.remove(0);
-            self.current_undo_size -= op.byte_len();
+            self.prune_oldest_undo();
         }
     }
 
@@ -204,11 +306,13 @@ impl Editor {
         self.cursor
     }
 
-    /// Set cursor position (will be clamped to valid bounds). Clears selection.
+    /// Set cursor position (will be clamped to valid bounds). Clears selection
+    /// and secondary cursors.
     pub fn set_cursor(&mut self, pos: CursorPosition) {
         let nav = CursorNavigator::new(&self.rope);
         self.cursor = nav.clamp(pos);
         self.selection = None;
+        self.extra_cursors.clear();
     }
 
     /// Current selection, if any.
@@ -250,6 +354,54 @@ impl Editor {
         })
     }
 
+    /// Take the block of lines changed since the last call, if any.
+    ///
+    /// Lets views that cache per-line data (e.g. syntax tokens) refresh only
+    /// the edited lines instead of rescanning the whole text.
+    pub fn take_line_changes(&mut self) -> Option<LineChanges> {
+        self.line_changes.take()
+    }
+
+    /// Record that `removed` lines at `start` became `inserted` lines.
+    fn record_line_change(&mut self, start: usize, removed: usize, inserted: usize) {
+        let change = LineChanges {
+            start,
+            removed,
+            inserted,
+        };
+        self.line_changes = Some(match self.line_changes {
+            Some(earlier) => earlier.merge(change),
+            None => change,
+        });
+    }
+
+    /// Insert into the rope, recording the touched lines.
+    fn rope_insert(&mut self, char_idx: usize, text: &str) {
+        let line = self.rope.char_to_line(char_idx);
+        let before = self.rope.len_lines();
+        self.rope.insert(char_idx, text);
+        let added = self.rope.len_lines() - before;
+        self.record_line_change(line, 1, 1 + added);
+    }
+
+    /// Remove from the rope, recording the touched lines.
+    fn rope_remove(&mut self, range: Range<usize>) {
+        let first = self.rope.char_to_line(range.start);
+        let last = self.rope.char_to_line(range.end);
+        let before = self.rope.len_lines();
+        self.rope.remove(range);
+        let removed = last - first + 1;
+        let inserted = removed - (before - self.rope.len_lines());
+        self.record_line_change(first, removed, inserted);
+    }
+
+    /// Replace the whole rope, recording every line as changed.
+    fn rope_replace(&mut self, text: &str) {
+        let before = self.rope.len_lines();
+        self.rope.replace(text);
+        self.record_line_change(0, before, self.rope.len_lines());
+    }
+
     // ====================================================================
     // Insert operations
     // ====================================================================
@@ -280,6 +432,11 @@ impl Editor {
             return;
         }
 
+        if !self.extra_cursors.is_empty() {
+            self.edit_carets(&sanitized, |_, pos| (pos, pos));
+            return;
+        }
+
         if let Some((start_byte, deleted)) = self.extract_selection() {
             let char_idx = self.rope.byte_to_char(start_byte);
 
@@ -289,7 +446,7 @@ impl Editor {
                 inserted: sanitized.clone(),
             });
 
-            self.rope.insert(char_idx, &sanitized);
+            self.rope_insert(char_idx, &sanitized);
 
             let new_byte_idx = start_byte + sanitized.len();
             let nav = CursorNavigator::new(&self.rope);
@@ -304,7 +461,7 @@ impl Editor {
                 text: sanitized.clone(),
             });
 
-            self.rope.insert(char_idx, &sanitized);
+            self.rope_insert(char_idx, &sanitized);
 
             // Move cursor to end of inserted text
             let new_byte_idx = byte_idx + sanitized.len();
@@ -326,6 +483,9 @@ impl Editor {
     ///
     /// Returns `true` if a character was deleted.
     pub fn delete_backward(&mut self) -> bool {
+        if !self.extra_cursors.is_empty() {
+            return self.edit_carets("", |nav, pos| (nav.move_left(pos), pos));
+        }
         if self.delete_selection_inner() {
             return true;
         }
@@ -348,7 +508,7 @@ impl Editor {
             text: deleted,
         });
 
-        self.rope.remove(start_char..end_char);
+        self.rope_remove(start_char..end_char);
 
         let nav = CursorNavigator::new(&self.rope);
         self.cursor = nav.from_byte_index(start_byte);
@@ -359,6 +519,9 @@ impl Editor {
     ///
     /// Returns `true` if a character was deleted.
     pub fn delete_forward(&mut self) -> bool {
+        if !self.extra_cursors.is_empty() {
+            return self.edit_carets("", |nav, pos| (pos, nav.move_right(pos)));
+        }
         if self.delete_selection_inner() {
             return true;
         }
@@ -381,7 +544,7 @@ impl Editor {
             text: deleted,
         });
 
-        self.rope.remove(start_char..end_char);
+        self.rope_remove(start_char..end_char);
 
         // Cursor stays at same position, just re-clamp
         let nav = CursorNavigator::new(&self.rope);
@@ -393,6 +556,9 @@ impl Editor {
     ///
     /// Returns `true` if any text was deleted.
     pub fn delete_word_backward(&mut self) -> bool {
+        if !self.extra_cursors.is_empty() {
+            return self.edit_carets("", |nav, pos| (nav.move_word_left(pos), pos));
+        }
         if self.delete_selection_inner() {
             return true;
         }
@@ -415,7 +581,7 @@ impl Editor {
             text: deleted,
         });
 
-        self.rope.remove(start_char..end_char);
+        self.rope_remove(start_char..end_char);
 
         let nav = CursorNavigator::new(&self.rope);
         self.cursor = nav.from_byte_index(start_byte);
@@ -426,6 +592,9 @@ impl Editor {
     ///
     /// Returns `true` if any text was deleted.
     pub fn delete_word_forward(&mut self) -> bool {
+        if !self.extra_cursors.is_empty() {
+            return self.edit_carets("", |nav, pos| (pos, nav.move_word_right(pos)));
+        }
         if self.delete_selection_inner() {
             return true;
         }
@@ -448,7 +617,7 @@ impl Editor {
             text: deleted,
         });
 
-        self.rope.remove(start_char..end_char);
+        self.rope_remove(start_char..end_char);
 
         // Re-clamp cursor just in case, matching other forward deletions
         let nav = CursorNavigator::new(&self.rope);
@@ -460,6 +629,16 @@ impl Editor {
     ///
     /// Returns `true` if any text was deleted.
     pub fn delete_to_end_of_line(&mut self) -> bool {
+        if !self.extra_cursors.is_empty() {
+            return self.edit_carets("", |nav, pos| {
+                let line_end = nav.line_end(pos);
+                if line_end == pos {
+                    (pos, nav.move_right(pos))
+                } else {
+                    (pos, line_end)
+                }
+            });
+        }
         if self.delete_selection_inner() {
             return true;
         }
@@ -483,7 +662,7 @@ impl Editor {
             text: deleted,
         });
 
-        self.rope.remove(start_char..end_char);
+        self.rope_remove(start_char..end_char);
 
         let nav = CursorNavigator::new(&self.rope);
         self.cursor = nav.clamp(self.cursor);
@@ -502,22 +681,24 @@ impl Editor {
 
         // Prune by count
         if self.undo_stack.len() > self.max_history {
-            if let Some((removed_op, _)) = self.undo_stack.first() {
-                self.current_undo_size =
-                    self.current_undo_size.saturating_sub(removed_op.byte_len());
-            }
-            self.undo_stack.remove(0);
+            self.prune_oldest_undo();
         }
 
         // Prune by size
         while self.current_undo_size > self.max_undo_size && !self.undo_stack.is_empty() {
-            let (removed_op, _) = self.undo_stack.remove(0);
-            self.current_undo_size = self.current_undo_size.saturating_sub(removed_op.byte_len());
+            self.prune_oldest_undo();
         }
 
         self.redo_stack.clear();
     }
 
+    /// Drop the oldest undo entry.
+    fn prune_oldest_undo(&mut self) {
+        let (removed_op, _) = self.undo_stack.remove(0);
+        self.current_undo_size = self.current_undo_size.saturating_sub(removed_op.byte_len());
+        self.undo_pruned += 1;
+    }
+
     /// Undo the last edit operation.
     pub fn undo(&mut self) -> bool {
         let Some((op, cursor_before)) = self.undo_stack.pop() else {
@@ -529,6 +710,7 @@ impl Editor {
         self.redo_stack.push((inverse, self.cursor));
         self.cursor = cursor_before;
         self.selection = None;
+        self.extra_cursors.clear();
         true
     }
 
@@ -546,29 +728,76 @@ impl Editor {
 
         // Ensure size limit after redo (edge case where redo grows stack)
         while self.current_undo_size > self.max_undo_size && !self.undo_stack.is_empty() {
-            let (removed_op, _) = self.undo_stack.remove(0);
-            self.current_undo_size = self.current_undo_size.saturating_sub(removed_op.byte_len());
+            self.prune_oldest_undo();
         }
 
         self.cursor = cursor_before;
         self.selection = None;
+        self.extra_cursors.clear();
         // Move cursor to the correct position after redo
         let nav = CursorNavigator::new(&self.rope);
         self.cursor = nav.clamp(self.cursor);
         true
     }
 
+    /// Current position in the undo history, for use with
+    /// [`Self::group_undo_since`].
+    ///
+    /// Positions count every entry ever pushed, so a checkpoint stays valid
+    /// when older history is pruned or cleared.
+    #[must_use]
+    pub fn undo_checkpoint(&self) -> usize {
+        self.undo_pruned + self.undo_stack.len()
+    }
+
+    /// Collapse every edit pushed since `checkpoint` into a single undo step.
+    ///
+    /// Lets a compound command (e.g. a modal "change word" followed by typed
+    /// text) undo as one unit. Edits from before the checkpoint are never
+    /// included, even if history was pruned in between.
+    pub fn group_undo_since(&mut self, checkpoint: usize) {
+        let start = checkpoint.saturating_sub(self.undo_pruned);
+        if self.undo_stack.len() <= start + 1 {
+            return;
+        }
+        let mut entries = self.undo_stack.split_off(start);
+        let cursor_before = entries[0].1;
+        let ops = entries.drain(..).map(|(op, _)| op).collect();
+        self.undo_stack.push((EditOp::Batch(ops), cursor_before));
+    }
+
+    /// Undo and forget every edit pushed since `checkpoint`.
+    ///
+    /// Rejects a command after the fact: unlike [`Self::undo`], the reverted
+    /// edits go nowhere and cannot be redone. Edits already pruned from the
+    /// history stay applied.
+    pub fn revert_to(&mut self, checkpoint: usize) {
+        let start = checkpoint.saturating_sub(self.undo_pruned);
+        while self.undo_stack.len() > start {
+            let Some((op, cursor_before)) = self.undo_stack.pop() else {
+                break;
+            };
+            self.current_undo_size = self.current_undo_size.saturating_sub(op.byte_len());
+            self.apply_op(&op.inverse());
+            self.cursor = cursor_before;
+        }
+        self.selection = None;
+        self.extra_cursors.clear();
+        let nav = CursorNavigator::new(&self.rope);
+        self.cursor = nav.clamp(self.cursor);
+    }
+
     /// Apply an edit operation directly to the rope.
     fn apply_op(&mut self, op: &EditOp) {
         match op {
             EditOp::Insert { byte_offset, text } => {
                 let char_idx = self.rope.byte_to_char(*byte_offset);
-                self.rope.insert(char_idx, text);
+                self.rope_insert(char_idx, text);
             }
             EditOp::Delete { byte_offset, text } => {
                 let start_char = self.rope.byte_to_char(*byte_offset);
                 let end_char = self.rope.byte_to_char(*byte_offset + text.len());
-                self.rope.remove(start_char..end_char);
+                self.rope_remove(start_char..end_char);
             }
             EditOp::Replace {
                 byte_offset,
@@ -577,8 +806,13 @@ impl Editor {
             } => {
                 let start_char = self.rope.byte_to_char(*byte_offset);
                 let end_char = self.rope.byte_to_char(*byte_offset + deleted.len());
-                self.rope.remove(start_char..end_char);
-                self.rope.insert(start_char, inserted);
+                self.rope_remove(start_char..end_char);
+                self.rope_insert(start_char, inserted);
+            }
+            EditOp::Batch(ops) => {
+                for op in ops {
+                    self.apply_op(op);
+                }
             }
         }
     }
@@ -600,7 +834,7 @@ impl Editor {
         let end_char = self.rope.byte_to_char(end_byte);
         let deleted = self.rope.slice(start_char..end_char).into_owned();
 
-        self.rope.remove(start_char..end_char);
+        self.rope_remove(start_char..end_char);
         let nav = CursorNavigator::new(&self.rope);
         self.cursor = nav.from_byte_index(start_byte);
 
@@ -626,72 +860,52 @@ impl Editor {
 
     /// Move cursor left by one grapheme.
     pub fn move_left(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.move_left(self.cursor);
+        self.move_carets(|nav, pos| nav.move_left(pos));
     }
 
     /// Move cursor right by one grapheme.
     pub fn move_right(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.move_right(self.cursor);
+        self.move_carets(|nav, pos| nav.move_right(pos));
     }
 
     /// Move cursor up one line.
     pub fn move_up(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.move_up(self.cursor);
+        self.move_carets(|nav, pos| nav.move_up(pos));
     }
 
     /// Move cursor down one line.
     pub fn move_down(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.move_down(self.cursor);
+        self.move_carets(|nav, pos| nav.move_down(pos));
     }
 
     /// Move cursor left by one word.
     pub fn move_word_left(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.move_word_left(self.cursor);
+        self.move_carets(|nav, pos| nav.move_word_left(pos));
     }
 
     /// Move cursor right by one word.
     pub fn move_word_right(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.move_word_right(self.cursor);
+        self.move_carets(|nav, pos| nav.move_word_right(pos));
     }
 
     /// Move cursor to start of line.
     pub fn move_to_line_start(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.line_start(self.cursor);
+        self.move_carets(|nav, pos| nav.line_start(pos));
     }
 
     /// Move cursor to end of line.
     pub fn move_to_line_end(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.line_end(self.cursor);
+        self.move_carets(|nav, pos| nav.line_end(pos));
     }
 
     /// Move cursor to start of document.
     pub fn move_to_document_start(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.document_start();
+        self.move_carets(|nav, _| nav.document_start());
     }
 
     /// Move cursor to end of document.
     pub fn move_to_document_end(&mut self) {
-        self.selection = None;
-        let nav = CursorNavigator::new(&self.rope);
-        self.cursor = nav.document_end();
+        self.move_carets(|nav, _| nav.document_end());
     }
 
     // ====================================================================
@@ -738,11 +952,16 @@ impl Editor {
             head: end,
         });
         self.cursor = end;
+        self.extra_cursors.clear();
     }
 
-    /// Clear current selection without moving cursor.
+    /// Clear current selection (of every cursor) without moving cursors.
     pub fn clear_selection(&mut self) {
         self.selection = None;
+        for caret in &mut self.extra_cursors {
+            caret.selection = None;
+        }
+        self.merge_carets();
     }
 
     /// Get selected text, if any non-empty selection exists.
@@ -761,11 +980,32 @@ impl Editor {
 
     fn extend_selection(
         &mut self,
-        f: impl FnOnce(&CursorNavigator<'_>, CursorPosition) -> CursorPosition,
+        f: impl Fn(&CursorNavigator<'_>, CursorPosition) -> CursorPosition,
     ) {
         let nav = CursorNavigator::new(&self.rope);
         let new_head = f(&nav, self.cursor);
+        for caret in &mut self.extra_cursors {
+            let anchor = caret.selection.map_or(caret.cursor, |sel| sel.anchor);
+            caret.cursor = f(&nav, caret.cursor);
+            caret.selection = Some(Selection {
+                anchor,
+                head: caret.cursor,
+            });
+        }
         self.extend_selection_to(new_head);
+        self.merge_carets();
+    }
+
+    /// Move every cursor with `f`, clearing selections.
+    fn move_carets(&mut self, f: impl Fn(&CursorNavigator<'_>, CursorPosition) -> CursorPosition) {
+        let nav = CursorNavigator::new(&self.rope);
+        self.selection = None;
+        self.cursor = f(&nav, self.cursor);
+        for caret in &mut self.extra_cursors {
+            caret.selection = None;
+            caret.cursor = f(&nav, caret.cursor);
+        }
+        self.merge_carets();
     }
 
     /// Extend selection to a specific cursor position.
@@ -783,26 +1023,449 @@ impl Editor {
         });
     }
 
+    // ====================================================================
+    // Multiple cursors
+    // ====================================================================
+
+    /// All cursors (primary included) in document order.
+    #[must_use]
+    pub fn carets(&self) -> Vec<Caret> {
+        let nav = CursorNavigator::new(&self.rope);
+        let mut carets: Vec<Caret> = std::iter::once(self.primary_caret())
+            .chain(self.extra_cursors.iter().copied())
+            .collect();
+        carets.sort_by_key(|caret| caret.byte_range(&nav));
+        carets
+    }
+
+    /// Number of cursors, including the primary one.
+    #[must_use]
+    pub fn cursor_count(&self) -> usize {
+        1 + self.extra_cursors.len()
+    }
+
+    /// Whether secondary cursors are active.
+    #[must_use]
+    pub fn has_multiple_cursors(&self) -> bool {
+        !self.extra_cursors.is_empty()
+    }
+
+    /// Non-empty selections of every cursor, in document order.
+    #[must_use]
+    pub fn selections(&self) -> Vec<Selection> {
+        self.carets()
+            .into_iter()
+            .filter_map(|caret| caret.selection.filter(|sel| !sel.is_empty()))
+            .collect()
+    }
+
+    /// Selected text of every cursor with a non-empty selection, in document
+    /// order.
+    #[must_use]
+    pub fn selected_texts(&self) -> Vec<String> {
+        let nav = CursorNavigator::new(&self.rope);
+        self.selections()
+            .iter()
+            .map(|sel| {
+                let (start, end) = sel.byte_range(&nav);
+                let start_char = self.rope.byte_to_char(start);
+                let end_char = self.rope.byte_to_char(end);
+                self.rope.slice(start_char..end_char).into_owned()
+            })
+            .collect()
+    }
+
+    /// Add a secondary cursor (clamped to valid bounds).
+    pub fn add_cursor(&mut self, pos: CursorPosition) {
+        let nav = CursorNavigator::new(&self.rope);
+        self.extra_cursors.push(Caret {
+            cursor: nav.clamp(pos),
+            selection: None,
+        });
+        self.merge_carets();
+    }
+
+    /// Add a cursor one line above the topmost cursor, at the same visual
+    /// column.
+    ///
+    /// Returns `false` when the topmost cursor is already on the first line.
+    pub fn add_cursor_above(&mut self) -> bool {
+        self.add_cursor_vertical(true)
+    }
+
+    /// Add a cursor one line below the bottommost cursor, at the same visual
+    /// column.
+    ///
+    /// Returns `false` when the bottommost cursor is already on the last line.
+    pub fn add_cursor_below(&mut self) -> bool {
+        self.add_cursor_vertical(false)
+    }
+
+    fn add_cursor_vertical(&mut self, up: bool) -> bool {
+        let carets = self.carets();
+        let edge = if up { carets.first() } else { carets.last() };
+        let Some(edge) = edge.map(|caret| caret.cursor) else {
+            return false;
+        };
+        let nav = CursorNavigator::new(&self.rope);
+        let target = if up {
+            nav.move_up(edge)
+        } else {
+            nav.move_down(edge)
+        };
+        if target.line == edge.line {
+            return false;
+        }
+        self.add_cursor(target);
+        true
+    }
+
+    /// Select the next occurrence of the primary selection with a new cursor.
+    ///
+    /// Without a selection, the word under the primary cursor is selected
+    /// first. The search wraps around the end of the document and skips
+    /// occurrences that are already selected; the new cursor becomes the
+    /// primary one. Returns `false` when nothing was added.
+    pub fn add_next_occurrence(&mut self) -> bool {
+        let nav = CursorNavigator::new(&self.rope);
+        let Some(sel) = self.selection.filter(|sel| !sel.is_empty()) else {
+            let Some((start, end)) = self.word_range_at(self.cursor) else {
+                return false;
+            };
+            let head = nav.from_byte_index(end);
+            self.selection = Some(Selection {
+                anchor: nav.from_byte_index(start),
+                head,
+            });
+            self.cursor = head;
+            self.merge_carets();
+            return true;
+        };
+
+        let (start, end) = sel.byte_range(&nav);
+        let text = self.rope.to_string();
+        let needle = &text[start..end];
+        let taken: Vec<(usize, usize)> = self
+            .carets()
+            .iter()
+            .map(|caret| caret.byte_range(&nav))
+            .collect();
+        let found = text[end..]
+            .match_indices(needle)
+            .map(|(i, _)| end + i)
+            .chain(text[..end].match_indices(needle).map(|(i, _)| i))
+            .find(|&at| !taken.iter().any(|&(a, b)| at < b && a < at + needle.len()));
+        let Some(at) = found else {
+            return false;
+        };
+
+        let head = nav.from_byte_index(at + needle.len());
+        let new_primary = Caret {
+            cursor: head,
+            selection: Some(Selection {
+                anchor: nav.from_byte_index(at),
+                head,
+            }),
+        };
+        let old_primary = self.primary_caret();
+        self.extra_cursors.push(old_primary);
+        self.cursor = new_primary.cursor;
+        self.selection = new_primary.selection;
+        self.merge_carets();
+        true
+    }
+
+    /// Column (box) selection between two positions.
+    ///
+    /// Creates one cursor per line from `anchor.line` to `head.line`, each
+    /// selecting the visual columns between `anchor.visual_col` and
+    /// `head.visual_col` (clamped to the line). The cursor on `head`'s line
+    /// becomes the primary one.
+    pub fn select_block(&mut self, anchor: CursorPosition, head: CursorPosition) {
+        let nav = CursorNavigator::new(&self.rope);
+        let last_line = self.line_count().saturating_sub(1);
+        let bottom = anchor.line.max(head.line).min(last_line);
+        let top = anchor.line.min(head.line).min(bottom);
+
+        let mut carets: Vec<Caret> = (top..=bottom)
+            .map(|line| {
+                let a = nav.from_visual_col(line, anchor.visual_col);
+                let h = nav.from_visual_col(line, head.visual_col);
+                Caret {
+                    cursor: h,
+                    selection: (a != h).then_some(Selection { anchor: a, head: h }),
+                }
+            })
+            .collect();
+        let primary = carets.remove(head.line.min(bottom) - top);
+        self.cursor = primary.cursor;
+        self.selection = primary.selection;
+        self.extra_cursors = carets;
+        self.merge_carets();
+    }
+
+    /// Drop all secondary cursors, keeping the primary one.
+    pub fn clear_extra_cursors(&mut self) {
+        self.extra_cursors.clear();
+    }
+
+    fn primary_caret(&self) -> Caret {
+        Caret {
+            cursor: self.cursor,
+            selection: self.selection,
+        }
+    }
+
+    /// Byte range of the word (alphanumerics and `_`) touching `pos`.
+    fn word_range_at(&self, pos: CursorPosition) -> Option<(usize, usize)> {
+        let nav = CursorNavigator::new(&self.rope);
+        let line = self.line_text(pos.line)?;
+        let line_start = nav.to_byte_index(nav.line_start(pos));
+        let at = nav
+            .to_byte_index(pos)
+            .saturating_sub(line_start)
+            .min(line.len());
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let start = line[..at]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| is_word(c))
+            .last()
+            .map_or(at, |(i, _)| i);
+        let end = line[at..]
+            .char_indices()
+            .find(|&(_, c)| !is_word(c))
+            .map_or(line.len(), |(i, _)| at + i);
+        (start < end).then_some((line_start + start, line_start + end))
+    }
+
+    /// Merge cursors whose selections overlap or touch, and cursors sharing
+    /// a position. The primary cursor survives any merge it takes part in.
+    fn merge_carets(&mut self) {
+        if self.extra_cursors.is_empty() {
+            return;
+        }
+        let nav = CursorNavigator::new(&self.rope);
+        let mut carets: Vec<(usize, usize, Caret, bool)> =
+            std::iter::once((self.primary_caret(), true))
+                .chain(self.extra_cursors.iter().map(|&caret| (caret, false)))
+                .map(|(caret, primary)| {
+                    let (start, end) = caret.byte_range(&nav);
+                    (start, end, caret, primary)
+                })
+                .collect();
+        carets.sort_by_key(|&(start, end, _, _)| (start, end));
+
+        let mut merged: Vec<(usize, usize, Caret, bool)> = Vec::with_capacity(carets.len());
+        for (start, end, caret, primary) in carets {
+            if let Some(last) = merged.last_mut()
+                && (start < last.1 || start == last.0 || (start == last.1 && start == end))
+            {
+                let end = end.max(last.1);
+                let head = nav.from_byte_index(end);
+                last.2 = Caret {
+                    cursor: head,
+                    selection: (end > last.0).then(|| Selection {
+                        anchor: nav.from_byte_index(last.0),
+                        head,
+                    }),
+                };
+                last.1 = end;
+                last.3 |= primary;
+                continue;
+            }
+            merged.push((start, end, caret, primary));
+        }
+
+        let primary_idx = merged.iter().position(|m| m.3).unwrap_or(0);
+        let primary = merged.remove(primary_idx).2;
+        self.cursor = primary.cursor;
+        self.selection = primary.selection;
+        self.extra_cursors = merged.into_iter().map(|m| m.2).collect();
+    }
+
+    /// Apply one edit at every cursor as a single undo step.
+    ///
+    /// Each cursor replaces its selection, or the range `range` returns for
+    /// its position, with `inserted`, and ends up after the inserted text.
+    /// Edits run back to front so earlier byte offsets stay valid.
+    fn edit_carets(
+        &mut self,
+        inserted: &str,
+        range: impl Fn(&CursorNavigator<'_>, CursorPosition) -> (CursorPosition, CursorPosition),
+    ) -> bool {
+        self.merge_carets();
+        let nav = CursorNavigator::new(&self.rope);
+        let mut edits: Vec<(usize, usize, bool)> = std::iter::once((self.primary_caret(), true))
+            .chain(self.extra_cursors.iter().map(|&caret| (caret, false)))
+            .map(|(caret, primary)| {
+                let (start, end) = match caret.selection {
+                    Some(sel) if !sel.is_empty() => sel.byte_range(&nav),
+                    _ => {
+                        let (a, b) = range(&nav, caret.cursor);
+                        let (a, b) = (nav.to_byte_index(a), nav.to_byte_index(b));
+                        (a.min(b), a.max(b))
+                    }
+                };
+                (start, end, primary)
+            })
+            .collect();
+        edits.sort_by_key(|&(start, _, _)| start);
+        // Ranges computed per cursor (e.g. word deletions) may overlap.
+        let mut floor = 0;
+        for edit in &mut edits {
+            edit.0 = edit.0.max(floor);
+            edit.1 = edit.1.max(edit.0);
+            floor = edit.1;
+        }
+
+        let mut ops = Vec::new();
+        for &(start, end, _) in edits.iter().rev() {
+            if start == end && inserted.is_empty() {
+                continue;
+            }
+            let start_char = self.rope.byte_to_char(start);
+            let end_char = self.rope.byte_to_char(end);
+            let deleted = self.rope.slice(start_char..end_char).into_owned();
+            self.rope_remove(start_char..end_char);
+            self.rope_insert(start_char, inserted);
+            ops.push(if deleted.is_empty() {
+                EditOp::Insert {
+                    byte_offset: start,
+                    text: inserted.to_string(),
+                }
+            } else if inserted.is_empty() {
+                EditOp::Delete {
+                    byte_offset: start,
+                    text: deleted,
+                }
+            } else {
+                EditOp::Replace {
+                    byte_offset: start,
+                    deleted,
+                    inserted: inserted.to_string(),
+                }
+            });
+        }
+        if ops.is_empty() {
+            return false;
+        }
+        self.push_undo(EditOp::Batch(ops));
+
+        let nav = CursorNavigator::new(&self.rope);
+        let mut shift: isize = 0;
+        let mut extras = Vec::with_capacity(edits.len().saturating_sub(1));
+        for (start, end, primary) in edits {
+            let edited = start != end || !inserted.is_empty();
+            let mut byte = start.saturating_add_signed(shift);
+            if edited {
+                byte += inserted.len();
+                shift += inserted.len() as isize - (end - start) as isize;
+            }
+            let caret = Caret {
+                cursor: nav.from_byte_index(byte),
+                selection: None,
+            };
+            if primary {
+                self.cursor = caret.cursor;
+                self.selection = None;
+            } else {
+                extras.push(caret);
+            }
+        }
+        self.extra_cursors = extras;
+        self.merge_carets();
+        true
+    }
+
+    /// Replace byte ranges (sorted, non-overlapping) as a single undo step.
+    ///
+    /// The cursor ends after the last replacement; selections and secondary
+    /// cursors are cleared. Returns the number of ranges replaced.
+    #[cfg(feature = "regex-search")]
+    pub(crate) fn replace_byte_ranges(&mut self, edits: Vec<(Range<usize>, String)>) -> usize {
+        let Some(last) = edits.last() else {
+            return 0;
+        };
+        let shift: isize = edits[..edits.len() - 1]
+            .iter()
+            .map(|(range, text)| text.len() as isize - range.len() as isize)
+            .sum();
+        let cursor_byte = last
+            .0
+            .start
+            .saturating_add_signed(shift)
+            .saturating_add(last.1.len());
+
+        let mut ops = Vec::with_capacity(edits.len());
+        for (range, inserted) in edits.iter().rev() {
+            let start_char = self.rope.byte_to_char(range.start);
+            let end_char = self.rope.byte_to_char(range.end);
+            let deleted = self.rope.slice(start_char..end_char).into_owned();
+            self.rope_remove(start_char..end_char);
+            self.rope_insert(start_char, inserted);
+            ops.push(EditOp::Replace {
+                byte_offset: range.start,
+                deleted,
+                inserted: inserted.clone(),
+            });
+        }
+        self.push_undo(EditOp::Batch(ops));
+
+        let nav = CursorNavigator::new(&self.rope);
+        self.cursor = nav.from_byte_index(cursor_byte);
+        self.selection = None;
+        self.extra_cursors.clear();
+        edits.len()
+    }
+
     // ====================================================================
     // Content replacement
     // ====================================================================
 
     /// Replace all content and reset cursor to end. Clears undo history.
     pub fn set_text(&mut self, text: &str) {
-        self.rope.replace(text);
+        self.rope_replace(text);
         let nav = CursorNavigator::new(&self.rope);
         self.cursor = nav.document_end();
         self.selection = None;
+        self.extra_cursors.clear();
+        self.undo_pruned += self.undo_stack.len();
         self.undo_stack.clear();
         self.redo_stack.clear();
         self.current_undo_size = 0;
     }
 
+    /// Replace all content as one undoable edit and move the cursor to the
+    /// end.
+    ///
+    /// Unlike [`Self::set_text`] the history is kept, so syncing the editor
+    /// with text changed elsewhere can be undone like any other edit. Does
+    /// nothing if the content is already `text`.
+    pub fn replace_text(&mut self, text: &str) {
+        let deleted = self.rope.to_string();
+        if deleted == text {
+            return;
+        }
+        self.selection = None;
+        self.extra_cursors.clear();
+        self.push_undo(EditOp::Replace {
+            byte_offset: 0,
+            deleted,
+            inserted: text.to_string(),
+        });
+        self.rope_replace(text);
+        let nav = CursorNavigator::new(&self.rope);
+        self.cursor = nav.document_end();
+    }
+
     /// Clear all content and reset cursor. Clears undo history.
     pub fn clear(&mut self) {
-        self.rope.clear();
+        self.rope_replace("");
         self.cursor = CursorPosition::default();
         self.selection = None;
+        self.extra_cursors.clear();
+        self.undo_pruned += self.undo_stack.len();
         self.undo_stack.clear();
         self.redo_stack.clear();
         self.current_undo_size = 0;
@@ -1197,6 +1860,83 @@ mod tests {
         assert!(!ed.can_redo());
     }
 
+    #[test]
+    fn line_changes_cover_edited_lines() {
+        let mut ed = Editor::with_text("a\nb\nc\nd");
+        assert_eq!(ed.take_line_changes(), None);
+
+        ed.set_cursor(CursorPosition::new(1, 1, 1));
+        ed.insert_text("x\ny");
+        assert_eq!(ed.text(), "a\nbx\ny\nc\nd");
+        assert_eq!(
+            ed.take_line_changes(),
+            Some(LineChanges {
+                start: 1,
+                removed: 1,
+                inserted: 2,
+            })
+        );
+        assert_eq!(ed.take_line_changes(), None);
+
+        // Two edits merge into one block spanning both.
+        ed.set_cursor(CursorPosition::new(0, 1, 1));
+        ed.delete_backward();
+        ed.set_cursor(CursorPosition::new(3, 0, 0));
+        ed.delete_backward();
+        assert_eq!(ed.text(), "\nbx\nyc\nd");
+        assert_eq!(
+            ed.take_line_changes(),
+            Some(LineChanges {
+                start: 0,
+                removed: 4,
+                inserted: 3,
+            })
+        );
+
+        ed.undo();
+        ed.undo();
+        assert_eq!(
+            ed.take_line_changes().map(|c| c.inserted - c.removed),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn replace_text_keeps_undo() {
+        let mut ed = Editor::new();
+        ed.insert_text("abc");
+        ed.replace_text("xyz");
+        assert_eq!(ed.text(), "xyz");
+        assert!(ed.undo());
+        assert_eq!(ed.text(), "abc");
+        assert!(ed.undo());
+        assert_eq!(ed.text(), "");
+        assert!(ed.redo());
+        assert!(ed.redo());
+        assert_eq!(ed.text(), "xyz");
+
+        ed.replace_text("xyz");
+        assert!(!ed.can_redo());
+        assert!(ed.undo());
+        assert_eq!(ed.text(), "abc");
+    }
+
+    #[test]
+    fn revert_to_drops_edits_without_redo() {
+        let mut ed = Editor::new();
+        ed.insert_text("ab");
+        let checkpoint = ed.undo_checkpoint();
+        ed.insert_char('c');
+        ed.delete_backward();
+        ed.delete_backward();
+        ed.revert_to(checkpoint);
+        assert_eq!(ed.text(), "ab");
+        assert_eq!(ed.cursor(), CursorPosition::new(0, 2, 2));
+        assert!(!ed.can_redo());
+        assert!(ed.undo());
+        assert_eq!(ed.text(), "");
+    }
+
     #[test]
     fn clear_clears_undo() {
         let mut ed = Editor::new();
@@ -1627,6 +2367,142 @@ mod tests {
         assert!(start <= end);
         assert_eq!(end - start, 3);
     }
+
+    // ================================================================
+    // Multiple cursors
+    // ================================================================
+
+    #[test]
+    fn multi_cursor_insert_applies_at_every_cursor() {
+        let mut ed = Editor::with_text("one\ntwo\nthree");
+        ed.move_to_document_start();
+        assert!(ed.add_cursor_below());
+        assert!(ed.add_cursor_below());
+        assert_eq!(ed.cursor_count(), 3);
+        ed.insert_text("> ");
+        assert_eq!(ed.text(), "> one\n> two\n> three");
+        let cols: Vec<usize> = ed.carets().iter().map(|c| c.cursor.grapheme).collect();
+        assert_eq!(cols, vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn multi_cursor_edit_undoes_as_one_step() {
+        let mut ed = Editor::with_text("ab\ncd");
+        ed.set_cursor(CursorPosition::new(0, 2, 2));
+        ed.add_cursor(CursorPosition::new(1, 2, 2));
+        assert!(ed.delete_backward());
+        assert_eq!(ed.text(), "a\nc");
+        assert!(ed.undo());
+        assert_eq!(ed.text(), "ab\ncd");
+        assert!(!ed.has_multiple_cursors());
+        assert!(ed.redo());
+        assert_eq!(ed.text(), "a\nc");
+    }
+
+    #[test]
+    fn multi_cursor_offsets_adjust_on_same_line() {
+        let mut ed = Editor::with_text("a b c");
+        ed.set_cursor(CursorPosition::new(0, 1, 1));
+        ed.add_cursor(CursorPosition::new(0, 3, 3));
+        ed.add_cursor(CursorPosition::new(0, 5, 5));
+        ed.insert_text("!!");
+        assert_eq!(ed.text(), "a!! b!! c!!");
+        ed.delete_backward();
+        assert_eq!(ed.text(), "a! b! c!");
+        assert_eq!(ed.cursor().grapheme, 2);
+    }
+
+    #[test]
+    fn cursors_merge_when_they_meet() {
+        let mut ed = Editor::with_text("abc");
+        ed.set_cursor(CursorPosition::new(0, 1, 1));
+        ed.add_cursor(CursorPosition::new(0, 2, 2));
+        ed.move_to_line_start();
+        assert_eq!(ed.cursor_count(), 1);
+
+        ed.set_cursor(CursorPosition::new(0, 0, 0));
+        ed.add_cursor(CursorPosition::new(0, 2, 2));
+        ed.select_right();
+        ed.select_right();
+        // [0, 2) and [2, 3) touch but do not overlap.
+        assert_eq!(ed.cursor_count(), 2);
+        ed.select_left();
+        ed.select_right();
+        ed.select_right();
+        assert_eq!(ed.cursor_count(), 1);
+        assert_eq!(ed.selected_text().as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn add_next_occurrence_selects_word_then_matches() {
+        let mut ed = Editor::with_text("foo bar foo baz foo");
+        ed.set_cursor(CursorPosition::new(0, 1, 1));
+        assert!(ed.add_next_occurrence());
+        assert_eq!(ed.selected_text().as_deref(), Some("foo"));
+        assert_eq!(ed.cursor_count(), 1);
+        assert!(ed.add_next_occurrence());
+        assert!(ed.add_next_occurrence());
+        assert_eq!(ed.cursor_count(), 3);
+        assert!(!ed.add_next_occurrence());
+        assert_eq!(ed.selected_texts(), vec!["foo", "foo", "foo"]);
+        ed.insert_text("qux");
+        assert_eq!(ed.text(), "qux bar qux baz qux");
+    }
+
+    #[test]
+    fn block_selection_covers_columns_per_line() {
+        let mut ed = Editor::with_text("abcd\nef\nghij");
+        ed.select_block(CursorPosition::new(0, 1, 1), CursorPosition::new(2, 3, 3));
+        assert_eq!(ed.cursor_count(), 3);
+        assert_eq!(ed.selected_texts(), vec!["bc", "f", "hi"]);
+        assert_eq!(ed.cursor().line, 2);
+        ed.delete_backward();
+        assert_eq!(ed.text(), "ad\ne\ngj");
+    }
+
+    #[test]
+    fn overlapping_word_deletions_are_clipped() {
+        let mut ed = Editor::with_text("abcdef");
+        ed.set_cursor(CursorPosition::new(0, 3, 3));
+        ed.add_cursor(CursorPosition::new(0, 6, 6));
+        assert!(ed.delete_word_backward());
+        assert_eq!(ed.text(), "");
+        assert_eq!(ed.cursor_count(), 1);
+    }
+
+    #[test]
+    fn grouped_edits_undo_and_redo_together() {
+        let mut ed = Editor::with_text("hello world");
+        ed.set_cursor(CursorPosition::new(0, 0, 0));
+        let checkpoint = ed.undo_checkpoint();
+        ed.delete_forward();
+        ed.insert_text("J");
+        ed.move_to_line_end();
+        ed.insert_char('!');
+        ed.group_undo_since(checkpoint);
+        assert_eq!(ed.text(), "Jello world!");
+        assert!(ed.undo());
+        assert_eq!(ed.text(), "hello world");
+        assert_eq!(ed.cursor(), CursorPosition::new(0, 0, 0));
+        assert!(!ed.can_undo());
+        assert!(ed.redo());
+        assert_eq!(ed.text(), "Jello world!");
+    }
+
+    #[test]
+    fn grouping_survives_history_pruning() {
+        let mut ed = Editor::new();
+        ed.insert_char('a');
+        let checkpoint = ed.undo_checkpoint();
+        ed.set_max_history(2);
+        ed.insert_char('b');
+        ed.insert_char('c');
+        ed.insert_char('d');
+        ed.group_undo_since(checkpoint);
+        assert!(ed.undo());
+        assert_eq!(ed.text(), "ab");
+        assert!(!ed.can_undo());
+    }
 }
 
 // ================================================================
@@ -0,0 +1,647 @@
+#![forbid(unsafe_code)]
+
+//! Find and replace over a [`Rope`] with literal or regex patterns.
+//!
+//! Feature-gated behind the `regex-search` feature flag.
+//!
+//! [`FindQuery`] compiles a pattern once with its [`FindOptions`] (regex or
+//! literal, case, whole word, search-in-selection) and finds matches as byte
+//! ranges. Regex replacements expand capture groups (`$1`, `${name}`);
+//! literal replacements are inserted verbatim.
+//!
+//! [`Finder`] keeps the results for a search box that updates on every
+//! keystroke, and [`Editor`] gains `find_next`, `find_prev`,
+//! `replace_current` and `replace_all`. A replace-all is undone in one step.
+//!
+//! # Example
+//! ```
+//! use ftui_text::editor::Editor;
+//! use ftui_text::find_replace::{FindOptions, FindQuery};
+//!
+//! let mut ed = Editor::with_text("width = 10\nheight = 20");
+//! let query = FindQuery::new(r"(\w+) = (\d+)", FindOptions::new().regex(true)).unwrap();
+//! assert_eq!(ed.find_all(&query).len(), 2);
+//!
+//! assert_eq!(ed.replace_all(&query, "$1: $2"), 2);
+//! assert_eq!(ed.text(), "width: 10\nheight: 20");
+//! ed.undo();
+//! assert_eq!(ed.text(), "width = 10\nheight = 20");
+//! ```
+
+use std::fmt;
+use std::ops::Range;
+
+use regex::{Captures, Regex, RegexBuilder};
+
+use crate::cursor::CursorNavigator;
+use crate::editor::Editor;
+use crate::rope::Rope;
+use crate::search::SearchResult;
+
+/// Upper bound on the compiled size of a search regex.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// How a [`FindQuery`] interprets and matches its pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FindOptions {
+    /// Treat the pattern as a regular expression instead of literal text.
+    pub regex: bool,
+    /// Ignore case when matching.
+    pub case_insensitive: bool,
+    /// Only accept matches not adjacent to word characters
+    /// (alphanumerics and `_`).
+    pub whole_word: bool,
+    /// Restrict [`Editor::find_all`] and [`Editor::replace_all`] to the
+    /// current selection.
+    pub in_selection: bool,
+}
+
+impl FindOptions {
+    /// Literal, case-sensitive matching over the whole text.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            regex: false,
+            case_insensitive: false,
+            whole_word: false,
+            in_selection: false,
+        }
+    }
+
+    /// Set regex mode.
+    #[must_use]
+    pub const fn regex(mut self, regex: bool) -> Self {
+        self.regex = regex;
+        self
+    }
+
+    /// Set case-insensitive matching.
+    #[must_use]
+    pub const fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Set whole-word matching.
+    #[must_use]
+    pub const fn whole_word(mut self, whole_word: bool) -> Self {
+        self.whole_word = whole_word;
+        self
+    }
+
+    /// Set search-in-selection.
+    #[must_use]
+    pub const fn in_selection(mut self, in_selection: bool) -> Self {
+        self.in_selection = in_selection;
+        self
+    }
+}
+
+/// Errors from compiling a [`FindQuery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FindError {
+    /// The pattern is empty.
+    EmptyPattern,
+    /// The regex did not compile.
+    InvalidPattern(String),
+}
+
+impl fmt::Display for FindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyPattern => write!(f, "empty search pattern"),
+            Self::InvalidPattern(msg) => write!(f, "invalid search pattern: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for FindError {}
+
+/// A compiled search pattern.
+#[derive(Debug, Clone)]
+pub struct FindQuery {
+    pattern: String,
+    options: FindOptions,
+    regex: Regex,
+}
+
+impl FindQuery {
+    /// Compile `pattern` with the given options.
+    ///
+    /// Literal patterns are escaped; `^` and `$` in regex patterns match at
+    /// line boundaries.
+    pub fn new(pattern: &str, options: FindOptions) -> Result<Self, FindError> {
+        if pattern.is_empty() {
+            return Err(FindError::EmptyPattern);
+        }
+        let source = if options.regex {
+            pattern.to_string()
+        } else {
+            regex::escape(pattern)
+        };
+        let regex = RegexBuilder::new(&source)
+            .case_insensitive(options.case_insensitive)
+            .multi_line(true)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .build()
+            .map_err(|err| FindError::InvalidPattern(err.to_string()))?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            options,
+            regex,
+        })
+    }
+
+    /// The pattern as given.
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// The options the pattern was compiled with.
+    #[must_use]
+    pub fn options(&self) -> FindOptions {
+        self.options
+    }
+
+    /// All matches in the rope.
+    #[must_use]
+    pub fn find_all(&self, rope: &Rope) -> Vec<SearchResult> {
+        self.find_in_str(&rope.to_string(), None)
+    }
+
+    /// Matches in `haystack`, optionally restricted to the byte range `scope`.
+    ///
+    /// Matches are non-overlapping and in order. Text outside `scope` still
+    /// counts for anchors and word boundaries.
+    #[must_use]
+    pub fn find_in_str(&self, haystack: &str, scope: Option<Range<usize>>) -> Vec<SearchResult> {
+        self.captures_in(haystack, scope)
+            .iter()
+            .filter_map(|caps| caps.get(0))
+            .map(|m| SearchResult::new(m.start(), m.end()))
+            .collect()
+    }
+
+    /// Matches in `haystack` paired with their replacement text.
+    ///
+    /// Regex queries expand `$1`, `${name}` and `$$` in `replacement`;
+    /// literal queries use it verbatim.
+    #[must_use]
+    pub fn replacements(
+        &self,
+        haystack: &str,
+        scope: Option<Range<usize>>,
+        replacement: &str,
+    ) -> Vec<(SearchResult, String)> {
+        self.captures_in(haystack, scope)
+            .iter()
+            .filter_map(|caps| {
+                let m = caps.get(0)?;
+                let text = if self.options.regex {
+                    let mut expanded = String::new();
+                    caps.expand(replacement, &mut expanded);
+                    expanded
+                } else {
+                    replacement.to_string()
+                };
+                Some((SearchResult::new(m.start(), m.end()), text))
+            })
+            .collect()
+    }
+
+    /// First match at or after byte `from`, wrapping to the start.
+    ///
+    /// An empty match exactly at `from` is skipped so repeated calls advance.
+    #[must_use]
+    pub fn find_next(&self, haystack: &str, from: usize) -> Option<SearchResult> {
+        let matches = self.find_in_str(haystack, None);
+        matches
+            .iter()
+            .find(|m| m.range.start >= from && !(m.range.is_empty() && m.range.start == from))
+            .or(matches.first())
+            .cloned()
+    }
+
+    /// Last match starting before byte `before`, wrapping to the end.
+    #[must_use]
+    pub fn find_prev(&self, haystack: &str, before: usize) -> Option<SearchResult> {
+        let matches = self.find_in_str(haystack, None);
+        matches
+            .iter()
+            .rev()
+            .find(|m| m.range.start < before)
+            .or(matches.last())
+            .cloned()
+    }
+
+    fn captures_in<'h>(&self, haystack: &'h str, scope: Option<Range<usize>>) -> Vec<Captures<'h>> {
+        let end = scope
+            .as_ref()
+            .map_or(haystack.len(), |scope| scope.end)
+            .min(haystack.len());
+        let mut at = scope.map_or(0, |scope| scope.start).min(end);
+
+        let mut found = Vec::new();
+        while at <= end {
+            let Some(caps) = self.regex.captures_at(haystack, at) else {
+                break;
+            };
+            let Some(m) = caps.get(0) else {
+                break;
+            };
+            if m.end() > end {
+                break;
+            }
+            at = if m.is_empty() {
+                next_char_boundary(haystack, m.end())
+            } else {
+                m.end()
+            };
+            if self.options.whole_word && !is_whole_word(haystack, m.range()) {
+                continue;
+            }
+            found.push(caps);
+        }
+        found
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_whole_word(haystack: &str, range: Range<usize>) -> bool {
+    let before = haystack[..range.start].chars().next_back();
+    let after = haystack[range.end..].chars().next();
+    !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+}
+
+fn next_char_boundary(s: &str, at: usize) -> usize {
+    at + s[at..].chars().next().map_or(1, char::len_utf8)
+}
+
+/// Incremental search state behind a find box.
+///
+/// Call [`set_pattern`](Self::set_pattern) on every keystroke. While the
+/// pattern does not compile (e.g. an unclosed group mid-typing), the
+/// previous matches are kept and [`error`](Self::error) reports why.
+#[derive(Debug, Clone, Default)]
+pub struct Finder {
+    query: Option<FindQuery>,
+    scope: Option<Range<usize>>,
+    matches: Vec<SearchResult>,
+    current: Option<usize>,
+    error: Option<FindError>,
+}
+
+impl Finder {
+    /// Create an empty finder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the pattern and search again.
+    ///
+    /// The current match becomes the first one at or after byte `origin`
+    /// (usually the cursor). Returns the number of matches.
+    pub fn set_pattern(
+        &mut self,
+        pattern: &str,
+        options: FindOptions,
+        rope: &Rope,
+        origin: usize,
+    ) -> usize {
+        if pattern.is_empty() {
+            self.clear();
+            return 0;
+        }
+        match FindQuery::new(pattern, options) {
+            Ok(query) => {
+                self.query = Some(query);
+                self.error = None;
+                self.refresh(rope, origin)
+            }
+            Err(err) => {
+                self.error = Some(err);
+                self.matches.len()
+            }
+        }
+    }
+
+    /// Restrict matches to a byte range (search-in-selection), or lift the
+    /// restriction with `None`. Takes effect on the next refresh.
+    pub fn set_scope(&mut self, scope: Option<Range<usize>>) {
+        self.scope = scope;
+    }
+
+    /// Search again after the text changed. Returns the number of matches.
+    pub fn refresh(&mut self, rope: &Rope, origin: usize) -> usize {
+        self.matches = match &self.query {
+            Some(query) => query.find_in_str(&rope.to_string(), self.scope.clone()),
+            None => Vec::new(),
+        };
+        self.current = if self.matches.is_empty() {
+            None
+        } else {
+            Some(
+                self.matches
+                    .iter()
+                    .position(|m| m.range.start >= origin)
+                    .unwrap_or(0),
+            )
+        };
+        self.matches.len()
+    }
+
+    /// Forget the pattern and all matches.
+    pub fn clear(&mut self) {
+        self.query = None;
+        self.matches.clear();
+        self.current = None;
+        self.error = None;
+    }
+
+    /// The last pattern that compiled.
+    #[must_use]
+    pub fn query(&self) -> Option<&FindQuery> {
+        self.query.as_ref()
+    }
+
+    /// Why the latest pattern did not compile, if it did not.
+    #[must_use]
+    pub fn error(&self) -> Option<&FindError> {
+        self.error.as_ref()
+    }
+
+    /// All matches, in order.
+    #[must_use]
+    pub fn matches(&self) -> &[SearchResult] {
+        &self.matches
+    }
+
+    /// Index of the current match.
+    #[must_use]
+    pub fn current_index(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// The current match.
+    #[must_use]
+    pub fn current(&self) -> Option<&SearchResult> {
+        self.matches.get(self.current?)
+    }
+
+    /// Advance to the next match, wrapping around.
+    pub fn next_match(&mut self) -> Option<&SearchResult> {
+        let len = self.matches.len();
+        self.current = self.current.map(|i| (i + 1) % len);
+        self.current()
+    }
+
+    /// Step back to the previous match, wrapping around.
+    pub fn prev_match(&mut self) -> Option<&SearchResult> {
+        let len = self.matches.len();
+        self.current = self.current.map(|i| (i + len - 1) % len);
+        self.current()
+    }
+
+    /// Byte ranges of all matches, for highlighting.
+    #[must_use]
+    pub fn highlight_ranges(&self) -> Vec<(usize, usize)> {
+        self.matches
+            .iter()
+            .map(|m| (m.range.start, m.range.end))
+            .collect()
+    }
+}
+
+impl Editor {
+    /// All matches of `query`, limited to the selection when the query has
+    /// `in_selection` set and a selection is active.
+    #[must_use]
+    pub fn find_all(&self, query: &FindQuery) -> Vec<SearchResult> {
+        query.find_in_str(&self.text(), self.find_scope(query))
+    }
+
+    /// Select the next match after the cursor or selection, wrapping around.
+    ///
+    /// Returns `false` when there is no match.
+    pub fn find_next(&mut self, query: &FindQuery) -> bool {
+        let (_, from) = self.selection_or_cursor_range();
+        match query.find_next(&self.text(), from) {
+            Some(found) => {
+                self.select_byte_range(found.range);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Select the previous match before the cursor or selection, wrapping
+    /// around.
+    ///
+    /// Returns `false` when there is no match.
+    pub fn find_prev(&mut self, query: &FindQuery) -> bool {
+        let (before, _) = self.selection_or_cursor_range();
+        match query.find_prev(&self.text(), before) {
+            Some(found) => {
+                self.select_byte_range(found.range);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replace the selection if it is exactly a match, then select the next
+    /// match.
+    ///
+    /// Returns `false` (and only moves to the next match) when the selection
+    /// is not a match.
+    pub fn replace_current(&mut self, query: &FindQuery, replacement: &str) -> bool {
+        let (start, end) = self.selection_or_cursor_range();
+        let replaced = start < end && {
+            let current = query
+                .replacements(&self.text(), Some(start..end), replacement)
+                .into_iter()
+                .find(|(m, _)| m.range == (start..end));
+            match current {
+                Some((m, text)) => self.replace_byte_ranges(vec![(m.range, text)]) == 1,
+                None => false,
+            }
+        };
+        self.find_next(query);
+        replaced
+    }
+
+    /// Replace every match as a single undo step. Returns the number of
+    /// replacements.
+    ///
+    /// With `in_selection` set, only matches inside the selection are
+    /// replaced.
+    pub fn replace_all(&mut self, query: &FindQuery, replacement: &str) -> usize {
+        let edits = query
+            .replacements(&self.text(), self.find_scope(query), replacement)
+            .into_iter()
+            .map(|(m, text)| (m.range, text))
+            .collect();
+        self.replace_byte_ranges(edits)
+    }
+
+    fn find_scope(&self, query: &FindQuery) -> Option<Range<usize>> {
+        if !query.options().in_selection {
+            return None;
+        }
+        let sel = self.selection().filter(|sel| !sel.is_empty())?;
+        let (start, end) = sel.byte_range(&CursorNavigator::new(self.rope()));
+        Some(start..end)
+    }
+
+    fn selection_or_cursor_range(&self) -> (usize, usize) {
+        let nav = CursorNavigator::new(self.rope());
+        match self.selection() {
+            Some(sel) if !sel.is_empty() => sel.byte_range(&nav),
+            _ => {
+                let byte = nav.to_byte_index(self.cursor());
+                (byte, byte)
+            }
+        }
+    }
+
+    fn select_byte_range(&mut self, range: Range<usize>) {
+        let nav = CursorNavigator::new(self.rope());
+        let start = nav.from_byte_index(range.start);
+        let end = nav.from_byte_index(range.end);
+        self.set_cursor(start);
+        self.extend_selection_to(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cursor::CursorPosition;
+
+    fn ranges(results: &[SearchResult]) -> Vec<Range<usize>> {
+        results.iter().map(|m| m.range.clone()).collect()
+    }
+
+    #[test]
+    fn literal_pattern_is_escaped() {
+        let query = FindQuery::new("a.b", FindOptions::new()).unwrap();
+        assert_eq!(ranges(&query.find_in_str("axb a.b", None)), vec![4..7]);
+    }
+
+    #[test]
+    fn case_and_whole_word_options() {
+        let text = "Foo foo food _foo";
+        let query = FindQuery::new("foo", FindOptions::new().case_insensitive(true)).unwrap();
+        assert_eq!(query.find_in_str(text, None).len(), 4);
+
+        let query = FindQuery::new(
+            "foo",
+            FindOptions::new().case_insensitive(true).whole_word(true),
+        )
+        .unwrap();
+        assert_eq!(ranges(&query.find_in_str(text, None)), vec![0..3, 4..7]);
+    }
+
+    #[test]
+    fn invalid_and_empty_patterns() {
+        assert_eq!(
+            FindQuery::new("", FindOptions::new()).unwrap_err(),
+            FindError::EmptyPattern
+        );
+        let err = FindQuery::new("(unclosed", FindOptions::new().regex(true)).unwrap_err();
+        assert!(matches!(err, FindError::InvalidPattern(_)));
+        assert!(err.to_string().starts_with("invalid search pattern"));
+    }
+
+    #[test]
+    fn scope_limits_matches_but_keeps_context() {
+        let query = FindQuery::new(r"\bab", FindOptions::new().regex(true)).unwrap();
+        // The "ab" at 4 follows 'x' outside the scope, so \b rejects it.
+        assert_eq!(
+            ranges(&query.find_in_str("ab xab ab", Some(4..9))),
+            vec![7..9]
+        );
+    }
+
+    #[test]
+    fn regex_replacement_expands_captures() {
+        let query = FindQuery::new(r"(?P<key>\w+)=(\d+)", FindOptions::new().regex(true)).unwrap();
+        let reps = query.replacements("a=1 b=2", None, "${key}:$2");
+        let texts: Vec<&str> = reps.iter().map(|(_, t)| t.as_str()).collect();
+        assert_eq!(texts, vec!["a:1", "b:2"]);
+
+        let literal = FindQuery::new("a", FindOptions::new()).unwrap();
+        assert_eq!(literal.replacements("a", None, "$1")[0].1, "$1");
+    }
+
+    #[test]
+    fn line_anchors_and_empty_matches() {
+        let query = FindQuery::new("^", FindOptions::new().regex(true)).unwrap();
+        let mut ed = Editor::with_text("a\nb\nc");
+        assert_eq!(ed.replace_all(&query, "- "), 3);
+        assert_eq!(ed.text(), "- a\n- b\n- c");
+    }
+
+    #[test]
+    fn find_next_and_prev_wrap() {
+        let query = FindQuery::new("x", FindOptions::new()).unwrap();
+        let mut ed = Editor::with_text("x1 x2 x3");
+        ed.set_cursor(CursorPosition::new(0, 4, 4));
+        assert!(ed.find_next(&query));
+        assert_eq!(ed.cursor().grapheme, 7);
+        assert!(ed.find_next(&query));
+        assert_eq!(ed.cursor().grapheme, 1);
+        assert!(ed.find_prev(&query));
+        assert_eq!(ed.cursor().grapheme, 7);
+    }
+
+    #[test]
+    fn replace_current_only_replaces_a_selected_match() {
+        let query = FindQuery::new("cat", FindOptions::new()).unwrap();
+        let mut ed = Editor::with_text("cat dog cat");
+        ed.move_to_document_start();
+        assert!(!ed.replace_current(&query, "cow"));
+        assert_eq!(ed.selected_text().as_deref(), Some("cat"));
+        assert!(ed.replace_current(&query, "cow"));
+        assert_eq!(ed.text(), "cow dog cat");
+        assert!(ed.replace_current(&query, "cow"));
+        assert_eq!(ed.text(), "cow dog cow");
+    }
+
+    #[test]
+    fn replace_all_in_selection_is_one_undo_step() {
+        let query = FindQuery::new("o", FindOptions::new().in_selection(true)).unwrap();
+        let mut ed = Editor::with_text("foo boo zoo");
+        ed.move_to_document_start();
+        for _ in 0..7 {
+            ed.select_right();
+        }
+        assert_eq!(ed.replace_all(&query, "0"), 4);
+        assert_eq!(ed.text(), "f00 b00 zoo");
+        assert!(ed.undo());
+        assert_eq!(ed.text(), "foo boo zoo");
+        assert!(!ed.can_undo());
+    }
+
+    #[test]
+    fn finder_keeps_matches_while_pattern_is_invalid() {
+        let rope = Rope::from_text("ab ab ab");
+        let mut finder = Finder::new();
+        let options = FindOptions::new().regex(true);
+        assert_eq!(finder.set_pattern("ab", options, &rope, 1), 3);
+        assert_eq!(finder.current_index(), Some(1));
+        assert_eq!(finder.set_pattern("ab(", options, &rope, 1), 3);
+        assert!(finder.error().is_some());
+        assert_eq!(finder.next_match().map(|m| m.range.clone()), Some(6..8));
+        assert_eq!(finder.next_match().map(|m| m.range.clone()), Some(0..2));
+        assert_eq!(finder.prev_match().map(|m| m.range.clone()), Some(6..8));
+        assert_eq!(finder.highlight_ranges().len(), 3);
+        assert_eq!(finder.set_pattern("", options, &rope, 0), 0);
+        assert!(finder.current().is_none());
+    }
+}
@@ -20,8 +20,24 @@
 //! Break points integrate with the penalty model from [`crate::wrap`]:
 //! - Each `HyphenBreakPoint` maps to a [`crate::wrap::BreakPenalty`] with `flagged = true`.
 //! - The [`crate::wrap::ParagraphObjective`] handles consecutive-hyphen demerits.
+//! - [`crate::wrap::WrapOptions::hyphenation`] enables hyphenated word and
+//!   optimal wrapping.
+//!
+//! # Loading dictionaries
+//!
+//! [`HyphenationDict::from_tex`] reads standard UTF-8 TeX pattern files
+//! (`\patterns{...}` and `\hyphenation{...}`). A compiled dictionary can be
+//! stored with [`HyphenationDict::to_bytes`] and reloaded with
+//! [`HyphenationDict::from_bytes`], skipping pattern compilation at startup.
+//! [`HyphenationRegistry`] maps language tags to dictionaries and resolves
+//! locales such as `"de_CH.UTF-8"` with fallback to the primary language.
 
 use std::collections::HashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::sync::Arc;
+
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::wrap::BreakPenalty;
 
@@ -218,12 +234,23 @@ impl HyphenationDict {
     /// Exceptions use hyphen-delimited words (e.g. `"hy-phen-ation"`).
     #[must_use]
     pub fn new(language: &str, patterns: &[&str], exceptions: &[&str]) -> Self {
-        let compiled: Vec<HyphenationPattern> =
-            patterns.iter().filter_map(|p| compile_pattern(p)).collect();
+        Self::build(
+            language,
+            patterns.iter().copied(),
+            exceptions.iter().copied(),
+        )
+    }
+
+    fn build<'a>(
+        language: &str,
+        patterns: impl Iterator<Item = &'a str>,
+        exceptions: impl Iterator<Item = &'a str>,
+    ) -> Self {
+        let compiled: Vec<HyphenationPattern> = patterns.filter_map(compile_pattern).collect();
         let trie = PatternTrie::new(&compiled);
 
         let mut exc_map = HashMap::new();
-        for &exc in exceptions {
+        for exc in exceptions {
             let (word, breaks) = parse_exception(exc);
             exc_map.insert(word, breaks);
         }
@@ -316,6 +343,40 @@ impl HyphenationDict {
     pub fn can_hyphenate(&self, word: &str) -> bool {
         !self.hyphenate(word).is_empty()
     }
+
+    /// Byte offsets within `word` where a hyphen may be inserted.
+    ///
+    /// Unlike [`hyphenate`](Self::hyphenate), this accepts a word as it
+    /// appears in running text: leading and trailing punctuation is ignored,
+    /// and words containing other non-letters are left unbroken. Offsets
+    /// always fall on grapheme boundaries.
+    #[must_use]
+    pub fn break_byte_offsets(&self, word: &str) -> Vec<usize> {
+        let not_letter = |c: char| !c.is_alphabetic();
+        let core = word.trim_end_matches(not_letter);
+        let start = core.len() - core.trim_start_matches(not_letter).len();
+        let core = &core[start..];
+        if core.is_empty()
+            || core
+                .graphemes(true)
+                .any(|g| !g.starts_with(char::is_alphabetic))
+        {
+            return Vec::new();
+        }
+
+        let char_offsets: Vec<usize> = core.char_indices().map(|(i, _)| i).collect();
+        if core.to_lowercase().chars().count() != char_offsets.len() {
+            // Case mapping changed the length; offsets would not line up.
+            return Vec::new();
+        }
+        let boundaries: Vec<usize> = core.grapheme_indices(true).map(|(i, _)| i).collect();
+        self.hyphenate(core)
+            .iter()
+            .filter_map(|bp| char_offsets.get(bp.offset).copied())
+            .filter(|off| boundaries.binary_search(off).is_ok())
+            .map(|off| start + off)
+            .collect()
+    }
 }
 
 /// Parse an exception string like `"hy-phen-ation"` into `("hyphenation", [2, 5])`.
@@ -344,7 +405,7 @@ fn parse_exception(exc: &str) -> (String, Vec<usize>) {
 ///
 /// This is a small representative subset of the full `hyph-en-us.tex` patterns,
 /// sufficient for common words and testing. Production use should load the
-/// full pattern set from TeX distributions.
+/// full pattern set from TeX distributions with [`HyphenationDict::from_tex`].
 pub const ENGLISH_PATTERNS_MINI: &[&str] = &[
     // Word-start patterns (with .)
     ".hy3p", ".re1i", ".in1t", ".un1d", ".ex1a", ".dis1c", ".pre1v", ".over3f", ".semi5", ".auto3",
@@ -384,6 +445,470 @@ pub fn english_dict_mini() -> HyphenationDict {
     HyphenationDict::new("en", ENGLISH_PATTERNS_MINI, ENGLISH_EXCEPTIONS_MINI)
 }
 
+// ---------------------------------------------------------------------------
+// TeX pattern files
+// ---------------------------------------------------------------------------
+
+/// Errors from loading or deserializing hyphenation data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HyphenationError {
+    /// A `{` group was never closed.
+    UnterminatedGroup {
+        /// The command that opened the group (e.g. `"patterns"`).
+        command: String,
+    },
+    /// The source contained no usable patterns.
+    NoPatterns,
+    /// Serialized data does not start with the expected header.
+    BadMagic,
+    /// Serialized data was written by an unsupported format version.
+    UnsupportedVersion(u8),
+    /// Serialized data is truncated or inconsistent.
+    Corrupt(&'static str),
+}
+
+impl fmt::Display for HyphenationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedGroup { command } => {
+                write!(f, "unterminated \\{command}{{...}} group")
+            }
+            Self::NoPatterns => f.write_str("no hyphenation patterns found"),
+            Self::BadMagic => f.write_str("not a serialized hyphenation dictionary"),
+            Self::UnsupportedVersion(v) => {
+                write!(f, "unsupported hyphenation dictionary version {v}")
+            }
+            Self::Corrupt(what) => write!(f, "corrupt hyphenation dictionary: {what}"),
+        }
+    }
+}
+
+impl std::error::Error for HyphenationError {}
+
+/// Patterns and exceptions read from a TeX hyphenation file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TexPatterns {
+    /// Pattern tokens, e.g. `".ach4"`.
+    pub patterns: Vec<String>,
+    /// Exception words, e.g. `"ta-ble"`.
+    pub exceptions: Vec<String>,
+}
+
+impl TexPatterns {
+    /// Parse a UTF-8 TeX hyphenation file such as `hyph-de-1996.tex`.
+    ///
+    /// Reads the contents of `\patterns{...}` and `\hyphenation{...}`
+    /// groups, skipping `%` comments and any other commands. A file with no
+    /// `\patterns` group is read as a plain whitespace-separated pattern
+    /// list, the format of the `hyph-*.pat.txt` files in hyph-utf8.
+    pub fn parse(source: &str) -> Result<Self, HyphenationError> {
+        let mut out = Self::default();
+        let mut saw_group = false;
+        let mut chars = strip_tex_comments(source);
+        let mut loose = Vec::new();
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => {
+                    let mut command = String::new();
+                    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                        command.push(c);
+                        chars.next();
+                    }
+                    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                    if chars.next_if_eq(&'{').is_none() {
+                        continue;
+                    }
+                    let body = read_tex_group(&mut chars).ok_or_else(|| {
+                        HyphenationError::UnterminatedGroup {
+                            command: command.clone(),
+                        }
+                    })?;
+                    let target = match command.as_str() {
+                        "patterns" => &mut out.patterns,
+                        "hyphenation" => &mut out.exceptions,
+                        _ => continue,
+                    };
+                    saw_group = true;
+                    target.extend(body.split_whitespace().map(str::to_string));
+                }
+                c if c.is_whitespace() => {}
+                c => {
+                    let mut token = String::from(c);
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '\\') {
+                        token.push(c);
+                    }
+                    loose.push(token);
+                }
+            }
+        }
+
+        if !saw_group {
+            out.patterns = loose;
+        }
+        if out.patterns.is_empty() {
+            return Err(HyphenationError::NoPatterns);
+        }
+        Ok(out)
+    }
+}
+
+/// Characters of `source` with `%` comments removed.
+fn strip_tex_comments(source: &str) -> Peekable<impl Iterator<Item = char> + '_> {
+    source
+        .lines()
+        .flat_map(|line| {
+            let code = line.split_once('%').map_or(line, |(code, _)| code);
+            code.chars().chain(std::iter::once('\n'))
+        })
+        .peekable()
+}
+
+/// Read up to the `}` matching an already consumed `{`.
+fn read_tex_group(chars: &mut impl Iterator<Item = char>) -> Option<String> {
+    let mut body = String::new();
+    let mut depth = 0usize;
+    for ch in chars {
+        match ch {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(body),
+            '}' => depth -= 1,
+            _ => {}
+        }
+        body.push(ch);
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// Compact serialization
+// ---------------------------------------------------------------------------
+
+const SERIAL_MAGIC: &[u8; 4] = b"FTHY";
+const SERIAL_VERSION: u8 = 1;
+
+fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    put_varint(out, s.len() as u64);
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Cursor over serialized dictionary bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], HyphenationError> {
+        if n > self.bytes.len() {
+            return Err(HyphenationError::Corrupt("unexpected end of data"));
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn varint(&mut self) -> Result<u64, HyphenationError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(HyphenationError::Corrupt("varint too long"))
+    }
+
+    fn usize(&mut self) -> Result<usize, HyphenationError> {
+        usize::try_from(self.varint()?).map_err(|_| HyphenationError::Corrupt("length overflow"))
+    }
+
+    fn string(&mut self) -> Result<String, HyphenationError> {
+        let len = self.usize()?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| HyphenationError::Corrupt("invalid UTF-8"))
+    }
+}
+
+impl PatternTrie {
+    /// Number of trie nodes.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn write_to(&self, out: &mut Vec<u8>) {
+        put_varint(out, self.nodes.len() as u64);
+        for node in &self.nodes {
+            match &node.levels {
+                None => put_varint(out, 0),
+                Some(levels) => {
+                    put_varint(out, levels.len() as u64 + 1);
+                    // Levels are single TeX digits: pack two per byte.
+                    for pair in levels.chunks(2) {
+                        out.push(pair[0] | pair.get(1).map_or(0, |hi| hi << 4));
+                    }
+                }
+            }
+            let mut children: Vec<(char, usize)> =
+                node.children.iter().map(|(&c, &i)| (c, i)).collect();
+            children.sort_unstable();
+            put_varint(out, children.len() as u64);
+            let mut prev = 0u32;
+            for (ch, idx) in children {
+                put_varint(out, u64::from(ch as u32 - prev));
+                put_varint(out, idx as u64);
+                prev = ch as u32;
+            }
+        }
+    }
+
+    fn read_from(reader: &mut Reader<'_>) -> Result<Self, HyphenationError> {
+        let count = reader.usize()?;
+        if count == 0 || count > reader.bytes.len() {
+            return Err(HyphenationError::Corrupt("bad node count"));
+        }
+        let mut nodes = Vec::with_capacity(count);
+        for _ in 0..count {
+            let levels = match reader.usize()? {
+                0 => None,
+                n => {
+                    let len = n - 1;
+                    let packed = reader.take(len.div_ceil(2))?;
+                    Some(
+                        (0..len)
+                            .map(|i| (packed[i / 2] >> ((i % 2) * 4)) & 0x0f)
+                            .collect(),
+                    )
+                }
+            };
+            let child_count = reader.usize()?;
+            let mut children = HashMap::with_capacity(child_count.min(64));
+            let mut prev = 0u32;
+            for _ in 0..child_count {
+                let delta = u32::try_from(reader.varint()?)
+                    .map_err(|_| HyphenationError::Corrupt("bad character"))?;
+                let code = prev
+                    .checked_add(delta)
+                    .ok_or(HyphenationError::Corrupt("bad character"))?;
+                let ch = char::from_u32(code).ok_or(HyphenationError::Corrupt("bad character"))?;
+                let idx = reader.usize()?;
+                if idx == 0 || idx >= count {
+                    return Err(HyphenationError::Corrupt("child index out of range"));
+                }
+                children.insert(ch, idx);
+                prev = code;
+            }
+            nodes.push(TrieNode { children, levels });
+        }
+        Ok(Self { nodes })
+    }
+}
+
+impl HyphenationDict {
+    /// Build a dictionary from a UTF-8 TeX hyphenation file.
+    ///
+    /// See [`TexPatterns::parse`] for the accepted formats.
+    pub fn from_tex(language: &str, source: &str) -> Result<Self, HyphenationError> {
+        let tex = TexPatterns::parse(source)?;
+        Ok(Self::build(
+            language,
+            tex.patterns.iter().map(String::as_str),
+            tex.exceptions.iter().map(String::as_str),
+        ))
+    }
+
+    /// Build a dictionary from separate pattern and exception lists, as
+    /// shipped in hyph-utf8's `hyph-*.pat.txt` and `hyph-*.hyp.txt` files.
+    pub fn from_pattern_lists(
+        language: &str,
+        patterns: &str,
+        exceptions: &str,
+    ) -> Result<Self, HyphenationError> {
+        let dict = Self::build(
+            language,
+            patterns.split_whitespace(),
+            exceptions.split_whitespace(),
+        );
+        if dict.trie.nodes.len() <= 1 {
+            return Err(HyphenationError::NoPatterns);
+        }
+        Ok(dict)
+    }
+
+    /// Serialize the compiled dictionary into a compact binary form.
+    ///
+    /// Loading it back with [`from_bytes`](Self::from_bytes) skips pattern
+    /// parsing and trie construction. The output is deterministic.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(SERIAL_MAGIC);
+        out.push(SERIAL_VERSION);
+        put_str(&mut out, &self.language);
+        put_varint(&mut out, self.left_min as u64);
+        put_varint(&mut out, self.right_min as u64);
+        self.trie.write_to(&mut out);
+
+        let mut exceptions: Vec<_> = self.exceptions.iter().collect();
+        exceptions.sort_unstable();
+        put_varint(&mut out, exceptions.len() as u64);
+        for (word, breaks) in exceptions {
+            put_str(&mut out, word);
+            put_varint(&mut out, breaks.len() as u64);
+            for &b in breaks {
+                put_varint(&mut out, b as u64);
+            }
+        }
+        out
+    }
+
+    /// Load a dictionary written by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HyphenationError> {
+        let mut reader = Reader { bytes };
+        if reader.take(SERIAL_MAGIC.len()).ok() != Some(SERIAL_MAGIC.as_slice()) {
+            return Err(HyphenationError::BadMagic);
+        }
+        match reader.take(1)?[0] {
+            SERIAL_VERSION => {}
+            v => return Err(HyphenationError::UnsupportedVersion(v)),
+        }
+        let language = reader.string()?;
+        let left_min = reader.usize()?;
+        let right_min = reader.usize()?;
+        let trie = PatternTrie::read_from(&mut reader)?;
+
+        let count = reader.usize()?;
+        let mut exceptions = HashMap::with_capacity(count.min(reader.bytes.len()));
+        for _ in 0..count {
+            let word = reader.string()?;
+            let n = reader.usize()?;
+            let breaks = (0..n)
+                .map(|_| reader.usize())
+                .collect::<Result<Vec<_>, _>>()?;
+            exceptions.insert(word, breaks);
+        }
+        if !reader.bytes.is_empty() {
+            return Err(HyphenationError::Corrupt("trailing bytes"));
+        }
+
+        Ok(Self {
+            language,
+            trie,
+            exceptions,
+            left_min,
+            right_min,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Registry
+// ---------------------------------------------------------------------------
+
+/// Normalize a language tag or POSIX locale name for registry lookup.
+///
+/// Drops any encoding or modifier suffix, converts `_` to `-` and
+/// lowercases: `"de_CH.UTF-8"` → `"de-ch"`.
+#[must_use]
+pub fn normalize_language_tag(tag: &str) -> String {
+    let tag = tag.split(['.', '@']).next().unwrap_or("");
+    tag.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+/// Hyphenation dictionaries keyed by language tag.
+///
+/// Lookups via [`for_locale`](Self::for_locale) fall back from the most
+/// specific tag to its primary language, so `"de-CH"` uses a `"de-ch"`
+/// dictionary if registered and `"de"` otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct HyphenationRegistry {
+    dicts: HashMap<String, Arc<HyphenationDict>>,
+}
+
+impl HyphenationRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry containing the built-in English dictionary.
+    #[must_use]
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.insert(english_dict_mini());
+        registry
+    }
+
+    /// Register a dictionary under its own [`language`](HyphenationDict::language).
+    pub fn insert(&mut self, dict: HyphenationDict) -> Option<Arc<HyphenationDict>> {
+        let tag = dict.language.clone();
+        self.insert_as(&tag, Arc::new(dict))
+    }
+
+    /// Register a dictionary under an explicit tag, replacing any previous one.
+    pub fn insert_as(
+        &mut self,
+        tag: &str,
+        dict: Arc<HyphenationDict>,
+    ) -> Option<Arc<HyphenationDict>> {
+        self.dicts.insert(normalize_language_tag(tag), dict)
+    }
+
+    /// Remove the dictionary registered under `tag`.
+    pub fn remove(&mut self, tag: &str) -> Option<Arc<HyphenationDict>> {
+        self.dicts.remove(&normalize_language_tag(tag))
+    }
+
+    /// Dictionary registered under exactly `tag` (after normalization).
+    #[must_use]
+    pub fn get(&self, tag: &str) -> Option<Arc<HyphenationDict>> {
+        self.dicts.get(&normalize_language_tag(tag)).cloned()
+    }
+
+    /// Best dictionary for a locale, trying progressively shorter tags.
+    #[must_use]
+    pub fn for_locale(&self, locale: &str) -> Option<Arc<HyphenationDict>> {
+        let mut tag = normalize_language_tag(locale);
+        loop {
+            if let Some(dict) = self.dicts.get(&tag) {
+                return Some(Arc::clone(dict));
+            }
+            let cut = tag.rfind('-')?;
+            tag.truncate(cut);
+        }
+    }
+
+    /// Registered tags, sorted.
+    #[must_use]
+    pub fn languages(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self.dicts.keys().map(String::as_str).collect();
+        tags.sort_unstable();
+        tags
+    }
+
+    /// Number of registered dictionaries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dicts.len()
+    }
+
+    /// Whether no dictionaries are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dicts.is_empty()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Penalty mapping for break-point sequences
 // ---------------------------------------------------------------------------
@@ -742,4 +1267,124 @@ mod tests {
         assert!(!offsets.contains(&2)); // b|c (even level)
         assert!(offsets.contains(&3)); // c|d
     }
+
+    // -- Loading and registry --
+
+    const GERMAN_TEX: &str = r"% Sample German patterns
+\message{Loading sample patterns}
+\patterns{ % compound boundaries
+1dampf 1schiff
+1fahrt
+}
+\hyphenation{
+do-nau
+}
+";
+
+    fn german() -> HyphenationDict {
+        HyphenationDict::from_tex("de", GERMAN_TEX).unwrap()
+    }
+
+    #[test]
+    fn parse_tex_file_reads_groups_and_skips_comments() {
+        let tex = TexPatterns::parse(GERMAN_TEX).unwrap();
+        assert_eq!(tex.patterns, ["1dampf", "1schiff", "1fahrt"]);
+        assert_eq!(tex.exceptions, ["do-nau"]);
+
+        let offsets: Vec<usize> = german()
+            .hyphenate("Donaudampfschifffahrt")
+            .iter()
+            .map(|b| b.offset)
+            .collect();
+        assert_eq!(offsets, [5, 10, 16]);
+    }
+
+    #[test]
+    fn parse_plain_pattern_list_and_errors() {
+        let tex = TexPatterns::parse(".ach4 ab1\n% comment\nä1b").unwrap();
+        assert_eq!(tex.patterns, [".ach4", "ab1", "ä1b"]);
+        assert!(tex.exceptions.is_empty());
+
+        assert_eq!(
+            TexPatterns::parse("% nothing here\n"),
+            Err(HyphenationError::NoPatterns)
+        );
+        assert_eq!(
+            TexPatterns::parse(r"\patterns{ 1ab"),
+            Err(HyphenationError::UnterminatedGroup {
+                command: "patterns".into()
+            })
+        );
+        assert!(HyphenationDict::from_pattern_lists("de", "1dampf", "do-nau").is_ok());
+        assert!(HyphenationDict::from_pattern_lists("de", "", "").is_err());
+    }
+
+    #[test]
+    fn serialized_dict_round_trips() {
+        let dict = german().with_margins(2, 2);
+        let bytes = dict.to_bytes();
+        assert_eq!(bytes, dict.to_bytes(), "serialization is deterministic");
+
+        let loaded = HyphenationDict::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.language, "de");
+        assert_eq!((loaded.left_min, loaded.right_min), (2, 2));
+        assert_eq!(loaded.trie.node_count(), dict.trie.node_count());
+        for word in ["Donaudampfschifffahrt", "donau", "Schifffahrt"] {
+            assert_eq!(loaded.hyphenate(word), dict.hyphenate(word), "{word}");
+        }
+
+        let english = english_dict_mini();
+        let loaded = HyphenationDict::from_bytes(&english.to_bytes()).unwrap();
+        assert_eq!(
+            loaded.hyphenate("hyphenation"),
+            english.hyphenate("hyphenation")
+        );
+    }
+
+    #[test]
+    fn corrupt_serialized_data_is_rejected() {
+        let bytes = german().to_bytes();
+        assert_eq!(
+            HyphenationDict::from_bytes(b"nope").unwrap_err(),
+            HyphenationError::BadMagic
+        );
+        let mut future = bytes.clone();
+        future[4] = 99;
+        assert_eq!(
+            HyphenationDict::from_bytes(&future).unwrap_err(),
+            HyphenationError::UnsupportedVersion(99)
+        );
+        for len in 5..bytes.len() {
+            assert!(HyphenationDict::from_bytes(&bytes[..len]).is_err(), "{len}");
+        }
+    }
+
+    #[test]
+    fn break_byte_offsets_skip_punctuation() {
+        let dict = german();
+        assert_eq!(dict.break_byte_offsets("(Dampfschiff),"), [6]);
+        assert_eq!(dict.break_byte_offsets("Dampf-Schiff"), Vec::<usize>::new());
+        let dict = HyphenationDict::new("de", &["ü1b"], &[]).with_margins(1, 1);
+        assert_eq!(
+            dict.break_byte_offsets("Übüberall"),
+            ["Ü".len(), "Übü".len()]
+        );
+    }
+
+    #[test]
+    fn registry_falls_back_to_primary_language() {
+        let mut registry = HyphenationRegistry::with_builtin();
+        registry.insert(german());
+        assert_eq!(registry.languages(), ["de", "en"]);
+
+        let dict = registry.for_locale("de_CH.UTF-8@euro").unwrap();
+        assert_eq!(dict.language, "de");
+        assert!(registry.get("de-CH").is_none());
+        assert!(registry.for_locale("nl-NL").is_none());
+
+        registry.insert_as("de-CH", Arc::new(german().with_margins(3, 3)));
+        assert_eq!(registry.for_locale("de_CH").unwrap().left_min, 3);
+        assert_eq!(registry.for_locale("de-DE").unwrap().left_min, 2);
+        assert_eq!(normalize_language_tag(" nl_BE.UTF-8 "), "nl-be");
+    }
 }
@@ -10,6 +10,7 @@
 //! - [`Line`] - a line of styled spans
 //! - [`Text`] - multi-line styled text
 //! - [`Rope`] - rope-backed text storage
+//! - [`LineSource`] - read-only line access shared by ropes and external sources
 //! - [`CursorPosition`] / [`CursorNavigator`] - text cursor utilities
 //! - [`Editor`] - core text editing operations (insert, delete, cursor movement)
 //! - [`WidthCache`] - LRU cache for text width measurements
@@ -56,6 +57,7 @@
 
 pub mod cursor;
 pub mod editor;
+pub mod line_source;
 pub mod rope;
 pub mod segment;
 pub mod text;
@@ -66,9 +68,18 @@ pub mod wrap;
 pub mod hyphenation;
 pub mod incremental_break;
 
+#[cfg(feature = "ansi")]
+pub mod ansi;
+
 #[cfg(feature = "markup")]
 pub mod markup;
 
+#[cfg(feature = "diff")]
+pub mod text_diff;
+
+#[cfg(feature = "regex-search")]
+pub mod find_replace;
+
 #[cfg(feature = "bidi")]
 pub mod bidi;
 
@@ -137,14 +148,16 @@ pub use cluster_map::{ClusterEntry, ClusterMap};
 pub use cursor::{CursorNavigator, CursorPosition};
 pub use editor::{Editor, Selection};
 pub use hyphenation::{
-    HyphenBreakPoint, HyphenationDict, HyphenationPattern, PatternTrie, break_penalties,
-    compile_pattern, english_dict_mini,
+    HyphenBreakPoint, HyphenationDict, HyphenationError, HyphenationPattern, HyphenationRegistry,
+    PatternTrie, TexPatterns, break_penalties, compile_pattern, english_dict_mini,
+    normalize_language_tag,
 };
 pub use incremental_break::{BreakerSnapshot, EditEvent, IncrementalBreaker, ReflowResult};
 pub use justification::{
     GlueSpec, JustificationControl, JustifyMode, SUBCELL_SCALE, SpaceCategory, SpacePenalty,
 };
 pub use layout_policy::{LayoutPolicy, LayoutTier, PolicyError, ResolvedPolicy, RuntimeCapability};
+pub use line_source::LineSource;
 pub use rope::Rope;
 pub use script_segmentation::{
     RunCacheKey, RunDirection, Script, ScriptRun, TextRun, partition_by_script, partition_text_runs,
@@ -172,11 +185,25 @@ pub use wrap::{
     KpBreakResult, WrapMode, WrapOptions, ascii_width, display_width, grapheme_count,
     grapheme_width, graphemes, has_wide_chars, is_ascii_only, truncate_to_width,
     truncate_to_width_with_info, truncate_with_ellipsis, word_boundaries, word_segments,
-    wrap_optimal, wrap_text, wrap_text_optimal, wrap_with_options,
+    wrap_optimal, wrap_optimal_hyphenated, wrap_text, wrap_text_optimal, wrap_with_options,
 };
 
+#[cfg(feature = "ansi")]
+pub use ansi::{AnsiParser, parse_ansi};
+
+#[cfg(feature = "regex-search")]
+pub use find_replace::{FindError, FindOptions, FindQuery, Finder};
+
 #[cfg(feature = "markup")]
-pub use markup::{MarkupError, MarkupParser, parse_markup};
+pub use markup::{
+    MarkupError, MarkupParser, ThemedMarkup, parse_markup, parse_themed_markup, text_to_markup,
+};
+
+#[cfg(feature = "diff")]
+pub use text_diff::{
+    DiffAlgorithm, DiffGranularity, DiffHunk, DiffOp, DiffOptions, DiffStats, DiffStyles, DiffTag,
+    TextDiff, diff_segments, diff_slices,
+};
 
 #[cfg(feature = "normalization")]
 pub use normalization::{NormForm, eq_normalized, is_normalized, normalize, normalize_for_search};
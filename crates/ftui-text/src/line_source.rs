@@ -0,0 +1,93 @@
+#![forbid(unsafe_code)]
+
+//! Read-only line access shared by in-memory and external text.
+//!
+//! [`LineSource`] is the minimal interface line-oriented renderers need:
+//! a line count and the text of one line. [`Rope`] implements it directly;
+//! other sources (for example a file indexed lazily in the background) can
+//! implement it without loading everything into memory. In `ftui-widgets`,
+//! `VirtualizedList::from_lines` renders any `&dyn LineSource`,
+//! `LogViewer::from_source` scrolls, filters and searches a shared one, and
+//! `TextArea::from_source` shows one read-only with line numbers and wrapping.
+
+use std::borrow::Cow;
+
+use crate::rope::Rope;
+
+/// Random access to the lines of a read-only text.
+pub trait LineSource {
+    /// Number of lines currently available.
+    ///
+    /// For sources still being loaded this can grow over time; see
+    /// [`is_complete`](Self::is_complete).
+    fn line_count(&self) -> usize;
+
+    /// Text of line `idx` without its terminator (`\n` or `\r\n`).
+    ///
+    /// Returns `None` if the line is out of range or not available yet.
+    fn line_text(&self, idx: usize) -> Option<Cow<'_, str>>;
+
+    /// Whether [`line_count`](Self::line_count) is final.
+    fn is_complete(&self) -> bool {
+        true
+    }
+}
+
+/// Strip one trailing `\n` or `\r\n` from a line.
+fn trim_terminator(line: Cow<'_, str>) -> Cow<'_, str> {
+    let trimmed = line.strip_suffix('\n').unwrap_or(&line);
+    let keep = trimmed.strip_suffix('\r').unwrap_or(trimmed).len();
+    if keep == line.len() {
+        return line;
+    }
+    match line {
+        Cow::Borrowed(s) => Cow::Borrowed(&s[..keep]),
+        Cow::Owned(mut s) => {
+            s.truncate(keep);
+            Cow::Owned(s)
+        }
+    }
+}
+
+impl LineSource for Rope {
+    fn line_count(&self) -> usize {
+        self.len_lines()
+    }
+
+    fn line_text(&self, idx: usize) -> Option<Cow<'_, str>> {
+        self.line(idx).map(trim_terminator)
+    }
+}
+
+impl LineSource for [String] {
+    fn line_count(&self) -> usize {
+        self.len()
+    }
+
+    fn line_text(&self, idx: usize) -> Option<Cow<'_, str>> {
+        self.get(idx).map(|line| Cow::Borrowed(line.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rope_lines_drop_terminators() {
+        let rope = Rope::from("one\r\ntwo\n");
+        assert_eq!(rope.line_count(), 3);
+        assert_eq!(rope.line_text(0).as_deref(), Some("one"));
+        assert_eq!(rope.line_text(1).as_deref(), Some("two"));
+        assert_eq!(rope.line_text(2).as_deref(), Some(""));
+        assert_eq!(rope.line_text(3), None);
+        assert!(rope.is_complete());
+    }
+
+    #[test]
+    fn string_slices_are_sources() {
+        let lines = ["a".to_owned(), "b".to_owned()];
+        assert_eq!(lines.line_count(), 2);
+        assert_eq!(lines.line_text(1).as_deref(), Some("b"));
+    }
+}
@@ -22,9 +22,24 @@
 //! ## Links
 //! - `[link=https://example.com]Click here[/link]` - Hyperlink (stored as metadata)
 //!
+//! ## Style Classes and Theme Colors
+//! - `[error]text[/error]` - Any other bare tag names a [`StyleSheet`] class
+//! - `[class=muted]text[/class]` - Explicit class
+//! - `[fg=theme.accent]text[/fg]` - Theme slot (`[bg=theme.surface]` likewise)
+//!
+//! [`parse_themed_markup`] keeps these symbolic in a [`ThemedMarkup`], which
+//! is resolved against the current stylesheet and theme at render time.
+//! [`parse_markup`] resolves them immediately: classes add no style and
+//! theme colors use the default theme.
+//!
+//! ## Round-tripping
+//! [`ThemedMarkup::to_markup`] and [`text_to_markup`] convert back to markup.
+//!
 //! ## Escaping
 //! - `\[` - Literal `[` character
 //! - `\\` - Literal `\` character
+//! - Inside a tag, `\]` and `\\` give a literal `]` or `\` in the value
+//!   (`[link=https://e.com/a\]b]`)
 //!
 //! # Example
 //! ```
@@ -36,7 +51,7 @@
 
 use crate::text::{Span, Text};
 use ftui_render::cell::PackedRgba;
-use ftui_style::Style;
+use ftui_style::{ResolvedTheme, Style, StyleFlags, StyleSheet, Theme};
 
 /// Errors that can occur during markup parsing.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -119,14 +134,395 @@ struct StyleEntry {
     tag: String,
     /// Position where this tag was opened.
     position: usize,
-    /// The style before this tag was applied.
-    previous_style: Style,
-    /// The style delta applied by this tag.
-    style_delta: Style,
+    /// The style contribution of this tag.
+    style_ref: StyleRef,
     /// URL for link tags.
     link_url: Option<String>,
 }
 
+/// A semantic color slot of a [`Theme`], referenced as `theme.<name>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeColor {
+    Primary,
+    Secondary,
+    Accent,
+    Background,
+    Surface,
+    Overlay,
+    Text,
+    TextMuted,
+    TextSubtle,
+    Success,
+    Warning,
+    Error,
+    Info,
+    Border,
+    BorderFocused,
+    SelectionBg,
+    SelectionFg,
+    ScrollbarTrack,
+    ScrollbarThumb,
+}
+
+impl ThemeColor {
+    /// Every slot, in declaration order.
+    pub const ALL: [Self; 19] = [
+        Self::Primary,
+        Self::Secondary,
+        Self::Accent,
+        Self::Background,
+        Self::Surface,
+        Self::Overlay,
+        Self::Text,
+        Self::TextMuted,
+        Self::TextSubtle,
+        Self::Success,
+        Self::Warning,
+        Self::Error,
+        Self::Info,
+        Self::Border,
+        Self::BorderFocused,
+        Self::SelectionBg,
+        Self::SelectionFg,
+        Self::ScrollbarTrack,
+        Self::ScrollbarThumb,
+    ];
+
+    /// The slot's markup name, matching the [`Theme`] field (e.g. `"text_muted"`).
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Secondary => "secondary",
+            Self::Accent => "accent",
+            Self::Background => "background",
+            Self::Surface => "surface",
+            Self::Overlay => "overlay",
+            Self::Text => "text",
+            Self::TextMuted => "text_muted",
+            Self::TextSubtle => "text_subtle",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Info => "info",
+            Self::Border => "border",
+            Self::BorderFocused => "border_focused",
+            Self::SelectionBg => "selection_bg",
+            Self::SelectionFg => "selection_fg",
+            Self::ScrollbarTrack => "scrollbar_track",
+            Self::ScrollbarThumb => "scrollbar_thumb",
+        }
+    }
+
+    /// Look up a slot by name, ignoring case and accepting `-` for `_`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase().replace('-', "_");
+        Self::ALL.into_iter().find(|slot| slot.name() == name)
+    }
+
+    /// The slot's color in a resolved theme.
+    #[must_use]
+    pub fn resolve(self, theme: &ResolvedTheme) -> PackedRgba {
+        let color = match self {
+            Self::Primary => theme.primary,
+            Self::Secondary => theme.secondary,
+            Self::Accent => theme.accent,
+            Self::Background => theme.background,
+            Self::Surface => theme.surface,
+            Self::Overlay => theme.overlay,
+            Self::Text => theme.text,
+            Self::TextMuted => theme.text_muted,
+            Self::TextSubtle => theme.text_subtle,
+            Self::Success => theme.success,
+            Self::Warning => theme.warning,
+            Self::Error => theme.error,
+            Self::Info => theme.info,
+            Self::Border => theme.border,
+            Self::BorderFocused => theme.border_focused,
+            Self::SelectionBg => theme.selection_bg,
+            Self::SelectionFg => theme.selection_fg,
+            Self::ScrollbarTrack => theme.scrollbar_track,
+            Self::ScrollbarThumb => theme.scrollbar_thumb,
+        };
+        let rgb = color.to_rgb();
+        PackedRgba::rgb(rgb.r, rgb.g, rgb.b)
+    }
+}
+
+/// The style contribution of one markup tag, kept unresolved until render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleRef {
+    /// A fixed style from an attribute or literal color tag.
+    Literal(Style),
+    /// A named style looked up in a [`StyleSheet`] (`[error]`, `[class=muted]`).
+    Class(String),
+    /// Foreground from a theme slot (`[fg=theme.accent]`).
+    ThemeFg(ThemeColor),
+    /// Background from a theme slot (`[bg=theme.surface]`).
+    ThemeBg(ThemeColor),
+    /// A `[link]` tag, rendered underlined.
+    Link(Option<String>),
+}
+
+impl StyleRef {
+    fn resolve(&self, classes: &dyn Fn(&str) -> Option<Style>, theme: &ResolvedTheme) -> Style {
+        match self {
+            Self::Literal(style) => *style,
+            Self::Class(name) => classes(name).unwrap_or_default(),
+            Self::ThemeFg(slot) => Style::new().fg(slot.resolve(theme)),
+            Self::ThemeBg(slot) => Style::new().bg(slot.resolve(theme)),
+            Self::Link(_) => Style::new().underline(),
+        }
+    }
+
+    fn write_open(&self, out: &mut String) -> Vec<&'static str> {
+        match self {
+            Self::Literal(style) => write_style_open(style, out),
+            Self::Class(name) => {
+                out.push_str("[class=");
+                escape_tag_value(name, out);
+                out.push(']');
+                vec!["class"]
+            }
+            Self::ThemeFg(slot) => {
+                out.push_str("[fg=theme.");
+                out.push_str(slot.name());
+                out.push(']');
+                vec!["fg"]
+            }
+            Self::ThemeBg(slot) => {
+                out.push_str("[bg=theme.");
+                out.push_str(slot.name());
+                out.push(']');
+                vec!["bg"]
+            }
+            Self::Link(url) => {
+                write_link_open(url.as_deref(), out);
+                vec!["link"]
+            }
+        }
+    }
+}
+
+/// A span of parsed markup whose style is resolved at render time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupSpan {
+    /// The text content.
+    pub content: String,
+    /// Style contributions of the enclosing tags, outermost first.
+    pub refs: Vec<StyleRef>,
+    /// Hyperlink URL, if inside a `[link]` tag.
+    pub link: Option<String>,
+}
+
+impl MarkupSpan {
+    /// Resolve this span's style against a stylesheet and theme.
+    #[must_use]
+    pub fn style(&self, sheet: &StyleSheet, theme: &ResolvedTheme) -> Style {
+        self.resolve_style(&|name| sheet.get(name), theme)
+    }
+
+    fn resolve_style(
+        &self,
+        classes: &dyn Fn(&str) -> Option<Style>,
+        theme: &ResolvedTheme,
+    ) -> Style {
+        self.refs.iter().fold(Style::new(), |style, style_ref| {
+            style.merge(&style_ref.resolve(classes, theme))
+        })
+    }
+}
+
+/// Parsed markup that keeps style classes and theme colors symbolic.
+///
+/// Store this instead of a [`Text`] and call [`resolve`](Self::resolve) when
+/// rendering: a theme switch or stylesheet change then restyles existing
+/// text without re-parsing.
+///
+/// # Example
+/// ```
+/// use ftui_style::{StyleSheet, Theme};
+/// use ftui_text::markup::parse_themed_markup;
+///
+/// let markup = parse_themed_markup("[error]Failed:[/error] [fg=theme.accent]retry[/fg]").unwrap();
+/// let sheet = StyleSheet::with_defaults();
+/// let text = markup.resolve(&sheet, &Theme::default().resolve(true));
+/// assert_eq!(text.to_plain_text(), "Failed: retry");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThemedMarkup {
+    spans: Vec<MarkupSpan>,
+}
+
+impl ThemedMarkup {
+    /// The parsed spans.
+    #[must_use]
+    pub fn spans(&self) -> &[MarkupSpan] {
+        &self.spans
+    }
+
+    /// The text without markup.
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        self.spans
+            .iter()
+            .map(|span| span.content.as_str())
+            .collect()
+    }
+
+    /// Resolve classes against `sheet` and theme colors against `theme`.
+    ///
+    /// Classes missing from the stylesheet contribute no style.
+    #[must_use]
+    pub fn resolve(&self, sheet: &StyleSheet, theme: &ResolvedTheme) -> Text<'static> {
+        self.resolve_with(&|name| sheet.get(name), theme)
+    }
+
+    fn resolve_with(
+        &self,
+        classes: &dyn Fn(&str) -> Option<Style>,
+        theme: &ResolvedTheme,
+    ) -> Text<'static> {
+        Text::from_spans(self.spans.iter().map(|markup| {
+            let style = markup.resolve_style(classes, theme);
+            let span = if style.is_empty() {
+                Span::raw(markup.content.clone())
+            } else {
+                Span::styled(markup.content.clone(), style)
+            };
+            match &markup.link {
+                Some(url) => span.link(url.clone()),
+                None => span,
+            }
+        }))
+    }
+
+    /// Convert back to markup, keeping classes and theme references.
+    ///
+    /// Parsing the result yields equal [`ThemedMarkup`], except that tag
+    /// spellings are normalized (`[error]` becomes `[class=error]`).
+    #[must_use]
+    pub fn to_markup(&self) -> String {
+        let mut out = String::new();
+        for span in &self.spans {
+            let mut closers = Vec::new();
+            for style_ref in &span.refs {
+                closers.extend(style_ref.write_open(&mut out));
+            }
+            escape_markup(&span.content, &mut out);
+            write_closers(&closers, &mut out);
+        }
+        out
+    }
+}
+
+/// Convert styled [`Text`] to markup that [`parse_markup`] reads back.
+///
+/// Colors are written as `#rrggbb` and alpha is dropped. Attributes without
+/// a markup tag (e.g. double underline) and underline colors are omitted.
+/// Links are written as `[link=...]`, which implies underline.
+#[must_use]
+pub fn text_to_markup(text: &Text<'_>) -> String {
+    let mut out = String::new();
+    for (idx, line) in text.lines().iter().enumerate() {
+        if idx > 0 {
+            out.push('\n');
+        }
+        for span in line.spans() {
+            let mut closers = Vec::new();
+            let mut style = span.style.unwrap_or_default();
+            if let Some(url) = &span.link {
+                write_link_open(Some(url.as_ref()), &mut out);
+                closers.push("link");
+                if let Some(attrs) = style.attrs.as_mut() {
+                    attrs.remove(StyleFlags::UNDERLINE);
+                }
+            }
+            closers.extend(write_style_open(&style, &mut out));
+            escape_markup(&span.content, &mut out);
+            write_closers(&closers, &mut out);
+        }
+    }
+    out
+}
+
+/// Attribute tags in the order they are emitted.
+const ATTR_TAGS: [(StyleFlags, &str); 8] = [
+    (StyleFlags::BOLD, "bold"),
+    (StyleFlags::ITALIC, "italic"),
+    (StyleFlags::UNDERLINE, "underline"),
+    (StyleFlags::DIM, "dim"),
+    (StyleFlags::REVERSE, "reverse"),
+    (StyleFlags::STRIKETHROUGH, "strikethrough"),
+    (StyleFlags::BLINK, "blink"),
+    (StyleFlags::HIDDEN, "hidden"),
+];
+
+/// Write opening tags for `style`, returning the tag names to close.
+fn write_style_open(style: &Style, out: &mut String) -> Vec<&'static str> {
+    let mut opened = Vec::new();
+    for (flag, tag) in ATTR_TAGS {
+        if style.has_attr(flag) {
+            out.push('[');
+            out.push_str(tag);
+            out.push(']');
+            opened.push(tag);
+        }
+    }
+    for (color, tag) in [(style.fg, "fg"), (style.bg, "bg")] {
+        if let Some(color) = color {
+            out.push_str(&format!(
+                "[{tag}=#{:02x}{:02x}{:02x}]",
+                color.r(),
+                color.g(),
+                color.b()
+            ));
+            opened.push(tag);
+        }
+    }
+    opened
+}
+
+fn write_link_open(url: Option<&str>, out: &mut String) {
+    match url {
+        Some(url) => {
+            out.push_str("[link=");
+            escape_tag_value(url, out);
+            out.push(']');
+        }
+        None => out.push_str("[link]"),
+    }
+}
+
+fn write_closers(closers: &[&str], out: &mut String) {
+    for tag in closers.iter().rev() {
+        out.push_str("[/");
+        out.push_str(tag);
+        out.push(']');
+    }
+}
+
+/// Escape characters the parser treats specially.
+fn escape_markup(text: &str, out: &mut String) {
+    for ch in text.chars() {
+        if matches!(ch, '[' | ']' | '\\') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+}
+
+/// Escape characters that would end a tag value early.
+fn escape_tag_value(value: &str, out: &mut String) {
+    for ch in value.chars() {
+        if matches!(ch, ']' | '\\') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+}
+
 /// Parse markup string into styled Text.
 ///
 /// This is a convenience function that creates a parser and parses the input.
@@ -144,17 +540,22 @@ pub fn parse_markup(input: &str) -> Result<Text<'static>, MarkupError> {
     Ok(parsed)
 }
 
+/// Parse markup, keeping style classes and theme colors unresolved.
+///
+/// See [`ThemedMarkup`].
+pub fn parse_themed_markup(input: &str) -> Result<ThemedMarkup, MarkupError> {
+    MarkupParser::new().parse_themed(input)
+}
+
 /// A parser for BBCode-style markup.
 ///
 /// The parser maintains a style stack to handle nested tags correctly.
-/// Each opening tag pushes the current style onto the stack and modifies it.
-/// Each closing tag pops the stack to restore the previous style.
+/// Each opening tag pushes its style contribution onto the stack; each
+/// closing tag removes it. A span's style combines the open entries.
 #[derive(Debug, Default)]
 pub struct MarkupParser {
     /// Stack of open style entries.
     style_stack: Vec<StyleEntry>,
-    /// Current accumulated style.
-    current_style: Style,
     /// Whether we're inside a link (links can't nest).
     in_link: bool,
     /// Current link URL if in a link.
@@ -171,16 +572,25 @@ impl MarkupParser {
     /// Reset the parser state.
     pub fn reset(&mut self) {
         self.style_stack.clear();
-        self.current_style = Style::default();
         self.in_link = false;
         self.current_link = None;
     }
 
     /// Parse a markup string into styled Text.
+    ///
+    /// Style classes contribute no style and theme colors resolve against
+    /// the default theme. Use [`parse_themed`](Self::parse_themed) to
+    /// resolve them at render time instead.
     pub fn parse(&mut self, input: &str) -> Result<Text<'static>, MarkupError> {
+        let markup = self.parse_themed(input)?;
+        Ok(markup.resolve_with(&|_| None, &Theme::default().resolve(true)))
+    }
+
+    /// Parse a markup string, keeping classes and theme colors symbolic.
+    pub fn parse_themed(&mut self, input: &str) -> Result<ThemedMarkup, MarkupError> {
         self.reset();
 
-        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut spans: Vec<MarkupSpan> = Vec::new();
         let mut current_text = String::new();
 
         let mut chars = input.char_indices().peekable();
@@ -204,7 +614,15 @@ impl MarkupParser {
                     let mut tag_content = String::new();
                     let mut found_close = false;
 
-                    for (_, tag_ch) in chars.by_ref() {
+                    while let Some((_, tag_ch)) = chars.next() {
+                        if tag_ch == '\\'
+                            && let Some(&(_, next_ch)) = chars.peek()
+                            && (next_ch == ']' || next_ch == '\\')
+                        {
+                            chars.next();
+                            tag_content.push(next_ch);
+                            continue;
+                        }
                         if tag_ch == ']' {
                             found_close = true;
                             break;
@@ -291,22 +709,21 @@ impl MarkupParser {
             });
         }
 
-        Ok(Text::from_spans(spans))
-    }
-
-    /// Create a span with the current style and link.
-    fn make_span(&self, text: String) -> Span<'static> {
-        let span = if self.current_style.is_empty() {
-            Span::raw(text)
-        } else {
-            Span::styled(text, self.current_style)
-        };
-
-        // Attach link if present
-        if let Some(url) = &self.current_link {
-            span.link(url.clone())
-        } else {
-            span
+        Ok(ThemedMarkup { spans })
+    }
+
+    /// Create a span with the open tags' styles and the current link.
+    fn make_span(&self, text: String) -> MarkupSpan {
+        MarkupSpan {
+            content: text,
+            refs: self
+                .style_stack
+                .iter()
+                .map(|entry| &entry.style_ref)
+                .filter(|style_ref| **style_ref != StyleRef::Literal(Style::new()))
+                .cloned()
+                .collect(),
+            link: self.current_link.clone(),
         }
     }
 
@@ -321,20 +738,13 @@ impl MarkupParser {
             return Err(MarkupError::DepthLimitExceeded { position });
         }
 
-        // Apply the new style to get the delta
-        let style_delta = self.apply_tag(name, value, position)?;
-
-        // Save current state
+        let style_ref = self.apply_tag(name, value, position)?;
         let entry = StyleEntry {
             tag: name.to_lowercase(),
             position,
-            previous_style: self.current_style,
-            style_delta,
+            style_ref,
             link_url: self.current_link.clone(),
         };
-
-        self.current_style = self.current_style.merge(&style_delta);
-
         self.style_stack.push(entry);
         Ok(())
     }
@@ -354,19 +764,9 @@ impl MarkupParser {
                 position,
             })?;
 
-        let entry = self.style_stack.remove(entry_idx);
-
-        // Restore the style to what it was before this tag was pushed
-        self.current_style = entry.previous_style;
-
-        // Re-apply styles from any stack entries that were above the removed
-        // entry (now shifted down to entry_idx..).
-        for remaining in &self.style_stack[entry_idx..] {
-            // Because we removed an entry below this one, we must rebuild the
-            // accumulated style chain. We use the stored style_delta to exactly
-            // replay the effect of each remaining tag.
-            self.current_style = self.current_style.merge(&remaining.style_delta);
-        }
+        // Entries above the removed one keep their contributions; span
+        // styles are rebuilt from the stack, so nothing needs replaying.
+        self.style_stack.remove(entry_idx);
 
         // Derive link state from remaining entries on the stack.
         // We cannot use entry.link_url because it may be stale if the link
@@ -385,41 +785,52 @@ impl MarkupParser {
         Ok(())
     }
 
-    /// Apply a tag and return the style delta.
+    /// Apply a tag and return its style contribution.
     fn apply_tag(
         &mut self,
         name: &str,
         value: Option<&str>,
         position: usize,
-    ) -> Result<Style, MarkupError> {
+    ) -> Result<StyleRef, MarkupError> {
         let name_lower = name.to_lowercase();
+        let required = || {
+            value.ok_or_else(|| MarkupError::InvalidAttribute {
+                name: name.to_string(),
+                position,
+            })
+        };
+        let literal = |style| Ok(StyleRef::Literal(style));
         match name_lower.as_str() {
             // Simple style attributes
-            "bold" | "b" => Ok(Style::new().bold()),
-            "italic" | "i" => Ok(Style::new().italic()),
-            "underline" | "u" => Ok(Style::new().underline()),
-            "dim" => Ok(Style::new().dim()),
-            "reverse" => Ok(Style::new().reverse()),
-            "strikethrough" | "s" => Ok(Style::new().strikethrough()),
-            "blink" => Ok(Style::new().blink()),
-            "hidden" => Ok(Style::new().hidden()),
+            "bold" | "b" => literal(Style::new().bold()),
+            "italic" | "i" => literal(Style::new().italic()),
+            "underline" | "u" => literal(Style::new().underline()),
+            "dim" => literal(Style::new().dim()),
+            "reverse" => literal(Style::new().reverse()),
+            "strikethrough" | "s" => literal(Style::new().strikethrough()),
+            "blink" => literal(Style::new().blink()),
+            "hidden" => literal(Style::new().hidden()),
 
             // Color attributes
-            "fg" | "color" => {
-                let color_str = value.ok_or_else(|| MarkupError::InvalidAttribute {
-                    name: name.to_string(),
-                    position,
-                })?;
-                let color = parse_color(color_str, position)?;
-                Ok(Style::new().fg(color))
-            }
-            "bg" | "background" => {
-                let color_str = value.ok_or_else(|| MarkupError::InvalidAttribute {
-                    name: name.to_string(),
-                    position,
-                })?;
-                let color = parse_color(color_str, position)?;
-                Ok(Style::new().bg(color))
+            "fg" | "color" => match parse_color_ref(required()?, position)? {
+                ColorRef::Literal(color) => literal(Style::new().fg(color)),
+                ColorRef::Theme(slot) => Ok(StyleRef::ThemeFg(slot)),
+            },
+            "bg" | "background" => match parse_color_ref(required()?, position)? {
+                ColorRef::Literal(color) => literal(Style::new().bg(color)),
+                ColorRef::Theme(slot) => Ok(StyleRef::ThemeBg(slot)),
+            },
+
+            // Stylesheet class
+            "class" => {
+                let class = required()?.trim();
+                if class.is_empty() {
+                    return Err(MarkupError::InvalidAttribute {
+                        name: name.to_string(),
+                        position,
+                    });
+                }
+                Ok(StyleRef::Class(class.to_string()))
             }
 
             // Link
@@ -429,16 +840,38 @@ impl MarkupParser {
                 }
                 self.in_link = true;
                 self.current_link = value.map(|s| s.to_string());
-                // Links are typically rendered with underline
-                Ok(Style::new().underline())
+                // Links are rendered with underline
+                Ok(StyleRef::Link(self.current_link.clone()))
             }
 
-            // Unknown tag - treat as no-op but still track for closing
-            _ => Ok(Style::new()),
+            // Any other bare tag names a stylesheet class (`[error]`)
+            _ if value.is_none() => Ok(StyleRef::Class(name.to_string())),
+
+            // Unknown tag with a value - no-op but still tracked for closing
+            _ => literal(Style::new()),
         }
     }
 }
 
+/// A color tag value: literal or theme slot.
+enum ColorRef {
+    Literal(PackedRgba),
+    Theme(ThemeColor),
+}
+
+/// Parse a color tag value, accepting `theme.<slot>` references.
+fn parse_color_ref(s: &str, position: usize) -> Result<ColorRef, MarkupError> {
+    match s.trim().strip_prefix("theme.") {
+        Some(slot) => ThemeColor::from_name(slot)
+            .map(ColorRef::Theme)
+            .ok_or_else(|| MarkupError::InvalidColor {
+                value: s.trim().to_string(),
+                position,
+            }),
+        None => parse_color(s, position).map(ColorRef::Literal),
+    }
+}
+
 /// Parse a color specification.
 ///
 /// Supports:
@@ -1355,4 +1788,123 @@ mod tests {
         let err: Box<dyn std::error::Error> = Box::new(MarkupError::EmptyTag { position: 0 });
         assert!(!err.to_string().is_empty());
     }
+
+    // =========================================================================
+    // Themed markup: classes, theme colors, round-tripping
+    // =========================================================================
+
+    fn span_style(text: &Text<'_>, idx: usize) -> Style {
+        text.lines()[0].spans()[idx].style.unwrap_or_default()
+    }
+
+    #[test]
+    fn themed_markup_resolves_classes_and_theme_colors() {
+        let markup =
+            parse_themed_markup("[error]a[/error][class=muted]b[/class][fg=theme.accent]c[/fg]")
+                .unwrap();
+        assert_eq!(markup.spans()[0].refs, [StyleRef::Class("error".into())]);
+        assert_eq!(
+            markup.spans()[2].refs,
+            [StyleRef::ThemeFg(ThemeColor::Accent)]
+        );
+
+        let sheet = StyleSheet::with_defaults();
+        let dark = Theme::default().resolve(true);
+        let text = markup.resolve(&sheet, &dark);
+        assert_eq!(text.to_plain_text(), "abc");
+        assert_eq!(span_style(&text, 0), sheet.get("error").unwrap());
+        assert_eq!(span_style(&text, 1), sheet.get("muted").unwrap());
+        assert_eq!(
+            span_style(&text, 2).fg,
+            Some(ThemeColor::Accent.resolve(&dark))
+        );
+    }
+
+    #[test]
+    fn themed_markup_restyles_on_theme_and_sheet_change() {
+        let markup = parse_themed_markup("[warning]w[/warning] [bg=theme.surface]s[/bg]").unwrap();
+        let sheet = StyleSheet::new();
+        let mut theme = Theme::default().resolve(true);
+
+        let before = markup.resolve(&sheet, &theme);
+        assert_eq!(before.lines()[0].spans()[0].style, None);
+
+        sheet.define("warning", Style::new().fg(PackedRgba::rgb(1, 2, 3)));
+        theme.surface = ftui_style::Color::rgb(9, 8, 7);
+        let after = markup.resolve(&sheet, &theme);
+        assert_eq!(span_style(&after, 0).fg, Some(PackedRgba::rgb(1, 2, 3)));
+        assert_eq!(span_style(&after, 2).bg, Some(PackedRgba::rgb(9, 8, 7)));
+    }
+
+    #[test]
+    fn theme_color_names_and_errors() {
+        assert_eq!(
+            ThemeColor::from_name("Text-Muted"),
+            Some(ThemeColor::TextMuted)
+        );
+        for slot in ThemeColor::ALL {
+            assert_eq!(ThemeColor::from_name(slot.name()), Some(slot));
+        }
+        assert!(matches!(
+            parse_markup("[fg=theme.nope]x[/fg]"),
+            Err(MarkupError::InvalidColor { .. })
+        ));
+        assert!(matches!(
+            parse_markup("[class=]x[/class]"),
+            Err(MarkupError::InvalidAttribute { .. })
+        ));
+
+        // Eager parsing uses the default theme.
+        let text = parse_markup("[fg=theme.error]x[/fg]").unwrap();
+        let theme = Theme::default().resolve(true);
+        assert_eq!(
+            span_style(&text, 0).fg,
+            Some(ThemeColor::Error.resolve(&theme))
+        );
+    }
+
+    #[test]
+    fn themed_markup_round_trips() {
+        let source = r"[error]Oops[/error] [b][fg=theme.accent]x[/fg][/b] \[y\] [link=https://e.com]go[/link]";
+        let markup = parse_themed_markup(source).unwrap();
+        let emitted = markup.to_markup();
+        assert_eq!(
+            emitted,
+            r"[class=error]Oops[/class] [bold][fg=theme.accent]x[/fg][/bold] \[y\] [link=https://e.com]go[/link]"
+        );
+        assert_eq!(parse_themed_markup(&emitted).unwrap(), markup);
+    }
+
+    #[test]
+    fn tag_values_round_trip_brackets() {
+        let source = r"[link=https://e.com/a\]b\\c]go[/link]";
+        let text = parse_markup(source).unwrap();
+        let span = &text.lines()[0].spans()[0];
+        assert_eq!(span.link.as_deref(), Some(r"https://e.com/a]b\c"));
+        assert_eq!(text_to_markup(&text), source);
+        assert_eq!(parse_markup(&text_to_markup(&text)).unwrap(), text);
+
+        let markup = parse_themed_markup(r"[class=odd\]name]x[/class][link=\]]y[/link]").unwrap();
+        let emitted = markup.to_markup();
+        assert_eq!(emitted, r"[class=odd\]name]x[/class][link=\]]y[/link]");
+        assert_eq!(parse_themed_markup(&emitted).unwrap(), markup);
+    }
+
+    #[test]
+    fn text_to_markup_round_trips() {
+        let text = parse_markup(
+            r"[bold]a[/bold] [fg=#ff0000][bg=#0000ff]\[x\\][/bg][/fg] [link=https://e.com]l[/link] [i][s]z[/s][/i]",
+        )
+        .unwrap();
+        let emitted = text_to_markup(&text);
+        assert_eq!(parse_markup(&emitted).unwrap(), text);
+        assert!(emitted.contains("[fg=#ff0000][bg=#0000ff]"));
+
+        let multi = Text::raw("one\ntwo [x]");
+        assert_eq!(
+            text_to_markup(&multi),
+            r"one
+two \[x\]"
+        );
+    }
 }
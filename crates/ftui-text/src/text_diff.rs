@@ -0,0 +1,1131 @@
+#![forbid(unsafe_code)]
+
+//! Text diffing at line, word and grapheme granularity.
+//!
+//! [`diff_slices`] computes an edit script between any two token sequences
+//! using Myers' O(ND) algorithm (linear-space variant) or patience diff.
+//! [`TextDiff`] applies it to the lines of two texts, groups changes into
+//! [`DiffHunk`]s with context, and renders them as styled [`Text`] in
+//! unified or side-by-side form. Changed line pairs are diffed again at
+//! word or grapheme granularity so the exact edits are emphasized.
+//!
+//! Grapheme diffs never split a cluster, and side-by-side columns are
+//! fitted by display width.
+//!
+//! # Example
+//! ```
+//! use ftui_text::text_diff::{DiffStyles, TextDiff};
+//!
+//! let diff = TextDiff::new("a\nb\nc\n", "a\nB\nc\n");
+//! let unified = diff.unified(&DiffStyles::default());
+//! assert_eq!(unified.to_plain_text(), "@@ -1,3 +1,3 @@\n a\n-b\n+B\n c");
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Range;
+
+use ftui_render::cell::PackedRgba;
+use ftui_style::{ResolvedTheme, Style};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::text::{Line, Span, Text};
+use crate::wrap::{display_width, truncate_to_width_with_info};
+
+// ---------------------------------------------------------------------------
+// Edit scripts
+// ---------------------------------------------------------------------------
+
+/// Diff algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DiffAlgorithm {
+    /// Myers' algorithm: a shortest edit script.
+    #[default]
+    Myers,
+    /// Patience diff: anchors on tokens unique to both sides, then falls
+    /// back to Myers between anchors. Often more readable for code.
+    Patience,
+}
+
+/// Kind of a diff operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffTag {
+    /// Present in both sides.
+    Equal,
+    /// Only in the old side.
+    Delete,
+    /// Only in the new side.
+    Insert,
+}
+
+/// A run of tokens with the same [`DiffTag`].
+///
+/// `old` and `new` are token index ranges; one of them is empty for
+/// deletes and inserts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffOp {
+    pub tag: DiffTag,
+    pub old: Range<usize>,
+    pub new: Range<usize>,
+}
+
+impl DiffOp {
+    /// Number of tokens covered (on the side that has them).
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.old.len().max(self.new.len())
+    }
+
+    /// Whether the op covers no tokens.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Compute the edit script turning `old` into `new`.
+///
+/// Within a change, deletes come before inserts. Equal runs are merged.
+#[must_use]
+pub fn diff_slices<T: Hash + Eq>(old: &[T], new: &[T], algorithm: DiffAlgorithm) -> Vec<DiffOp> {
+    // Intern tokens so the algorithms compare integers.
+    let mut ids: HashMap<&T, u32> = HashMap::new();
+    let mut intern = |token| {
+        let next = ids.len() as u32;
+        *ids.entry(token).or_insert(next)
+    };
+    let a: Vec<u32> = old.iter().map(&mut intern).collect();
+    let b: Vec<u32> = new.iter().map(&mut intern).collect();
+
+    let mut matches = Vec::new();
+    match algorithm {
+        DiffAlgorithm::Myers => myers(&a, &b, 0, 0, &mut matches),
+        DiffAlgorithm::Patience => patience(&a, &b, 0, 0, &mut matches),
+    }
+    ops_from_matches(&matches, a.len(), b.len())
+}
+
+/// Convert ascending matched index pairs into merged ops.
+fn ops_from_matches(matches: &[(usize, usize)], old_len: usize, new_len: usize) -> Vec<DiffOp> {
+    let mut ops: Vec<DiffOp> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for &(ai, bj) in matches.iter().chain(std::iter::once(&(old_len, new_len))) {
+        if i < ai {
+            ops.push(DiffOp {
+                tag: DiffTag::Delete,
+                old: i..ai,
+                new: j..j,
+            });
+        }
+        if j < bj {
+            ops.push(DiffOp {
+                tag: DiffTag::Insert,
+                old: ai..ai,
+                new: j..bj,
+            });
+        }
+        if ai == old_len {
+            break;
+        }
+        match ops.last_mut() {
+            Some(op) if op.tag == DiffTag::Equal && op.old.end == ai && op.new.end == bj => {
+                op.old.end += 1;
+                op.new.end += 1;
+            }
+            _ => ops.push(DiffOp {
+                tag: DiffTag::Equal,
+                old: ai..ai + 1,
+                new: bj..bj + 1,
+            }),
+        }
+        (i, j) = (ai + 1, bj + 1);
+    }
+    ops
+}
+
+/// Lengths of the common prefix and suffix (non-overlapping).
+fn trim_common(a: &[u32], b: &[u32]) -> (usize, usize) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    (prefix, suffix)
+}
+
+fn push_run(matches: &mut Vec<(usize, usize)>, a_off: usize, b_off: usize, len: usize) {
+    matches.extend((0..len).map(|i| (a_off + i, b_off + i)));
+}
+
+/// Linear-space Myers diff, appending matches in ascending order.
+fn myers(a: &[u32], b: &[u32], a_off: usize, b_off: usize, matches: &mut Vec<(usize, usize)>) {
+    let (prefix, suffix) = trim_common(a, b);
+    push_run(matches, a_off, b_off, prefix);
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+
+    if !a_mid.is_empty()
+        && !b_mid.is_empty()
+        && let Some((x, y)) = middle_snake(a_mid, b_mid)
+    {
+        let (a_off, b_off) = (a_off + prefix, b_off + prefix);
+        myers(&a_mid[..x], &b_mid[..y], a_off, b_off, matches);
+        myers(&a_mid[x..], &b_mid[y..], a_off + x, b_off + y, matches);
+    }
+
+    push_run(
+        matches,
+        a_off + a.len() - suffix,
+        b_off + b.len() - suffix,
+        suffix,
+    );
+}
+
+/// Find a split point on a shortest edit path by searching forward and
+/// backward at once. Returns `None` if the inputs share no tokens.
+fn middle_snake(a: &[u32], b: &[u32]) -> Option<(usize, usize)> {
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d + 1;
+    let v_len = (2 * max_d + 3) as usize;
+    let mut forward = vec![-1isize; v_len];
+    let mut backward = vec![-1isize; v_len];
+    forward[(offset + 1) as usize] = 0;
+    backward[(offset + 1) as usize] = 0;
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    // Diagonals that ran off the grid are skipped on later rounds.
+    let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+
+    for d in 0..max_d {
+        let mut k1 = -d + k1_start;
+        while k1 <= d - k1_end {
+            let idx = (offset + k1) as usize;
+            let mut x = if k1 == -d || (k1 != d && forward[idx - 1] < forward[idx + 1]) {
+                forward[idx + 1]
+            } else {
+                forward[idx - 1] + 1
+            };
+            let mut y = x - k1;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[idx] = x;
+            if x > n {
+                k1_end += 2;
+            } else if y > m {
+                k1_start += 2;
+            } else if odd {
+                let k2 = offset + delta - k1;
+                if (0..v_len as isize).contains(&k2)
+                    && backward[k2 as usize] != -1
+                    && x >= n - backward[k2 as usize]
+                {
+                    return Some((x as usize, y as usize));
+                }
+            }
+            k1 += 2;
+        }
+
+        let mut k2 = -d + k2_start;
+        while k2 <= d - k2_end {
+            let idx = (offset + k2) as usize;
+            let mut x = if k2 == -d || (k2 != d && backward[idx - 1] < backward[idx + 1]) {
+                backward[idx + 1]
+            } else {
+                backward[idx - 1] + 1
+            };
+            let mut y = x - k2;
+            while x < n && y < m && a[(n - x - 1) as usize] == b[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[idx] = x;
+            if x > n {
+                k2_end += 2;
+            } else if y > m {
+                k2_start += 2;
+            } else if !odd {
+                let k1 = offset + delta - k2;
+                if (0..v_len as isize).contains(&k1) && forward[k1 as usize] != -1 {
+                    let fx = forward[k1 as usize];
+                    let fy = fx - (k1 - offset);
+                    if fx >= n - x {
+                        return Some((fx as usize, fy as usize));
+                    }
+                }
+            }
+            k2 += 2;
+        }
+    }
+    None
+}
+
+/// Patience diff, appending matches in ascending order.
+fn patience(a: &[u32], b: &[u32], a_off: usize, b_off: usize, matches: &mut Vec<(usize, usize)>) {
+    let (prefix, suffix) = trim_common(a, b);
+    push_run(matches, a_off, b_off, prefix);
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+    let (a_off_mid, b_off_mid) = (a_off + prefix, b_off + prefix);
+
+    let anchors = unique_anchors(a_mid, b_mid);
+    if anchors.is_empty() {
+        myers(a_mid, b_mid, a_off_mid, b_off_mid, matches);
+    } else {
+        let (mut i, mut j) = (0, 0);
+        for (ai, bj) in anchors {
+            patience(
+                &a_mid[i..ai],
+                &b_mid[j..bj],
+                a_off_mid + i,
+                b_off_mid + j,
+                matches,
+            );
+            matches.push((a_off_mid + ai, b_off_mid + bj));
+            (i, j) = (ai + 1, bj + 1);
+        }
+        patience(
+            &a_mid[i..],
+            &b_mid[j..],
+            a_off_mid + i,
+            b_off_mid + j,
+            matches,
+        );
+    }
+
+    push_run(
+        matches,
+        a_off + a.len() - suffix,
+        b_off + b.len() - suffix,
+        suffix,
+    );
+}
+
+/// Longest increasing run of tokens occurring exactly once on each side.
+fn unique_anchors(a: &[u32], b: &[u32]) -> Vec<(usize, usize)> {
+    // token -> (count in a, index in a, count in b, index in b)
+    let mut seen: HashMap<u32, (u32, usize, u32, usize)> = HashMap::new();
+    for (i, &t) in a.iter().enumerate() {
+        let entry = seen.entry(t).or_insert((0, i, 0, 0));
+        entry.0 += 1;
+    }
+    for (j, &t) in b.iter().enumerate() {
+        if let Some(entry) = seen.get_mut(&t) {
+            entry.2 += 1;
+            entry.3 = j;
+        }
+    }
+    let mut pairs: Vec<(usize, usize)> = seen
+        .into_values()
+        .filter(|&(ca, _, cb, _)| ca == 1 && cb == 1)
+        .map(|(_, i, _, j)| (i, j))
+        .collect();
+    pairs.sort_unstable();
+
+    // Patience sorting: longest increasing subsequence by `b` index.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev: Vec<Option<usize>> = vec![None; pairs.len()];
+    for (idx, &(_, j)) in pairs.iter().enumerate() {
+        let pile = tails.partition_point(|&t| pairs[t].1 < j);
+        if pile > 0 {
+            prev[idx] = Some(tails[pile - 1]);
+        }
+        if pile == tails.len() {
+            tails.push(idx);
+        } else {
+            tails[pile] = idx;
+        }
+    }
+    let mut out = Vec::with_capacity(tails.len());
+    let mut cursor = tails.last().copied();
+    while let Some(idx) = cursor {
+        out.push(pairs[idx]);
+        cursor = prev[idx];
+    }
+    out.reverse();
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Tokens and segments
+// ---------------------------------------------------------------------------
+
+/// Token granularity for diffing strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DiffGranularity {
+    /// Whole lines.
+    Line,
+    /// Words, whitespace runs and punctuation (Unicode word boundaries).
+    #[default]
+    Word,
+    /// Extended grapheme clusters.
+    Grapheme,
+}
+
+/// Split `text` into diff tokens. Line tokens exclude their terminator.
+#[must_use]
+pub fn tokenize(text: &str, granularity: DiffGranularity) -> Vec<&str> {
+    token_indices(text, granularity)
+        .into_iter()
+        .map(|(_, token)| token)
+        .collect()
+}
+
+/// Tokens paired with their byte offset in `text`.
+fn token_indices(text: &str, granularity: DiffGranularity) -> Vec<(usize, &str)> {
+    match granularity {
+        DiffGranularity::Line => {
+            let mut offset = 0;
+            text.split_inclusive('\n')
+                .map(|line| {
+                    let start = offset;
+                    offset += line.len();
+                    let line = line.strip_suffix('\n').unwrap_or(line);
+                    (start, line.strip_suffix('\r').unwrap_or(line))
+                })
+                .collect()
+        }
+        DiffGranularity::Word => text.split_word_bound_indices().collect(),
+        DiffGranularity::Grapheme => text.grapheme_indices(true).collect(),
+    }
+}
+
+/// A piece of text from a string diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffSegment<'a> {
+    pub tag: DiffTag,
+    /// Text from the old side for `Equal`/`Delete`, new side for `Insert`.
+    pub text: &'a str,
+}
+
+/// Diff two strings, returning contiguous segments in display order.
+///
+/// Concatenating the `Equal` and `Delete` segments yields `old`; the
+/// `Equal` and `Insert` segments yield `new`, except that at line
+/// granularity the terminator after each segment's last line is dropped.
+#[must_use]
+pub fn diff_segments<'a>(
+    old: &'a str,
+    new: &'a str,
+    granularity: DiffGranularity,
+    algorithm: DiffAlgorithm,
+) -> Vec<DiffSegment<'a>> {
+    let old_tokens = token_indices(old, granularity);
+    let new_tokens = token_indices(new, granularity);
+    let old_keys: Vec<&str> = old_tokens.iter().map(|&(_, t)| t).collect();
+    let new_keys: Vec<&str> = new_tokens.iter().map(|&(_, t)| t).collect();
+    diff_slices(&old_keys, &new_keys, algorithm)
+        .into_iter()
+        .map(|op| {
+            let text = match op.tag {
+                DiffTag::Insert => token_run(new, &new_tokens, op.new),
+                _ => token_run(old, &old_tokens, op.old),
+            };
+            DiffSegment { tag: op.tag, text }
+        })
+        .collect()
+}
+
+/// The source slice covered by a run of tokens.
+fn token_run<'a>(source: &'a str, tokens: &[(usize, &str)], range: Range<usize>) -> &'a str {
+    match (
+        tokens.get(range.start),
+        range.end.checked_sub(1).and_then(|i| tokens.get(i)),
+    ) {
+        (Some(&(start, _)), Some(&(last, token))) if range.start < range.end => {
+            &source[start..last + token.len()]
+        }
+        _ => "",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Line diffs
+// ---------------------------------------------------------------------------
+
+/// Options for [`TextDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// Algorithm used for both the line diff and inline diffs.
+    pub algorithm: DiffAlgorithm,
+    /// Granularity of inline emphasis within changed lines.
+    /// [`DiffGranularity::Line`] disables inline emphasis.
+    pub inline: DiffGranularity,
+    /// Unchanged lines shown around each change.
+    pub context: usize,
+}
+
+impl DiffOptions {
+    /// Create options with defaults (Myers, word emphasis, 3 context lines).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the diff algorithm.
+    #[must_use]
+    pub fn algorithm(mut self, algorithm: DiffAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Set the inline emphasis granularity.
+    #[must_use]
+    pub fn inline(mut self, granularity: DiffGranularity) -> Self {
+        self.inline = granularity;
+        self
+    }
+
+    /// Set the number of context lines.
+    #[must_use]
+    pub fn context(mut self, lines: usize) -> Self {
+        self.context = lines;
+        self
+    }
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            algorithm: DiffAlgorithm::Myers,
+            inline: DiffGranularity::Word,
+            context: 3,
+        }
+    }
+}
+
+/// Line counts of a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DiffStats {
+    /// Lines only in the new text.
+    pub inserted: usize,
+    /// Lines only in the old text.
+    pub deleted: usize,
+}
+
+/// A group of nearby changes with surrounding context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    /// Old line range covered (0-based).
+    pub old: Range<usize>,
+    /// New line range covered (0-based).
+    pub new: Range<usize>,
+    /// Ops within the hunk, including context.
+    pub ops: Vec<DiffOp>,
+}
+
+impl DiffHunk {
+    /// Unified diff header, e.g. `@@ -2,7 +2,7 @@`.
+    #[must_use]
+    pub fn header(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for DiffHunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Line numbers are 1-based; an empty range names the line before it.
+        let start = |r: &Range<usize>| if r.is_empty() { r.start } else { r.start + 1 };
+        write!(
+            f,
+            "@@ -{},{} +{},{} @@",
+            start(&self.old),
+            self.old.len(),
+            start(&self.new),
+            self.new.len()
+        )
+    }
+}
+
+/// Line diff between two texts.
+#[derive(Debug, Clone)]
+pub struct TextDiff<'a> {
+    old: Vec<&'a str>,
+    new: Vec<&'a str>,
+    ops: Vec<DiffOp>,
+    options: DiffOptions,
+}
+
+impl<'a> TextDiff<'a> {
+    /// Diff `old` against `new` with default options.
+    #[must_use]
+    pub fn new(old: &'a str, new: &'a str) -> Self {
+        Self::with_options(old, new, DiffOptions::default())
+    }
+
+    /// Diff `old` against `new` with the given options.
+    #[must_use]
+    pub fn with_options(old: &'a str, new: &'a str, options: DiffOptions) -> Self {
+        let old = tokenize(old, DiffGranularity::Line);
+        let new = tokenize(new, DiffGranularity::Line);
+        let ops = diff_slices(&old, &new, options.algorithm);
+        Self {
+            old,
+            new,
+            ops,
+            options,
+        }
+    }
+
+    /// Lines of the old text.
+    #[must_use]
+    pub fn old_lines(&self) -> &[&'a str] {
+        &self.old
+    }
+
+    /// Lines of the new text.
+    #[must_use]
+    pub fn new_lines(&self) -> &[&'a str] {
+        &self.new
+    }
+
+    /// The full edit script over lines.
+    #[must_use]
+    pub fn ops(&self) -> &[DiffOp] {
+        &self.ops
+    }
+
+    /// Whether the texts differ (ignoring line terminators).
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        self.ops.iter().any(|op| op.tag != DiffTag::Equal)
+    }
+
+    /// Count inserted and deleted lines.
+    #[must_use]
+    pub fn stats(&self) -> DiffStats {
+        self.ops.iter().fold(DiffStats::default(), |mut stats, op| {
+            match op.tag {
+                DiffTag::Equal => {}
+                DiffTag::Delete => stats.deleted += op.old.len(),
+                DiffTag::Insert => stats.inserted += op.new.len(),
+            }
+            stats
+        })
+    }
+
+    /// Group changes into hunks with `options.context` lines of context.
+    ///
+    /// Changes separated by at most twice the context share a hunk.
+    #[must_use]
+    pub fn hunks(&self) -> Vec<DiffHunk> {
+        if !self.has_changes() {
+            return Vec::new();
+        }
+        let context = self.options.context;
+        let mut ops = self.ops.clone();
+        // Clip leading and trailing context.
+        if let Some(first) = ops.first_mut()
+            && first.tag == DiffTag::Equal
+        {
+            let skip = first.old.len().saturating_sub(context);
+            first.old.start += skip;
+            first.new.start += skip;
+        }
+        if let Some(last) = ops.last_mut()
+            && last.tag == DiffTag::Equal
+        {
+            let keep = last.old.len().min(context);
+            last.old.end = last.old.start + keep;
+            last.new.end = last.new.start + keep;
+        }
+
+        let mut hunks = Vec::new();
+        let mut current = Vec::new();
+        for op in ops.into_iter().filter(|op| !op.is_empty()) {
+            if op.tag == DiffTag::Equal && op.old.len() > 2 * context {
+                if context > 0 {
+                    current.push(DiffOp {
+                        tag: DiffTag::Equal,
+                        old: op.old.start..op.old.start + context,
+                        new: op.new.start..op.new.start + context,
+                    });
+                }
+                hunks.push(hunk_from_ops(std::mem::take(&mut current)));
+                if context > 0 {
+                    current.push(DiffOp {
+                        tag: DiffTag::Equal,
+                        old: op.old.end - context..op.old.end,
+                        new: op.new.end - context..op.new.end,
+                    });
+                }
+            } else {
+                current.push(op);
+            }
+        }
+        if current.iter().any(|op| op.tag != DiffTag::Equal) {
+            hunks.push(hunk_from_ops(current));
+        }
+        hunks
+    }
+
+    /// Render hunks as a unified diff.
+    ///
+    /// Each hunk starts with its header line, followed by ` `, `-` and `+`
+    /// prefixed lines. Returns empty text when nothing changed.
+    #[must_use]
+    pub fn unified(&self, styles: &DiffStyles) -> Text<'a> {
+        let mut lines = Vec::new();
+        for hunk in self.hunks() {
+            lines.push(Line::styled(hunk.header(), styles.header));
+            for row in self.rows(&hunk, styles) {
+                match row {
+                    Row::Equal(text) => {
+                        lines.push(Line::from_spans(prefixed(" ", styles.equal, text)));
+                    }
+                    Row::Change { old, new } => {
+                        let old = old.into_iter().map(|s| prefixed("-", styles.delete, s));
+                        let new = new.into_iter().map(|s| prefixed("+", styles.insert, s));
+                        lines.extend(old.chain(new).map(Line::from_spans));
+                    }
+                }
+            }
+        }
+        Text::from_lines(lines)
+    }
+
+    /// Render hunks as two columns, old on the left and new on the right.
+    ///
+    /// Every line is exactly `width` cells wide; content is truncated at
+    /// grapheme boundaries and padded with spaces.
+    #[must_use]
+    pub fn side_by_side(&self, width: usize, styles: &DiffStyles) -> Text<'a> {
+        let inner = width.saturating_sub(SEPARATOR_WIDTH);
+        let left_width = inner / 2;
+        let right_width = inner - left_width;
+        let row = |left: Vec<Span<'a>>, right: Vec<Span<'a>>| {
+            let mut spans = fit_spans(left, left_width);
+            spans.push(Span::styled(SEPARATOR, styles.separator));
+            spans.extend(fit_spans(right, right_width));
+            Line::from_spans(fit_spans(spans, width))
+        };
+
+        let mut lines = Vec::new();
+        for hunk in self.hunks() {
+            let header = vec![Span::styled(hunk.header(), styles.header)];
+            lines.push(Line::from_spans(fit_spans(header, width)));
+            for r in self.rows(&hunk, styles) {
+                match r {
+                    Row::Equal(text) => {
+                        let left = prefixed(" ", styles.equal, text.clone());
+                        let right = prefixed(" ", styles.equal, text);
+                        lines.push(row(left, right));
+                    }
+                    Row::Change { old, new } => {
+                        let mut old = old.into_iter();
+                        let mut new = new.into_iter();
+                        loop {
+                            let left = old.next().map(|s| prefixed("-", styles.delete, s));
+                            let right = new.next().map(|s| prefixed("+", styles.insert, s));
+                            if left.is_none() && right.is_none() {
+                                break;
+                            }
+                            lines.push(row(left.unwrap_or_default(), right.unwrap_or_default()));
+                        }
+                    }
+                }
+            }
+        }
+        Text::from_lines(lines)
+    }
+
+    /// Styled content rows of a hunk, without prefixes.
+    fn rows(&self, hunk: &DiffHunk, styles: &DiffStyles) -> Vec<Row<'a>> {
+        let mut rows = Vec::new();
+        let mut ops = hunk.ops.iter().peekable();
+        while let Some(op) = ops.next() {
+            match op.tag {
+                DiffTag::Equal => rows.extend(
+                    self.old[op.old.clone()]
+                        .iter()
+                        .map(|&line| Row::Equal(vec![Span::styled(line, styles.equal)])),
+                ),
+                DiffTag::Delete | DiffTag::Insert => {
+                    let (deleted, inserted) = match op.tag {
+                        DiffTag::Delete => {
+                            let inserted = match ops.peek() {
+                                Some(next) if next.tag == DiffTag::Insert => {
+                                    ops.next().map(|next| next.new.clone())
+                                }
+                                _ => None,
+                            };
+                            (op.old.clone(), inserted.unwrap_or(0..0))
+                        }
+                        _ => (0..0, op.new.clone()),
+                    };
+                    rows.push(self.change_row(deleted, inserted, styles));
+                }
+            }
+        }
+        rows
+    }
+
+    /// Style a block of deleted and inserted lines, emphasizing inline
+    /// edits between lines paired by position.
+    fn change_row(
+        &self,
+        deleted: Range<usize>,
+        inserted: Range<usize>,
+        styles: &DiffStyles,
+    ) -> Row<'a> {
+        let old_lines = &self.old[deleted];
+        let new_lines = &self.new[inserted];
+        let mut old: Vec<Vec<Span<'a>>> = old_lines
+            .iter()
+            .map(|&line| vec![Span::styled(line, styles.delete)])
+            .collect();
+        let mut new: Vec<Vec<Span<'a>>> = new_lines
+            .iter()
+            .map(|&line| vec![Span::styled(line, styles.insert)])
+            .collect();
+
+        if self.options.inline != DiffGranularity::Line {
+            for (i, (&a, &b)) in old_lines.iter().zip(new_lines).enumerate() {
+                let segments = diff_segments(a, b, self.options.inline, self.options.algorithm);
+                // Lines with nothing in common read better without emphasis.
+                let shared = segments
+                    .iter()
+                    .any(|seg| seg.tag == DiffTag::Equal && !seg.text.trim().is_empty());
+                if !shared {
+                    continue;
+                }
+                old[i] = segments
+                    .iter()
+                    .filter(|seg| seg.tag != DiffTag::Insert)
+                    .map(|seg| match seg.tag {
+                        DiffTag::Delete => Span::styled(seg.text, styles.delete_emphasis),
+                        _ => Span::styled(seg.text, styles.delete),
+                    })
+                    .collect();
+                new[i] = segments
+                    .iter()
+                    .filter(|seg| seg.tag != DiffTag::Delete)
+                    .map(|seg| match seg.tag {
+                        DiffTag::Insert => Span::styled(seg.text, styles.insert_emphasis),
+                        _ => Span::styled(seg.text, styles.insert),
+                    })
+                    .collect();
+            }
+        }
+        Row::Change { old, new }
+    }
+}
+
+/// A rendered row of a hunk: a context line or a block of changed lines.
+enum Row<'a> {
+    Equal(Vec<Span<'a>>),
+    Change {
+        old: Vec<Vec<Span<'a>>>,
+        new: Vec<Vec<Span<'a>>>,
+    },
+}
+
+fn hunk_from_ops(ops: Vec<DiffOp>) -> DiffHunk {
+    let first = &ops[0];
+    let last = &ops[ops.len() - 1];
+    DiffHunk {
+        old: first.old.start..last.old.end,
+        new: first.new.start..last.new.end,
+        ops,
+    }
+}
+
+fn prefixed<'a>(prefix: &'static str, style: Style, mut spans: Vec<Span<'a>>) -> Vec<Span<'a>> {
+    spans.insert(0, Span::styled(prefix, style));
+    spans
+}
+
+const SEPARATOR: &str = " │ ";
+const SEPARATOR_WIDTH: usize = 3;
+
+/// Truncate spans to `width` cells at grapheme boundaries and pad the rest
+/// with spaces.
+fn fit_spans(spans: Vec<Span<'_>>, width: usize) -> Vec<Span<'_>> {
+    let mut out = Vec::with_capacity(spans.len() + 1);
+    let mut remaining = width;
+    for span in spans {
+        if remaining == 0 {
+            break;
+        }
+        let span_width = display_width(&span.content);
+        if span_width <= remaining {
+            remaining -= span_width;
+            out.push(span);
+            continue;
+        }
+        let (content, used) = match span.content {
+            Cow::Borrowed(s) => {
+                let (head, used) = truncate_to_width_with_info(s, remaining);
+                (Cow::Borrowed(head), used)
+            }
+            Cow::Owned(s) => {
+                let (head, used) = truncate_to_width_with_info(&s, remaining);
+                (Cow::Owned(head.to_string()), used)
+            }
+        };
+        remaining -= used;
+        if !content.is_empty() {
+            out.push(Span {
+                content,
+                style: span.style,
+                link: span.link,
+            });
+        }
+        // A wide grapheme that did not fit ends the content.
+        break;
+    }
+    if remaining > 0 {
+        out.push(Span::raw(" ".repeat(remaining)));
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Styles
+// ---------------------------------------------------------------------------
+
+/// Styles used when rendering a [`TextDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffStyles {
+    /// Unchanged context lines.
+    pub equal: Style,
+    /// Deleted lines.
+    pub delete: Style,
+    /// Inserted lines.
+    pub insert: Style,
+    /// Changed text within a deleted line.
+    pub delete_emphasis: Style,
+    /// Changed text within an inserted line.
+    pub insert_emphasis: Style,
+    /// Hunk headers.
+    pub header: Style,
+    /// Side-by-side column separator.
+    pub separator: Style,
+}
+
+impl DiffStyles {
+    /// Derive styles from a resolved theme's semantic colors.
+    #[must_use]
+    pub fn from_theme(theme: &ResolvedTheme) -> Self {
+        let packed = |color: ftui_style::Color| {
+            let rgb = color.to_rgb();
+            PackedRgba::rgb(rgb.r, rgb.g, rgb.b)
+        };
+        let error = packed(theme.error);
+        let success = packed(theme.success);
+        Self {
+            equal: Style::new().fg(packed(theme.text_muted)),
+            delete: Style::new().fg(error),
+            insert: Style::new().fg(success),
+            delete_emphasis: Style::new().fg(packed(theme.background)).bg(error).bold(),
+            insert_emphasis: Style::new().fg(packed(theme.background)).bg(success).bold(),
+            header: Style::new().fg(packed(theme.info)),
+            separator: Style::new().fg(packed(theme.border)),
+        }
+    }
+}
+
+impl Default for DiffStyles {
+    fn default() -> Self {
+        let red = PackedRgba::rgb(255, 85, 85);
+        let green = PackedRgba::rgb(85, 255, 85);
+        Self {
+            equal: Style::new(),
+            delete: Style::new().fg(red),
+            insert: Style::new().fg(green),
+            delete_emphasis: Style::new().fg(red).bg(PackedRgba::rgb(90, 20, 20)).bold(),
+            insert_emphasis: Style::new()
+                .fg(green)
+                .bg(PackedRgba::rgb(20, 80, 20))
+                .bold(),
+            header: Style::new().fg(PackedRgba::rgb(85, 170, 255)),
+            separator: Style::new().fg(PackedRgba::rgb(128, 128, 128)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rebuild `new` from `old` by applying the ops.
+    fn apply<T: Clone>(old: &[T], new: &[T], ops: &[DiffOp]) -> Vec<T> {
+        let mut out = Vec::new();
+        for op in ops {
+            match op.tag {
+                DiffTag::Equal => out.extend_from_slice(&old[op.old.clone()]),
+                DiffTag::Insert => out.extend_from_slice(&new[op.new.clone()]),
+                DiffTag::Delete => {}
+            }
+        }
+        out
+    }
+
+    fn edit_distance(ops: &[DiffOp]) -> usize {
+        ops.iter()
+            .filter(|op| op.tag != DiffTag::Equal)
+            .map(DiffOp::len)
+            .sum()
+    }
+
+    #[test]
+    fn myers_finds_shortest_edit_script() {
+        let old: Vec<char> = "ABCABBA".chars().collect();
+        let new: Vec<char> = "CBABAC".chars().collect();
+        let ops = diff_slices(&old, &new, DiffAlgorithm::Myers);
+        assert_eq!(edit_distance(&ops), 5);
+        assert_eq!(apply(&old, &new, &ops), new);
+    }
+
+    #[test]
+    fn ops_reconstruct_new_for_both_algorithms() {
+        // Small alphabet LCG sequences exercise repeated tokens.
+        let mut seed = 0x2545_f491_u32;
+        let mut next = move |n: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) % n
+        };
+        for _ in 0..200 {
+            let old: Vec<u32> = (0..next(40)).map(|_| next(5)).collect();
+            let new: Vec<u32> = (0..next(40)).map(|_| next(5)).collect();
+            let myers = diff_slices(&old, &new, DiffAlgorithm::Myers);
+            let patience = diff_slices(&old, &new, DiffAlgorithm::Patience);
+            assert_eq!(apply(&old, &new, &myers), new);
+            assert_eq!(apply(&old, &new, &patience), new);
+            assert!(edit_distance(&myers) <= edit_distance(&patience));
+        }
+    }
+
+    #[test]
+    fn patience_anchors_on_unique_lines() {
+        let old = ["fn a() {", "}", "", "fn b() {", "}"];
+        let new = ["fn a() {", "}", "", "fn c() {", "}", "", "fn b() {", "}"];
+        let ops = diff_slices(&old, &new, DiffAlgorithm::Patience);
+        assert_eq!(apply(&old, &new, &ops), new);
+        let inserted: Vec<_> = ops
+            .iter()
+            .filter(|op| op.tag == DiffTag::Insert)
+            .flat_map(|op| new[op.new.clone()].iter().copied())
+            .collect();
+        assert_eq!(inserted, ["fn c() {", "}", ""]);
+    }
+
+    #[test]
+    fn grapheme_diff_keeps_clusters_intact() {
+        let segments = diff_segments(
+            "a👍🏽b",
+            "a👍🏿b",
+            DiffGranularity::Grapheme,
+            DiffAlgorithm::Myers,
+        );
+        let changed: Vec<_> = segments
+            .iter()
+            .filter(|seg| seg.tag != DiffTag::Equal)
+            .map(|seg| (seg.tag, seg.text))
+            .collect();
+        assert_eq!(changed, [(DiffTag::Delete, "👍🏽"), (DiffTag::Insert, "👍🏿")]);
+    }
+
+    #[test]
+    fn word_segments_cover_both_sides() {
+        let old = "the quick brown fox";
+        let new = "the slow brown dog";
+        let segments = diff_segments(old, new, DiffGranularity::Word, DiffAlgorithm::Myers);
+        let side = |skip: DiffTag| -> String {
+            segments
+                .iter()
+                .filter(|seg| seg.tag != skip)
+                .map(|seg| seg.text)
+                .collect()
+        };
+        assert_eq!(side(DiffTag::Insert), old);
+        assert_eq!(side(DiffTag::Delete), new);
+    }
+
+    #[test]
+    fn hunk_header_counts_context() {
+        let old: String = (1..=10).map(|i| format!("line {i}\n")).collect();
+        let new = old.replace("line 5\n", "line five\n");
+        let diff = TextDiff::new(&old, &new);
+        let hunks = diff.hunks();
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].header(), "@@ -2,7 +2,7 @@");
+        assert_eq!(
+            diff.stats(),
+            DiffStats {
+                inserted: 1,
+                deleted: 1
+            }
+        );
+    }
+
+    #[test]
+    fn distant_changes_split_into_hunks() {
+        let old: String = (1..=20).map(|i| format!("{i}\n")).collect();
+        let new: String = (1..=20)
+            .map(|i| match i {
+                2 => "two\n".to_owned(),
+                18 => "eighteen\n".to_owned(),
+                _ => format!("{i}\n"),
+            })
+            .collect();
+        let diff = TextDiff::with_options(&old, &new, DiffOptions::new().context(1));
+        let headers: Vec<_> = diff.hunks().iter().map(DiffHunk::header).collect();
+        assert_eq!(headers, ["@@ -1,3 +1,3 @@", "@@ -17,3 +17,3 @@"]);
+        assert!(TextDiff::new("same\n", "same").hunks().is_empty());
+    }
+
+    #[test]
+    fn unified_emphasizes_changed_words() {
+        let styles = DiffStyles::default();
+        let diff = TextDiff::new("let x = 1;\n", "let x = 2;\n");
+        let text = diff.unified(&styles);
+        assert_eq!(
+            text.to_plain_text(),
+            "@@ -1,1 +1,1 @@\n-let x = 1;\n+let x = 2;"
+        );
+        let emphasized: Vec<_> = text
+            .lines()
+            .iter()
+            .flat_map(|line| line.spans())
+            .filter(|span| {
+                span.style == Some(styles.delete_emphasis)
+                    || span.style == Some(styles.insert_emphasis)
+            })
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(emphasized, ["1", "2"]);
+    }
+
+    #[test]
+    fn side_by_side_lines_fill_width() {
+        let old = "日本語のテキスト\nkeep\nold line\n";
+        let new = "日本語のテクスト\nkeep\nnew line\nextra\n";
+        let diff = TextDiff::new(old, new);
+        for width in [0, 1, 4, 9, 20, 41] {
+            let text = diff.side_by_side(width, &DiffStyles::default());
+            assert!(text.height() > 0);
+            for line in text.lines() {
+                assert_eq!(
+                    line.width(),
+                    width,
+                    "width {width}: {:?}",
+                    line.to_plain_text()
+                );
+            }
+        }
+        let text = diff.side_by_side(41, &DiffStyles::default());
+        assert!(
+            text.lines()[4]
+                .to_plain_text()
+                .starts_with("                    │ +extra")
+        );
+    }
+}
@@ -21,8 +21,11 @@
 //! ```
 
 use std::borrow::Cow;
+use std::sync::Arc;
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::hyphenation::{HyphenationDict, HyphenationRegistry};
+
 /// Text wrapping mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum WrapMode {
@@ -54,6 +57,11 @@ pub struct WrapOptions {
     pub preserve_indent: bool,
     /// Trim trailing whitespace from wrapped lines.
     pub trim_trailing: bool,
+    /// Dictionary used to hyphenate words that would otherwise overflow.
+    ///
+    /// Applies to [`WrapMode::Word`], [`WrapMode::WordChar`] and
+    /// [`WrapMode::Optimal`].
+    pub hyphenation: Option<Arc<HyphenationDict>>,
 }
 
 impl WrapOptions {
@@ -65,6 +73,7 @@ impl WrapOptions {
             mode: WrapMode::Word,
             preserve_indent: false,
             trim_trailing: true,
+            hyphenation: None,
         }
     }
 
@@ -88,6 +97,21 @@ impl WrapOptions {
         self.trim_trailing = trim;
         self
     }
+
+    /// Set the hyphenation dictionary.
+    #[must_use]
+    pub fn hyphenation(mut self, dict: Option<Arc<HyphenationDict>>) -> Self {
+        self.hyphenation = dict;
+        self
+    }
+
+    /// Use the registry's dictionary for `locale`, or none if it has no
+    /// match. Pass the active locale, e.g. from the runtime's
+    /// `LocaleContext::current_locale()`.
+    #[must_use]
+    pub fn locale(self, registry: &HyphenationRegistry, locale: &str) -> Self {
+        self.hyphenation(registry.for_locale(locale))
+    }
 }
 
 impl Default for WrapOptions {
@@ -121,7 +145,9 @@ pub fn wrap_with_options(text: &str, options: &WrapOptions) -> Vec<String> {
         WrapMode::Char => wrap_chars(text, options),
         WrapMode::Word => wrap_words(text, options, false),
         WrapMode::WordChar => wrap_words(text, options, true),
-        WrapMode::Optimal => wrap_text_optimal(text, options.width),
+        WrapMode::Optimal => {
+            wrap_paragraphs_optimal(text, options.width, options.hyphenation.as_deref())
+        }
     }
 }
 
@@ -209,6 +235,12 @@ fn wrap_paragraph(
             continue;
         }
 
+        let word = match options.hyphenation.as_deref() {
+            Some(dict) if !is_whitespace_only => {
+                hyphenate_overflow(word, dict, options, lines, current_line, current_width)
+            }
+            _ => word,
+        };
         let word_width = display_width(word);
 
         // If word fits on current line
@@ -251,6 +283,48 @@ fn wrap_paragraph(
     }
 }
 
+/// Emit hyphenated pieces of `word` while it overflows the current line.
+///
+/// Returns the remainder still to be placed.
+fn hyphenate_overflow<'a>(
+    word: &'a str,
+    dict: &HyphenationDict,
+    options: &WrapOptions,
+    lines: &mut Vec<String>,
+    current_line: &mut String,
+    current_width: &mut usize,
+) -> &'a str {
+    if *current_width + display_width(word) <= options.width {
+        return word;
+    }
+    let breaks = dict.break_byte_offsets(word);
+    let mut start = 0;
+    while *current_width + display_width(&word[start..]) > options.width {
+        let avail = options.width.saturating_sub(*current_width);
+        let Some(&cut) = breaks
+            .iter()
+            .rev()
+            .find(|&&cut| cut > start && display_width(&word[start..cut]) < avail)
+        else {
+            if *current_width > 0 && display_width(&word[start..]) > options.width {
+                // Too long even for a fresh line: start one and break there.
+                lines.push(finalize_line(current_line, options));
+                current_line.clear();
+                *current_width = 0;
+                continue;
+            }
+            break;
+        };
+        current_line.push_str(&word[start..cut]);
+        current_line.push('-');
+        lines.push(finalize_line(current_line, options));
+        current_line.clear();
+        *current_width = 0;
+        start = cut;
+    }
+    &word[start..]
+}
+
 /// Break a long word that exceeds the width limit.
 fn wrap_long_word(
     word: &str,
@@ -584,7 +658,7 @@ pub fn word_segments(text: &str) -> impl Iterator<Item = &str> {
 //
 // ## Penalties
 //
-// - PENALTY_HYPHEN: cost for breaking at a hyphen (not yet used, reserved)
+// - PENALTY_HYPHEN: cost for breaking at a hyphenation point
 // - PENALTY_FLAGGED: cost for consecutive flagged breaks
 // - PENALTY_FORCE_BREAK: large penalty for forcing a break mid-word
 //
@@ -610,6 +684,9 @@ const BADNESS_INF: u64 = u64::MAX / 2;
 /// Penalty for forcing a mid-word character break.
 const PENALTY_FORCE_BREAK: u64 = 5000;
 
+/// Penalty for ending a line at a hyphenation point.
+const PENALTY_HYPHEN: u64 = 150;
+
 /// Maximum lookahead (words per line) for DP pruning.
 /// Limits worst-case to O(n × MAX_LOOKAHEAD) instead of O(n²).
 /// Any line with more than this many words will use the greedy breakpoint.
@@ -661,6 +738,8 @@ struct KpWord<'a> {
     content_width: usize,
     /// Cell width of the trailing space (0 if none).
     space_width: usize,
+    /// A hyphenation point follows: ending a line here appends `-`.
+    hyphen: bool,
 }
 
 /// Split text into KpWord tokens for Knuth-Plass processing.
@@ -688,6 +767,7 @@ fn kp_tokenize(text: &str) -> Vec<KpWord<'_>> {
                     space: Cow::Borrowed(seg),
                     content_width: current_content_width,
                     space_width: width,
+                    hyphen: false,
                 });
                 content_start = byte_offset + seg.len();
                 content_end = content_start;
@@ -708,6 +788,7 @@ fn kp_tokenize(text: &str) -> Vec<KpWord<'_>> {
                     space: Cow::Borrowed(seg),
                     content_width: 0,
                     space_width: width,
+                    hyphen: false,
                 });
                 content_start = byte_offset + seg.len();
                 content_end = content_start;
@@ -730,12 +811,54 @@ fn kp_tokenize(text: &str) -> Vec<KpWord<'_>> {
             space: Cow::Borrowed(""),
             content_width: current_content_width,
             space_width: 0,
+            hyphen: false,
         });
     }
 
     words
 }
 
+/// Split each word at its hyphenation points into fragments joined without
+/// space.
+fn kp_hyphenate<'a>(words: Vec<KpWord<'a>>, dict: &HyphenationDict) -> Vec<KpWord<'a>> {
+    let mut out = Vec::with_capacity(words.len());
+    for word in words {
+        let breaks = dict.break_byte_offsets(&word.content);
+        if breaks.is_empty() {
+            out.push(word);
+            continue;
+        }
+        let piece = |range: std::ops::Range<usize>| match &word.content {
+            Cow::Borrowed(s) => {
+                let s: &'a str = s;
+                Cow::Borrowed(&s[range])
+            }
+            Cow::Owned(s) => Cow::Owned(s[range].to_string()),
+        };
+        let mut start = 0;
+        for &cut in &breaks {
+            let content = piece(start..cut);
+            out.push(KpWord {
+                content_width: display_width(&content),
+                content,
+                space: Cow::Borrowed(""),
+                space_width: 0,
+                hyphen: true,
+            });
+            start = cut;
+        }
+        let content = piece(start..word.content.len());
+        out.push(KpWord {
+            content_width: display_width(&content),
+            content,
+            space: word.space,
+            space_width: word.space_width,
+            hyphen: false,
+        });
+    }
+    out
+}
+
 /// Result of optimal line breaking.
 #[derive(Debug, Clone)]
 pub struct KpBreakResult {
@@ -762,6 +885,19 @@ pub struct KpBreakResult {
 /// # Returns
 /// `KpBreakResult` with optimal lines, total cost, and per-line badness.
 pub fn wrap_optimal(text: &str, width: usize) -> KpBreakResult {
+    wrap_optimal_impl(text, width, None)
+}
+
+/// Compute optimal line breaks, also allowing breaks at hyphenation points.
+///
+/// Each hyphenation break costs `PENALTY_HYPHEN` and adds a `-` (one cell)
+/// to the end of its line. Long compound words can then be split to avoid
+/// very loose lines in narrow columns.
+pub fn wrap_optimal_hyphenated(text: &str, width: usize, dict: &HyphenationDict) -> KpBreakResult {
+    wrap_optimal_impl(text, width, Some(dict))
+}
+
+fn wrap_optimal_impl(text: &str, width: usize, dict: Option<&HyphenationDict>) -> KpBreakResult {
     if width == 0 || text.is_empty() {
         return KpBreakResult {
             lines: vec![text.to_string()],
@@ -770,7 +906,10 @@ pub fn wrap_optimal(text: &str, width: usize) -> KpBreakResult {
         };
     }
 
-    let words = kp_tokenize(text);
+    let mut words = kp_tokenize(text);
+    if let Some(dict) = dict {
+        words = kp_hyphenate(words, dict);
+    }
     if words.is_empty() {
         return KpBreakResult {
             lines: vec![text.to_string()],
@@ -800,20 +939,27 @@ pub fn wrap_optimal(text: &str, width: usize) -> KpBreakResult {
                 line_width += words[i].space_width;
             }
 
+            // A line ending at a hyphenation point also carries the hyphen.
+            let hyphen = words[j - 1].hyphen;
+            let total_width = line_width + usize::from(hyphen);
+
             // Check if line overflows
-            if line_width > width && i < j - 1 {
+            if total_width > width && i < j - 1 {
                 // Can't fit — and we've already tried adding more words
                 break;
             }
 
-            let slack = width as i64 - line_width as i64;
+            let slack = width as i64 - total_width as i64;
             let is_last = j == n;
-            let badness = if line_width > width {
+            let mut badness = if total_width > width {
                 // Single word too wide — must force-break
                 PENALTY_FORCE_BREAK
             } else {
                 knuth_plass_badness(slack, width, is_last)
             };
+            if hyphen {
+                badness = badness.saturating_add(PENALTY_HYPHEN);
+            }
 
             let candidate = cost[i].saturating_add(badness);
             // Tie-breaking: prefer later break (fewer lines)
@@ -854,6 +1000,9 @@ pub fn wrap_optimal(text: &str, width: usize) -> KpBreakResult {
                 line.push_str(&word.space);
             }
         }
+        if words[end - 1].hyphen {
+            line.push('-');
+        }
 
         // Trim trailing whitespace from each line (standard behavior)
         let trimmed = line.trim_end_matches(is_breaking_whitespace).to_string();
@@ -884,6 +1033,14 @@ pub fn wrap_optimal(text: &str, width: usize) -> KpBreakResult {
 /// Handles multiple paragraphs separated by `\n`.
 #[must_use]
 pub fn wrap_text_optimal(text: &str, width: usize) -> Vec<String> {
+    wrap_paragraphs_optimal(text, width, None)
+}
+
+fn wrap_paragraphs_optimal(
+    text: &str,
+    width: usize,
+    dict: Option<&HyphenationDict>,
+) -> Vec<String> {
     let mut result = Vec::new();
     for raw_paragraph in text.split('\n') {
         let paragraph = raw_paragraph.strip_suffix('\r').unwrap_or(raw_paragraph);
@@ -891,7 +1048,7 @@ pub fn wrap_text_optimal(text: &str, width: usize) -> Vec<String> {
             result.push(String::new());
             continue;
         }
-        let kp = wrap_optimal(paragraph, width);
+        let kp = wrap_optimal_impl(paragraph, width, dict);
         result.extend(kp.lines);
     }
     result
@@ -2043,6 +2200,56 @@ mod tests {
         );
     }
 
+    fn compound_dict() -> Arc<HyphenationDict> {
+        Arc::new(HyphenationDict::new(
+            "de",
+            &["1dampf", "1schiff", "1fahrt"],
+            &[],
+        ))
+    }
+
+    #[test]
+    fn word_wrap_hyphenates_long_compounds() {
+        let text = "Die Donaudampfschifffahrt fährt";
+        assert_eq!(
+            wrap_text(text, 12, WrapMode::Word),
+            ["Die", "Donaudampfschifffahrt", "fährt"]
+        );
+
+        let mut registry = HyphenationRegistry::new();
+        registry.insert_as("de", compound_dict());
+        let options = WrapOptions::new(12).locale(&registry, "de_AT.UTF-8");
+        assert_eq!(
+            wrap_with_options(text, &options),
+            ["Die Donau-", "dampfschiff-", "fahrt fährt"]
+        );
+    }
+
+    #[test]
+    fn optimal_wrap_uses_hyphenation_points() {
+        let text = "Die Donaudampfschifffahrt fährt";
+        let dict = compound_dict();
+        let plain = wrap_optimal(text, 12);
+        let hyphenated = wrap_optimal_hyphenated(text, 12, &dict);
+
+        assert!(hyphenated.total_cost < plain.total_cost);
+        assert!(hyphenated.lines.iter().all(|line| line.width() <= 12));
+        assert!(hyphenated.lines.iter().any(|line| line.ends_with('-')));
+        assert_eq!(
+            hyphenated
+                .lines
+                .join("\n")
+                .replace("-\n", "")
+                .replace('\n', " "),
+            text
+        );
+
+        let options = WrapOptions::new(12)
+            .mode(WrapMode::Optimal)
+            .hyphenation(Some(dict));
+        assert_eq!(wrap_with_options(text, &options), hyphenated.lines);
+    }
+
     #[test]
     fn kp_simple_wrap() {
         let result = wrap_optimal("Hello world foo bar", 10);
@@ -10,10 +10,13 @@ use ftui_core::geometry::Rect;
 use ftui_render::cell::{Cell, CellContent};
 use ftui_render::frame::Frame;
 use ftui_style::Style;
-use ftui_text::grapheme_width;
+use ftui_text::editor::Editor;
+use ftui_text::{CursorNavigator, grapheme_width};
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::readline::{InputHistory, KillRing, ReadlineAction, ReadlineKeymap, ReverseSearch};
 use crate::undo_support::{TextEditOperation, TextInputUndoExt, UndoSupport, UndoWidgetId};
+use crate::vim::{VimKeymap, VimMode, VimOutcome};
 use crate::{Widget, clear_text_area};
 
 /// A single-line text input widget.
@@ -47,6 +50,30 @@ pub struct TextInput {
     selection_style: Style,
     /// Whether the input is focused (controls cursor output).
     focused: bool,
+    /// Vim-style modal editing state, when enabled.
+    vim: Option<Box<VimInput>>,
+    /// Readline bindings, kill ring and history, when enabled.
+    readline: Option<Box<ReadlineInput>>,
+}
+
+/// Vim keymap plus an editor mirroring the input value.
+#[derive(Debug, Clone)]
+struct VimInput {
+    keymap: VimKeymap,
+    editor: Editor,
+}
+
+/// Readline keymap plus the state its commands carry between keys.
+#[derive(Debug, Clone, Default)]
+struct ReadlineInput {
+    keymap: ReadlineKeymap,
+    kill_ring: KillRing,
+    history: InputHistory,
+    search: Option<ReverseSearch>,
+    /// Previous readline command, for merging kills and yank-pop.
+    last_action: Option<ReadlineAction>,
+    /// Grapheme range inserted by the last yank or yank-pop.
+    yanked: Option<(usize, usize)>,
 }
 
 impl TextInput {
@@ -122,6 +149,41 @@ impl TextInput {
         self
     }
 
+    /// Enable Vim-style modal editing, starting in Normal mode (builder).
+    ///
+    /// Line-oriented commands (`j`, `k`, `o`, `O`) do nothing in a
+    /// single-line input. See [`crate::vim`].
+    #[must_use]
+    pub fn with_vim(mut self, enabled: bool) -> Self {
+        self.set_vim_enabled(enabled);
+        self
+    }
+
+    /// Enable readline/Emacs bindings with the default keymap (builder).
+    ///
+    /// Readline bindings take precedence over the built-in ones, so Ctrl+A
+    /// moves to the line start instead of selecting all. See
+    /// [`crate::readline`].
+    #[must_use]
+    pub fn with_readline(mut self, enabled: bool) -> Self {
+        self.set_readline_enabled(enabled);
+        self
+    }
+
+    /// Enable readline editing with a custom keymap (builder).
+    #[must_use]
+    pub fn with_readline_keymap(mut self, keymap: ReadlineKeymap) -> Self {
+        self.readline.get_or_insert_default().keymap = keymap;
+        self
+    }
+
+    /// Enable readline editing with the given history (builder).
+    #[must_use]
+    pub fn with_history(mut self, history: InputHistory) -> Self {
+        self.readline.get_or_insert_default().history = history;
+        self
+    }
+
     // --- Value access ---
 
     /// Get the current value.
@@ -246,6 +308,368 @@ impl TextInput {
         self.ime_composition.as_deref()
     }
 
+    // --- Modal editing ---
+
+    /// Turn Vim-style modal editing on or off.
+    pub fn set_vim_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.vim = None;
+        } else if self.vim.is_none() {
+            self.vim = Some(Box::new(VimInput {
+                keymap: VimKeymap::new().with_single_line(true),
+                editor: Editor::with_text(&self.value),
+            }));
+        }
+    }
+
+    /// Current Vim mode, for display in a status line. `None` when modal
+    /// editing is off.
+    #[must_use]
+    pub fn vim_mode(&self) -> Option<VimMode> {
+        self.vim.as_ref().map(|vim| vim.keymap.mode())
+    }
+
+    /// The Vim keymap, when modal editing is on.
+    #[must_use]
+    pub fn vim(&self) -> Option<&VimKeymap> {
+        self.vim.as_ref().map(|vim| &vim.keymap)
+    }
+
+    /// Mutable access to the Vim keymap, e.g. to take `:w` commands.
+    pub fn vim_mut(&mut self) -> Option<&mut VimKeymap> {
+        self.vim.as_mut().map(|vim| &mut vim.keymap)
+    }
+
+    /// Run a key through the Vim keymap, if enabled.
+    ///
+    /// Returns `false` when the key should get the regular bindings.
+    fn handle_vim_key(&mut self, key: &KeyEvent) -> bool {
+        let Some(vim) = self.vim.as_deref_mut() else {
+            return false;
+        };
+        // The value may have changed outside the keymap (paste, IME,
+        // setters); sync it as an edit so `u` can still undo past it.
+        vim.editor.replace_text(&self.value);
+        let pos = CursorNavigator::new(vim.editor.rope()).from_line_grapheme(0, self.cursor);
+        vim.editor.set_cursor(pos);
+
+        let checkpoint = vim.editor.undo_checkpoint();
+        if vim.keymap.handle_key(key, &mut vim.editor) == VimOutcome::Passthrough {
+            return false;
+        }
+
+        let raw = vim.editor.text();
+        let text = Self::sanitize_input_text(&raw);
+        let fits = self
+            .max_length
+            .is_none_or(|max| text.graphemes(true).count() <= max);
+        if !fits {
+            vim.editor.revert_to(checkpoint);
+            vim.editor.replace_text(&self.value);
+        } else if text != raw {
+            vim.editor.replace_text(&text);
+            vim.editor.group_undo_since(checkpoint);
+        }
+        if fits {
+            self.value = text;
+        }
+
+        let cursor = vim.editor.cursor().grapheme;
+        let selection = vim.editor.selection().filter(|sel| !sel.is_empty());
+        self.selection_anchor = selection.map(|sel| sel.anchor.grapheme);
+        self.cursor = selection.map_or(cursor, |sel| sel.head.grapheme);
+        self.cursor = self.cursor.min(self.grapheme_count());
+        true
+    }
+
+    // --- Readline editing ---
+
+    /// Turn readline bindings on or off. Turning them off drops the kill
+    /// ring and history.
+    pub fn set_readline_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.readline = None;
+        } else if self.readline.is_none() {
+            self.readline = Some(Box::default());
+        }
+    }
+
+    /// Whether readline bindings are enabled.
+    #[must_use]
+    pub fn readline_enabled(&self) -> bool {
+        self.readline.is_some()
+    }
+
+    /// Submitted-line history, when readline editing is on.
+    #[must_use]
+    pub fn history(&self) -> Option<&InputHistory> {
+        self.readline.as_ref().map(|rl| &rl.history)
+    }
+
+    /// Mutable access to the history, e.g. to record entries.
+    pub fn history_mut(&mut self) -> Option<&mut InputHistory> {
+        self.readline.as_mut().map(|rl| &mut rl.history)
+    }
+
+    /// The kill ring, when readline editing is on.
+    #[must_use]
+    pub fn kill_ring(&self) -> Option<&KillRing> {
+        self.readline.as_ref().map(|rl| &rl.kill_ring)
+    }
+
+    /// The active Ctrl-R search, for rendering its prompt.
+    #[must_use]
+    pub fn reverse_search(&self) -> Option<&ReverseSearch> {
+        self.readline.as_ref().and_then(|rl| rl.search.as_ref())
+    }
+
+    /// Take the current line, recording it in the history when readline
+    /// editing is on, and clear the input.
+    pub fn submit(&mut self) -> String {
+        let line = std::mem::take(&mut self.value);
+        if let Some(rl) = self.readline.as_deref_mut() {
+            rl.history.push(line.as_str());
+            rl.search = None;
+            rl.last_action = None;
+        }
+        self.clear();
+        line
+    }
+
+    /// Replace the whole line, leaving the cursor at the end.
+    ///
+    /// Lines longer than `max_length` are truncated.
+    fn replace_line(&mut self, line: &str) {
+        let mut value = Self::sanitize_input_text(line);
+        if let Some(max) = self.max_length
+            && let Some((end, _)) = value.grapheme_indices(true).nth(max)
+        {
+            value.truncate(end);
+        }
+        self.value = value;
+        self.cursor = self.grapheme_count();
+        self.selection_anchor = None;
+    }
+
+    /// Forget the previous readline command, so the next kill starts a new
+    /// kill-ring entry and yank-pop does nothing.
+    fn break_readline_chain(&mut self) {
+        if let Some(rl) = self.readline.as_deref_mut() {
+            rl.last_action = None;
+        }
+    }
+
+    /// Run a key through the readline keymap, if enabled.
+    ///
+    /// Returns `false` when the key should get the regular bindings.
+    fn handle_readline_key(&mut self, key: &KeyEvent) -> bool {
+        let Some(rl) = self.readline.as_deref_mut() else {
+            return false;
+        };
+        let action = rl.keymap.action_for(key);
+        if rl.search.is_some() && self.handle_search_key(key, action) {
+            return true;
+        }
+
+        let Some(rl) = self.readline.as_deref_mut() else {
+            return false;
+        };
+        let Some(action) = action else {
+            rl.last_action = None;
+            rl.history.reset_navigation();
+            return false;
+        };
+        if !matches!(
+            action,
+            ReadlineAction::HistoryPrevious | ReadlineAction::HistoryNext
+        ) {
+            rl.history.reset_navigation();
+        }
+        let last_action = rl.last_action.replace(action);
+        self.selection_anchor = None;
+
+        match action {
+            ReadlineAction::LineStart => {
+                self.cursor = 0;
+                self.scroll_cells.set(0);
+            }
+            ReadlineAction::LineEnd => self.cursor = self.grapheme_count(),
+            ReadlineAction::CharBackward => self.move_cursor_left(),
+            ReadlineAction::CharForward => self.move_cursor_right(),
+            ReadlineAction::WordBackward => self.move_cursor_word_left(false),
+            ReadlineAction::WordForward => self.move_cursor_word_right(false),
+            ReadlineAction::DeleteChar => self.delete_char_forward(),
+            ReadlineAction::KillToEnd
+            | ReadlineAction::KillToStart
+            | ReadlineAction::KillWordBackward
+            | ReadlineAction::KillWordForward => {
+                let merge = last_action.is_some_and(ReadlineAction::is_kill);
+                self.kill(action, merge);
+            }
+            ReadlineAction::Yank => self.yank(false),
+            ReadlineAction::YankPop => {
+                if matches!(
+                    last_action,
+                    Some(ReadlineAction::Yank | ReadlineAction::YankPop)
+                ) {
+                    self.yank(true);
+                } else if let Some(rl) = self.readline.as_deref_mut() {
+                    rl.last_action = None;
+                }
+            }
+            ReadlineAction::TransposeChars => self.transpose_chars(),
+            ReadlineAction::HistoryPrevious | ReadlineAction::HistoryNext => {
+                let current = self.value.clone();
+                let line = self.readline.as_deref_mut().and_then(|rl| {
+                    let line = if action == ReadlineAction::HistoryPrevious {
+                        rl.history.older(&current)
+                    } else {
+                        rl.history.newer()
+                    };
+                    line.map(str::to_owned)
+                });
+                if let Some(line) = line {
+                    self.replace_line(&line);
+                }
+            }
+            ReadlineAction::ReverseSearch => {
+                let search = ReverseSearch::new(self.value.clone());
+                if let Some(rl) = self.readline.as_deref_mut() {
+                    rl.search = Some(search);
+                }
+            }
+            ReadlineAction::Abort => {}
+        }
+        true
+    }
+
+    /// Handle a key while a reverse search is active.
+    ///
+    /// Returns `false` when the key ends the search and should then be
+    /// processed normally, as readline does.
+    fn handle_search_key(&mut self, key: &KeyEvent, action: Option<ReadlineAction>) -> bool {
+        let Some(rl) = self.readline.as_deref_mut() else {
+            return false;
+        };
+        let Some(search) = rl.search.as_mut() else {
+            return false;
+        };
+        let plain = !key
+            .modifiers
+            .intersects(Modifiers::CTRL | Modifiers::ALT | Modifiers::SUPER);
+
+        match (action, key.code) {
+            (Some(ReadlineAction::ReverseSearch), _) => search.next_older(&rl.history),
+            (Some(ReadlineAction::Abort), _) | (_, KeyCode::Escape) => {
+                let original = search.original().to_owned();
+                rl.search = None;
+                rl.last_action = None;
+                self.replace_line(&original);
+                return true;
+            }
+            (_, KeyCode::Char(c)) if plain => {
+                search.push_str(c.encode_utf8(&mut [0; 4]), &rl.history);
+            }
+            (_, KeyCode::Backspace) if plain => search.pop(&rl.history),
+            _ => {
+                // Any other key accepts the shown line and runs as usual.
+                rl.search = None;
+                rl.last_action = None;
+                return false;
+            }
+        }
+
+        let line = match search.matched() {
+            Some(index) => rl.history.entries()[index].clone(),
+            None => search.original().to_owned(),
+        };
+        self.replace_line(&line);
+        true
+    }
+
+    /// Kill the span `action` covers into the kill ring.
+    fn kill(&mut self, action: ReadlineAction, merge: bool) {
+        let (start, end) = match action {
+            ReadlineAction::KillToEnd => (self.cursor, self.grapheme_count()),
+            ReadlineAction::KillToStart => (0, self.cursor),
+            ReadlineAction::KillWordBackward => {
+                let end = self.cursor;
+                self.move_cursor_word_left(false);
+                (self.cursor, end)
+            }
+            _ => {
+                let start = self.cursor;
+                self.move_cursor_word_right(false);
+                (start, std::mem::replace(&mut self.cursor, start))
+            }
+        };
+        if start >= end {
+            return;
+        }
+
+        let byte_start = self.grapheme_byte_offset(start);
+        let byte_end = self.grapheme_byte_offset(end);
+        let killed: String = self.value.drain(byte_start..byte_end).collect();
+        self.cursor = start;
+
+        let Some(rl) = self.readline.as_deref_mut() else {
+            return;
+        };
+        let backward = matches!(
+            action,
+            ReadlineAction::KillToStart | ReadlineAction::KillWordBackward
+        );
+        if merge {
+            rl.kill_ring.extend(&killed, backward);
+        } else {
+            rl.kill_ring.push(killed);
+        }
+    }
+
+    /// Insert the newest kill, or with `pop`, replace the previous yank
+    /// with the next older one.
+    fn yank(&mut self, pop: bool) {
+        let Some(rl) = self.readline.as_deref_mut() else {
+            return;
+        };
+        let previous = rl.yanked.take().filter(|_| pop);
+        let text = if pop {
+            rl.kill_ring.yank_pop()
+        } else {
+            rl.kill_ring.yank()
+        };
+        let Some(text) = text.map(str::to_owned) else {
+            return;
+        };
+
+        if let Some((start, end)) = previous {
+            let byte_start = self.grapheme_byte_offset(start);
+            let byte_end = self.grapheme_byte_offset(end);
+            self.value.drain(byte_start..byte_end);
+            self.cursor = start;
+        }
+        let start = self.cursor;
+        self.insert_text(&text);
+        let end = self.cursor;
+        if let Some(rl) = self.readline.as_deref_mut() {
+            rl.yanked = Some((start, end));
+        }
+    }
+
+    /// Swap the graphemes around the cursor and step past them. At the end
+    /// of the line the last two graphemes are swapped.
+    fn transpose_chars(&mut self) {
+        let count = self.grapheme_count();
+        if count < 2 || self.cursor == 0 {
+            return;
+        }
+        let pos = self.cursor.min(count - 1);
+        let mut graphemes: Vec<&str> = self.value.graphemes(true).collect();
+        graphemes.swap(pos - 1, pos);
+        self.value = graphemes.concat();
+        self.cursor = pos + 1;
+    }
+
     // --- Event handling ---
 
     /// Handle a terminal event.
@@ -281,6 +705,7 @@ impl TextInput {
                     }
                 }
 
+                self.break_readline_chain();
                 self.delete_selection();
                 self.insert_text(&paste.text);
                 true
@@ -322,6 +747,10 @@ impl TextInput {
     }
 
     fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if self.handle_vim_key(key) || self.handle_readline_key(key) {
+            return true;
+        }
+
         let ctrl = key.modifiers.contains(Modifiers::CTRL);
         let shift = key.modifiers.contains(Modifiers::SHIFT);
 
@@ -1632,6 +2061,179 @@ mod tests {
         assert_eq!(input.selected_text(), Some("hello"));
     }
 
+    #[test]
+    fn test_vim_modal_editing() {
+        let mut input = TextInput::new().with_value("hello world").with_vim(true);
+        let press = |input: &mut TextInput, c| {
+            input.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char(c))));
+        };
+        assert_eq!(input.vim_mode(), Some(VimMode::Normal));
+        for c in "0wcwthere".chars() {
+            press(&mut input, c);
+        }
+        assert_eq!(input.vim_mode(), Some(VimMode::Insert));
+        input.handle_event(&Event::Key(KeyEvent::new(KeyCode::Escape)));
+        assert_eq!(input.value(), "hello there");
+        assert_eq!(input.cursor(), 10);
+
+        for c in "bviw".chars() {
+            press(&mut input, c);
+        }
+        assert_eq!(input.selected_text(), Some("there"));
+        press(&mut input, 'd');
+        assert_eq!(input.value(), "hello ");
+        press(&mut input, 'u');
+        assert_eq!(input.value(), "hello there");
+    }
+
+    #[test]
+    fn test_vim_respects_max_length() {
+        let mut input = TextInput::new()
+            .with_value("abc")
+            .with_max_length(4)
+            .with_vim(true);
+        let press = |input: &mut TextInput, c| {
+            input.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char(c))));
+        };
+        for c in "yyp".chars() {
+            press(&mut input, c);
+        }
+        assert_eq!(input.value(), "abc");
+
+        // A rejected keystroke leaves the earlier history undoable.
+        for c in "0xAdef".chars() {
+            press(&mut input, c);
+        }
+        input.handle_event(&Event::Key(KeyEvent::new(KeyCode::Escape)));
+        assert_eq!(input.value(), "bcde");
+        press(&mut input, 'u');
+        assert_eq!(input.value(), "bc");
+        press(&mut input, 'u');
+        assert_eq!(input.value(), "abc");
+    }
+
+    #[test]
+    fn test_vim_undo_survives_external_changes() {
+        let mut input = TextInput::new().with_value("one").with_vim(true);
+        let press = |input: &mut TextInput, c| {
+            input.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char(c))));
+        };
+        press(&mut input, '0');
+        press(&mut input, 'x');
+        assert_eq!(input.value(), "ne");
+        input.set_value("two");
+        press(&mut input, 'u');
+        assert_eq!(input.value(), "ne");
+        press(&mut input, 'u');
+        assert_eq!(input.value(), "one");
+    }
+
+    fn press(input: &mut TextInput, code: KeyCode, modifiers: Modifiers) {
+        input.handle_event(&Event::Key(KeyEvent::new(code).with_modifiers(modifiers)));
+    }
+
+    #[test]
+    fn test_readline_kill_and_yank() {
+        let mut input = TextInput::new()
+            .with_value("one two three")
+            .with_readline(true);
+        press(&mut input, KeyCode::Char('w'), Modifiers::CTRL);
+        assert_eq!(input.value(), "one two ");
+
+        press(&mut input, KeyCode::Char('a'), Modifiers::CTRL);
+        assert_eq!(input.cursor(), 0);
+        assert!(input.selected_text().is_none());
+        press(&mut input, KeyCode::Char('d'), Modifiers::ALT);
+        assert_eq!(input.value(), "two ");
+
+        press(&mut input, KeyCode::Char('e'), Modifiers::CTRL);
+        press(&mut input, KeyCode::Char('y'), Modifiers::CTRL);
+        assert_eq!(input.value(), "two one ");
+        press(&mut input, KeyCode::Char('y'), Modifiers::ALT);
+        assert_eq!(input.value(), "two three");
+        assert_eq!(input.cursor(), 9);
+
+        // Consecutive kills merge into one entry.
+        press(&mut input, KeyCode::Char('w'), Modifiers::CTRL);
+        press(&mut input, KeyCode::Char('u'), Modifiers::CTRL);
+        assert_eq!(input.value(), "");
+        assert_eq!(input.kill_ring().map(KillRing::len), Some(3));
+        press(&mut input, KeyCode::Char('y'), Modifiers::CTRL);
+        assert_eq!(input.value(), "two three");
+    }
+
+    #[test]
+    fn test_readline_transpose_and_motion() {
+        let mut input = TextInput::new().with_value("ab").with_readline(true);
+        press(&mut input, KeyCode::Char('b'), Modifiers::CTRL);
+        press(&mut input, KeyCode::Char('t'), Modifiers::CTRL);
+        assert_eq!(input.value(), "ba");
+        assert_eq!(input.cursor(), 2);
+        press(&mut input, KeyCode::Char('t'), Modifiers::CTRL);
+        assert_eq!(input.value(), "ab");
+
+        // Without readline, Ctrl+A keeps selecting all.
+        let mut plain = TextInput::new().with_value("ab");
+        press(&mut plain, KeyCode::Char('a'), Modifiers::CTRL);
+        assert_eq!(plain.selected_text(), Some("ab"));
+    }
+
+    #[test]
+    fn test_readline_history_prefix_and_search() {
+        let mut input = TextInput::new().with_readline(true);
+        for line in ["cargo build", "ls", "cargo test"] {
+            input.set_value(line);
+            assert_eq!(input.submit(), line);
+        }
+        assert_eq!(input.value(), "");
+        assert_eq!(input.history().map(InputHistory::len), Some(3));
+
+        for c in "car".chars() {
+            press(&mut input, KeyCode::Char(c), Modifiers::NONE);
+        }
+        press(&mut input, KeyCode::Up, Modifiers::NONE);
+        assert_eq!(input.value(), "cargo test");
+        press(&mut input, KeyCode::Char('p'), Modifiers::CTRL);
+        assert_eq!(input.value(), "cargo build");
+        press(&mut input, KeyCode::Down, Modifiers::NONE);
+        assert_eq!(input.value(), "cargo test");
+        press(&mut input, KeyCode::Char('n'), Modifiers::CTRL);
+        assert_eq!(input.value(), "car");
+
+        input.clear();
+        press(&mut input, KeyCode::Char('r'), Modifiers::CTRL);
+        press(&mut input, KeyCode::Char('b'), Modifiers::NONE);
+        assert_eq!(input.value(), "cargo build");
+        assert_eq!(input.reverse_search().map(ReverseSearch::query), Some("b"));
+        press(&mut input, KeyCode::Escape, Modifiers::NONE);
+        assert_eq!(input.value(), "");
+        assert!(input.reverse_search().is_none());
+
+        press(&mut input, KeyCode::Char('r'), Modifiers::CTRL);
+        press(&mut input, KeyCode::Char('l'), Modifiers::NONE);
+        press(&mut input, KeyCode::Right, Modifiers::NONE);
+        assert_eq!(input.value(), "ls");
+        assert!(input.reverse_search().is_none());
+    }
+
+    #[test]
+    fn test_readline_history_recall_respects_max_length() {
+        let mut input = TextInput::new().with_readline(true).with_max_length(5);
+        input
+            .history_mut()
+            .expect("readline history")
+            .push("cargo build");
+        press(&mut input, KeyCode::Up, Modifiers::NONE);
+        assert_eq!(input.value(), "cargo");
+        assert_eq!(input.cursor(), 5);
+
+        input.clear();
+        press(&mut input, KeyCode::Char('r'), Modifiers::CTRL);
+        press(&mut input, KeyCode::Char('b'), Modifiers::NONE);
+        press(&mut input, KeyCode::Enter, Modifiers::NONE);
+        assert_eq!(input.value(), "cargo");
+    }
+
     #[test]
     fn test_delete_selection() {
         let mut input = TextInput::new().with_value("hello world");
@@ -193,7 +193,10 @@ pub mod paragraph;
 pub mod popover;
 pub mod pretty;
 pub mod progress;
+/// Readline/Emacs bindings, kill ring and history for single-line inputs.
+pub mod readline;
 pub mod rule;
+pub mod scroll_view;
 pub mod scrollbar;
 pub mod sparkline;
 pub mod spinner;
@@ -213,6 +216,8 @@ pub mod tree;
 pub mod undo_support;
 /// Inline validation error display widget.
 pub mod validation_error;
+/// Vim-style modal editing for text widgets.
+pub mod vim;
 pub mod virtualized;
 pub mod voi_debug_overlay;
 
@@ -328,6 +333,7 @@ pub use stateful::{StateKey, Stateful, VersionedState};
 
 // Widget persist state types for state-persistence
 pub use list::ListPersistState;
+pub use scroll_view::ScrollViewPersistState;
 pub use table::TablePersistState;
 pub use tree::TreePersistState;
 pub use virtualized::VirtualizedListPersistState;
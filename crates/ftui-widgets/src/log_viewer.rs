@@ -18,6 +18,11 @@
 //! momentum scrolling, overscan, and page navigation for free. LogViewer adds
 //! capacity management (eviction), wrapping, filtering, and search on top.
 //!
+//! A viewer created with [`LogViewer::from_source`] keeps no lines of its own:
+//! it reads the visible lines from a shared [`LineSource`] (for example a
+//! lazily indexed multi-gigabyte file) on every render, and filtering and
+//! search read every line through the source.
+//!
 //! # Example
 //! ```ignore
 //! use ftui_widgets::log_viewer::{LogViewer, LogViewerState, LogWrapMode};
@@ -35,12 +40,17 @@
 //! viewer.render(area, frame, &mut state);
 //! ```
 
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
 use ftui_core::geometry::Rect;
 use ftui_render::frame::Frame;
 use ftui_style::Style;
 use ftui_text::search::{search_ascii_case_insensitive, search_exact};
 use ftui_text::{
-    Line, Span, Text as FtuiText, WrapMode, WrapOptions, display_width, wrap_with_options,
+    Line, LineSource, Span, Text as FtuiText, WrapMode, WrapOptions, display_width,
+    wrap_with_options,
 };
 
 use crate::virtualized::Virtualized;
@@ -194,6 +204,21 @@ pub struct LogViewer {
     search: Option<SearchState>,
     /// Incremental filter/search statistics.
     filter_stats: FilterStats,
+    /// External lines read on demand (None = lines pushed into `virt`).
+    source: Option<SharedSource>,
+}
+
+/// A [`LineSource`] shared between a viewer and its owner.
+#[derive(Clone)]
+pub(crate) struct SharedSource(pub(crate) Arc<dyn LineSource + Send + Sync>);
+
+impl fmt::Debug for SharedSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedSource")
+            .field("line_count", &self.0.line_count())
+            .field("is_complete", &self.0.is_complete())
+            .finish()
+    }
 }
 
 /// Separate state for StatefulWidget pattern.
@@ -227,6 +252,56 @@ impl LogViewer {
             filtered_scroll_offset: 0,
             search: None,
             filter_stats: FilterStats::default(),
+            source: None,
+        }
+    }
+
+    /// Create a viewer over an external line source.
+    ///
+    /// Only the visible lines are read, on every render. Follow mode starts
+    /// off; call [`refresh_source`](Self::refresh_source) to pick up lines a
+    /// growing source (such as a file still being indexed) has added.
+    /// [`push`](Self::push) is ignored for these viewers.
+    #[must_use]
+    pub fn from_source(source: Arc<dyn LineSource + Send + Sync>) -> Self {
+        let len = source.line_count();
+        Self {
+            virt: Virtualized::external(len, 0),
+            max_lines: usize::MAX,
+            source: Some(SharedSource(source)),
+            ..Self::new(0)
+        }
+    }
+
+    /// Re-read the line count of the source given to
+    /// [`from_source`](Self::from_source).
+    ///
+    /// New lines are checked against the active filter and search like
+    /// pushed lines. Does nothing for viewers that own their lines.
+    pub fn refresh_source(&mut self) {
+        let Some(source) = self.source.clone() else {
+            return;
+        };
+        let old_len = self.virt.len();
+        let new_len = source.0.line_count();
+        if new_len <= old_len {
+            return;
+        }
+        let follow_filtered = self.filtered_indices.as_ref().is_some_and(|indices| {
+            self.is_filtered_at_bottom(indices.len(), self.virt.visible_count())
+        });
+        if self.filter.is_some() || self.search.is_some() {
+            for idx in old_len..new_len {
+                let plain = source.0.line_text(idx).unwrap_or_default();
+                self.track_new_line(idx, &plain);
+            }
+        }
+        self.virt.set_external_len(new_len);
+        if follow_filtered
+            && let Some(indices) = self.filtered_indices.as_ref()
+            && !indices.is_empty()
+        {
+            self.filtered_scroll_offset = indices.len().saturating_sub(1);
         }
     }
 
@@ -280,7 +355,12 @@ impl LogViewer {
     ///
     /// # Auto-scroll Behavior
     /// If follow mode is enabled, view stays at bottom after push.
+    ///
+    /// Ignored for viewers created with [`from_source`](Self::from_source).
     pub fn push<'a>(&mut self, line: impl Into<FtuiText<'a>>) {
+        if self.source.is_some() {
+            return;
+        }
         let follow_filtered = self.filtered_indices.as_ref().is_some_and(|indices| {
             self.is_filtered_at_bottom(indices.len(), self.virt.visible_count())
         });
@@ -291,45 +371,7 @@ impl LogViewer {
             let item = Text::from_line(line);
             let plain = item.to_plain_text();
 
-            // Incremental filter check: test new line against active filter.
-            let filter_matched = if let Some(filter) = self.filter.as_ref() {
-                self.filter_stats.incremental_checks += 1;
-                let matched = plain.contains(filter.as_str());
-                if matched {
-                    if let Some(indices) = self.filtered_indices.as_mut() {
-                        let idx = self.virt.len();
-                        indices.push(idx);
-                    }
-                    self.filter_stats.incremental_matches += 1;
-                }
-                matched
-            } else {
-                false
-            };
-
-            // Incremental search check: test new line against active search query.
-            // Only add to search matches if (a) there is no filter or (b) the
-            // line passed the filter, because search results respect the filter.
-            if let Some(ref mut search) = self.search {
-                let should_check = self.filter.is_none() || filter_matched;
-                if should_check {
-                    self.filter_stats.incremental_search_checks += 1;
-                    let ranges = find_match_ranges(
-                        &plain,
-                        &search.query,
-                        search.query_lower.as_deref(),
-                        &search.config,
-                        #[cfg(feature = "regex-search")]
-                        search.compiled_regex.as_ref(),
-                    );
-                    if !ranges.is_empty() {
-                        let idx = self.virt.len();
-                        search.matches.push(idx);
-                        search.highlight_ranges.push(ranges);
-                        self.filter_stats.incremental_search_matches += 1;
-                    }
-                }
-            }
+            self.track_new_line(self.virt.len(), &plain);
 
             self.virt.push(item);
 
@@ -404,6 +446,58 @@ impl LogViewer {
         }
     }
 
+    /// Check a newly appended line against the active filter and search.
+    fn track_new_line(&mut self, idx: usize, plain: &str) {
+        // Incremental filter check: test new line against active filter.
+        let filter_matched = if let Some(filter) = self.filter.as_ref() {
+            self.filter_stats.incremental_checks += 1;
+            let matched = plain.contains(filter.as_str());
+            if matched {
+                if let Some(indices) = self.filtered_indices.as_mut() {
+                    indices.push(idx);
+                }
+                self.filter_stats.incremental_matches += 1;
+            }
+            matched
+        } else {
+            false
+        };
+
+        // Incremental search check: test new line against active search query.
+        // Only add to search matches if (a) there is no filter or (b) the
+        // line passed the filter, because search results respect the filter.
+        if let Some(ref mut search) = self.search {
+            let should_check = self.filter.is_none() || filter_matched;
+            if should_check {
+                self.filter_stats.incremental_search_checks += 1;
+                let ranges = find_match_ranges(
+                    plain,
+                    &search.query,
+                    search.query_lower.as_deref(),
+                    &search.config,
+                    #[cfg(feature = "regex-search")]
+                    search.compiled_regex.as_ref(),
+                );
+                if !ranges.is_empty() {
+                    search.matches.push(idx);
+                    search.highlight_ranges.push(ranges);
+                    self.filter_stats.incremental_search_matches += 1;
+                }
+            }
+        }
+    }
+
+    /// Plain text of line `idx`, from the source or the stored lines.
+    fn line_plain(&self, idx: usize) -> Option<Cow<'_, str>> {
+        match &self.source {
+            Some(source) => source.0.line_text(idx),
+            None => self
+                .virt
+                .get(idx)
+                .map(|item| Cow::Owned(item.to_plain_text())),
+        }
+    }
+
     /// Append multiple lines efficiently.
     pub fn push_many<'a>(&mut self, lines: impl IntoIterator<Item = impl Into<FtuiText<'a>>>) {
         for line in lines {
@@ -411,6 +505,14 @@ impl LogViewer {
         }
     }
 
+    /// Append colored subprocess output, keeping its SGR styles and links.
+    ///
+    /// `parser` carries styles and partial escape sequences between chunks;
+    /// only completed lines are appended.
+    pub fn push_ansi(&mut self, parser: &mut ftui_text::AnsiParser, chunk: &str) {
+        self.push_many(parser.feed(chunk));
+    }
+
     /// Scroll up by N lines. Disables follow mode.
     pub fn scroll_up(&mut self, lines: usize) {
         if self.filtered_indices.is_some() {
@@ -593,8 +695,8 @@ impl LogViewer {
                 self.filter_stats.full_rescan_lines += self.virt.len() as u64;
                 let mut indices = Vec::new();
                 for idx in 0..self.virt.len() {
-                    if let Some(item) = self.virt.get(idx)
-                        && item.to_plain_text().contains(pat)
+                    if let Some(plain) = self.line_plain(idx)
+                        && plain.contains(pat)
                     {
                         indices.push(idx);
                     }
@@ -679,8 +781,7 @@ impl LogViewer {
             };
 
         for idx in iter {
-            if let Some(item) = self.virt.get(idx) {
-                let plain = item.to_plain_text();
+            if let Some(plain) = self.line_plain(idx) {
                 let ranges = find_match_ranges(
                     &plain,
                     query,
@@ -1071,8 +1172,19 @@ impl StatefulWidget for LogViewer {
                 display_idx
             };
 
-            let Some(line) = self.virt.get(line_idx) else {
-                continue;
+            let sourced;
+            let line = match &self.source {
+                Some(source) => {
+                    let Some(text) = source.0.line_text(line_idx) else {
+                        continue;
+                    };
+                    sourced = Text::from_line(Line::raw(text.into_owned()));
+                    &sourced
+                }
+                None => match self.virt.get(line_idx) {
+                    Some(line) => line,
+                    None => continue,
+                },
             };
 
             let is_selected = state.selected_line == Some(line_idx);
@@ -1301,6 +1413,16 @@ mod tests {
         assert_eq!(log.line_count(), 2);
     }
 
+    #[test]
+    fn test_push_ansi_appends_completed_lines() {
+        let mut log = LogViewer::new(100);
+        let mut parser = ftui_text::AnsiParser::new();
+        log.push_ansi(&mut parser, "\x1b[32mok\x1b[0m\nwai");
+        assert_eq!(log.line_count(), 1);
+        log.push_ansi(&mut parser, "ting\n");
+        assert_eq!(log.line_count(), 2);
+    }
+
     #[test]
     fn test_circular_buffer_eviction() {
         let mut log = LogViewer::new(3);
@@ -1443,6 +1565,51 @@ mod tests {
         assert_eq!(line_text(&frame, 2, 20), " ".repeat(20));
     }
 
+    #[test]
+    fn test_from_source_reads_lines_on_demand() {
+        use std::sync::Mutex;
+
+        struct Growing(Mutex<Vec<String>>);
+
+        impl LineSource for Growing {
+            fn line_count(&self) -> usize {
+                self.0.lock().unwrap().len()
+            }
+
+            fn line_text(&self, idx: usize) -> Option<Cow<'_, str>> {
+                self.0.lock().unwrap().get(idx).cloned().map(Cow::Owned)
+            }
+        }
+
+        let source = Arc::new(Growing(Mutex::new(
+            (0..100).map(|i| format!("line {i}")).collect(),
+        )));
+        let mut log = LogViewer::from_source(source.clone());
+        assert_eq!(log.line_count(), 100);
+        log.push("ignored");
+        assert_eq!(log.line_count(), 100);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(20, 2, &mut pool);
+        let mut state = LogViewerState::default();
+        let area = Rect::new(0, 0, 20, 2);
+        log.render(area, &mut frame, &mut state);
+        assert!(line_text(&frame, 0, 20).starts_with("line 0 "));
+        assert!(line_text(&frame, 1, 20).starts_with("line 1 "));
+
+        assert_eq!(log.search("line 7"), 11);
+        source.0.lock().unwrap().push("line 700".into());
+        log.refresh_source();
+        assert_eq!(log.line_count(), 101);
+        assert_eq!(log.search_info(), Some((1, 12)));
+
+        log.set_filter(Some("9"));
+        log.scroll_to_top();
+        log.render(area, &mut frame, &mut state);
+        assert!(line_text(&frame, 0, 20).starts_with("line 9 "));
+        assert!(line_text(&frame, 1, 20).starts_with("line 19 "));
+    }
+
     #[test]
     fn test_render_some_lines() {
         let mut pool = GraphemePool::new();
@@ -0,0 +1,687 @@
+#![forbid(unsafe_code)]
+
+//! Readline/Emacs-style bindings for single-line inputs.
+//!
+//! This module holds the pieces [`TextInput`](crate::input::TextInput)
+//! uses when readline editing is switched on:
+//!
+//! - [`ReadlineKeymap`]: a configurable table from keys to
+//!   [`ReadlineAction`]s, defaulting to the usual Emacs vocabulary.
+//! - [`KillRing`]: killed text, with consecutive kills merged and
+//!   yank-pop rotation.
+//! - [`InputHistory`]: submitted lines, navigated by prefix or searched
+//!   incrementally (Ctrl-R), optionally backed by a [`HistoryStore`].
+//! - [`RegistryHistoryStore`]: a store that persists history through
+//!   [`StateRegistry`].
+//!
+//! # Default bindings
+//!
+//! | Key | Action |
+//! |-----|--------|
+//! | Ctrl-A / Ctrl-E | Start / end of line |
+//! | Ctrl-B / Ctrl-F | Back / forward one character |
+//! | Alt-B / Alt-F | Back / forward one word |
+//! | Ctrl-D | Delete character under the cursor |
+//! | Ctrl-K / Ctrl-U | Kill to end / start of line |
+//! | Ctrl-W, Alt-Backspace | Kill word before the cursor |
+//! | Alt-D | Kill word after the cursor |
+//! | Ctrl-Y / Alt-Y | Yank / yank-pop |
+//! | Ctrl-T | Transpose characters |
+//! | Ctrl-P, Up / Ctrl-N, Down | Older / newer history entry |
+//! | Ctrl-R | Incremental reverse history search |
+//! | Ctrl-G | Abort the current search |
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+use ftui_core::event::{KeyCode, KeyEvent, Modifiers};
+use ftui_runtime::state_persistence::StateRegistry;
+
+/// An editing command a key can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadlineAction {
+    /// Move to the start of the line.
+    LineStart,
+    /// Move to the end of the line.
+    LineEnd,
+    /// Move back one character.
+    CharBackward,
+    /// Move forward one character.
+    CharForward,
+    /// Move back to the start of the previous word.
+    WordBackward,
+    /// Move forward past the next word.
+    WordForward,
+    /// Delete the character under the cursor.
+    DeleteChar,
+    /// Kill from the cursor to the end of the line.
+    KillToEnd,
+    /// Kill from the start of the line to the cursor.
+    KillToStart,
+    /// Kill the word before the cursor.
+    KillWordBackward,
+    /// Kill the word after the cursor.
+    KillWordForward,
+    /// Insert the most recent kill.
+    Yank,
+    /// Replace the text just yanked with the next older kill.
+    YankPop,
+    /// Swap the characters around the cursor.
+    TransposeChars,
+    /// Recall an older history entry sharing the typed prefix.
+    HistoryPrevious,
+    /// Recall a newer history entry sharing the typed prefix.
+    HistoryNext,
+    /// Start, or step to the next match of, a reverse history search.
+    ReverseSearch,
+    /// Abort a reverse search, restoring the original line.
+    Abort,
+}
+
+impl ReadlineAction {
+    /// Whether the action kills text into the kill ring.
+    #[must_use]
+    pub const fn is_kill(self) -> bool {
+        matches!(
+            self,
+            Self::KillToEnd | Self::KillToStart | Self::KillWordBackward | Self::KillWordForward
+        )
+    }
+}
+
+/// Key-to-action table for readline editing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadlineKeymap {
+    bindings: Vec<(KeyCode, Modifiers, ReadlineAction)>,
+}
+
+impl Default for ReadlineKeymap {
+    fn default() -> Self {
+        Self::emacs()
+    }
+}
+
+impl ReadlineKeymap {
+    /// A keymap with no bindings.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// The standard Emacs bindings listed in the [module docs](self).
+    #[must_use]
+    pub fn emacs() -> Self {
+        use ReadlineAction as A;
+        let ctrl = |c| (KeyCode::Char(c), Modifiers::CTRL);
+        let alt = |c| (KeyCode::Char(c), Modifiers::ALT);
+        let bindings = [
+            (ctrl('a'), A::LineStart),
+            (ctrl('e'), A::LineEnd),
+            (ctrl('b'), A::CharBackward),
+            (ctrl('f'), A::CharForward),
+            (alt('b'), A::WordBackward),
+            (alt('f'), A::WordForward),
+            (ctrl('d'), A::DeleteChar),
+            (ctrl('k'), A::KillToEnd),
+            (ctrl('u'), A::KillToStart),
+            (ctrl('w'), A::KillWordBackward),
+            ((KeyCode::Backspace, Modifiers::ALT), A::KillWordBackward),
+            (alt('d'), A::KillWordForward),
+            (ctrl('y'), A::Yank),
+            (alt('y'), A::YankPop),
+            (ctrl('t'), A::TransposeChars),
+            (ctrl('p'), A::HistoryPrevious),
+            ((KeyCode::Up, Modifiers::NONE), A::HistoryPrevious),
+            (ctrl('n'), A::HistoryNext),
+            ((KeyCode::Down, Modifiers::NONE), A::HistoryNext),
+            (ctrl('r'), A::ReverseSearch),
+            (ctrl('g'), A::Abort),
+        ];
+        Self {
+            bindings: bindings
+                .into_iter()
+                .map(|((code, modifiers), action)| (code, modifiers, action))
+                .collect(),
+        }
+    }
+
+    /// Bind a key, replacing any existing binding for it.
+    #[must_use]
+    pub fn bind(mut self, code: KeyCode, modifiers: Modifiers, action: ReadlineAction) -> Self {
+        self.bindings
+            .retain(|&(c, m, _)| !(c == code && m == modifiers));
+        self.bindings.push((code, modifiers, action));
+        self
+    }
+
+    /// Remove the binding for a key, if any.
+    #[must_use]
+    pub fn unbind(mut self, code: KeyCode, modifiers: Modifiers) -> Self {
+        self.bindings
+            .retain(|&(c, m, _)| !(c == code && m == modifiers));
+        self
+    }
+
+    /// The action bound to a key event.
+    ///
+    /// Shift is ignored so that Caps Lock or shifted letters still match.
+    #[must_use]
+    pub fn action_for(&self, key: &KeyEvent) -> Option<ReadlineAction> {
+        let modifiers = key.modifiers - Modifiers::SHIFT;
+        let code = match key.code {
+            KeyCode::Char(c) if !modifiers.is_empty() => KeyCode::Char(c.to_ascii_lowercase()),
+            code => code,
+        };
+        self.bindings
+            .iter()
+            .find(|&&(c, m, _)| c == code && m == modifiers)
+            .map(|&(_, _, action)| action)
+    }
+}
+
+/// Default number of entries kept by a [`KillRing`].
+pub const DEFAULT_KILL_RING_CAPACITY: usize = 32;
+
+/// Ring of killed text for yank and yank-pop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillRing {
+    /// Newest entry at the front.
+    entries: VecDeque<String>,
+    capacity: usize,
+    /// Entry the last yank or yank-pop inserted.
+    yank_index: usize,
+}
+
+impl Default for KillRing {
+    fn default() -> Self {
+        Self::new(DEFAULT_KILL_RING_CAPACITY)
+    }
+}
+
+impl KillRing {
+    /// Create an empty ring holding at most `capacity` entries (minimum 1).
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+            yank_index: 0,
+        }
+    }
+
+    /// Number of entries in the ring.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the ring is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record killed text as a new entry.
+    pub fn push(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        if text.is_empty() {
+            return;
+        }
+        self.entries.push_front(text);
+        self.entries.truncate(self.capacity);
+        self.yank_index = 0;
+    }
+
+    /// Merge killed text into the newest entry, as consecutive kills do.
+    ///
+    /// `prepend` is set for backward kills so the entry reads in line order.
+    pub fn extend(&mut self, text: &str, prepend: bool) {
+        match self.entries.front_mut() {
+            Some(front) if prepend => front.insert_str(0, text),
+            Some(front) => front.push_str(text),
+            None => self.push(text),
+        }
+        self.yank_index = 0;
+    }
+
+    /// The newest entry, resetting the yank-pop rotation.
+    pub fn yank(&mut self) -> Option<&str> {
+        self.yank_index = 0;
+        self.entries.front().map(String::as_str)
+    }
+
+    /// Rotate to the next older entry, wrapping around.
+    pub fn yank_pop(&mut self) -> Option<&str> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.yank_index = (self.yank_index + 1) % self.entries.len();
+        self.entries.get(self.yank_index).map(String::as_str)
+    }
+}
+
+/// Backing storage for an [`InputHistory`].
+///
+/// Stores are shared (`Arc`) so that several inputs, or clones of one,
+/// can write to the same history.
+pub trait HistoryStore: fmt::Debug + Send + Sync {
+    /// Load stored entries, oldest first.
+    fn load(&self) -> Vec<String>;
+
+    /// Replace the stored entries, oldest first.
+    fn save(&self, entries: &[String]);
+}
+
+/// Persists history through a [`StateRegistry`] entry.
+///
+/// Entries are stored as newline-separated UTF-8. History entries are
+/// single lines, so no escaping is needed. Call
+/// [`StateRegistry::flush`] as usual to write the registry to disk.
+#[derive(Debug, Clone)]
+pub struct RegistryHistoryStore {
+    registry: Arc<StateRegistry>,
+    key: String,
+}
+
+impl RegistryHistoryStore {
+    /// Format version written alongside the data.
+    pub const VERSION: u32 = 1;
+
+    /// Store history under `key` in `registry`.
+    #[must_use]
+    pub fn new(registry: Arc<StateRegistry>, key: impl Into<String>) -> Self {
+        Self {
+            registry,
+            key: key.into(),
+        }
+    }
+
+    /// The registry key the history is stored under.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl HistoryStore for RegistryHistoryStore {
+    fn load(&self) -> Vec<String> {
+        let Some(entry) = self.registry.get(&self.key) else {
+            return Vec::new();
+        };
+        if entry.version != Self::VERSION {
+            return Vec::new();
+        }
+        String::from_utf8_lossy(&entry.data)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn save(&self, entries: &[String]) {
+        self.registry.set(
+            self.key.clone(),
+            Self::VERSION,
+            entries.join("\n").into_bytes(),
+        );
+    }
+}
+
+/// Default number of entries kept by an [`InputHistory`].
+pub const DEFAULT_HISTORY_CAPACITY: usize = 500;
+
+/// In-progress prefix navigation through history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Navigation {
+    /// Line being edited when navigation started.
+    draft: String,
+    /// Index of the entry currently shown, `None` while on the draft.
+    index: Option<usize>,
+}
+
+/// Submitted lines, with prefix navigation and substring search.
+#[derive(Debug, Clone, Default)]
+pub struct InputHistory {
+    /// Oldest entry first.
+    entries: Vec<String>,
+    capacity: Option<usize>,
+    navigation: Option<Navigation>,
+    store: Option<Arc<dyn HistoryStore>>,
+}
+
+impl InputHistory {
+    /// Create an empty in-memory history.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most `capacity` entries, dropping the oldest.
+    ///
+    /// Defaults to [`DEFAULT_HISTORY_CAPACITY`].
+    #[must_use]
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity.max(1));
+        self.trim();
+        self
+    }
+
+    /// Back the history with a store, loading its entries.
+    #[must_use]
+    pub fn with_store(mut self, store: Arc<dyn HistoryStore>) -> Self {
+        self.entries = store.load();
+        self.store = Some(store);
+        self.trim();
+        self.navigation = None;
+        self
+    }
+
+    /// All entries, oldest first.
+    #[must_use]
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record a submitted line.
+    ///
+    /// Blank lines and repeats of the newest entry are skipped. Line breaks
+    /// become spaces. Ends any navigation and writes through to the store.
+    pub fn push(&mut self, entry: impl Into<String>) {
+        self.navigation = None;
+        let entry = entry.into().replace(['\r', '\n'], " ");
+        if entry.trim().is_empty() || self.entries.last() == Some(&entry) {
+            return;
+        }
+        self.entries.push(entry);
+        self.trim();
+        if let Some(store) = &self.store {
+            store.save(&self.entries);
+        }
+    }
+
+    /// Remove all entries, writing through to the store.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.navigation = None;
+        if let Some(store) = &self.store {
+            store.save(&self.entries);
+        }
+    }
+
+    /// Whether [`older`](Self::older)/[`newer`](Self::newer) navigation is
+    /// in progress.
+    #[must_use]
+    pub fn is_navigating(&self) -> bool {
+        self.navigation.is_some()
+    }
+
+    /// Stop navigating, keeping whatever line is shown.
+    pub fn reset_navigation(&mut self) {
+        self.navigation = None;
+    }
+
+    /// Step to the next older entry starting with the line as it was when
+    /// navigation began.
+    ///
+    /// `current` is the line being edited; it is remembered on the first
+    /// step so [`newer`](Self::newer) can return to it. Returns `None` when
+    /// no older entry matches.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let nav = self.navigation.get_or_insert_with(|| Navigation {
+            draft: current.to_owned(),
+            index: None,
+        });
+        let end = nav.index.unwrap_or(self.entries.len());
+        let shown = nav.index.map_or(current, |i| self.entries[i].as_str());
+        let found = self.entries[..end]
+            .iter()
+            .rposition(|entry| entry.starts_with(&nav.draft) && entry != shown)?;
+        nav.index = Some(found);
+        Some(&self.entries[found])
+    }
+
+    /// Step to the next newer matching entry, or back to the original line.
+    ///
+    /// Returns `None` when not navigating.
+    pub fn newer(&mut self) -> Option<&str> {
+        let nav = self.navigation.as_mut()?;
+        let index = nav.index?;
+        let shown = &self.entries[index];
+        let found = self.entries[index + 1..]
+            .iter()
+            .position(|entry| entry.starts_with(&nav.draft) && entry != shown)
+            .map(|offset| index + 1 + offset);
+        nav.index = found;
+        Some(match found {
+            Some(i) => &self.entries[i],
+            None => &nav.draft,
+        })
+    }
+
+    /// Index of the newest entry before `before` containing `query`.
+    #[must_use]
+    pub fn search_backward(&self, query: &str, before: usize) -> Option<usize> {
+        let end = before.min(self.entries.len());
+        self.entries[..end]
+            .iter()
+            .rposition(|entry| entry.contains(query))
+    }
+
+    fn trim(&mut self) {
+        let capacity = self.capacity.unwrap_or(DEFAULT_HISTORY_CAPACITY);
+        if self.entries.len() > capacity {
+            let excess = self.entries.len() - capacity;
+            self.entries.drain(..excess);
+        }
+    }
+}
+
+/// State of an incremental reverse search (Ctrl-R).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReverseSearch {
+    query: String,
+    /// History index of the current match.
+    matched: Option<usize>,
+    /// Line being edited when the search started.
+    original: String,
+    failed: bool,
+}
+
+impl ReverseSearch {
+    pub(crate) fn new(original: impl Into<String>) -> Self {
+        Self {
+            query: String::new(),
+            matched: None,
+            original: original.into(),
+            failed: false,
+        }
+    }
+
+    /// The search text typed so far.
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// History index of the current match.
+    #[must_use]
+    pub fn matched(&self) -> Option<usize> {
+        self.matched
+    }
+
+    /// Whether the last refinement found no match.
+    #[must_use]
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+
+    /// The line as it was before the search started.
+    #[must_use]
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    /// Prompt text in the style of readline, e.g. ``(reverse-i-search)`ls': ``.
+    #[must_use]
+    pub fn prompt(&self) -> String {
+        let prefix = if self.failed { "failing " } else { "" };
+        format!("({prefix}reverse-i-search)`{}': ", self.query)
+    }
+
+    /// Append to the query and re-search from the current match.
+    pub(crate) fn push_str(&mut self, text: &str, history: &InputHistory) {
+        self.query.push_str(text);
+        let from = self.matched.map_or(history.len(), |i| i + 1);
+        self.search(history, from);
+    }
+
+    /// Drop the last character of the query and re-search from the newest entry.
+    pub(crate) fn pop(&mut self, history: &InputHistory) {
+        self.query.pop();
+        self.matched = None;
+        if self.query.is_empty() {
+            self.failed = false;
+        } else {
+            self.search(history, history.len());
+        }
+    }
+
+    /// Step to the next older match.
+    pub(crate) fn next_older(&mut self, history: &InputHistory) {
+        if self.query.is_empty() {
+            return;
+        }
+        let from = self.matched.unwrap_or(history.len());
+        self.search(history, from);
+    }
+
+    fn search(&mut self, history: &InputHistory, before: usize) {
+        match history.search_backward(&self.query, before) {
+            Some(i) => {
+                self.matched = Some(i);
+                self.failed = false;
+            }
+            None => self.failed = true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keymap_defaults_and_overrides() {
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c)).with_modifiers(Modifiers::CTRL);
+        let map = ReadlineKeymap::emacs();
+        assert_eq!(map.action_for(&ctrl('a')), Some(ReadlineAction::LineStart));
+        assert_eq!(
+            map.action_for(&KeyEvent::new(KeyCode::Char('b')).with_modifiers(Modifiers::ALT)),
+            Some(ReadlineAction::WordBackward)
+        );
+        assert_eq!(map.action_for(&KeyEvent::new(KeyCode::Char('a'))), None);
+
+        let map = map.unbind(KeyCode::Char('t'), Modifiers::CTRL).bind(
+            KeyCode::Char('a'),
+            Modifiers::CTRL,
+            ReadlineAction::LineEnd,
+        );
+        assert_eq!(map.action_for(&ctrl('t')), None);
+        assert_eq!(map.action_for(&ctrl('a')), Some(ReadlineAction::LineEnd));
+        assert_eq!(ReadlineKeymap::empty().action_for(&ctrl('a')), None);
+    }
+
+    #[test]
+    fn kill_ring_merges_and_rotates() {
+        let mut ring = KillRing::new(2);
+        assert_eq!(ring.yank(), None);
+        ring.push("one");
+        ring.push("two");
+        ring.extend(" more", false);
+        ring.extend("even ", true);
+        assert_eq!(ring.yank(), Some("even two more"));
+        assert_eq!(ring.yank_pop(), Some("one"));
+        assert_eq!(ring.yank_pop(), Some("even two more"));
+
+        ring.push("three");
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.yank_pop(), Some("even two more"));
+        assert_eq!(ring.yank_pop(), Some("three"));
+    }
+
+    #[test]
+    fn history_prefix_navigation() {
+        let mut history = InputHistory::new();
+        for line in ["git status", "ls", "git log", "git log", "  "] {
+            history.push(line);
+        }
+        assert_eq!(history.entries(), ["git status", "ls", "git log"]);
+
+        assert_eq!(history.older("git"), Some("git log"));
+        assert_eq!(history.older("git log"), Some("git status"));
+        assert_eq!(history.older("git status"), None);
+        assert_eq!(history.newer(), Some("git log"));
+        assert_eq!(history.newer(), Some("git"));
+        assert_eq!(history.newer(), None);
+
+        history.reset_navigation();
+        assert_eq!(history.older(""), Some("git log"));
+        assert_eq!(history.older("git log"), Some("ls"));
+    }
+
+    #[test]
+    fn reverse_search_steps_through_matches() {
+        let mut history = InputHistory::new();
+        for line in ["cargo build", "ls", "cargo test", "cat file"] {
+            history.push(line);
+        }
+        let mut search = ReverseSearch::new("draft");
+        search.push_str("ca", &history);
+        assert_eq!(search.matched(), Some(3));
+        search.push_str("r", &history);
+        assert_eq!(search.matched(), Some(2));
+        search.next_older(&history);
+        assert_eq!(search.matched(), Some(0));
+        search.next_older(&history);
+        assert!(search.failed());
+        assert_eq!(search.matched(), Some(0));
+        assert_eq!(search.prompt(), "(failing reverse-i-search)`car': ");
+
+        search.pop(&history);
+        assert_eq!(search.matched(), Some(3));
+        assert_eq!(search.original(), "draft");
+    }
+
+    #[test]
+    fn registry_store_round_trips() {
+        let registry = Arc::new(StateRegistry::in_memory());
+        let store: Arc<dyn HistoryStore> = Arc::new(RegistryHistoryStore::new(
+            Arc::clone(&registry),
+            "History::prompt",
+        ));
+        let mut history = InputHistory::new().with_store(Arc::clone(&store));
+        history.push("first");
+        history.push("second\nline");
+
+        let entry = registry.get("History::prompt").expect("history saved");
+        assert_eq!(entry.version, RegistryHistoryStore::VERSION);
+
+        let restored = InputHistory::new().with_store(store);
+        assert_eq!(restored.entries(), ["first", "second line"]);
+    }
+}
@@ -0,0 +1,791 @@
+#![forbid(unsafe_code)]
+
+//! Scrollable container widget.
+//!
+//! A [`ScrollView`] places child widgets on a virtual content area that can
+//! be larger than the space it is given, and shows the part selected by the
+//! scroll offset in [`ScrollViewState`]. Rendering and hit regions of the
+//! children are clipped to the viewport; optional [`Scrollbar`]s are drawn
+//! along the right and bottom edges when the content overflows.
+//!
+//! Children are rendered at their content positions into an off-screen frame
+//! that covers only the visible window, then copied into the viewport, so a
+//! child much larger than the viewport costs no more memory than the viewport
+//! itself. Wide glyphs cut by the viewport edge are replaced with blanks.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let view = ScrollView::new(Size::new(60, 40))
+//!     .child(Rect::new(0, 0, 60, 1), &title)
+//!     .child(Rect::new(0, 2, 60, 30), &form)
+//!     .hit_id(HitId::new(7));
+//! StatefulWidget::render(&view, area, frame, &mut state);
+//!
+//! // Keep the focused field visible.
+//! state.scroll_into_view(focused_rect);
+//! ```
+
+use crate::mouse::MouseResult;
+use crate::scrollbar::{Scrollbar, ScrollbarOrientation, ScrollbarState};
+use crate::stateful::{StateKey, Stateful};
+use crate::{StatefulWidget, Widget, clear_text_area};
+use ftui_core::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ftui_core::geometry::{Rect, Size};
+use ftui_render::buffer::Buffer;
+use ftui_render::cell::Cell;
+use ftui_render::frame::{Frame, HitGrid, HitId, HitRegion};
+use ftui_style::Style;
+
+/// Rows or columns moved per mouse wheel notch.
+const WHEEL_STEP: u16 = 3;
+
+/// A container that scrolls child widgets laid out on a virtual area.
+pub struct ScrollView<'a> {
+    content_size: Size,
+    children: Vec<(Rect, &'a dyn Widget)>,
+    style: Style,
+    vertical_scrollbar: bool,
+    horizontal_scrollbar: bool,
+    scrollbar_style: Style,
+    hit_id: Option<HitId>,
+}
+
+impl<'a> ScrollView<'a> {
+    /// Create a scroll view over a virtual area of `content_size` cells.
+    #[must_use]
+    pub fn new(content_size: Size) -> Self {
+        Self {
+            content_size,
+            children: Vec::new(),
+            style: Style::default(),
+            vertical_scrollbar: true,
+            horizontal_scrollbar: true,
+            scrollbar_style: Style::default(),
+            hit_id: None,
+        }
+    }
+
+    /// Add a child widget at `rect`, in content coordinates.
+    ///
+    /// Children are rendered in insertion order; parts outside the content
+    /// area are clipped.
+    #[must_use]
+    pub fn child(mut self, rect: Rect, widget: &'a dyn Widget) -> Self {
+        self.children.push((rect, widget));
+        self
+    }
+
+    /// Set the style used to clear the viewport.
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Show a vertical scrollbar when the content is taller than the view.
+    #[must_use]
+    pub fn vertical_scrollbar(mut self, enabled: bool) -> Self {
+        self.vertical_scrollbar = enabled;
+        self
+    }
+
+    /// Show a horizontal scrollbar when the content is wider than the view.
+    #[must_use]
+    pub fn horizontal_scrollbar(mut self, enabled: bool) -> Self {
+        self.horizontal_scrollbar = enabled;
+        self
+    }
+
+    /// Set the style of the scrollbar thumbs.
+    #[must_use]
+    pub fn scrollbar_style(mut self, style: Style) -> Self {
+        self.scrollbar_style = style;
+        self
+    }
+
+    /// Set a hit ID for the viewport and scrollbars.
+    #[must_use]
+    pub fn hit_id(mut self, id: HitId) -> Self {
+        self.hit_id = Some(id);
+        self
+    }
+
+    /// Split `area` into the viewport and the scrollbar strips.
+    fn layout(&self, area: Rect) -> (Rect, Option<Rect>, Option<Rect>) {
+        let mut width = area.width;
+        let mut height = area.height;
+        let mut vertical = false;
+        let mut horizontal = false;
+        // A vertical bar narrows the view, which may make a horizontal one
+        // necessary (and vice versa); two passes settle it.
+        for _ in 0..2 {
+            vertical = self.vertical_scrollbar && self.content_size.height > height;
+            horizontal = self.horizontal_scrollbar && self.content_size.width > width;
+            width = area.width.saturating_sub(u16::from(vertical));
+            height = area.height.saturating_sub(u16::from(horizontal));
+        }
+        let viewport = Rect::new(area.x, area.y, width, height);
+        let vertical = vertical.then(|| Rect::new(viewport.right(), area.y, 1, height));
+        let horizontal = horizontal.then(|| Rect::new(area.x, viewport.bottom(), width, 1));
+        (viewport, vertical, horizontal)
+    }
+
+    /// Render the children intersecting `window` into an off-screen frame
+    /// clipped to `window` and copy it to `viewport`.
+    fn render_content(&self, viewport: Rect, window: Rect, frame: &mut Frame) {
+        let content = Rect::from_size(self.content_size.width, self.content_size.height);
+        let visible: Vec<_> = self
+            .children
+            .iter()
+            .filter_map(|&(rect, widget)| {
+                let rect = rect.intersection_opt(&content)?;
+                rect.intersection_opt(&window)?;
+                Some((rect, widget))
+            })
+            .collect();
+        let Some(canvas) = visible
+            .iter()
+            .map(|(rect, _)| *rect)
+            .reduce(|a, b| a.union(&b))
+            .and_then(|canvas| canvas.intersection_opt(&window))
+        else {
+            return;
+        };
+
+        // The off-screen frame is addressed in content coordinates, so a child
+        // starting above or left of the window renders at its own position and
+        // only the cells inside the window are kept.
+        let mut offscreen = Frame::from_buffer(Buffer::new_at(canvas), &mut *frame.pool);
+        offscreen.links = frame.links.as_deref_mut();
+        offscreen.widget_budget = frame.widget_budget.clone();
+        offscreen.set_degradation(frame.degradation);
+        if frame.hit_grid.is_some() {
+            offscreen.enable_hit_testing();
+        }
+        for (rect, widget) in visible {
+            offscreen.buffer.push_scissor(rect);
+            widget.render(rect, &mut offscreen);
+            offscreen.buffer.pop_scissor();
+        }
+        let cursor = offscreen.cursor_position;
+        let signals = offscreen.take_widget_signals();
+        let hits = offscreen.hit_grid.take();
+        let buffer = offscreen.buffer;
+
+        let to_screen = |x: u16, y: u16| (viewport.x + x - window.x, viewport.y + y - window.y);
+        blit(frame, &buffer, window, to_screen);
+        if let (Some(hits), Some(grid)) = (hits, frame.hit_grid.as_mut()) {
+            copy_hits(grid, &hits, &frame.buffer.current_scissor(), to_screen);
+        }
+        if let Some((x, y)) = cursor
+            && canvas.contains(x, y)
+        {
+            frame.cursor_position = Some(to_screen(x, y));
+        }
+        for signal in signals {
+            frame.register_widget_signal(signal);
+        }
+    }
+}
+
+/// Copy the cells of the off-screen `buffer` (content coordinates) into the
+/// frame.
+fn blit(
+    frame: &mut Frame,
+    buffer: &Buffer,
+    window: Rect,
+    to_screen: impl Fn(u16, u16) -> (u16, u16),
+) {
+    let shown = buffer.bounds();
+    for cy in shown.top()..shown.bottom() {
+        for cx in shown.left()..shown.right() {
+            let Some(cell) = buffer.get(cx, cy) else {
+                continue;
+            };
+            let (x, y) = to_screen(cx, cy);
+            if cell.is_continuation() {
+                // The head writes its own tail; a tail without its head is cut.
+                if cx == window.left() {
+                    frame.buffer.set(x, y, Cell::default());
+                }
+                continue;
+            }
+            let width = cell.content.width().max(1) as u16;
+            if cx.saturating_add(width) > window.right() {
+                frame.buffer.set(x, y, Cell::default());
+            } else {
+                frame.buffer.set(x, y, *cell);
+            }
+        }
+    }
+}
+
+/// Copy the off-screen hit cells into the parent grid, clipped to `scissor`.
+fn copy_hits(
+    grid: &mut HitGrid,
+    hits: &HitGrid,
+    scissor: &Rect,
+    to_screen: impl Fn(u16, u16) -> (u16, u16),
+) {
+    let shown = hits.bounds();
+    for cy in shown.top()..shown.bottom() {
+        for cx in shown.left()..shown.right() {
+            let Some(hit) = hits.get(cx, cy) else {
+                continue;
+            };
+            let (x, y) = to_screen(cx, cy);
+            if !hit.is_empty()
+                && scissor.contains(x, y)
+                && let Some(slot) = grid.get_mut(x, y)
+            {
+                *slot = *hit;
+            }
+        }
+    }
+}
+
+/// Scroll position and viewport geometry of a [`ScrollView`].
+#[derive(Debug, Clone, Default)]
+pub struct ScrollViewState {
+    offset_x: u16,
+    offset_y: u16,
+    /// Viewport size from the last render.
+    viewport: Rect,
+    /// Content size from the last render.
+    content_size: Size,
+    /// Rect to reveal once the viewport size is known.
+    pending_reveal: Option<Rect>,
+    vertical: ScrollbarState,
+    horizontal: ScrollbarState,
+    vertical_area: Option<Rect>,
+    horizontal_area: Option<Rect>,
+    /// Optional persistence ID for state saving/restoration.
+    persistence_id: Option<String>,
+}
+
+impl ScrollViewState {
+    /// Create a state scrolled to the top-left corner.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the persistence ID used by [`Stateful::state_key`].
+    #[must_use]
+    pub fn with_persistence_id(mut self, id: impl Into<String>) -> Self {
+        self.persistence_id = Some(id.into());
+        self
+    }
+
+    /// Get the persistence ID, if set.
+    #[must_use = "use the persistence id (if any)"]
+    pub fn persistence_id(&self) -> Option<&str> {
+        self.persistence_id.as_deref()
+    }
+
+    /// Current `(x, y)` scroll offset.
+    #[must_use]
+    pub const fn offset(&self) -> (u16, u16) {
+        (self.offset_x, self.offset_y)
+    }
+
+    /// Viewport area from the last render (empty before the first render).
+    #[must_use]
+    pub const fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
+    /// Set the scroll offset, clamped to the content from the last render.
+    pub fn set_offset(&mut self, x: u16, y: u16) {
+        self.offset_x = x;
+        self.offset_y = y;
+        self.clamp();
+    }
+
+    /// Scroll up by `rows`.
+    pub fn scroll_up(&mut self, rows: u16) {
+        self.set_offset(self.offset_x, self.offset_y.saturating_sub(rows));
+    }
+
+    /// Scroll down by `rows`.
+    pub fn scroll_down(&mut self, rows: u16) {
+        self.set_offset(self.offset_x, self.offset_y.saturating_add(rows));
+    }
+
+    /// Scroll left by `columns`.
+    pub fn scroll_left(&mut self, columns: u16) {
+        self.set_offset(self.offset_x.saturating_sub(columns), self.offset_y);
+    }
+
+    /// Scroll right by `columns`.
+    pub fn scroll_right(&mut self, columns: u16) {
+        self.set_offset(self.offset_x.saturating_add(columns), self.offset_y);
+    }
+
+    /// Scroll the smallest distance that makes `rect` (content coordinates)
+    /// visible, preferring its top-left corner when it is larger than the
+    /// viewport.
+    ///
+    /// Call this when focus moves to a child. Before the first render the
+    /// request is kept and applied once the viewport size is known.
+    pub fn scroll_into_view(&mut self, rect: Rect) {
+        if self.viewport.is_empty() {
+            self.pending_reveal = Some(rect);
+            return;
+        }
+        self.offset_x = reveal(self.offset_x, self.viewport.width, rect.x, rect.width);
+        self.offset_y = reveal(self.offset_y, self.viewport.height, rect.y, rect.height);
+        self.clamp();
+    }
+
+    /// Handle scrolling keys: arrows, `PageUp`/`PageDown`, `Home`/`End`.
+    ///
+    /// Returns `true` when the offset changed.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let before = self.offset();
+        let page = self.viewport.height.saturating_sub(1).max(1);
+        match key.code {
+            KeyCode::Up => self.scroll_up(1),
+            KeyCode::Down => self.scroll_down(1),
+            KeyCode::Left => self.scroll_left(1),
+            KeyCode::Right => self.scroll_right(1),
+            KeyCode::PageUp => self.scroll_up(page),
+            KeyCode::PageDown => self.scroll_down(page),
+            KeyCode::Home => self.set_offset(self.offset_x, 0),
+            KeyCode::End => self.set_offset(self.offset_x, u16::MAX),
+            _ => return false,
+        }
+        self.offset() != before
+    }
+
+    /// Handle wheel scrolling over the view and scrollbar clicks and drags.
+    ///
+    /// `hit` is the hit-test result at the event position and `expected_id`
+    /// the ID the view was rendered with. The wheel scrolls when the pointer
+    /// is anywhere over the last rendered view, including its children.
+    pub fn handle_mouse(
+        &mut self,
+        event: &MouseEvent,
+        hit: Option<(HitId, HitRegion, u64)>,
+        expected_id: HitId,
+    ) -> MouseResult {
+        let over = |area: Option<Rect>| area.is_some_and(|a| a.contains(event.x, event.y));
+        let to_vertical = self.vertical.drag_anchor.is_some()
+            || (self.horizontal.drag_anchor.is_none() && over(self.vertical_area));
+        let to_horizontal =
+            !to_vertical && (self.horizontal.drag_anchor.is_some() || over(self.horizontal_area));
+        let is_wheel = matches!(
+            event.kind,
+            MouseEventKind::ScrollUp
+                | MouseEventKind::ScrollDown
+                | MouseEventKind::ScrollLeft
+                | MouseEventKind::ScrollRight
+        );
+
+        if !is_wheel && (to_vertical || to_horizontal) {
+            let bar = if to_vertical {
+                &mut self.vertical
+            } else {
+                &mut self.horizontal
+            };
+            let result = bar.handle_mouse(event, hit, expected_id);
+            let (x, y) = if to_vertical {
+                (self.offset_x, self.vertical.position as u16)
+            } else {
+                (self.horizontal.position as u16, self.offset_y)
+            };
+            self.set_offset(x, y);
+            return result;
+        }
+
+        let inside = over(Some(self.viewport)) || to_vertical || to_horizontal;
+        if !is_wheel || !inside {
+            return MouseResult::Ignored;
+        }
+        let before = self.offset();
+        match event.kind {
+            MouseEventKind::ScrollUp => self.scroll_up(WHEEL_STEP),
+            MouseEventKind::ScrollDown => self.scroll_down(WHEEL_STEP),
+            MouseEventKind::ScrollLeft => self.scroll_left(WHEEL_STEP),
+            _ => self.scroll_right(WHEEL_STEP),
+        }
+        if self.offset() == before {
+            MouseResult::Ignored
+        } else {
+            MouseResult::Scrolled
+        }
+    }
+
+    /// Clamp the offset so the viewport stays inside the content.
+    fn clamp(&mut self) {
+        if self.viewport.is_empty() {
+            return;
+        }
+        let max_x = self.content_size.width.saturating_sub(self.viewport.width);
+        let max_y = self
+            .content_size
+            .height
+            .saturating_sub(self.viewport.height);
+        self.offset_x = self.offset_x.min(max_x);
+        self.offset_y = self.offset_y.min(max_y);
+    }
+}
+
+/// New offset along one axis so that `[start, start + len)` is visible.
+fn reveal(offset: u16, view: u16, start: u16, len: u16) -> u16 {
+    let end = start.saturating_add(len);
+    if start < offset || len >= view {
+        start
+    } else if end > offset.saturating_add(view) {
+        end - view
+    } else {
+        offset
+    }
+}
+
+impl StatefulWidget for ScrollView<'_> {
+    type State = ScrollViewState;
+
+    fn render(&self, area: Rect, frame: &mut Frame, state: &mut Self::State) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "widget_render",
+            widget = "ScrollView",
+            x = area.x,
+            y = area.y,
+            w = area.width,
+            h = area.height
+        )
+        .entered();
+
+        let (viewport, vertical_area, horizontal_area) = self.layout(area);
+        state.viewport = viewport;
+        state.content_size = self.content_size;
+        state.vertical_area = vertical_area;
+        state.horizontal_area = horizontal_area;
+        if let Some(rect) = state.pending_reveal.take() {
+            state.scroll_into_view(rect);
+        }
+        state.clamp();
+
+        clear_text_area(frame, viewport, self.style);
+        if viewport.is_empty() {
+            return;
+        }
+        if let Some(id) = self.hit_id {
+            frame.register_hit(viewport, id, HitRegion::Content, 0);
+        }
+
+        let window = Rect::new(
+            state.offset_x,
+            state.offset_y,
+            viewport.width,
+            viewport.height,
+        );
+        self.render_content(viewport, window, frame);
+
+        let mut bar = |orientation, area: Option<Rect>, bar_state: &mut ScrollbarState| {
+            let Some(area) = area else {
+                return;
+            };
+            let mut scrollbar = Scrollbar::new(orientation).thumb_style(self.scrollbar_style);
+            if let Some(id) = self.hit_id {
+                scrollbar = scrollbar.hit_id(id);
+            }
+            StatefulWidget::render(&scrollbar, area, frame, bar_state);
+        };
+        state.vertical.content_length = usize::from(self.content_size.height);
+        state.vertical.viewport_length = usize::from(viewport.height);
+        state.vertical.position = usize::from(state.offset_y);
+        bar(
+            ScrollbarOrientation::VerticalRight,
+            vertical_area,
+            &mut state.vertical,
+        );
+        state.horizontal.content_length = usize::from(self.content_size.width);
+        state.horizontal.viewport_length = usize::from(viewport.width);
+        state.horizontal.position = usize::from(state.offset_x);
+        bar(
+            ScrollbarOrientation::HorizontalBottom,
+            horizontal_area,
+            &mut state.horizontal,
+        );
+    }
+}
+
+// ============================================================================
+// Stateful Persistence Implementation
+// ============================================================================
+
+/// Persistable state for a [`ScrollViewState`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "state-persistence",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct ScrollViewPersistState {
+    /// Horizontal scroll offset.
+    pub offset_x: u16,
+    /// Vertical scroll offset.
+    pub offset_y: u16,
+}
+
+impl Stateful for ScrollViewState {
+    type State = ScrollViewPersistState;
+
+    fn state_key(&self) -> StateKey {
+        StateKey::new(
+            "ScrollView",
+            self.persistence_id.as_deref().unwrap_or("default"),
+        )
+    }
+
+    fn save_state(&self) -> ScrollViewPersistState {
+        ScrollViewPersistState {
+            offset_x: self.offset_x,
+            offset_y: self.offset_y,
+        }
+    }
+
+    fn restore_state(&mut self, state: ScrollViewPersistState) {
+        // Clamped against the content on the next render.
+        self.offset_x = state.offset_x;
+        self.offset_y = state.offset_y;
+        self.pending_reveal = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ftui_core::event::MouseButton;
+    use ftui_render::grapheme_pool::GraphemePool;
+
+    /// Fills its area with one character and registers a hit region.
+    struct Fill(char, Option<HitId>);
+
+    impl Widget for Fill {
+        fn render(&self, area: Rect, frame: &mut Frame) {
+            frame.buffer.fill(area, Cell::from_char(self.0));
+            if let Some(id) = self.1 {
+                frame.register_hit(area, id, HitRegion::Content, 0);
+            }
+        }
+    }
+
+    fn row(frame: &Frame, y: u16, width: u16) -> String {
+        (0..width)
+            .map(|x| {
+                frame
+                    .buffer
+                    .get(x, y)
+                    .and_then(|c| c.content.as_char())
+                    .unwrap_or(' ')
+            })
+            .collect()
+    }
+
+    #[test]
+    fn renders_window_selected_by_offset() {
+        let top = Fill('a', None);
+        let bottom = Fill('b', None);
+        let view = ScrollView::new(Size::new(4, 10))
+            .child(Rect::new(0, 0, 4, 5), &top)
+            .child(Rect::new(0, 5, 4, 5), &bottom)
+            .vertical_scrollbar(false);
+        let mut state = ScrollViewState::new();
+        state.set_offset(0, 3);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(4, 4, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 4, 4), &mut frame, &mut state);
+
+        assert_eq!(row(&frame, 0, 4), "aaaa");
+        assert_eq!(row(&frame, 1, 4), "aaaa");
+        assert_eq!(row(&frame, 2, 4), "bbbb");
+        assert_eq!(state.offset(), (0, 3));
+    }
+
+    #[test]
+    fn offset_is_clamped_to_content() {
+        let fill = Fill('x', None);
+        let view = ScrollView::new(Size::new(4, 6)).child(Rect::new(0, 0, 4, 6), &fill);
+        let mut state = ScrollViewState::new();
+        state.set_offset(9, 9);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(5, 4, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 5, 4), &mut frame, &mut state);
+
+        assert_eq!(state.viewport(), Rect::new(0, 0, 4, 4));
+        assert_eq!(state.offset(), (0, 2));
+        assert!(frame.buffer.get(4, 0).unwrap().content.as_char().is_some());
+    }
+
+    #[test]
+    fn hit_regions_are_clipped_and_translated() {
+        let button = Fill('b', Some(HitId::new(5)));
+        let view = ScrollView::new(Size::new(4, 10))
+            .child(Rect::new(0, 6, 4, 2), &button)
+            .vertical_scrollbar(false)
+            .hit_id(HitId::new(1));
+        let mut state = ScrollViewState::new();
+        state.set_offset(0, 5);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::with_hit_grid(4, 4, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 4, 4), &mut frame, &mut state);
+        let id = |y| frame.hit_test(0, y).map(|(id, _, _)| id);
+        assert_eq!(id(0), Some(HitId::new(1)));
+        assert_eq!(id(1), Some(HitId::new(5)));
+        assert_eq!(id(2), Some(HitId::new(5)));
+        assert_eq!(id(3), Some(HitId::new(1)));
+
+        // Scrolled past the button: nothing of it remains.
+        state.set_offset(0, 0);
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::with_hit_grid(4, 4, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 4, 4), &mut frame, &mut state);
+        assert!((0..4).all(|y| frame.hit_test(0, y).map(|h| h.0) != Some(HitId::new(5))));
+    }
+
+    #[test]
+    fn tall_child_renders_into_viewport_sized_buffer() {
+        /// Writes each row's index mod 10 and records the buffer it drew into.
+        struct Rows(std::cell::Cell<Rect>);
+        impl Widget for Rows {
+            fn render(&self, area: Rect, frame: &mut Frame) {
+                self.0.set(frame.buffer.bounds());
+                for y in area.top()..area.bottom() {
+                    let digit = char::from_digit(u32::from(y % 10), 10).unwrap();
+                    frame.buffer.set(area.x, y, Cell::from_char(digit));
+                }
+            }
+        }
+        let rows = Rows(std::cell::Cell::new(Rect::default()));
+        let view = ScrollView::new(Size::new(4, 10_000))
+            .child(Rect::new(0, 0, 4, 10_000), &rows)
+            .vertical_scrollbar(false);
+        let mut state = ScrollViewState::new();
+        state.set_offset(0, 5_003);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(4, 3, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 4, 3), &mut frame, &mut state);
+
+        let offscreen = rows.0.get();
+        assert!(offscreen.height <= 3, "off-screen buffer {offscreen:?}");
+        assert_eq!(offscreen, Rect::new(0, 5_003, 4, 3));
+        assert!(row(&frame, 0, 4).starts_with('3'));
+        assert!(row(&frame, 1, 4).starts_with('4'));
+        assert!(row(&frame, 2, 4).starts_with('5'));
+    }
+
+    #[test]
+    fn wide_glyph_cut_by_viewport_edge_is_blanked() {
+        struct Wide;
+        impl Widget for Wide {
+            fn render(&self, area: Rect, frame: &mut Frame) {
+                frame.buffer.set(area.x, area.y, Cell::from_char('中'));
+            }
+        }
+        let wide = Wide;
+        let view = ScrollView::new(Size::new(6, 1))
+            .child(Rect::new(0, 0, 6, 1), &wide)
+            .horizontal_scrollbar(false);
+        let mut state = ScrollViewState::new();
+        state.set_offset(1, 0);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(3, 1, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 3, 1), &mut frame, &mut state);
+        assert_eq!(state.offset(), (1, 0));
+        let first = frame.buffer.get(0, 0).unwrap();
+        assert!(!first.is_continuation());
+        assert!(first.content.as_char().is_none_or(|c| c == ' '));
+    }
+
+    #[test]
+    fn scroll_into_view_moves_minimally() {
+        let mut state = ScrollViewState::new();
+        state.scroll_into_view(Rect::new(0, 12, 4, 2));
+        let fill = Fill('x', None);
+        let view = ScrollView::new(Size::new(4, 30)).child(Rect::new(0, 0, 4, 30), &fill);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(5, 5, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 5, 5), &mut frame, &mut state);
+        assert_eq!(state.offset(), (0, 9), "pending reveal applied on render");
+
+        state.scroll_into_view(Rect::new(0, 10, 4, 1));
+        assert_eq!(state.offset(), (0, 9), "already visible");
+        state.scroll_into_view(Rect::new(0, 2, 4, 1));
+        assert_eq!(state.offset(), (0, 2));
+    }
+
+    #[test]
+    fn keyboard_and_wheel_scrolling() {
+        let fill = Fill('x', None);
+        let view = ScrollView::new(Size::new(4, 30)).child(Rect::new(0, 0, 4, 30), &fill);
+        let mut state = ScrollViewState::new();
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(5, 5, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 5, 5), &mut frame, &mut state);
+
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::Down)));
+        assert_eq!(state.offset(), (0, 1));
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::PageDown)));
+        assert_eq!(state.offset(), (0, 5));
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::End)));
+        assert_eq!(state.offset(), (0, 25));
+        assert!(!state.handle_key(&KeyEvent::new(KeyCode::Right)));
+
+        let id = HitId::new(1);
+        let wheel = MouseEvent::new(MouseEventKind::ScrollUp, 1, 1);
+        assert_eq!(state.handle_mouse(&wheel, None, id), MouseResult::Scrolled);
+        assert_eq!(state.offset(), (0, 22));
+        let outside = MouseEvent::new(MouseEventKind::ScrollUp, 9, 9);
+        assert_eq!(state.handle_mouse(&outside, None, id), MouseResult::Ignored);
+
+        // Home and End only move vertically, keeping the horizontal offset.
+        let wide = ScrollView::new(Size::new(20, 30)).child(Rect::new(0, 0, 20, 30), &fill);
+        StatefulWidget::render(&wide, Rect::new(0, 0, 5, 5), &mut frame, &mut state);
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::Right)));
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::Home)));
+        assert_eq!(state.offset(), (1, 0));
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::End)));
+        assert_eq!(state.offset().0, 1);
+    }
+
+    #[test]
+    fn scrollbar_click_scrolls_view() {
+        let fill = Fill('x', None);
+        let id = HitId::new(1);
+        let view = ScrollView::new(Size::new(4, 40))
+            .child(Rect::new(0, 0, 4, 40), &fill)
+            .hit_id(id);
+        let mut state = ScrollViewState::new();
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::with_hit_grid(5, 10, &mut pool);
+        StatefulWidget::render(&view, Rect::new(0, 0, 5, 10), &mut frame, &mut state);
+
+        let click = MouseEvent::new(MouseEventKind::Down(MouseButton::Left), 4, 9);
+        let hit = frame.hit_test(4, 9);
+        assert_eq!(hit.map(|h| h.1), Some(HitRegion::Scrollbar));
+        assert_eq!(state.handle_mouse(&click, hit, id), MouseResult::Scrolled);
+        assert_eq!(state.offset(), (0, 30));
+    }
+
+    #[test]
+    fn persistence_round_trip() {
+        let mut state = ScrollViewState::new().with_persistence_id("settings");
+        state.set_offset(3, 7);
+        assert_eq!(state.state_key(), StateKey::new("ScrollView", "settings"));
+
+        let saved = state.save_state();
+        let mut restored = ScrollViewState::new();
+        restored.restore_state(saved);
+        assert_eq!(restored.offset(), (3, 7));
+    }
+}
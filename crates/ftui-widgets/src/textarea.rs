@@ -2,6 +2,8 @@
 //!
 //! [`TextArea`] wraps [`Editor`] for text manipulation and
 //! provides Frame-based rendering with viewport scrolling and cursor display.
+//! [`TextArea::from_source`] instead shows a shared [`LineSource`] read-only,
+//! reading only the visible lines on each render.
 //!
 //! # Example
 //! ```
@@ -12,15 +14,19 @@
 //! assert_eq!(ta.line_count(), 2);
 //! ```
 
+use std::sync::Arc;
+
 use ftui_core::event::{Event, KeyCode, KeyEvent, KeyEventKind, Modifiers};
 use ftui_core::geometry::Rect;
 use ftui_render::frame::Frame;
 use ftui_style::Style;
 use ftui_text::editor::{Editor, Selection};
 use ftui_text::wrap::display_width;
-use ftui_text::{CursorNavigator, CursorPosition};
+use ftui_text::{CursorNavigator, CursorPosition, LineSource};
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::log_viewer::SharedSource;
+use crate::vim::{VimKeymap, VimMode, VimOutcome};
 use crate::{StatefulWidget, Widget, apply_style, clear_text_area, draw_text_span};
 
 /// Multi-line text editor widget.
@@ -39,6 +45,12 @@ pub struct TextArea {
     cursor_line_style: Option<Style>,
     /// Selection highlight style.
     selection_style: Style,
+    /// Style of the cells under secondary cursors.
+    secondary_cursor_style: Style,
+    /// Byte ranges of search matches to highlight.
+    search_highlights: Vec<(usize, usize)>,
+    /// Search match highlight style.
+    search_match_style: Style,
     /// Placeholder style.
     placeholder_style: Style,
     /// Line number style.
@@ -62,6 +74,10 @@ pub struct TextArea {
     last_viewport_height: std::cell::Cell<usize>,
     /// Last viewport width for visibility checks.
     last_viewport_width: std::cell::Cell<usize>,
+    /// Vim-style modal keymap, when enabled.
+    vim: Option<VimKeymap>,
+    /// Read-only lines shown instead of the editor (None = editable).
+    source: Option<SharedSource>,
 }
 
 impl Default for TextArea {
@@ -99,6 +115,9 @@ impl TextArea {
             style: Style::default(),
             cursor_line_style: None,
             selection_style: Style::new().reverse(),
+            secondary_cursor_style: Style::new().reverse(),
+            search_highlights: Vec::new(),
+            search_match_style: Style::new().bold().underline(),
             placeholder_style: Style::new().dim(),
             line_number_style: Style::new().dim(),
             soft_wrap: false,
@@ -107,9 +126,61 @@ impl TextArea {
             scroll_left: std::cell::Cell::new(0),
             last_viewport_height: std::cell::Cell::new(0),
             last_viewport_width: std::cell::Cell::new(0),
+            vim: None,
+            source: None,
+        }
+    }
+
+    /// Create a view-only text area over an external line source.
+    ///
+    /// Only the visible lines are read, on every render, so a file that is
+    /// still being indexed can be shown as it grows. Keys scroll the view
+    /// rather than edit it, and no cursor, selection or search highlight is
+    /// drawn. The editor stays empty; its editing methods do not affect
+    /// what is shown.
+    #[must_use]
+    pub fn from_source(source: Arc<dyn LineSource + Send + Sync>) -> Self {
+        Self {
+            scroll_anchor: std::cell::Cell::new((0, 0)),
+            source: Some(SharedSource(source)),
+            ..Self::new()
+        }
+    }
+
+    /// Whether this text area shows a line source instead of the editor.
+    #[must_use]
+    pub fn is_view_only(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// First line shown at the top of the viewport.
+    #[must_use]
+    pub fn scroll_top(&self) -> usize {
+        match self.scroll_anchor.get().0 {
+            usize::MAX => 0,
+            line => line,
+        }
+    }
+
+    /// Scroll a view-only text area so `line` is at the top.
+    ///
+    /// The line is clamped so the last page stays full. Does nothing for
+    /// editable text areas, which scroll to follow the cursor.
+    pub fn scroll_to(&mut self, line: usize) {
+        if self.source.is_some() {
+            self.scroll_anchor.set((line.min(self.max_view_top()), 0));
         }
     }
 
+    fn max_view_top(&self) -> usize {
+        let page = if self.soft_wrap {
+            1
+        } else {
+            self.last_viewport_height.get().max(1)
+        };
+        self.line_count().saturating_sub(page)
+    }
+
     // ── Event Handling ─────────────────────────────────────────────
 
     /// Handle a terminal event.
@@ -117,12 +188,18 @@ impl TextArea {
     /// Returns `true` if the state changed.
     pub fn handle_event(&mut self, event: &Event) -> bool {
         match event {
+            Event::Key(key)
+                if self.source.is_some()
+                    && (key.kind == KeyEventKind::Press || key.kind == KeyEventKind::Repeat) =>
+            {
+                self.handle_view_key(key)
+            }
             Event::Key(key)
                 if key.kind == KeyEventKind::Press || key.kind == KeyEventKind::Repeat =>
             {
                 self.handle_key(key)
             }
-            Event::Paste(paste) => {
+            Event::Paste(paste) if self.source.is_none() => {
                 self.insert_text(&paste.text);
                 true
             }
@@ -130,10 +207,35 @@ impl TextArea {
         }
     }
 
+    fn handle_view_key(&mut self, key: &KeyEvent) -> bool {
+        let top = self.scroll_top();
+        let left = self.scroll_left.get();
+        let page = self.last_viewport_height.get().max(1);
+        match key.code {
+            KeyCode::Up => self.scroll_to(top.saturating_sub(1)),
+            KeyCode::Down => self.scroll_to(top.saturating_add(1)),
+            KeyCode::PageUp => self.scroll_to(top.saturating_sub(page)),
+            KeyCode::PageDown => self.scroll_to(top.saturating_add(page)),
+            KeyCode::Home => self.scroll_to(0),
+            KeyCode::End => self.scroll_to(usize::MAX),
+            KeyCode::Left if !self.soft_wrap => self.scroll_left.set(left.saturating_sub(1)),
+            KeyCode::Right if !self.soft_wrap => self.scroll_left.set(left.saturating_add(1)),
+            _ => return false,
+        }
+        (self.scroll_top(), self.scroll_left.get()) != (top, left)
+    }
+
     fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if let Some(vim) = &mut self.vim
+            && vim.handle_key(key, &mut self.editor) == VimOutcome::Consumed
+        {
+            self.ensure_cursor_visible();
+            return true;
+        }
+
         let ctrl = key.modifiers.contains(Modifiers::CTRL);
         let shift = key.modifiers.contains(Modifiers::SHIFT);
-        let _alt = key.modifiers.contains(Modifiers::ALT);
+        let alt = key.modifiers.contains(Modifiers::ALT);
 
         match key.code {
             KeyCode::Char(c) if !ctrl => {
@@ -184,6 +286,14 @@ impl TextArea {
                 }
                 true
             }
+            KeyCode::Up if ctrl && alt => {
+                self.add_cursor_above();
+                true
+            }
+            KeyCode::Down if ctrl && alt => {
+                self.add_cursor_below();
+                true
+            }
             KeyCode::Up => {
                 if shift {
                     self.select_up();
@@ -244,6 +354,15 @@ impl TextArea {
                 self.select_all();
                 true
             }
+            // Ctrl+D: Add a cursor at the next occurrence of the selection
+            KeyCode::Char('d') if ctrl => {
+                self.add_next_occurrence();
+                true
+            }
+            KeyCode::Escape if self.editor.has_multiple_cursors() => {
+                self.clear_extra_cursors();
+                true
+            }
             // Ctrl+K: Delete to end of line (common emacs/shell binding)
             KeyCode::Char('k') if ctrl => {
                 self.delete_to_end_of_line();
@@ -315,6 +434,20 @@ impl TextArea {
         self
     }
 
+    /// Set the style of secondary cursors (builder).
+    #[must_use]
+    pub fn with_secondary_cursor_style(mut self, style: Style) -> Self {
+        self.secondary_cursor_style = style;
+        self
+    }
+
+    /// Set the search match highlight style (builder).
+    #[must_use]
+    pub fn with_search_match_style(mut self, style: Style) -> Self {
+        self.search_match_style = style;
+        self
+    }
+
     /// Enable soft wrapping (builder).
     #[must_use]
     pub fn with_soft_wrap(mut self, wrap: bool) -> Self {
@@ -322,6 +455,15 @@ impl TextArea {
         self
     }
 
+    /// Enable Vim-style modal editing, starting in Normal mode (builder).
+    ///
+    /// See [`crate::vim`] for the supported commands.
+    #[must_use]
+    pub fn with_vim(mut self, enabled: bool) -> Self {
+        self.set_vim_enabled(enabled);
+        self
+    }
+
     /// Set maximum height in lines (builder). 0 = fill available area.
     #[must_use]
     pub fn with_max_height(mut self, max: usize) -> Self {
@@ -344,10 +486,13 @@ impl TextArea {
         self.scroll_left.set(0);
     }
 
-    /// Number of lines.
+    /// Number of lines (of the source, for view-only text areas).
     #[must_use]
     pub fn line_count(&self) -> usize {
-        self.editor.line_count()
+        match &self.source {
+            Some(source) => source.0.line_count(),
+            None => self.editor.line_count(),
+        }
     }
 
     /// Current cursor position.
@@ -482,7 +627,7 @@ impl TextArea {
 
     /// Move cursor up.
     pub fn move_up(&mut self) {
-        if self.soft_wrap {
+        if self.soft_wrap && !self.editor.has_multiple_cursors() {
             self.move_cursor_visual_up(1, false);
         } else {
             self.editor.move_up();
@@ -492,7 +637,7 @@ impl TextArea {
 
     /// Move cursor down.
     pub fn move_down(&mut self) {
-        if self.soft_wrap {
+        if self.soft_wrap && !self.editor.has_multiple_cursors() {
             self.move_cursor_visual_down(1, false);
         } else {
             self.editor.move_down();
@@ -751,7 +896,7 @@ impl TextArea {
 
     /// Extend selection up.
     pub fn select_up(&mut self) {
-        if self.soft_wrap {
+        if self.soft_wrap && !self.editor.has_multiple_cursors() {
             self.move_cursor_visual_up(1, true);
         } else {
             self.editor.select_up();
@@ -761,7 +906,7 @@ impl TextArea {
 
     /// Extend selection down.
     pub fn select_down(&mut self) {
-        if self.soft_wrap {
+        if self.soft_wrap && !self.editor.has_multiple_cursors() {
             self.move_cursor_visual_down(1, true);
         } else {
             self.editor.select_down();
@@ -779,6 +924,82 @@ impl TextArea {
         self.editor.clear_selection();
     }
 
+    // ── Search highlights ──────────────────────────────────────────
+
+    /// Highlight search matches given as byte ranges into the text, e.g.
+    /// from `Finder::highlight_ranges`.
+    ///
+    /// Ranges are not adjusted for later edits; refresh them after the text
+    /// changes.
+    pub fn set_search_highlights(&mut self, ranges: impl IntoIterator<Item = (usize, usize)>) {
+        self.search_highlights = ranges.into_iter().collect();
+    }
+
+    /// Remove all search match highlights.
+    pub fn clear_search_highlights(&mut self) {
+        self.search_highlights.clear();
+    }
+
+    // ── Multiple cursors ───────────────────────────────────────────
+
+    /// Add a cursor on the line above the topmost cursor (Ctrl+Alt+Up).
+    pub fn add_cursor_above(&mut self) {
+        self.editor.add_cursor_above();
+        self.ensure_cursor_visible();
+    }
+
+    /// Add a cursor on the line below the bottommost cursor (Ctrl+Alt+Down).
+    pub fn add_cursor_below(&mut self) {
+        self.editor.add_cursor_below();
+        self.ensure_cursor_visible();
+    }
+
+    /// Select the next occurrence of the selection with a new cursor (Ctrl+D).
+    pub fn add_next_occurrence(&mut self) {
+        self.editor.add_next_occurrence();
+        self.ensure_cursor_visible();
+    }
+
+    /// Column (box) selection between two positions.
+    pub fn select_block(&mut self, anchor: CursorPosition, head: CursorPosition) {
+        self.editor.select_block(anchor, head);
+        self.ensure_cursor_visible();
+    }
+
+    /// Drop secondary cursors (Escape).
+    pub fn clear_extra_cursors(&mut self) {
+        self.editor.clear_extra_cursors();
+    }
+
+    // ── Modal editing ──────────────────────────────────────────────
+
+    /// Turn Vim-style modal editing on or off.
+    pub fn set_vim_enabled(&mut self, enabled: bool) {
+        if enabled {
+            self.vim.get_or_insert_with(VimKeymap::new);
+        } else if let Some(mut vim) = self.vim.take() {
+            vim.reset(&mut self.editor);
+        }
+    }
+
+    /// Current Vim mode, for display in a status line. `None` when modal
+    /// editing is off.
+    #[must_use]
+    pub fn vim_mode(&self) -> Option<VimMode> {
+        self.vim.as_ref().map(VimKeymap::mode)
+    }
+
+    /// The Vim keymap, when modal editing is on.
+    #[must_use]
+    pub fn vim(&self) -> Option<&VimKeymap> {
+        self.vim.as_ref()
+    }
+
+    /// Mutable access to the Vim keymap, e.g. to take `:w` commands.
+    pub fn vim_mut(&mut self) -> Option<&mut VimKeymap> {
+        self.vim.as_mut()
+    }
+
     // ── Viewport management ────────────────────────────────────────
 
     /// Page up (move viewport and cursor up by viewport height).
@@ -1256,6 +1477,11 @@ impl Widget for TextArea {
 
         self.last_viewport_width.set(text_area_w);
 
+        if let Some(source) = &self.source {
+            self.render_source(source.0.as_ref(), area, frame, base_style);
+            return;
+        }
+
         let cursor = self.editor.cursor();
 
         self.ensure_cursor_visible();
@@ -1267,15 +1493,33 @@ impl Widget for TextArea {
         let rope = self.editor.rope();
         let nav = CursorNavigator::new(rope);
 
-        // Selection byte range for highlighting
-        let sel_range = self.editor.selection().and_then(|sel| {
-            if sel.is_empty() {
-                None
-            } else {
-                let (a, b) = sel.byte_range(&nav);
-                Some((a, b))
-            }
-        });
+        // Selection byte ranges (one per cursor) for highlighting
+        let carets = self.editor.carets();
+        let sel_ranges: Vec<(usize, usize)> = carets
+            .iter()
+            .filter_map(|caret| caret.selection.filter(|sel| !sel.is_empty()))
+            .map(|sel| sel.byte_range(&nav))
+            .collect();
+        let in_selection =
+            |offset: usize| sel_ranges.iter().any(|&(a, b)| offset >= a && offset < b);
+        let in_search_match = |offset: usize| {
+            self.search_highlights
+                .iter()
+                .any(|&(a, b)| offset >= a && offset < b)
+        };
+
+        // Secondary cursors are drawn as styled cells; the terminal cursor
+        // marks the primary one.
+        let primary_byte = nav.to_byte_index(cursor);
+        let extra_cursor_bytes: Vec<usize> = if self.focused && deg.apply_styling() {
+            carets
+                .iter()
+                .map(|caret| nav.to_byte_index(caret.cursor))
+                .filter(|&byte| byte != primary_byte)
+                .collect()
+        } else {
+            Vec::new()
+        };
 
         // Show placeholder if empty
         if self.editor.is_empty() && !self.placeholder.is_empty() {
@@ -1380,13 +1624,15 @@ impl Widget for TextArea {
 
                         // Determine style (selection highlight)
                         let mut g_style = base_style;
-                        if let Some((sel_start, sel_end)) = sel_range
-                            && grapheme_byte_offset >= sel_start
-                            && grapheme_byte_offset < sel_end
-                            && deg.apply_styling()
-                        {
+                        if in_search_match(grapheme_byte_offset) && deg.apply_styling() {
+                            g_style = g_style.merge(&self.search_match_style);
+                        }
+                        if in_selection(grapheme_byte_offset) && deg.apply_styling() {
                             g_style = g_style.merge(&self.selection_style);
                         }
+                        if extra_cursor_bytes.contains(&grapheme_byte_offset) {
+                            g_style = g_style.merge(&self.secondary_cursor_style);
+                        }
 
                         if g_width > 0 {
                             draw_text_span(frame, px, current_y, g, g_style, area.right());
@@ -1396,6 +1642,16 @@ impl Widget for TextArea {
                         grapheme_byte_offset += g_byte_len;
                     }
 
+                    // Secondary cursor past the end of the line
+                    if slice_idx + 1 == slices.len()
+                        && visual_x < text_area_w
+                        && extra_cursor_bytes.contains(&(line_start_byte + line_text.len()))
+                    {
+                        let px = text_area_x + visual_x as u16;
+                        let style = base_style.merge(&self.secondary_cursor_style);
+                        draw_text_span(frame, px, current_y, " ", style, area.right());
+                    }
+
                     // Set cursor position if focused
                     if self.focused && line_idx == cursor.line && slice_idx == cursor_wrap_idx {
                         let cursor_screen_x = text_area_x.saturating_add(cursor_col_in_wrap as u16);
@@ -1463,13 +1719,15 @@ impl Widget for TextArea {
 
                 // Determine style (selection highlight)
                 let mut g_style = base_style;
-                if let Some((sel_start, sel_end)) = sel_range
-                    && grapheme_byte_offset >= sel_start
-                    && grapheme_byte_offset < sel_end
-                    && deg.apply_styling()
-                {
+                if in_search_match(grapheme_byte_offset) && deg.apply_styling() {
+                    g_style = g_style.merge(&self.search_match_style);
+                }
+                if in_selection(grapheme_byte_offset) && deg.apply_styling() {
                     g_style = g_style.merge(&self.selection_style);
                 }
+                if extra_cursor_bytes.contains(&grapheme_byte_offset) {
+                    g_style = g_style.merge(&self.secondary_cursor_style);
+                }
 
                 // Skip graphemes before horizontal scroll
                 if visual_x + g_width <= scroll_left {
@@ -1514,6 +1772,16 @@ impl Widget for TextArea {
                 visual_x += g_width;
                 grapheme_byte_offset += g_byte_len;
             }
+
+            // Secondary cursor past the end of the line
+            if extra_cursor_bytes.contains(&(line_start_byte + line_text.len())) {
+                let end_col = display_width(line_text);
+                if end_col >= scroll_left && end_col - scroll_left < text_area_w {
+                    let px = text_area_x + (end_col - scroll_left) as u16;
+                    let style = base_style.merge(&self.secondary_cursor_style);
+                    draw_text_span(frame, px, y, " ", style, area.right());
+                }
+            }
         }
 
         // Set cursor position if focused
@@ -1535,6 +1803,74 @@ impl Widget for TextArea {
     }
 }
 
+impl TextArea {
+    /// Draw the visible lines of a view-only text area.
+    fn render_source(&self, source: &dyn LineSource, area: Rect, frame: &mut Frame, style: Style) {
+        let deg = frame.buffer.degradation;
+        let gutter_w = self.gutter_width();
+        let text_area_x = area.x.saturating_add(gutter_w);
+        let text_area_w = area.width.saturating_sub(gutter_w) as usize;
+        let number_style = if deg.apply_styling() {
+            self.line_number_style
+        } else {
+            Style::default()
+        };
+        let scroll_left = if self.soft_wrap {
+            self.scroll_left.set(0);
+            0
+        } else {
+            self.scroll_left.get()
+        };
+
+        let mut y = area.y;
+        let mut line_idx = self.scroll_top().min(self.max_view_top());
+        while y < area.bottom() && line_idx < source.line_count() {
+            let line_text = source.line_text(line_idx).unwrap_or_default();
+            let slices = if self.soft_wrap {
+                Self::wrap_line_slices(&line_text, text_area_w)
+            } else {
+                Self::wrap_line_slices(&line_text, 0)
+            };
+            for (slice_idx, slice) in slices.iter().enumerate() {
+                if y >= area.bottom() {
+                    break;
+                }
+                if self.show_line_numbers && slice_idx == 0 {
+                    let num_str =
+                        format!("{:>width$} ", line_idx + 1, width = (gutter_w - 2) as usize);
+                    draw_text_span(frame, area.x, y, &num_str, number_style, text_area_x);
+                }
+
+                let mut visual_x: usize = 0;
+                for g in slice.text.graphemes(true) {
+                    let g_width = display_width(g);
+                    let start = visual_x;
+                    visual_x += g_width;
+                    if visual_x <= scroll_left {
+                        continue;
+                    }
+                    if start < scroll_left {
+                        // Blank the visible part of a grapheme cut by the left edge.
+                        let blank = " ".repeat(visual_x - scroll_left);
+                        draw_text_span(frame, text_area_x, y, &blank, style, area.right());
+                        continue;
+                    }
+                    let screen_x = start - scroll_left;
+                    if screen_x >= text_area_w {
+                        break;
+                    }
+                    if g_width > 0 {
+                        let px = text_area_x + screen_x as u16;
+                        draw_text_span(frame, px, y, g, style, area.right());
+                    }
+                }
+                y += 1;
+            }
+            line_idx += 1;
+        }
+    }
+}
+
 impl StatefulWidget for TextArea {
     type State = TextAreaState;
 
@@ -1943,6 +2279,94 @@ mod tests {
         assert_eq!(cell.content.as_char(), Some('a'));
     }
 
+    #[test]
+    fn render_draws_secondary_cursors() {
+        use ftui_render::cell::StyleFlags;
+        use ftui_render::grapheme_pool::GraphemePool;
+
+        let mut ta = TextArea::new().with_text("ab\ncdef").with_focus(true);
+        ta.set_cursor_position(CursorPosition::new(1, 1, 1));
+        ta.add_cursor_above();
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(10, 3, &mut pool);
+        Widget::render(&ta, Rect::new(0, 0, 10, 3), &mut frame);
+
+        let reversed = |x, y| {
+            frame
+                .buffer
+                .get(x, y)
+                .unwrap()
+                .attrs
+                .flags()
+                .contains(StyleFlags::REVERSE)
+        };
+        assert!(reversed(1, 0));
+        assert!(!reversed(1, 1));
+        assert_eq!(frame.cursor_position, Some((1, 1)));
+    }
+
+    #[test]
+    fn render_highlights_search_matches() {
+        use ftui_render::cell::StyleFlags;
+        use ftui_render::grapheme_pool::GraphemePool;
+
+        let mut ta = TextArea::new().with_text("one two one");
+        ta.set_search_highlights([(0, 3), (8, 11)]);
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(12, 1, &mut pool);
+        Widget::render(&ta, Rect::new(0, 0, 12, 1), &mut frame);
+
+        let underlined = |x| {
+            frame
+                .buffer
+                .get(x, 0)
+                .unwrap()
+                .attrs
+                .flags()
+                .contains(StyleFlags::UNDERLINE)
+        };
+        assert!(underlined(0));
+        assert!(!underlined(4));
+        assert!(underlined(10));
+    }
+
+    #[test]
+    fn multi_cursor_keys_edit_every_line() {
+        let mut ta = TextArea::new().with_text("x\ny").with_focus(true);
+        ta.move_to_document_start();
+        ta.handle_event(&Event::Key(
+            KeyEvent::new(KeyCode::Down).with_modifiers(Modifiers::CTRL | Modifiers::ALT),
+        ));
+        ta.insert_char('-');
+        assert_eq!(ta.text(), "-x\n-y");
+        ta.handle_event(&Event::Key(KeyEvent::new(KeyCode::Escape)));
+        assert!(!ta.editor().has_multiple_cursors());
+    }
+
+    #[test]
+    fn vim_mode_routes_keys_through_keymap() {
+        let mut ta = TextArea::new().with_text("one two").with_vim(true);
+        let press = |ta: &mut TextArea, code| ta.handle_event(&Event::Key(KeyEvent::new(code)));
+        assert_eq!(ta.vim_mode(), Some(VimMode::Normal));
+        press(&mut ta, KeyCode::Char('w'));
+        press(&mut ta, KeyCode::Char('i'));
+        assert_eq!(ta.vim_mode(), Some(VimMode::Insert));
+        press(&mut ta, KeyCode::Char('x'));
+        // Keys the keymap does not use fall back to the regular bindings.
+        press(&mut ta, KeyCode::End);
+        press(&mut ta, KeyCode::Char('!'));
+        press(&mut ta, KeyCode::Escape);
+        assert_eq!(ta.text(), "one xtwo!");
+        assert_eq!(ta.vim_mode(), Some(VimMode::Normal));
+        press(&mut ta, KeyCode::Char('u'));
+        assert_eq!(ta.text(), "one two");
+
+        ta.set_vim_enabled(false);
+        assert_eq!(ta.vim_mode(), None);
+        press(&mut ta, KeyCode::Char('u'));
+        assert!(ta.text().contains('u'));
+    }
+
     #[test]
     fn render_shorter_text_clears_stale_suffix_and_extra_lines() {
         use ftui_render::grapheme_pool::GraphemePool;
@@ -1979,6 +2403,66 @@ mod tests {
         assert_eq!(cell.content.as_char(), Some('1'));
     }
 
+    #[test]
+    fn from_source_renders_and_scrolls_without_editing() {
+        use ftui_core::event::PasteEvent;
+        use ftui_render::grapheme_pool::GraphemePool;
+        use ftui_text::Rope;
+
+        let text: Vec<String> = (0..100).map(|i| format!("line {i}")).collect();
+        let source = Arc::new(Rope::from(text.join("\n").as_str()));
+        let mut ta = TextArea::from_source(source.clone())
+            .with_line_numbers(true)
+            .with_focus(true);
+        assert!(ta.is_view_only());
+        assert_eq!(ta.line_count(), 100);
+
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(14, 3, &mut pool);
+        let area = Rect::new(0, 0, 14, 3);
+        Widget::render(&ta, area, &mut frame);
+        assert_eq!(raw_row_text(&frame, 0, 14), "  1  line 0   ");
+        assert_eq!(raw_row_text(&frame, 2, 14), "  3  line 2   ");
+        assert_eq!(frame.cursor_position, None);
+
+        assert!(ta.handle_event(&Event::Key(KeyEvent::new(KeyCode::PageDown))));
+        assert!(ta.handle_event(&Event::Key(KeyEvent::new(KeyCode::Down))));
+        assert_eq!(ta.scroll_top(), 4);
+        assert!(ta.handle_event(&Event::Key(KeyEvent::new(KeyCode::End))));
+        assert!(!ta.handle_event(&Event::Key(KeyEvent::new(KeyCode::Down))));
+        Widget::render(&ta, area, &mut frame);
+        assert_eq!(raw_row_text(&frame, 0, 14), " 98  line 97  ");
+        assert_eq!(raw_row_text(&frame, 2, 14), "100  line 99  ");
+
+        assert!(!ta.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char('x')))));
+        assert!(!ta.handle_event(&Event::Paste(PasteEvent::bracketed("y"))));
+        assert!(ta.is_empty());
+        assert_eq!(source.line_text(97).as_deref(), Some("line 97"));
+    }
+
+    #[test]
+    fn from_source_wraps_and_scrolls_horizontally() {
+        use ftui_render::grapheme_pool::GraphemePool;
+        use ftui_text::Rope;
+
+        let source = Arc::new(Rope::from("abcdefgh\nij"));
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(4, 3, &mut pool);
+        let area = Rect::new(0, 0, 4, 3);
+
+        let wrapped = TextArea::from_source(source.clone()).with_soft_wrap(true);
+        Widget::render(&wrapped, area, &mut frame);
+        assert_eq!(raw_row_text(&frame, 0, 4), "abcd");
+        assert_eq!(raw_row_text(&frame, 1, 4), "efgh");
+        assert_eq!(raw_row_text(&frame, 2, 4), "ij  ");
+
+        let mut ta = TextArea::from_source(source);
+        assert!(ta.handle_event(&Event::Key(KeyEvent::new(KeyCode::Right))));
+        Widget::render(&ta, area, &mut frame);
+        assert_eq!(raw_row_text(&frame, 0, 4), "bcde");
+        assert_eq!(raw_row_text(&frame, 1, 4), "j   ");
+    }
+
     #[test]
     fn stateful_render_updates_viewport_state() {
         use ftui_render::grapheme_pool::GraphemePool;
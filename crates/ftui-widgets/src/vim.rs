@@ -0,0 +1,1911 @@
+#![forbid(unsafe_code)]
+
+//! Vim-style modal editing for [`TextArea`](crate::textarea::TextArea) and
+//! [`TextInput`](crate::input::TextInput).
+//!
+//! [`VimKeymap`] sits in front of a widget's regular key handling and drives
+//! an [`Editor`] through its public primitives. It supports:
+//!
+//! - Normal, Insert, Visual, Visual-Line and Command-line modes ([`VimMode`])
+//! - counts (`3w`, `2dd`, `d3w`)
+//! - operators `d`, `c`, `y` with the motions
+//!   `h j k l w b e W B E 0 ^ $ gg G f t F T ; ,`
+//! - text objects `iw aw iW aW i" a" i' a' i( a( i[ a[ i{ a{ i< a< ip ap`
+//! - registers: `"ayw` yanks into `a`, `"Ayw` appends to it, `"_` discards
+//!   and `"0` holds the last yank
+//! - `.` to repeat the last Normal-mode change, `u` and `Ctrl+R`
+//! - `:` commands: a line number jumps to that line, while `:w`, `:q`, `:q!`,
+//!   `:wq` and `:x` are handed to the application through
+//!   [`VimKeymap::take_command`]
+//!
+//! Multi-key input (counts, `"a`, operators awaiting a motion, `gg`, `f{char}`)
+//! is buffered by a
+//! [`KeySequenceInterpreter`](ftui_core::key_sequence::KeySequenceInterpreter)
+//! driven with the Vim command grammar, and shown by
+//! [`VimKeymap::pending_keys`]. As in Vim, pending keys never time out.
+//! Hosts that run their own interpreter in front of the keymap (for example
+//! to detect Esc Esc) pass its output to
+//! [`VimKeymap::handle_sequence_action`], which replays each key in order.
+//!
+//! Edits made by one command, including the text typed in the Insert mode it
+//! opens, are grouped into a single undo step.
+//!
+//! # Example
+//! ```
+//! use ftui_core::event::{KeyCode, KeyEvent};
+//! use ftui_text::editor::Editor;
+//! use ftui_widgets::vim::{VimKeymap, VimMode};
+//!
+//! let mut ed = Editor::with_text("one two three");
+//! ed.move_to_document_start();
+//! let mut vim = VimKeymap::new();
+//! for c in "dw".chars() {
+//!     vim.handle_key(&KeyEvent::new(KeyCode::Char(c)), &mut ed);
+//! }
+//! assert_eq!(ed.text(), "two three");
+//! assert_eq!(vim.mode(), VimMode::Normal);
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use ftui_core::event::{KeyCode, KeyEvent, Modifiers};
+use ftui_core::key_sequence::{KeySequenceAction, KeySequenceInterpreter, SequenceMatch};
+use ftui_text::CursorNavigator;
+use ftui_text::editor::Editor;
+use unicode_segmentation::UnicodeSegmentation;
+use web_time::Instant;
+
+/// The active editing mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VimMode {
+    /// Keys are commands and motions.
+    #[default]
+    Normal,
+    /// Keys insert text.
+    Insert,
+    /// Character-wise selection.
+    Visual,
+    /// Line-wise selection.
+    VisualLine,
+    /// Typing a `:` command.
+    Command,
+}
+
+impl VimMode {
+    /// Label for a status line, e.g. `"NORMAL"`.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Normal => "NORMAL",
+            Self::Insert => "INSERT",
+            Self::Visual => "VISUAL",
+            Self::VisualLine => "VISUAL LINE",
+            Self::Command => "COMMAND",
+        }
+    }
+}
+
+/// A `:` command the keymap cannot carry out on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VimCommand {
+    /// `:w`
+    Write,
+    /// `:q`, or `:q!` when `force` is set.
+    Quit {
+        /// Whether unsaved changes should be discarded.
+        force: bool,
+    },
+    /// `:wq` or `:x`
+    WriteQuit,
+    /// Any other command line, without the leading `:`.
+    Other(String),
+}
+
+/// Contents of a register.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VimRegister {
+    /// Stored text. Line-wise registers end with a newline.
+    pub text: String,
+    /// Whether the text was yanked or deleted as whole lines.
+    pub linewise: bool,
+}
+
+/// Keys [`VimKeymap::handle_sequence_action`] left for the widget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VimSequenceOutcome {
+    /// Every key was handled by the keymap.
+    Consumed,
+    /// These keys, in order, should go to the widget's regular handling.
+    Passthrough(Vec<KeyEvent>),
+}
+
+/// Whether [`VimKeymap::handle_key`] used a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimOutcome {
+    /// The key was handled by the keymap.
+    Consumed,
+    /// The key should go to the widget's regular handling.
+    Passthrough,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordStart { big: bool },
+    WordBack { big: bool },
+    WordEnd { big: bool },
+    LineStart,
+    FirstNonBlank,
+    LineEnd,
+    Top,
+    Bottom,
+    Find { kind: char, ch: char },
+    RepeatFind { reverse: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Motion(Motion),
+    Object { around: bool, kind: char },
+    Line,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Move(Motion),
+    Operate { op: Operator, target: Target },
+    Object { around: bool, kind: char },
+    Replace(char),
+    Key(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Command {
+    register: Option<char>,
+    count: Option<usize>,
+    action: Action,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Parse<T> {
+    Incomplete,
+    Invalid,
+    Done(T),
+}
+
+/// Modal key handling state for one editing widget.
+#[derive(Debug, Clone, Default)]
+pub struct VimKeymap {
+    mode: VimMode,
+    single_line: bool,
+    /// Buffers keys typed towards a Normal/Visual command that is not
+    /// complete yet.
+    sequence: KeySequenceInterpreter,
+    registers: HashMap<char, VimRegister>,
+    command_line: String,
+    command: Option<VimCommand>,
+    /// Visual mode selection ends (byte offsets, head inclusive).
+    visual_anchor: usize,
+    visual_head: usize,
+    last_find: Option<(char, char)>,
+    /// Undo depth when the current command began.
+    checkpoint: usize,
+    /// Keys of the command in progress, for dot-repeat.
+    recording: Vec<KeyEvent>,
+    last_change: Vec<KeyEvent>,
+    /// Whether leaving Insert mode completes a repeatable change.
+    insert_repeatable: bool,
+    replaying: bool,
+}
+
+impl VimKeymap {
+    /// Create a keymap in Normal mode.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict editing to a single line (builder).
+    ///
+    /// `j`, `k`, `o` and `O` do nothing and Enter in Insert mode passes
+    /// through to the widget.
+    #[must_use]
+    pub fn with_single_line(mut self, single_line: bool) -> Self {
+        self.single_line = single_line;
+        self
+    }
+
+    /// Current mode.
+    #[must_use]
+    pub fn mode(&self) -> VimMode {
+        self.mode
+    }
+
+    /// Keys of an incomplete command, e.g. `"2d"`.
+    #[must_use]
+    pub fn pending_keys(&self) -> String {
+        self.pending_chars().into_iter().collect()
+    }
+
+    fn pending_chars(&self) -> Vec<char> {
+        command_chars(self.sequence.pending_keys())
+    }
+
+    /// The `:` command being typed, when in Command mode.
+    #[must_use]
+    pub fn command_line(&self) -> Option<&str> {
+        (self.mode == VimMode::Command).then_some(self.command_line.as_str())
+    }
+
+    /// Take the last `:` command left for the application.
+    pub fn take_command(&mut self) -> Option<VimCommand> {
+        self.command.take()
+    }
+
+    /// Contents of a register (`'"'` is the unnamed register).
+    #[must_use]
+    pub fn register(&self, name: char) -> Option<&VimRegister> {
+        self.registers.get(&name.to_ascii_lowercase())
+    }
+
+    /// Set a register, e.g. to seed `'"'` from the system clipboard.
+    pub fn set_register(&mut self, name: char, register: VimRegister) {
+        self.registers.insert(name.to_ascii_lowercase(), register);
+    }
+
+    /// Return to Normal mode, dropping any pending keys and selection.
+    pub fn reset(&mut self, editor: &mut Editor) {
+        self.sequence.reset();
+        self.command_line.clear();
+        if matches!(self.mode, VimMode::Visual | VimMode::VisualLine) {
+            editor.clear_selection();
+        }
+        self.mode = VimMode::Normal;
+    }
+
+    /// Handle a key press against `editor`.
+    pub fn handle_key(&mut self, key: &KeyEvent, editor: &mut Editor) -> VimOutcome {
+        if !self.replaying {
+            if self.mode == VimMode::Normal && self.sequence.pending_keys().is_empty() {
+                self.recording.clear();
+            }
+            self.recording.push(*key);
+        }
+        match self.mode {
+            VimMode::Insert => self.handle_insert(key, editor),
+            VimMode::Command => self.handle_command_line(key, editor),
+            VimMode::Normal | VimMode::Visual | VimMode::VisualLine => {
+                self.handle_normal(key, editor)
+            }
+        }
+    }
+
+    /// Handle the output of a
+    /// [`KeySequenceInterpreter`](ftui_core::key_sequence::KeySequenceInterpreter).
+    ///
+    /// Emitted keys are handled as if pressed individually, so a detected
+    /// Esc Esc is two Escape presses. Keys the keymap passes through are
+    /// returned in order. [`Pending`](KeySequenceAction::Pending) is
+    /// consumed: the interpreter still holds the key and will emit it.
+    pub fn handle_sequence_action(
+        &mut self,
+        action: &KeySequenceAction,
+        editor: &mut Editor,
+    ) -> VimSequenceOutcome {
+        let keys = match action {
+            KeySequenceAction::Emit(key) => std::slice::from_ref(key),
+            KeySequenceAction::EmitSequence { keys, .. } => keys.as_slice(),
+            KeySequenceAction::Pending => return VimSequenceOutcome::Consumed,
+        };
+        let mut passed = Vec::new();
+        for key in keys {
+            if self.handle_key(key, editor) == VimOutcome::Passthrough {
+                passed.push(*key);
+            }
+        }
+        if passed.is_empty() {
+            VimSequenceOutcome::Consumed
+        } else {
+            VimSequenceOutcome::Passthrough(passed)
+        }
+    }
+
+    // ── Insert and Command-line modes ──────────────────────────────
+
+    fn handle_insert(&mut self, key: &KeyEvent, editor: &mut Editor) -> VimOutcome {
+        let plain = !key.modifiers.intersects(Modifiers::CTRL | Modifiers::ALT);
+        match key.code {
+            KeyCode::Escape => {
+                self.mode = VimMode::Normal;
+                editor.group_undo_since(self.checkpoint);
+                if self.insert_repeatable && !self.replaying {
+                    self.last_change = self.recording.clone();
+                }
+                self.insert_repeatable = false;
+                let text = editor.text();
+                let pos = cursor_byte(editor);
+                if pos > line_start(&text, pos) {
+                    set_cursor_byte(editor, prev_boundary(&text, pos));
+                }
+            }
+            KeyCode::Char(c) if plain => editor.insert_char(c),
+            KeyCode::Enter if plain && !self.single_line => editor.insert_newline(),
+            KeyCode::Backspace if plain => {
+                editor.delete_backward();
+            }
+            KeyCode::Delete if plain => {
+                editor.delete_forward();
+            }
+            _ => return VimOutcome::Passthrough,
+        }
+        VimOutcome::Consumed
+    }
+
+    fn handle_command_line(&mut self, key: &KeyEvent, editor: &mut Editor) -> VimOutcome {
+        match key.code {
+            KeyCode::Escape => {
+                self.command_line.clear();
+                self.mode = VimMode::Normal;
+            }
+            KeyCode::Enter => {
+                let line = std::mem::take(&mut self.command_line);
+                self.mode = VimMode::Normal;
+                self.run_command_line(line.trim(), editor);
+            }
+            KeyCode::Backspace => {
+                if self.command_line.pop().is_none() {
+                    self.mode = VimMode::Normal;
+                }
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(Modifiers::CTRL) => {
+                self.command_line.push(c);
+            }
+            _ => {}
+        }
+        VimOutcome::Consumed
+    }
+
+    fn run_command_line(&mut self, line: &str, editor: &mut Editor) {
+        let text = editor.text();
+        let goto = if line == "$" {
+            Some(line_count(&text))
+        } else {
+            line.parse::<usize>().ok()
+        };
+        if let Some(n) = goto {
+            let target = line_start_of(&text, n.saturating_sub(1));
+            set_cursor_byte(editor, first_non_blank(&text, target));
+            return;
+        }
+        self.command = match line {
+            "" => None,
+            "w" => Some(VimCommand::Write),
+            "q" => Some(VimCommand::Quit { force: false }),
+            "q!" => Some(VimCommand::Quit { force: true }),
+            "wq" | "x" => Some(VimCommand::WriteQuit),
+            other => Some(VimCommand::Other(other.to_string())),
+        };
+    }
+
+    // ── Normal and Visual modes ────────────────────────────────────
+
+    fn handle_normal(&mut self, key: &KeyEvent, editor: &mut Editor) -> VimOutcome {
+        if self.sequence.pending_keys().is_empty() {
+            self.checkpoint = editor.undo_checkpoint();
+        }
+        if key.modifiers.contains(Modifiers::ALT) {
+            return VimOutcome::Passthrough;
+        }
+        if key.modifiers.contains(Modifiers::CTRL) {
+            if key.code != KeyCode::Char('r') {
+                return VimOutcome::Passthrough;
+            }
+            if self.mode == VimMode::Normal {
+                let count = plain_count(&self.pending_chars()).unwrap_or(1);
+                for _ in 0..count {
+                    editor.redo();
+                }
+                self.finish_normal(editor, false);
+            }
+            self.sequence.reset();
+            return VimOutcome::Consumed;
+        }
+        if key.code == KeyCode::Escape {
+            self.sequence.reset();
+            if self.mode != VimMode::Normal {
+                self.exit_visual(editor);
+            }
+            return VimOutcome::Consumed;
+        }
+        if command_char(key).is_none() {
+            return VimOutcome::Consumed;
+        }
+
+        // The interpreter buffers keys until the grammar accepts or rejects
+        // them; either way the run is complete and the buffer is emptied.
+        let visual = self.mode != VimMode::Normal;
+        let mut parsed = Parse::Incomplete;
+        self.sequence.feed_with(key, Instant::now(), |keys| {
+            parsed = parse_command(&command_chars(keys), visual);
+            match parsed {
+                Parse::Incomplete => SequenceMatch::Prefix,
+                Parse::Invalid | Parse::Done(_) => SequenceMatch::Complete,
+            }
+        });
+        match parsed {
+            Parse::Incomplete | Parse::Invalid => {}
+            Parse::Done(cmd) => {
+                if self.mode == VimMode::Normal {
+                    let changed = self.execute_normal(cmd, editor);
+                    self.finish_normal(editor, changed);
+                } else {
+                    self.execute_visual(cmd, editor);
+                    if self.mode == VimMode::Normal {
+                        self.finish_normal(editor, false);
+                    }
+                }
+            }
+        }
+        VimOutcome::Consumed
+    }
+
+    /// Wrap up a command that left the keymap in Normal (or Insert) mode.
+    fn finish_normal(&mut self, editor: &mut Editor, changed: bool) {
+        if self.mode == VimMode::Insert {
+            self.insert_repeatable = changed;
+            return;
+        }
+        editor.group_undo_since(self.checkpoint);
+        if changed && !self.replaying {
+            self.last_change = self.recording.clone();
+        }
+        if self.mode == VimMode::Normal {
+            let text = editor.text();
+            set_cursor_byte(editor, clamp_normal(&text, cursor_byte(editor)));
+        }
+    }
+
+    /// Run a Normal-mode command. Returns whether it is a repeatable change.
+    fn execute_normal(&mut self, cmd: Command, editor: &mut Editor) -> bool {
+        let n = cmd.count.unwrap_or(1);
+        let text = editor.text();
+        let pos = cursor_byte(editor);
+        match cmd.action {
+            Action::Move(motion) => {
+                if let Some((target, _, _)) =
+                    self.motion_target(&text, pos, motion, cmd.count, false)
+                {
+                    set_cursor_byte(editor, target);
+                }
+                false
+            }
+            Action::Operate { op, target } => {
+                self.operate(editor, &text, pos, op, target, cmd.count, cmd.register)
+            }
+            Action::Object { .. } => false,
+            Action::Replace(ch) => {
+                let end_of_line = line_end(&text, pos);
+                let graphemes: Vec<(usize, &str)> = text[pos..end_of_line]
+                    .grapheme_indices(true)
+                    .take(n)
+                    .collect();
+                if graphemes.len() < n {
+                    return false;
+                }
+                let end = pos + graphemes[n - 1].0 + graphemes[n - 1].1.len();
+                let with: String = std::iter::repeat_n(ch, n).collect();
+                replace_range(editor, pos..end, &with);
+                set_cursor_byte(editor, pos + with.len() - ch.len_utf8());
+                true
+            }
+            Action::Key(key) => self.normal_key(editor, &text, pos, key, cmd),
+        }
+    }
+
+    fn normal_key(
+        &mut self,
+        editor: &mut Editor,
+        text: &str,
+        pos: usize,
+        key: char,
+        cmd: Command,
+    ) -> bool {
+        let n = cmd.count.unwrap_or(1);
+        let shorthand = match key {
+            'x' => Some((Operator::Delete, Target::Motion(Motion::Right))),
+            'X' => Some((Operator::Delete, Target::Motion(Motion::Left))),
+            'D' => Some((Operator::Delete, Target::Motion(Motion::LineEnd))),
+            'C' => Some((Operator::Change, Target::Motion(Motion::LineEnd))),
+            's' => Some((Operator::Change, Target::Motion(Motion::Right))),
+            'S' => Some((Operator::Change, Target::Line)),
+            'Y' => Some((Operator::Yank, Target::Line)),
+            _ => None,
+        };
+        if let Some((op, target)) = shorthand {
+            return self.operate(editor, text, pos, op, target, cmd.count, cmd.register);
+        }
+        match key {
+            'i' => self.enter_insert(editor, pos),
+            'a' => {
+                let at = if pos < line_end(text, pos) {
+                    next_boundary(text, pos)
+                } else {
+                    pos
+                };
+                self.enter_insert(editor, at);
+            }
+            'I' => self.enter_insert(editor, first_non_blank(text, pos)),
+            'A' => self.enter_insert(editor, line_end(text, pos)),
+            'o' | 'O' => {
+                if self.single_line {
+                    return false;
+                }
+                let at = if key == 'o' {
+                    line_end(text, pos)
+                } else {
+                    line_start(text, pos)
+                };
+                replace_range(editor, at..at, "\n");
+                let cursor = if key == 'o' { at + 1 } else { at };
+                self.enter_insert(editor, cursor);
+            }
+            'p' | 'P' => return self.put(editor, text, pos, cmd.register, n, key == 'P'),
+            'J' => return self.join_lines(editor, text, pos, n),
+            '~' => {
+                let end_of_line = line_end(text, pos);
+                let end = text[pos..end_of_line]
+                    .grapheme_indices(true)
+                    .take(n)
+                    .last()
+                    .map_or(pos, |(i, g)| pos + i + g.len());
+                if end == pos {
+                    return false;
+                }
+                let toggled = toggle_case(&text[pos..end]);
+                replace_range(editor, pos..end, &toggled);
+                set_cursor_byte(editor, pos + toggled.len());
+                return true;
+            }
+            'u' => {
+                for _ in 0..n {
+                    editor.undo();
+                }
+                return false;
+            }
+            '.' => {
+                let keys = self.last_change.clone();
+                if keys.is_empty() {
+                    return false;
+                }
+                let checkpoint = self.checkpoint;
+                self.replaying = true;
+                for _ in 0..n {
+                    for key in &keys {
+                        self.handle_key(key, editor);
+                    }
+                }
+                self.replaying = false;
+                self.checkpoint = checkpoint;
+                return false;
+            }
+            'v' => self.enter_visual(editor, pos, VimMode::Visual),
+            'V' => self.enter_visual(editor, pos, VimMode::VisualLine),
+            ':' => {
+                self.command_line.clear();
+                self.mode = VimMode::Command;
+            }
+            _ => return false,
+        }
+        self.mode == VimMode::Insert
+    }
+
+    fn enter_insert(&mut self, editor: &mut Editor, at: usize) {
+        set_cursor_byte(editor, at);
+        self.mode = VimMode::Insert;
+    }
+
+    fn enter_visual(&mut self, editor: &mut Editor, pos: usize, mode: VimMode) {
+        self.mode = mode;
+        self.visual_anchor = pos;
+        self.visual_head = pos;
+        self.show_visual(editor);
+    }
+
+    fn exit_visual(&mut self, editor: &mut Editor) {
+        self.mode = VimMode::Normal;
+        let text = editor.text();
+        set_cursor_byte(editor, clamp_normal(&text, self.visual_head));
+    }
+
+    /// Mirror the Visual selection into the editor, cursor at the head end.
+    fn show_visual(&self, editor: &mut Editor) {
+        let text = editor.text();
+        let range = self.visual_range(&text);
+        let (from, to) = if self.visual_head >= self.visual_anchor {
+            (range.start, range.end)
+        } else {
+            (range.end, range.start)
+        };
+        set_cursor_byte(editor, from);
+        let nav = CursorNavigator::new(editor.rope());
+        let to = nav.from_byte_index(to);
+        editor.extend_selection_to(to);
+    }
+
+    fn visual_range(&self, text: &str) -> Range<usize> {
+        let lo = self.visual_anchor.min(self.visual_head).min(text.len());
+        let hi = self.visual_anchor.max(self.visual_head).min(text.len());
+        if self.mode == VimMode::VisualLine {
+            line_start(text, lo)..line_end(text, hi)
+        } else {
+            lo..inclusive_end(text, hi)
+        }
+    }
+
+    fn execute_visual(&mut self, cmd: Command, editor: &mut Editor) {
+        let text = editor.text();
+        let linewise = self.mode == VimMode::VisualLine;
+        match cmd.action {
+            Action::Move(motion) => {
+                if let Some((target, _, _)) =
+                    self.motion_target(&text, self.visual_head, motion, cmd.count, false)
+                {
+                    self.visual_head = clamp_normal(&text, target);
+                }
+            }
+            Action::Object { around, kind } => {
+                if let Some((start, end, object_linewise)) =
+                    text_object(&text, self.visual_head, around, kind)
+                {
+                    self.visual_anchor = start;
+                    self.visual_head = if object_linewise {
+                        self.mode = VimMode::VisualLine;
+                        end
+                    } else {
+                        prev_boundary(&text, end).max(start)
+                    };
+                }
+            }
+            Action::Key(key) => {
+                let range = self.visual_range(&text);
+                let (start, end) = (range.start, range.end);
+                let op = match key {
+                    'd' | 'x' | 'X' | 'D' => Some(Operator::Delete),
+                    'c' | 's' | 'C' | 'S' => Some(Operator::Change),
+                    'y' | 'Y' => Some(Operator::Yank),
+                    _ => None,
+                };
+                if let Some(op) = op {
+                    let linewise = linewise || key.is_ascii_uppercase();
+                    self.mode = VimMode::Normal;
+                    self.apply_operator(
+                        editor,
+                        &text,
+                        start,
+                        op,
+                        start,
+                        end,
+                        linewise,
+                        cmd.register,
+                    );
+                    return;
+                }
+                match key {
+                    'o' => std::mem::swap(&mut self.visual_anchor, &mut self.visual_head),
+                    'v' | 'V' => {
+                        let mode = if key == 'v' {
+                            VimMode::Visual
+                        } else {
+                            VimMode::VisualLine
+                        };
+                        if self.mode == mode {
+                            self.exit_visual(editor);
+                            return;
+                        }
+                        self.mode = mode;
+                    }
+                    '~' | 'u' | 'U' => {
+                        let selected = &text[start..end];
+                        let changed = match key {
+                            '~' => toggle_case(selected),
+                            'u' => selected.to_lowercase(),
+                            _ => selected.to_uppercase(),
+                        };
+                        replace_range(editor, start..end, &changed);
+                        self.mode = VimMode::Normal;
+                        set_cursor_byte(editor, start);
+                        return;
+                    }
+                    'p' | 'P' => {
+                        let Some(reg) = self.registers.get(&cmd.register.unwrap_or('"')).cloned()
+                        else {
+                            return;
+                        };
+                        let body = if linewise || !reg.linewise {
+                            reg.text.trim_end_matches('\n').to_string()
+                        } else {
+                            reg.text
+                        };
+                        replace_range(editor, start..end, &body);
+                        self.mode = VimMode::Normal;
+                        set_cursor_byte(editor, start);
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+            Action::Operate { .. } | Action::Replace(_) => {}
+        }
+        self.show_visual(editor);
+    }
+
+    // ── Operators ──────────────────────────────────────────────────
+
+    #[allow(clippy::too_many_arguments)]
+    fn operate(
+        &mut self,
+        editor: &mut Editor,
+        text: &str,
+        pos: usize,
+        op: Operator,
+        target: Target,
+        count: Option<usize>,
+        register: Option<char>,
+    ) -> bool {
+        let (start, end, linewise) = match target {
+            Target::Line => {
+                let n = count.unwrap_or(1);
+                let last_line = (line_of(text, pos) + n - 1).min(line_count(text) - 1);
+                (pos, line_start_of(text, last_line), true)
+            }
+            Target::Object { around, kind } => match text_object(text, pos, around, kind) {
+                Some(range) => range,
+                None => return false,
+            },
+            // `cw` on a word stops at the end of the word, like `ce`.
+            Target::Motion(Motion::WordStart { big })
+                if op == Operator::Change && !is_blank_at(text, pos) =>
+            {
+                let mut end = word_run_end(text, pos, big);
+                for _ in 1..count.unwrap_or(1) {
+                    end = word_end(text, end, big);
+                }
+                (pos, inclusive_end(text, end), false)
+            }
+            Target::Motion(motion) => {
+                let Some((target, linewise, inclusive)) =
+                    self.motion_target(text, pos, motion, count, true)
+                else {
+                    return false;
+                };
+                let (lo, hi) = (pos.min(target), pos.max(target));
+                let hi = if inclusive {
+                    inclusive_end(text, hi)
+                } else {
+                    hi
+                };
+                (lo, hi, linewise)
+            }
+        };
+        self.apply_operator(editor, text, pos, op, start, end, linewise, register);
+        op != Operator::Yank
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn apply_operator(
+        &mut self,
+        editor: &mut Editor,
+        text: &str,
+        pos: usize,
+        op: Operator,
+        start: usize,
+        end: usize,
+        linewise: bool,
+        register: Option<char>,
+    ) {
+        if linewise {
+            let s = line_start(text, start);
+            let e = line_end(text, end);
+            self.store(register, op, format!("{}\n", &text[s..e]), true);
+            match op {
+                Operator::Yank => set_cursor_byte(editor, start.min(pos)),
+                Operator::Delete => {
+                    let range = if e < text.len() {
+                        s..e + 1
+                    } else {
+                        s.saturating_sub(1)..e
+                    };
+                    replace_range(editor, range.clone(), "");
+                    let text = editor.text();
+                    let at = line_start(&text, range.start.min(text.len()));
+                    set_cursor_byte(editor, first_non_blank(&text, at));
+                }
+                Operator::Change => {
+                    replace_range(editor, s..e, "");
+                    self.enter_insert(editor, s);
+                }
+            }
+            return;
+        }
+        if start == end && op != Operator::Change {
+            return;
+        }
+        self.store(register, op, text[start..end].to_string(), false);
+        match op {
+            Operator::Yank => set_cursor_byte(editor, start),
+            Operator::Delete => {
+                replace_range(editor, start..end, "");
+                set_cursor_byte(editor, start);
+            }
+            Operator::Change => {
+                replace_range(editor, start..end, "");
+                self.enter_insert(editor, start);
+            }
+        }
+    }
+
+    fn store(&mut self, register: Option<char>, op: Operator, text: String, linewise: bool) {
+        let mut stored = VimRegister { text, linewise };
+        match register {
+            Some('_') => return,
+            Some(name) if name.is_ascii_uppercase() => {
+                let entry = self.registers.entry(name.to_ascii_lowercase()).or_default();
+                entry.text.push_str(&stored.text);
+                entry.linewise |= stored.linewise;
+                stored = entry.clone();
+            }
+            Some(name) if name.is_ascii_lowercase() => {
+                self.registers.insert(name, stored.clone());
+            }
+            _ => {}
+        }
+        if op == Operator::Yank && register.is_none() {
+            self.registers.insert('0', stored.clone());
+        }
+        self.registers.insert('"', stored);
+    }
+
+    fn put(
+        &mut self,
+        editor: &mut Editor,
+        text: &str,
+        pos: usize,
+        register: Option<char>,
+        count: usize,
+        before: bool,
+    ) -> bool {
+        let name = register.map_or('"', |c| c.to_ascii_lowercase());
+        let Some(reg) = self.registers.get(&name).cloned() else {
+            return false;
+        };
+        if reg.text.is_empty() {
+            return false;
+        }
+        if reg.linewise && !self.single_line {
+            let body = reg.text.repeat(count);
+            let line_at = if before {
+                let at = line_start(text, pos);
+                replace_range(editor, at..at, &body);
+                at
+            } else {
+                let e = line_end(text, pos);
+                if e < text.len() {
+                    replace_range(editor, e + 1..e + 1, &body);
+                } else {
+                    let body = format!("\n{}", body.trim_end_matches('\n'));
+                    replace_range(editor, e..e, &body);
+                }
+                e + 1
+            };
+            let text = editor.text();
+            set_cursor_byte(editor, first_non_blank(&text, line_at));
+        } else {
+            let body = reg.text.trim_end_matches('\n').repeat(count);
+            let at = if before || pos >= line_end(text, pos) {
+                pos
+            } else {
+                next_boundary(text, pos)
+            };
+            replace_range(editor, at..at, &body);
+            let text = editor.text();
+            set_cursor_byte(editor, prev_boundary(&text, at + body.len()).max(at));
+        }
+        true
+    }
+
+    fn join_lines(&mut self, editor: &mut Editor, text: &str, pos: usize, count: usize) -> bool {
+        let mut text = text.to_string();
+        let mut pos = pos;
+        let mut joined = false;
+        for _ in 0..count.max(2) - 1 {
+            let e = line_end(&text, pos);
+            if e >= text.len() {
+                break;
+            }
+            let rest = &text[e + 1..];
+            let indent = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+            let next_blank = rest[indent..].is_empty() || rest[indent..].starts_with('\n');
+            let sep = if next_blank || e == line_start(&text, e) {
+                ""
+            } else {
+                " "
+            };
+            replace_range(editor, e..e + 1 + indent, sep);
+            text = editor.text();
+            pos = e;
+            joined = true;
+        }
+        set_cursor_byte(editor, pos);
+        joined
+    }
+
+    // ── Motions ────────────────────────────────────────────────────
+
+    /// Resolve a motion from `pos` into `(target, linewise, inclusive)`.
+    fn motion_target(
+        &mut self,
+        text: &str,
+        pos: usize,
+        motion: Motion,
+        count: Option<usize>,
+        for_operator: bool,
+    ) -> Option<(usize, bool, bool)> {
+        let n = count.unwrap_or(1).max(1);
+        let ls = line_start(text, pos);
+        let le = line_end(text, pos);
+        let result = match motion {
+            Motion::Left => {
+                let mut t = pos;
+                for _ in 0..n {
+                    if t <= ls {
+                        break;
+                    }
+                    t = prev_boundary(text, t);
+                }
+                (t, false, false)
+            }
+            Motion::Right => {
+                let limit = if for_operator {
+                    le
+                } else {
+                    last_char(text, pos)
+                };
+                let mut t = pos;
+                for _ in 0..n {
+                    if t >= limit {
+                        break;
+                    }
+                    t = next_boundary(text, t);
+                }
+                (t, false, false)
+            }
+            Motion::Up | Motion::Down => {
+                if self.single_line {
+                    return None;
+                }
+                let line = line_of(text, pos);
+                let target_line = if motion == Motion::Down {
+                    (line + n).min(line_count(text) - 1)
+                } else {
+                    line.saturating_sub(n)
+                };
+                if target_line == line {
+                    return None;
+                }
+                let col = text[ls..pos].graphemes(true).count();
+                let s = line_start_of(text, target_line);
+                let e = line_end(text, s);
+                let t = text[s..e]
+                    .grapheme_indices(true)
+                    .nth(col)
+                    .map_or(e, |(i, _)| s + i);
+                (t, true, false)
+            }
+            Motion::WordStart { big } => {
+                let mut t = pos;
+                for _ in 0..n {
+                    t = word_forward(text, t, big);
+                }
+                // An exclusive motion ending at the start of a later line
+                // stops at the end of the previous line instead.
+                if for_operator && t > le && t == line_start(text, t) {
+                    t = (t - 1).max(pos);
+                }
+                (t, false, false)
+            }
+            Motion::WordBack { big } => {
+                let mut t = pos;
+                for _ in 0..n {
+                    t = word_back(text, t, big);
+                }
+                (t, false, false)
+            }
+            Motion::WordEnd { big } => {
+                let mut t = pos;
+                for _ in 0..n {
+                    t = word_end(text, t, big);
+                }
+                (t, false, true)
+            }
+            Motion::LineStart => (ls, false, false),
+            Motion::FirstNonBlank => (first_non_blank(text, pos), false, false),
+            Motion::LineEnd => {
+                let line = (line_of(text, pos) + n - 1).min(line_count(text) - 1);
+                let s = line_start_of(text, line);
+                (last_char(text, s), false, true)
+            }
+            Motion::Top | Motion::Bottom => {
+                let last = line_count(text) - 1;
+                let line = match (motion, count) {
+                    (_, Some(c)) => c.saturating_sub(1).min(last),
+                    (Motion::Top, None) => 0,
+                    _ => last,
+                };
+                (
+                    first_non_blank(text, line_start_of(text, line)),
+                    true,
+                    false,
+                )
+            }
+            Motion::Find { kind, ch } => {
+                self.last_find = Some((kind, ch));
+                return find_in_line(text, pos, kind, ch, n);
+            }
+            Motion::RepeatFind { reverse } => {
+                let (kind, ch) = self.last_find?;
+                let kind = if reverse {
+                    match kind {
+                        'f' => 'F',
+                        'F' => 'f',
+                        't' => 'T',
+                        _ => 't',
+                    }
+                } else {
+                    kind
+                };
+                return find_in_line(text, pos, kind, ch, n);
+            }
+        };
+        Some(result)
+    }
+}
+
+// ── Command parsing ────────────────────────────────────────────────
+
+/// The command character a Normal or Visual mode key stands for.
+fn command_char(key: &KeyEvent) -> Option<char> {
+    match key.code {
+        KeyCode::Char(c) => Some(c),
+        KeyCode::Left | KeyCode::Backspace => Some('h'),
+        KeyCode::Right => Some('l'),
+        KeyCode::Up => Some('k'),
+        KeyCode::Down => Some('j'),
+        KeyCode::Home => Some('0'),
+        KeyCode::End => Some('$'),
+        _ => None,
+    }
+}
+
+fn command_chars(keys: &[KeyEvent]) -> Vec<char> {
+    keys.iter().filter_map(command_char).collect()
+}
+
+fn parse_count(keys: &[char], i: &mut usize) -> Option<usize> {
+    let mut count: Option<usize> = None;
+    while let Some(&c) = keys.get(*i) {
+        let Some(d) = c.to_digit(10) else { break };
+        if d == 0 && count.is_none() {
+            break;
+        }
+        count = Some(
+            count
+                .unwrap_or(0)
+                .saturating_mul(10)
+                .saturating_add(d as usize),
+        );
+        *i += 1;
+    }
+    count
+}
+
+/// The count when `keys` holds nothing but a count.
+fn plain_count(keys: &[char]) -> Option<usize> {
+    let mut i = 0;
+    let count = parse_count(keys, &mut i);
+    (i == keys.len()).then_some(count).flatten()
+}
+
+fn parse_motion(keys: &[char]) -> Parse<Motion> {
+    let motion = match keys {
+        [] | ['g'] | ['f' | 'F' | 't' | 'T'] => return Parse::Incomplete,
+        ['g', 'g'] => Motion::Top,
+        [kind @ ('f' | 'F' | 't' | 'T'), ch] => Motion::Find {
+            kind: *kind,
+            ch: *ch,
+        },
+        [c] => match c {
+            'h' => Motion::Left,
+            'l' | ' ' => Motion::Right,
+            'k' => Motion::Up,
+            'j' => Motion::Down,
+            'w' | 'W' => Motion::WordStart { big: *c == 'W' },
+            'b' | 'B' => Motion::WordBack { big: *c == 'B' },
+            'e' | 'E' => Motion::WordEnd { big: *c == 'E' },
+            '0' => Motion::LineStart,
+            '^' => Motion::FirstNonBlank,
+            '$' => Motion::LineEnd,
+            'G' => Motion::Bottom,
+            ';' => Motion::RepeatFind { reverse: false },
+            ',' => Motion::RepeatFind { reverse: true },
+            _ => return Parse::Invalid,
+        },
+        _ => return Parse::Invalid,
+    };
+    Parse::Done(motion)
+}
+
+fn is_object_kind(c: char) -> bool {
+    matches!(
+        c,
+        'w' | 'W'
+            | '"'
+            | '\''
+            | '`'
+            | '('
+            | ')'
+            | 'b'
+            | '['
+            | ']'
+            | '{'
+            | '}'
+            | 'B'
+            | '<'
+            | '>'
+            | 'p'
+    )
+}
+
+fn parse_object(keys: &[char]) -> Parse<(bool, char)> {
+    match keys {
+        [_] => Parse::Incomplete,
+        [prefix, kind] if is_object_kind(*kind) => Parse::Done((*prefix == 'a', *kind)),
+        _ => Parse::Invalid,
+    }
+}
+
+/// Parse `["x][count]command`, where the command may be an operator with its
+/// own count and target.
+fn parse_command(keys: &[char], visual: bool) -> Parse<Command> {
+    let mut i = 0;
+    let mut register = None;
+    if keys.first() == Some(&'"') {
+        match keys.get(1) {
+            None => return Parse::Incomplete,
+            Some(&name) => register = Some(name),
+        }
+        i = 2;
+    }
+    let count = parse_count(keys, &mut i);
+    let Some(&c) = keys.get(i) else {
+        return Parse::Incomplete;
+    };
+    let rest = &keys[i + 1..];
+    let action = match c {
+        'd' | 'c' | 'y' if !visual => {
+            let op = match c {
+                'd' => Operator::Delete,
+                'c' => Operator::Change,
+                _ => Operator::Yank,
+            };
+            let mut j = 0;
+            let count2 = parse_count(rest, &mut j);
+            let target_keys = &rest[j..];
+            let target = match target_keys {
+                [] => return Parse::Incomplete,
+                [t] if *t == c => Target::Line,
+                ['i' | 'a', ..] => match parse_object(target_keys) {
+                    Parse::Done((around, kind)) => Target::Object { around, kind },
+                    Parse::Incomplete => return Parse::Incomplete,
+                    Parse::Invalid => return Parse::Invalid,
+                },
+                _ => match parse_motion(target_keys) {
+                    Parse::Done(motion) => Target::Motion(motion),
+                    Parse::Incomplete => return Parse::Incomplete,
+                    Parse::Invalid => return Parse::Invalid,
+                },
+            };
+            let count = match (count, count2) {
+                (None, None) => None,
+                (a, b) => Some(a.unwrap_or(1).saturating_mul(b.unwrap_or(1))),
+            };
+            return Parse::Done(Command {
+                register,
+                count,
+                action: Action::Operate { op, target },
+            });
+        }
+        'i' | 'a' if visual => match parse_object(&keys[i..]) {
+            Parse::Done((around, kind)) => Action::Object { around, kind },
+            Parse::Incomplete => return Parse::Incomplete,
+            Parse::Invalid => return Parse::Invalid,
+        },
+        'r' if !visual => match rest {
+            [] => return Parse::Incomplete,
+            [ch] => Action::Replace(*ch),
+            _ => return Parse::Invalid,
+        },
+        _ => match parse_motion(&keys[i..]) {
+            Parse::Done(motion) => Action::Move(motion),
+            Parse::Incomplete => return Parse::Incomplete,
+            Parse::Invalid if rest.is_empty() => Action::Key(c),
+            Parse::Invalid => return Parse::Invalid,
+        },
+    };
+    Parse::Done(Command {
+        register,
+        count,
+        action,
+    })
+}
+
+// ── Text helpers (byte offsets) ────────────────────────────────────
+
+fn cursor_byte(editor: &Editor) -> usize {
+    CursorNavigator::new(editor.rope()).to_byte_index(editor.cursor())
+}
+
+fn set_cursor_byte(editor: &mut Editor, byte: usize) {
+    let pos = CursorNavigator::new(editor.rope()).from_byte_index(byte);
+    editor.set_cursor(pos);
+}
+
+/// Replace `range` with `with` through the editor's undoable primitives.
+fn replace_range(editor: &mut Editor, range: Range<usize>, with: &str) {
+    set_cursor_byte(editor, range.start);
+    if !range.is_empty() {
+        let end = CursorNavigator::new(editor.rope()).from_byte_index(range.end);
+        editor.extend_selection_to(end);
+        if with.is_empty() {
+            editor.delete_backward();
+            return;
+        }
+    }
+    editor.insert_text(with);
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map_or(text.len(), |i| pos + i)
+}
+
+fn line_of(text: &str, pos: usize) -> usize {
+    text[..pos].matches('\n').count()
+}
+
+fn line_count(text: &str) -> usize {
+    text.matches('\n').count() + 1
+}
+
+fn line_start_of(text: &str, line: usize) -> usize {
+    if line == 0 {
+        return 0;
+    }
+    text.match_indices('\n')
+        .nth(line - 1)
+        .map_or_else(|| line_start(text, text.len()), |(i, _)| i + 1)
+}
+
+fn next_boundary(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .graphemes(true)
+        .next()
+        .map_or(pos, |g| pos + g.len())
+}
+
+fn prev_boundary(text: &str, pos: usize) -> usize {
+    text[..pos]
+        .graphemes(true)
+        .next_back()
+        .map_or(pos, |g| pos - g.len())
+}
+
+fn first_non_blank(text: &str, pos: usize) -> usize {
+    let s = line_start(text, pos);
+    let e = line_end(text, s);
+    let line = &text[s..e];
+    s + (line.len() - line.trim_start_matches([' ', '\t']).len())
+}
+
+/// Start of the last grapheme on the line, or the line start when empty.
+fn last_char(text: &str, pos: usize) -> usize {
+    let s = line_start(text, pos);
+    prev_boundary(text, line_end(text, pos)).max(s)
+}
+
+/// Keep a Normal-mode cursor on a character rather than past the line end.
+fn clamp_normal(text: &str, pos: usize) -> usize {
+    let pos = pos.min(text.len());
+    if pos == line_end(text, pos) {
+        last_char(text, pos)
+    } else {
+        pos
+    }
+}
+
+/// End of an inclusive range whose last character starts at `pos`.
+fn inclusive_end(text: &str, pos: usize) -> usize {
+    if pos < line_end(text, pos) {
+        next_boundary(text, pos)
+    } else {
+        pos
+    }
+}
+
+fn is_blank_at(text: &str, pos: usize) -> bool {
+    text[pos..].chars().next().is_none_or(char::is_whitespace)
+}
+
+fn toggle_case(s: &str) -> String {
+    s.chars()
+        .flat_map(|c| {
+            let flipped: Vec<char> = if c.is_uppercase() {
+                c.to_lowercase().collect()
+            } else {
+                c.to_uppercase().collect()
+            };
+            flipped
+        })
+        .collect()
+}
+
+/// 0 for whitespace, 1 for word characters, 2 for punctuation. Big words
+/// treat everything but whitespace as one class.
+fn char_class(g: &str, big: bool) -> u8 {
+    if g.chars().all(char::is_whitespace) {
+        0
+    } else if big || g.chars().any(|c| c.is_alphanumeric() || c == '_') {
+        1
+    } else {
+        2
+    }
+}
+
+fn word_forward(text: &str, pos: usize, big: bool) -> usize {
+    let mut iter = text[pos..]
+        .grapheme_indices(true)
+        .map(|(i, g)| (pos + i, g))
+        .peekable();
+    let Some(&(_, first)) = iter.peek() else {
+        return text.len();
+    };
+    let start = char_class(first, big);
+    if start != 0 {
+        while iter
+            .next_if(|&(_, g)| char_class(g, big) == start)
+            .is_some()
+        {}
+    }
+    let mut prev_newline = false;
+    while let Some(&(i, g)) = iter.peek() {
+        if char_class(g, big) != 0 {
+            return i;
+        }
+        // An empty line counts as a word.
+        if g == "\n" && prev_newline {
+            return i;
+        }
+        prev_newline = g == "\n";
+        iter.next();
+    }
+    text.len()
+}
+
+fn word_back(text: &str, pos: usize, big: bool) -> usize {
+    let mut iter = text[..pos].grapheme_indices(true).rev().peekable();
+    while iter.next_if(|&(_, g)| char_class(g, big) == 0).is_some() {}
+    let Some(&(mut start, first)) = iter.peek() else {
+        return 0;
+    };
+    let class = char_class(first, big);
+    while let Some((i, _)) = iter.next_if(|&(_, g)| char_class(g, big) == class) {
+        start = i;
+    }
+    start
+}
+
+/// Last grapheme of the run of same-class graphemes containing `pos`.
+fn word_run_end(text: &str, pos: usize, big: bool) -> usize {
+    let mut iter = text[pos..]
+        .grapheme_indices(true)
+        .map(|(i, g)| (pos + i, g))
+        .peekable();
+    let Some(&(mut end, first)) = iter.peek() else {
+        return pos;
+    };
+    let class = char_class(first, big);
+    while let Some((i, _)) = iter.next_if(|&(_, g)| char_class(g, big) == class) {
+        end = i;
+    }
+    end
+}
+
+fn word_end(text: &str, pos: usize, big: bool) -> usize {
+    let mut iter = text[pos..]
+        .grapheme_indices(true)
+        .map(|(i, g)| (pos + i, g))
+        .skip(1)
+        .peekable();
+    while iter.next_if(|&(_, g)| char_class(g, big) == 0).is_some() {}
+    let Some(&(mut end, first)) = iter.peek() else {
+        return pos;
+    };
+    let class = char_class(first, big);
+    while let Some((i, _)) = iter.next_if(|&(_, g)| char_class(g, big) == class) {
+        end = i;
+    }
+    end
+}
+
+/// `f`/`t` (forward, inclusive) and `F`/`T` (backward, exclusive) within the line.
+fn find_in_line(
+    text: &str,
+    pos: usize,
+    kind: char,
+    ch: char,
+    count: usize,
+) -> Option<(usize, bool, bool)> {
+    let ls = line_start(text, pos);
+    let le = line_end(text, pos);
+    match kind {
+        'f' | 't' => {
+            let from = next_boundary(text, pos).min(le);
+            let found = from
+                + text[from..le]
+                    .match_indices(ch)
+                    .nth(count - 1)
+                    .map(|(i, _)| i)?;
+            let target = if kind == 't' {
+                prev_boundary(text, found)
+            } else {
+                found
+            };
+            Some((target, false, true))
+        }
+        _ => {
+            let found = ls + text[ls..pos].rmatch_indices(ch).nth(count - 1)?.0;
+            let target = if kind == 'T' {
+                next_boundary(text, found)
+            } else {
+                found
+            };
+            Some((target, false, false))
+        }
+    }
+}
+
+/// Resolve a text object at `pos` into `(start, end, linewise)`.
+fn text_object(text: &str, pos: usize, around: bool, kind: char) -> Option<(usize, usize, bool)> {
+    match kind {
+        'w' | 'W' => word_object(text, pos, around, kind == 'W'),
+        '"' | '\'' | '`' => quote_object(text, pos, around, kind),
+        '(' | ')' | 'b' => bracket_object(text, pos, around, b'(', b')'),
+        '[' | ']' => bracket_object(text, pos, around, b'[', b']'),
+        '{' | '}' | 'B' => bracket_object(text, pos, around, b'{', b'}'),
+        '<' | '>' => bracket_object(text, pos, around, b'<', b'>'),
+        'p' => Some(paragraph_object(text, pos, around)),
+        _ => None,
+    }
+}
+
+fn word_object(text: &str, pos: usize, around: bool, big: bool) -> Option<(usize, usize, bool)> {
+    let ls = line_start(text, pos);
+    let le = line_end(text, pos);
+    if pos >= le {
+        return None;
+    }
+    let cells: Vec<(usize, u8)> = text[ls..le]
+        .grapheme_indices(true)
+        .map(|(i, g)| (ls + i, char_class(g, big)))
+        .collect();
+    let idx = cells.iter().position(|&(i, _)| i >= pos)?;
+    let class = cells[idx].1;
+    let run = |from: usize, class: u8, forward: bool| -> usize {
+        let mut k = from;
+        if forward {
+            while k + 1 < cells.len() && cells[k + 1].1 == class {
+                k += 1;
+            }
+        } else {
+            while k > 0 && cells[k - 1].1 == class {
+                k -= 1;
+            }
+        }
+        k
+    };
+    let mut first = run(idx, class, false);
+    let mut last = run(idx, class, true);
+    if around {
+        if last + 1 < cells.len() && (class != 0) == (cells[last + 1].1 == 0) {
+            last = run(last + 1, cells[last + 1].1, true);
+        } else if class != 0 && first > 0 && cells[first - 1].1 == 0 {
+            first = run(first - 1, 0, false);
+        }
+    }
+    let end = cells.get(last + 1).map_or(le, |&(i, _)| i);
+    Some((cells[first].0, end, false))
+}
+
+fn quote_object(text: &str, pos: usize, around: bool, quote: char) -> Option<(usize, usize, bool)> {
+    let ls = line_start(text, pos);
+    let le = line_end(text, pos);
+    let quotes: Vec<usize> = text[ls..le]
+        .match_indices(quote)
+        .map(|(i, _)| ls + i)
+        .collect();
+    let (open, close) = quotes
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .find(|&(_, close)| close >= pos)?;
+    let q = quote.len_utf8();
+    if around {
+        Some((open, close + q, false))
+    } else {
+        Some((open + q, close, false))
+    }
+}
+
+fn bracket_object(
+    text: &str,
+    pos: usize,
+    around: bool,
+    open: u8,
+    close: u8,
+) -> Option<(usize, usize, bool)> {
+    let bytes = text.as_bytes();
+    let open_at = if bytes.get(pos) == Some(&open) {
+        pos
+    } else {
+        let mut depth = 0usize;
+        let mut found = None;
+        for i in (0..pos).rev() {
+            if bytes[i] == close {
+                depth += 1;
+            } else if bytes[i] == open {
+                if depth == 0 {
+                    found = Some(i);
+                    break;
+                }
+                depth -= 1;
+            }
+        }
+        found?
+    };
+    let mut depth = 0usize;
+    let mut close_at = None;
+    for (i, &b) in bytes.iter().enumerate().skip(open_at + 1) {
+        if b == open {
+            depth += 1;
+        } else if b == close {
+            if depth == 0 {
+                close_at = Some(i);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let close_at = close_at?;
+    if around {
+        Some((open_at, close_at + 1, false))
+    } else {
+        Some((open_at + 1, close_at, false))
+    }
+}
+
+/// A run of lines sharing the cursor line's blankness; `ap` adds the
+/// following (or else preceding) run of the opposite kind.
+fn paragraph_object(text: &str, pos: usize, around: bool) -> (usize, usize, bool) {
+    let blank = |s: usize| text[s..line_end(text, s)].trim().is_empty();
+    let prev_line = |s: usize| (s > 0).then(|| line_start(text, s - 1));
+    let next_line = |s: usize| {
+        let e = line_end(text, s);
+        (e < text.len()).then_some(e + 1)
+    };
+    let want = blank(line_start(text, pos));
+    let mut first = line_start(text, pos);
+    while let Some(p) = prev_line(first).filter(|&p| blank(p) == want) {
+        first = p;
+    }
+    let mut last = line_start(text, pos);
+    while let Some(n) = next_line(last).filter(|&n| blank(n) == want) {
+        last = n;
+    }
+    if around {
+        let mut extended = false;
+        while let Some(n) = next_line(last).filter(|&n| blank(n) != want) {
+            last = n;
+            extended = true;
+        }
+        if !extended {
+            while let Some(p) = prev_line(first).filter(|&p| blank(p) != want) {
+                first = p;
+            }
+        }
+    }
+    (first, last, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(vim: &mut VimKeymap, ed: &mut Editor, input: &str) {
+        for c in input.chars() {
+            let code = match c {
+                '\u{1b}' => KeyCode::Escape,
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            vim.handle_key(&KeyEvent::new(code), ed);
+        }
+    }
+
+    fn editor(text: &str) -> Editor {
+        let mut ed = Editor::with_text(text);
+        ed.move_to_document_start();
+        ed
+    }
+
+    #[test]
+    fn counts_and_motions_move_the_cursor() {
+        let mut ed = editor("one two three four\nsecond line");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "2w");
+        assert_eq!(cursor_byte(&ed), 8);
+        keys(&mut vim, &mut ed, "$");
+        assert_eq!(cursor_byte(&ed), 17);
+        keys(&mut vim, &mut ed, "j0");
+        assert_eq!(ed.cursor().line, 1);
+        assert_eq!(ed.cursor().grapheme, 0);
+        keys(&mut vim, &mut ed, "ggfe");
+        assert_eq!(cursor_byte(&ed), 2);
+        keys(&mut vim, &mut ed, ";");
+        assert_eq!(cursor_byte(&ed), 11);
+        keys(&mut vim, &mut ed, "G");
+        assert_eq!(ed.cursor().line, 1);
+    }
+
+    #[test]
+    fn operators_combine_with_motions_and_counts() {
+        let mut ed = editor("one two three four");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "d2w");
+        assert_eq!(ed.text(), "three four");
+        keys(&mut vim, &mut ed, "dt ");
+        assert_eq!(ed.text(), " four");
+        keys(&mut vim, &mut ed, "x$D");
+        assert_eq!(ed.text(), "fou");
+
+        let mut ed = editor("alpha beta\ngamma");
+        keys(&mut vim, &mut ed, "cwomega\u{1b}");
+        assert_eq!(ed.text(), "omega beta\ngamma");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        assert_eq!(cursor_byte(&ed), 4);
+        keys(&mut vim, &mut ed, "wdw");
+        assert_eq!(ed.text(), "omega \ngamma");
+    }
+
+    #[test]
+    fn linewise_delete_yank_and_put() {
+        let mut ed = editor("a\nb\nc\nd");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "2dd");
+        assert_eq!(ed.text(), "c\nd");
+        assert_eq!(vim.register('"').unwrap().text, "a\nb\n");
+        keys(&mut vim, &mut ed, "p");
+        assert_eq!(ed.text(), "c\na\nb\nd");
+        assert_eq!(ed.cursor().line, 1);
+        keys(&mut vim, &mut ed, "Gyyggp");
+        assert_eq!(ed.text(), "c\nd\na\nb\nd");
+        keys(&mut vim, &mut ed, "Gdd");
+        assert_eq!(ed.text(), "c\nd\na\nb");
+        assert_eq!(ed.cursor().line, 3);
+    }
+
+    #[test]
+    fn text_objects() {
+        let mut ed = editor("say \"hello there\" (a (b) c) now");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "fhdi\"");
+        assert_eq!(ed.text(), "say \"\" (a (b) c) now");
+        keys(&mut vim, &mut ed, "fbci(x\u{1b}");
+        assert_eq!(ed.text(), "say \"\" (a (x) c) now");
+        keys(&mut vim, &mut ed, "0da(");
+        assert_eq!(ed.text(), "say \"\" (a (x) c) now");
+        keys(&mut vim, &mut ed, "2fada(");
+        assert_eq!(ed.text(), "say \"\"  now");
+        keys(&mut vim, &mut ed, "0diw");
+        assert_eq!(ed.text(), " \"\"  now");
+        keys(&mut vim, &mut ed, "$daw");
+        assert_eq!(ed.text(), " \"\"");
+
+        let mut ed = editor("p1 a\np1 b\n\np2\n");
+        keys(&mut vim, &mut ed, "jdap");
+        assert_eq!(ed.text(), "p2\n");
+        keys(&mut vim, &mut ed, "yip");
+        assert_eq!(vim.register('0').unwrap().text, "p2\n");
+    }
+
+    #[test]
+    fn registers_named_append_and_blackhole() {
+        let mut ed = editor("one two three");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "\"ayw");
+        keys(&mut vim, &mut ed, "w\"Ayw");
+        assert_eq!(vim.register('a').unwrap().text, "one two ");
+        keys(&mut vim, &mut ed, "yw\"_dw");
+        assert_eq!(ed.text(), "one three");
+        assert_eq!(vim.register('"').unwrap().text, "two ");
+        keys(&mut vim, &mut ed, "$\"ap");
+        assert_eq!(ed.text(), "one threeone two ");
+    }
+
+    #[test]
+    fn dot_repeats_last_change_and_undo_is_one_step() {
+        let mut ed = editor("a b c d");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "cwX\u{1b}w.w.");
+        assert_eq!(ed.text(), "X X X d");
+        keys(&mut vim, &mut ed, "u");
+        assert_eq!(ed.text(), "X X c d");
+        keys(&mut vim, &mut ed, "0x2.");
+        assert_eq!(ed.text(), " c d");
+
+        let mut ed = editor("x\ny\nz");
+        keys(&mut vim, &mut ed, "Aend\u{1b}j.");
+        assert_eq!(ed.text(), "xend\nyend\nz");
+        keys(&mut vim, &mut ed, "uu");
+        assert_eq!(ed.text(), "x\ny\nz");
+    }
+
+    #[test]
+    fn visual_modes_select_and_operate() {
+        let mut ed = editor("hello world\nsecond\nthird");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "vl");
+        assert_eq!(vim.mode(), VimMode::Visual);
+        assert_eq!(ed.selected_text().as_deref(), Some("he"));
+        keys(&mut vim, &mut ed, "ey");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        assert_eq!(vim.register('"').unwrap().text, "hello");
+
+        keys(&mut vim, &mut ed, "jVjd");
+        assert_eq!(ed.text(), "hello world");
+        assert!(ed.selection().is_none());
+
+        keys(&mut vim, &mut ed, "wviwU");
+        assert_eq!(ed.text(), "hello WORLD");
+        keys(&mut vim, &mut ed, "v\u{1b}");
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn command_line_goto_and_app_commands() {
+        let mut ed = editor("a\nb\nc");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, ":3");
+        assert_eq!(vim.mode(), VimMode::Command);
+        assert_eq!(vim.command_line(), Some("3"));
+        keys(&mut vim, &mut ed, "\n");
+        assert_eq!(ed.cursor().line, 2);
+        assert_eq!(vim.take_command(), None);
+        keys(&mut vim, &mut ed, ":wq\n");
+        assert_eq!(vim.take_command(), Some(VimCommand::WriteQuit));
+        keys(&mut vim, &mut ed, ":q!\n");
+        assert_eq!(vim.take_command(), Some(VimCommand::Quit { force: true }));
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn pending_keys_and_insert_passthrough() {
+        let mut ed = editor("abc");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "2d");
+        assert_eq!(vim.pending_keys(), "2d");
+        keys(&mut vim, &mut ed, "\u{1b}");
+        assert_eq!(vim.pending_keys(), "");
+        assert_eq!(ed.text(), "abc");
+        keys(&mut vim, &mut ed, "dx");
+        assert_eq!(vim.pending_keys(), "");
+        assert_eq!(ed.text(), "abc");
+
+        keys(&mut vim, &mut ed, "i");
+        assert_eq!(vim.mode(), VimMode::Insert);
+        let left = KeyEvent::new(KeyCode::Left);
+        assert_eq!(vim.handle_key(&left, &mut ed), VimOutcome::Passthrough);
+        let ctrl_a = KeyEvent::new(KeyCode::Char('a')).with_modifiers(Modifiers::CTRL);
+        keys(&mut vim, &mut ed, "\u{1b}");
+        assert_eq!(vim.handle_key(&ctrl_a, &mut ed), VimOutcome::Passthrough);
+    }
+
+    #[test]
+    fn key_sequence_interpreter_output_drives_keymap() {
+        use ftui_core::key_sequence::KeySequenceInterpreter;
+        use web_time::{Duration, Instant};
+
+        let mut ed = editor("one two\nthree");
+        let mut vim = VimKeymap::new();
+        let mut interp = KeySequenceInterpreter::with_defaults();
+        let t = Instant::now();
+        let mut feed = |vim: &mut VimKeymap, ed: &mut Editor, code: KeyCode| {
+            let action = interp.feed(&KeyEvent::new(code), t);
+            vim.handle_sequence_action(&action, ed)
+        };
+
+        feed(&mut vim, &mut ed, KeyCode::Char('d'));
+        assert_eq!(vim.pending_keys(), "d");
+        feed(&mut vim, &mut ed, KeyCode::Char('d'));
+        assert_eq!(ed.text(), "three");
+
+        feed(&mut vim, &mut ed, KeyCode::Char('i'));
+        feed(&mut vim, &mut ed, KeyCode::Char('x'));
+        let outcome = feed(&mut vim, &mut ed, KeyCode::Escape);
+        assert_eq!(outcome, VimSequenceOutcome::Consumed);
+        assert_eq!(vim.mode(), VimMode::Insert);
+        for action in interp.check_timeout(t + Duration::from_secs(1)).unwrap() {
+            vim.handle_sequence_action(&action, &mut ed);
+        }
+        assert_eq!(vim.mode(), VimMode::Normal);
+        assert_eq!(ed.text(), "xthree");
+
+        let mut feed = |code| {
+            let action = interp.feed(&KeyEvent::new(code), t);
+            vim.handle_sequence_action(&action, &mut ed)
+        };
+        feed(KeyCode::Escape);
+        assert_eq!(feed(KeyCode::Escape), VimSequenceOutcome::Consumed);
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn sequence_action_returns_passed_through_keys() {
+        use ftui_core::key_sequence::KeySequenceKind;
+
+        let mut ed = editor("");
+        let mut vim = VimKeymap::new();
+        keys(&mut vim, &mut ed, "i");
+        let ctrl_a = KeyEvent::new(KeyCode::Char('a')).with_modifiers(Modifiers::CTRL);
+        let action = KeySequenceAction::EmitSequence {
+            kind: KeySequenceKind::Custom,
+            keys: vec![KeyEvent::new(KeyCode::Char('x')), ctrl_a],
+        };
+        assert_eq!(
+            vim.handle_sequence_action(&action, &mut ed),
+            VimSequenceOutcome::Passthrough(vec![ctrl_a])
+        );
+        assert_eq!(ed.text(), "x");
+    }
+
+    #[test]
+    fn single_line_ignores_line_commands() {
+        let mut ed = editor("one two");
+        let mut vim = VimKeymap::new().with_single_line(true);
+        keys(&mut vim, &mut ed, "ojyyP");
+        assert_eq!(ed.text(), "one twoone two");
+        keys(&mut vim, &mut ed, "dd");
+        assert_eq!(ed.text(), "");
+    }
+}
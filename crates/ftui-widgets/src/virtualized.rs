@@ -11,6 +11,7 @@
 //! - [`VirtualizedStorage`] - Owned vs external storage abstraction
 //! - [`ItemHeight`] - Fixed vs variable height support
 //! - [`HeightCache`] - LRU cache for measured item heights
+//! - [`VirtualizedList`] - Widget over a slice of items or a [`LineSource`]
 //!
 //! # Example
 //!
@@ -41,6 +42,7 @@ use ftui_core::geometry::Rect;
 use ftui_render::cell::Cell;
 use ftui_render::frame::Frame;
 use ftui_style::Style;
+use ftui_text::LineSource;
 
 /// A virtualized content container that tracks scroll state and computes visible ranges.
 ///
@@ -1168,6 +1170,10 @@ impl crate::stateful::Stateful for VirtualizedListState {
 /// that are currently visible in the viewport, with optional overscan
 /// for smooth scrolling.
 ///
+/// Items come from a slice ([`new`](Self::new)) or from any [`LineSource`]
+/// ([`from_lines`](Self::from_lines)), which is asked only for the lines in
+/// view, so sources larger than memory render in O(visible).
+///
 /// # Limitations
 ///
 /// Currently, `VirtualizedList` only supports **fixed height** items.
@@ -1175,7 +1181,7 @@ impl crate::stateful::Stateful for VirtualizedListState {
 #[derive(Debug)]
 pub struct VirtualizedList<'a, T> {
     /// Items to render.
-    items: &'a [T],
+    items: ListItems<'a, T>,
     /// Base style.
     style: Style,
     /// Style for selected item.
@@ -1188,10 +1194,49 @@ pub struct VirtualizedList<'a, T> {
     hit_id: Option<ftui_render::frame::HitId>,
 }
 
+/// Where a [`VirtualizedList`] reads its items from.
+enum ListItems<'a, T> {
+    Slice(&'a [T]),
+    Lines(&'a dyn LineSource),
+}
+
+impl<T> ListItems<'_, T> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Slice(items) => items.len(),
+            Self::Lines(source) => source.line_count(),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for ListItems<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Slice(items) => f.debug_tuple("Slice").field(items).finish(),
+            Self::Lines(source) => f
+                .debug_struct("Lines")
+                .field("line_count", &source.line_count())
+                .finish(),
+        }
+    }
+}
+
+impl<'a> VirtualizedList<'a, &'a str> {
+    /// Create a list showing the lines of `source`.
+    #[must_use]
+    pub fn from_lines(source: &'a dyn LineSource) -> Self {
+        Self::with_items(ListItems::Lines(source))
+    }
+}
+
 impl<'a, T> VirtualizedList<'a, T> {
     /// Create a new virtualized list.
     #[must_use]
     pub fn new(items: &'a [T]) -> Self {
+        Self::with_items(ListItems::Slice(items))
+    }
+
+    fn with_items(items: ListItems<'a, T>) -> Self {
         Self {
             items,
             style: Style::default(),
@@ -1390,7 +1435,17 @@ impl<T: RenderItem> StatefulWidget for VirtualizedList<'_, T> {
             clear_text_area(frame, row_area, row_style);
 
             // Render the item
-            self.items[idx].render(row_area, frame, is_selected, skip_rows);
+            match &self.items {
+                ListItems::Slice(items) => {
+                    items[idx].render(row_area, frame, is_selected, skip_rows);
+                }
+                ListItems::Lines(source) => {
+                    if let Some(line) = source.line_text(idx) {
+                        line.as_ref()
+                            .render(row_area, frame, is_selected, skip_rows);
+                    }
+                }
+            }
         }
 
         // Render scrollbar
@@ -2134,6 +2189,26 @@ mod tests {
         assert_eq!(raw_row_text(&frame, 0), "Hi    ");
     }
 
+    #[test]
+    fn render_from_line_source_fetches_visible_lines() {
+        use ftui_render::grapheme_pool::GraphemePool;
+        use ftui_text::Rope;
+
+        let rope = Rope::from("zero\r\none\ntwo\nthree\nfour");
+        let area = Rect::new(0, 0, 6, 2);
+        let mut state = VirtualizedListState::new();
+        state.scroll_offset = 2;
+        let mut pool = GraphemePool::new();
+        let mut frame = Frame::new(6, 2, &mut pool);
+
+        VirtualizedList::from_lines(&rope)
+            .show_scrollbar(false)
+            .render(area, &mut frame, &mut state);
+
+        assert_eq!(raw_row_text(&frame, 0), "two   ");
+        assert_eq!(raw_row_text(&frame, 1), "three ");
+    }
+
     #[test]
     fn test_memory_bounded_by_ring_capacity() {
         use crate::log_ring::LogRing;